@@ -0,0 +1,212 @@
+//! Compares this crate with other zxcvbn implementations, such as zxcvbn-c and the
+//! `zxcvbn` Python package, scoring the same passwords.
+//!
+//! Other implementations run as subprocesses through the drivers in `benches/ports`, listed
+//! in `ZXCVBN_PORTS` as `name=command` entries separated by `;`:
+//!
+//! ```text
+//! ZXCVBN_PORTS="zxcvbn-python=python3 benches/ports/zxcvbn_python.py;zxcvbn-c=./zxcvbn_c" \
+//!     cargo bench --bench ports
+//! ```
+//!
+//! A driver reads passwords from stdin, one per line, and scores all of them the number of
+//! times given as its only argument. It prints the base 10 logarithm of the guesses of each
+//! password, then the nanoseconds spent scoring as `elapsed_ns <n>`, so that starting the
+//! process is not measured. Passwords are read from the file `ZXCVBN_BENCH_CORPUS` names,
+//! one per line, or taken from a small built-in corpus.
+//!
+//! Before benchmarking, how often each implementation gives the same score as this crate is
+//! printed, with the passwords they disagree on the most.
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use criterion::black_box;
+use criterion::{criterion_group, criterion_main};
+use criterion::{Criterion, Throughput};
+
+use zxcvbn::{zxcvbn, Score};
+
+const CORPUS: &[&str] = &[
+    "password",
+    "123456",
+    "qwerty123",
+    "iloveyou",
+    "P@ssw0rd!",
+    "correcthorsebatterystaple",
+    "Tr0ub4dor&3",
+    "r0sebudmaelstrom11/20/91aaaa",
+    "zxcvbnm,./",
+    "1qaz2wsx3edc",
+    "abcdefghijk",
+    "aaaaaaaaaaaa",
+    "drowssap",
+    "johnsmith1987",
+    "Summer2024!",
+    "01/01/1990",
+    "monkey12345",
+    "letmein",
+    "dragon",
+    "thequickbrownfox",
+    "Xk7#q*zP2!vR",
+    "hunter2",
+    "baseball!",
+    "trustno1",
+    "asdfghjkl;'",
+    "passwordpassword",
+    "coRrecTh0rseBatteRy",
+    "9f8e7d6c5b4a",
+    "sunshine2020",
+    "p4ssw0rd123",
+];
+
+/// Another implementation, run through its driver.
+struct Port {
+    name: String,
+    command: Vec<String>,
+}
+
+impl Port {
+    /// Scores the passwords `rounds` times, returning the base 10 logarithm of the guesses of
+    /// each password and the time spent scoring.
+    fn run(&self, passwords: &[String], rounds: u64) -> (Vec<f64>, Duration) {
+        let mut child = Command::new(&self.command[0])
+            .args(&self.command[1..])
+            .arg(rounds.to_string())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap_or_else(|err| panic!("failed to run {}: {}", self.name, err));
+        let mut stdin = child.stdin.take().unwrap();
+        for password in passwords {
+            writeln!(stdin, "{}", password).unwrap();
+        }
+        drop(stdin);
+        let output = child.wait_with_output().unwrap();
+        assert!(output.status.success(), "{} failed", self.name);
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let mut guesses_log10 = Vec::with_capacity(passwords.len());
+        let mut elapsed = None;
+        for line in stdout.lines() {
+            match line.strip_prefix("elapsed_ns ") {
+                Some(ns) => elapsed = Some(Duration::from_nanos(ns.trim().parse().unwrap())),
+                None => guesses_log10.push(line.trim().parse().unwrap()),
+            }
+        }
+        assert_eq!(guesses_log10.len(), passwords.len(), "{}", self.name);
+        let elapsed = elapsed.unwrap_or_else(|| panic!("{} printed no elapsed_ns", self.name));
+        (guesses_log10, elapsed)
+    }
+}
+
+fn ports() -> Vec<Port> {
+    let ports = env::var("ZXCVBN_PORTS").unwrap_or_default();
+    ports
+        .split(';')
+        .filter(|port| !port.trim().is_empty())
+        .map(|port| {
+            let (name, command) = port
+                .split_once('=')
+                .unwrap_or_else(|| panic!("expected name=command, got {}", port));
+            Port {
+                name: name.trim().to_string(),
+                command: command.split_whitespace().map(str::to_string).collect(),
+            }
+        })
+        .collect()
+}
+
+fn corpus() -> Vec<String> {
+    match env::var("ZXCVBN_BENCH_CORPUS") {
+        Ok(path) => fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect(),
+        Err(_) => CORPUS.iter().map(|password| password.to_string()).collect(),
+    }
+}
+
+fn score(guesses_log10: f64) -> Score {
+    Score::from_guesses(10f64.powf(guesses_log10).round() as u64)
+}
+
+/// Prints how well each port agrees with this crate, and how fast it is in comparison.
+fn report_agreement(passwords: &[String], ports: &[Port]) {
+    // the dictionaries are built on first use, which should not count
+    zxcvbn("warmup", &[]);
+    let start = Instant::now();
+    let ours = passwords
+        .iter()
+        .map(|password| zxcvbn(password, &[]).guesses_log10())
+        .collect::<Vec<_>>();
+    let elapsed = start.elapsed();
+
+    for port in ports {
+        let (theirs, their_elapsed) = port.run(passwords, 1);
+        let mut differences = passwords
+            .iter()
+            .zip(ours.iter().zip(&theirs))
+            .map(|(password, (&ours, &theirs))| (password, ours, theirs))
+            .collect::<Vec<_>>();
+        let agreeing = differences
+            .iter()
+            .filter(|(_, ours, theirs)| score(*ours) == score(*theirs))
+            .count();
+        let mean_difference = differences
+            .iter()
+            .map(|(_, ours, theirs)| (ours - theirs).abs())
+            .sum::<f64>()
+            / passwords.len() as f64;
+        println!(
+            "{}: same score for {}/{} passwords, guesses off by 10^{:.2} on average, {:.2}x the \
+             throughput of zxcvbn-rs",
+            port.name,
+            agreeing,
+            passwords.len(),
+            mean_difference,
+            elapsed.as_secs_f64() / their_elapsed.as_secs_f64(),
+        );
+        differences.sort_by(|a, b| (b.1 - b.2).abs().total_cmp(&(a.1 - a.2).abs()));
+        for (password, ours, theirs) in differences.iter().take(5) {
+            println!(
+                "    {:?}: 10^{:.2} guesses, {} gives 10^{:.2}",
+                password, ours, port.name, theirs
+            );
+        }
+    }
+}
+
+pub fn bench_ports(c: &mut Criterion) {
+    let passwords = corpus();
+    let ports = ports();
+    if ports.is_empty() {
+        println!("ZXCVBN_PORTS is not set, only benchmarking zxcvbn-rs");
+    }
+    report_agreement(&passwords, &ports);
+
+    let mut group = c.benchmark_group("ports");
+    group.throughput(Throughput::Elements(passwords.len() as u64));
+    group.bench_function("zxcvbn-rs", |b| {
+        b.iter(|| {
+            for password in &passwords {
+                black_box(zxcvbn(black_box(password), &[]));
+            }
+        })
+    });
+    for port in &ports {
+        group.sample_size(10);
+        group.bench_function(&port.name, |b| {
+            b.iter_custom(|rounds| port.run(&passwords, rounds).1)
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_ports);
+criterion_main!(benches);
@@ -0,0 +1,59 @@
+/*
+ * Driver for the `ports` benchmark, scoring passwords with zxcvbn-c.
+ *
+ * Reads passwords from stdin, one per line, and scores all of them the number of times
+ * given as the only argument. Prints the base 10 logarithm of the guesses of each password,
+ * then the nanoseconds spent scoring as `elapsed_ns <n>`.
+ *
+ * Build it from a checkout of https://github.com/tsyrogit/zxcvbn-c, e.g.
+ *
+ *     cc -O2 -I zxcvbn-c zxcvbn_c.c zxcvbn-c/zxcvbn.c -lm -o zxcvbn_c
+ */
+
+#define _POSIX_C_SOURCE 200809L
+
+#include <math.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <time.h>
+
+#include "zxcvbn.h"
+
+int main(int argc, char **argv) {
+    if (argc != 2) {
+        fprintf(stderr, "usage: %s <rounds>\n", argv[0]);
+        return 1;
+    }
+    long rounds = strtol(argv[1], NULL, 10);
+
+    size_t count = 0, capacity = 64;
+    char **passwords = malloc(capacity * sizeof(char *));
+    char line[1024];
+    while (fgets(line, sizeof line, stdin)) {
+        line[strcspn(line, "\r\n")] = '\0';
+        if (count == capacity) {
+            capacity *= 2;
+            passwords = realloc(passwords, capacity * sizeof(char *));
+        }
+        passwords[count++] = strdup(line);
+    }
+
+    double *bits = calloc(count, sizeof(double));
+    struct timespec start, end;
+    clock_gettime(CLOCK_MONOTONIC, &start);
+    for (long round = 0; round < rounds; round++) {
+        for (size_t i = 0; i < count; i++) {
+            bits[i] = ZxcvbnMatch(passwords[i], NULL, NULL);
+        }
+    }
+    clock_gettime(CLOCK_MONOTONIC, &end);
+
+    for (size_t i = 0; i < count; i++) {
+        printf("%f\n", bits[i] * log10(2.0));
+    }
+    long long elapsed =
+        (long long)(end.tv_sec - start.tv_sec) * 1000000000LL + (end.tv_nsec - start.tv_nsec);
+    printf("elapsed_ns %lld\n", elapsed);
+    return 0;
+}
@@ -0,0 +1,28 @@
+"""Driver for the `ports` benchmark, scoring passwords with the `zxcvbn` Python package.
+
+Reads passwords from stdin, one per line, and scores all of them the number of times given
+as the only argument. Prints the base 10 logarithm of the guesses of each password, then the
+nanoseconds spent scoring as `elapsed_ns <n>`.
+"""
+
+import sys
+import time
+
+from zxcvbn import zxcvbn
+
+
+def main():
+    rounds = int(sys.argv[1])
+    passwords = sys.stdin.read().splitlines()
+    results = []
+    start = time.perf_counter_ns()
+    for _ in range(rounds):
+        results = [zxcvbn(password)["guesses_log10"] for password in passwords]
+    elapsed = time.perf_counter_ns() - start
+    for guesses_log10 in results:
+        print(float(guesses_log10))
+    print("elapsed_ns", elapsed)
+
+
+if __name__ == "__main__":
+    main()
@@ -10,5 +10,41 @@ pub fn bench_zxcvbn(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, bench_zxcvbn);
+/// Long lowercase passphrases are dominated by dictionary lookups, one per substring
+/// and dictionary.
+pub fn bench_dictionary_lookups(c: &mut Criterion) {
+    c.bench_function("zxcvbn_dictionary_lookups", |b| {
+        b.iter(|| {
+            zxcvbn(
+                black_box("correcthorsebatterystaplethequickbrownfoxjumpsoverthelazydog"),
+                &[],
+            )
+        })
+    });
+}
+
+/// Passwords made of palindromes around every middle, of text retyped with shift toggled at
+/// every index, or of echoed text with many double letters, each of which the mirror, shift
+/// repeat and echo matchers could match and recursively estimate the base of.
+pub fn bench_crafted_passwords(c: &mut Criterion) {
+    let mut group = c.benchmark_group("zxcvbn_crafted_passwords");
+    for (name, password) in [
+        ("ab", "ab".repeat(50)),
+        ("a1", "a1".repeat(50)),
+        ("xyzzyx", "xyzzyx".repeat(16)),
+        ("abcba", "abcba".repeat(20)),
+        ("aA", "aA".repeat(50)),
+        ("ppaasswwoorrdd", "ppaasswwoorrdd".repeat(7)),
+    ] {
+        group.bench_function(name, |b| b.iter(|| zxcvbn(black_box(&password), &[])));
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_zxcvbn,
+    bench_dictionary_lookups,
+    bench_crafted_passwords
+);
 criterion_main!(benches);
@@ -0,0 +1,104 @@
+/*
+ * C interface to zxcvbn-rs.
+ *
+ * The result struct is versioned so that bindings built against one release
+ * keep working, or fail cleanly, when linked against another:
+ *
+ *  - ZXCVBN_ABI_VERSION packs a major version in the high 16 bits and a minor
+ *    version in the low 16 bits. Minor versions only ever append fields to
+ *    zxcvbn_result_t; a major version bump means the layout changed.
+ *  - zxcvbn_estimate() takes the version the caller was compiled against and
+ *    returns ZXCVBN_ERR_ABI_VERSION instead of a result when the library
+ *    cannot provide that layout.
+ *  - struct_size and features in the result say how much of the struct, and
+ *    which groups of fields, were filled in.
+ */
+
+#ifndef ZXCVBN_H
+#define ZXCVBN_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define ZXCVBN_ABI_VERSION_MAJOR 1
+#define ZXCVBN_ABI_VERSION_MINOR 0
+#define ZXCVBN_ABI_VERSION ((ZXCVBN_ABI_VERSION_MAJOR << 16) | ZXCVBN_ABI_VERSION_MINOR)
+
+/* Bits of zxcvbn_result_t.features. */
+#define ZXCVBN_FEATURE_CRACK_TIMES (1u << 0)      /* crack_times_seconds, crack_times_display */
+#define ZXCVBN_FEATURE_FEEDBACK (1u << 1)         /* warning, suggestions */
+#define ZXCVBN_FEATURE_MINIMUM_ADDITION (1u << 2) /* minimum_addition_* */
+
+/* Indices into the crack time arrays. */
+#define ZXCVBN_ONLINE_THROTTLING_100_PER_HOUR 0
+#define ZXCVBN_ONLINE_NO_THROTTLING_10_PER_SECOND 1
+#define ZXCVBN_OFFLINE_SLOW_HASHING_1E4_PER_SECOND 2
+#define ZXCVBN_OFFLINE_FAST_HASHING_1E10_PER_SECOND 3
+
+typedef enum {
+    ZXCVBN_OK = 0,
+    ZXCVBN_ERR_NULL_POINTER = 1,
+    ZXCVBN_ERR_INVALID_UTF8 = 2,
+    ZXCVBN_ERR_ABI_VERSION = 3,
+    /* The estimate panicked; *out was not written. */
+    ZXCVBN_ERR_PANIC = 4,
+} zxcvbn_status_t;
+
+typedef struct {
+    /* Size of the struct as filled in by the library, in bytes. */
+    uint32_t struct_size;
+    /* ABI version of the layout that was filled in. */
+    uint32_t abi_version;
+    /* ZXCVBN_FEATURE_* bits of the field groups that were filled in. */
+    uint64_t features;
+
+    uint64_t guesses;
+    double guesses_log10;
+    /* Overall strength score from 0 to 4. */
+    uint32_t score;
+    /* How long the estimate took, in milliseconds. */
+    double calc_time_ms;
+
+    double crack_times_seconds[4];
+    const char *crack_times_display[4];
+
+    /* NULL when there is no warning. */
+    const char *warning;
+    const char *const *suggestions;
+    size_t suggestions_len;
+
+    /* 0 when the password is already strong enough. */
+    uint32_t minimum_addition_random_characters;
+    /* Also 0 when no number of words fits within the estimated characters. */
+    uint32_t minimum_addition_words;
+} zxcvbn_result_t;
+
+/* ABI version implemented by the library. */
+uint32_t zxcvbn_abi_version(void);
+
+/* ZXCVBN_FEATURE_* bits the library can fill in. */
+uint64_t zxcvbn_features(void);
+
+/*
+ * Estimates the strength of the NUL-terminated UTF-8 password. user_inputs
+ * may be NULL when user_inputs_len is 0. Pass ZXCVBN_ABI_VERSION as
+ * abi_version. On success, *out must be released with zxcvbn_result_free().
+ */
+zxcvbn_status_t zxcvbn_estimate(const char *password,
+                                const char *const *user_inputs,
+                                size_t user_inputs_len,
+                                uint32_t abi_version,
+                                zxcvbn_result_t **out);
+
+/* Frees a result returned by zxcvbn_estimate(). NULL is ignored. */
+void zxcvbn_result_free(zxcvbn_result_t *result);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* ZXCVBN_H */
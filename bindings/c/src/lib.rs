@@ -0,0 +1,320 @@
+//! C ABI for zxcvbn.
+//!
+//! The result is returned as a versioned [`ZxcvbnResult`], laid out as described in
+//! `include/zxcvbn.h`. Language bindings pass the [`ABI_VERSION`] they were built
+//! against to [`zxcvbn_estimate`], which refuses with
+//! [`ZxcvbnStatus::AbiVersionMismatch`] rather than handing back a struct the caller
+//! would misread.
+
+#![warn(missing_docs)]
+
+use std::ffi::{CStr, CString};
+use std::mem;
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use zxcvbn::time_estimates::CrackTimeSeconds;
+
+/// Bumped whenever the layout of [`ZxcvbnResult`] changes incompatibly.
+pub const ABI_VERSION_MAJOR: u32 = 1;
+/// Bumped whenever fields are appended to [`ZxcvbnResult`].
+pub const ABI_VERSION_MINOR: u32 = 0;
+/// Major version in the high 16 bits, minor version in the low 16 bits.
+pub const ABI_VERSION: u32 = (ABI_VERSION_MAJOR << 16) | ABI_VERSION_MINOR;
+
+/// `crack_times_seconds` and `crack_times_display` are filled in.
+pub const FEATURE_CRACK_TIMES: u64 = 1 << 0;
+/// `warning` and `suggestions` are filled in.
+pub const FEATURE_FEEDBACK: u64 = 1 << 1;
+/// `minimum_addition_random_characters` and `minimum_addition_words` are filled in.
+pub const FEATURE_MINIMUM_ADDITION: u64 = 1 << 2;
+const FEATURES: u64 = FEATURE_CRACK_TIMES | FEATURE_FEEDBACK | FEATURE_MINIMUM_ADDITION;
+
+/// Status codes returned by [`zxcvbn_estimate`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZxcvbnStatus {
+    /// The estimate was written to `out`.
+    Ok = 0,
+    /// A required pointer argument was null.
+    NullPointer = 1,
+    /// The password or a user input was not valid UTF-8.
+    InvalidUtf8 = 2,
+    /// The caller's ABI version cannot be served by this library.
+    AbiVersionMismatch = 3,
+    /// The estimate panicked. Nothing was written to `out`.
+    Panic = 4,
+}
+
+/// The result of an estimate, mirroring `zxcvbn_result_t`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct ZxcvbnResult {
+    /// Size of the struct as filled in by the library, in bytes.
+    pub struct_size: u32,
+    /// ABI version of the layout that was filled in.
+    pub abi_version: u32,
+    /// `FEATURE_*` bits of the field groups that were filled in.
+    pub features: u64,
+    /// Estimated guesses needed to crack the password.
+    pub guesses: u64,
+    /// Order of magnitude of `guesses`.
+    pub guesses_log10: f64,
+    /// Overall strength score from 0 to 4.
+    pub score: u32,
+    /// How long the estimate took, in milliseconds.
+    pub calc_time_ms: f64,
+    /// Crack times in seconds, indexed like `ZXCVBN_ONLINE_THROTTLING_100_PER_HOUR` etc.
+    pub crack_times_seconds: [f64; 4],
+    /// Human readable crack times, indexed like `crack_times_seconds`.
+    pub crack_times_display: [*const c_char; 4],
+    /// Null when there is no warning.
+    pub warning: *const c_char,
+    /// Array of `suggestions_len` suggestions.
+    pub suggestions: *const *const c_char,
+    /// Number of suggestions.
+    pub suggestions_len: usize,
+    /// Random characters to append to reach an acceptable score, or 0.
+    pub minimum_addition_random_characters: u32,
+    /// Words to append to reach an acceptable score, or 0 if none fit within the estimated
+    /// characters.
+    pub minimum_addition_words: u32,
+}
+
+/// Keeps the strings a [`ZxcvbnResult`] points into alive. `result` must stay the first
+/// field so a pointer to this struct is also a pointer to the result.
+#[repr(C)]
+struct OwnedResult {
+    result: ZxcvbnResult,
+    strings: Vec<CString>,
+    suggestions: Vec<*const c_char>,
+}
+
+/// Returns the ABI version implemented by the library.
+#[no_mangle]
+pub extern "C" fn zxcvbn_abi_version() -> u32 {
+    ABI_VERSION
+}
+
+/// Returns the `FEATURE_*` bits the library can fill in.
+#[no_mangle]
+pub extern "C" fn zxcvbn_features() -> u64 {
+    FEATURES
+}
+
+/// Whether a caller built against `abi_version` can read results from this library.
+// Always false for older minor versions until the first field is appended.
+#[allow(clippy::absurd_extreme_comparisons)]
+fn is_compatible(abi_version: u32) -> bool {
+    abi_version >> 16 == ABI_VERSION_MAJOR && abi_version & 0xffff <= ABI_VERSION_MINOR
+}
+
+/// Estimates the strength of a password.
+///
+/// # Safety
+///
+/// `password` must be a valid NUL-terminated string, `user_inputs` must point to
+/// `user_inputs_len` valid NUL-terminated strings (or be null when `user_inputs_len` is 0),
+/// and `out` must be valid for writes. On success, `*out` must be released with
+/// [`zxcvbn_result_free`].
+#[no_mangle]
+pub unsafe extern "C" fn zxcvbn_estimate(
+    password: *const c_char,
+    user_inputs: *const *const c_char,
+    user_inputs_len: usize,
+    abi_version: u32,
+    out: *mut *mut ZxcvbnResult,
+) -> ZxcvbnStatus {
+    if out.is_null() || password.is_null() || (user_inputs.is_null() && user_inputs_len > 0) {
+        return ZxcvbnStatus::NullPointer;
+    }
+    if !is_compatible(abi_version) {
+        return ZxcvbnStatus::AbiVersionMismatch;
+    }
+    let password = match CStr::from_ptr(password).to_str() {
+        Ok(password) => password,
+        Err(_) => return ZxcvbnStatus::InvalidUtf8,
+    };
+    let mut inputs = Vec::with_capacity(user_inputs_len);
+    for k in 0..user_inputs_len {
+        let input = *user_inputs.add(k);
+        if input.is_null() {
+            return ZxcvbnStatus::NullPointer;
+        }
+        match CStr::from_ptr(input).to_str() {
+            Ok(input) => inputs.push(input),
+            Err(_) => return ZxcvbnStatus::InvalidUtf8,
+        }
+    }
+
+    // unwinding into C is undefined behavior
+    let result = catch_panic(|| Box::new(to_owned_result(&zxcvbn::zxcvbn(password, &inputs))));
+    match result {
+        Ok(result) => {
+            *out = Box::into_raw(result).cast();
+            ZxcvbnStatus::Ok
+        }
+        Err(status) => status,
+    }
+}
+
+/// Runs `f`, returning [`ZxcvbnStatus::Panic`] if it panics.
+fn catch_panic<T, F: FnOnce() -> T>(f: F) -> Result<T, ZxcvbnStatus> {
+    // nothing `f` borrows is used again after a panic
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|_| ZxcvbnStatus::Panic)
+}
+
+/// Frees a result returned by [`zxcvbn_estimate`]. Null is ignored.
+///
+/// # Safety
+///
+/// `result` must be null or a pointer returned by [`zxcvbn_estimate`] that has not been
+/// freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn zxcvbn_result_free(result: *mut ZxcvbnResult) {
+    if !result.is_null() {
+        drop(Box::from_raw(result.cast::<OwnedResult>()));
+    }
+}
+
+fn to_owned_result(entropy: &zxcvbn::Entropy) -> OwnedResult {
+    // Strings never contain NUL, all of them come from the crate's own messages.
+    let mut strings = Vec::new();
+    let mut push = |s: String| {
+        let s = CString::new(s).expect("no interior NUL");
+        let ptr = s.as_ptr();
+        strings.push(s);
+        ptr
+    };
+
+    let times = entropy.crack_times();
+    let crack_times = [
+        times.online_throttling_100_per_hour(),
+        times.online_no_throttling_10_per_second(),
+        times.offline_slow_hashing_1e4_per_second(),
+        times.offline_fast_hashing_1e10_per_second(),
+    ];
+    let crack_times_display = crack_times.map(|time| push(time.to_string()));
+    let crack_times_seconds = crack_times.map(|time| match time {
+        CrackTimeSeconds::Integer(i) => i as f64,
+        CrackTimeSeconds::Float(f) => f,
+    });
+
+    let feedback = entropy.feedback().as_ref();
+    let warning = feedback
+        .and_then(|f| f.warning())
+        .map_or(ptr::null(), |w| push(w.to_string()));
+    let suggestions: Vec<_> = feedback
+        .map(|f| {
+            f.suggestions()
+                .iter()
+                .map(|s| push(s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    let addition = feedback.and_then(|f| f.minimum_addition());
+
+    let mut owned = OwnedResult {
+        result: ZxcvbnResult {
+            struct_size: mem::size_of::<ZxcvbnResult>() as u32,
+            abi_version: ABI_VERSION,
+            features: FEATURES,
+            guesses: entropy.guesses(),
+            guesses_log10: entropy.guesses_log10(),
+            score: u32::from(u8::from(entropy.score())),
+            calc_time_ms: entropy.calculation_time().as_secs_f64() * 1000.0,
+            crack_times_seconds,
+            crack_times_display,
+            warning,
+            suggestions: ptr::null(),
+            suggestions_len: suggestions.len(),
+            minimum_addition_random_characters: addition
+                .map_or(0, |a| a.random_characters() as u32),
+            minimum_addition_words: addition
+                .and_then(|a| a.words())
+                .map_or(0, |words| words as u32),
+        },
+        strings,
+        suggestions,
+    };
+    owned.result.suggestions = owned.suggestions.as_ptr();
+    owned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn estimate(password: &CStr, abi_version: u32) -> Result<*mut ZxcvbnResult, ZxcvbnStatus> {
+        let user_input = CString::new("alice").unwrap();
+        let user_inputs = [user_input.as_ptr()];
+        let mut out = ptr::null_mut();
+        let status = unsafe {
+            zxcvbn_estimate(
+                password.as_ptr(),
+                user_inputs.as_ptr(),
+                1,
+                abi_version,
+                &mut out,
+            )
+        };
+        match status {
+            ZxcvbnStatus::Ok => Ok(out),
+            status => Err(status),
+        }
+    }
+
+    #[test]
+    fn test_estimate() {
+        let result = estimate(&CString::new("alice1990").unwrap(), ABI_VERSION).unwrap();
+        let r = unsafe { &*result };
+        assert_eq!(r.struct_size as usize, mem::size_of::<ZxcvbnResult>());
+        assert_eq!(r.abi_version, ABI_VERSION);
+        assert_eq!(r.features, zxcvbn_features());
+        assert!(r.score <= 1);
+        let display = unsafe { CStr::from_ptr(r.crack_times_display[0]) };
+        assert!(!display.to_bytes().is_empty());
+        assert!(r.suggestions_len > 0);
+        let suggestion = unsafe { CStr::from_ptr(*r.suggestions) };
+        assert!(!suggestion.to_bytes().is_empty());
+        assert!(r.minimum_addition_random_characters > 0);
+        unsafe { zxcvbn_result_free(result) };
+    }
+
+    #[test]
+    fn test_abi_version_mismatch() {
+        let password = CString::new("password").unwrap();
+        assert_eq!(
+            estimate(&password, (ABI_VERSION_MAJOR + 1) << 16),
+            Err(ZxcvbnStatus::AbiVersionMismatch)
+        );
+        assert_eq!(
+            estimate(&password, ABI_VERSION + 1),
+            Err(ZxcvbnStatus::AbiVersionMismatch)
+        );
+        let result = estimate(&password, ABI_VERSION_MAJOR << 16).unwrap();
+        unsafe { zxcvbn_result_free(result) };
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let mut out = ptr::null_mut();
+        let status = unsafe { zxcvbn_estimate(ptr::null(), ptr::null(), 0, ABI_VERSION, &mut out) };
+        assert_eq!(status, ZxcvbnStatus::NullPointer);
+        let password = CString::new(vec![0xff]).unwrap();
+        assert_eq!(
+            estimate(&password, ABI_VERSION),
+            Err(ZxcvbnStatus::InvalidUtf8)
+        );
+    }
+
+    #[test]
+    fn test_catch_panic() {
+        assert_eq!(catch_panic(|| 1), Ok(1));
+        assert_eq!(
+            catch_panic(|| -> u32 { panic!("estimate failed") }),
+            Err(ZxcvbnStatus::Panic)
+        );
+    }
+}
@@ -0,0 +1,22 @@
+import assert from 'node:assert/strict';
+import { createRequire } from 'node:module';
+import test from 'node:test';
+
+const { zxcvbn } = createRequire(import.meta.url)('../index.js');
+
+test('result has the shape of the JS library', () => {
+  const result = zxcvbn('alice1990', ['alice']);
+  assert.equal(result.password, 'alice1990');
+  assert.ok(result.score <= 1);
+  assert.equal(result.sequence[0].pattern, 'dictionary');
+  assert.equal(result.sequence[0].dictionary_name, 'user_inputs');
+  assert.equal(typeof result.calc_time, 'number');
+  assert.equal(typeof result.crack_times_seconds.offline_slow_hashing_1e4_per_second, 'number');
+  assert.equal(typeof result.crack_times_display.offline_slow_hashing_1e4_per_second, 'string');
+  assert.ok(Array.isArray(result.feedback.suggestions));
+});
+
+test('strong passwords get empty feedback', () => {
+  const result = zxcvbn('1wQ@5ti$xN8!kZv');
+  assert.deepEqual(result.feedback, { warning: '', suggestions: [] });
+});
@@ -0,0 +1,266 @@
+//! Node.js bindings for zxcvbn.
+//!
+//! The exported `zxcvbn` function returns an object with exactly the shape of
+//! the JavaScript [zxcvbn](https://www.npmjs.com/package/zxcvbn) library's
+//! result, so server code only needs its `require` changed:
+//!
+//! ```js
+//! const { zxcvbn } = require('zxcvbn-rs');
+//!
+//! const result = zxcvbn('correcthorsebatterystaple', ['alice']);
+//! console.log(result.score, result.crack_times_display);
+//! ```
+
+#![forbid(unsafe_code)]
+
+use napi::{Env, JsObject, Result};
+use napi_derive::napi;
+
+use zxcvbn::matching::patterns::{DictionaryType, MatchPattern};
+use zxcvbn::time_estimates::CrackTimeSeconds;
+use zxcvbn::Match;
+
+/// Estimates the strength of a password.
+#[napi(js_name = "zxcvbn")]
+pub fn estimate(env: Env, password: String, user_inputs: Option<Vec<String>>) -> Result<JsObject> {
+    let user_inputs = user_inputs.unwrap_or_default();
+    let user_inputs = user_inputs.iter().map(String::as_str).collect::<Vec<_>>();
+    let entropy = zxcvbn::zxcvbn(&password, &user_inputs);
+
+    let mut result = env.create_object()?;
+    result.set("password", password)?;
+    // Guesses may exceed 2^53, where JS numbers lose precision, just like in the JS library.
+    result.set("guesses", entropy.guesses() as f64)?;
+    result.set("guesses_log10", entropy.guesses_log10())?;
+    let mut sequence = env.create_array_with_length(entropy.sequence().len())?;
+    for (k, m) in entropy.sequence().iter().enumerate() {
+        sequence.set_element(k as u32, match_to_object(&env, m)?)?;
+    }
+    result.set("sequence", sequence)?;
+    // Milliseconds, like the JS library.
+    result.set(
+        "calc_time",
+        entropy.calculation_time().as_secs_f64() * 1000.0,
+    )?;
+
+    let times = entropy.crack_times();
+    let mut seconds = env.create_object()?;
+    let mut display = env.create_object()?;
+    for (name, time) in [
+        (
+            "online_throttling_100_per_hour",
+            times.online_throttling_100_per_hour(),
+        ),
+        (
+            "online_no_throttling_10_per_second",
+            times.online_no_throttling_10_per_second(),
+        ),
+        (
+            "offline_slow_hashing_1e4_per_second",
+            times.offline_slow_hashing_1e4_per_second(),
+        ),
+        (
+            "offline_fast_hashing_1e10_per_second",
+            times.offline_fast_hashing_1e10_per_second(),
+        ),
+    ] {
+        let value = match time {
+            CrackTimeSeconds::Integer(i) => i as f64,
+            CrackTimeSeconds::Float(f) => f,
+        };
+        seconds.set(name, value)?;
+        display.set(name, time.to_string())?;
+    }
+    result.set("crack_times_seconds", seconds)?;
+    result.set("crack_times_display", display)?;
+    result.set("score", u32::from(u8::from(entropy.score())))?;
+
+    let mut feedback = env.create_object()?;
+    let (warning, suggestions) = match entropy.feedback() {
+        Some(f) => (
+            f.warning().map(|w| w.to_string()).unwrap_or_default(),
+            f.suggestions().iter().map(|s| s.to_string()).collect(),
+        ),
+        None => (String::new(), Vec::new()),
+    };
+    feedback.set("warning", warning)?;
+    feedback.set("suggestions", suggestions)?;
+    result.set("feedback", feedback)?;
+    Ok(result)
+}
+
+fn match_to_object(env: &Env, m: &Match) -> Result<JsObject> {
+    let mut object = env.create_object()?;
+    object.set("i", m.i as u32)?;
+    object.set("j", m.j as u32)?;
+    object.set("token", m.token.as_str())?;
+    if let Some(guesses) = m.guesses {
+        object.set("guesses", guesses as f64)?;
+        object.set("guesses_log10", (guesses as f64).log10())?;
+    }
+    match &m.pattern {
+        MatchPattern::Dictionary(p) => {
+            object.set("pattern", "dictionary")?;
+            object.set("matched_word", p.matched_word.as_str())?;
+            object.set("rank", p.rank as u32)?;
+            object.set("dictionary_name", dictionary_name(p.dictionary_name))?;
+            object.set("reversed", p.reversed)?;
+            object.set("l33t", p.l33t)?;
+            if let Some(sub) = &p.sub {
+                let mut sub_object = env.create_object()?;
+                for (from, to) in sub {
+                    sub_object.set(from.to_string(), to.to_string())?;
+                }
+                object.set("sub", sub_object)?;
+            }
+            if let Some(sub_display) = &p.sub_display {
+                object.set("sub_display", sub_display.as_str())?;
+            }
+            object.set("base_guesses", p.base_guesses as f64)?;
+            object.set("uppercase_variations", p.uppercase_variations as f64)?;
+            object.set("l33t_variations", p.l33t_variations as f64)?;
+        }
+        MatchPattern::Spatial(p) => {
+            object.set("pattern", "spatial")?;
+            object.set("graph", p.graph.as_str())?;
+            object.set("turns", p.turns as u32)?;
+            object.set("shifted_count", p.shifted_count as u32)?;
+        }
+        MatchPattern::Repeat(p) => {
+            object.set("pattern", "repeat")?;
+            object.set("base_token", p.base_token.as_str())?;
+            object.set("base_guesses", p.base_guesses as f64)?;
+            let mut base_matches = env.create_array_with_length(p.base_matches.len())?;
+            for (k, m) in p.base_matches.iter().enumerate() {
+                base_matches.set_element(k as u32, match_to_object(env, m)?)?;
+            }
+            object.set("base_matches", base_matches)?;
+            object.set("repeat_count", p.repeat_count as u32)?;
+        }
+        MatchPattern::Mirror(p) => {
+            object.set("pattern", "mirror")?;
+            object.set("base_token", p.base_token.as_str())?;
+            object.set("base_guesses", p.base_guesses as f64)?;
+            let mut base_matches = env.create_array_with_length(p.base_matches.len())?;
+            for (k, m) in p.base_matches.iter().enumerate() {
+                base_matches.set_element(k as u32, match_to_object(env, m)?)?;
+            }
+            object.set("base_matches", base_matches)?;
+        }
+        MatchPattern::ShiftRepeat(p) => {
+            object.set("pattern", "shift_repeat")?;
+            object.set("base_token", p.base_token.as_str())?;
+            object.set("reversed", p.reversed)?;
+            object.set("base_guesses", p.base_guesses as f64)?;
+            let mut base_matches = env.create_array_with_length(p.base_matches.len())?;
+            for (k, m) in p.base_matches.iter().enumerate() {
+                base_matches.set_element(k as u32, match_to_object(env, m)?)?;
+            }
+            object.set("base_matches", base_matches)?;
+        }
+        MatchPattern::Phone(p) => {
+            object.set("pattern", "phone")?;
+            object.set("part", p.part)?;
+            object.set("rank", p.rank as u32)?;
+        }
+        MatchPattern::NationalId(p) => {
+            object.set("pattern", "national_id")?;
+            object.set("id_name", p.id_name)?;
+            object.set("region", p.region)?;
+        }
+        MatchPattern::Payment(p) => {
+            object.set("pattern", "payment")?;
+            object.set("payment_kind", p.payment_kind)?;
+        }
+        MatchPattern::Unlock(p) => {
+            object.set("pattern", "unlock")?;
+            object.set("knight_moves", p.knight_moves as u32)?;
+        }
+        MatchPattern::WordPair(p) => {
+            object.set("pattern", "word_pair")?;
+            object.set("first_word", p.first_word.as_str())?;
+            object.set("second_word", p.second_word.as_str())?;
+            object.set("first_rank", p.first_rank as u32)?;
+            object.set("second_rank", p.second_rank as u32)?;
+            object.set("pair_rank", p.pair_rank.map(|rank| rank as u32))?;
+            object.set("dictionary_name", dictionary_name(p.dictionary_name))?;
+        }
+        MatchPattern::Echo(p) => {
+            object.set("pattern", "echo")?;
+            object.set("base_token", p.base_token.as_str())?;
+            object.set("echo_count", p.echo_count as u32)?;
+            object.set("collapsed", p.collapsed)?;
+            object.set("base_guesses", p.base_guesses as f64)?;
+            let mut base_matches = env.create_array_with_length(p.base_matches.len())?;
+            for (k, m) in p.base_matches.iter().enumerate() {
+                base_matches.set_element(k as u32, match_to_object(env, m)?)?;
+            }
+            object.set("base_matches", base_matches)?;
+        }
+        MatchPattern::Acronym(p) => {
+            object.set("pattern", "acronym")?;
+            object.set("phrase", p.phrase)?;
+            object.set("rank", p.rank as u32)?;
+        }
+        MatchPattern::Sequence(p) => {
+            object.set("pattern", "sequence")?;
+            object.set("sequence_name", p.sequence_name)?;
+            object.set("sequence_space", u32::from(p.sequence_space))?;
+            object.set("ascending", p.ascending)?;
+        }
+        MatchPattern::Regex(p) => {
+            object.set("pattern", "regex")?;
+            object.set("regex_name", p.regex_name)?;
+            object.set("regex_match", p.regex_match.clone())?;
+        }
+        MatchPattern::Date(p) => {
+            object.set("pattern", "date")?;
+            object.set("separator", p.separator.as_str())?;
+            object.set("year", p.year)?;
+            object.set("month", i32::from(p.month))?;
+            object.set("day", i32::from(p.day))?;
+            object.set("user_input", p.user_input)?;
+        }
+        MatchPattern::Emoji(p) => {
+            object.set("pattern", "emoji")?;
+            object.set("emoji_kind", p.emoji_kind)?;
+            object.set("emoji_count", p.emoji_count as u32)?;
+            object.set("ascending", p.ascending)?;
+            object.set("base_guesses", p.base_guesses as f64)?;
+        }
+        MatchPattern::Interleaved(p) => {
+            object.set("pattern", "interleaved")?;
+            object.set("first_token", p.first_token.as_str())?;
+            object.set("first_sequence_name", p.first_sequence.sequence_name)?;
+            object.set("second_token", p.second_token.as_str())?;
+            object.set("second_sequence_name", p.second_sequence.sequence_name)?;
+        }
+        MatchPattern::BruteForce => object.set("pattern", "bruteforce")?,
+        // Patterns added after this binding was written
+        _ => object.set("pattern", "unknown")?,
+    }
+    Ok(object)
+}
+
+/// Dictionary names as used by the JS library.
+fn dictionary_name(dictionary: DictionaryType) -> &'static str {
+    match dictionary {
+        DictionaryType::Passwords => "passwords",
+        DictionaryType::English => "english_wikipedia",
+        DictionaryType::FemaleNames => "female_names",
+        DictionaryType::MaleNames => "male_names",
+        DictionaryType::Surnames => "surnames",
+        DictionaryType::UsTvAndFilm => "us_tv_and_film",
+        DictionaryType::Bigrams => "bigrams",
+        DictionaryType::SportsTeams => "sports_teams",
+        DictionaryType::CarBrands => "car_brands",
+        DictionaryType::Bands => "bands",
+        DictionaryType::VideoGames => "video_games",
+        DictionaryType::Russian => "russian",
+        DictionaryType::Autosuggest => "autosuggest",
+        DictionaryType::UserInputs => "user_inputs",
+        DictionaryType::Custom => "custom",
+        DictionaryType::Banned => "banned",
+        _ => "unknown",
+    }
+}
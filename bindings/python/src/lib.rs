@@ -0,0 +1,353 @@
+//! Python bindings for zxcvbn.
+//!
+//! The `zxcvbn` function returns the same dict as the pure Python
+//! [zxcvbn](https://pypi.org/project/zxcvbn/) package, so existing code only
+//! needs its import changed:
+//!
+//! ```python
+//! from zxcvbn_rs import zxcvbn
+//!
+//! result = zxcvbn("correcthorsebatterystaple", user_inputs=["alice"])
+//! print(result["score"], result["crack_times_display"])
+//! ```
+
+#![forbid(unsafe_code)]
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use zxcvbn::matching::patterns::{DictionaryType, MatchPattern};
+use zxcvbn::time_estimates::{CrackTimeSeconds, CrackTimes};
+use zxcvbn::{Entropy, Match};
+
+/// Estimates the strength of a password.
+///
+/// Returns a dict shaped like the one returned by the `zxcvbn` Python package.
+#[pyfunction]
+#[pyo3(name = "zxcvbn", signature = (password, user_inputs = None))]
+fn estimate<'py>(
+    py: Python<'py>,
+    password: &str,
+    user_inputs: Option<Vec<String>>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let user_inputs = user_inputs.unwrap_or_default();
+    let entropy = py.allow_threads(|| {
+        let user_inputs = user_inputs.iter().map(String::as_str).collect::<Vec<_>>();
+        zxcvbn::zxcvbn(password, &user_inputs)
+    });
+    entropy_to_dict(py, password, &entropy)
+}
+
+/// Python module definition.
+#[pymodule]
+fn zxcvbn_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    m.add_function(wrap_pyfunction!(estimate, m)?)
+}
+
+fn entropy_to_dict<'py>(
+    py: Python<'py>,
+    password: &str,
+    entropy: &Entropy,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("password", password)?;
+    dict.set_item("guesses", entropy.guesses())?;
+    dict.set_item("guesses_log10", entropy.guesses_log10())?;
+    let sequence = entropy
+        .sequence()
+        .iter()
+        .map(|m| match_to_dict(py, m))
+        .collect::<PyResult<Vec<_>>>()?;
+    dict.set_item("sequence", sequence)?;
+    dict.set_item("calc_time", entropy.calculation_time())?;
+    let (seconds, display) = crack_times_to_dicts(py, entropy.crack_times())?;
+    dict.set_item("crack_times_seconds", seconds)?;
+    dict.set_item("crack_times_display", display)?;
+    dict.set_item("score", u8::from(entropy.score()))?;
+
+    let feedback = PyDict::new(py);
+    match entropy.feedback() {
+        Some(f) => {
+            feedback.set_item(
+                "warning",
+                f.warning().map(|w| w.to_string()).unwrap_or_default(),
+            )?;
+            let suggestions = f
+                .suggestions()
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>();
+            feedback.set_item("suggestions", suggestions)?;
+        }
+        None => {
+            feedback.set_item("warning", "")?;
+            feedback.set_item("suggestions", Vec::<String>::new())?;
+        }
+    }
+    dict.set_item("feedback", feedback)?;
+    Ok(dict)
+}
+
+fn crack_times_to_dicts(
+    py: Python<'_>,
+    times: CrackTimes,
+) -> PyResult<(Bound<'_, PyDict>, Bound<'_, PyDict>)> {
+    let seconds = PyDict::new(py);
+    let display = PyDict::new(py);
+    for (name, time) in [
+        (
+            "online_throttling_100_per_hour",
+            times.online_throttling_100_per_hour(),
+        ),
+        (
+            "online_no_throttling_10_per_second",
+            times.online_no_throttling_10_per_second(),
+        ),
+        (
+            "offline_slow_hashing_1e4_per_second",
+            times.offline_slow_hashing_1e4_per_second(),
+        ),
+        (
+            "offline_fast_hashing_1e10_per_second",
+            times.offline_fast_hashing_1e10_per_second(),
+        ),
+    ] {
+        let value = match time {
+            CrackTimeSeconds::Integer(i) => i as f64,
+            CrackTimeSeconds::Float(f) => f,
+        };
+        seconds.set_item(name, value)?;
+        display.set_item(name, time.to_string())?;
+    }
+    Ok((seconds, display))
+}
+
+fn match_to_dict<'py>(py: Python<'py>, m: &Match) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("i", m.i)?;
+    dict.set_item("j", m.j)?;
+    dict.set_item("token", &m.token)?;
+    if let Some(guesses) = m.guesses {
+        dict.set_item("guesses", guesses)?;
+        dict.set_item("guesses_log10", (guesses as f64).log10())?;
+    }
+    match &m.pattern {
+        MatchPattern::Dictionary(p) => {
+            dict.set_item("pattern", "dictionary")?;
+            dict.set_item("matched_word", &p.matched_word)?;
+            dict.set_item("rank", p.rank)?;
+            dict.set_item("dictionary_name", dictionary_name(p.dictionary_name))?;
+            dict.set_item("reversed", p.reversed)?;
+            dict.set_item("l33t", p.l33t)?;
+            if let Some(sub) = &p.sub {
+                dict.set_item("sub", sub)?;
+            }
+            if let Some(sub_display) = &p.sub_display {
+                dict.set_item("sub_display", sub_display)?;
+            }
+            dict.set_item("base_guesses", p.base_guesses)?;
+            dict.set_item("uppercase_variations", p.uppercase_variations)?;
+            dict.set_item("l33t_variations", p.l33t_variations)?;
+        }
+        MatchPattern::Spatial(p) => {
+            dict.set_item("pattern", "spatial")?;
+            dict.set_item("graph", &p.graph)?;
+            dict.set_item("turns", p.turns)?;
+            dict.set_item("shifted_count", p.shifted_count)?;
+        }
+        MatchPattern::Repeat(p) => {
+            dict.set_item("pattern", "repeat")?;
+            dict.set_item("base_token", &p.base_token)?;
+            dict.set_item("base_guesses", p.base_guesses)?;
+            let base_matches = p
+                .base_matches
+                .iter()
+                .map(|m| match_to_dict(py, m))
+                .collect::<PyResult<Vec<_>>>()?;
+            dict.set_item("base_matches", base_matches)?;
+            dict.set_item("repeat_count", p.repeat_count)?;
+        }
+        MatchPattern::Mirror(p) => {
+            dict.set_item("pattern", "mirror")?;
+            dict.set_item("base_token", &p.base_token)?;
+            dict.set_item("base_guesses", p.base_guesses)?;
+            let base_matches = p
+                .base_matches
+                .iter()
+                .map(|m| match_to_dict(py, m))
+                .collect::<PyResult<Vec<_>>>()?;
+            dict.set_item("base_matches", base_matches)?;
+        }
+        MatchPattern::ShiftRepeat(p) => {
+            dict.set_item("pattern", "shift_repeat")?;
+            dict.set_item("base_token", &p.base_token)?;
+            dict.set_item("reversed", p.reversed)?;
+            dict.set_item("base_guesses", p.base_guesses)?;
+            let base_matches = p
+                .base_matches
+                .iter()
+                .map(|m| match_to_dict(py, m))
+                .collect::<PyResult<Vec<_>>>()?;
+            dict.set_item("base_matches", base_matches)?;
+        }
+        MatchPattern::Phone(p) => {
+            dict.set_item("pattern", "phone")?;
+            dict.set_item("part", p.part)?;
+            dict.set_item("rank", p.rank)?;
+        }
+        MatchPattern::NationalId(p) => {
+            dict.set_item("pattern", "national_id")?;
+            dict.set_item("id_name", p.id_name)?;
+            dict.set_item("region", p.region)?;
+        }
+        MatchPattern::Payment(p) => {
+            dict.set_item("pattern", "payment")?;
+            dict.set_item("payment_kind", p.payment_kind)?;
+        }
+        MatchPattern::Unlock(p) => {
+            dict.set_item("pattern", "unlock")?;
+            dict.set_item("knight_moves", p.knight_moves)?;
+        }
+        MatchPattern::WordPair(p) => {
+            dict.set_item("pattern", "word_pair")?;
+            dict.set_item("first_word", &p.first_word)?;
+            dict.set_item("second_word", &p.second_word)?;
+            dict.set_item("first_rank", p.first_rank)?;
+            dict.set_item("second_rank", p.second_rank)?;
+            dict.set_item("pair_rank", p.pair_rank)?;
+            dict.set_item("dictionary_name", dictionary_name(p.dictionary_name))?;
+        }
+        MatchPattern::Echo(p) => {
+            dict.set_item("pattern", "echo")?;
+            dict.set_item("base_token", &p.base_token)?;
+            dict.set_item("echo_count", p.echo_count)?;
+            dict.set_item("collapsed", p.collapsed)?;
+            dict.set_item("base_guesses", p.base_guesses)?;
+            let base_matches = p
+                .base_matches
+                .iter()
+                .map(|m| match_to_dict(py, m))
+                .collect::<PyResult<Vec<_>>>()?;
+            dict.set_item("base_matches", base_matches)?;
+        }
+        MatchPattern::Acronym(p) => {
+            dict.set_item("pattern", "acronym")?;
+            dict.set_item("phrase", p.phrase)?;
+            dict.set_item("rank", p.rank)?;
+        }
+        MatchPattern::Sequence(p) => {
+            dict.set_item("pattern", "sequence")?;
+            dict.set_item("sequence_name", p.sequence_name)?;
+            dict.set_item("sequence_space", p.sequence_space)?;
+            dict.set_item("ascending", p.ascending)?;
+        }
+        MatchPattern::Regex(p) => {
+            dict.set_item("pattern", "regex")?;
+            dict.set_item("regex_name", p.regex_name)?;
+            dict.set_item("regex_match", &p.regex_match)?;
+        }
+        MatchPattern::Date(p) => {
+            dict.set_item("pattern", "date")?;
+            dict.set_item("separator", &p.separator)?;
+            dict.set_item("year", p.year)?;
+            dict.set_item("month", p.month)?;
+            dict.set_item("day", p.day)?;
+            dict.set_item("user_input", p.user_input)?;
+        }
+        MatchPattern::Emoji(p) => {
+            dict.set_item("pattern", "emoji")?;
+            dict.set_item("emoji_kind", p.emoji_kind)?;
+            dict.set_item("emoji_count", p.emoji_count)?;
+            dict.set_item("ascending", p.ascending)?;
+            dict.set_item("base_guesses", p.base_guesses)?;
+        }
+        MatchPattern::Interleaved(p) => {
+            dict.set_item("pattern", "interleaved")?;
+            dict.set_item("first_token", &p.first_token)?;
+            dict.set_item("first_sequence_name", p.first_sequence.sequence_name)?;
+            dict.set_item("second_token", &p.second_token)?;
+            dict.set_item("second_sequence_name", p.second_sequence.sequence_name)?;
+        }
+        MatchPattern::BruteForce => dict.set_item("pattern", "bruteforce")?,
+        // Patterns added after this binding was written
+        _ => dict.set_item("pattern", "unknown")?,
+    }
+    Ok(dict)
+}
+
+/// Dictionary names as used by the Python package.
+fn dictionary_name(dictionary: DictionaryType) -> &'static str {
+    match dictionary {
+        DictionaryType::Passwords => "passwords",
+        DictionaryType::English => "english_wikipedia",
+        DictionaryType::FemaleNames => "female_names",
+        DictionaryType::MaleNames => "male_names",
+        DictionaryType::Surnames => "surnames",
+        DictionaryType::UsTvAndFilm => "us_tv_and_film",
+        DictionaryType::Bigrams => "bigrams",
+        DictionaryType::SportsTeams => "sports_teams",
+        DictionaryType::CarBrands => "car_brands",
+        DictionaryType::Bands => "bands",
+        DictionaryType::VideoGames => "video_games",
+        DictionaryType::Russian => "russian",
+        DictionaryType::Autosuggest => "autosuggest",
+        DictionaryType::UserInputs => "user_inputs",
+        DictionaryType::Custom => "custom",
+        DictionaryType::Banned => "banned",
+        _ => "unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_result_shape() {
+        Python::with_gil(|py| {
+            let result = estimate(py, "alice1990", Some(vec!["alice".to_string()])).unwrap();
+            let score: u8 = result
+                .get_item("score")
+                .unwrap()
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert!(score <= 1);
+            let sequence = result.get_item("sequence").unwrap().unwrap();
+            let first = sequence.get_item(0).unwrap();
+            let pattern: String = first.get_item("pattern").unwrap().extract().unwrap();
+            assert_eq!(pattern, "dictionary");
+            let dictionary: String = first
+                .get_item("dictionary_name")
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(dictionary, "user_inputs");
+            let result = estimate(py, "password", None).unwrap();
+            let warning: String = result
+                .get_item("feedback")
+                .unwrap()
+                .unwrap()
+                .get_item("warning")
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert!(!warning.is_empty());
+            for key in ["calc_time", "crack_times_seconds", "crack_times_display"] {
+                assert!(result.contains(key).unwrap());
+            }
+        });
+    }
+
+    #[test]
+    fn test_strong_password_feedback() {
+        Python::with_gil(|py| {
+            let result = estimate(py, "1wQ@5ti$xN8!kZv", None).unwrap();
+            let feedback = result.get_item("feedback").unwrap().unwrap();
+            let warning: String = feedback.get_item("warning").unwrap().extract().unwrap();
+            assert_eq!(warning, "");
+            assert_eq!(feedback.get_item("suggestions").unwrap().len().unwrap(), 0);
+        });
+    }
+}
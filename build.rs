@@ -0,0 +1,301 @@
+//! Generates the dictionary compiled into the crate: the enabled word lists, shortened
+//! without the `full-dictionaries` feature or to the rank given in `ZXCVBN_MAX_RANK`, merged
+//! into one table sorted by word. With the `extra-keyboards` feature, also generates the graphs
+//! of the keyboards in `keyboards/`. Also generates the pairs of words matched as one, from the
+//! bundled bigrams, and, with the `all-matchers` feature, the acronyms of the bundled phrases.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+#[path = "src/frequency_lists/words.rs"]
+#[allow(dead_code)]
+mod words;
+
+#[path = "src/frequency_lists/packs.rs"]
+#[allow(dead_code)]
+mod packs;
+
+/// Number of passwords kept without the `full-dictionaries` feature.
+const LITE_PASSWORDS: usize = 10_000;
+/// Most bytes of word lists bundled without the `full-dictionaries` feature, for the small
+/// builds it is meant for.
+const LITE_WORD_LISTS_BUDGET: usize = 100 * 1024;
+/// Shorter acronyms are too likely to show up by chance.
+const MIN_ACRONYM_LEN: usize = 5;
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/frequency_lists/words.rs");
+    println!("cargo:rerun-if-changed=src/frequency_lists/packs.rs");
+    println!("cargo:rerun-if-changed=keyboards");
+    println!("cargo:rerun-if-env-changed=ZXCVBN_MAX_RANK");
+    let out_dir = env::var_os("OUT_DIR").unwrap();
+    let full_dictionaries = env::var_os("CARGO_FEATURE_FULL_DICTIONARIES").is_some();
+    let all_matchers = env::var_os("CARGO_FEATURE_ALL_MATCHERS").is_some();
+    // Trades accuracy for size: words ranked lower than this are left out of every list.
+    let max_rank = env::var("ZXCVBN_MAX_RANK").ok().map(|max_rank| {
+        max_rank
+            .parse::<usize>()
+            .ok()
+            .filter(|&max_rank| max_rank > 0)
+            .expect("ZXCVBN_MAX_RANK must be a positive integer")
+    });
+
+    // Dictionary type and list, in the order the dictionaries are searched.
+    let mut dictionaries = vec![("Passwords", words::PASSWORDS)];
+    if full_dictionaries {
+        dictionaries.extend([
+            ("English", words::ENGLISH_WIKI),
+            ("FemaleNames", words::FEMALE_NAMES),
+            ("MaleNames", words::MALE_NAMES),
+            ("Surnames", words::SURNAMES),
+            ("UsTvAndFilm", words::US_TV_AND_FILM),
+        ]);
+    } else {
+        dictionaries[0].1 = truncate(words::PASSWORDS, LITE_PASSWORDS);
+    }
+    #[cfg(feature = "sports-teams")]
+    dictionaries.push(("SportsTeams", packs::SPORTS_TEAMS));
+    #[cfg(feature = "car-brands")]
+    dictionaries.push(("CarBrands", packs::CAR_BRANDS));
+    #[cfg(feature = "bands")]
+    dictionaries.push(("Bands", packs::BANDS));
+    #[cfg(feature = "video-games")]
+    dictionaries.push(("VideoGames", packs::VIDEO_GAMES));
+    #[cfg(feature = "russian")]
+    dictionaries.push(("Russian", packs::RUSSIAN));
+    #[cfg(feature = "autosuggest")]
+    dictionaries.push(("Autosuggest", packs::AUTOSUGGEST));
+    if let Some(max_rank) = max_rank {
+        for (_, list) in &mut dictionaries {
+            *list = truncate(list, max_rank);
+        }
+    }
+    if !full_dictionaries {
+        // the optional word list packs are left out, as enabling them asks for more words
+        let mut bundled = dictionaries[0].1.len() + words::BIGRAMS.len();
+        if all_matchers {
+            bundled += words::PHRASES.len();
+        }
+        assert!(
+            bundled <= LITE_WORD_LISTS_BUDGET,
+            "the word lists bundled without `full-dictionaries` take {} bytes, over the budget of {}",
+            bundled,
+            LITE_WORD_LISTS_BUDGET
+        );
+    }
+
+    // Merge the lists into one table, sorted by word, so that each word is stored once
+    // along with the dictionaries it is in and its rank there, best rank first.
+    let mut merged: BTreeMap<&str, Vec<(usize, usize)>> = BTreeMap::new();
+    for (index, &(_, list)) in dictionaries.iter().enumerate() {
+        for (i, word) in list.split(',').enumerate() {
+            let ranks = merged.entry(word).or_default();
+            // a word listed twice keeps its best rank
+            if !ranks.iter().any(|&(other, _)| other == index) {
+                ranks.push((index, i + 1));
+            }
+        }
+    }
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "static DICTIONARY: &[(&str, &[(DictionaryType, u32)])] = &["
+    )
+    .unwrap();
+    for (word, mut ranks) in merged {
+        // ties go to the dictionary searched first
+        ranks.sort_by_key(|&(index, rank)| (rank, index));
+        let ranks = ranks
+            .iter()
+            .map(|&(index, rank)| format!("(DictionaryType::{}, {})", dictionaries[index].0, rank))
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(out, "    ({:?}, &[{}]),", word, ranks).unwrap();
+    }
+    writeln!(out, "];").unwrap();
+
+    fs::write(Path::new(&out_dir).join("dictionary.rs"), out).unwrap();
+
+    // Pairs of words with the dictionary they are common in, in the order they are ranked.
+    let pair_lists: &[(&str, &str)] = &[("Bigrams", words::BIGRAMS)];
+    fs::write(
+        Path::new(&out_dir).join("word_pairs.rs"),
+        word_pairs(pair_lists),
+    )
+    .unwrap();
+
+    if all_matchers {
+        fs::write(
+            Path::new(&out_dir).join("acronyms.rs"),
+            acronyms(words::PHRASES),
+        )
+        .unwrap();
+    }
+
+    if env::var_os("CARGO_FEATURE_EXTRA_KEYBOARDS").is_some() {
+        let out = keyboards(Path::new("keyboards"));
+        fs::write(Path::new(&out_dir).join("keyboards.rs"), out).unwrap();
+    }
+}
+
+/// Generates `WORD_PAIRS`, the pairs of words of each list, sorted by the two words written
+/// together, each with the length of the first word, the dictionary of its list and its rank
+/// there.
+fn word_pairs(lists: &[(&str, &str)]) -> String {
+    let mut pairs = BTreeMap::new();
+    for &(dictionary, list) in lists {
+        for (i, pair) in list.split(',').enumerate() {
+            let (first, second) = pair
+                .split_once(' ')
+                .filter(|(_, second)| !second.contains(' '))
+                .unwrap_or_else(|| panic!("{:?} is not two words separated by a space", pair));
+            // lists come in the order they are searched
+            pairs
+                .entry(format!("{}{}", first, second))
+                .or_insert((first.len(), dictionary, i + 1));
+        }
+    }
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "static WORD_PAIRS: &[(&str, (usize, DictionaryType, u32))] = &["
+    )
+    .unwrap();
+    for (words, (first_len, dictionary, rank)) in pairs {
+        writeln!(
+            out,
+            "    ({:?}, ({}, DictionaryType::{}, {})),",
+            words, first_len, dictionary, rank
+        )
+        .unwrap();
+    }
+    writeln!(out, "];").unwrap();
+    out
+}
+
+/// Generates `ACRONYMS`, the first letters of the words of each phrase, sorted, each with its
+/// phrase and its rank.
+fn acronyms(phrases: &str) -> String {
+    let mut acronyms = BTreeMap::new();
+    for (i, phrase) in phrases.split(',').enumerate() {
+        let acronym = phrase
+            .split(' ')
+            .map(|word| word.chars().next().unwrap())
+            .collect::<String>();
+        assert!(
+            acronym.chars().count() >= MIN_ACRONYM_LEN,
+            "{:?} has fewer than {} words",
+            phrase,
+            MIN_ACRONYM_LEN
+        );
+        acronyms.entry(acronym).or_insert((phrase, i + 1));
+    }
+
+    let mut out = String::new();
+    writeln!(out, "static ACRONYMS: &[(&str, (&str, u32))] = &[").unwrap();
+    for (acronym, (phrase, rank)) in acronyms {
+        writeln!(out, "    ({:?}, ({:?}, {})),", acronym, phrase, rank).unwrap();
+    }
+    writeln!(out, "];").unwrap();
+    out
+}
+
+/// Generates `KEYBOARDS`, the name of every keyboard file in `dir` with the neighbors of each
+/// of its keys, in the directions of the keypad graphs: left, upper left, up, upper right,
+/// right, lower right, down and lower left. See `keyboards/README.md` for the format.
+fn keyboards(dir: &Path) -> String {
+    let mut files = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+        .collect::<Vec<_>>();
+    files.sort();
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "static KEYBOARDS: &[(&str, &[(char, GridNeighbors)])] = &["
+    )
+    .unwrap();
+    for path in files {
+        let name = path.file_stem().unwrap().to_str().unwrap().to_string();
+        assert!(
+            name.chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'),
+            "{}: keyboard names may only have lowercase letters, digits and underscores",
+            path.display()
+        );
+        let text = fs::read_to_string(&path).unwrap();
+        let grid = text
+            .lines()
+            .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                line.split_whitespace()
+                    .map(|key| {
+                        assert!(
+                            key.chars().count() <= 2,
+                            "{}: {:?} is not a key and its shifted character",
+                            path.display(),
+                            key
+                        );
+                        Some(key).filter(|&key| key != "_")
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let at = |y: usize, x: usize| grid.get(y).and_then(|row| row.get(x)).copied().flatten();
+
+        let mut keys = BTreeMap::new();
+        for (y, row) in grid.iter().enumerate() {
+            for (x, key) in row.iter().enumerate() {
+                let key = match key {
+                    Some(key) => key,
+                    None => continue,
+                };
+                let neighbors = [
+                    x.checked_sub(1).and_then(|x| at(y, x)),
+                    y.checked_sub(1)
+                        .zip(x.checked_sub(1))
+                        .and_then(|(y, x)| at(y, x)),
+                    y.checked_sub(1).and_then(|y| at(y, x)),
+                    y.checked_sub(1).and_then(|y| at(y, x + 1)),
+                    at(y, x + 1),
+                    at(y + 1, x + 1),
+                    at(y + 1, x),
+                    x.checked_sub(1).and_then(|x| at(y + 1, x)),
+                ];
+                for c in key.chars() {
+                    let previous = keys.insert(c, neighbors);
+                    assert!(
+                        previous.is_none(),
+                        "{}: {:?} is on more than one key",
+                        path.display(),
+                        c
+                    );
+                }
+            }
+        }
+
+        writeln!(out, "    ({:?}, &[", name).unwrap();
+        for (c, neighbors) in keys {
+            writeln!(out, "        ({:?}, {:?}),", c, neighbors).unwrap();
+        }
+        writeln!(out, "    ]),").unwrap();
+    }
+    writeln!(out, "];").unwrap();
+    out
+}
+
+/// The first `max_rank` words of a comma separated list.
+fn truncate(list: &str, max_rank: usize) -> &str {
+    let end = list
+        .match_indices(',')
+        .nth(max_rank - 1)
+        .map_or(list.len(), |(i, _)| i);
+    &list[..end]
+}
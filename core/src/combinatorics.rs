@@ -0,0 +1,82 @@
+//! Counting the variations a pattern could take, saturating instead of overflowing.
+
+use core::cmp;
+
+/// The number of ways to choose `k` of `n` items, saturating at `u64::MAX`.
+///
+/// ```
+/// use zxcvbn_core::combinatorics::n_ck;
+///
+/// assert_eq!(n_ck(4, 2), 6);
+/// assert_eq!(n_ck(2, 4), 0);
+/// assert_eq!(n_ck(100, 50), u64::MAX);
+/// ```
+pub fn n_ck(n: usize, k: usize) -> u64 {
+    // http://blog.plover.com/math/choose.html
+    if k > n {
+        return 0;
+    }
+    // counted in u128, where the product of a count below u64::MAX and n can't overflow, and
+    // from the smaller side, along which the counts only grow, so the first count past
+    // u64::MAX means the result is too
+    let k = cmp::min(k, n - k) as u128;
+    let n = n as u128;
+    let mut r: u128 = 1;
+    for d in 1..=k {
+        r = r * (n - k + d) / d;
+        if r > u128::from(u64::MAX) {
+            return u64::MAX;
+        }
+    }
+    r as u64
+}
+
+/// The number of orders `n` items can come in, saturating at `u64::MAX`.
+///
+/// ```
+/// use zxcvbn_core::combinatorics::factorial;
+///
+/// assert_eq!(factorial(5), 120);
+/// assert_eq!(factorial(100), u64::MAX);
+/// ```
+pub fn factorial(n: usize) -> u64 {
+    // in u64 rather than usize, so it saturates at the same value on 32-bit targets such as
+    // WebAssembly
+    (1..=n as u64).fold(1, u64::saturating_mul)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_n_ck() {
+        let test_data = [
+            (0, 0, 1),
+            (1, 0, 1),
+            (5, 0, 1),
+            (0, 1, 0),
+            (0, 5, 0),
+            (2, 1, 2),
+            (4, 2, 6),
+            (33, 7, 4_272_048),
+            (100, 99, 100),
+            (66, 33, 7_219_428_434_016_265_740),
+            (67, 33, 14_226_520_737_620_288_370),
+            (68, 34, u64::MAX),
+            (100, 50, u64::MAX),
+        ];
+        for &(n, k, result) in &test_data {
+            assert_eq!(n_ck(n, k), result);
+        }
+    }
+
+    #[test]
+    fn test_factorial() {
+        assert_eq!(factorial(0), 1);
+        assert_eq!(factorial(1), 1);
+        assert_eq!(factorial(5), 120);
+        assert_eq!(factorial(20), 2_432_902_008_176_640_000);
+        assert_eq!(factorial(21), u64::MAX);
+    }
+}
@@ -0,0 +1,167 @@
+//! The guesses of the patterns that don't depend on any data: bruteforce, the case, l33t and
+//! shift variations of a token and walks on a keyboard graph.
+//!
+//! Matchers working with their own dictionaries and keyboards estimate their matches with
+//! these, then hand them to the [search](crate::search) as spans.
+
+use crate::combinatorics::n_ck;
+use crate::guesses::Guesses;
+
+/// Characters each bruteforced character is assumed to be one of.
+pub const BRUTEFORCE_CARDINALITY: u64 = 10;
+/// The fewest guesses of a one-character match covering part of a password.
+pub const MIN_SUBMATCH_GUESSES_SINGLE_CHAR: u64 = 10;
+/// The fewest guesses of a longer match covering part of a password.
+pub const MIN_SUBMATCH_GUESSES_MULTI_CHAR: u64 = 50;
+
+/// The fewest guesses of a match of `token_len` characters in a password of `password_len`:
+/// 10 or 50 for parts of passwords, so that many short matches don't add up to fewer guesses
+/// than a few long ones, and 1 for whole passwords.
+///
+/// ```
+/// use zxcvbn_core::estimates::min_guesses;
+///
+/// assert_eq!(min_guesses(1, 9), 10);
+/// assert_eq!(min_guesses(8, 9), 50);
+/// assert_eq!(min_guesses(9, 9), 1);
+/// ```
+pub fn min_guesses(token_len: usize, password_len: usize) -> u64 {
+    if token_len < password_len {
+        if token_len == 1 {
+            MIN_SUBMATCH_GUESSES_SINGLE_CHAR
+        } else {
+            MIN_SUBMATCH_GUESSES_MULTI_CHAR
+        }
+    } else {
+        1
+    }
+}
+
+/// The guesses of bruteforcing `token_len` characters, a little more than the fewest
+/// guesses of any other match of as many, so that other matches over the same characters
+/// take precedence.
+///
+/// ```
+/// use zxcvbn_core::estimates::bruteforce;
+///
+/// assert_eq!(bruteforce(1), 11);
+/// assert_eq!(bruteforce(4), 10_000);
+/// ```
+pub fn bruteforce(token_len: usize) -> u64 {
+    let guesses = Guesses::new(BRUTEFORCE_CARDINALITY)
+        .pow(token_len.max(1) as u32)
+        .get();
+    let min_guesses = if token_len == 1 {
+        MIN_SUBMATCH_GUESSES_SINGLE_CHAR + 1
+    } else {
+        MIN_SUBMATCH_GUESSES_MULTI_CHAR + 1
+    };
+    guesses.max(min_guesses)
+}
+
+/// The ways to capitalize a token the way it is: 1 if it is lowercase, 2 if it is capitalized,
+/// has only its last letter uppercase or is all uppercase, and otherwise the ways to pick up
+/// to as many letters as the fewer of its uppercase and lowercase ones.
+///
+/// ```
+/// use zxcvbn_core::estimates::uppercase_variations;
+///
+/// assert_eq!(uppercase_variations("password"), 1);
+/// assert_eq!(uppercase_variations("Password"), 2);
+/// assert_eq!(uppercase_variations("PaSsword"), 36);
+/// ```
+pub fn uppercase_variations(token: &str) -> u64 {
+    if token.chars().all(|c| c.to_lowercase().eq([c])) {
+        return 1;
+    }
+    // a capitalized token is the most common capitalization scheme,
+    // so it only doubles the search space (uncapitalized + capitalized).
+    // allcaps and end-capitalized are common enough too, underestimate as 2x factor to be safe.
+    let upper = token.chars().filter(|c| c.is_uppercase()).count();
+    let lower = token.chars().filter(|c| c.is_lowercase()).count();
+    let first = token.chars().next().map_or(false, char::is_uppercase);
+    let last = token.chars().next_back().map_or(false, char::is_uppercase);
+    if ((first || last) && upper == 1) || lower == 0 {
+        return 2;
+    }
+    // otherwise calculate the number of ways to capitalize U+L uppercase+lowercase letters
+    // with U uppercase letters or less. or, if there's more uppercase than lower (for eg. PASSwORD),
+    // the number of ways to lowercase U+L letters with L lowercase letters or less.
+    partial_variations(upper, lower)
+}
+
+/// The ways to substitute a character `subbed` times and leave it `unsubbed` times, for one
+/// l33t substitution: 2 if it is substituted everywhere or nowhere, otherwise the ways to pick
+/// up to as many as the fewer of the two.
+///
+/// ```
+/// use zxcvbn_core::estimates::substitution_variations;
+///
+/// // "p4ssword"
+/// assert_eq!(substitution_variations(1, 0), 2);
+/// // "aa44a"
+/// assert_eq!(substitution_variations(2, 3), 15);
+/// ```
+pub fn substitution_variations(subbed: usize, unsubbed: usize) -> u64 {
+    if subbed == 0 || unsubbed == 0 {
+        // for this sub, password is either fully subbed (444) or fully unsubbed (aaa)
+        // treat that as doubling the space (attacker needs to try fully subbed chars in addition to
+        // unsubbed.)
+        2
+    } else {
+        // this case is similar to capitalization:
+        // with aa44a, U = 3, S = 2, attacker needs to try unsubbed + one sub + two subs
+        partial_variations(subbed, unsubbed)
+    }
+}
+
+/// The ways to type `shifted_count` of `len` keys with shift held: 2 if all of them are,
+/// otherwise the ways to pick up to as many keys as the fewer of the shifted and unshifted
+/// ones.
+///
+/// ```
+/// use zxcvbn_core::estimates::shift_variations;
+///
+/// assert_eq!(shift_variations(4, 4), 2);
+/// assert_eq!(shift_variations(1, 4), 4);
+/// ```
+pub fn shift_variations(shifted_count: usize, len: usize) -> u64 {
+    let unshifted_count = len - shifted_count;
+    if unshifted_count == 0 {
+        2
+    } else {
+        partial_variations(shifted_count, unshifted_count)
+    }
+}
+
+/// The ways to pick between 1 and the fewer of `a` and `b` of `a + b` items.
+fn partial_variations(a: usize, b: usize) -> u64 {
+    (1..=a.min(b))
+        .map(|i| n_ck(a + b, i))
+        .sum::<Guesses>()
+        .get()
+}
+
+/// The guesses of a walk of `len` keys with `turns` turns or less on a keyboard graph with
+/// `starting_positions` keys of `average_degree` neighbors on average, before shifted keys
+/// are accounted for with [`shift_variations`]: every walk of up to `len` keys with up to
+/// `turns` turns.
+///
+/// ```
+/// use zxcvbn_core::estimates::spatial;
+///
+/// // "qwerty" on QWERTY
+/// assert_eq!(spatial(6, 1, 94, 4), 1880);
+/// ```
+pub fn spatial(len: usize, turns: usize, starting_positions: u64, average_degree: u64) -> u64 {
+    let mut guesses = Guesses::new(0);
+    for i in 2..=len {
+        let possible_turns = turns.min(i - 1);
+        for j in 1..=possible_turns {
+            guesses += Guesses::new(n_ck(i - 1, j - 1))
+                * starting_positions
+                * Guesses::new(average_degree).pow(j as u32);
+        }
+    }
+    guesses.get()
+}
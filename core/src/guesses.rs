@@ -0,0 +1,114 @@
+//! A number of guesses that saturates at `u64::MAX` instead of overflowing.
+//!
+//! The guesses of a pattern are a product of the variations it could take. For a long token
+//! each of them can be huge, and so can their product. Every operation on [`Guesses`]
+//! saturates, so the estimate of a long password is as many guesses as can be counted.
+//! It never wraps around to a few guesses or panics.
+//!
+//! ```
+//! use zxcvbn_core::guesses::Guesses;
+//!
+//! assert_eq!((Guesses::new(6) * 7 + 1).get(), 43);
+//! assert_eq!(Guesses::new(u64::MAX / 2) * 3 + 1, Guesses::MAX);
+//! assert_eq!(Guesses::new(26).pow(100), Guesses::MAX);
+//! ```
+
+use core::iter::{Product, Sum};
+use core::ops::{Add, AddAssign, Mul, MulAssign};
+
+/// A number of guesses, saturating at [`Guesses::MAX`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Guesses(u64);
+
+impl Guesses {
+    /// The most guesses that can be counted.
+    pub const MAX: Guesses = Guesses(u64::MAX);
+
+    /// The number `guesses`.
+    pub const fn new(guesses: u64) -> Self {
+        Guesses(guesses)
+    }
+
+    /// The number of guesses.
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+
+    /// The guesses raised to the power `exp`.
+    pub const fn pow(self, exp: u32) -> Self {
+        Guesses(self.0.saturating_pow(exp))
+    }
+}
+
+impl From<u64> for Guesses {
+    fn from(guesses: u64) -> Self {
+        Guesses(guesses)
+    }
+}
+
+impl From<Guesses> for u64 {
+    fn from(guesses: Guesses) -> Self {
+        guesses.0
+    }
+}
+
+impl<T: Into<Guesses>> Add<T> for Guesses {
+    type Output = Guesses;
+
+    fn add(self, rhs: T) -> Guesses {
+        Guesses(self.0.saturating_add(rhs.into().0))
+    }
+}
+
+impl<T: Into<Guesses>> AddAssign<T> for Guesses {
+    fn add_assign(&mut self, rhs: T) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Into<Guesses>> Mul<T> for Guesses {
+    type Output = Guesses;
+
+    fn mul(self, rhs: T) -> Guesses {
+        Guesses(self.0.saturating_mul(rhs.into().0))
+    }
+}
+
+impl<T: Into<Guesses>> MulAssign<T> for Guesses {
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T: Into<Guesses>> Sum<T> for Guesses {
+    fn sum<I: Iterator<Item = T>>(iter: I) -> Self {
+        iter.fold(Guesses(0), |sum, guesses| sum + guesses)
+    }
+}
+
+impl<T: Into<Guesses>> Product<T> for Guesses {
+    fn product<I: Iterator<Item = T>>(iter: I) -> Self {
+        iter.fold(Guesses(1), |product, guesses| product * guesses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saturating_ops() {
+        assert_eq!(Guesses::MAX + 1, Guesses::MAX);
+        assert_eq!(Guesses::MAX * Guesses::new(2), Guesses::MAX);
+        assert_eq!(Guesses::new(0) * u64::MAX, Guesses::new(0));
+        let mut guesses = Guesses::new(10);
+        guesses *= 10;
+        guesses += 5;
+        assert_eq!(guesses.get(), 105);
+        assert_eq!([u64::MAX, 1].into_iter().sum::<Guesses>(), Guesses::MAX);
+        assert_eq!(
+            (1..=3).map(Guesses::new).product::<Guesses>(),
+            Guesses::new(6)
+        );
+    }
+}
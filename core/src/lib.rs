@@ -0,0 +1,11 @@
+#![doc = include_str!("../README.md")]
+#![no_std]
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+
+extern crate alloc;
+
+pub mod combinatorics;
+pub mod estimates;
+pub mod guesses;
+pub mod search;
@@ -0,0 +1,557 @@
+//! The search for the sequence of matches covering a password with the fewest guesses.
+//!
+//! A sequence of `l` matches takes `l! * product of guesses + penalty^(l - 1)` guesses: the
+//! factorial for the orders its patterns could come in, the additive term for the attacker
+//! trying every shorter sequence first. The additive term can follow another curve with
+//! [`SequencePenalty`]. Parts of the password no match covers are
+//! bruteforced, and the search keeps the best sequence of each length ending at each
+//! character.
+
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::combinatorics::factorial;
+use crate::guesses::Guesses;
+
+/// The guesses sequences of many matches are penalized with before moving on to sequences
+/// of one more match.
+pub const MIN_GUESSES_BEFORE_GROWING_SEQUENCE: u64 = 10_000;
+
+/// A part of the password a match covers, from character `i` to character `j` inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    /// Index of the first character.
+    pub i: usize,
+    /// Index of the last character.
+    pub j: usize,
+    /// The guesses of the match.
+    pub guesses: u64,
+}
+
+/// The additive term of the guesses of a sequence, by its number of matches.
+#[derive(Clone)]
+pub enum SequencePenalty {
+    /// `base^(l - 1)` for a sequence of `l` matches.
+    Exponential(u64),
+    /// The terms of sequences of one, two, three... matches. Longer sequences take the last
+    /// one, and none take any if it's empty.
+    Table(Vec<u64>),
+    /// The term a closure gives the number of matches, see [`SequencePenalty::function`].
+    Function(Arc<dyn Fn(usize) -> u64 + Send + Sync>),
+}
+
+impl SequencePenalty {
+    /// The term `penalty` gives the number of matches, which may capture the constants it
+    /// needs.
+    ///
+    /// ```
+    /// use zxcvbn_core::search::SequencePenalty;
+    ///
+    /// let step = 1_000;
+    /// let linear = SequencePenalty::function(move |len| step * len as u64);
+    /// assert_eq!(linear.penalty(3).get(), 3_000);
+    /// ```
+    pub fn function(penalty: impl Fn(usize) -> u64 + Send + Sync + 'static) -> Self {
+        SequencePenalty::Function(Arc::new(penalty))
+    }
+
+    /// The additive term of a sequence of `len` matches, at least one match long.
+    pub fn penalty(&self, len: usize) -> Guesses {
+        match self {
+            SequencePenalty::Exponential(base) => {
+                (1..len).fold(Guesses::new(1), |acc, _| acc * *base)
+            }
+            SequencePenalty::Table(table) => {
+                let penalty = table.get(len - 1).or_else(|| table.last());
+                Guesses::new(penalty.copied().unwrap_or(0))
+            }
+            SequencePenalty::Function(penalty) => Guesses::new(penalty(len)),
+        }
+    }
+}
+
+impl PartialEq for SequencePenalty {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (SequencePenalty::Exponential(a), SequencePenalty::Exponential(b)) => a == b,
+            (SequencePenalty::Table(a), SequencePenalty::Table(b)) => a == b,
+            // closures can't be compared, so only clones of the same one are equal
+            (SequencePenalty::Function(a), SequencePenalty::Function(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for SequencePenalty {}
+
+impl fmt::Debug for SequencePenalty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SequencePenalty::Exponential(base) => f.debug_tuple("Exponential").field(base).finish(),
+            SequencePenalty::Table(table) => f.debug_tuple("Table").field(table).finish(),
+            SequencePenalty::Function(_) => f.write_str("Function(..)"),
+        }
+    }
+}
+
+impl Default for SequencePenalty {
+    fn default() -> Self {
+        SequencePenalty::Exponential(MIN_GUESSES_BEFORE_GROWING_SEQUENCE)
+    }
+}
+
+/// How the guesses of a sequence follow from those of its matches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequenceParams {
+    /// Whether the guesses are multiplied by the factorial of the number of matches.
+    pub match_orderings: bool,
+    /// Whether to leave out the additive term.
+    pub exclude_additive: bool,
+    /// The additive term.
+    pub penalty: SequencePenalty,
+}
+
+impl Default for SequenceParams {
+    fn default() -> Self {
+        SequenceParams {
+            match_orderings: true,
+            exclude_additive: false,
+            penalty: SequencePenalty::default(),
+        }
+    }
+}
+
+impl SequenceParams {
+    /// The guesses of a sequence of `len` matches whose guesses multiply to `pi`.
+    pub fn guesses(&self, len: usize, pi: u64) -> u64 {
+        let mut guesses = if self.match_orderings {
+            Guesses::new(factorial(len)) * pi
+        } else {
+            Guesses::new(pi)
+        };
+        if !self.exclude_additive {
+            guesses += self.penalty.penalty(len);
+        }
+        guesses.get()
+    }
+}
+
+/// A match of a sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// The candidate at this index.
+    Candidate(usize),
+    /// Bruteforcing the characters from `i` to `j` inclusive.
+    Bruteforce {
+        /// Index of the first character.
+        i: usize,
+        /// Index of the last character.
+        j: usize,
+    },
+}
+
+/// The best sequence of a length ending at a character, as far as the search went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Best {
+    /// The last match of the sequence. The matches before it are those of the best
+    /// sequence one match shorter ending right before it.
+    pub last: Step,
+    /// Index of the first character of the last match.
+    pub i: usize,
+    /// The guesses of the last match.
+    pub last_guesses: u64,
+    /// The product of the guesses of the matches of the sequence.
+    pub pi: u64,
+    /// The guesses of the sequence.
+    pub guesses: u64,
+}
+
+/// The outcome of [`search`].
+///
+/// Its tables can be kept and handed to [`search_into`] to search again without allocating
+/// them anew.
+#[derive(Debug, Clone, Default)]
+pub struct Search {
+    /// `best[j]` holds the best sequences ending at character `j` by their length, if no
+    /// shorter sequence ending there takes fewer guesses. Only the first `n` are in use.
+    best: Vec<Vec<(usize, Best)>>,
+    /// The number of characters of the password searched last.
+    n: usize,
+    /// The lengths of the sequences a match may extend, kept to reuse its allocation.
+    lens: Vec<usize>,
+}
+
+/// Finds the sequence of `candidates` and bruteforced parts covering a password of `n`
+/// characters with the fewest guesses.
+///
+/// `candidates` must be sorted by `j`, and of those ending at the same character by `i`,
+/// which breaks ties between equally good sequences. `bruteforce_starts[j]` is the first
+/// character a bruteforced part ending at `j` may start at, and `bruteforce` gives the
+/// guesses of bruteforcing the characters from `i` to `j`.
+pub fn search<B>(
+    n: usize,
+    candidates: &[Span],
+    bruteforce_starts: &[usize],
+    bruteforce: B,
+    params: &SequenceParams,
+) -> Search
+where
+    B: FnMut(usize, usize) -> u64,
+{
+    let mut found = Search::default();
+    search_into(
+        &mut found,
+        n,
+        candidates,
+        bruteforce_starts,
+        bruteforce,
+        params,
+    );
+    found
+}
+
+/// Like [`search`], but reuses the tables of an earlier search, so that searching passwords
+/// no longer than those searched before allocates nothing.
+pub fn search_into<B>(
+    found: &mut Search,
+    n: usize,
+    candidates: &[Span],
+    bruteforce_starts: &[usize],
+    mut bruteforce: B,
+    params: &SequenceParams,
+) where
+    B: FnMut(usize, usize) -> u64,
+{
+    let Search { best, n: len, lens } = found;
+    *len = n;
+    if best.len() < n {
+        best.resize_with(n, Vec::new);
+    }
+    let best = &mut best[..n];
+    best.iter_mut().for_each(Vec::clear);
+
+    /// Considers whether a sequence of `len` matches ending with `last` is better than the
+    /// sequences found before, keeping it if so.
+    fn update(
+        best: &mut [Vec<(usize, Best)>],
+        last: Step,
+        span: Span,
+        len: usize,
+        params: &SequenceParams,
+    ) {
+        let mut pi = Guesses::new(span.guesses);
+        if len > 1 {
+            // the product of the sequence one match shorter, ending right before this match
+            pi *= get(&best[span.i - 1], len - 1).pi;
+        }
+        let pi = pi.get();
+        let guesses = params.guesses(len, pi);
+        // skip the sequence if one of as many matches or fewer ending here fares at least as well
+        if best[span.j]
+            .iter()
+            .any(|&(competing_len, competing)| competing_len <= len && competing.guesses <= guesses)
+        {
+            return;
+        }
+        let new = Best {
+            last,
+            i: span.i,
+            last_guesses: span.guesses,
+            pi,
+            guesses,
+        };
+        match best[span.j].binary_search_by_key(&len, |&(len, _)| len) {
+            Ok(index) => best[span.j][index].1 = new,
+            Err(index) => best[span.j].insert(index, (len, new)),
+        }
+    }
+
+    debug_assert!(candidates
+        .windows(2)
+        .all(|pair| (pair[0].j, pair[0].i) <= (pair[1].j, pair[1].i)));
+    let mut candidates = candidates.iter().enumerate().peekable();
+    for (k, &start) in bruteforce_starts.iter().enumerate().take(n) {
+        while let Some((index, &span)) = candidates.next_if(|(_, span)| span.j == k) {
+            let step = Step::Candidate(index);
+            if span.i > 0 {
+                lens.clear();
+                lens.extend(best[span.i - 1].iter().map(|&(len, _)| len));
+                for &len in lens.iter() {
+                    update(best, step, span, len + 1, params);
+                }
+            } else {
+                update(best, step, span, 1, params);
+            }
+        }
+
+        if start == 0 {
+            // a single bruteforced part spanning the whole prefix
+            let span = Span {
+                i: 0,
+                j: k,
+                guesses: bruteforce(0, k),
+            };
+            update(best, Step::Bruteforce { i: 0, j: k }, span, 1, params);
+        }
+        for i in start.max(1)..=k {
+            let span = Span {
+                i,
+                j: k,
+                guesses: bruteforce(i, k),
+            };
+            lens.clear();
+            lens.extend(
+                best[i - 1]
+                    .iter()
+                    // a sequence with two adjacent bruteforced parts is never the best: one
+                    // part spanning both takes as many guesses in a shorter sequence.
+                    .filter(|(_, last)| !matches!(last.last, Step::Bruteforce { .. }))
+                    .map(|&(len, _)| len),
+            );
+            for &len in lens.iter() {
+                update(best, Step::Bruteforce { i, j: k }, span, len + 1, params);
+            }
+        }
+    }
+}
+
+/// The best sequence of `len` matches among `best`, which must hold one.
+fn get(best: &[(usize, Best)], len: usize) -> &Best {
+    let index = best
+        .binary_search_by_key(&len, |&(len, _)| len)
+        .expect("a sequence one match shorter");
+    &best[index].1
+}
+
+impl PartialEq for Search {
+    fn eq(&self, other: &Self) -> bool {
+        self.best[..self.n] == other.best[..other.n]
+    }
+}
+
+impl Eq for Search {}
+
+impl Search {
+    /// The guesses of the best sequence, 1 for an empty password.
+    pub fn guesses(&self) -> u64 {
+        self.best_len()
+            .map_or(1, |len| get(&self.best[self.n - 1], len).guesses)
+    }
+
+    /// The matches of the best sequence, in order.
+    pub fn sequence(&self) -> Vec<Step> {
+        let mut sequence = Vec::new();
+        self.sequence_into(&mut sequence);
+        sequence
+    }
+
+    /// Replaces the contents of `sequence` with the matches of the best sequence, in order, so
+    /// that it allocates nothing once it has held a sequence as long.
+    pub fn sequence_into(&self, sequence: &mut Vec<Step>) {
+        sequence.clear();
+        let mut len = match self.best_len() {
+            Some(len) => len,
+            None => return,
+        };
+        let mut j = self.n - 1;
+        loop {
+            let Best { last, i, .. } = *get(&self.best[j], len);
+            sequence.push(last);
+            if i == 0 {
+                break;
+            }
+            j = i - 1;
+            len -= 1;
+        }
+        sequence.reverse();
+    }
+
+    /// The best sequences the search kept, by the character they end at and their length.
+    pub fn best(&self) -> impl Iterator<Item = (usize, usize, &Best)> {
+        self.best[..self.n]
+            .iter()
+            .enumerate()
+            .flat_map(|(j, best)| best.iter().map(move |(len, best)| (j, *len, best)))
+    }
+
+    /// The length of the best sequence covering the whole password, the shortest on ties.
+    fn best_len(&self) -> Option<usize> {
+        let mut found: Option<(usize, u64)> = None;
+        for &(len, best) in self.best[..self.n].last()? {
+            if found.map_or(true, |(_, guesses)| best.guesses < guesses) {
+                found = Some((len, best.guesses));
+            }
+        }
+        found.map(|(len, _)| len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn bruteforce(i: usize, j: usize) -> u64 {
+        10u64.saturating_pow((j - i + 1) as u32)
+    }
+
+    fn without_additive() -> SequenceParams {
+        SequenceParams {
+            exclude_additive: true,
+            ..SequenceParams::default()
+        }
+    }
+
+    #[test]
+    fn test_sequence_guesses() {
+        let params = SequenceParams::default();
+        assert_eq!(params.guesses(1, 5), 6);
+        assert_eq!(params.guesses(3, 5), 3 * 2 * 5 + 10_000 * 10_000);
+        assert_eq!(without_additive().guesses(3, 5), 30);
+        let params = SequenceParams {
+            match_orderings: false,
+            penalty: SequencePenalty::Exponential(10),
+            ..SequenceParams::default()
+        };
+        assert_eq!(params.guesses(4, 5), 5 + 1_000);
+    }
+
+    #[test]
+    fn test_sequence_penalties() {
+        let table = SequencePenalty::Table(vec![1, 100, 500]);
+        assert_eq!(table.penalty(2).get(), 100);
+        assert_eq!(table.penalty(7).get(), 500);
+        assert_eq!(SequencePenalty::Table(vec![]).penalty(3).get(), 0);
+        let step = 1_000;
+        let linear = SequencePenalty::function(move |len| step * len as u64);
+        assert_eq!(linear.penalty(3).get(), 3_000);
+        assert_eq!(linear.clone(), linear);
+        assert_ne!(
+            SequencePenalty::function(move |len| step * len as u64),
+            linear
+        );
+        assert_eq!(SequencePenalty::Exponential(10).penalty(1).get(), 1);
+        assert_eq!(
+            SequencePenalty::Exponential(u64::MAX).penalty(3),
+            Guesses::MAX
+        );
+    }
+
+    #[test]
+    fn test_search_bruteforces_without_candidates() {
+        let found = search(10, &[], &[0; 10], bruteforce, &without_additive());
+        assert_eq!(found.sequence(), [Step::Bruteforce { i: 0, j: 9 }]);
+        assert_eq!(found.guesses(), 10_000_000_000);
+    }
+
+    #[test]
+    fn test_search_covers_an_infix() {
+        let candidates = [Span {
+            i: 1,
+            j: 8,
+            guesses: 1,
+        }];
+        let found = search(10, &candidates, &[0; 10], bruteforce, &without_additive());
+        assert_eq!(
+            found.sequence(),
+            [
+                Step::Bruteforce { i: 0, j: 0 },
+                Step::Candidate(0),
+                Step::Bruteforce { i: 9, j: 9 }
+            ]
+        );
+        assert_eq!(found.guesses(), 3 * 2 * 10 * 10);
+    }
+
+    #[test]
+    fn test_search_chooses_fewer_guesses() {
+        // one match over the whole password, or two matches over its halves
+        let candidates = [
+            Span {
+                i: 0,
+                j: 3,
+                guesses: 2,
+            },
+            Span {
+                i: 0,
+                j: 9,
+                guesses: 5,
+            },
+            Span {
+                i: 4,
+                j: 9,
+                guesses: 1,
+            },
+        ];
+        let found = search(10, &candidates, &[0; 10], bruteforce, &without_additive());
+        assert_eq!(found.sequence(), [Step::Candidate(0), Step::Candidate(2)]);
+        assert_eq!(found.guesses(), 4);
+        // the additive term makes the longer sequence worse
+        let found = search(
+            10,
+            &candidates,
+            &[0; 10],
+            bruteforce,
+            &SequenceParams::default(),
+        );
+        assert_eq!(found.sequence(), [Step::Candidate(1)]);
+        assert_eq!(found.guesses(), 6);
+        assert!(found
+            .best()
+            .any(|(j, len, best)| j == 3 && len == 1 && best.guesses == 3));
+    }
+
+    #[test]
+    fn test_search_respects_bruteforce_starts() {
+        // the third character can't be bruteforced, only matched
+        let candidates = [Span {
+            i: 2,
+            j: 2,
+            guesses: 1_000,
+        }];
+        let starts = [0, 0, 3, 3];
+        let found = search(4, &candidates, &starts, bruteforce, &without_additive());
+        assert_eq!(
+            found.sequence(),
+            [
+                Step::Bruteforce { i: 0, j: 1 },
+                Step::Candidate(0),
+                Step::Bruteforce { i: 3, j: 3 }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_into_reuses_tables() {
+        let candidates = [Span {
+            i: 1,
+            j: 8,
+            guesses: 1,
+        }];
+        let mut found = Search::default();
+        search_into(
+            &mut found,
+            10,
+            &candidates,
+            &[0; 10],
+            bruteforce,
+            &without_additive(),
+        );
+        // a shorter password searched with the tables of a longer one
+        search_into(&mut found, 4, &[], &[0; 4], bruteforce, &without_additive());
+        assert_eq!(
+            found,
+            search(4, &[], &[0; 4], bruteforce, &without_additive())
+        );
+        assert_eq!(found.sequence(), [Step::Bruteforce { i: 0, j: 3 }]);
+        assert!(found.best().all(|(j, _, _)| j < 4));
+    }
+
+    #[test]
+    fn test_search_of_empty_password() {
+        let found = search(0, &[], &[], bruteforce, &SequenceParams::default());
+        assert!(found.sequence().is_empty());
+        assert_eq!(found.guesses(), 1);
+    }
+}
@@ -1,1683 +1,1820 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-lazy_static! {
-    pub static ref QWERTY: HashMap<char, Vec<Option<&'static str>>> = {
-        let mut table = HashMap::with_capacity(94);
-        table.insert(
-            '!',
-            vec![Some("`~"), None, None, Some("2@"), Some("qQ"), None],
-        );
-        table.insert(
-            '"',
-            vec![Some(";:"), Some("[{"), Some("]}"), None, None, Some("/?")],
-        );
-        table.insert(
-            '#',
-            vec![Some("2@"), None, None, Some("4$"), Some("eE"), Some("wW")],
-        );
-        table.insert(
-            '$',
-            vec![Some("3#"), None, None, Some("5%"), Some("rR"), Some("eE")],
-        );
-        table.insert(
-            '%',
-            vec![Some("4$"), None, None, Some("6^"), Some("tT"), Some("rR")],
-        );
-        table.insert(
-            '&',
-            vec![Some("6^"), None, None, Some("8*"), Some("uU"), Some("yY")],
-        );
-        table.insert(
-            '\'',
-            vec![Some(";:"), Some("[{"), Some("]}"), None, None, Some("/?")],
-        );
-        table.insert(
-            '(',
-            vec![Some("8*"), None, None, Some("0)"), Some("oO"), Some("iI")],
-        );
-        table.insert(
-            ')',
-            vec![Some("9("), None, None, Some("-_"), Some("pP"), Some("oO")],
-        );
-        table.insert(
-            '*',
-            vec![Some("7&"), None, None, Some("9("), Some("iI"), Some("uU")],
-        );
-        table.insert(
-            '+',
-            vec![Some("-_"), None, None, None, Some("]}"), Some("[{")],
-        );
-        table.insert(
-            ',',
-            vec![Some("mM"), Some("kK"), Some("lL"), Some(".>"), None, None],
-        );
-        table.insert(
-            '-',
-            vec![Some("0)"), None, None, Some("=+"), Some("[{"), Some("pP")],
-        );
-        table.insert(
-            '.',
-            vec![Some(",<"), Some("lL"), Some(";:"), Some("/?"), None, None],
-        );
-        table.insert(
-            '/',
-            vec![Some(".>"), Some(";:"), Some("'\""), None, None, None],
-        );
-        table.insert(
-            '0',
-            vec![Some("9("), None, None, Some("-_"), Some("pP"), Some("oO")],
-        );
-        table.insert(
-            '1',
-            vec![Some("`~"), None, None, Some("2@"), Some("qQ"), None],
-        );
-        table.insert(
-            '2',
-            vec![Some("1!"), None, None, Some("3#"), Some("wW"), Some("qQ")],
-        );
-        table.insert(
-            '3',
-            vec![Some("2@"), None, None, Some("4$"), Some("eE"), Some("wW")],
-        );
-        table.insert(
-            '4',
-            vec![Some("3#"), None, None, Some("5%"), Some("rR"), Some("eE")],
-        );
-        table.insert(
-            '5',
-            vec![Some("4$"), None, None, Some("6^"), Some("tT"), Some("rR")],
-        );
-        table.insert(
-            '6',
-            vec![Some("5%"), None, None, Some("7&"), Some("yY"), Some("tT")],
-        );
-        table.insert(
-            '7',
-            vec![Some("6^"), None, None, Some("8*"), Some("uU"), Some("yY")],
-        );
-        table.insert(
-            '8',
-            vec![Some("7&"), None, None, Some("9("), Some("iI"), Some("uU")],
-        );
-        table.insert(
-            '9',
-            vec![Some("8*"), None, None, Some("0)"), Some("oO"), Some("iI")],
-        );
-        table.insert(
-            ':',
-            vec![
-                Some("lL"),
-                Some("pP"),
-                Some("[{"),
-                Some("'\""),
-                Some("/?"),
-                Some(".>"),
-            ],
-        );
-        table.insert(
-            ';',
-            vec![
-                Some("lL"),
-                Some("pP"),
-                Some("[{"),
-                Some("'\""),
-                Some("/?"),
-                Some(".>"),
-            ],
-        );
-        table.insert(
-            '<',
-            vec![Some("mM"), Some("kK"), Some("lL"), Some(".>"), None, None],
-        );
-        table.insert(
-            '=',
-            vec![Some("-_"), None, None, None, Some("]}"), Some("[{")],
-        );
-        table.insert(
-            '>',
-            vec![Some(",<"), Some("lL"), Some(";:"), Some("/?"), None, None],
-        );
-        table.insert(
-            '?',
-            vec![Some(".>"), Some(";:"), Some("'\""), None, None, None],
-        );
-        table.insert(
-            '@',
-            vec![Some("1!"), None, None, Some("3#"), Some("wW"), Some("qQ")],
-        );
-        table.insert(
-            'A',
-            vec![None, Some("qQ"), Some("wW"), Some("sS"), Some("zZ"), None],
-        );
-        table.insert(
-            'B',
-            vec![Some("vV"), Some("gG"), Some("hH"), Some("nN"), None, None],
-        );
-        table.insert(
-            'C',
-            vec![Some("xX"), Some("dD"), Some("fF"), Some("vV"), None, None],
-        );
-        table.insert(
-            'D',
-            vec![
-                Some("sS"),
-                Some("eE"),
-                Some("rR"),
-                Some("fF"),
-                Some("cC"),
-                Some("xX"),
-            ],
-        );
-        table.insert(
-            'E',
-            vec![
-                Some("wW"),
-                Some("3#"),
-                Some("4$"),
-                Some("rR"),
-                Some("dD"),
-                Some("sS"),
-            ],
-        );
-        table.insert(
-            'F',
-            vec![
-                Some("dD"),
-                Some("rR"),
-                Some("tT"),
-                Some("gG"),
-                Some("vV"),
-                Some("cC"),
-            ],
-        );
-        table.insert(
-            'G',
-            vec![
-                Some("fF"),
-                Some("tT"),
-                Some("yY"),
-                Some("hH"),
-                Some("bB"),
-                Some("vV"),
-            ],
-        );
-        table.insert(
-            'H',
-            vec![
-                Some("gG"),
-                Some("yY"),
-                Some("uU"),
-                Some("jJ"),
-                Some("nN"),
-                Some("bB"),
-            ],
-        );
-        table.insert(
-            'I',
-            vec![
-                Some("uU"),
-                Some("8*"),
-                Some("9("),
-                Some("oO"),
-                Some("kK"),
-                Some("jJ"),
-            ],
-        );
-        table.insert(
-            'J',
-            vec![
-                Some("hH"),
-                Some("uU"),
-                Some("iI"),
-                Some("kK"),
-                Some("mM"),
-                Some("nN"),
-            ],
-        );
-        table.insert(
-            'K',
-            vec![
-                Some("jJ"),
-                Some("iI"),
-                Some("oO"),
-                Some("lL"),
-                Some(",<"),
-                Some("mM"),
-            ],
-        );
-        table.insert(
-            'L',
-            vec![
-                Some("kK"),
-                Some("oO"),
-                Some("pP"),
-                Some(";:"),
-                Some(".>"),
-                Some(",<"),
-            ],
-        );
-        table.insert(
-            'M',
-            vec![Some("nN"), Some("jJ"), Some("kK"), Some(",<"), None, None],
-        );
-        table.insert(
-            'N',
-            vec![Some("bB"), Some("hH"), Some("jJ"), Some("mM"), None, None],
-        );
-        table.insert(
-            'O',
-            vec![
-                Some("iI"),
-                Some("9("),
-                Some("0)"),
-                Some("pP"),
-                Some("lL"),
-                Some("kK"),
-            ],
-        );
-        table.insert(
-            'P',
-            vec![
-                Some("oO"),
-                Some("0)"),
-                Some("-_"),
-                Some("[{"),
-                Some(";:"),
-                Some("lL"),
-            ],
-        );
-        table.insert(
-            'Q',
-            vec![None, Some("1!"), Some("2@"), Some("wW"), Some("aA"), None],
-        );
-        table.insert(
-            'R',
-            vec![
-                Some("eE"),
-                Some("4$"),
-                Some("5%"),
-                Some("tT"),
-                Some("fF"),
-                Some("dD"),
-            ],
-        );
-        table.insert(
-            'S',
-            vec![
-                Some("aA"),
-                Some("wW"),
-                Some("eE"),
-                Some("dD"),
-                Some("xX"),
-                Some("zZ"),
-            ],
-        );
-        table.insert(
-            'T',
-            vec![
-                Some("rR"),
-                Some("5%"),
-                Some("6^"),
-                Some("yY"),
-                Some("gG"),
-                Some("fF"),
-            ],
-        );
-        table.insert(
-            'U',
-            vec![
-                Some("yY"),
-                Some("7&"),
-                Some("8*"),
-                Some("iI"),
-                Some("jJ"),
-                Some("hH"),
-            ],
-        );
-        table.insert(
-            'V',
-            vec![Some("cC"), Some("fF"), Some("gG"), Some("bB"), None, None],
-        );
-        table.insert(
-            'W',
-            vec![
-                Some("qQ"),
-                Some("2@"),
-                Some("3#"),
-                Some("eE"),
-                Some("sS"),
-                Some("aA"),
-            ],
-        );
-        table.insert(
-            'X',
-            vec![Some("zZ"), Some("sS"), Some("dD"), Some("cC"), None, None],
-        );
-        table.insert(
-            'Y',
-            vec![
-                Some("tT"),
-                Some("6^"),
-                Some("7&"),
-                Some("uU"),
-                Some("hH"),
-                Some("gG"),
-            ],
-        );
-        table.insert(
-            'Z',
-            vec![None, Some("aA"), Some("sS"), Some("xX"), None, None],
-        );
-        table.insert(
-            '[',
-            vec![
-                Some("pP"),
-                Some("-_"),
-                Some("=+"),
-                Some("]}"),
-                Some("'\""),
-                Some(";:"),
-            ],
-        );
-        table.insert('\\', vec![Some("]}"), None, None, None, None, None]);
-        table.insert(
-            ']',
-            vec![Some("[{"), Some("=+"), None, Some("\\|"), None, Some("'\"")],
-        );
-        table.insert(
-            '^',
-            vec![Some("5%"), None, None, Some("7&"), Some("yY"), Some("tT")],
-        );
-        table.insert(
-            '_',
-            vec![Some("0)"), None, None, Some("=+"), Some("[{"), Some("pP")],
-        );
-        table.insert('`', vec![None, None, None, Some("1!"), None, None]);
-        table.insert(
-            'a',
-            vec![None, Some("qQ"), Some("wW"), Some("sS"), Some("zZ"), None],
-        );
-        table.insert(
-            'b',
-            vec![Some("vV"), Some("gG"), Some("hH"), Some("nN"), None, None],
-        );
-        table.insert(
-            'c',
-            vec![Some("xX"), Some("dD"), Some("fF"), Some("vV"), None, None],
-        );
-        table.insert(
-            'd',
-            vec![
-                Some("sS"),
-                Some("eE"),
-                Some("rR"),
-                Some("fF"),
-                Some("cC"),
-                Some("xX"),
-            ],
-        );
-        table.insert(
-            'e',
-            vec![
-                Some("wW"),
-                Some("3#"),
-                Some("4$"),
-                Some("rR"),
-                Some("dD"),
-                Some("sS"),
-            ],
-        );
-        table.insert(
-            'f',
-            vec![
-                Some("dD"),
-                Some("rR"),
-                Some("tT"),
-                Some("gG"),
-                Some("vV"),
-                Some("cC"),
-            ],
-        );
-        table.insert(
-            'g',
-            vec![
-                Some("fF"),
-                Some("tT"),
-                Some("yY"),
-                Some("hH"),
-                Some("bB"),
-                Some("vV"),
-            ],
-        );
-        table.insert(
-            'h',
-            vec![
-                Some("gG"),
-                Some("yY"),
-                Some("uU"),
-                Some("jJ"),
-                Some("nN"),
-                Some("bB"),
-            ],
-        );
-        table.insert(
-            'i',
-            vec![
-                Some("uU"),
-                Some("8*"),
-                Some("9("),
-                Some("oO"),
-                Some("kK"),
-                Some("jJ"),
-            ],
-        );
-        table.insert(
-            'j',
-            vec![
-                Some("hH"),
-                Some("uU"),
-                Some("iI"),
-                Some("kK"),
-                Some("mM"),
-                Some("nN"),
-            ],
-        );
-        table.insert(
-            'k',
-            vec![
-                Some("jJ"),
-                Some("iI"),
-                Some("oO"),
-                Some("lL"),
-                Some(",<"),
-                Some("mM"),
-            ],
-        );
-        table.insert(
-            'l',
-            vec![
-                Some("kK"),
-                Some("oO"),
-                Some("pP"),
-                Some(";:"),
-                Some(".>"),
-                Some(",<"),
-            ],
-        );
-        table.insert(
-            'm',
-            vec![Some("nN"), Some("jJ"), Some("kK"), Some(",<"), None, None],
-        );
-        table.insert(
-            'n',
-            vec![Some("bB"), Some("hH"), Some("jJ"), Some("mM"), None, None],
-        );
-        table.insert(
-            'o',
-            vec![
-                Some("iI"),
-                Some("9("),
-                Some("0)"),
-                Some("pP"),
-                Some("lL"),
-                Some("kK"),
-            ],
-        );
-        table.insert(
-            'p',
-            vec![
-                Some("oO"),
-                Some("0)"),
-                Some("-_"),
-                Some("[{"),
-                Some(";:"),
-                Some("lL"),
-            ],
-        );
-        table.insert(
-            'q',
-            vec![None, Some("1!"), Some("2@"), Some("wW"), Some("aA"), None],
-        );
-        table.insert(
-            'r',
-            vec![
-                Some("eE"),
-                Some("4$"),
-                Some("5%"),
-                Some("tT"),
-                Some("fF"),
-                Some("dD"),
-            ],
-        );
-        table.insert(
-            's',
-            vec![
-                Some("aA"),
-                Some("wW"),
-                Some("eE"),
-                Some("dD"),
-                Some("xX"),
-                Some("zZ"),
-            ],
-        );
-        table.insert(
-            't',
-            vec![
-                Some("rR"),
-                Some("5%"),
-                Some("6^"),
-                Some("yY"),
-                Some("gG"),
-                Some("fF"),
-            ],
-        );
-        table.insert(
-            'u',
-            vec![
-                Some("yY"),
-                Some("7&"),
-                Some("8*"),
-                Some("iI"),
-                Some("jJ"),
-                Some("hH"),
-            ],
-        );
-        table.insert(
-            'v',
-            vec![Some("cC"), Some("fF"), Some("gG"), Some("bB"), None, None],
-        );
-        table.insert(
-            'w',
-            vec![
-                Some("qQ"),
-                Some("2@"),
-                Some("3#"),
-                Some("eE"),
-                Some("sS"),
-                Some("aA"),
-            ],
-        );
-        table.insert(
-            'x',
-            vec![Some("zZ"), Some("sS"), Some("dD"), Some("cC"), None, None],
-        );
-        table.insert(
-            'y',
-            vec![
-                Some("tT"),
-                Some("6^"),
-                Some("7&"),
-                Some("uU"),
-                Some("hH"),
-                Some("gG"),
-            ],
-        );
-        table.insert(
-            'z',
-            vec![None, Some("aA"), Some("sS"), Some("xX"), None, None],
-        );
-        table.insert(
-            '{',
-            vec![
-                Some("pP"),
-                Some("-_"),
-                Some("=+"),
-                Some("]}"),
-                Some("'\""),
-                Some(";:"),
-            ],
-        );
-        table.insert('|', vec![Some("]}"), None, None, None, None, None]);
-        table.insert(
-            '}',
-            vec![Some("[{"), Some("=+"), None, Some("\\|"), None, Some("'\"")],
-        );
-        table.insert('~', vec![None, None, None, Some("1!"), None, None]);
-        table
-    };
-    pub static ref DVORAK: HashMap<char, Vec<Option<&'static str>>> = {
-        let mut table = HashMap::with_capacity(94);
-        table.insert(
-            '!',
-            vec![Some("`~"), None, None, Some("2@"), Some("'\""), None],
-        );
-        table.insert(
-            '"',
-            vec![None, Some("1!"), Some("2@"), Some(",<"), Some("aA"), None],
-        );
-        table.insert(
-            '#',
-            vec![Some("2@"), None, None, Some("4$"), Some(".>"), Some(",<")],
-        );
-        table.insert(
-            '$',
-            vec![Some("3#"), None, None, Some("5%"), Some("pP"), Some(".>")],
-        );
-        table.insert(
-            '%',
-            vec![Some("4$"), None, None, Some("6^"), Some("yY"), Some("pP")],
-        );
-        table.insert(
-            '&',
-            vec![Some("6^"), None, None, Some("8*"), Some("gG"), Some("fF")],
-        );
-        table.insert(
-            '\'',
-            vec![None, Some("1!"), Some("2@"), Some(",<"), Some("aA"), None],
-        );
-        table.insert(
-            '(',
-            vec![Some("8*"), None, None, Some("0)"), Some("rR"), Some("cC")],
-        );
-        table.insert(
-            ')',
-            vec![Some("9("), None, None, Some("[{"), Some("lL"), Some("rR")],
-        );
-        table.insert(
-            '*',
-            vec![Some("7&"), None, None, Some("9("), Some("cC"), Some("gG")],
-        );
-        table.insert(
-            '+',
-            vec![Some("/?"), Some("]}"), None, Some("\\|"), None, Some("-_")],
-        );
-        table.insert(
-            ',',
-            vec![
-                Some("'\""),
-                Some("2@"),
-                Some("3#"),
-                Some(".>"),
-                Some("oO"),
-                Some("aA"),
-            ],
-        );
-        table.insert(
-            '-',
-            vec![Some("sS"), Some("/?"), Some("=+"), None, None, Some("zZ")],
-        );
-        table.insert(
-            '.',
-            vec![
-                Some(",<"),
-                Some("3#"),
-                Some("4$"),
-                Some("pP"),
-                Some("eE"),
-                Some("oO"),
-            ],
-        );
-        table.insert(
-            '/',
-            vec![
-                Some("lL"),
-                Some("[{"),
-                Some("]}"),
-                Some("=+"),
-                Some("-_"),
-                Some("sS"),
-            ],
-        );
-        table.insert(
-            '0',
-            vec![Some("9("), None, None, Some("[{"), Some("lL"), Some("rR")],
-        );
-        table.insert(
-            '1',
-            vec![Some("`~"), None, None, Some("2@"), Some("'\""), None],
-        );
-        table.insert(
-            '2',
-            vec![Some("1!"), None, None, Some("3#"), Some(",<"), Some("'\"")],
-        );
-        table.insert(
-            '3',
-            vec![Some("2@"), None, None, Some("4$"), Some(".>"), Some(",<")],
-        );
-        table.insert(
-            '4',
-            vec![Some("3#"), None, None, Some("5%"), Some("pP"), Some(".>")],
-        );
-        table.insert(
-            '5',
-            vec![Some("4$"), None, None, Some("6^"), Some("yY"), Some("pP")],
-        );
-        table.insert(
-            '6',
-            vec![Some("5%"), None, None, Some("7&"), Some("fF"), Some("yY")],
-        );
-        table.insert(
-            '7',
-            vec![Some("6^"), None, None, Some("8*"), Some("gG"), Some("fF")],
-        );
-        table.insert(
-            '8',
-            vec![Some("7&"), None, None, Some("9("), Some("cC"), Some("gG")],
-        );
-        table.insert(
-            '9',
-            vec![Some("8*"), None, None, Some("0)"), Some("rR"), Some("cC")],
-        );
-        table.insert(
-            ':',
-            vec![None, Some("aA"), Some("oO"), Some("qQ"), None, None],
-        );
-        table.insert(
-            ';',
-            vec![None, Some("aA"), Some("oO"), Some("qQ"), None, None],
-        );
-        table.insert(
-            '<',
-            vec![
-                Some("'\""),
-                Some("2@"),
-                Some("3#"),
-                Some(".>"),
-                Some("oO"),
-                Some("aA"),
-            ],
-        );
-        table.insert(
-            '=',
-            vec![Some("/?"), Some("]}"), None, Some("\\|"), None, Some("-_")],
-        );
-        table.insert(
-            '>',
-            vec![
-                Some(",<"),
-                Some("3#"),
-                Some("4$"),
-                Some("pP"),
-                Some("eE"),
-                Some("oO"),
-            ],
-        );
-        table.insert(
-            '?',
-            vec![
-                Some("lL"),
-                Some("[{"),
-                Some("]}"),
-                Some("=+"),
-                Some("-_"),
-                Some("sS"),
-            ],
-        );
-        table.insert(
-            '@',
-            vec![Some("1!"), None, None, Some("3#"), Some(",<"), Some("'\"")],
-        );
-        table.insert(
-            'A',
-            vec![None, Some("'\""), Some(",<"), Some("oO"), Some(";:"), None],
-        );
-        table.insert(
-            'B',
-            vec![Some("xX"), Some("dD"), Some("hH"), Some("mM"), None, None],
-        );
-        table.insert(
-            'C',
-            vec![
-                Some("gG"),
-                Some("8*"),
-                Some("9("),
-                Some("rR"),
-                Some("tT"),
-                Some("hH"),
-            ],
-        );
-        table.insert(
-            'D',
-            vec![
-                Some("iI"),
-                Some("fF"),
-                Some("gG"),
-                Some("hH"),
-                Some("bB"),
-                Some("xX"),
-            ],
-        );
-        table.insert(
-            'E',
-            vec![
-                Some("oO"),
-                Some(".>"),
-                Some("pP"),
-                Some("uU"),
-                Some("jJ"),
-                Some("qQ"),
-            ],
-        );
-        table.insert(
-            'F',
-            vec![
-                Some("yY"),
-                Some("6^"),
-                Some("7&"),
-                Some("gG"),
-                Some("dD"),
-                Some("iI"),
-            ],
-        );
-        table.insert(
-            'G',
-            vec![
-                Some("fF"),
-                Some("7&"),
-                Some("8*"),
-                Some("cC"),
-                Some("hH"),
-                Some("dD"),
-            ],
-        );
-        table.insert(
-            'H',
-            vec![
-                Some("dD"),
-                Some("gG"),
-                Some("cC"),
-                Some("tT"),
-                Some("mM"),
-                Some("bB"),
-            ],
-        );
-        table.insert(
-            'I',
-            vec![
-                Some("uU"),
-                Some("yY"),
-                Some("fF"),
-                Some("dD"),
-                Some("xX"),
-                Some("kK"),
-            ],
-        );
-        table.insert(
-            'J',
-            vec![Some("qQ"), Some("eE"), Some("uU"), Some("kK"), None, None],
-        );
-        table.insert(
-            'K',
-            vec![Some("jJ"), Some("uU"), Some("iI"), Some("xX"), None, None],
-        );
-        table.insert(
-            'L',
-            vec![
-                Some("rR"),
-                Some("0)"),
-                Some("[{"),
-                Some("/?"),
-                Some("sS"),
-                Some("nN"),
-            ],
-        );
-        table.insert(
-            'M',
-            vec![Some("bB"), Some("hH"), Some("tT"), Some("wW"), None, None],
-        );
-        table.insert(
-            'N',
-            vec![
-                Some("tT"),
-                Some("rR"),
-                Some("lL"),
-                Some("sS"),
-                Some("vV"),
-                Some("wW"),
-            ],
-        );
-        table.insert(
-            'O',
-            vec![
-                Some("aA"),
-                Some(",<"),
-                Some(".>"),
-                Some("eE"),
-                Some("qQ"),
-                Some(";:"),
-            ],
-        );
-        table.insert(
-            'P',
-            vec![
-                Some(".>"),
-                Some("4$"),
-                Some("5%"),
-                Some("yY"),
-                Some("uU"),
-                Some("eE"),
-            ],
-        );
-        table.insert(
-            'Q',
-            vec![Some(";:"), Some("oO"), Some("eE"), Some("jJ"), None, None],
-        );
-        table.insert(
-            'R',
-            vec![
-                Some("cC"),
-                Some("9("),
-                Some("0)"),
-                Some("lL"),
-                Some("nN"),
-                Some("tT"),
-            ],
-        );
-        table.insert(
-            'S',
-            vec![
-                Some("nN"),
-                Some("lL"),
-                Some("/?"),
-                Some("-_"),
-                Some("zZ"),
-                Some("vV"),
-            ],
-        );
-        table.insert(
-            'T',
-            vec![
-                Some("hH"),
-                Some("cC"),
-                Some("rR"),
-                Some("nN"),
-                Some("wW"),
-                Some("mM"),
-            ],
-        );
-        table.insert(
-            'U',
-            vec![
-                Some("eE"),
-                Some("pP"),
-                Some("yY"),
-                Some("iI"),
-                Some("kK"),
-                Some("jJ"),
-            ],
-        );
-        table.insert(
-            'V',
-            vec![Some("wW"), Some("nN"), Some("sS"), Some("zZ"), None, None],
-        );
-        table.insert(
-            'W',
-            vec![Some("mM"), Some("tT"), Some("nN"), Some("vV"), None, None],
-        );
-        table.insert(
-            'X',
-            vec![Some("kK"), Some("iI"), Some("dD"), Some("bB"), None, None],
-        );
-        table.insert(
-            'Y',
-            vec![
-                Some("pP"),
-                Some("5%"),
-                Some("6^"),
-                Some("fF"),
-                Some("iI"),
-                Some("uU"),
-            ],
-        );
-        table.insert(
-            'Z',
-            vec![Some("vV"), Some("sS"), Some("-_"), None, None, None],
-        );
-        table.insert(
-            '[',
-            vec![Some("0)"), None, None, Some("]}"), Some("/?"), Some("lL")],
-        );
-        table.insert('\\', vec![Some("=+"), None, None, None, None, None]);
-        table.insert(
-            ']',
-            vec![Some("[{"), None, None, None, Some("=+"), Some("/?")],
-        );
-        table.insert(
-            '^',
-            vec![Some("5%"), None, None, Some("7&"), Some("fF"), Some("yY")],
-        );
-        table.insert(
-            '_',
-            vec![Some("sS"), Some("/?"), Some("=+"), None, None, Some("zZ")],
-        );
-        table.insert('`', vec![None, None, None, Some("1!"), None, None]);
-        table.insert(
-            'a',
-            vec![None, Some("'\""), Some(",<"), Some("oO"), Some(";:"), None],
-        );
-        table.insert(
-            'b',
-            vec![Some("xX"), Some("dD"), Some("hH"), Some("mM"), None, None],
-        );
-        table.insert(
-            'c',
-            vec![
-                Some("gG"),
-                Some("8*"),
-                Some("9("),
-                Some("rR"),
-                Some("tT"),
-                Some("hH"),
-            ],
-        );
-        table.insert(
-            'd',
-            vec![
-                Some("iI"),
-                Some("fF"),
-                Some("gG"),
-                Some("hH"),
-                Some("bB"),
-                Some("xX"),
-            ],
-        );
-        table.insert(
-            'e',
-            vec![
-                Some("oO"),
-                Some(".>"),
-                Some("pP"),
-                Some("uU"),
-                Some("jJ"),
-                Some("qQ"),
-            ],
-        );
-        table.insert(
-            'f',
-            vec![
-                Some("yY"),
-                Some("6^"),
-                Some("7&"),
-                Some("gG"),
-                Some("dD"),
-                Some("iI"),
-            ],
-        );
-        table.insert(
-            'g',
-            vec![
-                Some("fF"),
-                Some("7&"),
-                Some("8*"),
-                Some("cC"),
-                Some("hH"),
-                Some("dD"),
-            ],
-        );
-        table.insert(
-            'h',
-            vec![
-                Some("dD"),
-                Some("gG"),
-                Some("cC"),
-                Some("tT"),
-                Some("mM"),
-                Some("bB"),
-            ],
-        );
-        table.insert(
-            'i',
-            vec![
-                Some("uU"),
-                Some("yY"),
-                Some("fF"),
-                Some("dD"),
-                Some("xX"),
-                Some("kK"),
-            ],
-        );
-        table.insert(
-            'j',
-            vec![Some("qQ"), Some("eE"), Some("uU"), Some("kK"), None, None],
-        );
-        table.insert(
-            'k',
-            vec![Some("jJ"), Some("uU"), Some("iI"), Some("xX"), None, None],
-        );
-        table.insert(
-            'l',
-            vec![
-                Some("rR"),
-                Some("0)"),
-                Some("[{"),
-                Some("/?"),
-                Some("sS"),
-                Some("nN"),
-            ],
-        );
-        table.insert(
-            'm',
-            vec![Some("bB"), Some("hH"), Some("tT"), Some("wW"), None, None],
-        );
-        table.insert(
-            'n',
-            vec![
-                Some("tT"),
-                Some("rR"),
-                Some("lL"),
-                Some("sS"),
-                Some("vV"),
-                Some("wW"),
-            ],
-        );
-        table.insert(
-            'o',
-            vec![
-                Some("aA"),
-                Some(",<"),
-                Some(".>"),
-                Some("eE"),
-                Some("qQ"),
-                Some(";:"),
-            ],
-        );
-        table.insert(
-            'p',
-            vec![
-                Some(".>"),
-                Some("4$"),
-                Some("5%"),
-                Some("yY"),
-                Some("uU"),
-                Some("eE"),
-            ],
-        );
-        table.insert(
-            'q',
-            vec![Some(";:"), Some("oO"), Some("eE"), Some("jJ"), None, None],
-        );
-        table.insert(
-            'r',
-            vec![
-                Some("cC"),
-                Some("9("),
-                Some("0)"),
-                Some("lL"),
-                Some("nN"),
-                Some("tT"),
-            ],
-        );
-        table.insert(
-            's',
-            vec![
-                Some("nN"),
-                Some("lL"),
-                Some("/?"),
-                Some("-_"),
-                Some("zZ"),
-                Some("vV"),
-            ],
-        );
-        table.insert(
-            't',
-            vec![
-                Some("hH"),
-                Some("cC"),
-                Some("rR"),
-                Some("nN"),
-                Some("wW"),
-                Some("mM"),
-            ],
-        );
-        table.insert(
-            'u',
-            vec![
-                Some("eE"),
-                Some("pP"),
-                Some("yY"),
-                Some("iI"),
-                Some("kK"),
-                Some("jJ"),
-            ],
-        );
-        table.insert(
-            'v',
-            vec![Some("wW"), Some("nN"), Some("sS"), Some("zZ"), None, None],
-        );
-        table.insert(
-            'w',
-            vec![Some("mM"), Some("tT"), Some("nN"), Some("vV"), None, None],
-        );
-        table.insert(
-            'x',
-            vec![Some("kK"), Some("iI"), Some("dD"), Some("bB"), None, None],
-        );
-        table.insert(
-            'y',
-            vec![
-                Some("pP"),
-                Some("5%"),
-                Some("6^"),
-                Some("fF"),
-                Some("iI"),
-                Some("uU"),
-            ],
-        );
-        table.insert(
-            'z',
-            vec![Some("vV"), Some("sS"), Some("-_"), None, None, None],
-        );
-        table.insert(
-            '{',
-            vec![Some("0)"), None, None, Some("]}"), Some("/?"), Some("lL")],
-        );
-        table.insert('|', vec![Some("=+"), None, None, None, None, None]);
-        table.insert(
-            '}',
-            vec![Some("[{"), None, None, None, Some("=+"), Some("/?")],
-        );
-        table.insert('~', vec![None, None, None, Some("1!"), None, None]);
-        table
-    };
-    pub static ref KEYPAD: HashMap<char, Vec<Option<&'static str>>> = {
-        let mut table = HashMap::with_capacity(15);
-        table.insert(
-            '*',
-            vec![
-                Some("/"),
-                None,
-                None,
-                None,
-                Some("-"),
-                Some("+"),
-                Some("9"),
-                Some("8"),
-            ],
-        );
-        table.insert(
-            '+',
-            vec![
-                Some("9"),
-                Some("*"),
-                Some("-"),
-                None,
-                None,
-                None,
-                None,
-                Some("6"),
-            ],
-        );
-        table.insert(
-            '-',
-            vec![
-                Some("*"),
-                None,
-                None,
-                None,
-                None,
-                None,
-                Some("+"),
-                Some("9"),
-            ],
-        );
-        table.insert(
-            '.',
-            vec![
-                Some("0"),
-                Some("2"),
-                Some("3"),
-                None,
-                None,
-                None,
-                None,
-                None,
-            ],
-        );
-        table.insert(
-            '/',
-            vec![
-                None,
-                None,
-                None,
-                None,
-                Some("*"),
-                Some("9"),
-                Some("8"),
-                Some("7"),
-            ],
-        );
-        table.insert(
-            '0',
-            vec![
-                None,
-                Some("1"),
-                Some("2"),
-                Some("3"),
-                Some("."),
-                None,
-                None,
-                None,
-            ],
-        );
-        table.insert(
-            '1',
-            vec![
-                None,
-                None,
-                Some("4"),
-                Some("5"),
-                Some("2"),
-                Some("0"),
-                None,
-                None,
-            ],
-        );
-        table.insert(
-            '2',
-            vec![
-                Some("1"),
-                Some("4"),
-                Some("5"),
-                Some("6"),
-                Some("3"),
-                Some("."),
-                Some("0"),
-                None,
-            ],
-        );
-        table.insert(
-            '3',
-            vec![
-                Some("2"),
-                Some("5"),
-                Some("6"),
-                None,
-                None,
-                None,
-                Some("."),
-                Some("0"),
-            ],
-        );
-        table.insert(
-            '4',
-            vec![
-                None,
-                None,
-                Some("7"),
-                Some("8"),
-                Some("5"),
-                Some("2"),
-                Some("1"),
-                None,
-            ],
-        );
-        table.insert(
-            '5',
-            vec![
-                Some("4"),
-                Some("7"),
-                Some("8"),
-                Some("9"),
-                Some("6"),
-                Some("3"),
-                Some("2"),
-                Some("1"),
-            ],
-        );
-        table.insert(
-            '6',
-            vec![
-                Some("5"),
-                Some("8"),
-                Some("9"),
-                Some("+"),
-                None,
-                None,
-                Some("3"),
-                Some("2"),
-            ],
-        );
-        table.insert(
-            '7',
-            vec![
-                None,
-                None,
-                None,
-                Some("/"),
-                Some("8"),
-                Some("5"),
-                Some("4"),
-                None,
-            ],
-        );
-        table.insert(
-            '8',
-            vec![
-                Some("7"),
-                None,
-                Some("/"),
-                Some("*"),
-                Some("9"),
-                Some("6"),
-                Some("5"),
-                Some("4"),
-            ],
-        );
-        table.insert(
-            '9',
-            vec![
-                Some("8"),
-                Some("/"),
-                Some("*"),
-                Some("-"),
-                Some("+"),
-                None,
-                Some("6"),
-                Some("5"),
-            ],
-        );
-        table
-    };
-    pub static ref MAC_KEYPAD: HashMap<char, Vec<Option<&'static str>>> = {
-        let mut table = HashMap::with_capacity(16);
-        table.insert(
-            '*',
-            vec![
-                Some("/"),
-                None,
-                None,
-                None,
-                None,
-                None,
-                Some("-"),
-                Some("9"),
-            ],
-        );
-        table.insert(
-            '+',
-            vec![
-                Some("6"),
-                Some("9"),
-                Some("-"),
-                None,
-                None,
-                None,
-                None,
-                Some("3"),
-            ],
-        );
-        table.insert(
-            '-',
-            vec![
-                Some("9"),
-                Some("/"),
-                Some("*"),
-                None,
-                None,
-                None,
-                Some("+"),
-                Some("6"),
-            ],
-        );
-        table.insert(
-            '.',
-            vec![
-                Some("0"),
-                Some("2"),
-                Some("3"),
-                None,
-                None,
-                None,
-                None,
-                None,
-            ],
-        );
-        table.insert(
-            '/',
-            vec![
-                Some("="),
-                None,
-                None,
-                None,
-                Some("*"),
-                Some("-"),
-                Some("9"),
-                Some("8"),
-            ],
-        );
-        table.insert(
-            '0',
-            vec![
-                None,
-                Some("1"),
-                Some("2"),
-                Some("3"),
-                Some("."),
-                None,
-                None,
-                None,
-            ],
-        );
-        table.insert(
-            '1',
-            vec![
-                None,
-                None,
-                Some("4"),
-                Some("5"),
-                Some("2"),
-                Some("0"),
-                None,
-                None,
-            ],
-        );
-        table.insert(
-            '2',
-            vec![
-                Some("1"),
-                Some("4"),
-                Some("5"),
-                Some("6"),
-                Some("3"),
-                Some("."),
-                Some("0"),
-                None,
-            ],
-        );
-        table.insert(
-            '3',
-            vec![
-                Some("2"),
-                Some("5"),
-                Some("6"),
-                Some("+"),
-                None,
-                None,
-                Some("."),
-                Some("0"),
-            ],
-        );
-        table.insert(
-            '4',
-            vec![
-                None,
-                None,
-                Some("7"),
-                Some("8"),
-                Some("5"),
-                Some("2"),
-                Some("1"),
-                None,
-            ],
-        );
-        table.insert(
-            '5',
-            vec![
-                Some("4"),
-                Some("7"),
-                Some("8"),
-                Some("9"),
-                Some("6"),
-                Some("3"),
-                Some("2"),
-                Some("1"),
-            ],
-        );
-        table.insert(
-            '6',
-            vec![
-                Some("5"),
-                Some("8"),
-                Some("9"),
-                Some("-"),
-                Some("+"),
-                None,
-                Some("3"),
-                Some("2"),
-            ],
-        );
-        table.insert(
-            '7',
-            vec![
-                None,
-                None,
-                None,
-                Some("="),
-                Some("8"),
-                Some("5"),
-                Some("4"),
-                None,
-            ],
-        );
-        table.insert(
-            '8',
-            vec![
-                Some("7"),
-                None,
-                Some("="),
-                Some("/"),
-                Some("9"),
-                Some("6"),
-                Some("5"),
-                Some("4"),
-            ],
-        );
-        table.insert(
-            '9',
-            vec![
-                Some("8"),
-                Some("="),
-                Some("/"),
-                Some("*"),
-                Some("-"),
-                Some("+"),
-                Some("6"),
-                Some("5"),
-            ],
-        );
-        table.insert(
-            '=',
-            vec![
-                None,
-                None,
-                None,
-                None,
-                Some("/"),
-                Some("9"),
-                Some("8"),
-                Some("7"),
-            ],
-        );
-        table
+use once_cell::sync::Lazy;
+
+pub static QWERTY: Lazy<HashMap<char, Vec<Option<&'static str>>>> = Lazy::new(|| {
+    let mut table = HashMap::with_capacity(94);
+    table.insert(
+        '!',
+        vec![Some("`~"), None, None, Some("2@"), Some("qQ"), None],
+    );
+    table.insert(
+        '"',
+        vec![Some(";:"), Some("[{"), Some("]}"), None, None, Some("/?")],
+    );
+    table.insert(
+        '#',
+        vec![Some("2@"), None, None, Some("4$"), Some("eE"), Some("wW")],
+    );
+    table.insert(
+        '$',
+        vec![Some("3#"), None, None, Some("5%"), Some("rR"), Some("eE")],
+    );
+    table.insert(
+        '%',
+        vec![Some("4$"), None, None, Some("6^"), Some("tT"), Some("rR")],
+    );
+    table.insert(
+        '&',
+        vec![Some("6^"), None, None, Some("8*"), Some("uU"), Some("yY")],
+    );
+    table.insert(
+        '\'',
+        vec![Some(";:"), Some("[{"), Some("]}"), None, None, Some("/?")],
+    );
+    table.insert(
+        '(',
+        vec![Some("8*"), None, None, Some("0)"), Some("oO"), Some("iI")],
+    );
+    table.insert(
+        ')',
+        vec![Some("9("), None, None, Some("-_"), Some("pP"), Some("oO")],
+    );
+    table.insert(
+        '*',
+        vec![Some("7&"), None, None, Some("9("), Some("iI"), Some("uU")],
+    );
+    table.insert(
+        '+',
+        vec![Some("-_"), None, None, None, Some("]}"), Some("[{")],
+    );
+    table.insert(
+        ',',
+        vec![Some("mM"), Some("kK"), Some("lL"), Some(".>"), None, None],
+    );
+    table.insert(
+        '-',
+        vec![Some("0)"), None, None, Some("=+"), Some("[{"), Some("pP")],
+    );
+    table.insert(
+        '.',
+        vec![Some(",<"), Some("lL"), Some(";:"), Some("/?"), None, None],
+    );
+    table.insert(
+        '/',
+        vec![Some(".>"), Some(";:"), Some("'\""), None, None, None],
+    );
+    table.insert(
+        '0',
+        vec![Some("9("), None, None, Some("-_"), Some("pP"), Some("oO")],
+    );
+    table.insert(
+        '1',
+        vec![Some("`~"), None, None, Some("2@"), Some("qQ"), None],
+    );
+    table.insert(
+        '2',
+        vec![Some("1!"), None, None, Some("3#"), Some("wW"), Some("qQ")],
+    );
+    table.insert(
+        '3',
+        vec![Some("2@"), None, None, Some("4$"), Some("eE"), Some("wW")],
+    );
+    table.insert(
+        '4',
+        vec![Some("3#"), None, None, Some("5%"), Some("rR"), Some("eE")],
+    );
+    table.insert(
+        '5',
+        vec![Some("4$"), None, None, Some("6^"), Some("tT"), Some("rR")],
+    );
+    table.insert(
+        '6',
+        vec![Some("5%"), None, None, Some("7&"), Some("yY"), Some("tT")],
+    );
+    table.insert(
+        '7',
+        vec![Some("6^"), None, None, Some("8*"), Some("uU"), Some("yY")],
+    );
+    table.insert(
+        '8',
+        vec![Some("7&"), None, None, Some("9("), Some("iI"), Some("uU")],
+    );
+    table.insert(
+        '9',
+        vec![Some("8*"), None, None, Some("0)"), Some("oO"), Some("iI")],
+    );
+    table.insert(
+        ':',
+        vec![
+            Some("lL"),
+            Some("pP"),
+            Some("[{"),
+            Some("'\""),
+            Some("/?"),
+            Some(".>"),
+        ],
+    );
+    table.insert(
+        ';',
+        vec![
+            Some("lL"),
+            Some("pP"),
+            Some("[{"),
+            Some("'\""),
+            Some("/?"),
+            Some(".>"),
+        ],
+    );
+    table.insert(
+        '<',
+        vec![Some("mM"), Some("kK"), Some("lL"), Some(".>"), None, None],
+    );
+    table.insert(
+        '=',
+        vec![Some("-_"), None, None, None, Some("]}"), Some("[{")],
+    );
+    table.insert(
+        '>',
+        vec![Some(",<"), Some("lL"), Some(";:"), Some("/?"), None, None],
+    );
+    table.insert(
+        '?',
+        vec![Some(".>"), Some(";:"), Some("'\""), None, None, None],
+    );
+    table.insert(
+        '@',
+        vec![Some("1!"), None, None, Some("3#"), Some("wW"), Some("qQ")],
+    );
+    table.insert(
+        'A',
+        vec![None, Some("qQ"), Some("wW"), Some("sS"), Some("zZ"), None],
+    );
+    table.insert(
+        'B',
+        vec![Some("vV"), Some("gG"), Some("hH"), Some("nN"), None, None],
+    );
+    table.insert(
+        'C',
+        vec![Some("xX"), Some("dD"), Some("fF"), Some("vV"), None, None],
+    );
+    table.insert(
+        'D',
+        vec![
+            Some("sS"),
+            Some("eE"),
+            Some("rR"),
+            Some("fF"),
+            Some("cC"),
+            Some("xX"),
+        ],
+    );
+    table.insert(
+        'E',
+        vec![
+            Some("wW"),
+            Some("3#"),
+            Some("4$"),
+            Some("rR"),
+            Some("dD"),
+            Some("sS"),
+        ],
+    );
+    table.insert(
+        'F',
+        vec![
+            Some("dD"),
+            Some("rR"),
+            Some("tT"),
+            Some("gG"),
+            Some("vV"),
+            Some("cC"),
+        ],
+    );
+    table.insert(
+        'G',
+        vec![
+            Some("fF"),
+            Some("tT"),
+            Some("yY"),
+            Some("hH"),
+            Some("bB"),
+            Some("vV"),
+        ],
+    );
+    table.insert(
+        'H',
+        vec![
+            Some("gG"),
+            Some("yY"),
+            Some("uU"),
+            Some("jJ"),
+            Some("nN"),
+            Some("bB"),
+        ],
+    );
+    table.insert(
+        'I',
+        vec![
+            Some("uU"),
+            Some("8*"),
+            Some("9("),
+            Some("oO"),
+            Some("kK"),
+            Some("jJ"),
+        ],
+    );
+    table.insert(
+        'J',
+        vec![
+            Some("hH"),
+            Some("uU"),
+            Some("iI"),
+            Some("kK"),
+            Some("mM"),
+            Some("nN"),
+        ],
+    );
+    table.insert(
+        'K',
+        vec![
+            Some("jJ"),
+            Some("iI"),
+            Some("oO"),
+            Some("lL"),
+            Some(",<"),
+            Some("mM"),
+        ],
+    );
+    table.insert(
+        'L',
+        vec![
+            Some("kK"),
+            Some("oO"),
+            Some("pP"),
+            Some(";:"),
+            Some(".>"),
+            Some(",<"),
+        ],
+    );
+    table.insert(
+        'M',
+        vec![Some("nN"), Some("jJ"), Some("kK"), Some(",<"), None, None],
+    );
+    table.insert(
+        'N',
+        vec![Some("bB"), Some("hH"), Some("jJ"), Some("mM"), None, None],
+    );
+    table.insert(
+        'O',
+        vec![
+            Some("iI"),
+            Some("9("),
+            Some("0)"),
+            Some("pP"),
+            Some("lL"),
+            Some("kK"),
+        ],
+    );
+    table.insert(
+        'P',
+        vec![
+            Some("oO"),
+            Some("0)"),
+            Some("-_"),
+            Some("[{"),
+            Some(";:"),
+            Some("lL"),
+        ],
+    );
+    table.insert(
+        'Q',
+        vec![None, Some("1!"), Some("2@"), Some("wW"), Some("aA"), None],
+    );
+    table.insert(
+        'R',
+        vec![
+            Some("eE"),
+            Some("4$"),
+            Some("5%"),
+            Some("tT"),
+            Some("fF"),
+            Some("dD"),
+        ],
+    );
+    table.insert(
+        'S',
+        vec![
+            Some("aA"),
+            Some("wW"),
+            Some("eE"),
+            Some("dD"),
+            Some("xX"),
+            Some("zZ"),
+        ],
+    );
+    table.insert(
+        'T',
+        vec![
+            Some("rR"),
+            Some("5%"),
+            Some("6^"),
+            Some("yY"),
+            Some("gG"),
+            Some("fF"),
+        ],
+    );
+    table.insert(
+        'U',
+        vec![
+            Some("yY"),
+            Some("7&"),
+            Some("8*"),
+            Some("iI"),
+            Some("jJ"),
+            Some("hH"),
+        ],
+    );
+    table.insert(
+        'V',
+        vec![Some("cC"), Some("fF"), Some("gG"), Some("bB"), None, None],
+    );
+    table.insert(
+        'W',
+        vec![
+            Some("qQ"),
+            Some("2@"),
+            Some("3#"),
+            Some("eE"),
+            Some("sS"),
+            Some("aA"),
+        ],
+    );
+    table.insert(
+        'X',
+        vec![Some("zZ"), Some("sS"), Some("dD"), Some("cC"), None, None],
+    );
+    table.insert(
+        'Y',
+        vec![
+            Some("tT"),
+            Some("6^"),
+            Some("7&"),
+            Some("uU"),
+            Some("hH"),
+            Some("gG"),
+        ],
+    );
+    table.insert(
+        'Z',
+        vec![None, Some("aA"), Some("sS"), Some("xX"), None, None],
+    );
+    table.insert(
+        '[',
+        vec![
+            Some("pP"),
+            Some("-_"),
+            Some("=+"),
+            Some("]}"),
+            Some("'\""),
+            Some(";:"),
+        ],
+    );
+    table.insert('\\', vec![Some("]}"), None, None, None, None, None]);
+    table.insert(
+        ']',
+        vec![Some("[{"), Some("=+"), None, Some("\\|"), None, Some("'\"")],
+    );
+    table.insert(
+        '^',
+        vec![Some("5%"), None, None, Some("7&"), Some("yY"), Some("tT")],
+    );
+    table.insert(
+        '_',
+        vec![Some("0)"), None, None, Some("=+"), Some("[{"), Some("pP")],
+    );
+    table.insert('`', vec![None, None, None, Some("1!"), None, None]);
+    table.insert(
+        'a',
+        vec![None, Some("qQ"), Some("wW"), Some("sS"), Some("zZ"), None],
+    );
+    table.insert(
+        'b',
+        vec![Some("vV"), Some("gG"), Some("hH"), Some("nN"), None, None],
+    );
+    table.insert(
+        'c',
+        vec![Some("xX"), Some("dD"), Some("fF"), Some("vV"), None, None],
+    );
+    table.insert(
+        'd',
+        vec![
+            Some("sS"),
+            Some("eE"),
+            Some("rR"),
+            Some("fF"),
+            Some("cC"),
+            Some("xX"),
+        ],
+    );
+    table.insert(
+        'e',
+        vec![
+            Some("wW"),
+            Some("3#"),
+            Some("4$"),
+            Some("rR"),
+            Some("dD"),
+            Some("sS"),
+        ],
+    );
+    table.insert(
+        'f',
+        vec![
+            Some("dD"),
+            Some("rR"),
+            Some("tT"),
+            Some("gG"),
+            Some("vV"),
+            Some("cC"),
+        ],
+    );
+    table.insert(
+        'g',
+        vec![
+            Some("fF"),
+            Some("tT"),
+            Some("yY"),
+            Some("hH"),
+            Some("bB"),
+            Some("vV"),
+        ],
+    );
+    table.insert(
+        'h',
+        vec![
+            Some("gG"),
+            Some("yY"),
+            Some("uU"),
+            Some("jJ"),
+            Some("nN"),
+            Some("bB"),
+        ],
+    );
+    table.insert(
+        'i',
+        vec![
+            Some("uU"),
+            Some("8*"),
+            Some("9("),
+            Some("oO"),
+            Some("kK"),
+            Some("jJ"),
+        ],
+    );
+    table.insert(
+        'j',
+        vec![
+            Some("hH"),
+            Some("uU"),
+            Some("iI"),
+            Some("kK"),
+            Some("mM"),
+            Some("nN"),
+        ],
+    );
+    table.insert(
+        'k',
+        vec![
+            Some("jJ"),
+            Some("iI"),
+            Some("oO"),
+            Some("lL"),
+            Some(",<"),
+            Some("mM"),
+        ],
+    );
+    table.insert(
+        'l',
+        vec![
+            Some("kK"),
+            Some("oO"),
+            Some("pP"),
+            Some(";:"),
+            Some(".>"),
+            Some(",<"),
+        ],
+    );
+    table.insert(
+        'm',
+        vec![Some("nN"), Some("jJ"), Some("kK"), Some(",<"), None, None],
+    );
+    table.insert(
+        'n',
+        vec![Some("bB"), Some("hH"), Some("jJ"), Some("mM"), None, None],
+    );
+    table.insert(
+        'o',
+        vec![
+            Some("iI"),
+            Some("9("),
+            Some("0)"),
+            Some("pP"),
+            Some("lL"),
+            Some("kK"),
+        ],
+    );
+    table.insert(
+        'p',
+        vec![
+            Some("oO"),
+            Some("0)"),
+            Some("-_"),
+            Some("[{"),
+            Some(";:"),
+            Some("lL"),
+        ],
+    );
+    table.insert(
+        'q',
+        vec![None, Some("1!"), Some("2@"), Some("wW"), Some("aA"), None],
+    );
+    table.insert(
+        'r',
+        vec![
+            Some("eE"),
+            Some("4$"),
+            Some("5%"),
+            Some("tT"),
+            Some("fF"),
+            Some("dD"),
+        ],
+    );
+    table.insert(
+        's',
+        vec![
+            Some("aA"),
+            Some("wW"),
+            Some("eE"),
+            Some("dD"),
+            Some("xX"),
+            Some("zZ"),
+        ],
+    );
+    table.insert(
+        't',
+        vec![
+            Some("rR"),
+            Some("5%"),
+            Some("6^"),
+            Some("yY"),
+            Some("gG"),
+            Some("fF"),
+        ],
+    );
+    table.insert(
+        'u',
+        vec![
+            Some("yY"),
+            Some("7&"),
+            Some("8*"),
+            Some("iI"),
+            Some("jJ"),
+            Some("hH"),
+        ],
+    );
+    table.insert(
+        'v',
+        vec![Some("cC"), Some("fF"), Some("gG"), Some("bB"), None, None],
+    );
+    table.insert(
+        'w',
+        vec![
+            Some("qQ"),
+            Some("2@"),
+            Some("3#"),
+            Some("eE"),
+            Some("sS"),
+            Some("aA"),
+        ],
+    );
+    table.insert(
+        'x',
+        vec![Some("zZ"), Some("sS"), Some("dD"), Some("cC"), None, None],
+    );
+    table.insert(
+        'y',
+        vec![
+            Some("tT"),
+            Some("6^"),
+            Some("7&"),
+            Some("uU"),
+            Some("hH"),
+            Some("gG"),
+        ],
+    );
+    table.insert(
+        'z',
+        vec![None, Some("aA"), Some("sS"), Some("xX"), None, None],
+    );
+    table.insert(
+        '{',
+        vec![
+            Some("pP"),
+            Some("-_"),
+            Some("=+"),
+            Some("]}"),
+            Some("'\""),
+            Some(";:"),
+        ],
+    );
+    table.insert('|', vec![Some("]}"), None, None, None, None, None]);
+    table.insert(
+        '}',
+        vec![Some("[{"), Some("=+"), None, Some("\\|"), None, Some("'\"")],
+    );
+    table.insert('~', vec![None, None, None, Some("1!"), None, None]);
+    table
+});
+pub static DVORAK: Lazy<HashMap<char, Vec<Option<&'static str>>>> = Lazy::new(|| {
+    let mut table = HashMap::with_capacity(94);
+    table.insert(
+        '!',
+        vec![Some("`~"), None, None, Some("2@"), Some("'\""), None],
+    );
+    table.insert(
+        '"',
+        vec![None, Some("1!"), Some("2@"), Some(",<"), Some("aA"), None],
+    );
+    table.insert(
+        '#',
+        vec![Some("2@"), None, None, Some("4$"), Some(".>"), Some(",<")],
+    );
+    table.insert(
+        '$',
+        vec![Some("3#"), None, None, Some("5%"), Some("pP"), Some(".>")],
+    );
+    table.insert(
+        '%',
+        vec![Some("4$"), None, None, Some("6^"), Some("yY"), Some("pP")],
+    );
+    table.insert(
+        '&',
+        vec![Some("6^"), None, None, Some("8*"), Some("gG"), Some("fF")],
+    );
+    table.insert(
+        '\'',
+        vec![None, Some("1!"), Some("2@"), Some(",<"), Some("aA"), None],
+    );
+    table.insert(
+        '(',
+        vec![Some("8*"), None, None, Some("0)"), Some("rR"), Some("cC")],
+    );
+    table.insert(
+        ')',
+        vec![Some("9("), None, None, Some("[{"), Some("lL"), Some("rR")],
+    );
+    table.insert(
+        '*',
+        vec![Some("7&"), None, None, Some("9("), Some("cC"), Some("gG")],
+    );
+    table.insert(
+        '+',
+        vec![Some("/?"), Some("]}"), None, Some("\\|"), None, Some("-_")],
+    );
+    table.insert(
+        ',',
+        vec![
+            Some("'\""),
+            Some("2@"),
+            Some("3#"),
+            Some(".>"),
+            Some("oO"),
+            Some("aA"),
+        ],
+    );
+    table.insert(
+        '-',
+        vec![Some("sS"), Some("/?"), Some("=+"), None, None, Some("zZ")],
+    );
+    table.insert(
+        '.',
+        vec![
+            Some(",<"),
+            Some("3#"),
+            Some("4$"),
+            Some("pP"),
+            Some("eE"),
+            Some("oO"),
+        ],
+    );
+    table.insert(
+        '/',
+        vec![
+            Some("lL"),
+            Some("[{"),
+            Some("]}"),
+            Some("=+"),
+            Some("-_"),
+            Some("sS"),
+        ],
+    );
+    table.insert(
+        '0',
+        vec![Some("9("), None, None, Some("[{"), Some("lL"), Some("rR")],
+    );
+    table.insert(
+        '1',
+        vec![Some("`~"), None, None, Some("2@"), Some("'\""), None],
+    );
+    table.insert(
+        '2',
+        vec![Some("1!"), None, None, Some("3#"), Some(",<"), Some("'\"")],
+    );
+    table.insert(
+        '3',
+        vec![Some("2@"), None, None, Some("4$"), Some(".>"), Some(",<")],
+    );
+    table.insert(
+        '4',
+        vec![Some("3#"), None, None, Some("5%"), Some("pP"), Some(".>")],
+    );
+    table.insert(
+        '5',
+        vec![Some("4$"), None, None, Some("6^"), Some("yY"), Some("pP")],
+    );
+    table.insert(
+        '6',
+        vec![Some("5%"), None, None, Some("7&"), Some("fF"), Some("yY")],
+    );
+    table.insert(
+        '7',
+        vec![Some("6^"), None, None, Some("8*"), Some("gG"), Some("fF")],
+    );
+    table.insert(
+        '8',
+        vec![Some("7&"), None, None, Some("9("), Some("cC"), Some("gG")],
+    );
+    table.insert(
+        '9',
+        vec![Some("8*"), None, None, Some("0)"), Some("rR"), Some("cC")],
+    );
+    table.insert(
+        ':',
+        vec![None, Some("aA"), Some("oO"), Some("qQ"), None, None],
+    );
+    table.insert(
+        ';',
+        vec![None, Some("aA"), Some("oO"), Some("qQ"), None, None],
+    );
+    table.insert(
+        '<',
+        vec![
+            Some("'\""),
+            Some("2@"),
+            Some("3#"),
+            Some(".>"),
+            Some("oO"),
+            Some("aA"),
+        ],
+    );
+    table.insert(
+        '=',
+        vec![Some("/?"), Some("]}"), None, Some("\\|"), None, Some("-_")],
+    );
+    table.insert(
+        '>',
+        vec![
+            Some(",<"),
+            Some("3#"),
+            Some("4$"),
+            Some("pP"),
+            Some("eE"),
+            Some("oO"),
+        ],
+    );
+    table.insert(
+        '?',
+        vec![
+            Some("lL"),
+            Some("[{"),
+            Some("]}"),
+            Some("=+"),
+            Some("-_"),
+            Some("sS"),
+        ],
+    );
+    table.insert(
+        '@',
+        vec![Some("1!"), None, None, Some("3#"), Some(",<"), Some("'\"")],
+    );
+    table.insert(
+        'A',
+        vec![None, Some("'\""), Some(",<"), Some("oO"), Some(";:"), None],
+    );
+    table.insert(
+        'B',
+        vec![Some("xX"), Some("dD"), Some("hH"), Some("mM"), None, None],
+    );
+    table.insert(
+        'C',
+        vec![
+            Some("gG"),
+            Some("8*"),
+            Some("9("),
+            Some("rR"),
+            Some("tT"),
+            Some("hH"),
+        ],
+    );
+    table.insert(
+        'D',
+        vec![
+            Some("iI"),
+            Some("fF"),
+            Some("gG"),
+            Some("hH"),
+            Some("bB"),
+            Some("xX"),
+        ],
+    );
+    table.insert(
+        'E',
+        vec![
+            Some("oO"),
+            Some(".>"),
+            Some("pP"),
+            Some("uU"),
+            Some("jJ"),
+            Some("qQ"),
+        ],
+    );
+    table.insert(
+        'F',
+        vec![
+            Some("yY"),
+            Some("6^"),
+            Some("7&"),
+            Some("gG"),
+            Some("dD"),
+            Some("iI"),
+        ],
+    );
+    table.insert(
+        'G',
+        vec![
+            Some("fF"),
+            Some("7&"),
+            Some("8*"),
+            Some("cC"),
+            Some("hH"),
+            Some("dD"),
+        ],
+    );
+    table.insert(
+        'H',
+        vec![
+            Some("dD"),
+            Some("gG"),
+            Some("cC"),
+            Some("tT"),
+            Some("mM"),
+            Some("bB"),
+        ],
+    );
+    table.insert(
+        'I',
+        vec![
+            Some("uU"),
+            Some("yY"),
+            Some("fF"),
+            Some("dD"),
+            Some("xX"),
+            Some("kK"),
+        ],
+    );
+    table.insert(
+        'J',
+        vec![Some("qQ"), Some("eE"), Some("uU"), Some("kK"), None, None],
+    );
+    table.insert(
+        'K',
+        vec![Some("jJ"), Some("uU"), Some("iI"), Some("xX"), None, None],
+    );
+    table.insert(
+        'L',
+        vec![
+            Some("rR"),
+            Some("0)"),
+            Some("[{"),
+            Some("/?"),
+            Some("sS"),
+            Some("nN"),
+        ],
+    );
+    table.insert(
+        'M',
+        vec![Some("bB"), Some("hH"), Some("tT"), Some("wW"), None, None],
+    );
+    table.insert(
+        'N',
+        vec![
+            Some("tT"),
+            Some("rR"),
+            Some("lL"),
+            Some("sS"),
+            Some("vV"),
+            Some("wW"),
+        ],
+    );
+    table.insert(
+        'O',
+        vec![
+            Some("aA"),
+            Some(",<"),
+            Some(".>"),
+            Some("eE"),
+            Some("qQ"),
+            Some(";:"),
+        ],
+    );
+    table.insert(
+        'P',
+        vec![
+            Some(".>"),
+            Some("4$"),
+            Some("5%"),
+            Some("yY"),
+            Some("uU"),
+            Some("eE"),
+        ],
+    );
+    table.insert(
+        'Q',
+        vec![Some(";:"), Some("oO"), Some("eE"), Some("jJ"), None, None],
+    );
+    table.insert(
+        'R',
+        vec![
+            Some("cC"),
+            Some("9("),
+            Some("0)"),
+            Some("lL"),
+            Some("nN"),
+            Some("tT"),
+        ],
+    );
+    table.insert(
+        'S',
+        vec![
+            Some("nN"),
+            Some("lL"),
+            Some("/?"),
+            Some("-_"),
+            Some("zZ"),
+            Some("vV"),
+        ],
+    );
+    table.insert(
+        'T',
+        vec![
+            Some("hH"),
+            Some("cC"),
+            Some("rR"),
+            Some("nN"),
+            Some("wW"),
+            Some("mM"),
+        ],
+    );
+    table.insert(
+        'U',
+        vec![
+            Some("eE"),
+            Some("pP"),
+            Some("yY"),
+            Some("iI"),
+            Some("kK"),
+            Some("jJ"),
+        ],
+    );
+    table.insert(
+        'V',
+        vec![Some("wW"), Some("nN"), Some("sS"), Some("zZ"), None, None],
+    );
+    table.insert(
+        'W',
+        vec![Some("mM"), Some("tT"), Some("nN"), Some("vV"), None, None],
+    );
+    table.insert(
+        'X',
+        vec![Some("kK"), Some("iI"), Some("dD"), Some("bB"), None, None],
+    );
+    table.insert(
+        'Y',
+        vec![
+            Some("pP"),
+            Some("5%"),
+            Some("6^"),
+            Some("fF"),
+            Some("iI"),
+            Some("uU"),
+        ],
+    );
+    table.insert(
+        'Z',
+        vec![Some("vV"), Some("sS"), Some("-_"), None, None, None],
+    );
+    table.insert(
+        '[',
+        vec![Some("0)"), None, None, Some("]}"), Some("/?"), Some("lL")],
+    );
+    table.insert('\\', vec![Some("=+"), None, None, None, None, None]);
+    table.insert(
+        ']',
+        vec![Some("[{"), None, None, None, Some("=+"), Some("/?")],
+    );
+    table.insert(
+        '^',
+        vec![Some("5%"), None, None, Some("7&"), Some("fF"), Some("yY")],
+    );
+    table.insert(
+        '_',
+        vec![Some("sS"), Some("/?"), Some("=+"), None, None, Some("zZ")],
+    );
+    table.insert('`', vec![None, None, None, Some("1!"), None, None]);
+    table.insert(
+        'a',
+        vec![None, Some("'\""), Some(",<"), Some("oO"), Some(";:"), None],
+    );
+    table.insert(
+        'b',
+        vec![Some("xX"), Some("dD"), Some("hH"), Some("mM"), None, None],
+    );
+    table.insert(
+        'c',
+        vec![
+            Some("gG"),
+            Some("8*"),
+            Some("9("),
+            Some("rR"),
+            Some("tT"),
+            Some("hH"),
+        ],
+    );
+    table.insert(
+        'd',
+        vec![
+            Some("iI"),
+            Some("fF"),
+            Some("gG"),
+            Some("hH"),
+            Some("bB"),
+            Some("xX"),
+        ],
+    );
+    table.insert(
+        'e',
+        vec![
+            Some("oO"),
+            Some(".>"),
+            Some("pP"),
+            Some("uU"),
+            Some("jJ"),
+            Some("qQ"),
+        ],
+    );
+    table.insert(
+        'f',
+        vec![
+            Some("yY"),
+            Some("6^"),
+            Some("7&"),
+            Some("gG"),
+            Some("dD"),
+            Some("iI"),
+        ],
+    );
+    table.insert(
+        'g',
+        vec![
+            Some("fF"),
+            Some("7&"),
+            Some("8*"),
+            Some("cC"),
+            Some("hH"),
+            Some("dD"),
+        ],
+    );
+    table.insert(
+        'h',
+        vec![
+            Some("dD"),
+            Some("gG"),
+            Some("cC"),
+            Some("tT"),
+            Some("mM"),
+            Some("bB"),
+        ],
+    );
+    table.insert(
+        'i',
+        vec![
+            Some("uU"),
+            Some("yY"),
+            Some("fF"),
+            Some("dD"),
+            Some("xX"),
+            Some("kK"),
+        ],
+    );
+    table.insert(
+        'j',
+        vec![Some("qQ"), Some("eE"), Some("uU"), Some("kK"), None, None],
+    );
+    table.insert(
+        'k',
+        vec![Some("jJ"), Some("uU"), Some("iI"), Some("xX"), None, None],
+    );
+    table.insert(
+        'l',
+        vec![
+            Some("rR"),
+            Some("0)"),
+            Some("[{"),
+            Some("/?"),
+            Some("sS"),
+            Some("nN"),
+        ],
+    );
+    table.insert(
+        'm',
+        vec![Some("bB"), Some("hH"), Some("tT"), Some("wW"), None, None],
+    );
+    table.insert(
+        'n',
+        vec![
+            Some("tT"),
+            Some("rR"),
+            Some("lL"),
+            Some("sS"),
+            Some("vV"),
+            Some("wW"),
+        ],
+    );
+    table.insert(
+        'o',
+        vec![
+            Some("aA"),
+            Some(",<"),
+            Some(".>"),
+            Some("eE"),
+            Some("qQ"),
+            Some(";:"),
+        ],
+    );
+    table.insert(
+        'p',
+        vec![
+            Some(".>"),
+            Some("4$"),
+            Some("5%"),
+            Some("yY"),
+            Some("uU"),
+            Some("eE"),
+        ],
+    );
+    table.insert(
+        'q',
+        vec![Some(";:"), Some("oO"), Some("eE"), Some("jJ"), None, None],
+    );
+    table.insert(
+        'r',
+        vec![
+            Some("cC"),
+            Some("9("),
+            Some("0)"),
+            Some("lL"),
+            Some("nN"),
+            Some("tT"),
+        ],
+    );
+    table.insert(
+        's',
+        vec![
+            Some("nN"),
+            Some("lL"),
+            Some("/?"),
+            Some("-_"),
+            Some("zZ"),
+            Some("vV"),
+        ],
+    );
+    table.insert(
+        't',
+        vec![
+            Some("hH"),
+            Some("cC"),
+            Some("rR"),
+            Some("nN"),
+            Some("wW"),
+            Some("mM"),
+        ],
+    );
+    table.insert(
+        'u',
+        vec![
+            Some("eE"),
+            Some("pP"),
+            Some("yY"),
+            Some("iI"),
+            Some("kK"),
+            Some("jJ"),
+        ],
+    );
+    table.insert(
+        'v',
+        vec![Some("wW"), Some("nN"), Some("sS"), Some("zZ"), None, None],
+    );
+    table.insert(
+        'w',
+        vec![Some("mM"), Some("tT"), Some("nN"), Some("vV"), None, None],
+    );
+    table.insert(
+        'x',
+        vec![Some("kK"), Some("iI"), Some("dD"), Some("bB"), None, None],
+    );
+    table.insert(
+        'y',
+        vec![
+            Some("pP"),
+            Some("5%"),
+            Some("6^"),
+            Some("fF"),
+            Some("iI"),
+            Some("uU"),
+        ],
+    );
+    table.insert(
+        'z',
+        vec![Some("vV"), Some("sS"), Some("-_"), None, None, None],
+    );
+    table.insert(
+        '{',
+        vec![Some("0)"), None, None, Some("]}"), Some("/?"), Some("lL")],
+    );
+    table.insert('|', vec![Some("=+"), None, None, None, None, None]);
+    table.insert(
+        '}',
+        vec![Some("[{"), None, None, None, Some("=+"), Some("/?")],
+    );
+    table.insert('~', vec![None, None, None, Some("1!"), None, None]);
+    table
+});
+pub static KEYPAD: Lazy<HashMap<char, Vec<Option<&'static str>>>> = Lazy::new(|| {
+    let mut table = HashMap::with_capacity(15);
+    table.insert(
+        '*',
+        vec![
+            Some("/"),
+            None,
+            None,
+            None,
+            Some("-"),
+            Some("+"),
+            Some("9"),
+            Some("8"),
+        ],
+    );
+    table.insert(
+        '+',
+        vec![
+            Some("9"),
+            Some("*"),
+            Some("-"),
+            None,
+            None,
+            None,
+            None,
+            Some("6"),
+        ],
+    );
+    table.insert(
+        '-',
+        vec![
+            Some("*"),
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some("+"),
+            Some("9"),
+        ],
+    );
+    table.insert(
+        '.',
+        vec![
+            Some("0"),
+            Some("2"),
+            Some("3"),
+            None,
+            None,
+            None,
+            None,
+            None,
+        ],
+    );
+    table.insert(
+        '/',
+        vec![
+            None,
+            None,
+            None,
+            None,
+            Some("*"),
+            Some("9"),
+            Some("8"),
+            Some("7"),
+        ],
+    );
+    table.insert(
+        '0',
+        vec![
+            None,
+            Some("1"),
+            Some("2"),
+            Some("3"),
+            Some("."),
+            None,
+            None,
+            None,
+        ],
+    );
+    table.insert(
+        '1',
+        vec![
+            None,
+            None,
+            Some("4"),
+            Some("5"),
+            Some("2"),
+            Some("0"),
+            None,
+            None,
+        ],
+    );
+    table.insert(
+        '2',
+        vec![
+            Some("1"),
+            Some("4"),
+            Some("5"),
+            Some("6"),
+            Some("3"),
+            Some("."),
+            Some("0"),
+            None,
+        ],
+    );
+    table.insert(
+        '3',
+        vec![
+            Some("2"),
+            Some("5"),
+            Some("6"),
+            None,
+            None,
+            None,
+            Some("."),
+            Some("0"),
+        ],
+    );
+    table.insert(
+        '4',
+        vec![
+            None,
+            None,
+            Some("7"),
+            Some("8"),
+            Some("5"),
+            Some("2"),
+            Some("1"),
+            None,
+        ],
+    );
+    table.insert(
+        '5',
+        vec![
+            Some("4"),
+            Some("7"),
+            Some("8"),
+            Some("9"),
+            Some("6"),
+            Some("3"),
+            Some("2"),
+            Some("1"),
+        ],
+    );
+    table.insert(
+        '6',
+        vec![
+            Some("5"),
+            Some("8"),
+            Some("9"),
+            Some("+"),
+            None,
+            None,
+            Some("3"),
+            Some("2"),
+        ],
+    );
+    table.insert(
+        '7',
+        vec![
+            None,
+            None,
+            None,
+            Some("/"),
+            Some("8"),
+            Some("5"),
+            Some("4"),
+            None,
+        ],
+    );
+    table.insert(
+        '8',
+        vec![
+            Some("7"),
+            None,
+            Some("/"),
+            Some("*"),
+            Some("9"),
+            Some("6"),
+            Some("5"),
+            Some("4"),
+        ],
+    );
+    table.insert(
+        '9',
+        vec![
+            Some("8"),
+            Some("/"),
+            Some("*"),
+            Some("-"),
+            Some("+"),
+            None,
+            Some("6"),
+            Some("5"),
+        ],
+    );
+    table
+});
+pub static MAC_KEYPAD: Lazy<HashMap<char, Vec<Option<&'static str>>>> = Lazy::new(|| {
+    let mut table = HashMap::with_capacity(16);
+    table.insert(
+        '*',
+        vec![
+            Some("/"),
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some("-"),
+            Some("9"),
+        ],
+    );
+    table.insert(
+        '+',
+        vec![
+            Some("6"),
+            Some("9"),
+            Some("-"),
+            None,
+            None,
+            None,
+            None,
+            Some("3"),
+        ],
+    );
+    table.insert(
+        '-',
+        vec![
+            Some("9"),
+            Some("/"),
+            Some("*"),
+            None,
+            None,
+            None,
+            Some("+"),
+            Some("6"),
+        ],
+    );
+    table.insert(
+        '.',
+        vec![
+            Some("0"),
+            Some("2"),
+            Some("3"),
+            None,
+            None,
+            None,
+            None,
+            None,
+        ],
+    );
+    table.insert(
+        '/',
+        vec![
+            Some("="),
+            None,
+            None,
+            None,
+            Some("*"),
+            Some("-"),
+            Some("9"),
+            Some("8"),
+        ],
+    );
+    table.insert(
+        '0',
+        vec![
+            None,
+            Some("1"),
+            Some("2"),
+            Some("3"),
+            Some("."),
+            None,
+            None,
+            None,
+        ],
+    );
+    table.insert(
+        '1',
+        vec![
+            None,
+            None,
+            Some("4"),
+            Some("5"),
+            Some("2"),
+            Some("0"),
+            None,
+            None,
+        ],
+    );
+    table.insert(
+        '2',
+        vec![
+            Some("1"),
+            Some("4"),
+            Some("5"),
+            Some("6"),
+            Some("3"),
+            Some("."),
+            Some("0"),
+            None,
+        ],
+    );
+    table.insert(
+        '3',
+        vec![
+            Some("2"),
+            Some("5"),
+            Some("6"),
+            Some("+"),
+            None,
+            None,
+            Some("."),
+            Some("0"),
+        ],
+    );
+    table.insert(
+        '4',
+        vec![
+            None,
+            None,
+            Some("7"),
+            Some("8"),
+            Some("5"),
+            Some("2"),
+            Some("1"),
+            None,
+        ],
+    );
+    table.insert(
+        '5',
+        vec![
+            Some("4"),
+            Some("7"),
+            Some("8"),
+            Some("9"),
+            Some("6"),
+            Some("3"),
+            Some("2"),
+            Some("1"),
+        ],
+    );
+    table.insert(
+        '6',
+        vec![
+            Some("5"),
+            Some("8"),
+            Some("9"),
+            Some("-"),
+            Some("+"),
+            None,
+            Some("3"),
+            Some("2"),
+        ],
+    );
+    table.insert(
+        '7',
+        vec![
+            None,
+            None,
+            None,
+            Some("="),
+            Some("8"),
+            Some("5"),
+            Some("4"),
+            None,
+        ],
+    );
+    table.insert(
+        '8',
+        vec![
+            Some("7"),
+            None,
+            Some("="),
+            Some("/"),
+            Some("9"),
+            Some("6"),
+            Some("5"),
+            Some("4"),
+        ],
+    );
+    table.insert(
+        '9',
+        vec![
+            Some("8"),
+            Some("="),
+            Some("/"),
+            Some("*"),
+            Some("-"),
+            Some("+"),
+            Some("6"),
+            Some("5"),
+        ],
+    );
+    table.insert(
+        '=',
+        vec![
+            None,
+            None,
+            None,
+            None,
+            Some("/"),
+            Some("9"),
+            Some("8"),
+            Some("7"),
+        ],
+    );
+    table
+});
+
+/// The neighbors of a key on a grid, left, upper left, up, upper right, right, lower right,
+/// down and lower left of it.
+#[cfg(feature = "extra-keyboards")]
+type GridNeighbors = [Option<&'static str>; 8];
+
+// Defines `KEYBOARDS`, the graphs the build script generates from the keyboard files in
+// `keyboards/`, each with its name and the neighbors of each key.
+#[cfg(feature = "extra-keyboards")]
+include!(concat!(env!("OUT_DIR"), "/keyboards.rs"));
+
+/// The keys adjacent to each key of a keyboard, in each direction it may have a neighbor in.
+pub(crate) type AdjacencyGraph = HashMap<char, Vec<Option<&'static str>>>;
+
+/// The keys of a phone keypad, with 1 2 3 on top, unlike a computer keypad.
+const PHONE_PAD_ROWS: [[&str; 3]; 4] = [
+    ["1", "2", "3"],
+    ["4", "5", "6"],
+    ["7", "8", "9"],
+    ["*", "0", "#"],
+];
+/// The keys of an ATM or door PIN pad: the digits of a phone keypad.
+const PIN_PAD_ROWS: [[&str; 3]; 4] = [
+    ["1", "2", "3"],
+    ["4", "5", "6"],
+    ["7", "8", "9"],
+    ["", "0", ""],
+];
+
+pub static PHONE_PAD: Lazy<AdjacencyGraph> = Lazy::new(|| grid_graph(&PHONE_PAD_ROWS));
+pub static PIN_PAD: Lazy<AdjacencyGraph> = Lazy::new(|| grid_graph(&PIN_PAD_ROWS));
+
+/// The graph of keys laid out on a grid, each next to the keys around it in the directions of
+/// the keypad graphs: left, upper left, up, upper right, right, lower right, down and lower
+/// left. Empty strings are positions without a key.
+fn grid_graph(rows: &[[&'static str; 3]]) -> AdjacencyGraph {
+    let at = |y: usize, x: usize| {
+        rows.get(y)
+            .and_then(|row| row.get(x))
+            .copied()
+            .filter(|key| !key.is_empty())
     };
+    let mut graph = HashMap::new();
+    for (y, row) in rows.iter().enumerate() {
+        for (x, key) in row.iter().enumerate() {
+            if let Some(c) = key.chars().next() {
+                let neighbors = vec![
+                    x.checked_sub(1).and_then(|x| at(y, x)),
+                    y.checked_sub(1)
+                        .zip(x.checked_sub(1))
+                        .and_then(|(y, x)| at(y, x)),
+                    y.checked_sub(1).and_then(|y| at(y, x)),
+                    y.checked_sub(1).and_then(|y| at(y, x + 1)),
+                    at(y, x + 1),
+                    at(y + 1, x + 1),
+                    at(y + 1, x),
+                    x.checked_sub(1).and_then(|x| at(y + 1, x)),
+                ];
+                graph.insert(c, neighbors);
+            }
+        }
+    }
+    graph
 }
+
+/// The keyboards of the `extra-keyboards` feature, by name.
+#[cfg(feature = "extra-keyboards")]
+static EXTRA_KEYBOARDS: Lazy<Vec<(&'static str, AdjacencyGraph)>> = Lazy::new(|| {
+    KEYBOARDS
+        .iter()
+        .map(|&(name, keys)| {
+            let graph = keys
+                .iter()
+                .map(|(key, neighbors)| (*key, neighbors.to_vec()))
+                .collect();
+            (name, graph)
+        })
+        .collect()
+});
+
+/// Names of the keyboards of the `extra-keyboards` feature compiled in.
+pub(crate) fn extra_keyboards() -> impl Iterator<Item = &'static str> {
+    #[cfg(feature = "extra-keyboards")]
+    let names = KEYBOARDS.iter().map(|&(name, _)| name).collect::<Vec<_>>();
+    #[cfg(not(feature = "extra-keyboards"))]
+    let names = Vec::new();
+    names.into_iter()
+}
+
+/// A keyboard graph, with the statistics spatial patterns typed on it are guessed by.
+#[derive(Debug)]
+pub(crate) struct Graph {
+    pub(crate) keys: &'static AdjacencyGraph,
+    /// Number of keys a pattern may start at.
+    pub(crate) starting_positions: u64,
+    /// Average number of neighbors of a key, rounded down.
+    pub(crate) average_degree: u64,
+}
+
+impl Graph {
+    fn new(keys: &'static AdjacencyGraph) -> Self {
+        let neighbors: u64 = keys
+            .values()
+            .map(|neighbors| neighbors.iter().filter(|n| n.is_some()).count() as u64)
+            .sum();
+        Graph {
+            keys,
+            starting_positions: keys.len() as u64,
+            average_degree: neighbors / keys.len() as u64,
+        }
+    }
+}
+
+/// The unshifted keys next to a key on a QWERTY keyboard.
+pub(crate) fn qwerty_neighbors(key: char) -> impl Iterator<Item = char> {
+    QWERTY
+        .get(&key)
+        .into_iter()
+        .flatten()
+        .filter_map(|neighbor| neighbor.and_then(|keys| keys.chars().next()))
+}
+
+/// Every keyboard graph, by the name spatial patterns refer to it by.
+pub(crate) static GRAPHS: Lazy<BTreeMap<&'static str, Graph>> = Lazy::new(|| {
+    let mut table = BTreeMap::new();
+    table.insert("qwerty", Graph::new(&QWERTY));
+    table.insert("dvorak", Graph::new(&DVORAK));
+    table.insert("keypad", Graph::new(&KEYPAD));
+    table.insert("mac_keypad", Graph::new(&MAC_KEYPAD));
+    table.insert("phone_pad", Graph::new(&PHONE_PAD));
+    table.insert("pin_pad", Graph::new(&PIN_PAD));
+    #[cfg(feature = "extra-keyboards")]
+    for (name, keys) in EXTRA_KEYBOARDS.iter() {
+        table.insert(*name, Graph::new(keys));
+    }
+    table
+});
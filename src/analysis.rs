@@ -0,0 +1,467 @@
+//! Aggregate statistics over a corpus of password estimates, and analyses of the parts of a
+//! single password.
+//!
+//! Useful to audit a password dump as a whole rather than row by row:
+//!
+//! ```
+//! use zxcvbn::analysis::CorpusStats;
+//! use zxcvbn::zxcvbn;
+//!
+//! let mut stats = CorpusStats::new();
+//! for password in ["password1", "dragon14.3.1987", "asdfghjkl;'", "correcthorsebatterystaple"] {
+//!     stats.add(&zxcvbn(password, &[]));
+//! }
+//! assert_eq!(stats.passwords(), 4);
+//! assert_eq!(stats.pattern_prevalence("date"), 0.25);
+//! assert_eq!(stats.top_dictionary_words(1), [("battery", 1)]);
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, BufRead, Write};
+
+use crate::matching::patterns::MatchPattern;
+use crate::{zxcvbn, Entropy, Match, ScoringParams};
+
+/// Statistics aggregated from the estimates of many passwords.
+///
+/// Estimates can be added from several threads by giving each its own `CorpusStats` and
+/// combining them with [`merge`](CorpusStats::merge).
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+pub struct CorpusStats {
+    passwords: u64,
+    scores: [u64; 5],
+    guesses_log10_sum: f64,
+    /// Number of dictionary matches of each word.
+    dictionary_words: BTreeMap<String, u64>,
+    /// Number of passwords with at least one match of each pattern.
+    pattern_passwords: BTreeMap<&'static str, u64>,
+    /// Number of matches of each pattern.
+    pattern_matches: BTreeMap<&'static str, u64>,
+}
+
+impl CorpusStats {
+    /// Creates empty statistics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the estimate of one password.
+    pub fn add(&mut self, entropy: &Entropy) {
+        self.passwords += 1;
+        self.scores[usize::from(u8::from(entropy.score()))] += 1;
+        // Empty passwords have no guesses to speak of.
+        self.guesses_log10_sum += entropy.guesses_log10().max(0.0);
+        let mut patterns = BTreeSet::new();
+        for m in entropy.sequence() {
+            let variant = m.pattern.variant();
+            patterns.insert(variant);
+            *self.pattern_matches.entry(variant).or_default() += 1;
+            if let MatchPattern::Dictionary(ref pattern) = m.pattern {
+                *self
+                    .dictionary_words
+                    .entry(pattern.matched_word.clone())
+                    .or_default() += 1;
+            }
+        }
+        for variant in patterns {
+            *self.pattern_passwords.entry(variant).or_default() += 1;
+        }
+    }
+
+    /// Adds all estimates aggregated by `other`.
+    pub fn merge(&mut self, other: CorpusStats) {
+        self.passwords += other.passwords;
+        for (count, other) in self.scores.iter_mut().zip(other.scores) {
+            *count += other;
+        }
+        self.guesses_log10_sum += other.guesses_log10_sum;
+        for (word, count) in other.dictionary_words {
+            *self.dictionary_words.entry(word).or_default() += count;
+        }
+        for (variant, count) in other.pattern_passwords {
+            *self.pattern_passwords.entry(variant).or_default() += count;
+        }
+        for (variant, count) in other.pattern_matches {
+            *self.pattern_matches.entry(variant).or_default() += count;
+        }
+    }
+
+    /// Number of passwords added.
+    pub fn passwords(&self) -> u64 {
+        self.passwords
+    }
+
+    /// Number of passwords with each score, indexed by score.
+    pub fn score_distribution(&self) -> [u64; 5] {
+        self.scores
+    }
+
+    /// Average `guesses_log10` of the passwords, or `None` if there are none.
+    pub fn mean_guesses_log10(&self) -> Option<f64> {
+        (self.passwords > 0).then(|| self.guesses_log10_sum / self.passwords as f64)
+    }
+
+    /// The `n` dictionary words matched most often with their number of matches, most
+    /// common first. Ties are ordered alphabetically.
+    pub fn top_dictionary_words(&self, n: usize) -> Vec<(&str, u64)> {
+        let mut words = self
+            .dictionary_words
+            .iter()
+            .map(|(word, &count)| (word.as_str(), count))
+            .collect::<Vec<_>>();
+        // The map is ordered by word, and the stable sort keeps it that way for ties.
+        words.sort_by(|(_, a), (_, b)| b.cmp(a));
+        words.truncate(n);
+        words
+    }
+
+    /// Fraction of the passwords containing at least one match of the pattern named
+    /// like [`MatchPattern::variant`], e.g. `"date"` or `"spatial"`.
+    pub fn pattern_prevalence(&self, variant: &str) -> f64 {
+        if self.passwords == 0 {
+            return 0.0;
+        }
+        let count = self.pattern_passwords.get(variant).copied().unwrap_or(0);
+        count as f64 / self.passwords as f64
+    }
+
+    /// Number of matches of each pattern over all passwords, keyed by
+    /// [`MatchPattern::variant`].
+    pub fn pattern_matches(&self) -> &BTreeMap<&'static str, u64> {
+        &self.pattern_matches
+    }
+
+    /// Writes the statistics in a line based text format that [`load`](CorpusStats::load)
+    /// reads back exactly, e.g. to resume aggregating a corpus later.
+    pub fn save<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "{}", STATS_HEADER)?;
+        writeln!(writer, "passwords {}", self.passwords)?;
+        let scores = self.scores.map(|count| count.to_string());
+        writeln!(writer, "scores {}", scores.join(" "))?;
+        writeln!(writer, "guesses_log10_sum {}", self.guesses_log10_sum)?;
+        for (variant, count) in &self.pattern_passwords {
+            writeln!(writer, "pattern_passwords {} {}", variant, count)?;
+        }
+        for (variant, count) in &self.pattern_matches {
+            writeln!(writer, "pattern_matches {} {}", variant, count)?;
+        }
+        for (word, count) in &self.dictionary_words {
+            let word = word.replace('\\', "\\\\").replace('\n', "\\n");
+            writeln!(writer, "dictionary_word {} {}", count, word)?;
+        }
+        Ok(())
+    }
+
+    /// Reads statistics written by [`save`](CorpusStats::save).
+    ///
+    /// Fails with [`io::ErrorKind::InvalidData`] if the input is not in that format.
+    pub fn load<R: BufRead>(reader: R) -> io::Result<Self> {
+        let mut lines = reader.lines();
+        if lines.next().transpose()?.as_deref() != Some(STATS_HEADER) {
+            return Err(invalid_data("not saved corpus statistics"));
+        }
+        let mut stats = CorpusStats::new();
+        for line in lines {
+            let line = line?;
+            let (key, value) = line.split_once(' ').unwrap_or((&line, ""));
+            match key {
+                "passwords" => stats.passwords = parse(value)?,
+                "scores" => {
+                    let scores = value
+                        .split(' ')
+                        .map(parse)
+                        .collect::<io::Result<Vec<_>>>()?;
+                    stats.scores = scores
+                        .try_into()
+                        .map_err(|_| invalid_data("expected 5 scores"))?;
+                }
+                "guesses_log10_sum" => stats.guesses_log10_sum = parse(value)?,
+                "pattern_passwords" | "pattern_matches" => {
+                    let (variant, count) = value.split_once(' ').unwrap_or((value, ""));
+                    let variant = MatchPattern::VARIANTS
+                        .iter()
+                        .find(|&&known| known == variant)
+                        .ok_or_else(|| invalid_data(&format!("unknown pattern {}", variant)))?;
+                    let counts = if key == "pattern_passwords" {
+                        &mut stats.pattern_passwords
+                    } else {
+                        &mut stats.pattern_matches
+                    };
+                    counts.insert(variant, parse(count)?);
+                }
+                "dictionary_word" => {
+                    let (count, word) = value.split_once(' ').unwrap_or((value, ""));
+                    stats.dictionary_words.insert(unescape(word), parse(count)?);
+                }
+                _ => return Err(invalid_data(&format!("unknown line {}", line))),
+            }
+        }
+        Ok(stats)
+    }
+}
+
+/// First line of saved [`CorpusStats`], with the version of the format.
+const STATS_HEADER: &str = "zxcvbn-corpus-stats 1";
+
+fn parse<T: std::str::FromStr>(value: &str) -> io::Result<T> {
+    value
+        .parse()
+        .map_err(|_| invalid_data(&format!("invalid number {}", value)))
+}
+
+/// Reverts the escaping of backslashes and newlines in saved dictionary words.
+fn unescape(word: &str) -> String {
+    let mut unescaped = String::with_capacity(word.len());
+    let mut chars = word.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some(c) => unescaped.push(c),
+            None => {}
+        }
+    }
+    unescaped
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// A part of a password, estimated on its own.
+#[derive(Debug, Clone)]
+pub struct Window {
+    /// Index of the first character of the window.
+    pub i: usize,
+    /// Index of the last character of the window.
+    pub j: usize,
+    /// The characters of the window.
+    pub token: String,
+    /// The estimate of the window as a password of its own.
+    pub entropy: Entropy,
+}
+
+/// Finds the `k` characters long part of the password with the fewest guesses on its own,
+/// or `None` if the password is shorter than `k` or `k` is 0. Of equally weak parts, the
+/// first is returned.
+///
+/// Useful for feedback on a weak part of an otherwise strong password:
+///
+/// ```
+/// use zxcvbn::analysis::weakest_window;
+///
+/// let window = weakest_window("Xk7#iloveyouq9!Zr", 8).unwrap();
+/// assert_eq!(window.token, "iloveyou");
+/// assert_eq!((window.i, window.j), (4, 11));
+/// ```
+///
+/// Each window is estimated separately, so this is about as slow as estimating the password
+/// once per character.
+pub fn weakest_window(password: &str, k: usize) -> Option<Window> {
+    // the same limit as the estimate of a whole password
+    let chars = password.chars().take(100).collect::<Vec<_>>();
+    if k == 0 || k > chars.len() {
+        return None;
+    }
+    let mut weakest: Option<Window> = None;
+    for (i, window) in chars.windows(k).enumerate() {
+        let token = window.iter().collect::<String>();
+        let entropy = zxcvbn(&token, &[]);
+        if weakest.as_ref().map_or(true, |weakest| {
+            entropy.guesses() < weakest.entropy.guesses()
+        }) {
+            weakest = Some(Window {
+                i,
+                j: i + k - 1,
+                token,
+                entropy,
+            });
+        }
+    }
+    weakest
+}
+
+/// A way of covering a password with matches.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+pub struct Alternative {
+    /// Estimated guesses needed to crack the password, were it made of these matches.
+    pub guesses: u64,
+    /// Order of magnitude of `guesses`.
+    pub guesses_log10: f64,
+    /// The matches covering the password, ordered by position.
+    pub sequence: Vec<Match>,
+}
+
+/// Finds up to `k` sequences of the matches of an estimate covering the whole password,
+/// those with the fewest guesses first, so that near-optimal interpretations of a password
+/// can be told apart, e.g. whether a part is better explained as a date or as a word.
+///
+/// The first is the [sequence](Entropy::sequence) of the estimate, or one with as many
+/// guesses, if it was estimated with the same `params`. The guesses of every match are those
+/// of the estimate; only how the sequences are scored follows `params`.
+///
+/// ```
+/// use zxcvbn::analysis::alternative_sequences;
+/// use zxcvbn::{zxcvbn, ScoringParams};
+///
+/// let entropy = zxcvbn("march1987", &[]);
+/// let alternatives = alternative_sequences(&entropy, 5, &ScoringParams::default());
+/// assert_eq!(alternatives[0].guesses, entropy.guesses());
+/// assert!(alternatives[1].guesses >= alternatives[0].guesses);
+/// ```
+///
+/// Passwords matched as banned have no matches and no alternatives. Finding `k` sequences
+/// takes about `k` times as long as finding the best one.
+pub fn alternative_sequences(
+    entropy: &Entropy,
+    k: usize,
+    params: &ScoringParams,
+) -> Vec<Alternative> {
+    // the matches of the sequence cover the password, overlapping where a character was
+    // normalized into several
+    let mut password = Vec::new();
+    for m in entropy.sequence() {
+        password.truncate(m.i);
+        password.extend(m.token.chars());
+    }
+    let password = password.into_iter().collect::<String>();
+    crate::scoring::k_best_match_sequences(&password, entropy.all_matches(), params, k)
+        .into_iter()
+        .map(|calculation| Alternative {
+            guesses: calculation.guesses,
+            guesses_log10: calculation.guesses_log10,
+            sequence: calculation.sequence,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    const CORPUS: [&str; 6] = [
+        "password",
+        "password!",
+        "qwerty",
+        "monkey1987",
+        "Tr0ub4dour&3",
+        "",
+    ];
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_corpus_stats() {
+        let mut stats = CorpusStats::new();
+        assert_eq!(stats.mean_guesses_log10(), None);
+        assert_eq!(stats.pattern_prevalence("date"), 0.0);
+
+        for password in CORPUS {
+            stats.add(&zxcvbn(password, &[]));
+        }
+        assert_eq!(stats.passwords(), 6);
+        assert_eq!(stats.score_distribution().iter().sum::<u64>(), 6);
+        assert_eq!(stats.score_distribution(), [3, 2, 1, 0, 0]);
+        assert_eq!(stats.top_dictionary_words(1), [("password", 2)]);
+        assert!(stats.top_dictionary_words(10).contains(&("monkey", 1)));
+        assert_eq!(stats.pattern_prevalence("dictionary"), 5.0 / 6.0);
+        assert_eq!(stats.pattern_prevalence("spatial"), 0.0);
+        assert!(stats.pattern_matches()["dictionary"] >= 5);
+        let mean = stats.mean_guesses_log10().unwrap();
+        assert!(mean > 0.0 && mean < 12.0, "{}", mean);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_save_and_load() {
+        let mut stats = CorpusStats::new();
+        for password in CORPUS {
+            stats.add(&zxcvbn(password, &[]));
+        }
+        stats.add(&zxcvbn("back\\slash\nnewline", &["back\\slash\nnewline"]));
+        let mut saved = Vec::new();
+        stats.save(&mut saved).unwrap();
+        assert_eq!(CorpusStats::load(&saved[..]).unwrap(), stats);
+        assert_eq!(
+            CorpusStats::load(&b"zxcvbn-corpus-stats 1\n"[..]).unwrap(),
+            CorpusStats::new()
+        );
+
+        for invalid in [
+            "",
+            "passwords 3",
+            "zxcvbn-corpus-stats 1\npasswords three",
+            "zxcvbn-corpus-stats 1\nscores 1 2",
+            "zxcvbn-corpus-stats 1\npattern_matches keyboard 3",
+            "zxcvbn-corpus-stats 1\nfavorite_color blue",
+        ] {
+            let err = CorpusStats::load(invalid.as_bytes()).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData, "{}", invalid);
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_weakest_window() {
+        let window = weakest_window("q9!Zr#password", 8).unwrap();
+        assert_eq!(window.token, "password");
+        assert_eq!((window.i, window.j), (6, 13));
+        assert_eq!(window.entropy.guesses(), zxcvbn("password", &[]).guesses());
+
+        let window = weakest_window("aaaa", 2).unwrap();
+        assert_eq!((window.i, window.token.as_str()), (0, "aa"));
+        assert_eq!(weakest_window("password", 8).unwrap().token, "password");
+        assert!(weakest_window("password", 9).is_none());
+        assert!(weakest_window("password", 0).is_none());
+        assert!(weakest_window("", 1).is_none());
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_corpus_stats_merge() {
+        let mut all = CorpusStats::new();
+        let mut halves = [CorpusStats::new(), CorpusStats::new()];
+        for (k, password) in CORPUS.iter().enumerate() {
+            let entropy = zxcvbn(password, &[]);
+            all.add(&entropy);
+            halves[k % 2].add(&entropy);
+        }
+        let [mut merged, other] = halves;
+        merged.merge(other);
+        assert_eq!(merged.passwords(), all.passwords());
+        assert_eq!(merged.score_distribution(), all.score_distribution());
+        assert_eq!(
+            merged.top_dictionary_words(10),
+            all.top_dictionary_words(10)
+        );
+        assert_eq!(merged.pattern_matches(), all.pattern_matches());
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_alternative_sequences() {
+        let entropy = zxcvbn("march1987", &[]);
+        let alternatives = alternative_sequences(&entropy, 5, &ScoringParams::default());
+        assert_eq!(alternatives.len(), 5);
+        assert_eq!(alternatives[0].guesses, entropy.guesses());
+        assert_eq!(alternatives[0].sequence, entropy.sequence());
+        // "1987" is a recent year, but also a date
+        assert!(alternatives.iter().any(|alternative| alternative
+            .sequence
+            .iter()
+            .any(|m| m.pattern.variant() == "date")));
+
+        let params = ScoringParams::new().exclude_additive(true);
+        let alternatives = alternative_sequences(&entropy, 1, &params);
+        assert!(alternatives[0].guesses < entropy.guesses());
+
+        assert!(alternative_sequences(&zxcvbn("", &[]), 5, &ScoringParams::default()).is_empty());
+    }
+}
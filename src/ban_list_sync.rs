@@ -0,0 +1,396 @@
+//! Keeping a list of banned words maintained elsewhere in sync with a live estimator, such as
+//! an organization's list of employee names, office locations and wifi network names that
+//! identity providers should keep out of passwords.
+//!
+//! A list is fetched from a [`BanListSource`], one word per line, and replaces the words of
+//! the previous version with [`Zxcvbn::set_ban_list`], without blocking estimates. Sources
+//! tag each version they return, like an HTTP ETag, so that a list is only downloaded and
+//! compiled again once it has changed. Empty lines and lines starting with `#` are skipped.
+//!
+//! ```
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! use zxcvbn::ban_list_sync::{BanListSync, FileSource};
+//! use zxcvbn::Zxcvbn;
+//!
+//! # let path = std::env::temp_dir().join("zxcvbn-ban-list-sync-doctest");
+//! # std::fs::write(&path, "# offices\nspringfield\n")?;
+//! let estimator = Arc::new(Zxcvbn::new());
+//! let mut sync = BanListSync::new("organization", FileSource::new(&path))
+//!     .interval(Duration::from_secs(300))
+//!     .on_error(|err| eprintln!("failed to sync the banned words: {}", err));
+//! // fail early if the list can't be read, then keep it in sync in the background
+//! sync.sync(&estimator)?;
+//! let handle = sync.spawn(Arc::clone(&estimator));
+//! # handle.stop();
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::{BanRule, Zxcvbn};
+
+/// How long [`BanListSync`] waits between fetches unless configured otherwise.
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// The outcome of fetching a list from a [`BanListSource`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    /// The list is the version tagged with the tag it was fetched with.
+    Unchanged,
+    /// The list has changed.
+    Changed {
+        /// The list, one word per line.
+        contents: String,
+        /// The tag of this version, or `None` if the source doesn't tag versions.
+        tag: Option<String>,
+    },
+}
+
+/// Where a list of banned words is fetched from.
+pub trait BanListSource: Send {
+    /// Fetches the list, unless it is still the version tagged `tag`, the tag of the last
+    /// version fetched.
+    fn fetch(&mut self, tag: Option<&str>) -> io::Result<Fetched>;
+}
+
+/// A list in a file, tagged with its modification time and length.
+#[derive(Debug, Clone)]
+pub struct FileSource {
+    path: PathBuf,
+}
+
+impl FileSource {
+    /// A source reading the file at `path`.
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        FileSource {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+}
+
+impl BanListSource for FileSource {
+    fn fetch(&mut self, tag: Option<&str>) -> io::Result<Fetched> {
+        let metadata = fs::metadata(&self.path)?;
+        let modified = metadata
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let current = format!("{}-{}", modified.as_nanos(), metadata.len());
+        if tag == Some(current.as_str()) {
+            return Ok(Fetched::Unchanged);
+        }
+        Ok(Fetched::Changed {
+            contents: fs::read_to_string(&self.path)?,
+            tag: Some(current),
+        })
+    }
+}
+
+/// A list downloaded over HTTP or HTTPS, tagged with its ETag. The list is only downloaded
+/// again once the server no longer answers `304 Not Modified` to its ETag.
+#[cfg(feature = "ban-list-http")]
+#[derive(Debug, Clone)]
+pub struct HttpSource {
+    url: String,
+    agent: ureq::Agent,
+}
+
+#[cfg(feature = "ban-list-http")]
+impl HttpSource {
+    /// A source downloading the list at `url`, giving up on requests after 30 seconds.
+    pub fn new(url: &str) -> Self {
+        Self::with_agent(
+            url,
+            ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .build(),
+        )
+    }
+
+    /// A source downloading the list at `url` with the given agent, e.g. one with a proxy or
+    /// other timeouts. Headers such as credentials can be added with
+    /// [`ureq::AgentBuilder::middleware`].
+    pub fn with_agent(url: &str, agent: ureq::Agent) -> Self {
+        HttpSource {
+            url: url.to_string(),
+            agent,
+        }
+    }
+}
+
+#[cfg(feature = "ban-list-http")]
+impl BanListSource for HttpSource {
+    fn fetch(&mut self, tag: Option<&str>) -> io::Result<Fetched> {
+        let mut request = self.agent.get(&self.url);
+        if let Some(tag) = tag {
+            request = request.set("If-None-Match", tag);
+        }
+        let response = match request.call() {
+            Ok(response) => response,
+            Err(ureq::Error::Status(status, _)) => {
+                return Err(io::Error::other(format!(
+                    "{} answered with status {}",
+                    self.url, status
+                )))
+            }
+            Err(err) => return Err(io::Error::other(err)),
+        };
+        if response.status() == 304 {
+            return Ok(Fetched::Unchanged);
+        }
+        let tag = response.header("ETag").map(str::to_string);
+        Ok(Fetched::Changed {
+            contents: response.into_string()?,
+            tag,
+        })
+    }
+}
+
+/// Keeps a named list of banned words of an estimator in sync with a [`BanListSource`].
+pub struct BanListSync<S> {
+    name: String,
+    source: S,
+    tag: Option<String>,
+    interval: Duration,
+    on_error: Option<ErrorCallback>,
+}
+
+type ErrorCallback = Box<dyn Fn(&io::Error) + Send>;
+
+impl<S: BanListSource> BanListSync<S> {
+    /// Syncs the list called `name` from `source` every [`DEFAULT_INTERVAL`].
+    pub fn new(name: &str, source: S) -> Self {
+        BanListSync {
+            name: name.to_string(),
+            source,
+            tag: None,
+            interval: DEFAULT_INTERVAL,
+            on_error: None,
+        }
+    }
+
+    /// Sets how long to wait between fetches once [spawned](BanListSync::spawn).
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Calls `on_error` on the syncing thread when fetching the list fails. The estimator
+    /// keeps the last version of the list until a fetch succeeds again.
+    pub fn on_error<F>(mut self, on_error: F) -> Self
+    where
+        F: Fn(&io::Error) + Send + 'static,
+    {
+        self.on_error = Some(Box::new(on_error));
+        self
+    }
+
+    /// Fetches the list now, replacing the estimator's words of this list if it has changed.
+    /// Returns whether it has, or an error of kind `InvalidData` if the list is too large to
+    /// compile, in which case the estimator keeps the last version of it.
+    pub fn sync(&mut self, estimator: &Zxcvbn) -> io::Result<bool> {
+        match self.source.fetch(self.tag.as_deref())? {
+            Fetched::Unchanged => Ok(false),
+            Fetched::Changed { contents, tag } => {
+                estimator
+                    .set_ban_list(&self.name, parse(&contents))
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+                self.tag = tag;
+                Ok(true)
+            }
+        }
+    }
+
+    /// Syncs the list on a thread of its own, right away and then after every interval,
+    /// until the returned handle is stopped or dropped.
+    pub fn spawn(mut self, estimator: Arc<Zxcvbn>) -> SyncHandle
+    where
+        S: 'static,
+    {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let thread = thread::spawn(move || loop {
+            if let Err(err) = self.sync(&estimator) {
+                if let Some(on_error) = &self.on_error {
+                    on_error(&err);
+                }
+            }
+            match stopped.recv_timeout(self.interval) {
+                Err(RecvTimeoutError::Timeout) => {}
+                Ok(()) | Err(RecvTimeoutError::Disconnected) => break,
+            }
+        });
+        SyncHandle {
+            stop: Some(stop),
+            thread: Some(thread),
+        }
+    }
+}
+
+impl<S: fmt::Debug> fmt::Debug for BanListSync<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BanListSync")
+            .field("name", &self.name)
+            .field("source", &self.source)
+            .field("tag", &self.tag)
+            .field("interval", &self.interval)
+            .field("on_error", &self.on_error.is_some())
+            .finish()
+    }
+}
+
+/// A list being synced on a thread of its own. Dropping it stops syncing, like
+/// [`stop`](SyncHandle::stop).
+#[derive(Debug)]
+pub struct SyncHandle {
+    stop: Option<mpsc::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl SyncHandle {
+    /// Stops syncing, waiting for a fetch in progress to finish. The estimator keeps the
+    /// last version of the list.
+    pub fn stop(self) {}
+}
+
+impl Drop for SyncHandle {
+    fn drop(&mut self) {
+        drop(self.stop.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// The rules of a list with one word per line.
+fn parse(contents: &str) -> Vec<BanRule> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(BanRule::new)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_banned(estimator: &Zxcvbn, word: &str) -> bool {
+        let password = format!("{}Q7#x", word);
+        estimator.estimate(&password, &[]).guesses()
+            < Zxcvbn::new().estimate(&password, &[]).guesses()
+    }
+
+    #[test]
+    fn test_parse() {
+        let rules = parse("# offices\nSpringfield\n\n  Shelbyville \r\n#wifi\n");
+        let words = rules.iter().map(BanRule::word).collect::<Vec<_>>();
+        assert_eq!(words, ["springfield", "shelbyville"]);
+    }
+
+    #[test]
+    fn test_sync_file() {
+        let path = std::env::temp_dir().join("zxcvbn-ban-list-sync-test");
+        fs::write(&path, "springfield\n").unwrap();
+        let estimator = Zxcvbn::new();
+        estimator.add_banned_words(["acme"]).unwrap();
+        let mut sync = BanListSync::new("offices", FileSource::new(&path));
+        assert!(sync.sync(&estimator).unwrap());
+        assert!(is_banned(&estimator, "springfield"));
+        assert!(!sync.sync(&estimator).unwrap());
+
+        fs::write(&path, "shelbyville\nogdenville\n").unwrap();
+        assert!(sync.sync(&estimator).unwrap());
+        assert!(!is_banned(&estimator, "springfield"));
+        assert!(is_banned(&estimator, "ogdenville"));
+        assert!(is_banned(&estimator, "acme"));
+
+        // the last version is kept while the list can't be fetched
+        fs::remove_file(&path).unwrap();
+        assert!(sync.sync(&estimator).is_err());
+        assert!(is_banned(&estimator, "ogdenville"));
+    }
+
+    #[test]
+    fn test_spawn() {
+        /// Returns the versions last to first, then fails.
+        struct Versions(Vec<&'static str>);
+
+        impl BanListSource for Versions {
+            fn fetch(&mut self, _: Option<&str>) -> io::Result<Fetched> {
+                let contents = self.0.pop().ok_or(io::ErrorKind::NotFound)?;
+                Ok(Fetched::Changed {
+                    contents: contents.to_string(),
+                    tag: None,
+                })
+            }
+        }
+
+        let estimator = Arc::new(Zxcvbn::new());
+        let (errors, errored) = mpsc::channel();
+        let handle = BanListSync::new("offices", Versions(vec!["shelbyville", "springfield"]))
+            .interval(Duration::from_millis(1))
+            .on_error(move |err| {
+                let _ = errors.send(err.kind());
+            })
+            .spawn(Arc::clone(&estimator));
+        assert_eq!(errored.recv().unwrap(), io::ErrorKind::NotFound);
+        handle.stop();
+        assert!(is_banned(&estimator, "shelbyville"));
+        assert!(!is_banned(&estimator, "springfield"));
+    }
+
+    #[cfg(feature = "ban-list-http")]
+    #[test]
+    fn test_sync_http() {
+        use std::io::{BufRead, BufReader, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/banned.txt", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let mut if_none_match = Vec::new();
+            for _ in 0..2 {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(&stream);
+                let mut tag = None;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("if-none-match") {
+                            tag = Some(value.trim().to_string());
+                        }
+                    }
+                }
+                let response = if tag.as_deref() == Some("\"v1\"") {
+                    "HTTP/1.1 304 Not Modified\r\nContent-Length: 0\r\n\r\n".to_string()
+                } else {
+                    "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Length: 12\r\n\r\nspringfield\n"
+                        .to_string()
+                };
+                (&stream).write_all(response.as_bytes()).unwrap();
+                if_none_match.push(tag);
+            }
+            if_none_match
+        });
+
+        let estimator = Zxcvbn::new();
+        let mut sync = BanListSync::new("offices", HttpSource::new(&url));
+        assert!(sync.sync(&estimator).unwrap());
+        assert!(!sync.sync(&estimator).unwrap());
+        assert!(is_banned(&estimator, "springfield"));
+        assert_eq!(server.join().unwrap(), [None, Some("\"v1\"".to_string())]);
+    }
+}
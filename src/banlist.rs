@@ -0,0 +1,413 @@
+//! Words banned inside passwords, with rules for where and how much of them has to appear.
+//!
+//! The rules added one by one and each named list are compiled into an Aho-Corasick automaton
+//! of their own, so the dictionary matcher finds every banned word in one pass over the password
+//! per list however long they grow, and replacing a list does not compile the others again. A
+//! rule banning parts of a word only adds the parts of exactly its minimum length, which are
+//! then extended along the password, so long words with short minimum lengths stay cheap to add.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+use aho_corasick::AhoCorasick;
+
+/// Where in a password a banned word has to appear for its rule to apply.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BanPosition {
+    /// Anywhere in the password.
+    #[default]
+    Anywhere,
+    /// At the very start of the password.
+    Start,
+    /// At the very end of the password.
+    End,
+}
+
+/// A word that is banned inside passwords, such as a company or product name.
+///
+/// ```
+/// use zxcvbn::{BanPosition, BanRule, Zxcvbn};
+///
+/// let estimator = Zxcvbn::new();
+/// estimator.add_ban_rules([
+///     // The company name, anywhere in the password.
+///     BanRule::new("Acme"),
+///     // The product name, or any 5 or more characters of it, at the start of the password.
+///     BanRule::new("RoadRunner").min_length(5).position(BanPosition::Start),
+/// ])?;
+/// # Ok::<(), zxcvbn::BanlistError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BanRule {
+    word: String,
+    min_length: usize,
+    position: BanPosition,
+}
+
+impl BanRule {
+    /// Bans the whole word anywhere in a password. Words are compared case-insensitively.
+    pub fn new(word: &str) -> Self {
+        let word = word.to_lowercase();
+        Self {
+            min_length: word.chars().count(),
+            word,
+            position: BanPosition::Anywhere,
+        }
+    }
+
+    /// Also bans any part of the word that is at least `min_length` characters long,
+    /// e.g. "runner" and "roadr" for "roadrunner" with a minimum length of 5.
+    pub fn min_length(mut self, min_length: usize) -> Self {
+        self.min_length = min_length.max(1);
+        self
+    }
+
+    /// Only bans the word where it appears at the given position in the password.
+    pub fn position(mut self, position: BanPosition) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// The lowercased banned word.
+    pub fn word(&self) -> &str {
+        &self.word
+    }
+
+    /// The parts of the word as long as its minimum length, with the char index each starts
+    /// at. Every part the rule bans starts with one of them.
+    fn seeds(&self) -> impl Iterator<Item = (usize, &str)> + '_ {
+        let starts = self
+            .word
+            .char_indices()
+            .map(|(k, _)| k)
+            .chain([self.word.len()])
+            .collect::<Vec<_>>();
+        let min_length = self.min_length.min(starts.len() - 1);
+        (0..starts.len() - min_length)
+            .map(move |i| (i, &self.word[starts[i]..starts[i + min_length]]))
+    }
+}
+
+/// The error returned when a banlist is too large to compile.
+#[derive(Debug, Clone)]
+pub struct BanlistError(aho_corasick::BuildError);
+
+impl fmt::Display for BanlistError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "banlist is too large to compile: {}", self.0)
+    }
+}
+
+impl Error for BanlistError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+/// A match of a banned word, as an inclusive range of char indexes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct BannedMatch<'a> {
+    pub(crate) i: usize,
+    pub(crate) j: usize,
+    pub(crate) word: &'a str,
+}
+
+/// A rule as compiled into a [`RuleSet`].
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    chars: Vec<char>,
+    /// The byte index of each char of the word, and the length of the word.
+    char_starts: Vec<usize>,
+    rule: BanRule,
+}
+
+/// A set of [`BanRule`]s compiled into one automaton.
+#[derive(Debug, Clone)]
+pub(crate) struct RuleSet {
+    automaton: AhoCorasick,
+    compiled: Vec<CompiledRule>,
+    /// The rule and char index of every seed behind each automaton pattern.
+    patterns: Vec<Vec<(usize, usize)>>,
+}
+
+impl RuleSet {
+    pub(crate) fn new(rules: Vec<BanRule>) -> Result<Self, BanlistError> {
+        let compiled = rules
+            .into_iter()
+            .filter(|rule| !rule.word.is_empty())
+            .map(|rule| CompiledRule {
+                chars: rule.word.chars().collect(),
+                char_starts: rule
+                    .word
+                    .char_indices()
+                    .map(|(k, _)| k)
+                    .chain([rule.word.len()])
+                    .collect(),
+                rule,
+            })
+            .collect::<Vec<_>>();
+        let mut seeds: BTreeMap<&str, Vec<(usize, usize)>> = BTreeMap::new();
+        for (index, compiled_rule) in compiled.iter().enumerate() {
+            for (start, seed) in compiled_rule.rule.seeds() {
+                seeds.entry(seed).or_default().push((index, start));
+            }
+        }
+        let automaton = AhoCorasick::new(seeds.keys()).map_err(BanlistError)?;
+        let patterns = seeds.into_values().collect();
+        Ok(Self {
+            automaton,
+            compiled,
+            patterns,
+        })
+    }
+
+    fn rules(&self) -> impl Iterator<Item = &BanRule> {
+        self.compiled.iter().map(|compiled| &compiled.rule)
+    }
+
+    /// Adds every banned part of the password to `found`, by the inclusive range of char
+    /// indexes it spans.
+    fn find_into<'a>(
+        &'a self,
+        password: &str,
+        chars: &[char],
+        reversed: bool,
+        found: &mut BTreeMap<(usize, usize), &'a str>,
+    ) {
+        let char_starts = password.char_indices().map(|(k, _)| k).collect::<Vec<_>>();
+        let last = chars.len().saturating_sub(1);
+        for m in self.automaton.find_overlapping_iter(password) {
+            let i = char_starts.partition_point(|&k| k < m.start());
+            let seed_len = char_starts.partition_point(|&k| k < m.end()) - i;
+            for &(index, start) in &self.patterns[m.pattern().as_usize()] {
+                let compiled = &self.compiled[index];
+                // extend the seed as far as the password goes on with the word
+                let mut len = seed_len;
+                while i + len < chars.len()
+                    && start + len < compiled.chars.len()
+                    && chars[i + len] == compiled.chars[start + len]
+                {
+                    len += 1;
+                }
+                for len in seed_len..=len {
+                    let j = i + len - 1;
+                    let (at_start, at_end) = if reversed {
+                        (j == last, i == 0)
+                    } else {
+                        (i == 0, j == last)
+                    };
+                    let banned = match compiled.rule.position {
+                        BanPosition::Anywhere => true,
+                        BanPosition::Start => at_start,
+                        BanPosition::End => at_end,
+                    };
+                    if banned {
+                        let word = &compiled.rule.word
+                            [compiled.char_starts[start]..compiled.char_starts[start + len]];
+                        found.entry((i, j)).or_insert(word);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The rules banned words are matched with: those added one by one, and the named lists.
+///
+/// Each list is compiled on its own and shared between the versions of the banlist, so
+/// replacing one list leaves the others as they were compiled.
+#[derive(Debug, Clone)]
+pub(crate) struct Banlist {
+    rules: Arc<RuleSet>,
+    /// Named lists of rules, each replaced as a whole when it changes.
+    lists: BTreeMap<String, Arc<RuleSet>>,
+}
+
+impl Default for Banlist {
+    fn default() -> Self {
+        Self {
+            rules: Arc::new(RuleSet::new(Vec::new()).expect("no rules always compile")),
+            lists: BTreeMap::new(),
+        }
+    }
+}
+
+impl Banlist {
+    /// A new banlist with the rules of this one and the given ones.
+    pub(crate) fn with_rules(&self, rules: &[BanRule]) -> Result<Self, BanlistError> {
+        let rules = RuleSet::new(self.rules.rules().chain(rules).cloned().collect())?;
+        Ok(Self {
+            rules: Arc::new(rules),
+            lists: self.lists.clone(),
+        })
+    }
+
+    /// A new banlist with the named list replaced by the given rules, or removed if there are
+    /// none.
+    pub(crate) fn with_list(&self, name: &str, rules: Option<Arc<RuleSet>>) -> Self {
+        let mut lists = self.lists.clone();
+        match rules {
+            Some(rules) => lists.insert(name.to_string(), rules),
+            None => lists.remove(name),
+        };
+        Self {
+            rules: Arc::clone(&self.rules),
+            lists,
+        }
+    }
+
+    fn rule_sets(&self) -> impl Iterator<Item = &RuleSet> {
+        [&self.rules]
+            .into_iter()
+            .chain(self.lists.values())
+            .map(|rules| &**rules)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.rule_sets().map(|rules| rules.compiled.len()).sum()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.rule_sets().all(|rules| rules.compiled.is_empty())
+    }
+
+    /// Finds every banned word in a lowercased password. With `reversed`, the password has
+    /// been reversed, so the start and end positions are swapped.
+    pub(crate) fn find(&self, password: &str, reversed: bool) -> Vec<BannedMatch<'_>> {
+        let chars = password.chars().collect::<Vec<_>>();
+        // the same part of a password may be banned by several rules or seeds
+        let mut found = BTreeMap::new();
+        for rules in self.rule_sets() {
+            rules.find_into(password, &chars, reversed, &mut found);
+        }
+        found
+            .into_iter()
+            .map(|((i, j), word)| BannedMatch { i, j, word })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    fn list(words: &[&str]) -> Option<Arc<RuleSet>> {
+        let rules = words.iter().map(|word| BanRule::new(word)).collect();
+        Some(Arc::new(RuleSet::new(rules).unwrap()))
+    }
+
+    fn find(banlist: &Banlist, password: &str, reversed: bool) -> Vec<(usize, usize, String)> {
+        banlist
+            .find(password, reversed)
+            .into_iter()
+            .map(|m| (m.i, m.j, m.word.to_string()))
+            .collect()
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_ban_rule_seeds() {
+        let rule = BanRule::new("ACME");
+        assert_eq!(rule.word(), "acme");
+        assert_eq!(rule.seeds().collect::<Vec<_>>(), [(0, "acme")]);
+        let rule = rule.min_length(3);
+        assert_eq!(rule.seeds().collect::<Vec<_>>(), [(0, "acm"), (1, "cme")]);
+        assert_eq!(BanRule::new("ñb").min_length(0).seeds().count(), 2);
+        assert_eq!(BanRule::new("ab").min_length(5).seeds().count(), 1);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_banlist_find() {
+        let banlist = Banlist::default()
+            .with_rules(&[
+                BanRule::new("acme"),
+                BanRule::new("roadrunner")
+                    .min_length(5)
+                    .position(BanPosition::Start),
+            ])
+            .unwrap();
+        assert_eq!(banlist.len(), 2);
+        assert_eq!(
+            find(&banlist, "xacmex", false),
+            [(1, 4, "acme".to_string())]
+        );
+        assert_eq!(
+            find(&banlist, "runner1", false),
+            [(0, 4, "runne".to_string()), (0, 5, "runner".to_string())]
+        );
+        assert!(find(&banlist, "1runner", false).is_empty());
+        assert!(find(&banlist, "roadr1", true).is_empty());
+        assert_eq!(find(&banlist, "1roadr", true).len(), 1);
+        assert_eq!(find(&banlist, "ñacme", false), [(1, 4, "acme".to_string())]);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_banlist_finds_every_part_of_a_word() {
+        let banlist = Banlist::default()
+            .with_rules(&[
+                BanRule::new("abcab").min_length(2),
+                BanRule::new("bca").min_length(2).position(BanPosition::End),
+            ])
+            .unwrap();
+        assert_eq!(
+            find(&banlist, "xcabcx", false),
+            [
+                (1, 2, "ca".to_string()),
+                (1, 3, "cab".to_string()),
+                (2, 3, "ab".to_string()),
+                (2, 4, "abc".to_string()),
+                (3, 4, "bc".to_string()),
+            ]
+        );
+        assert_eq!(
+            find(&banlist, "bca", false),
+            [
+                (0, 1, "bc".to_string()),
+                (0, 2, "bca".to_string()),
+                (1, 2, "ca".to_string()),
+            ]
+        );
+
+        // only the parts of the minimum length are compiled, however long the word
+        let word = "abcdefghij".repeat(1_000);
+        let banlist = Banlist::default()
+            .with_rules(&[BanRule::new(&word).min_length(5)])
+            .unwrap();
+        assert_eq!(banlist.rules.patterns.len(), 10);
+        assert_eq!(find(&banlist, "xxfghijaxx", false).len(), 3);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_banlist_lists() {
+        let banlist = Banlist::default()
+            .with_rules(&[BanRule::new("acme")])
+            .unwrap()
+            .with_list("offices", list(&["springfield"]));
+        assert_eq!(banlist.len(), 2);
+        assert_eq!(find(&banlist, "springfield1", false).len(), 1);
+
+        // a list is replaced as a whole, leaving the other rules alone
+        let banlist = banlist.with_list("wifi", list(&["acmeguest"]));
+        let replaced = banlist.with_list("offices", list(&["shelbyville"]));
+        // the other lists are not compiled again
+        assert!(Arc::ptr_eq(&banlist.rules, &replaced.rules));
+        assert!(Arc::ptr_eq(&banlist.lists["wifi"], &replaced.lists["wifi"]));
+        let banlist = replaced.with_list("wifi", None);
+        assert!(find(&banlist, "springfield1", false).is_empty());
+        assert_eq!(find(&banlist, "shelbyville1", false).len(), 1);
+        assert_eq!(find(&banlist, "acme1", false).len(), 1);
+
+        let banlist = banlist.with_list("offices", None);
+        assert_eq!(banlist.len(), 1);
+        assert!(!banlist.is_empty());
+    }
+}
@@ -0,0 +1,602 @@
+//! Batch scoring of newline-delimited passwords.
+//!
+//! Lines are read in fixed-size chunks and handed to a pool of worker threads
+//! over bounded channels, so memory use stays flat regardless of input size
+//! while output order still follows input order.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::{self, BufRead, Write};
+use std::num::NonZeroUsize;
+use std::str::FromStr;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use zxcvbn::analysis::CorpusStats;
+use zxcvbn::{zxcvbn, Entropy};
+
+use crate::{pattern_name, PATTERNS};
+
+/// Number of lines handed to a worker at once.
+const CHUNK_SIZE: usize = 256;
+/// Number of chunks queued per worker before the reader blocks.
+const CHUNKS_IN_FLIGHT_PER_WORKER: usize = 2;
+
+/// How each scored password is written out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum Format {
+    /// Tab separated score, password, guesses_log10 and slow-hash crack time.
+    #[default]
+    Text,
+    /// One JSON object per line holding the selected fields.
+    Jsonl,
+    /// Comma separated values with a header row, holding the selected fields.
+    Csv,
+    /// An Apache Parquet file holding the selected fields.
+    #[cfg(feature = "parquet")]
+    Parquet,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "jsonl" => Ok(Format::Jsonl),
+            "csv" => Ok(Format::Csv),
+            #[cfg(feature = "parquet")]
+            "parquet" => Ok(Format::Parquet),
+            _ => Err(format!("unknown format {}", s)),
+        }
+    }
+}
+
+/// A field that can be selected for structured output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Field {
+    Password,
+    Score,
+    Guesses,
+    GuessesLog10,
+    CrackTime,
+    TopPattern,
+    Warning,
+    /// Pattern names of the match sequence, joined with `+`.
+    Sequence,
+    /// Number of matches of each pattern in the match sequence.
+    PatternCounts,
+}
+
+impl Field {
+    pub(crate) const DEFAULT: [Field; 3] = [Field::Password, Field::Score, Field::GuessesLog10];
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Field::Password => "password",
+            Field::Score => "score",
+            Field::Guesses => "guesses",
+            Field::GuessesLog10 => "guesses_log10",
+            Field::CrackTime => "crack_time",
+            Field::TopPattern => "top_pattern",
+            Field::Warning => "warning",
+            Field::Sequence => "sequence",
+            Field::PatternCounts => "pattern_counts",
+        }
+    }
+
+    /// Parses a comma separated list of field names.
+    pub(crate) fn parse_list(s: &str) -> Result<Vec<Field>, String> {
+        const ALL: [Field; 9] = [
+            Field::Password,
+            Field::Score,
+            Field::Guesses,
+            Field::GuessesLog10,
+            Field::CrackTime,
+            Field::TopPattern,
+            Field::Warning,
+            Field::Sequence,
+            Field::PatternCounts,
+        ];
+        s.split(',')
+            .map(|name| {
+                ALL.iter()
+                    .copied()
+                    .find(|field| field.name() == name.trim())
+                    .ok_or_else(|| format!("unknown field {}", name))
+            })
+            .collect()
+    }
+}
+
+/// Options for a batch run.
+#[derive(Debug, Clone)]
+pub(crate) struct Options {
+    pub(crate) format: Format,
+    pub(crate) fields: Vec<Field>,
+    pub(crate) threads: NonZeroUsize,
+    pub(crate) user_inputs: Vec<String>,
+    /// Whether to aggregate corpus statistics while scoring.
+    pub(crate) summary: bool,
+}
+
+/// Everything the output formats may need to know about one scored password.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Record {
+    pub(crate) password: String,
+    pub(crate) score: u8,
+    pub(crate) guesses: u64,
+    pub(crate) guesses_log10: f64,
+    pub(crate) crack_time: String,
+    pub(crate) top_pattern: Option<&'static str>,
+    pub(crate) warning: Option<String>,
+    pub(crate) sequence: String,
+    /// Indexed like [`PATTERNS`].
+    pub(crate) pattern_counts: [u32; PATTERNS.len()],
+}
+
+impl Record {
+    pub(crate) fn new(password: &str, entropy: &Entropy) -> Self {
+        let mut pattern_counts = [0; PATTERNS.len()];
+        for m in entropy.sequence() {
+            let k = PATTERNS.iter().position(|&p| p == pattern_name(m)).unwrap();
+            pattern_counts[k] += 1;
+        }
+        Record {
+            password: password.to_string(),
+            score: entropy.score().into(),
+            guesses: entropy.guesses(),
+            guesses_log10: entropy.guesses_log10(),
+            crack_time: entropy
+                .crack_times()
+                .offline_slow_hashing_1e4_per_second()
+                .to_string(),
+            top_pattern: entropy
+                .sequence()
+                .iter()
+                .max_by_key(|m| m.token.chars().count())
+                .map(pattern_name),
+            warning: entropy
+                .feedback()
+                .as_ref()
+                .and_then(|f| f.warning())
+                .map(|w| w.to_string()),
+            sequence: entropy
+                .sequence()
+                .iter()
+                .map(pattern_name)
+                .collect::<Vec<_>>()
+                .join("+"),
+            pattern_counts,
+        }
+    }
+}
+
+/// Destination for scored records, receiving them in input order.
+pub(crate) trait Sink {
+    fn write_records(&mut self, records: &[Record]) -> io::Result<()>;
+
+    /// Called once after the last record, e.g. to write a file footer.
+    fn finish(&mut self) -> io::Result<()>;
+}
+
+/// Creates the sink for the format selected in `opts`, writing to `out`.
+pub(crate) fn sink<'a>(
+    out: impl Write + Send + 'a,
+    opts: &Options,
+) -> io::Result<Box<dyn Sink + 'a>> {
+    Ok(match opts.format {
+        #[cfg(feature = "parquet")]
+        Format::Parquet => Box::new(crate::parquet::ParquetSink::new(out, &opts.fields)?),
+        format => Box::new(TextSink {
+            out,
+            format,
+            fields: opts.fields.clone(),
+            wrote_header: false,
+            buffer: String::new(),
+        }),
+    })
+}
+
+/// Scores every line of `input`, passing the records to `sink` in input order.
+/// Returns the corpus statistics, which are only aggregated if `opts.summary` is set.
+pub(crate) fn run(
+    input: impl BufRead + Send,
+    sink: &mut dyn Sink,
+    opts: &Options,
+) -> io::Result<CorpusStats> {
+    let workers = opts.threads.get();
+    let max_in_flight = workers * CHUNKS_IN_FLIGHT_PER_WORKER;
+    let (chunk_tx, chunk_rx) = mpsc::sync_channel::<(usize, Vec<String>)>(max_in_flight);
+    let (record_tx, record_rx) =
+        mpsc::sync_channel::<(usize, Vec<Record>, CorpusStats)>(max_in_flight);
+    let chunk_rx = Arc::new(Mutex::new(chunk_rx));
+    // The reader takes a permit per chunk and the writer hands it back once the chunk is
+    // written, which bounds the chunks held anywhere in the pipeline, including those
+    // finished early and waiting for their turn to be written.
+    let (permit_tx, permit_rx) = mpsc::sync_channel::<()>(max_in_flight);
+    for _ in 0..max_in_flight {
+        permit_tx.send(()).unwrap();
+    }
+
+    thread::scope(|scope| {
+        let reader = scope.spawn(move || -> io::Result<()> {
+            let mut lines = input.lines();
+            for index in 0.. {
+                if permit_rx.recv().is_err() {
+                    break;
+                }
+                let chunk = lines
+                    .by_ref()
+                    .take(CHUNK_SIZE)
+                    .collect::<io::Result<Vec<_>>>()?;
+                if chunk.is_empty() || chunk_tx.send((index, chunk)).is_err() {
+                    break;
+                }
+            }
+            Ok(())
+        });
+
+        for _ in 0..workers {
+            let chunk_rx = Arc::clone(&chunk_rx);
+            let record_tx = record_tx.clone();
+            scope.spawn(move || {
+                let user_inputs = opts
+                    .user_inputs
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>();
+                loop {
+                    let next = chunk_rx.lock().unwrap().recv();
+                    let (index, chunk) = match next {
+                        Ok(next) => next,
+                        Err(_) => break,
+                    };
+                    let mut stats = CorpusStats::new();
+                    let records = chunk
+                        .iter()
+                        .map(|password| {
+                            let entropy = zxcvbn(password, &user_inputs);
+                            if opts.summary {
+                                stats.add(&entropy);
+                            }
+                            Record::new(password, &entropy)
+                        })
+                        .collect();
+                    if record_tx.send((index, records, stats)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(record_tx);
+
+        let mut pending = BTreeMap::new();
+        let mut next_index = 0;
+        let mut result = Ok(());
+        let mut stats = CorpusStats::new();
+        'records: for (index, records, chunk_stats) in record_rx {
+            stats.merge(chunk_stats);
+            pending.insert(index, records);
+            while let Some(records) = pending.remove(&next_index) {
+                result = sink.write_records(&records);
+                if result.is_err() {
+                    // Dropping the receivers stops the workers and the reader in turn.
+                    break 'records;
+                }
+                next_index += 1;
+                let _ = permit_tx.send(());
+            }
+        }
+        drop(permit_tx);
+        reader.join().unwrap()?;
+        result.map(|()| stats)
+    })
+    .and_then(|stats| sink.finish().map(|()| stats))
+}
+
+/// Writes a plain text summary of corpus statistics for people to read.
+pub(crate) fn write_summary(out: &mut impl Write, stats: &CorpusStats) -> io::Result<()> {
+    /// Number of dictionary words listed.
+    const TOP_WORDS: usize = 10;
+
+    let passwords = stats.passwords();
+    let percent = |count: u64| 100.0 * count as f64 / passwords.max(1) as f64;
+    writeln!(out, "Passwords: {}", passwords)?;
+    if let Some(mean) = stats.mean_guesses_log10() {
+        writeln!(out, "Mean guesses: 10^{:.2}", mean)?;
+    }
+    writeln!(out, "\nScores:")?;
+    for (score, &count) in stats.score_distribution().iter().enumerate() {
+        writeln!(out, "  {}  {:>10}  {:5.1}%", score, count, percent(count))?;
+    }
+    writeln!(out, "\nPasswords containing each pattern:")?;
+    for pattern in PATTERNS {
+        writeln!(
+            out,
+            "  {:<11} {:5.1}%",
+            pattern,
+            100.0 * stats.pattern_prevalence(pattern)
+        )?;
+    }
+    let words = stats.top_dictionary_words(TOP_WORDS);
+    if !words.is_empty() {
+        writeln!(out, "\nMost common dictionary words:")?;
+        for (word, count) in words {
+            writeln!(out, "  {:<20} {:>10}", word, count)?;
+        }
+    }
+    Ok(())
+}
+
+/// Sink for the line based formats.
+struct TextSink<W> {
+    out: W,
+    format: Format,
+    fields: Vec<Field>,
+    wrote_header: bool,
+    buffer: String,
+}
+
+impl<W: Write> Sink for TextSink<W> {
+    fn write_records(&mut self, records: &[Record]) -> io::Result<()> {
+        self.buffer.clear();
+        if self.format == Format::Csv && !self.wrote_header {
+            let header = self
+                .fields
+                .iter()
+                .flat_map(|field| match field {
+                    Field::PatternCounts => {
+                        PATTERNS.iter().map(|p| format!("{}_count", p)).collect()
+                    }
+                    field => vec![field.name().to_string()],
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+            self.buffer.push_str(&header);
+            self.buffer.push('\n');
+            self.wrote_header = true;
+        }
+        for record in records {
+            match self.format {
+                Format::Jsonl => format_jsonl(&mut self.buffer, record, &self.fields),
+                Format::Csv => format_csv(&mut self.buffer, record, &self.fields),
+                _ => {
+                    let _ = writeln!(
+                        self.buffer,
+                        "{}\t{}\t{:.2}\t{}",
+                        record.score, record.password, record.guesses_log10, record.crack_time
+                    );
+                }
+            }
+        }
+        self.out.write_all(self.buffer.as_bytes())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+fn format_jsonl(out: &mut String, record: &Record, fields: &[Field]) {
+    out.push('{');
+    for (k, field) in fields.iter().enumerate() {
+        if k > 0 {
+            out.push(',');
+        }
+        push_json_string(out, field.name());
+        out.push(':');
+        match field {
+            Field::Password => push_json_string(out, &record.password),
+            Field::Score => {
+                let _ = write!(out, "{}", record.score);
+            }
+            Field::Guesses => {
+                let _ = write!(out, "{}", record.guesses);
+            }
+            Field::GuessesLog10 => {
+                // JSON has no representation for the empty password's negative infinity.
+                if record.guesses_log10.is_finite() {
+                    let _ = write!(out, "{:.4}", record.guesses_log10);
+                } else {
+                    out.push_str("null");
+                }
+            }
+            Field::CrackTime => push_json_string(out, &record.crack_time),
+            Field::TopPattern => match record.top_pattern {
+                Some(pattern) => push_json_string(out, pattern),
+                None => out.push_str("null"),
+            },
+            Field::Warning => match record.warning {
+                Some(ref warning) => push_json_string(out, warning),
+                None => out.push_str("null"),
+            },
+            Field::Sequence => push_json_string(out, &record.sequence),
+            Field::PatternCounts => {
+                out.push('{');
+                for (k, (pattern, count)) in PATTERNS.iter().zip(&record.pattern_counts).enumerate()
+                {
+                    if k > 0 {
+                        out.push(',');
+                    }
+                    push_json_string(out, pattern);
+                    let _ = write!(out, ":{}", count);
+                }
+                out.push('}');
+            }
+        }
+    }
+    out.push_str("}\n");
+}
+
+fn push_json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if u32::from(c) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn format_csv(out: &mut String, record: &Record, fields: &[Field]) {
+    for (k, field) in fields.iter().enumerate() {
+        if k > 0 {
+            out.push(',');
+        }
+        match field {
+            Field::Password => push_csv_string(out, &record.password),
+            Field::Score => {
+                let _ = write!(out, "{}", record.score);
+            }
+            Field::Guesses => {
+                let _ = write!(out, "{}", record.guesses);
+            }
+            Field::GuessesLog10 => {
+                // Left empty for the empty password, most readers treat that as missing.
+                if record.guesses_log10.is_finite() {
+                    let _ = write!(out, "{:.4}", record.guesses_log10);
+                }
+            }
+            Field::CrackTime => push_csv_string(out, &record.crack_time),
+            Field::TopPattern => out.push_str(record.top_pattern.unwrap_or_default()),
+            Field::Warning => push_csv_string(out, record.warning.as_deref().unwrap_or_default()),
+            Field::Sequence => out.push_str(&record.sequence),
+            Field::PatternCounts => {
+                let counts = record
+                    .pattern_counts
+                    .iter()
+                    .map(|c| c.to_string())
+                    .collect::<Vec<_>>();
+                out.push_str(&counts.join(","));
+            }
+        }
+    }
+    out.push('\n');
+}
+
+/// Writes `s` as a CSV field, quoting it per RFC 4180 when needed.
+fn push_csv_string(out: &mut String, s: &str) {
+    if s.contains([',', '"', '\n', '\r']) {
+        out.push('"');
+        out.push_str(&s.replace('"', "\"\""));
+        out.push('"');
+    } else {
+        out.push_str(s);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(format: Format, fields: &str, threads: usize) -> Options {
+        Options {
+            format,
+            fields: Field::parse_list(fields).unwrap(),
+            threads: NonZeroUsize::new(threads).unwrap(),
+            user_inputs: Vec::new(),
+            summary: false,
+        }
+    }
+
+    fn run_to_string(input: &str, opts: &Options) -> String {
+        let mut output = Vec::new();
+        run(
+            input.as_bytes(),
+            &mut *sink(&mut output, opts).unwrap(),
+            opts,
+        )
+        .unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut opts = options(Format::Text, "password", 3);
+        opts.summary = true;
+        let input = "password\nqwerty\npassword\n".repeat(300);
+        let stats = run(
+            input.as_bytes(),
+            &mut *sink(io::sink(), &opts).unwrap(),
+            &opts,
+        )
+        .unwrap();
+        assert_eq!(stats.passwords(), 900);
+        assert_eq!(stats.score_distribution()[0], 900);
+        assert_eq!(stats.top_dictionary_words(1), [("password", 600)]);
+
+        let mut summary = Vec::new();
+        write_summary(&mut summary, &stats).unwrap();
+        let summary = String::from_utf8(summary).unwrap();
+        assert!(summary.starts_with("Passwords: 900\n"));
+        assert!(summary.contains("  0         900  100.0%\n"));
+        assert!(summary.contains("  dictionary  100.0%\n"));
+        assert!(summary.contains("  password                    600\n"));
+
+        opts.summary = false;
+        let stats = run(
+            input.as_bytes(),
+            &mut *sink(io::sink(), &opts).unwrap(),
+            &opts,
+        )
+        .unwrap();
+        assert_eq!(stats.passwords(), 0);
+    }
+
+    #[test]
+    fn test_jsonl_output() {
+        let opts = options(Format::Jsonl, "password,score,top_pattern,warning", 1);
+        assert_eq!(
+            run_to_string("password\n\"quoted\"\n", &opts),
+            "{\"password\":\"password\",\"score\":0,\"top_pattern\":\"dictionary\",\
+             \"warning\":\"This is a top-10 common password.\"}\n\
+             {\"password\":\"\\\"quoted\\\"\",\"score\":2,\"top_pattern\":\"bruteforce\",\
+             \"warning\":null}\n"
+        );
+    }
+
+    #[test]
+    fn test_csv_output() {
+        let opts = options(Format::Csv, "password,sequence,pattern_counts", 2);
+        assert_eq!(
+            run_to_string("password1\nhi, there\n", &opts),
+            "password,sequence,dictionary_count,spatial_count,repeat_count,sequence_count,\
+             regex_count,date_count,emoji_count,interleaved_count,mirror_count,\
+             shift_repeat_count,echo_count,phone_count,national_id_count,payment_count,\
+             unlock_count,word_pair_count,acronym_count,bruteforce_count\n\
+             password1,dictionary,1,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0\n\
+             \"hi, there\",bruteforce+dictionary,1,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1\n"
+        );
+    }
+
+    #[test]
+    fn test_output_keeps_input_order() {
+        let input = (0..2000)
+            .map(|i| format!("pass{}\n", i))
+            .collect::<String>();
+        let output = run_to_string(&input, &options(Format::Jsonl, "password", 4));
+        for (i, line) in output.lines().enumerate() {
+            assert_eq!(line, format!("{{\"password\":\"pass{}\"}}", i));
+        }
+        assert_eq!(output.lines().count(), 2000);
+    }
+
+    #[test]
+    fn test_parse_fields() {
+        assert_eq!(
+            Field::parse_list("guesses_log10, score,top_pattern"),
+            Ok(vec![Field::GuessesLog10, Field::Score, Field::TopPattern])
+        );
+        assert!(Field::parse_list("score,entropy").is_err());
+    }
+}
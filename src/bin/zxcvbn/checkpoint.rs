@@ -0,0 +1,208 @@
+//! Checkpoints of long `zxcvbn report` runs, so that an interrupted run over a large file
+//! resumes where it stopped instead of starting over.
+//!
+//! A checkpoint is a sidecar file holding the byte offset of the first line not yet
+//! scored, the model version scores were estimated with and the statistics of the lines
+//! before the offset.
+
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use zxcvbn::analysis::CorpusStats;
+
+/// First line of a checkpoint file, with the version of the format.
+const HEADER: &str = "zxcvbn-checkpoint 1";
+
+/// How far a run got.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Checkpoint {
+    /// Byte offset of the first line not yet scored.
+    pub(crate) offset: u64,
+    pub(crate) stats: CorpusStats,
+}
+
+impl Checkpoint {
+    /// Reads the checkpoint at `path`, or returns `None` if there is none to resume from.
+    ///
+    /// Fails if the checkpoint was made with another model version, whose statistics can't
+    /// be mixed with new ones.
+    pub(crate) fn load(path: &Path) -> io::Result<Option<Self>> {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        let mut reader = BufReader::new(file);
+        let mut line = || -> io::Result<String> {
+            let mut line = String::new();
+            reader.read_line(&mut line)?;
+            Ok(line.trim_end_matches('\n').to_string())
+        };
+        if line()? != HEADER {
+            return Err(invalid_data(path, "not a checkpoint file"));
+        }
+        let offset = line()?
+            .strip_prefix("offset ")
+            .and_then(|offset| offset.parse().ok())
+            .ok_or_else(|| invalid_data(path, "invalid offset"))?;
+        let model_version = line()?;
+        match model_version.strip_prefix("model_version ") {
+            Some(version) if version == self::model_version() => {}
+            Some(version) => {
+                return Err(invalid_data(
+                    path,
+                    &format!(
+                        "made with model version {}, not {}; delete it to start over",
+                        version,
+                        self::model_version()
+                    ),
+                ))
+            }
+            None => return Err(invalid_data(path, "invalid model version")),
+        }
+        let stats = CorpusStats::load(reader)?;
+        Ok(Some(Checkpoint { offset, stats }))
+    }
+
+    /// Writes the checkpoint to `path`, replacing the previous one only once it is complete,
+    /// so that an interruption while saving leaves the previous checkpoint intact.
+    pub(crate) fn save(&self, path: &Path) -> io::Result<()> {
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        let mut writer = BufWriter::new(File::create(&tmp)?);
+        writeln!(writer, "{}", HEADER)?;
+        writeln!(writer, "offset {}", self.offset)?;
+        writeln!(writer, "model_version {}", model_version())?;
+        self.stats.save(&mut writer)?;
+        writer
+            .into_inner()
+            .map_err(|err| err.into_error())?
+            .sync_all()?;
+        fs::rename(tmp, path)
+    }
+}
+
+/// The model version of the estimates reports are made of.
+fn model_version() -> String {
+    zxcvbn::zxcvbn("", &[]).model_version().to_string()
+}
+
+fn invalid_data(path: &Path, message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("checkpoint {}: {}", path.display(), message),
+    )
+}
+
+/// Reads up to a number of lines from the inner reader, counting the bytes read.
+pub(crate) struct LineLimited<R> {
+    inner: R,
+    lines_left: usize,
+    pub(crate) bytes: u64,
+}
+
+impl<R: BufRead> LineLimited<R> {
+    pub(crate) fn new(inner: R, lines: usize) -> Self {
+        LineLimited {
+            inner,
+            lines_left: lines,
+            bytes: 0,
+        }
+    }
+
+    /// Whether the inner reader has no more lines.
+    pub(crate) fn is_at_end(&mut self) -> io::Result<bool> {
+        Ok(self.inner.fill_buf()?.is_empty())
+    }
+}
+
+impl<R: BufRead> Read for LineLimited<R> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        let buf = self.fill_buf()?;
+        let len = buf.len().min(out.len());
+        out[..len].copy_from_slice(&buf[..len]);
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl<R: BufRead> BufRead for LineLimited<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let buf = self.inner.fill_buf()?;
+        // up to and including the newline ending the last line still allowed
+        let allowed = match self.lines_left {
+            0 => 0,
+            lines_left => buf
+                .iter()
+                .enumerate()
+                .filter(|&(_, &b)| b == b'\n')
+                .nth(lines_left - 1)
+                .map_or(buf.len(), |(newline, _)| newline + 1),
+        };
+        Ok(&buf[..allowed])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let newlines = match self.inner.fill_buf() {
+            Ok(buf) => buf[..amt].iter().filter(|&&b| b == b'\n').count(),
+            Err(_) => 0,
+        };
+        self.lines_left -= newlines;
+        self.bytes += amt as u64;
+        self.inner.consume(amt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_limited() {
+        let mut input = BufReader::with_capacity(4, &b"one\ntwo\nthree\nfour"[..]);
+        let mut lines = LineLimited::new(&mut input, 2);
+        let read = lines
+            .by_ref()
+            .lines()
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(read, ["one", "two"]);
+        assert_eq!(lines.bytes, 8);
+        assert!(!lines.is_at_end().unwrap());
+
+        let mut lines = LineLimited::new(&mut input, 5);
+        let mut rest = String::new();
+        lines.read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, "three\nfour");
+        assert_eq!(lines.bytes, 10);
+        assert!(lines.is_at_end().unwrap());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir().join("zxcvbn-checkpoint-test");
+        let _ = fs::remove_file(&path);
+        assert_eq!(Checkpoint::load(&path).unwrap(), None);
+
+        let mut stats = CorpusStats::new();
+        stats.add(&zxcvbn::zxcvbn("password", &[]));
+        let checkpoint = Checkpoint { offset: 9, stats };
+        checkpoint.save(&path).unwrap();
+        assert_eq!(Checkpoint::load(&path).unwrap(), Some(checkpoint));
+
+        let saved = fs::read_to_string(&path).unwrap();
+        let stale = saved.replace("model_version ", "model_version 0");
+        fs::write(&path, stale).unwrap();
+        let err = Checkpoint::load(&path).unwrap_err();
+        assert!(
+            err.to_string().contains("delete it to start over"),
+            "{}",
+            err
+        );
+        fs::write(&path, "offset 9\n").unwrap();
+        assert!(Checkpoint::load(&path).is_err());
+        fs::remove_file(&path).unwrap();
+    }
+}
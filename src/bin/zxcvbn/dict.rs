@@ -0,0 +1,80 @@
+//! `zxcvbn dict`: compiling word lists into dictionary files that estimators load with
+//! `DictionaryFile`.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+
+use zxcvbn::DictionaryFile;
+
+/// A `zxcvbn dict` subcommand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Command {
+    /// Compiles the word list at `input`, one word per line with the most common first, into
+    /// the dictionary file at `output`.
+    Compile { input: String, output: String },
+}
+
+pub(crate) fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    match args.next().as_deref() {
+        Some("compile") => {}
+        Some(command) => return Err(format!("unknown dict command {}", command)),
+        None => return Err("missing dict command".to_string()),
+    }
+    let paths = args.collect::<Vec<_>>();
+    match <[String; 2]>::try_from(paths) {
+        Ok([input, output]) => Ok(Command::Compile { input, output }),
+        Err(_) => Err("dict compile takes an input and an output file".to_string()),
+    }
+}
+
+pub(crate) fn run(command: &Command) -> io::Result<()> {
+    match command {
+        Command::Compile { input, output } => {
+            let words = fs::read_to_string(input)?;
+            let mut writer = BufWriter::new(File::create(output)?);
+            DictionaryFile::compile(words.lines(), &mut writer)?;
+            writer.flush()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<Command, String> {
+        parse_args(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            args(&["compile", "words.txt", "words.zdict"]),
+            Ok(Command::Compile {
+                input: "words.txt".to_string(),
+                output: "words.zdict".to_string(),
+            })
+        );
+        assert!(args(&[]).is_err());
+        assert!(args(&["decompile", "words.zdict"]).is_err());
+        assert!(args(&["compile", "words.txt"]).is_err());
+        assert!(args(&["compile", "a.txt", "b.txt", "c.zdict"]).is_err());
+    }
+
+    #[test]
+    fn test_compile() {
+        let dir = std::env::temp_dir();
+        let input = dir.join("zxcvbn-dict-test.txt");
+        let output = dir.join("zxcvbn-dict-test.zdict");
+        fs::write(&input, "acmecorp\nroadrunner\n").unwrap();
+        run(&Command::Compile {
+            input: input.to_string_lossy().into_owned(),
+            output: output.to_string_lossy().into_owned(),
+        })
+        .unwrap();
+        let dictionary = DictionaryFile::open(&output).unwrap();
+        assert_eq!(dictionary.rank("roadrunner"), Some(2));
+        fs::remove_file(input).unwrap();
+        fs::remove_file(output).unwrap();
+    }
+}
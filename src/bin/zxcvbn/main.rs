@@ -0,0 +1,308 @@
+//! Command line interface to the zxcvbn password strength estimator.
+
+use std::env;
+use std::io;
+use std::num::NonZeroUsize;
+use std::process;
+use std::thread;
+
+use zxcvbn::analysis::CorpusStats;
+use zxcvbn::{zxcvbn, Entropy, Match};
+
+mod batch;
+mod checkpoint;
+mod dict;
+#[cfg(feature = "parquet")]
+mod parquet;
+mod repl;
+mod report;
+
+const USAGE: &str = "\
+Usage: zxcvbn [OPTIONS] [PASSWORD]...
+       zxcvbn report [OPTIONS] [FILE]
+       zxcvbn dict compile WORDS OUTPUT
+
+Estimates the strength of each PASSWORD, or of each line read from
+standard input when no password is given.
+
+Options:
+  -u, --user-input <WORD>  Penalize passwords containing WORD (repeatable)
+  -f, --format <FORMAT>    Output format: text (default), jsonl, csv
+                           or parquet (with the parquet feature)
+      --fields <FIELDS>    Comma separated fields for structured formats:
+                           password, score, guesses, guesses_log10,
+                           crack_time, top_pattern, warning, sequence,
+                           pattern_counts
+                           [default: password,score,guesses_log10]
+  -j, --threads <N>        Number of worker threads for standard input
+                           [default: number of CPUs]
+  -s, --summary            Print corpus statistics to standard error
+                           after scoring
+  -i, --interactive        Re-score the password on every keystroke
+  -h, --help               Print this help
+
+Report options, for an HTML or markdown summary of FILE or standard input:
+  -o, --output <PATH>      Write the report to PATH instead of standard output
+      --report-format <F>  html or markdown [default: from the extension of
+                           PATH, or html]
+      --checkpoint <PATH>  Save progress through FILE to PATH as it is
+                           scored, and resume from PATH if it exists
+  -u, --user-input, -j, --threads as above
+
+zxcvbn dict compile compiles WORDS, a word list with one word per line and
+the most common first, into the dictionary file OUTPUT.
+";
+
+#[derive(Debug)]
+struct Args {
+    passwords: Vec<String>,
+    interactive: bool,
+    batch: batch::Options,
+    /// Set for `zxcvbn report`, whose input file is the first of `passwords`.
+    report: Option<report::Options>,
+    /// Set for `zxcvbn dict`.
+    dict: Option<dict::Command>,
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut args = args.peekable();
+    let is_report = args.peek().map(String::as_str) == Some("report");
+    if is_report {
+        args.next();
+    }
+    let mut parsed = Args {
+        passwords: Vec::new(),
+        interactive: false,
+        batch: batch::Options {
+            format: batch::Format::default(),
+            fields: batch::Field::DEFAULT.to_vec(),
+            threads: thread::available_parallelism().unwrap_or(NonZeroUsize::new(1).unwrap()),
+            user_inputs: Vec::new(),
+            summary: false,
+        },
+        report: None,
+        dict: None,
+    };
+    if !is_report && args.peek().map(String::as_str) == Some("dict") {
+        args.next();
+        parsed.dict = Some(dict::parse_args(args)?);
+        return Ok(parsed);
+    }
+    let mut output = None;
+    let mut report_format = None;
+    let mut checkpoint = None;
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => {
+                print!("{}", USAGE);
+                process::exit(0);
+            }
+            "-i" | "--interactive" => parsed.interactive = true,
+            "-s" | "--summary" => parsed.batch.summary = true,
+            "-u" | "--user-input" => parsed.batch.user_inputs.push(value()?),
+            "-f" | "--format" => parsed.batch.format = value()?.parse()?,
+            "--fields" => parsed.batch.fields = batch::Field::parse_list(&value()?)?,
+            "-j" | "--threads" => {
+                parsed.batch.threads = value()?
+                    .parse()
+                    .map_err(|_| format!("invalid value for {}", arg))?;
+            }
+            "-o" | "--output" if is_report => output = Some(value()?),
+            "--report-format" if is_report => report_format = Some(value()?.parse()?),
+            "--checkpoint" if is_report => checkpoint = Some(value()?),
+            "--" => parsed.passwords.extend(args.by_ref()),
+            _ if arg.starts_with('-') && arg.len() > 1 => {
+                return Err(format!("unknown option {}", arg));
+            }
+            _ => parsed.passwords.push(arg),
+        }
+    }
+    if is_report {
+        if parsed.passwords.len() > 1 {
+            return Err("report takes at most one input file".to_string());
+        }
+        if checkpoint.is_some() && parsed.passwords.is_empty() {
+            return Err("--checkpoint needs an input file".to_string());
+        }
+        parsed.report = Some(report::Options {
+            input: parsed.passwords.pop(),
+            output,
+            format: report_format,
+            checkpoint,
+            batch: parsed.batch.clone(),
+        });
+    }
+    Ok(parsed)
+}
+
+fn main() {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+    let user_inputs = args
+        .batch
+        .user_inputs
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+
+    let result = if let Some(command) = &args.dict {
+        dict::run(command)
+    } else if let Some(report) = &args.report {
+        report::run(report)
+    } else if args.interactive {
+        repl::run(&user_inputs)
+    } else {
+        batch::sink(io::stdout(), &args.batch)
+            .and_then(|mut sink| {
+                if args.passwords.is_empty() {
+                    batch::run(io::BufReader::new(io::stdin()), &mut *sink, &args.batch)
+                } else {
+                    let mut stats = CorpusStats::new();
+                    let records = args
+                        .passwords
+                        .iter()
+                        .map(|password| {
+                            let entropy = zxcvbn(password, &user_inputs);
+                            stats.add(&entropy);
+                            batch::Record::new(password, &entropy)
+                        })
+                        .collect::<Vec<_>>();
+                    sink.write_records(&records)?;
+                    sink.finish()?;
+                    Ok(stats)
+                }
+            })
+            .and_then(|stats| {
+                if args.batch.summary {
+                    batch::write_summary(&mut io::stderr(), &stats)?;
+                }
+                Ok(())
+            })
+    };
+    if let Err(err) = result {
+        if err.kind() != io::ErrorKind::BrokenPipe {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+    }
+}
+
+/// Short names of all patterns, in the order used for per-pattern columns.
+pub(crate) const PATTERNS: [&str; 18] = [
+    "dictionary",
+    "spatial",
+    "repeat",
+    "sequence",
+    "regex",
+    "date",
+    "emoji",
+    "interleaved",
+    "mirror",
+    "shift_repeat",
+    "echo",
+    "phone",
+    "national_id",
+    "payment",
+    "unlock",
+    "word_pair",
+    "acronym",
+    "bruteforce",
+];
+
+/// Short name of the pattern a match was found with.
+pub(crate) fn pattern_name(m: &Match) -> &'static str {
+    m.pattern.variant()
+}
+
+/// Crack time scenarios shown in the detailed views, in display order.
+pub(crate) fn crack_time_table(entropy: &Entropy) -> [(&'static str, String); 4] {
+    let times = entropy.crack_times();
+    [
+        (
+            "online, throttled (100/hour)",
+            times.online_throttling_100_per_hour().to_string(),
+        ),
+        (
+            "online, unthrottled (10/second)",
+            times.online_no_throttling_10_per_second().to_string(),
+        ),
+        (
+            "offline, slow hash (1e4/second)",
+            times.offline_slow_hashing_1e4_per_second().to_string(),
+        ),
+        (
+            "offline, fast hash (1e10/second)",
+            times.offline_fast_hashing_1e10_per_second().to_string(),
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<Args, String> {
+        parse_args(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let parsed = args(&["-u", "alice", "--interactive", "hunter2"]).unwrap();
+        assert_eq!(parsed.batch.user_inputs, ["alice"]);
+        assert_eq!(parsed.passwords, ["hunter2"]);
+        assert!(parsed.interactive);
+
+        let parsed = args(&["--format", "jsonl", "--fields", "score", "-j", "3"]).unwrap();
+        assert_eq!(parsed.batch.format, batch::Format::Jsonl);
+        assert_eq!(parsed.batch.fields, [batch::Field::Score]);
+        assert_eq!(parsed.batch.threads.get(), 3);
+
+        assert!(!parsed.batch.summary);
+        assert!(args(&["--summary"]).unwrap().batch.summary);
+
+        let parsed = args(&["--", "-i"]).unwrap();
+        assert_eq!(parsed.passwords, ["-i"]);
+        assert!(!parsed.interactive);
+
+        let parsed = args(&["report", "dump.txt", "-o", "report.md", "-j", "2"]).unwrap();
+        let report = parsed.report.unwrap();
+        assert_eq!(report.input.as_deref(), Some("dump.txt"));
+        assert_eq!(report.output.as_deref(), Some("report.md"));
+        assert_eq!(report.format, None);
+        assert_eq!(report.batch.threads.get(), 2);
+        let parsed = args(&["report", "--report-format", "markdown"]).unwrap();
+        assert_eq!(
+            parsed.report.unwrap().format,
+            Some(report::ReportFormat::Markdown)
+        );
+        assert!(args(&["report", "a.txt", "b.txt"]).is_err());
+        let parsed = args(&["report", "dump.txt", "--checkpoint", "dump.checkpoint"]).unwrap();
+        assert_eq!(
+            parsed.report.unwrap().checkpoint.as_deref(),
+            Some("dump.checkpoint")
+        );
+        assert!(args(&["report", "--checkpoint", "dump.checkpoint"]).is_err());
+        assert!(args(&["--checkpoint", "dump.checkpoint"]).is_err());
+        assert!(args(&["-o", "report.html"]).is_err());
+        assert!(args(&["hunter2", "report"]).unwrap().report.is_none());
+
+        let parsed = args(&["dict", "compile", "words.txt", "words.zdict"]).unwrap();
+        assert!(matches!(parsed.dict, Some(dict::Command::Compile { .. })));
+        assert!(args(&["dict"]).is_err());
+        assert!(args(&["hunter2", "dict"]).unwrap().dict.is_none());
+
+        assert!(args(&["--user-input"]).is_err());
+        assert!(args(&["--frobnicate"]).is_err());
+        assert!(args(&["--format", "xml"]).is_err());
+        assert!(args(&["--threads", "0"]).is_err());
+    }
+}
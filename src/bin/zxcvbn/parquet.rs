@@ -0,0 +1,142 @@
+//! Apache Parquet output, for loading batch results straight into dataframe libraries.
+
+use std::io::{self, Write};
+use std::sync::Arc;
+
+use arrow_array::builder::{
+    Float64Builder, StringBuilder, UInt32Builder, UInt64Builder, UInt8Builder,
+};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{DataType, Field as Column, Schema, SchemaRef};
+use parquet::arrow::ArrowWriter;
+
+use crate::batch::{Field, Record, Sink};
+use crate::PATTERNS;
+
+/// Writes each chunk of records as one row group.
+pub(crate) struct ParquetSink<W: Write + Send> {
+    writer: Option<ArrowWriter<W>>,
+    schema: SchemaRef,
+    fields: Vec<Field>,
+}
+
+impl<W: Write + Send> ParquetSink<W> {
+    pub(crate) fn new(out: W, fields: &[Field]) -> io::Result<Self> {
+        let columns = fields
+            .iter()
+            .flat_map(|field| match field {
+                Field::PatternCounts => PATTERNS
+                    .iter()
+                    .map(|p| Column::new(format!("{}_count", p), DataType::UInt32, false))
+                    .collect(),
+                field => vec![Column::new(
+                    field.name(),
+                    match field {
+                        Field::Score => DataType::UInt8,
+                        Field::Guesses => DataType::UInt64,
+                        Field::GuessesLog10 => DataType::Float64,
+                        _ => DataType::Utf8,
+                    },
+                    matches!(
+                        field,
+                        Field::GuessesLog10 | Field::TopPattern | Field::Warning
+                    ),
+                )],
+            })
+            .collect::<Vec<_>>();
+        let schema = Arc::new(Schema::new(columns));
+        let writer = ArrowWriter::try_new(out, Arc::clone(&schema), None).map_err(to_io_error)?;
+        Ok(ParquetSink {
+            writer: Some(writer),
+            schema,
+            fields: fields.to_vec(),
+        })
+    }
+}
+
+impl<W: Write + Send> Sink for ParquetSink<W> {
+    fn write_records(&mut self, records: &[Record]) -> io::Result<()> {
+        let mut columns: Vec<ArrayRef> = Vec::new();
+        for field in &self.fields {
+            match field {
+                Field::Password => columns.push(strings(records, |r| Some(&r.password))),
+                Field::Score => {
+                    let mut builder = UInt8Builder::with_capacity(records.len());
+                    records.iter().for_each(|r| builder.append_value(r.score));
+                    columns.push(Arc::new(builder.finish()));
+                }
+                Field::Guesses => {
+                    let mut builder = UInt64Builder::with_capacity(records.len());
+                    records.iter().for_each(|r| builder.append_value(r.guesses));
+                    columns.push(Arc::new(builder.finish()));
+                }
+                Field::GuessesLog10 => {
+                    let mut builder = Float64Builder::with_capacity(records.len());
+                    for r in records {
+                        builder.append_option(Some(r.guesses_log10).filter(|x| x.is_finite()));
+                    }
+                    columns.push(Arc::new(builder.finish()));
+                }
+                Field::CrackTime => columns.push(strings(records, |r| Some(&r.crack_time))),
+                Field::TopPattern => columns.push(strings(records, |r| r.top_pattern)),
+                Field::Warning => columns.push(strings(records, |r| r.warning.as_deref())),
+                Field::Sequence => columns.push(strings(records, |r| Some(&r.sequence))),
+                Field::PatternCounts => {
+                    for k in 0..PATTERNS.len() {
+                        let mut builder = UInt32Builder::with_capacity(records.len());
+                        records
+                            .iter()
+                            .for_each(|r| builder.append_value(r.pattern_counts[k]));
+                        columns.push(Arc::new(builder.finish()));
+                    }
+                }
+            }
+        }
+        let batch =
+            RecordBatch::try_new(Arc::clone(&self.schema), columns).map_err(io::Error::other)?;
+        self.writer
+            .as_mut()
+            .expect("write after finish")
+            .write(&batch)
+            .map_err(to_io_error)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        match self.writer.take() {
+            Some(writer) => writer.close().map(|_| ()).map_err(to_io_error),
+            None => Ok(()),
+        }
+    }
+}
+
+fn strings<'a>(records: &'a [Record], value: impl Fn(&'a Record) -> Option<&'a str>) -> ArrayRef {
+    let mut builder = StringBuilder::new();
+    records.iter().for_each(|r| builder.append_option(value(r)));
+    Arc::new(builder.finish())
+}
+
+fn to_io_error(err: parquet::errors::ParquetError) -> io::Error {
+    io::Error::other(err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zxcvbn::zxcvbn;
+
+    #[test]
+    fn test_parquet_output() {
+        let fields = Field::parse_list("password,score,guesses_log10,pattern_counts").unwrap();
+        let mut output = Vec::new();
+        let mut sink = ParquetSink::new(&mut output, &fields).unwrap();
+        let records = ["password", ""]
+            .iter()
+            .map(|p| Record::new(p, &zxcvbn(p, &[])))
+            .collect::<Vec<_>>();
+        sink.write_records(&records).unwrap();
+        sink.finish().unwrap();
+        drop(sink);
+        assert_eq!(&output[..4], b"PAR1");
+        assert_eq!(&output[output.len() - 4..], b"PAR1");
+    }
+}
@@ -0,0 +1,191 @@
+//! Interactive mode: the password is re-scored on every keystroke and the
+//! result redrawn in place, which makes for an easy live demo.
+
+use std::io::{self, Write};
+
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Color, Print, ResetColor, SetForegroundColor, Stylize};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+
+use zxcvbn::display::{format_guesses, Locale};
+use zxcvbn::matching::patterns::MatchPattern;
+use zxcvbn::{zxcvbn, Entropy, Match, Score};
+
+use crate::{crack_time_table, pattern_name};
+
+/// Width of the strength meter, in cells.
+const METER_WIDTH: usize = 20;
+
+/// Puts the terminal into raw mode on an alternate screen and restores it on drop,
+/// so the user's shell is left intact even if rendering fails midway.
+struct RawTerminal;
+
+impl RawTerminal {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+        Ok(RawTerminal)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+pub(crate) fn run(user_inputs: &[&str]) -> io::Result<()> {
+    let _terminal = RawTerminal::enter()?;
+    let mut stdout = io::stdout();
+    let mut password = String::new();
+    loop {
+        render(&mut stdout, &password, &zxcvbn(&password, user_inputs))?;
+        let key = match event::read()? {
+            Event::Key(key) if key.kind != KeyEventKind::Release => key,
+            _ => continue,
+        };
+        match key {
+            KeyEvent {
+                code: KeyCode::Esc, ..
+            } => break,
+            KeyEvent {
+                code: KeyCode::Char('c') | KeyCode::Char('d'),
+                modifiers,
+                ..
+            } if modifiers.contains(KeyModifiers::CONTROL) => break,
+            KeyEvent {
+                code: KeyCode::Char('u'),
+                modifiers,
+                ..
+            } if modifiers.contains(KeyModifiers::CONTROL) => password.clear(),
+            KeyEvent {
+                code: KeyCode::Backspace,
+                ..
+            } => {
+                password.pop();
+            }
+            KeyEvent {
+                code: KeyCode::Char(c),
+                ..
+            } => password.push(c),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn render(out: &mut impl Write, password: &str, entropy: &Entropy) -> io::Result<()> {
+    queue!(
+        out,
+        Clear(ClearType::All),
+        MoveTo(0, 0),
+        Print("Password: ")
+    )?;
+    print_colored_spans(out, password, entropy.sequence())?;
+    queue!(out, Print("\r\n\r\n"))?;
+
+    let filled = METER_WIDTH * usize::from(u8::from(entropy.score())) / 4;
+    queue!(
+        out,
+        Print("Strength: ["),
+        Print("#".repeat(filled).with(score_color(entropy.score()))),
+        Print("-".repeat(METER_WIDTH - filled)),
+        Print(format!(
+            "] {}/4   guesses: {}\r\n\r\n",
+            entropy.score(),
+            format_guesses(entropy.guesses(), Locale::English)
+        )),
+    )?;
+
+    if !entropy.sequence().is_empty() {
+        queue!(out, Print("Matches:\r\n"))?;
+        for m in entropy.sequence() {
+            queue!(
+                out,
+                Print("  "),
+                Print(format!("{:<11}", pattern_name(m)).with(pattern_color(m))),
+                Print(format!(
+                    "{:<24} 10^{:.1}\r\n",
+                    format!("{:?}", m.token),
+                    (m.guesses.unwrap_or(1) as f64).log10()
+                )),
+            )?;
+        }
+        queue!(out, Print("\r\n"))?;
+    }
+
+    queue!(out, Print("Crack times:\r\n"))?;
+    for (scenario, time) in &crack_time_table(entropy) {
+        queue!(out, Print(format!("  {:<34}{}\r\n", scenario, time)))?;
+    }
+
+    if let Some(feedback) = entropy.feedback() {
+        queue!(out, Print("\r\n"))?;
+        if let Some(warning) = feedback.warning() {
+            queue!(out, Print(format!("Warning: {}\r\n", warning).yellow()))?;
+        }
+        for suggestion in feedback.suggestions() {
+            queue!(out, Print(format!("  - {}\r\n", suggestion)))?;
+        }
+        if let Some(addition) = feedback.minimum_addition() {
+            queue!(out, Print(format!("  - {}\r\n", addition)))?;
+        }
+        if let Some(recipe) = feedback.recipe() {
+            queue!(out, Print(format!("Recipe: {}\r\n", recipe)))?;
+        }
+    }
+
+    queue!(
+        out,
+        Print("\r\nEsc or Ctrl-C to quit, Ctrl-U to clear.".dark_grey())
+    )?;
+    out.flush()
+}
+
+fn print_colored_spans(out: &mut impl Write, password: &str, sequence: &[Match]) -> io::Result<()> {
+    // Tokens are taken from the normalized password, which may differ from what was typed.
+    for m in sequence {
+        queue!(
+            out,
+            SetForegroundColor(pattern_color(m)),
+            Print(&m.token),
+            ResetColor
+        )?;
+    }
+    // Only the first 100 characters are scored, show the rest as-is.
+    queue!(out, Print(password.chars().skip(100).collect::<String>()))
+}
+
+fn pattern_color(m: &Match) -> Color {
+    match m.pattern {
+        MatchPattern::Dictionary(_) => Color::Red,
+        MatchPattern::Spatial(_) => Color::Yellow,
+        MatchPattern::Repeat(_) => Color::Magenta,
+        MatchPattern::Sequence(_) => Color::Cyan,
+        MatchPattern::Regex(_) => Color::Blue,
+        MatchPattern::Date(_) => Color::Green,
+        MatchPattern::Emoji(_) => Color::DarkYellow,
+        MatchPattern::Interleaved(_) => Color::DarkCyan,
+        MatchPattern::Mirror(_) => Color::DarkMagenta,
+        MatchPattern::ShiftRepeat(_) => Color::DarkYellow,
+        MatchPattern::Echo(_) => Color::DarkYellow,
+        MatchPattern::Phone(_) => Color::DarkGreen,
+        MatchPattern::Unlock(_) => Color::DarkBlue,
+        MatchPattern::WordPair(_) => Color::Red,
+        MatchPattern::Acronym(_) => Color::DarkRed,
+        MatchPattern::NationalId(_) | MatchPattern::Payment(_) => Color::DarkRed,
+        MatchPattern::BruteForce => Color::Reset,
+        _ => Color::Reset,
+    }
+}
+
+fn score_color(score: Score) -> Color {
+    match score {
+        Score::TooGuessable | Score::VeryGuessable => Color::Red,
+        Score::SomewhatGuessable => Color::Yellow,
+        Score::SafelyUnguessable | Score::VeryUnguessable => Color::Green,
+    }
+}
@@ -0,0 +1,446 @@
+//! `zxcvbn report`: corpus statistics rendered as a self-contained HTML or markdown
+//! document, for sharing with people who won't read per-password output.
+
+use std::f64::consts::PI;
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io::{self, BufRead, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use zxcvbn::analysis::CorpusStats;
+
+use crate::batch::{self, Record, Sink};
+use crate::checkpoint::{Checkpoint, LineLimited};
+use crate::PATTERNS;
+
+/// Number of lines scored between checkpoints.
+const CHECKPOINT_LINES: usize = 100_000;
+
+/// Number of dictionary words listed in a report.
+const TOP_WORDS: usize = 20;
+/// Bar colors of the score histogram, by score.
+const SCORE_COLORS: [&str; 5] = ["#d73027", "#fc8d59", "#fee08b", "#91cf60", "#1a9850"];
+/// Slice colors of the pattern pie chart, indexed like [`PATTERNS`].
+const PATTERN_COLORS: [&str; 11] = [
+    "#e41a1c", "#ff7f00", "#984ea3", "#377eb8", "#4daf4a", "#a65628", "#f781bf", "#17becf",
+    "#bcbd22", "#8c564b", "#999999",
+];
+
+/// How a report is written out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReportFormat {
+    Html,
+    Markdown,
+}
+
+impl ReportFormat {
+    /// Guesses the format from the extension of the output file, defaulting to HTML.
+    pub(crate) fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("md") | Some("markdown") => ReportFormat::Markdown,
+            _ => ReportFormat::Html,
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "html" => Ok(ReportFormat::Html),
+            "markdown" | "md" => Ok(ReportFormat::Markdown),
+            _ => Err(format!("unknown report format {}", s)),
+        }
+    }
+}
+
+/// Options for `zxcvbn report`.
+#[derive(Debug, Clone)]
+pub(crate) struct Options {
+    /// Password file to read, standard input if not given.
+    pub(crate) input: Option<String>,
+    /// File to write the report to, standard output if not given.
+    pub(crate) output: Option<String>,
+    /// Report format, guessed from `output` if not given.
+    pub(crate) format: Option<ReportFormat>,
+    /// Checkpoint file to resume from and save progress to, only with an `input` file.
+    pub(crate) checkpoint: Option<String>,
+    pub(crate) batch: batch::Options,
+}
+
+/// Sink discarding the records, as a report only needs the statistics.
+struct NullSink;
+
+impl Sink for NullSink {
+    fn write_records(&mut self, _records: &[Record]) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+pub(crate) fn run(opts: &Options) -> io::Result<()> {
+    let mut batch_opts = opts.batch.clone();
+    batch_opts.summary = true;
+    let stats = match (&opts.input, &opts.checkpoint) {
+        (Some(path), Some(checkpoint)) => scores_with_checkpoints(
+            File::open(path)?,
+            checkpoint.as_ref(),
+            CHECKPOINT_LINES,
+            &batch_opts,
+        )?,
+        (Some(path), None) => scores(io::BufReader::new(File::open(path)?), &batch_opts)?,
+        (None, _) => scores(io::BufReader::new(io::stdin()), &batch_opts)?,
+    };
+    let title = match &opts.input {
+        Some(path) => format!("Password strength report: {}", path),
+        None => "Password strength report".to_string(),
+    };
+    let format = opts.format.unwrap_or_else(|| {
+        opts.output.as_deref().map_or(ReportFormat::Html, |path| {
+            ReportFormat::from_path(path.as_ref())
+        })
+    });
+    let report = match format {
+        ReportFormat::Html => render_html(&title, &stats),
+        ReportFormat::Markdown => render_markdown(&title, &stats),
+    };
+    match &opts.output {
+        Some(path) => File::create(path)?.write_all(report.as_bytes())?,
+        None => io::stdout().write_all(report.as_bytes())?,
+    }
+    // the run is complete, a new one starts over
+    match opts.checkpoint.as_ref().map(fs::remove_file) {
+        Some(Err(err)) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+fn scores(input: impl BufRead + Send, opts: &batch::Options) -> io::Result<CorpusStats> {
+    batch::run(input, &mut NullSink, opts)
+}
+
+/// Scores the lines of `file` like [`scores`], resuming from the checkpoint at `path` if
+/// there is one, and saving a checkpoint there every `lines_per_checkpoint` lines.
+fn scores_with_checkpoints(
+    mut file: File,
+    path: &Path,
+    lines_per_checkpoint: usize,
+    opts: &batch::Options,
+) -> io::Result<CorpusStats> {
+    let mut checkpoint = Checkpoint::load(path)?.unwrap_or(Checkpoint {
+        offset: 0,
+        stats: CorpusStats::new(),
+    });
+    file.seek(SeekFrom::Start(checkpoint.offset))?;
+    let mut input = io::BufReader::new(file);
+    loop {
+        let mut lines = LineLimited::new(&mut input, lines_per_checkpoint);
+        if lines.is_at_end()? {
+            return Ok(checkpoint.stats);
+        }
+        let stats = scores(&mut lines, opts)?;
+        checkpoint.offset += lines.bytes;
+        checkpoint.stats.merge(stats);
+        checkpoint.save(path)?;
+    }
+}
+
+/// Percentage of `count` in `total`, 0 for an empty corpus.
+fn percent(count: u64, total: u64) -> f64 {
+    100.0 * count as f64 / total.max(1) as f64
+}
+
+pub(crate) fn render_markdown(title: &str, stats: &CorpusStats) -> String {
+    let passwords = stats.passwords();
+    let mut out = String::new();
+    let _ = writeln!(out, "# {}\n", escape_markdown(title));
+    let _ = writeln!(out, "- Passwords: {}", passwords);
+    if let Some(mean) = stats.mean_guesses_log10() {
+        let _ = writeln!(out, "- Mean guesses: 10^{:.2}", mean);
+    }
+
+    out.push_str("\n## Scores\n\n| Score | Passwords | Share | |\n|---:|---:|---:|:---|\n");
+    for (score, &count) in stats.score_distribution().iter().enumerate() {
+        let share = percent(count, passwords);
+        let _ = writeln!(
+            out,
+            "| {} | {} | {:.1}% | {} |",
+            score,
+            count,
+            share,
+            "█".repeat((share / 5.0).round() as usize)
+        );
+    }
+
+    out.push_str("\n## Pattern mix\n\n| Pattern | Matches | Share of matches | Passwords containing it |\n|:---|---:|---:|---:|\n");
+    let total_matches = stats.pattern_matches().values().sum();
+    for pattern in PATTERNS {
+        let count = stats.pattern_matches().get(pattern).copied().unwrap_or(0);
+        let _ = writeln!(
+            out,
+            "| {} | {} | {:.1}% | {:.1}% |",
+            pattern,
+            count,
+            percent(count, total_matches),
+            100.0 * stats.pattern_prevalence(pattern)
+        );
+    }
+
+    let words = stats.top_dictionary_words(TOP_WORDS);
+    if !words.is_empty() {
+        out.push_str("\n## Most common base words\n\n| Word | Matches |\n|:---|---:|\n");
+        for (word, count) in words {
+            let _ = writeln!(out, "| {} | {} |", escape_markdown(word), count);
+        }
+    }
+    out
+}
+
+pub(crate) fn render_html(title: &str, stats: &CorpusStats) -> String {
+    let passwords = stats.passwords();
+    let title = escape_html(title);
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>\n\
+         body {{ font-family: sans-serif; max-width: 60em; margin: 2em auto; color: #222; }}\n\
+         table {{ border-collapse: collapse; }}\n\
+         td, th {{ padding: 0.2em 0.8em; border-bottom: 1px solid #ddd; text-align: right; }}\n\
+         td:first-child, th:first-child {{ text-align: left; }}\n\
+         .charts {{ display: flex; flex-wrap: wrap; gap: 3em; align-items: flex-start; }}\n\
+         </style>\n</head>\n<body>\n<h1>{title}</h1>\n<p>Passwords: {passwords}",
+    );
+    if let Some(mean) = stats.mean_guesses_log10() {
+        let _ = write!(out, " &middot; Mean guesses: 10<sup>{:.2}</sup>", mean);
+    }
+    out.push_str("</p>\n<div class=\"charts\">\n");
+    write_score_histogram(&mut out, stats);
+    write_pattern_pie(&mut out, stats);
+    out.push_str("</div>\n");
+
+    let words = stats.top_dictionary_words(TOP_WORDS);
+    if !words.is_empty() {
+        out.push_str(
+            "<h2>Most common base words</h2>\n<table>\n<tr><th>Word</th><th>Matches</th></tr>\n",
+        );
+        for (word, count) in words {
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td>{}</td></tr>",
+                escape_html(word),
+                count
+            );
+        }
+        out.push_str("</table>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn write_score_histogram(out: &mut String, stats: &CorpusStats) {
+    const WIDTH: f64 = 50.0;
+    const HEIGHT: f64 = 200.0;
+
+    let scores = stats.score_distribution();
+    let max = scores.iter().copied().max().unwrap_or(0).max(1);
+    out.push_str("<figure>\n<h2>Scores</h2>\n<svg width=\"300\" height=\"250\" role=\"img\">\n");
+    for (score, &count) in scores.iter().enumerate() {
+        let height = HEIGHT * count as f64 / max as f64;
+        let x = 10.0 + score as f64 * (WIDTH + 8.0);
+        let _ = writeln!(
+            out,
+            "<rect x=\"{x}\" y=\"{y:.1}\" width=\"{WIDTH}\" height=\"{height:.1}\" fill=\"{color}\">\
+             <title>{count} ({share:.1}%)</title></rect>\n\
+             <text x=\"{tx}\" y=\"{ty}\" text-anchor=\"middle\">{score}</text>",
+            y = 10.0 + HEIGHT - height,
+            color = SCORE_COLORS[score],
+            share = percent(count, stats.passwords()),
+            tx = x + WIDTH / 2.0,
+            ty = HEIGHT + 30.0,
+        );
+    }
+    out.push_str("</svg>\n</figure>\n");
+}
+
+fn write_pattern_pie(out: &mut String, stats: &CorpusStats) {
+    const RADIUS: f64 = 100.0;
+    const CENTER: f64 = 110.0;
+
+    let counts = PATTERNS
+        .iter()
+        .map(|pattern| stats.pattern_matches().get(pattern).copied().unwrap_or(0))
+        .collect::<Vec<_>>();
+    let total = counts.iter().sum::<u64>();
+    out.push_str(
+        "<figure>\n<h2>Pattern mix</h2>\n<svg width=\"220\" height=\"220\" role=\"img\">\n",
+    );
+    let mut angle = 0.0;
+    for (k, &count) in counts.iter().enumerate().filter(|&(_, &count)| count > 0) {
+        let share = count as f64 / total as f64;
+        let tooltip = format!("{}: {} ({:.1}%)", PATTERNS[k], count, 100.0 * share);
+        if count == total {
+            // A single pattern is a full circle, which an arc can't draw.
+            let _ = writeln!(
+                out,
+                "<circle cx=\"{CENTER}\" cy=\"{CENTER}\" r=\"{RADIUS}\" fill=\"{}\"><title>{}</title></circle>",
+                PATTERN_COLORS[k], tooltip
+            );
+            break;
+        }
+        let point = |angle: f64| {
+            (
+                CENTER + RADIUS * (angle - PI / 2.0).cos(),
+                CENTER + RADIUS * (angle - PI / 2.0).sin(),
+            )
+        };
+        let (x1, y1) = point(angle);
+        angle += 2.0 * PI * share;
+        let (x2, y2) = point(angle);
+        let _ = writeln!(
+            out,
+            "<path d=\"M{CENTER},{CENTER} L{x1:.2},{y1:.2} A{RADIUS},{RADIUS} 0 {large},1 {x2:.2},{y2:.2} Z\" \
+             fill=\"{}\"><title>{}</title></path>",
+            PATTERN_COLORS[k],
+            tooltip,
+            large = u8::from(share > 0.5),
+        );
+    }
+    out.push_str("</svg>\n<ul>\n");
+    for (k, &count) in counts.iter().enumerate().filter(|&(_, &count)| count > 0) {
+        let _ = writeln!(
+            out,
+            "<li><span style=\"color: {}\">&#9632;</span> {} {:.1}%</li>",
+            PATTERN_COLORS[k],
+            PATTERNS[k],
+            percent(count, total)
+        );
+    }
+    out.push_str("</ul>\n</figure>\n");
+}
+
+fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn escape_markdown(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if "\\`*_{}[]<>()#+-.!|".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::num::NonZeroUsize;
+
+    fn stats_options() -> batch::Options {
+        batch::Options {
+            format: batch::Format::default(),
+            fields: Vec::new(),
+            threads: NonZeroUsize::new(2).unwrap(),
+            user_inputs: Vec::new(),
+            summary: true,
+        }
+    }
+
+    fn stats(input: &str) -> CorpusStats {
+        scores(input.as_bytes(), &stats_options()).unwrap()
+    }
+
+    #[test]
+    fn test_scores_with_checkpoints() {
+        let dir = std::env::temp_dir();
+        let input = dir.join("zxcvbn-report-checkpoint-test.txt");
+        let checkpoint = dir.join("zxcvbn-report-checkpoint-test.checkpoint");
+        let passwords = "password\nqwerty\nmonkey1987\nTr0ub4dour&3\ndragon\n";
+        fs::write(&input, passwords).unwrap();
+        let _ = fs::remove_file(&checkpoint);
+        let opts = batch::Options {
+            summary: true,
+            ..stats_options()
+        };
+        let resumed = |lines| {
+            scores_with_checkpoints(File::open(&input).unwrap(), &checkpoint, lines, &opts).unwrap()
+        };
+
+        assert_eq!(resumed(2), stats(passwords));
+        let saved = Checkpoint::load(&checkpoint).unwrap().unwrap();
+        assert_eq!(saved.offset, passwords.len() as u64);
+
+        // as if interrupted after the first two lines
+        Checkpoint {
+            offset: "password\nqwerty\n".len() as u64,
+            stats: stats("password\nqwerty\n"),
+        }
+        .save(&checkpoint)
+        .unwrap();
+        assert_eq!(resumed(100), stats(passwords));
+        fs::remove_file(&input).unwrap();
+        fs::remove_file(&checkpoint).unwrap();
+    }
+
+    #[test]
+    fn test_report_format() {
+        assert_eq!(
+            ReportFormat::from_path("out/report.md".as_ref()),
+            ReportFormat::Markdown
+        );
+        assert_eq!(
+            ReportFormat::from_path("report.html".as_ref()),
+            ReportFormat::Html
+        );
+        assert_eq!(
+            ReportFormat::from_path("report".as_ref()),
+            ReportFormat::Html
+        );
+        assert_eq!("md".parse(), Ok(ReportFormat::Markdown));
+        assert!("pdf".parse::<ReportFormat>().is_err());
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let report = render_markdown("dump_1.txt", &stats("password\npassword\nqwerty\n"));
+        assert!(report.starts_with("# dump\\_1\\.txt\n\n- Passwords: 3\n"));
+        assert!(report.contains("| 0 | 3 | 100.0% | ████████████████████ |\n"));
+        assert!(report.contains("| dictionary | 3 | 100.0% | 100.0% |\n"));
+        assert!(report.contains("| password | 2 |\n| qwerty | 1 |\n"));
+    }
+
+    #[test]
+    fn test_render_html() {
+        let report = render_html("<dump>", &stats("password\nqwerty\nzxcvbn1987\n"));
+        assert!(report.starts_with("<!DOCTYPE html>"));
+        assert!(report.contains("<title>&lt;dump&gt;</title>"));
+        assert!(report.contains("<tr><td>password</td><td>1</td></tr>"));
+        // Several patterns make up the pie, so it is drawn in slices.
+        assert!(report.contains("<path d=\"M110,110 L110.00,10.00 A100,100"));
+        assert!(report.ends_with("</html>\n"));
+
+        let report = render_html("empty", &stats(""));
+        assert!(report.contains("Passwords: 0</p>"));
+        assert!(!report.contains("<path"));
+    }
+}
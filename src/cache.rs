@@ -0,0 +1,217 @@
+//! Memoizing estimates of passwords that are checked again and again, such as the weak
+//! passwords signup forms see all the time.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::Mutex;
+
+use crate::util::secure::{Digest, Pepper};
+use crate::{Entropy, Zxcvbn};
+
+/// A bounded cache of estimates, evicting the least recently used one when full.
+///
+/// Entries are looked up by a hash keyed with a random key of this cache, so passwords are
+/// never stored as keys and can't be checked against the cache without the key. The cached
+/// estimates still hold the matched parts of passwords, like any [`Entropy`].
+pub(crate) struct EntropyCache {
+    pepper: Pepper,
+    capacity: usize,
+    entries: Mutex<Entries>,
+}
+
+#[derive(Default)]
+struct Entries {
+    by_digest: HashMap<Digest, (Entropy, u64)>,
+    /// Digests by when they were last used, least recently used first.
+    by_use: BTreeMap<u64, Digest>,
+    uses: u64,
+    /// How many times the cache was cleared.
+    generation: u64,
+}
+
+impl EntropyCache {
+    fn new(capacity: usize) -> Self {
+        EntropyCache {
+            pepper: Pepper::random(),
+            capacity,
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+
+    pub(crate) fn digest(&self, password: &str, user_inputs: &[&str]) -> Digest {
+        self.pepper.digest_with_inputs(password, user_inputs)
+    }
+
+    pub(crate) fn get(&self, digest: &Digest) -> Option<Entropy> {
+        let mut entries = self.lock();
+        let Entries {
+            by_digest,
+            by_use,
+            uses,
+            ..
+        } = &mut *entries;
+        let (entropy, last_use) = by_digest.get_mut(digest)?;
+        by_use.remove(last_use);
+        *uses += 1;
+        *last_use = *uses;
+        by_use.insert(*uses, *digest);
+        Some(entropy.clone())
+    }
+
+    /// The generation to insert an estimate with, read before estimating it.
+    pub(crate) fn generation(&self) -> u64 {
+        self.lock().generation
+    }
+
+    /// Caches an estimate, unless the cache was cleared since `generation` was read, in
+    /// which case the estimate may have been made with the words from before.
+    pub(crate) fn insert(&self, digest: Digest, entropy: &Entropy, generation: u64) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.lock();
+        if entries.generation != generation {
+            return;
+        }
+        entries.uses += 1;
+        let uses = entries.uses;
+        if let Some((_, last_use)) = entries.by_digest.insert(digest, (entropy.clone(), uses)) {
+            entries.by_use.remove(&last_use);
+        }
+        entries.by_use.insert(uses, digest);
+        while entries.by_digest.len() > self.capacity {
+            let (&last_use, &oldest) = entries.by_use.iter().next().unwrap();
+            entries.by_use.remove(&last_use);
+            entries.by_digest.remove(&oldest);
+        }
+    }
+
+    /// Drops every cached estimate, as they are stale once words or dictionaries are added.
+    pub(crate) fn clear(&self) {
+        let mut entries = self.lock();
+        *entries = Entries {
+            generation: entries.generation + 1,
+            ..Entries::default()
+        };
+    }
+
+    fn len(&self) -> usize {
+        self.lock().by_digest.len()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        // the entries are consistent whenever the lock is released, even by a panic
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl fmt::Debug for EntropyCache {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EntropyCache")
+            .field("capacity", &self.capacity)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl Zxcvbn {
+    /// Remembers the estimates of the `capacity` passwords estimated most recently, returning
+    /// them again when the same password is estimated with the same user inputs, including
+    /// their calculation time.
+    ///
+    /// Passwords are looked up by their digest with a random [`Pepper`] of this estimator, so
+    /// they are not kept as keys, though the cached estimates hold the matched parts of
+    /// passwords like any [`Entropy`]. Adding banned words, banned passwords or dictionaries
+    /// empties the cache.
+    ///
+    /// ```
+    /// use zxcvbn::Zxcvbn;
+    ///
+    /// let estimator = Zxcvbn::new().with_cache(10_000);
+    /// let entropy = estimator.estimate("password1", &[]);
+    /// let cached = estimator.estimate("password1", &[]);
+    /// assert_eq!(cached.calculation_time(), entropy.calculation_time());
+    /// ```
+    pub fn with_cache(mut self, capacity: usize) -> Self {
+        self.cache = Some(EntropyCache::new(capacity));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    fn cache(estimator: &Zxcvbn) -> &EntropyCache {
+        estimator.cache.as_ref().unwrap()
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_cache() {
+        let estimator = Zxcvbn::new().with_cache(2);
+        let entropy = estimator.estimate("password", &[]);
+        let cached = estimator.estimate("password", &[]);
+        assert_eq!(cached.guesses(), entropy.guesses());
+        assert_eq!(cached.sequence(), entropy.sequence());
+        assert_eq!(cached.calculation_time(), entropy.calculation_time());
+        assert_eq!(cache(&estimator).len(), 1);
+
+        // user inputs are part of the key
+        let with_inputs = estimator.estimate("password", &["password"]);
+        assert!(with_inputs.guesses() < entropy.guesses());
+        assert_eq!(cache(&estimator).len(), 2);
+
+        // "password" was used less recently than "password" with user inputs
+        estimator.estimate("password", &["password"]);
+        estimator.estimate("hunter2", &[]);
+        assert_eq!(cache(&estimator).len(), 2);
+        let digest = cache(&estimator).digest("password", &[]);
+        assert!(cache(&estimator).get(&digest).is_none());
+        let digest = cache(&estimator).digest("password", &["password"]);
+        assert!(cache(&estimator).get(&digest).is_some());
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_cache_is_cleared() {
+        let estimator = Zxcvbn::new().with_cache(10);
+        let entropy = estimator.estimate("SummerSale2026", &[]);
+        estimator.add_banned_words(["summersale"]).unwrap();
+        assert_eq!(cache(&estimator).len(), 0);
+        assert!(estimator.estimate("SummerSale2026", &[]).guesses() < entropy.guesses());
+
+        estimator.add_banned_passwords(["summersale2026"]);
+        assert_eq!(estimator.estimate("SummerSale2026", &[]).guesses(), 1);
+
+        // estimates started before clearing are not cached
+        let cache = cache(&estimator);
+        let generation = cache.generation();
+        let entropy = estimator.estimate("hunter2", &[]);
+        cache.clear();
+        cache.insert(cache.digest("hunter2", &[]), &entropy, generation);
+        assert_eq!(cache.len(), 0);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_digest() {
+        let estimator = Zxcvbn::new().with_cache(0);
+        let cache = cache(&estimator);
+        assert_ne!(cache.digest("ab", &["c"]), cache.digest("a", &["bc"]));
+        assert_eq!(cache.digest("ab", &["c"]), cache.digest("ab", &["c"]));
+        let other = Zxcvbn::new().with_cache(0);
+        assert_ne!(
+            cache.digest("ab", &[]),
+            other.cache.as_ref().unwrap().digest("ab", &[])
+        );
+
+        estimator.estimate("password", &[]);
+        assert_eq!(cache.len(), 0);
+    }
+}
@@ -0,0 +1,194 @@
+//! Words about the site a password is chosen for, which users are as likely to build their
+//! passwords from as their own name.
+//!
+//! A [`Context`] splits the names and addresses it is given into words, on punctuation, on
+//! camel case and between letters and digits, and [`Zxcvbn::with_context`] adds them to the
+//! user inputs of every estimate:
+//!
+//! ```
+//! use zxcvbn::context::Context;
+//! use zxcvbn::{Score, Zxcvbn};
+//!
+//! let context = Context::new()
+//!     .site_name("MyApp")
+//!     .company("Acme Widgets, Inc.")
+//!     .url("https://www.myapp.com/signup");
+//! assert_eq!(context.words(), ["myapp", "app", "acme", "widgets", "inc"]);
+//!
+//! let estimator = Zxcvbn::new().with_context(context);
+//! let entropy = estimator.estimate("myapp2024", &[]);
+//! assert_eq!(entropy.score(), Score::VeryGuessable);
+//! assert!(entropy.guesses() < Zxcvbn::new().estimate("myapp2024", &[]).guesses());
+//! ```
+
+use crate::Zxcvbn;
+
+/// Words shorter than this are left out, as they would match inside unrelated passwords.
+pub(crate) const MIN_WORD_LENGTH: usize = 3;
+
+/// Labels of host names that say nothing about the site.
+const IGNORED_HOST_LABELS: &[&str] = &["www", "app", "login", "auth", "accounts", "mail"];
+
+/// The words of a site's names and addresses, added to the user inputs of every estimate by
+/// [`Zxcvbn::with_context`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Context {
+    words: Vec<String>,
+}
+
+impl Context {
+    /// Creates a context with no words.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the words of the site's name, e.g. "my" and "app" as well as "myapp" for "MyApp".
+    pub fn site_name(self, name: &str) -> Self {
+        self.text(name)
+    }
+
+    /// Adds the words of the name of the company running the site.
+    pub fn company(self, name: &str) -> Self {
+        self.text(name)
+    }
+
+    /// Adds the words of the host of a URL, e.g. "myapp" for `https://www.myapp.com/signup`.
+    /// The top-level domain and labels such as "www" are left out.
+    pub fn url(self, url: &str) -> Self {
+        let host = url.split_once("://").map_or(url, |(_, rest)| rest);
+        let host = host.split(['/', '?', '#']).next().unwrap_or_default();
+        let host = host.rsplit('@').next().unwrap_or_default();
+        let host = host.split(':').next().unwrap_or_default();
+        self.host(host)
+    }
+
+    /// Adds the words of the domain of an email address, e.g. "acme" for "jsmith@acme.com",
+    /// such as the address the site's mail is sent from.
+    pub fn email_domain(self, email: &str) -> Self {
+        let domain = email.rsplit_once('@').map_or(email, |(_, domain)| domain);
+        self.host(domain)
+    }
+
+    /// Adds a word as is, lowercased.
+    pub fn word(mut self, word: &str) -> Self {
+        self.push(word.to_lowercase());
+        self
+    }
+
+    /// The words added so far, lowercased and without duplicates, in the order they were
+    /// added.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Whether no words have been added.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    fn host(mut self, host: &str) -> Self {
+        let mut labels = host
+            .split('.')
+            .filter(|label| !label.is_empty())
+            .collect::<Vec<_>>();
+        if labels.len() > 1 {
+            labels.pop();
+        }
+        for label in labels {
+            if !IGNORED_HOST_LABELS.contains(&label.to_lowercase().as_str()) {
+                self = self.text(label);
+            }
+        }
+        self
+    }
+
+    fn text(mut self, text: &str) -> Self {
+        for part in text.split(|c: char| !c.is_alphanumeric()) {
+            let words = split_words(part);
+            if words.len() > 1 {
+                self.push(part.to_lowercase());
+            }
+            for word in words {
+                self.push(word.to_lowercase());
+            }
+        }
+        self
+    }
+
+    fn push(&mut self, word: String) {
+        if word.chars().count() >= MIN_WORD_LENGTH && !self.words.contains(&word) {
+            self.words.push(word);
+        }
+    }
+}
+
+/// Splits a run of letters and digits on camel case and between letters and digits, e.g.
+/// "MyApp2" into "My", "App" and "2".
+pub(crate) fn split_words(part: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = 0;
+    let mut prev: Option<char> = None;
+    for (i, c) in part.char_indices() {
+        if let Some(p) = prev {
+            let boundary =
+                (p.is_lowercase() && c.is_uppercase()) || (p.is_alphabetic() != c.is_alphabetic());
+            if boundary {
+                words.push(&part[start..i]);
+                start = i;
+            }
+        }
+        prev = Some(c);
+    }
+    if start < part.len() {
+        words.push(&part[start..]);
+    }
+    words
+}
+
+impl Zxcvbn {
+    /// Adds the words of `context` to the user inputs of every estimate, after the user
+    /// inputs given to the estimate.
+    pub fn with_context(mut self, context: Context) -> Self {
+        self.context = context;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_words() {
+        assert_eq!(split_words("MyApp2024"), ["My", "App", "2024"]);
+        assert_eq!(split_words("myapp"), ["myapp"]);
+        assert_eq!(split_words("HTTPServer"), ["HTTPServer"]);
+        assert_eq!(split_words(""), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_context_words() {
+        let context = Context::new()
+            .site_name("PhotoShare Pro")
+            .url("https://user@login.photoshare.co:8443/path?x=1")
+            .email_domain("noreply@mail.photo-share.io")
+            .word("Lumen");
+        assert_eq!(
+            context.words(),
+            ["photoshare", "photo", "share", "pro", "lumen"]
+        );
+        assert!(Context::new().url("localhost").words() == ["localhost"]);
+        assert!(Context::new().is_empty());
+    }
+
+    #[test]
+    fn test_with_context() {
+        let password = "photoshare2024!";
+        let estimator = Zxcvbn::new().with_context(Context::new().site_name("PhotoShare"));
+        let with_context = estimator.estimate(password, &[]);
+        assert!(with_context.guesses() < Zxcvbn::new().estimate(password, &[]).guesses());
+        // the same as passing the words as user inputs
+        let entropy = estimator.estimate(password, &["photoshare"]);
+        assert_eq!(entropy.guesses(), with_context.guesses());
+    }
+}
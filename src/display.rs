@@ -0,0 +1,435 @@
+//! Human-readable guesses and crack times, rounded the same way in every frontend and with
+//! unit names in a few languages.
+//!
+//! ```
+//! use zxcvbn::display::{format_crack_time, format_guesses, Locale};
+//! use zxcvbn::zxcvbn;
+//!
+//! let entropy = zxcvbn("Tr0ub4dour&3", &[]);
+//! assert_eq!(format_guesses(entropy.guesses(), Locale::English), "~19 million");
+//! assert_eq!(format_guesses(entropy.guesses(), Locale::German), "~19 Millionen");
+//! let time = entropy.crack_times().online_throttling_100_per_hour();
+//! assert_eq!(format_crack_time(time, Locale::French), "21 ans");
+//! ```
+//!
+//! Other languages and number conventions can be supported by implementing [`Formatter`].
+
+use crate::time_estimates::CrackTimeSeconds;
+
+/// Language of the unit names.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Locale {
+    /// English, as used by the `Display` implementations of the crate.
+    #[default]
+    English,
+    /// German.
+    German,
+    /// French.
+    French,
+    /// Spanish.
+    Spanish,
+}
+
+/// A unit crack times are written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TimeUnit {
+    /// Seconds.
+    Second,
+    /// Minutes.
+    Minute,
+    /// Hours.
+    Hour,
+    /// Days.
+    Day,
+    /// Months of 31 days.
+    Month,
+    /// Years of 12 months.
+    Year,
+}
+
+/// A magnitude guesses are written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Magnitude {
+    /// Thousands.
+    Thousand,
+    /// Millions.
+    Million,
+    /// Billions, i.e. thousands of millions.
+    Billion,
+}
+
+/// Writes the parts of guesses and crack times whose wording depends on the language or
+/// number conventions, for [`format_guesses_with`] and [`format_crack_time_with`].
+///
+/// The rounding is the same for every formatter, only the wording differs:
+///
+/// ```
+/// use zxcvbn::display::{format_guesses_with, Formatter, Magnitude, TimeUnit};
+///
+/// /// Guesses as digits grouped with apostrophes, as in Switzerland.
+/// struct Swiss;
+///
+/// impl Formatter for Swiss {
+///     fn less_than_a_second(&self) -> String {
+///         "weniger als eine Sekunde".to_string()
+///     }
+///
+///     fn centuries(&self) -> String {
+///         "Jahrhunderte".to_string()
+///     }
+///
+///     fn time(&self, count: u64, unit: TimeUnit) -> String {
+///         format!("{count} {unit:?}")
+///     }
+///
+///     fn guesses(&self, whole: u64, tenths: u64, magnitude: Magnitude) -> String {
+///         let zeros = match magnitude {
+///             Magnitude::Thousand => 2,
+///             Magnitude::Million => 5,
+///             Magnitude::Billion => 8,
+///         };
+///         let digits = format!("{whole}{tenths}{}", "0".repeat(zeros));
+///         let mut grouped = String::new();
+///         for (k, digit) in digits.chars().enumerate() {
+///             if k > 0 && (digits.len() - k) % 3 == 0 {
+///                 grouped.push('\'');
+///             }
+///             grouped.push(digit);
+///         }
+///         format!("~{grouped}")
+///     }
+/// }
+///
+/// assert_eq!(format_guesses_with(1_234_567, &Swiss), "~1'200'000");
+/// assert_eq!(format_guesses_with(596, &Swiss), "596");
+/// ```
+pub trait Formatter {
+    /// A crack time under a second, e.g. "less than a second".
+    fn less_than_a_second(&self) -> String;
+
+    /// A crack time of a century or more, e.g. "centuries".
+    fn centuries(&self) -> String;
+
+    /// A crack time of `count` of a unit, e.g. "5 hours".
+    fn time(&self, count: u64, unit: TimeUnit) -> String;
+
+    /// Guesses rounded to `whole` and `tenths` of a magnitude, e.g. "~1.2 million". `tenths`
+    /// is 0 when the guesses are a whole number of the magnitude.
+    fn guesses(&self, whole: u64, tenths: u64, magnitude: Magnitude) -> String;
+
+    /// Fewer than a thousand guesses, written exactly.
+    fn exact_guesses(&self, guesses: u64) -> String {
+        guesses.to_string()
+    }
+
+    /// Guesses of a trillion or more, of which only the order of magnitude `exponent` is
+    /// meaningful, e.g. "10^14".
+    fn power_of_ten(&self, exponent: u32) -> String {
+        format!("10^{exponent}")
+    }
+}
+
+impl Formatter for Locale {
+    fn less_than_a_second(&self) -> String {
+        self.words().less_than_a_second.to_string()
+    }
+
+    fn centuries(&self) -> String {
+        self.words().centuries.to_string()
+    }
+
+    fn time(&self, count: u64, unit: TimeUnit) -> String {
+        let names = self.words().times[unit as usize];
+        format!("{count} {}", if count > 1 { names.1 } else { names.0 })
+    }
+
+    fn guesses(&self, whole: u64, tenths: u64, magnitude: Magnitude) -> String {
+        let words = self.words();
+        let names = words.magnitudes[magnitude as usize];
+        let name = if whole == 1 && tenths == 0 {
+            names.0
+        } else {
+            names.1
+        };
+        if tenths == 0 {
+            format!("~{whole} {name}")
+        } else {
+            format!("~{whole}{}{tenths} {name}", words.decimal_separator)
+        }
+    }
+}
+
+/// The words of a locale, singular and plural.
+struct Words {
+    less_than_a_second: &'static str,
+    centuries: &'static str,
+    /// Seconds, minutes, hours, days, months and years, in the order of [`TimeUnit`].
+    times: [(&'static str, &'static str); 6],
+    /// Thousands, millions and billions, in the order of [`Magnitude`].
+    magnitudes: [(&'static str, &'static str); 3],
+    decimal_separator: char,
+}
+
+impl Locale {
+    fn words(self) -> &'static Words {
+        match self {
+            Locale::English => &Words {
+                less_than_a_second: "less than a second",
+                centuries: "centuries",
+                times: [
+                    ("second", "seconds"),
+                    ("minute", "minutes"),
+                    ("hour", "hours"),
+                    ("day", "days"),
+                    ("month", "months"),
+                    ("year", "years"),
+                ],
+                magnitudes: [
+                    ("thousand", "thousand"),
+                    ("million", "million"),
+                    ("billion", "billion"),
+                ],
+                decimal_separator: '.',
+            },
+            Locale::German => &Words {
+                less_than_a_second: "weniger als eine Sekunde",
+                centuries: "Jahrhunderte",
+                times: [
+                    ("Sekunde", "Sekunden"),
+                    ("Minute", "Minuten"),
+                    ("Stunde", "Stunden"),
+                    ("Tag", "Tage"),
+                    ("Monat", "Monate"),
+                    ("Jahr", "Jahre"),
+                ],
+                magnitudes: [
+                    ("Tausend", "Tausend"),
+                    ("Million", "Millionen"),
+                    ("Milliarde", "Milliarden"),
+                ],
+                decimal_separator: ',',
+            },
+            Locale::French => &Words {
+                less_than_a_second: "moins d'une seconde",
+                centuries: "des siècles",
+                times: [
+                    ("seconde", "secondes"),
+                    ("minute", "minutes"),
+                    ("heure", "heures"),
+                    ("jour", "jours"),
+                    ("mois", "mois"),
+                    ("an", "ans"),
+                ],
+                magnitudes: [
+                    ("mille", "mille"),
+                    ("million", "millions"),
+                    ("milliard", "milliards"),
+                ],
+                decimal_separator: ',',
+            },
+            Locale::Spanish => &Words {
+                less_than_a_second: "menos de un segundo",
+                centuries: "siglos",
+                times: [
+                    ("segundo", "segundos"),
+                    ("minuto", "minutos"),
+                    ("hora", "horas"),
+                    ("día", "días"),
+                    ("mes", "meses"),
+                    ("año", "años"),
+                ],
+                magnitudes: [
+                    ("mil", "mil"),
+                    ("millón", "millones"),
+                    ("mil millones", "mil millones"),
+                ],
+                decimal_separator: ',',
+            },
+        }
+    }
+}
+
+/// Guesses from this many on are written as a power of ten.
+const POWER_OF_TEN_GUESSES: u64 = 1_000_000_000_000;
+
+/// Formats a number of guesses for display.
+///
+/// Fewer than a thousand guesses are written exactly, e.g. "596". Up to a trillion, guesses
+/// are rounded to two significant digits and written with the largest magnitude that fits,
+/// e.g. "~1.2 million" or "~19 million". Beyond that only the order of magnitude is
+/// meaningful, e.g. "10^14".
+pub fn format_guesses(guesses: u64, locale: Locale) -> String {
+    format_guesses_with(guesses, &locale)
+}
+
+/// Formats a number of guesses for display like [`format_guesses`], worded by `formatter`.
+pub fn format_guesses_with<F: Formatter + ?Sized>(guesses: u64, formatter: &F) -> String {
+    if guesses < 1_000 {
+        return formatter.exact_guesses(guesses);
+    }
+    let digits = guesses.to_string().len() as u32;
+    let unit = 10u64.pow(digits - 2);
+    // round half up to two significant digits
+    let rounded = guesses.saturating_add(unit / 2) / unit * unit;
+    if rounded >= POWER_OF_TEN_GUESSES {
+        return formatter.power_of_ten(rounded.to_string().len() as u32 - 1);
+    }
+    let (magnitude, name) = [
+        (1_000, Magnitude::Thousand),
+        (1_000_000, Magnitude::Million),
+        (1_000_000_000, Magnitude::Billion),
+    ]
+    .into_iter()
+    .rev()
+    .find(|&(magnitude, _)| rounded >= magnitude)
+    .unwrap();
+    let whole = rounded / magnitude;
+    let tenths = rounded % magnitude / (magnitude / 10);
+    formatter.guesses(whole, tenths, name)
+}
+
+/// Formats a crack time for display, e.g. "5 hours" or "centuries". The `Display`
+/// implementation of [`CrackTimeSeconds`] is the same in English.
+pub fn format_crack_time(time: CrackTimeSeconds, locale: Locale) -> String {
+    format_crack_time_with(time, &locale)
+}
+
+/// Formats a crack time for display like [`format_crack_time`], worded by `formatter`.
+pub fn format_crack_time_with<F: Formatter + ?Sized>(
+    time: CrackTimeSeconds,
+    formatter: &F,
+) -> String {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = MINUTE * 60;
+    const DAY: u64 = HOUR * 24;
+    const MONTH: u64 = DAY * 31;
+    const YEAR: u64 = MONTH * 12;
+    const CENTURY: u64 = YEAR * 100;
+
+    let seconds = match time {
+        CrackTimeSeconds::Integer(i) => i,
+        CrackTimeSeconds::Float(f) => f as u64,
+    };
+    if seconds < 1 {
+        return formatter.less_than_a_second();
+    }
+    if seconds >= CENTURY {
+        return formatter.centuries();
+    }
+    let (unit, name) = [
+        (1, TimeUnit::Second),
+        (MINUTE, TimeUnit::Minute),
+        (HOUR, TimeUnit::Hour),
+        (DAY, TimeUnit::Day),
+        (MONTH, TimeUnit::Month),
+        (YEAR, TimeUnit::Year),
+    ]
+    .into_iter()
+    .rev()
+    .find(|&(unit, _)| seconds >= unit)
+    .unwrap();
+    formatter.time(seconds / unit, name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_format_guesses() {
+        let test_data = [
+            (0, "0"),
+            (596, "596"),
+            (1_000, "~1 thousand"),
+            (12_345, "~12 thousand"),
+            (999_600, "~1 million"),
+            (1_234_567, "~1.2 million"),
+            (41_000_000, "~41 million"),
+            (999_999_999_999, "10^12"),
+            (123_456_789_012_345, "10^14"),
+            (u64::MAX, "10^19"),
+        ];
+        for (guesses, expected) in test_data {
+            assert_eq!(format_guesses(guesses, Locale::English), expected);
+        }
+        assert_eq!(format_guesses(1_000_000, Locale::German), "~1 Million");
+        assert_eq!(format_guesses(1_500_000, Locale::German), "~1,5 Millionen");
+        assert_eq!(
+            format_guesses(2_000_000_000, Locale::Spanish),
+            "~2 mil millones"
+        );
+        assert_eq!(format_guesses(123, Locale::French), "123");
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_format_crack_time() {
+        let test_data = [
+            (CrackTimeSeconds::Float(0.5), "less than a second"),
+            (CrackTimeSeconds::Integer(1), "1 second"),
+            (CrackTimeSeconds::Integer(59), "59 seconds"),
+            (CrackTimeSeconds::Integer(3_600), "1 hour"),
+            (CrackTimeSeconds::Float(2e9), "62 years"),
+            (CrackTimeSeconds::Float(1e12), "centuries"),
+        ];
+        for (time, expected) in test_data {
+            assert_eq!(format_crack_time(time, Locale::English), expected);
+            assert_eq!(time.to_string(), expected);
+        }
+        assert_eq!(
+            format_crack_time(CrackTimeSeconds::Integer(7_200), Locale::German),
+            "2 Stunden"
+        );
+        assert_eq!(
+            format_crack_time(CrackTimeSeconds::Integer(86_400), Locale::Spanish),
+            "1 día"
+        );
+        assert_eq!(
+            format_crack_time(CrackTimeSeconds::Float(0.0), Locale::French),
+            "moins d'une seconde"
+        );
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_format_with_custom_formatter() {
+        struct Terse;
+
+        impl Formatter for Terse {
+            fn less_than_a_second(&self) -> String {
+                "<1s".to_string()
+            }
+
+            fn centuries(&self) -> String {
+                "100y+".to_string()
+            }
+
+            fn time(&self, count: u64, unit: TimeUnit) -> String {
+                let unit = ["s", "min", "h", "d", "mo", "y"][unit as usize];
+                format!("{count}{unit}")
+            }
+
+            fn guesses(&self, whole: u64, tenths: u64, magnitude: Magnitude) -> String {
+                let suffix = ["k", "M", "G"][magnitude as usize];
+                format!("{whole}.{tenths}{suffix}")
+            }
+        }
+
+        assert_eq!(format_guesses_with(1_234_567, &Terse), "1.2M");
+        assert_eq!(format_guesses_with(12_000, &Terse), "12.0k");
+        assert_eq!(format_guesses_with(u64::MAX, &Terse), "10^19");
+        assert_eq!(
+            format_crack_time_with(CrackTimeSeconds::Integer(7_200), &Terse),
+            "2h"
+        );
+        assert_eq!(
+            format_crack_time_with(CrackTimeSeconds::Float(0.5), &Terse),
+            "<1s"
+        );
+    }
+}
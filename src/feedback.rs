@@ -3,13 +3,34 @@
 
 use crate::frequency_lists::DictionaryType;
 use crate::matching::patterns::*;
-use crate::matching::Match;
+use crate::matching::{Match, YearWindow};
+use crate::scoring::{self, ScoringParams};
+use crate::time_estimates::{self, Score};
 use std::fmt;
+use std::iter;
+use zxcvbn_core::guesses::Guesses;
+
+/// The tone warnings and suggestions are written in, for products whose users need different
+/// wording than the standard messages, all from the same [`Warning`] and [`Suggestion`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Phrasing {
+    /// The messages of the original zxcvbn, as written by the `Display` implementations.
+    #[default]
+    Standard,
+    /// Short words and sentences, for general audiences and screen readers.
+    PlainLanguage,
+    /// What the estimator found, in its own terms, for administrators and developers.
+    Technical,
+    /// A few lowercase words without punctuation, for command line tools.
+    Terse,
+}
 
 /// A warning explains what's wrong with the password.
 #[derive(Debug, Copy, Clone, PartialEq)]
 #[cfg_attr(feature = "ser", derive(Serialize))]
 #[allow(missing_docs)]
+#[non_exhaustive]
 pub enum Warning {
     StraightRowsOfKeysAreEasyToGuess,
     ShortKeyboardPatternsAreEasyToGuess,
@@ -25,50 +46,300 @@ pub enum Warning {
     DatesAreOftenEasyToGuess,
     NamesAndSurnamesByThemselvesAreEasyToGuess,
     CommonNamesAndSurnamesAreEasyToGuess,
+    ThisContainsABannedWord,
+    ThisIsABannedPassword,
+    PopularEmojiAreEasyToGuess,
+    EmojiNextToEachOtherOnTheKeyboardAreEasyToGuess,
+    CharacterCodesAreEasyToGuess,
+    NamesOfSportsTeamsAreEasyToGuess,
+    CarBrandsAreEasyToGuess,
+    BandNamesAreEasyToGuess,
+    VideoGameNamesAreEasyToGuess,
+    BlocksOfKeysAreEasyToGuess,
+    AlternatingSequencesAreEasyToGuess,
+    MirroredTextIsHardlyStrongerThanHalfOfIt,
+    RetypingWithShiftIsEasyToGuess,
+    ThisIsADateFromYourPersonalData,
+    ThisIsYourPhoneNumber,
+    ThisLooksLikeANationalIdNumber,
+    ThisLooksLikeACardNumber,
+    ThisLooksLikeABankAccountNumber,
+    PinPadPatternsAreEasyToGuess,
+    UnlockPatternsAreEasyToGuess,
+    WordsSuggestedByPhoneKeyboardsAreEasyToGuess,
+    CommonPairsOfWordsAreEasyToGuess,
+    FirstLettersOfFamousPhrasesAreEasyToGuess,
+    ShapesDrawnOnTheKeyboardAreEasyToGuess,
+    TypingEachCharacterTwiceIsEasyToGuess,
 }
 
-impl fmt::Display for Warning {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+impl Warning {
+    /// The warning written in a phrasing. The `Display` implementation is
+    /// [`Phrasing::Standard`].
+    ///
+    /// ```
+    /// use zxcvbn::feedback::{Phrasing, Warning};
+    ///
+    /// let warning = Warning::ThisIsATop10Password;
+    /// assert_eq!(warning.phrase(Phrasing::Standard), warning.to_string());
+    /// assert_eq!(warning.phrase(Phrasing::Terse), "top-10 password");
+    /// ```
+    pub fn phrase(self, phrasing: Phrasing) -> &'static str {
+        self.phrases()[phrasing as usize]
+    }
+
+    /// The warning in every phrasing, in the order of [`Phrasing`].
+    fn phrases(self) -> [&'static str; 4] {
         match self {
-            Warning::StraightRowsOfKeysAreEasyToGuess => {
-                write!(f, "Straight rows of keys are easy to guess.")
-            }
-            Warning::ShortKeyboardPatternsAreEasyToGuess => {
-                write!(f, "Short keyboard patterns are easy to guess.")
-            }
-            Warning::RepeatsLikeAaaAreEasyToGuess => {
-                write!(f, "Repeats like \"aaa\" are easy to guess.")
-            }
-            Warning::RepeatsLikeAbcAbcAreOnlySlightlyHarderToGuess => write!(
-                f,
-                "Repeats like \"abcabcabc\" are only slightly harder to guess than \"abc\"."
-            ),
-            Warning::ThisIsATop10Password => write!(f, "This is a top-10 common password."),
-            Warning::ThisIsATop100Password => write!(f, "This is a top-100 common password."),
-            Warning::ThisIsACommonPassword => write!(f, "This is a very common password."),
-            Warning::ThisIsSimilarToACommonlyUsedPassword => {
-                write!(f, "This is similar to a commonly used password.")
-            }
-            Warning::SequencesLikeAbcAreEasyToGuess => {
-                write!(f, "Sequences like abc or 6543 are easy to guess.")
-            }
-            Warning::RecentYearsAreEasyToGuess => write!(f, "Recent years are easy to guess."),
-            Warning::AWordByItselfIsEasyToGuess => write!(f, "A word by itself is easy to guess."),
-            Warning::DatesAreOftenEasyToGuess => write!(f, "Dates are often easy to guess."),
-            Warning::NamesAndSurnamesByThemselvesAreEasyToGuess => {
-                write!(f, "Names and surnames by themselves are easy to guess.")
-            }
-            Warning::CommonNamesAndSurnamesAreEasyToGuess => {
-                write!(f, "Common names and surnames are easy to guess.")
-            }
+            Warning::StraightRowsOfKeysAreEasyToGuess => [
+                "Straight rows of keys are easy to guess.",
+                "Keys in a row on the keyboard, like qwerty, are easy to guess.",
+                "Contains a straight walk along a row of the keyboard.",
+                "keyboard row",
+            ],
+            Warning::ShortKeyboardPatternsAreEasyToGuess => [
+                "Short keyboard patterns are easy to guess.",
+                "Short patterns of keys next to each other are easy to guess.",
+                "Contains a short keyboard walk with few turns.",
+                "short keyboard pattern",
+            ],
+            Warning::RepeatsLikeAaaAreEasyToGuess => [
+                "Repeats like \"aaa\" are easy to guess.",
+                "Typing the same character again and again, like aaa, is easy to guess.",
+                "Contains a run of one repeated character.",
+                "repeated character",
+            ],
+            Warning::RepeatsLikeAbcAbcAreOnlySlightlyHarderToGuess => [
+                "Repeats like \"abcabcabc\" are only slightly harder to guess than \"abc\".",
+                "Typing the same group of characters again, like abcabc, adds very little.",
+                "Contains a repeated substring, guessed as the substring times the repetitions.",
+                "repeated text",
+            ],
+            Warning::ThisIsATop10Password => [
+                "This is a top-10 common password.",
+                "This is one of the 10 passwords people use most.",
+                "Ranks in the top 10 of leaked password lists.",
+                "top-10 password",
+            ],
+            Warning::ThisIsATop100Password => [
+                "This is a top-100 common password.",
+                "This is one of the 100 passwords people use most.",
+                "Ranks in the top 100 of leaked password lists.",
+                "top-100 password",
+            ],
+            Warning::ThisIsACommonPassword => [
+                "This is a very common password.",
+                "Lots of people use this password.",
+                "Appears in leaked password lists.",
+                "common password",
+            ],
+            Warning::ThisIsSimilarToACommonlyUsedPassword => [
+                "This is similar to a commonly used password.",
+                "This is close to a password lots of people use.",
+                "Is a leaked password with small changes, such as substitutions or a typo.",
+                "near a common password",
+            ],
+            Warning::SequencesLikeAbcAreEasyToGuess => [
+                "Sequences like abc or 6543 are easy to guess.",
+                "Characters in order, like abc or 6543, are easy to guess.",
+                "Contains a sequence of characters with a constant step.",
+                "sequence",
+            ],
+            Warning::RecentYearsAreEasyToGuess => [
+                "Recent years are easy to guess.",
+                "Years close to now are easy to guess.",
+                "Contains a year close to the reference year.",
+                "recent year",
+            ],
+            Warning::AWordByItselfIsEasyToGuess => [
+                "A word by itself is easy to guess.",
+                "A single word is easy to guess.",
+                "Consists of a single dictionary word.",
+                "single word",
+            ],
+            Warning::DatesAreOftenEasyToGuess => [
+                "Dates are often easy to guess.",
+                "Dates are often easy to guess.",
+                "Contains a date in a common format.",
+                "date",
+            ],
+            Warning::NamesAndSurnamesByThemselvesAreEasyToGuess => [
+                "Names and surnames by themselves are easy to guess.",
+                "A name on its own is easy to guess.",
+                "Consists of a single first name or surname.",
+                "single name",
+            ],
+            Warning::CommonNamesAndSurnamesAreEasyToGuess => [
+                "Common names and surnames are easy to guess.",
+                "Common first and last names are easy to guess.",
+                "Contains a common first name or surname.",
+                "common name",
+            ],
+            Warning::ThisContainsABannedWord => [
+                "This contains a word that isn't allowed.",
+                "This has a word in it that you can't use.",
+                "Contains a word from the ban list.",
+                "banned word",
+            ],
+            Warning::ThisIsABannedPassword => [
+                "This password isn't allowed.",
+                "You can't use this password.",
+                "Is on the list of banned passwords.",
+                "banned password",
+            ],
+            Warning::PopularEmojiAreEasyToGuess => [
+                "Popular emoji are easy to guess.",
+                "Emoji that lots of people use are easy to guess.",
+                "Contains popular emoji.",
+                "popular emoji",
+            ],
+            Warning::EmojiNextToEachOtherOnTheKeyboardAreEasyToGuess => [
+                "Emoji next to each other on the emoji keyboard are easy to guess.",
+                "Emoji picked from next to each other are easy to guess.",
+                "Contains emoji adjacent in emoji keyboard order.",
+                "emoji keyboard pattern",
+            ],
+            Warning::CharacterCodesAreEasyToGuess => [
+                "Character codes like alt+0169 or \\u00e9 are easy to guess.",
+                "Codes for characters, like alt+0169, are easy to guess.",
+                "Contains an alt code or an escaped code point.",
+                "character code",
+            ],
+            Warning::NamesOfSportsTeamsAreEasyToGuess => [
+                "Names of sports teams are easy to guess.",
+                "Names of sports teams are easy to guess.",
+                "Contains the name of a sports team.",
+                "sports team",
+            ],
+            Warning::CarBrandsAreEasyToGuess => [
+                "Car brands are easy to guess.",
+                "Car brands are easy to guess.",
+                "Contains a car brand.",
+                "car brand",
+            ],
+            Warning::BandNamesAreEasyToGuess => [
+                "Band names are easy to guess.",
+                "Band names are easy to guess.",
+                "Contains the name of a band.",
+                "band name",
+            ],
+            Warning::VideoGameNamesAreEasyToGuess => [
+                "Names from video games are easy to guess.",
+                "Names from video games are easy to guess.",
+                "Contains a name from a video game.",
+                "video game name",
+            ],
+            Warning::BlocksOfKeysAreEasyToGuess => [
+                "Blocks of keys next to each other are easy to guess.",
+                "Groups of keys next to each other are easy to guess.",
+                "Contains a whole row, column or rectangular block of keys.",
+                "block of keys",
+            ],
+            Warning::AlternatingSequencesAreEasyToGuess => [
+                "Alternating sequences like a1b2c3 are easy to guess.",
+                "Two sequences mixed together, like a1b2c3, are easy to guess.",
+                "Contains two interleaved sequences.",
+                "interleaved sequences",
+            ],
+            Warning::MirroredTextIsHardlyStrongerThanHalfOfIt => [
+                "Mirrored text like abccba is hardly stronger than half of it.",
+                "Text followed by itself backwards, like abccba, adds very little.",
+                "Contains a palindrome, guessed as its first half.",
+                "mirrored text",
+            ],
+            Warning::RetypingWithShiftIsEasyToGuess => [
+                "Typing the same keys again with shift held, like 123!@#, is easy to guess.",
+                "Typing the same keys again while holding shift, like 123!@#, is easy to guess.",
+                "Contains text followed by the same keystrokes with shift toggled.",
+                "shift repeat",
+            ],
+            Warning::ThisIsADateFromYourPersonalData => [
+                "This is a date from your personal data. Did you mean to include your birthday?",
+                "This is a date from your details. Is it your birthday?",
+                "Contains a date from the user inputs.",
+                "personal date",
+            ],
+            Warning::ThisIsYourPhoneNumber => [
+                "This is your phone number, or part of it.",
+                "This is your phone number or part of it.",
+                "Contains digits of a phone number from the user inputs.",
+                "phone number",
+            ],
+            Warning::ThisLooksLikeANationalIdNumber => [
+                "This looks like a national ID number, which a leaked password would give away.",
+                "This looks like an ID number, such as a social security number.",
+                "Contains a national identification number.",
+                "national ID",
+            ],
+            Warning::ThisLooksLikeACardNumber => [
+                "This looks like a card number, which a leaked password would give away.",
+                "This looks like a credit or debit card number.",
+                "Contains a digit run passing the Luhn check of payment card numbers.",
+                "card number",
+            ],
+            Warning::ThisLooksLikeABankAccountNumber => [
+                "This looks like a bank account number, which a leaked password would give away.",
+                "This looks like a bank account number (IBAN).",
+                "Contains an IBAN passing its mod-97 check.",
+                "bank account",
+            ],
+            Warning::PinPadPatternsAreEasyToGuess => [
+                "Patterns on a phone or PIN pad are easy to guess.",
+                "Digits next to each other on a phone keypad, like 2580, are easy to guess.",
+                "Contains a walk across the keys of a phone or PIN pad.",
+                "PIN pad pattern",
+            ],
+            Warning::UnlockPatternsAreEasyToGuess => [
+                "Unlock patterns written down as digits are easy to guess.",
+                "This looks like a phone unlock pattern, like 14789 for an L.",
+                "Contains digits tracing an unlock pattern on the 3x3 grid of a lock screen.",
+                "unlock pattern",
+            ],
+            Warning::WordsSuggestedByPhoneKeyboardsAreEasyToGuess => [
+                "Words suggested by phone keyboards are easy to guess.",
+                "Words your phone suggests as you type, like okaythanks, are easy to guess.",
+                "Contains words from the autosuggest bar of phone keyboards.",
+                "autosuggested words",
+            ],
+            Warning::CommonPairsOfWordsAreEasyToGuess => [
+                "Common pairs of words are easy to guess.",
+                "Two words often written together, like whitetiger or sarahjane, are easy to guess.",
+                "Contains two words that are commonly written together.",
+                "common word pair",
+            ],
+            Warning::FirstLettersOfFamousPhrasesAreEasyToGuess => [
+                "The first letters of a famous quote or song are easy to guess.",
+                "Using the first letter of each word of a famous line, like tbontbtitq, is easy to guess.",
+                "Contains the acronym of a well-known quote or song lyric.",
+                "famous phrase acronym",
+            ],
+            Warning::ShapesDrawnOnTheKeyboardAreEasyToGuess => [
+                "Shapes drawn on the keyboard are easy to guess.",
+                "Boxes, triangles and crosses of keys, like qazxcvfr, are easy to guess.",
+                "Contains the outline of a shape drawn on the keyboard.",
+                "keyboard shape",
+            ],
+            Warning::TypingEachCharacterTwiceIsEasyToGuess => [
+                "Typing each character twice, like ppaasswwoorrdd, is easy to guess.",
+                "Doubling every character, like ppaasswwoorrdd, adds very little.",
+                "Contains text with every character typed more than once.",
+                "echoed characters",
+            ],
         }
     }
 }
 
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.phrase(Phrasing::Standard))
+    }
+}
+
 /// A suggestion helps to choose a better password.
 #[derive(Debug, Copy, Clone, PartialEq)]
 #[cfg_attr(feature = "ser", derive(Serialize))]
 #[allow(missing_docs)]
+#[non_exhaustive]
 pub enum Suggestion {
     UseAFewWordsAvoidCommonPhrases,
     NoNeedForSymbolsDigitsOrUppercaseLetters,
@@ -77,60 +348,142 @@ pub enum Suggestion {
     AllUppercaseIsAlmostAsEasyToGuessAsAllLowercase,
     ReversedWordsArentMuchHarderToGuess,
     PredictableSubstitutionsDontHelpVeryMuch,
+    TypingWithAnotherKeyboardLayoutDoesntHelp,
     UseALongerKeyboardPatternWithMoreTurns,
     AvoidRepeatedWordsAndCharacters,
     AvoidSequences,
     AvoidRecentYears,
     AvoidYearsThatAreAssociatedWithYou,
     AvoidDatesAndYearsThatAreAssociatedWithYou,
+    AvoidNumbersThatAreAssociatedWithYou,
+    NeverUseIdNumbers,
+    NeverUsePaymentDetails,
 }
 
-impl fmt::Display for Suggestion {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+impl Suggestion {
+    /// The suggestion written in a phrasing. The `Display` implementation is
+    /// [`Phrasing::Standard`].
+    pub fn phrase(self, phrasing: Phrasing) -> &'static str {
+        self.phrases()[phrasing as usize]
+    }
+
+    /// The suggestion in every phrasing, in the order of [`Phrasing`].
+    fn phrases(self) -> [&'static str; 4] {
         match self {
-            Suggestion::UseAFewWordsAvoidCommonPhrases => {
-                write!(f, "Use a few words, avoid common phrases.")
-            }
-            Suggestion::NoNeedForSymbolsDigitsOrUppercaseLetters => {
-                write!(f, "No need for symbols, digits, or uppercase letters.")
-            }
-            Suggestion::AddAnotherWordOrTwo => {
-                write!(f, "Add another word or two. Uncommon words are better.")
-            }
-            Suggestion::CapitalizationDoesntHelpVeryMuch => {
-                write!(f, "Capitalization doesn't help very much.")
-            }
-            Suggestion::AllUppercaseIsAlmostAsEasyToGuessAsAllLowercase => write!(
-                f,
-                "All-uppercase is almost as easy to guess as all-lowercase."
-            ),
-            Suggestion::ReversedWordsArentMuchHarderToGuess => {
-                write!(f, "Reversed words aren't much harder to guess.")
-            }
-            Suggestion::PredictableSubstitutionsDontHelpVeryMuch => write!(
-                f,
-                "Predictable substitutions like '@' instead of 'a' don't help very much."
-            ),
-            Suggestion::UseALongerKeyboardPatternWithMoreTurns => {
-                write!(f, "Use a longer keyboard pattern with more turns.")
-            }
-            Suggestion::AvoidRepeatedWordsAndCharacters => {
-                write!(f, "Avoid repeated words and characters.")
-            }
-            Suggestion::AvoidSequences => write!(f, "Avoid sequences."),
-            Suggestion::AvoidRecentYears => write!(f, "Avoid recent years."),
-            Suggestion::AvoidYearsThatAreAssociatedWithYou => {
-                write!(f, "Avoid years that are associated with you.")
-            }
-            Suggestion::AvoidDatesAndYearsThatAreAssociatedWithYou => {
-                write!(f, "Avoid dates and years that are associated with you.")
-            }
+            Suggestion::UseAFewWordsAvoidCommonPhrases => [
+                "Use a few words, avoid common phrases.",
+                "Use a few words that don't usually go together.",
+                "Use several uncommon words rather than a known phrase.",
+                "use a few words",
+            ],
+            Suggestion::NoNeedForSymbolsDigitsOrUppercaseLetters => [
+                "No need for symbols, digits, or uppercase letters.",
+                "You don't need symbols, numbers or capital letters.",
+                "Length adds more guesses than mixing character classes.",
+                "symbols optional",
+            ],
+            Suggestion::AddAnotherWordOrTwo => [
+                "Add another word or two. Uncommon words are better.",
+                "Add another word or two. Unusual words are better.",
+                "Append one or two uncommon words.",
+                "add a word",
+            ],
+            Suggestion::CapitalizationDoesntHelpVeryMuch => [
+                "Capitalization doesn't help very much.",
+                "Capital letters don't help much.",
+                "Capitalization adds few guesses.",
+                "capitals don't help",
+            ],
+            Suggestion::AllUppercaseIsAlmostAsEasyToGuessAsAllLowercase => [
+                "All-uppercase is almost as easy to guess as all-lowercase.",
+                "All capitals is almost as easy to guess as all small letters.",
+                "All-uppercase is a single extra variation of a word.",
+                "all caps don't help",
+            ],
+            Suggestion::ReversedWordsArentMuchHarderToGuess => [
+                "Reversed words aren't much harder to guess.",
+                "Spelling words backwards doesn't help much.",
+                "Reversing a word only doubles its guesses.",
+                "reversing doesn't help",
+            ],
+            Suggestion::PredictableSubstitutionsDontHelpVeryMuch => [
+                "Predictable substitutions like '@' instead of 'a' don't help very much.",
+                "Swapping letters for look-alikes, like @ for a, doesn't help much.",
+                "Common l33t substitutions add few guesses.",
+                "substitutions don't help",
+            ],
+            Suggestion::TypingWithAnotherKeyboardLayoutDoesntHelp => [
+                "Typing a word with another keyboard layout doesn't help.",
+                "Typing a word with the wrong keyboard language switched on doesn't help.",
+                "Words typed with another keyboard layout only double their guesses.",
+                "other layouts don't help",
+            ],
+            Suggestion::UseALongerKeyboardPatternWithMoreTurns => [
+                "Use a longer keyboard pattern with more turns.",
+                "Use a longer keyboard pattern that changes direction more often.",
+                "Keyboard walks need more length and turns.",
+                "longer keyboard pattern",
+            ],
+            Suggestion::AvoidRepeatedWordsAndCharacters => [
+                "Avoid repeated words and characters.",
+                "Don't repeat words or characters.",
+                "Avoid repeated substrings.",
+                "avoid repeats",
+            ],
+            Suggestion::AvoidSequences => [
+                "Avoid sequences.",
+                "Don't use characters in order.",
+                "Avoid sequences of characters with a constant step.",
+                "avoid sequences",
+            ],
+            Suggestion::AvoidRecentYears => [
+                "Avoid recent years.",
+                "Don't use years close to now.",
+                "Avoid years close to the reference year.",
+                "avoid recent years",
+            ],
+            Suggestion::AvoidYearsThatAreAssociatedWithYou => [
+                "Avoid years that are associated with you.",
+                "Don't use years that mean something to you.",
+                "Avoid years derived from personal data.",
+                "avoid personal years",
+            ],
+            Suggestion::AvoidDatesAndYearsThatAreAssociatedWithYou => [
+                "Avoid dates and years that are associated with you.",
+                "Don't use dates or years that mean something to you.",
+                "Avoid dates and years derived from personal data.",
+                "avoid personal dates",
+            ],
+            Suggestion::AvoidNumbersThatAreAssociatedWithYou => [
+                "Avoid numbers that are associated with you.",
+                "Don't use phone numbers or other numbers that belong to you.",
+                "Avoid digit strings derived from personal data.",
+                "avoid personal numbers",
+            ],
+            Suggestion::NeverUseIdNumbers => [
+                "Never use ID numbers, such as your social security number, in a password.",
+                "Don't put your social security number or any other ID number in a password.",
+                "Exclude government-issued identifiers from passwords.",
+                "no ID numbers",
+            ],
+            Suggestion::NeverUsePaymentDetails => [
+                "Never use card numbers or bank account numbers in a password.",
+                "Don't put your card number or bank details in a password.",
+                "Exclude payment card numbers and IBANs from passwords.",
+                "no payment details",
+            ],
         }
     }
 }
 
+impl fmt::Display for Suggestion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.phrase(Phrasing::Standard))
+    }
+}
+
 /// Verbal feedback to help choose better passwords
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 #[cfg_attr(feature = "ser", derive(Serialize))]
 pub struct Feedback {
     /// Explains what's wrong, e.g. "This is a top-10 common password". Not always set.
@@ -138,6 +491,10 @@ pub struct Feedback {
     /// A possibly-empty list of suggestions to help choose a less guessable password.
     /// E.g. "Add another word or two".
     suggestions: Vec<Suggestion>,
+    /// How much would need to be appended to the password for it to be strong enough.
+    minimum_addition: Option<MinimumAddition>,
+    /// The common recipe the password was put together from, e.g. a word followed by digits.
+    recipe: Option<Recipe>,
 }
 
 impl Feedback {
@@ -151,9 +508,188 @@ impl Feedback {
     pub fn suggestions(&self) -> &[Suggestion] {
         &self.suggestions
     }
+
+    /// How much would need to be appended to the password for it to be strong enough,
+    /// e.g. "Add about 4 more random characters or 1 more word".
+    pub fn minimum_addition(&self) -> Option<MinimumAddition> {
+        self.minimum_addition
+    }
+
+    /// The common recipe the password was put together from, e.g. a word followed by digits.
+    pub fn recipe(&self) -> Option<Recipe> {
+        self.recipe
+    }
+}
+
+/// A concrete amount of extra material that would make a weak password acceptable,
+/// estimated with the same scoring model used for the password itself.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+pub struct MinimumAddition {
+    /// Number of random lowercase characters to append.
+    random_characters: usize,
+    /// Number of random words from a diceware-sized word list to append, if any fit.
+    words: Option<usize>,
+}
+
+impl MinimumAddition {
+    /// Number of random lowercase characters to append.
+    pub fn random_characters(self) -> usize {
+        self.random_characters
+    }
+
+    /// Number of random words from a diceware-sized word list to append, or `None` if no
+    /// number of them fits within the characters a password is estimated on.
+    pub fn words(self) -> Option<usize> {
+        self.words
+    }
+}
+
+impl fmt::Display for MinimumAddition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Add about {} more random character{}",
+            self.random_characters,
+            if self.random_characters > 1 { "s" } else { "" },
+        )?;
+        if let Some(words) = self.words {
+            write!(
+                f,
+                " or {} more word{}",
+                words,
+                if words > 1 { "s" } else { "" }
+            )?;
+        }
+        write!(f, ".")
+    }
+}
+
+/// A common template passwords are put together from, recognized from the kinds of matches
+/// in the sequence. Password policies may want to reject some of them outright.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[non_exhaustive]
+pub enum Recipe {
+    /// One or more words followed by digits, e.g. "monkey123".
+    WordDigits,
+    /// Words separated by symbols, e.g. "green-house".
+    WordSymbolWord,
+    /// A capitalized word, a year and symbols, e.g. "Summer2024!".
+    CapitalizedWordYearSymbol,
+}
+
+impl fmt::Display for Recipe {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Recipe::WordDigits => write!(f, "word + digits"),
+            Recipe::WordSymbolWord => write!(f, "word + symbol + word"),
+            Recipe::CapitalizedWordYearSymbol => write!(f, "Capitalized word + year + symbol"),
+        }
+    }
+}
+
+/// The kinds of text a recipe is made of.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Ingredient {
+    Word,
+    Digits,
+    Symbols,
+}
+
+/// Shortest dictionary match taken as a word of a recipe, so that gibberish isn't taken
+/// for a string of short words.
+const MIN_RECIPE_WORD_LENGTH: usize = 3;
+
+/// Finds the recipe the password follows, if any.
+///
+/// The password is split into runs of letters, digits and symbols, and runs of letters must
+/// split into dictionary words. All matches are considered rather than only the most
+/// guessable sequence, which often bruteforces a short word next to a symbol.
+pub(crate) fn get_recipe(password: &str, matches: &[Match]) -> Option<Recipe> {
+    let chars = password.chars().collect::<Vec<_>>();
+    let mut parts: Vec<(Ingredient, usize, usize)> = Vec::new();
+    for (k, &c) in chars.iter().enumerate() {
+        let ingredient = if c.is_alphabetic() {
+            Ingredient::Word
+        } else if c.is_ascii_digit() {
+            Ingredient::Digits
+        } else {
+            Ingredient::Symbols
+        };
+        match parts.last_mut() {
+            Some((last, _, j)) if *last == ingredient => *j = k,
+            _ => parts.push((ingredient, k, k)),
+        }
+    }
+    for &(ingredient, i, j) in &parts {
+        if ingredient != Ingredient::Word {
+            continue;
+        }
+        // whether chars[i..k] can be split into dictionary words, for each k
+        let mut covered = vec![false; j - i + 2];
+        covered[0] = true;
+        for k in i..=j {
+            if !covered[k - i] {
+                continue;
+            }
+            for m in matches {
+                if m.i == k
+                    && m.j <= j
+                    && m.j + 1 - m.i >= MIN_RECIPE_WORD_LENGTH
+                    && matches!(m.pattern, MatchPattern::Dictionary(_))
+                {
+                    covered[m.j + 1 - i] = true;
+                }
+            }
+        }
+        if !covered[j + 1 - i] {
+            return None;
+        }
+    }
+    let parts = parts
+        .into_iter()
+        .map(|(ingredient, i, j)| (ingredient, chars[i..=j].iter().collect::<String>()))
+        .collect::<Vec<_>>();
+    let is_capitalized = |word: &str| {
+        let mut chars = word.chars();
+        chars.next().is_some_and(char::is_uppercase) && !chars.any(char::is_uppercase)
+    };
+    let is_year =
+        |digits: &str| digits.len() == 4 && (digits.starts_with("19") || digits.starts_with("20"));
+    match parts.as_slice() {
+        [(Ingredient::Word, word), (Ingredient::Digits, year), (Ingredient::Symbols, _)]
+            if is_capitalized(word) && is_year(year) =>
+        {
+            Some(Recipe::CapitalizedWordYearSymbol)
+        }
+        [(Ingredient::Word, _), (Ingredient::Digits, _)] => Some(Recipe::WordDigits),
+        [(Ingredient::Word, _), (Ingredient::Symbols, _), (Ingredient::Word, _)] => {
+            Some(Recipe::WordSymbolWord)
+        }
+        _ => None,
+    }
 }
 
-pub(crate) fn get_feedback(score: u8, sequence: &[Match]) -> Option<Feedback> {
+/// Lowest score that does not warrant feedback.
+const ACCEPTABLE_SCORE: Score = Score::SafelyUnguessable;
+/// Guesses of a random lowercase character.
+const LOWERCASE_CARDINALITY: u64 = 26;
+/// Size of the EFF long diceware word list.
+const DICEWARE_WORDS: u64 = 7776;
+/// Length of the placeholder token standing in for an appended word.
+const DICEWARE_WORD_LENGTH: usize = 5;
+/// Give up looking for an addition beyond this many characters or words.
+const MAX_ADDITION: usize = 32;
+
+pub(crate) fn get_feedback(
+    password: &str,
+    score: Score,
+    sequence: &[Match],
+    recipe: Option<Recipe>,
+    years: &YearWindow,
+    scoring: &ScoringParams,
+) -> Option<Feedback> {
     if sequence.is_empty() {
         // default feedback
         return Some(Feedback {
@@ -162,9 +698,11 @@ pub(crate) fn get_feedback(score: u8, sequence: &[Match]) -> Option<Feedback> {
                 Suggestion::UseAFewWordsAvoidCommonPhrases,
                 Suggestion::NoNeedForSymbolsDigitsOrUppercaseLetters,
             ],
+            minimum_addition: get_minimum_addition(password, sequence, scoring),
+            recipe: None,
         });
     }
-    if score >= 3 {
+    if score >= ACCEPTABLE_SCORE {
         return None;
     }
 
@@ -176,21 +714,128 @@ pub(crate) fn get_feedback(score: u8, sequence: &[Match]) -> Option<Feedback> {
     let extra_feedback = Suggestion::AddAnotherWordOrTwo;
 
     feedback.suggestions.insert(0, extra_feedback);
+    // the current year appended to a password is called out even if it isn't the longest match
+    let ends_with_current_year = sequence
+        .last()
+        .is_some_and(|m| scoring::is_appended_year(m, password, years));
+    if ends_with_current_year && !feedback.suggestions.contains(&Suggestion::AvoidRecentYears) {
+        feedback.suggestions.insert(1, Suggestion::AvoidRecentYears);
+    }
+    feedback.minimum_addition = get_minimum_addition(password, sequence, scoring);
+    feedback.recipe = recipe;
     Some(feedback)
 }
 
+/// Feedback for a password that is on the banlist. No minimum addition is given, as the
+/// password has to be replaced rather than extended.
+pub(crate) fn get_banned_password_feedback() -> Feedback {
+    Feedback {
+        warning: Some(Warning::ThisIsABannedPassword),
+        suggestions: vec![Suggestion::UseAFewWordsAvoidCommonPhrases],
+        minimum_addition: None,
+        recipe: None,
+    }
+}
+
+/// Feedback for a password holding a national ID number, card number or IBAN, if any of
+/// `matches` is one. It is given however strong the password is, and whether or not the
+/// optimal sequence goes through the number, as a leaked password would give it away.
+pub(crate) fn get_sensitive_data_feedback(matches: &[Match]) -> Option<Feedback> {
+    matches
+        .iter()
+        .find_map(|m| sensitive_data_feedback(&m.pattern))
+}
+
+/// No minimum addition is given, as the number has to be taken out rather than padded.
+fn sensitive_data_feedback(pattern: &MatchPattern) -> Option<Feedback> {
+    let (warning, suggestion) = match *pattern {
+        MatchPattern::NationalId(_) => (
+            Warning::ThisLooksLikeANationalIdNumber,
+            Suggestion::NeverUseIdNumbers,
+        ),
+        MatchPattern::Payment(ref pattern) => (
+            if pattern.payment_kind == "iban" {
+                Warning::ThisLooksLikeABankAccountNumber
+            } else {
+                Warning::ThisLooksLikeACardNumber
+            },
+            Suggestion::NeverUsePaymentDetails,
+        ),
+        _ => return None,
+    };
+    Some(Feedback {
+        warning: Some(warning),
+        suggestions: vec![suggestion, Suggestion::UseAFewWordsAvoidCommonPhrases],
+        minimum_addition: None,
+        recipe: None,
+    })
+}
+
+/// Finds the smallest number of random characters, and separately of random words,
+/// which appended to the password would bring it up to `ACCEPTABLE_SCORE`.
+///
+/// The password keeps the sequence it was scored with, and the addition is one more match
+/// after it: appended characters are random lowercase letters of 26 guesses each, and each
+/// appended word is one more match of diceware rank. The guesses are counted directly with
+/// the scoring parameters the password was scored with, without searching the padded
+/// password again. Additions are only counted up to the characters a password is estimated
+/// on.
+fn get_minimum_addition(
+    password: &str,
+    sequence: &[Match],
+    scoring: &ScoringParams,
+) -> Option<MinimumAddition> {
+    let pi = sequence.iter().fold(Guesses::new(1), |pi, m| {
+        pi * m
+            .guesses
+            .unwrap_or_else(|| scoring::estimate_match(m, password))
+    });
+    let room = crate::MAX_PASSWORD_LENGTH.saturating_sub(password.chars().count());
+    let is_acceptable = |matches: usize, pi: Guesses| {
+        let guesses = scoring.sequence_guesses(sequence.len() + matches, pi.get());
+        time_estimates::estimate_attack_times(guesses).1 >= ACCEPTABLE_SCORE
+    };
+
+    // more of an addition never takes fewer guesses, so the first that is enough is the fewest
+    let random_characters = (1..=MAX_ADDITION.min(room)).find(|&n| {
+        let padding = iter::repeat(LOWERCASE_CARDINALITY)
+            .take(n)
+            .product::<Guesses>();
+        is_acceptable(1, pi * padding)
+    })?;
+    let max_words = MAX_ADDITION.min(room / DICEWARE_WORD_LENGTH);
+    let words = (1..=max_words).find(|&n| {
+        let words = iter::repeat(DICEWARE_WORDS).take(n).product::<Guesses>();
+        is_acceptable(n, pi * words)
+    });
+    Some(MinimumAddition {
+        random_characters,
+        words,
+    })
+}
+
 fn get_match_feedback(cur_match: &Match, is_sole_match: bool) -> Feedback {
     match cur_match.pattern {
         MatchPattern::Dictionary(ref pattern) => {
             get_dictionary_match_feedback(cur_match, pattern, is_sole_match)
         }
         MatchPattern::Spatial(ref pattern) => Feedback {
-            warning: Some(if pattern.turns == 1 {
-                Warning::StraightRowsOfKeysAreEasyToGuess
-            } else {
-                Warning::ShortKeyboardPatternsAreEasyToGuess
-            }),
+            warning: Some(
+                if ["phone_pad", "pin_pad"].contains(&pattern.graph.as_str()) {
+                    Warning::PinPadPatternsAreEasyToGuess
+                } else if pattern.shape.is_some() {
+                    Warning::ShapesDrawnOnTheKeyboardAreEasyToGuess
+                } else if pattern.region == Some("block") {
+                    Warning::BlocksOfKeysAreEasyToGuess
+                } else if pattern.turns == 1 {
+                    Warning::StraightRowsOfKeysAreEasyToGuess
+                } else {
+                    Warning::ShortKeyboardPatternsAreEasyToGuess
+                },
+            ),
             suggestions: vec![Suggestion::UseALongerKeyboardPatternWithMoreTurns],
+            minimum_addition: None,
+            recipe: None,
         },
         MatchPattern::Repeat(ref pattern) => Feedback {
             warning: Some(if pattern.base_token.chars().count() == 1 {
@@ -199,31 +844,132 @@ fn get_match_feedback(cur_match: &Match, is_sole_match: bool) -> Feedback {
                 Warning::RepeatsLikeAbcAbcAreOnlySlightlyHarderToGuess
             }),
             suggestions: vec![Suggestion::AvoidRepeatedWordsAndCharacters],
+            minimum_addition: None,
+            recipe: None,
         },
         MatchPattern::Sequence(_) => Feedback {
             warning: Some(Warning::SequencesLikeAbcAreEasyToGuess),
             suggestions: vec![Suggestion::AvoidSequences],
+            minimum_addition: None,
+            recipe: None,
+        },
+        MatchPattern::Mirror(_) => Feedback {
+            warning: Some(Warning::MirroredTextIsHardlyStrongerThanHalfOfIt),
+            suggestions: vec![Suggestion::AvoidRepeatedWordsAndCharacters],
+            minimum_addition: None,
+            recipe: None,
+        },
+        MatchPattern::ShiftRepeat(_) => Feedback {
+            warning: Some(Warning::RetypingWithShiftIsEasyToGuess),
+            suggestions: vec![Suggestion::AvoidRepeatedWordsAndCharacters],
+            minimum_addition: None,
+            recipe: None,
+        },
+        MatchPattern::Echo(_) => Feedback {
+            warning: Some(Warning::TypingEachCharacterTwiceIsEasyToGuess),
+            suggestions: vec![Suggestion::AvoidRepeatedWordsAndCharacters],
+            minimum_addition: None,
+            recipe: None,
+        },
+        MatchPattern::Unlock(_) => Feedback {
+            warning: Some(Warning::UnlockPatternsAreEasyToGuess),
+            suggestions: vec![Suggestion::UseALongerKeyboardPatternWithMoreTurns],
+            minimum_addition: None,
+            recipe: None,
+        },
+        MatchPattern::Acronym(_) => Feedback {
+            warning: Some(Warning::FirstLettersOfFamousPhrasesAreEasyToGuess),
+            suggestions: vec![Suggestion::UseAFewWordsAvoidCommonPhrases],
+            minimum_addition: None,
+            recipe: None,
         },
-        MatchPattern::Regex(ref pattern) => {
-            if pattern.regex_name == "recent_year" {
-                Feedback {
-                    warning: Some(Warning::RecentYearsAreEasyToGuess),
-                    suggestions: vec![
-                        Suggestion::AvoidRecentYears,
-                        Suggestion::AvoidYearsThatAreAssociatedWithYou,
-                    ],
+        MatchPattern::WordPair(ref pattern) => Feedback {
+            warning: match pattern.dictionary_name {
+                DictionaryType::Bigrams => Some(Warning::CommonPairsOfWordsAreEasyToGuess),
+                DictionaryType::Autosuggest => {
+                    Some(Warning::WordsSuggestedByPhoneKeyboardsAreEasyToGuess)
                 }
-            } else {
-                Feedback::default()
-            }
+                _ => None,
+            },
+            suggestions: vec![Suggestion::UseAFewWordsAvoidCommonPhrases],
+            minimum_addition: None,
+            recipe: None,
+        },
+        MatchPattern::Phone(_) => Feedback {
+            warning: Some(Warning::ThisIsYourPhoneNumber),
+            suggestions: vec![Suggestion::AvoidNumbersThatAreAssociatedWithYou],
+            minimum_addition: None,
+            recipe: None,
+        },
+        MatchPattern::NationalId(_) | MatchPattern::Payment(_) => {
+            sensitive_data_feedback(&cur_match.pattern).unwrap_or_default()
         }
-        MatchPattern::Date(_) => Feedback {
-            warning: Some(Warning::DatesAreOftenEasyToGuess),
+        MatchPattern::Interleaved(_) => Feedback {
+            warning: Some(Warning::AlternatingSequencesAreEasyToGuess),
+            suggestions: vec![Suggestion::AvoidSequences],
+            minimum_addition: None,
+            recipe: None,
+        },
+        MatchPattern::Regex(ref pattern) => match pattern.regex_name {
+            "recent_year" => Feedback {
+                warning: Some(Warning::RecentYearsAreEasyToGuess),
+                suggestions: vec![
+                    Suggestion::AvoidRecentYears,
+                    Suggestion::AvoidYearsThatAreAssociatedWithYou,
+                ],
+                minimum_addition: None,
+                recipe: None,
+            },
+            "decade" | "year_range" => Feedback {
+                warning: Some(Warning::DatesAreOftenEasyToGuess),
+                suggestions: vec![Suggestion::AvoidDatesAndYearsThatAreAssociatedWithYou],
+                minimum_addition: None,
+                recipe: None,
+            },
+            "alt_code" | "unicode_escape" => Feedback {
+                warning: Some(Warning::CharacterCodesAreEasyToGuess),
+                suggestions: vec![Suggestion::AddAnotherWordOrTwo],
+                minimum_addition: None,
+                recipe: None,
+            },
+            _ => Feedback::default(),
+        },
+        MatchPattern::Date(ref pattern) => Feedback {
+            warning: Some(if pattern.user_input {
+                Warning::ThisIsADateFromYourPersonalData
+            } else {
+                Warning::DatesAreOftenEasyToGuess
+            }),
             suggestions: vec![Suggestion::AvoidDatesAndYearsThatAreAssociatedWithYou],
+            minimum_addition: None,
+            recipe: None,
+        },
+        MatchPattern::Emoji(ref pattern) => match pattern.emoji_kind {
+            "repeat" => Feedback {
+                warning: Some(Warning::RepeatsLikeAaaAreEasyToGuess),
+                suggestions: vec![Suggestion::AvoidRepeatedWordsAndCharacters],
+                minimum_addition: None,
+                recipe: None,
+            },
+            "keyboard" => Feedback {
+                warning: Some(Warning::EmojiNextToEachOtherOnTheKeyboardAreEasyToGuess),
+                suggestions: vec![Suggestion::AvoidSequences],
+                minimum_addition: None,
+                recipe: None,
+            },
+            "common" => Feedback {
+                warning: Some(Warning::PopularEmojiAreEasyToGuess),
+                suggestions: vec![],
+                minimum_addition: None,
+                recipe: None,
+            },
+            _ => Feedback::default(),
         },
         _ => Feedback {
             warning: None,
             suggestions: vec![],
+            minimum_addition: None,
+            recipe: None,
         },
     }
 }
@@ -234,25 +980,39 @@ fn get_dictionary_match_feedback(
     is_sole_match: bool,
 ) -> Feedback {
     let warning: Option<Warning> = match pattern.dictionary_name {
-        DictionaryType::Passwords => Some(if is_sole_match && !pattern.l33t && !pattern.reversed {
-            let rank = pattern.rank;
-            if rank <= 10 {
-                Warning::ThisIsATop10Password
-            } else if rank <= 100 {
-                Warning::ThisIsATop100Password
+        DictionaryType::Passwords => Some(
+            if is_sole_match
+                && !pattern.l33t
+                && !pattern.reversed
+                && pattern.typos == 0
+                && pattern.layout.is_none()
+            {
+                let rank = pattern.rank;
+                if rank <= 10 {
+                    Warning::ThisIsATop10Password
+                } else if rank <= 100 {
+                    Warning::ThisIsATop100Password
+                } else {
+                    Warning::ThisIsACommonPassword
+                }
             } else {
-                Warning::ThisIsACommonPassword
-            }
-        } else {
-            Warning::ThisIsSimilarToACommonlyUsedPassword
-        }),
-        DictionaryType::English => {
+                Warning::ThisIsSimilarToACommonlyUsedPassword
+            },
+        ),
+        DictionaryType::English | DictionaryType::Russian => {
             if is_sole_match {
                 Some(Warning::AWordByItselfIsEasyToGuess)
             } else {
                 None
             }
         }
+        DictionaryType::Autosuggest => {
+            if is_sole_match {
+                Some(Warning::WordsSuggestedByPhoneKeyboardsAreEasyToGuess)
+            } else {
+                None
+            }
+        }
         DictionaryType::Surnames | DictionaryType::FemaleNames | DictionaryType::MaleNames => {
             Some(if is_sole_match {
                 Warning::NamesAndSurnamesByThemselvesAreEasyToGuess
@@ -260,6 +1020,11 @@ fn get_dictionary_match_feedback(
                 Warning::CommonNamesAndSurnamesAreEasyToGuess
             })
         }
+        DictionaryType::Banned => Some(Warning::ThisContainsABannedWord),
+        DictionaryType::SportsTeams => Some(Warning::NamesOfSportsTeamsAreEasyToGuess),
+        DictionaryType::CarBrands => Some(Warning::CarBrandsAreEasyToGuess),
+        DictionaryType::Bands => Some(Warning::BandNamesAreEasyToGuess),
+        DictionaryType::VideoGames => Some(Warning::VideoGameNamesAreEasyToGuess),
         _ => None,
     };
 
@@ -281,10 +1046,15 @@ fn get_dictionary_match_feedback(
     if pattern.l33t {
         suggestions.push(Suggestion::PredictableSubstitutionsDontHelpVeryMuch);
     }
+    if pattern.layout.is_some() {
+        suggestions.push(Suggestion::TypingWithAnotherKeyboardLayoutDoesntHelp);
+    }
 
     Feedback {
         warning,
         suggestions,
+        minimum_addition: None,
+        recipe: None,
     }
 }
 
@@ -295,6 +1065,55 @@ mod tests {
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::wasm_bindgen_test;
 
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_phrasings() {
+        let entropy = crate::zxcvbn("qwerty", &[]);
+        let feedback = entropy.feedback().as_ref().unwrap();
+        let warning = feedback.warning().unwrap();
+        assert_eq!(warning.phrase(Phrasing::Standard), warning.to_string());
+        assert_eq!(warning.phrase(Phrasing::Terse), "top-10 password");
+        for &suggestion in feedback.suggestions() {
+            assert_eq!(
+                suggestion.phrase(Phrasing::default()),
+                suggestion.to_string()
+            );
+            let terse = suggestion.phrase(Phrasing::Terse);
+            assert!(!terse.ends_with('.'), "{}", terse);
+            assert_ne!(suggestion.phrase(Phrasing::PlainLanguage), terse);
+            assert_ne!(suggestion.phrase(Phrasing::Technical), terse);
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_recipes() {
+        use crate::zxcvbn;
+
+        for (password, recipe) in [
+            ("monkey123", Some(Recipe::WordDigits)),
+            ("correcthorse1987", Some(Recipe::WordDigits)),
+            ("green-house", Some(Recipe::WordSymbolWord)),
+            ("Summer2024!", Some(Recipe::CapitalizedWordYearSymbol)),
+            ("summer2024!", None),
+            ("Summer202412!", None),
+            ("monkey", None),
+            ("123monkey", None),
+            ("xkcd7q!z", None),
+            ("xkcdqz123", None),
+        ] {
+            assert_eq!(zxcvbn(password, &[]).recipe(), recipe, "{}", password);
+        }
+        assert_eq!(
+            zxcvbn("monkey123", &[])
+                .feedback()
+                .as_ref()
+                .unwrap()
+                .recipe(),
+            Some(Recipe::WordDigits)
+        );
+    }
+
     #[cfg_attr(not(target_arch = "wasm32"), test)]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn test_top_password_feedback() {
@@ -321,4 +1140,154 @@ mod tests {
             Some(Warning::ThisIsSimilarToACommonlyUsedPassword)
         );
     }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_pin_pad_feedback() {
+        use crate::zxcvbn;
+
+        for password in ["0852", "*0#"] {
+            let entropy = zxcvbn(password, &[]);
+            assert_eq!(
+                entropy.feedback.unwrap().warning,
+                Some(Warning::PinPadPatternsAreEasyToGuess),
+                "{}",
+                password
+            );
+        }
+    }
+
+    #[cfg(feature = "all-matchers")]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_unlock_pattern_feedback() {
+        use crate::zxcvbn;
+
+        for password in ["15963", "pass15948"] {
+            let entropy = zxcvbn(password, &[]);
+            assert_eq!(
+                entropy.feedback.unwrap().warning,
+                Some(Warning::UnlockPatternsAreEasyToGuess),
+                "{}",
+                password
+            );
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_shape_feedback() {
+        use crate::zxcvbn;
+
+        let feedback = zxcvbn("qazxcvfr", &[]).feedback.unwrap();
+        assert_eq!(
+            feedback.warning,
+            Some(Warning::ShapesDrawnOnTheKeyboardAreEasyToGuess)
+        );
+    }
+
+    #[cfg(feature = "all-matchers")]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_acronym_feedback() {
+        use crate::zxcvbn;
+
+        let entropy = zxcvbn("tbontbtitq", &[]);
+        let feedback = entropy.feedback.unwrap();
+        assert_eq!(
+            feedback.warning,
+            Some(Warning::FirstLettersOfFamousPhrasesAreEasyToGuess)
+        );
+        assert!(feedback
+            .suggestions
+            .contains(&Suggestion::UseAFewWordsAvoidCommonPhrases));
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_bigram_feedback() {
+        use crate::zxcvbn;
+
+        let entropy = zxcvbn("SarahJane", &[]);
+        assert_eq!(
+            entropy.feedback.unwrap().warning,
+            Some(Warning::CommonPairsOfWordsAreEasyToGuess)
+        );
+    }
+
+    #[cfg(feature = "autosuggest")]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_autosuggest_feedback() {
+        use crate::zxcvbn;
+
+        for password in ["OkayThanks", "thankyou"] {
+            let entropy = zxcvbn(password, &[]);
+            assert_eq!(
+                entropy.feedback.unwrap().warning,
+                Some(Warning::WordsSuggestedByPhoneKeyboardsAreEasyToGuess),
+                "{}",
+                password
+            );
+        }
+    }
+
+    #[cfg(all(feature = "car-brands", feature = "video-games"))]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_wordlist_pack_feedback() {
+        use crate::zxcvbn;
+
+        let entropy = zxcvbn("ferrari99", &[]);
+        assert_eq!(
+            entropy.feedback.unwrap().warning,
+            Some(Warning::CarBrandsAreEasyToGuess)
+        );
+
+        let entropy = zxcvbn("herobrine", &[]);
+        assert_eq!(
+            entropy.feedback.unwrap().warning,
+            Some(Warning::VideoGameNamesAreEasyToGuess)
+        );
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_minimum_addition() {
+        use crate::zxcvbn;
+
+        let entropy = zxcvbn("password", &[]);
+        let addition = entropy.feedback.unwrap().minimum_addition.unwrap();
+        assert_eq!(addition.random_characters(), 6);
+        assert_eq!(addition.words(), Some(2));
+        assert_eq!(
+            addition.to_string(),
+            "Add about 6 more random characters or 2 more words."
+        );
+
+        // 26^6 guesses of random lowercase letters are enough, 26^5 aren't
+        let entropy = zxcvbn("", &[]);
+        let addition = entropy.feedback.unwrap().minimum_addition.unwrap();
+        assert_eq!(addition.random_characters(), 6);
+
+        // only 100 characters are estimated, leaving room for characters but not for words
+        let entropy = zxcvbn(&format!("{}password", "a".repeat(91)), &[]);
+        let addition = entropy.feedback.unwrap().minimum_addition.unwrap();
+        assert_eq!(addition.random_characters(), 1);
+        assert_eq!(addition.words(), None);
+        assert_eq!(addition.to_string(), "Add about 1 more random character.");
+        let entropy = zxcvbn(&format!("{}password", "a".repeat(92)), &[]);
+        assert_eq!(entropy.feedback.unwrap().minimum_addition, None);
+
+        // additions are scored with the parameters the password is
+        let entropy = crate::Zxcvbn::new()
+            .with_scoring_params(ScoringParams::new().min_guesses(PatternKind::Dictionary, 100_000))
+            .estimate("password", &[]);
+        let addition = entropy.feedback.unwrap().minimum_addition.unwrap();
+        assert_eq!(addition.random_characters(), 2);
+        assert_eq!(addition.words(), Some(1));
+
+        let entropy = zxcvbn("r0sebudmaelstrom11/20/91aaaa", &[]);
+        assert!(entropy.feedback.is_none());
+    }
 }
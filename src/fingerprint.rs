@@ -0,0 +1,61 @@
+//! Hashes of the settings and data guesses depend on, for model versions.
+//!
+//! Model versions are stored next to scores and compared by later releases on other
+//! platforms, so the hash is FNV-1a rather than the standard library's hashers, whose
+//! output may change between releases.
+
+use std::fmt;
+
+/// A 64-bit FNV-1a hash of everything written to it.
+#[derive(Clone, Copy)]
+pub(crate) struct Fingerprint(u64);
+
+impl Fingerprint {
+    pub(crate) fn new() -> Self {
+        Fingerprint(0xcbf2_9ce4_8422_2325)
+    }
+
+    pub(crate) fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    /// Writes a string followed by a separator, so that "ab", "c" and "a", "bc" differ.
+    pub(crate) fn write_str(&mut self, s: &str) {
+        self.write(s.as_bytes());
+        self.write(&[0xff]);
+    }
+
+    pub(crate) fn write_u64(&mut self, n: u64) {
+        self.write(&n.to_le_bytes());
+    }
+
+    pub(crate) fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fnv1a() {
+        // reference values of 64-bit FNV-1a
+        let mut fingerprint = Fingerprint::new();
+        assert_eq!(fingerprint.finish(), 0xcbf2_9ce4_8422_2325);
+        fingerprint.write(b"a");
+        assert_eq!(fingerprint.finish(), 0xaf63_dc4c_8601_ec8c);
+        let mut fingerprint = Fingerprint::new();
+        fingerprint.write(b"foobar");
+        assert_eq!(fingerprint.finish(), 0x8594_4171_f739_67e8);
+    }
+}
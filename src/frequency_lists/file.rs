@@ -0,0 +1,191 @@
+//! Word lists compiled into a compact binary file, loaded at runtime.
+//!
+//! A dictionary file is a finite state transducer mapping each lowercased word to its rank,
+//! 1 being the most common. Lookups read it in place, so a file mapped into memory is
+//! shared by every process using it and only the pages that are searched get loaded.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use fst::{Map, MapBuilder};
+
+use super::Ranks;
+use crate::fingerprint::Fingerprint;
+
+/// The bytes of a dictionary file, however they are held.
+struct Bytes(Box<dyn AsRef<[u8]> + Send + Sync>);
+
+impl AsRef<[u8]> for Bytes {
+    fn as_ref(&self) -> &[u8] {
+        (*self.0).as_ref()
+    }
+}
+
+/// A word list loaded from a dictionary file, matched alongside the built-in dictionaries
+/// once added with [`Zxcvbn::add_dictionary`](crate::Zxcvbn::add_dictionary).
+///
+/// Compile a file from a word list, most common word first, with
+/// [`DictionaryFile::compile`] or `zxcvbn dict compile words.txt words.zdict`. To share one
+/// file between processes, map it into memory, e.g. with the `memmap2` crate, and load the
+/// mapping with [`DictionaryFile::new`]:
+///
+/// ```
+/// # use std::fs::File;
+/// # use zxcvbn::{DictionaryFile, Zxcvbn};
+/// # let path = std::env::temp_dir().join("zxcvbn-doctest.zdict");
+/// # DictionaryFile::compile(["acmecorp", "roadrunner"], File::create(&path)?)?;
+/// let file = File::open(&path)?;
+/// // Safety: the file must not be modified while it is mapped.
+/// let mmap = unsafe { memmap2::Mmap::map(&file)? };
+///
+/// let estimator = Zxcvbn::new();
+/// estimator.add_dictionary(DictionaryFile::new(mmap)?);
+/// assert_eq!(estimator.estimate("acmecorp", &[]).guesses(), 2);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct DictionaryFile {
+    map: Map<Bytes>,
+}
+
+impl DictionaryFile {
+    /// Loads a dictionary file from its bytes, such as a memory mapping of the file.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidData`] if the bytes are not a dictionary file.
+    pub fn new<D>(data: D) -> io::Result<Self>
+    where
+        D: AsRef<[u8]> + Send + Sync + 'static,
+    {
+        let map = Map::new(Bytes(Box::new(data))).map_err(io_error)?;
+        Ok(DictionaryFile { map })
+    }
+
+    /// Reads a dictionary file into memory and loads it.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::new(fs::read(path)?)
+    }
+
+    /// Compiles a word list, most common word first, into a dictionary file.
+    ///
+    /// Words are lowercased and trimmed, and blank ones are skipped. A word listed more
+    /// than once keeps its first, best rank.
+    pub fn compile<I, W>(words: I, writer: W) -> io::Result<()>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+        W: Write,
+    {
+        let mut ranks = BTreeMap::new();
+        let words = words
+            .into_iter()
+            .map(|word| word.as_ref().trim().to_lowercase())
+            .filter(|word| !word.is_empty());
+        for (i, word) in words.enumerate() {
+            ranks.entry(word).or_insert(i as u64 + 1);
+        }
+        let mut builder = MapBuilder::new(writer).map_err(io_error)?;
+        builder.extend_iter(ranks).map_err(io_error)?;
+        builder.finish().map_err(io_error)
+    }
+
+    /// Number of words in the dictionary.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Whether the dictionary has no words.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Rank of a lowercased word, 1 being the most common, or `None` if it is not in the
+    /// dictionary.
+    pub fn rank(&self, word: &str) -> Option<usize> {
+        self.map.get(word).map(|rank| rank as usize)
+    }
+
+    /// Hashes the file into a model version. A file ends with the number of words, the
+    /// address of its root and a checksum of the rest, so only the end is read, rather
+    /// than every page of a mapped file.
+    pub(crate) fn fingerprint(&self, fingerprint: &mut Fingerprint) {
+        let bytes = self.map.as_fst().as_bytes();
+        fingerprint.write_u64(bytes.len() as u64);
+        fingerprint.write(&bytes[bytes.len().saturating_sub(32)..]);
+    }
+}
+
+impl Ranks for DictionaryFile {
+    fn search(&self, word: &str) -> (Option<usize>, bool) {
+        // Walks the transducer by hand, as the node the word ends at also tells whether
+        // longer words start with it.
+        let fst = self.map.as_fst();
+        let mut node = fst.root();
+        let mut output = fst::raw::Output::zero();
+        for &byte in word.as_bytes() {
+            let transition = match node.find_input(byte) {
+                Some(i) => node.transition(i),
+                None => return (None, false),
+            };
+            output = output.cat(transition.out);
+            node = fst.node(transition.addr);
+        }
+        let rank = if node.is_final() {
+            Some(output.cat(node.final_output()).value() as usize)
+        } else {
+            None
+        };
+        (rank, !node.is_empty())
+    }
+}
+
+/// Converts an error of the `fst` crate, passing I/O errors through.
+fn io_error(err: fst::Error) -> io::Error {
+    match err {
+        fst::Error::Io(err) => err,
+        err => io::Error::new(io::ErrorKind::InvalidData, err),
+    }
+}
+
+impl fmt::Debug for DictionaryFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DictionaryFile")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compile(words: &[&str]) -> DictionaryFile {
+        let mut bytes = Vec::new();
+        DictionaryFile::compile(words, &mut bytes).unwrap();
+        DictionaryFile::new(bytes).unwrap()
+    }
+
+    #[test]
+    fn test_compile() {
+        let dictionary = compile(&["Acme", "  roadrunner ", "", "acme", "road"]);
+        assert_eq!(dictionary.len(), 3);
+        assert_eq!(dictionary.rank("acme"), Some(1));
+        assert_eq!(dictionary.rank("roadrunner"), Some(2));
+        assert_eq!(dictionary.rank("road"), Some(4));
+        assert_eq!(dictionary.rank("Acme"), None);
+        assert!(DictionaryFile::new(b"not a dictionary".to_vec()).is_err());
+        assert!(compile(&[" "]).is_empty());
+    }
+
+    #[test]
+    fn test_search() {
+        let dictionary = compile(&["pass", "password", "passwords", "qwerty"]);
+        assert_eq!(dictionary.search("pass"), (Some(1), true));
+        assert_eq!(dictionary.search("passw"), (None, true));
+        assert_eq!(dictionary.search("passwords"), (Some(3), false));
+        assert_eq!(dictionary.search("pat"), (None, false));
+        assert_eq!(dictionary.search("qwerty"), (Some(4), false));
+        assert_eq!(dictionary.search(""), (None, true));
+    }
+}
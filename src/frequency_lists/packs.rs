@@ -0,0 +1,31 @@
+//! Optional word lists of popular names and common Russian words people build passwords from,
+//! most common first.
+//! Each list is only compiled in with its feature.
+//!
+//! Names are lowercased and written without spaces, the way they show up in passwords.
+//! Russian words are written in Cyrillic, and are also matched when typed with a QWERTY
+//! layout active, e.g. "gfhjkm" for "пароль".
+//! Words suggested by phone keyboards are written without apostrophes.
+
+#[cfg(feature = "sports-teams")]
+pub(crate) const SPORTS_TEAMS: &str = "yankees,cowboys,lakers,steelers,redsox,packers,chelsea,arsenal,liverpool,barcelona,realmadrid,manutd,manchesterunited,celtics,bulls,patriots,raiders,broncos,eagles,giants,dodgers,cubs,bears,niners,49ers,saints,dolphins,vikings,seahawks,chiefs,ravens,bengals,browns,colts,titans,jaguars,texans,chargers,rams,cardinals,falcons,panthers,buccaneers,commanders,redskins,jets,bills,lions,mets,braves,astros,phillies,mariners,orioles,tigers,twins,royals,padres,rockies,marlins,rangers,angels,athletics,brewers,pirates,nationals,bluejays,whitesox,warriors,knicks,spurs,rockets,mavericks,clippers,sixers,76ers,pistons,pacers,bucks,hawks,cavaliers,cavs,raptors,blazers,grizzlies,pelicans,hornets,timberwolves,wizards,nuggets,canadiens,habs,bruins,blackhawks,redwings,mapleleafs,leafs,flyers,penguins,oilers,canucks,flames,senators,sabres,islanders,devils,capitals,lightning,predators,avalanche,sharks,ducks,kraken,goldenknights,tottenham,mancity,manchestercity,everton,newcastle,astonvilla,westham,leeds,celtic,rangersfc,juventus,acmilan,milan,inter,intermilan,napoli,roma,lazio,bayern,bayernmunich,dortmund,borussia,schalke,psg,parissaintgermain,marseille,ajax,feyenoord,psv,benfica,porto,galatasaray,fenerbahce,besiktas,boca,bocajuniors,riverplate,flamengo,corinthians,palmeiras,santos,atletico,valencia,sevilla,gators,buckeyes,wolverines,longhorns,crimsontide,rolltide,seminoles,hurricanes,trojans,huskies,tarheels,bluedevils,wildcats,jayhawks,sooners,cornhuskers,hoosiers,spartans,fightingirish,hokies,razorbacks,volunteers,aggies,gamecocks,bulldogs,mountaineers,badgers,hawkeyes";
+
+#[cfg(feature = "car-brands")]
+pub(crate) const CAR_BRANDS: &str = "ferrari,porsche,mustang,corvette,camaro,bmw,mercedes,audi,honda,toyota,ford,chevy,chevrolet,nissan,mazda,subaru,lamborghini,jaguar,jeep,dodge,cadillac,lexus,volvo,volkswagen,vw,hyundai,kia,mitsubishi,suzuki,yamaha,kawasaki,ducati,harley,harleydavidson,bentley,rollsroyce,astonmartin,maserati,bugatti,mclaren,lotus,tesla,chrysler,buick,pontiac,gmc,lincoln,acura,infiniti,saab,peugeot,renault,citroen,fiat,alfaromeo,lancia,skoda,opel,vauxhall,landrover,rangerover,dacia,lada,tata,mahindra,geely,byd,scion,saturn,oldsmobile,plymouth,hummer,hudson,studebaker,packard,delorean,koenigsegg,pagani,rivian,lucid,polestar,genesis,silverado,f150,civic,accord,corolla,camry,supra,skyline,gtr,impreza,wrx,miata,charger,challenger,viper,firebird,transam,gto,chevelle,thunderbird,bronco,wrangler,cherokee,beetle,jetta,passat,eclipse,lancer,celica,prelude,integra,s2000,nsx,rx7,rx8,z28,ss396,hemi,cobra,shelby,roadster,carrera,testarossa,countach,diablo,murcielago,gallardo,huracan,aventador,veyron,chiron,enzo,escalade,hellcat";
+
+#[cfg(feature = "bands")]
+pub(crate) const BANDS: &str = "metallica,nirvana,slipknot,beatles,thebeatles,ledzeppelin,acdc,queen,pinkfloyd,greenday,linkinpark,blink182,ironmaiden,megadeth,slayer,pantera,korn,rammstein,evanescence,coldplay,radiohead,oasis,blur,u2,rollingstones,aerosmith,kiss,guns,gunsnroses,bonjovi,defleppard,motleycrue,poison,whitesnake,scorpions,judaspriest,blacksabbath,ozzy,deeppurple,thedoors,eagles,fleetwoodmac,journey,boston,toto,genesis,thewho,kinks,deftones,disturbed,godsmack,staind,creed,nickelback,paramore,mcr,mychemicalromance,fallout,falloutboy,thekillers,killers,strokes,arcticmonkeys,placebo,thecure,depechemode,neworder,joydivision,smiths,ramones,sexpistols,misfits,offspring,sum41,rancid,nofx,badreligion,weezer,foofighters,pearljam,soundgarden,aliceinchains,stp,redhotchilipeppers,rhcp,ratm,audioslave,soad,systemofadown,avengedsevenfold,a7x,bulletformyvalentine,bfmv,trivium,lambofgod,mastodon,gojira,opeth,dreamtheater,nightwish,sabaton,manowar,helloween,dragonforce,bts,blackpink,exo,twice,bigbang,onedirection,backstreetboys,nsync,westlife,spicegirls,abba,beegees,eminem,tupac,2pac,biggie,drake,beyonce,rihanna,madonna,michaeljackson,elvis,prince,bowie,davidbowie,tswift,taylorswift,ladygaga,shakira,adele,justinbieber,bieber";
+
+#[cfg(feature = "video-games")]
+pub(crate) const VIDEO_GAMES: &str = "minecraft,pokemon,pikachu,mario,supermario,zelda,halo,masterchief,fortnite,roblox,callofduty,cod,warcraft,worldofwarcraft,wow,starcraft,diablo,overwatch,counterstrike,csgo,dota,dota2,leagueoflegends,lol,runescape,skyrim,fallout,witcher,geralt,gta,grandtheftauto,sonic,tetris,pacman,donkeykong,kirby,metroid,samus,yoshi,luigi,bowser,ganon,ganondorf,triforce,hyrule,finalfantasy,sephiroth,chocobo,kingdomhearts,sora,megaman,streetfighter,ryu,mortalkombat,scorpion,subzero,tekken,smashbros,pokeball,charizard,bulbasaur,squirtle,eevee,mewtwo,jigglypuff,bandicoot,spyro,doom,quake,wolfenstein,halflife,valve,xbox,playstation,ps4,ps5,nintendo,gamecube,n64,wii,sega,atari,creeper,enderman,herobrine,noob,pwned,leet,gamer,headshot,respawn,fragger,noscope,sniper,pubg,apex,valorant,rocketleague,amongus,animalcrossing,splatoon,fifa,madden,nba2k,destiny,borderlands,bioshock,masseffect,darksouls,eldenring,bloodborne,sekiro,residentevil,silenthill,metalgear,snake,solidsnake,assassinscreed,ezio,godofwar,kratos,uncharted,lastofus,spartan,cortana,arbiter,tracer,genji,hanzo,reaper,lucio,mercy,jinx,teemo,yasuo,zed,ahri,thrall,arthas,illidan,sylvanas,azeroth,horde,alliance";
+
+#[cfg(feature = "russian")]
+pub(crate) const RUSSIAN: &str = "пароль,привет,марина,александр,наташа,андрей,анастасия,максим,солнышко,виктория,татьяна,сергей,екатерина,алексей,никита,кристина,светлана,солнце,дмитрий,владимир,котенок,счастье,кирилл,полина,оксана,вероника,евгений,карина,малышка,ксения,валентина,николай,галина,константин,пупсик,виктор,спартак,руслан,анечка,наталья,звезда,серега,россия,машенька,машина,москва,принцесса,лапочка,людмила,дракон,дашенька,интернет,самсунг,красота,наташка,цветок,сонечка,деньги,медведь,кисуля,музыка,зайчик,вконтакте,регина,гитара,динамо,природа,ольга,родина,игорек,ирина,игорь,настя,яндекс,компьютер,елена,кошка,ангел,камила,роман,света,игрушка,антон,любовь,люблю,котик,зайка,саша,катя,танюша,мама,папа,собака,мышка,рыбка,малыш,зенит,питер,весна,лето,осень,зима,жизнь,свобода,победа,надежда,вера,анна,юлия,мария,дарья,таня,лена,оля,юля,маша,даша,женя,дима,миша,коля,ваня,вова,иван,павел,олег,денис,артем,михаил,илья,вадим,алина,богдан,небо,море,друг,дружба,семья,дом,работа,школа,любимый,любимая,милая,зая,киска,лиса,тигр,волк,кот,собачка,пушок,барсик,мурка,рыжик,шарик,бобик,чебурашка,гена,нокиа,мечта,удача,сила,бог,господь,христос,иисус,спасибо,пожалуйста,хорошо,отлично,супер,класс,круто,пиво,водка,футбол,хоккей,баскетбол,рок,металл,фильм,игра,сашка,димка,вовка,мишка,леха,лешка,андрюша,аня,катюша,соня,лиза,алиса,вика,ника,кира,мила,люба,люся,валя,галя,инна,ира,алла,яна,диана,эльвира,гуля,айгуль,тимур,артур,марат,ренат,азамат";
+
+/// Keyboards suggest the words people type most often in their messages, the way they talk, so
+/// these are the first 300 words of `words::US_TV_AND_FILM`, Wiktionary's frequency list of TV
+/// and movie scripts (2006), in its order. As in that list, words ranked higher in the other
+/// bundled lists, such as "the", are left out. Apostrophes are dropped, keeping a word such as
+/// "well" once, and so are single letters, which on their own would match everywhere.
+#[cfg(feature = "autosuggest")]
+pub(crate) const AUTOSUGGEST: &str = "you,to,that,it,me,what,this,know,im,no,have,my,dont,just,not,do,be,your,we,its,so,but,all,well,oh,about,right,youre,get,here,out,going,like,yeah,if,can,up,want,think,thats,now,go,him,how,got,did,why,see,come,good,really,look,will,okay,back,cant,mean,tell,ill,hey,hes,could,didnt,yes,something,because,say,take,way,little,make,need,gonna,never,were,too,shes,ive,sure,our,sorry,whats,let,thing,maybe,down,man,very,theres,should,anything,said,much,any,even,off,please,doing,thank,give,thought,help,talk,god,still,wait,find,nothing,again,things,lets,doesnt,call,told,great,better,ever,night,away,believe,feel,everything,youve,fine,last,keep,does,put,around,stop,theyre,id,guy,isnt,always,listen,wanted,guys,huh,those,big,lot,happened,thanks,wont,trying,kind,wrong,talking,guess,care,bad,mom,remember,getting,together,dad,leave,understand,wouldnt,actually,hear,baby,nice,father,else,stay,done,wasnt,course,might,mind,every,enough,try,hell,came,someone,youll,whole,yourself,idea,ask,must,coming,looking,woman,room,knew,tonight,real,son,hope,went,hmm,happy,pretty,saw,girl,sir,friend,already,saying,next,job,problem,minute,thinking,havent,heard,honey,matter,myself,couldnt,exactly,having,probably,happen,weve,hurt,boy,dead,gotta,alone,excuse,start,kill,hard,youd,today,car,ready,without,wants,hold,wanna,yet,seen,deal,once,gone,morning,supposed,friends,head,stuff,worry,live,truth,face,forget,true,cause,soon,knows,telling,wife,whos,chance,run,move,anyone,person,bye,somebody,heart,miss,making,meet,anyway,phone,reason,damn,lost,looks,bring,case,turn,wish,tomorrow,kids,trust,check,change,anymore,least,arent,working,makes,taking,means,brother,hate,ago,says,beautiful,gave,fact,crazy,sit";
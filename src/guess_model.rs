@@ -0,0 +1,150 @@
+//! Plugging other guess-number estimators, such as PCFG or neural models, into an estimator.
+//!
+//! A [`GuessModel`] estimates the guesses of a password its own way. The models added with
+//! [`Zxcvbn::with_guess_model`] run after the patterns are matched, and their estimates are
+//! reported by [`Entropy::model_guesses`], leaving the score to the patterns.
+//! [`Entropy::conservative_guesses`] combines them with the pattern estimate by taking the
+//! fewest guesses any of them found:
+//!
+//! ```
+//! use std::sync::Arc;
+//! use zxcvbn::guess_model::GuessModel;
+//! use zxcvbn::Zxcvbn;
+//!
+//! /// Guesses passwords of lowercase letters by length, as a brute force attack would.
+//! #[derive(Debug)]
+//! struct Lowercase;
+//!
+//! impl GuessModel for Lowercase {
+//!     fn name(&self) -> &str {
+//!         "lowercase"
+//!     }
+//!
+//!     fn guesses(&self, password: &str) -> Option<u64> {
+//!         let lowercase = password.chars().all(|c| c.is_ascii_lowercase());
+//!         lowercase.then(|| 26u64.saturating_pow(password.len() as u32))
+//!     }
+//! }
+//!
+//! let estimator = Zxcvbn::new().with_guess_model(Arc::new(Lowercase));
+//! let entropy = estimator.estimate("zqxj", &[]);
+//! assert_eq!(entropy.model_guesses()[0].name, "lowercase");
+//! assert_eq!(entropy.model_guesses()[0].guesses, 26u64.pow(4));
+//! assert!(entropy.conservative_guesses() <= entropy.guesses());
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::markov::MarkovModel;
+use crate::{Entropy, Zxcvbn};
+
+/// An estimator of the number of guesses a password takes, e.g. a PCFG or a neural network
+/// backed by ONNX, implemented outside this crate.
+pub trait GuessModel: fmt::Debug + Send + Sync {
+    /// A short name the estimates of the model are reported under.
+    fn name(&self) -> &str;
+
+    /// The guesses of a password, at most 100 characters long and never empty, or `None` if
+    /// the model can't estimate it, e.g. because it has characters the model doesn't know.
+    fn guesses(&self, password: &str) -> Option<u64>;
+}
+
+/// Markov models can be added as other models too, reported under the name `markov`.
+impl GuessModel for MarkovModel {
+    fn name(&self) -> &str {
+        "markov"
+    }
+
+    fn guesses(&self, password: &str) -> Option<u64> {
+        Some(MarkovModel::guesses(self, password))
+    }
+}
+
+/// The estimate of a password by a [`GuessModel`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+pub struct ModelGuesses {
+    /// The [name](GuessModel::name) of the model.
+    pub name: String,
+    /// The guesses of the password by the model.
+    pub guesses: u64,
+}
+
+impl Zxcvbn {
+    /// Estimates passwords with another model too, reported by [`Entropy::model_guesses`]
+    /// after the models added before.
+    pub fn with_guess_model(mut self, model: Arc<dyn GuessModel>) -> Self {
+        self.guess_models.push(model);
+        self
+    }
+}
+
+/// Adds the estimates of `models` to the estimate of a non-empty password.
+pub(crate) fn apply(entropy: &mut Entropy, password: &str, models: &[Arc<dyn GuessModel>]) {
+    let password = password.chars().take(100).collect::<String>();
+    if password.is_empty() {
+        return;
+    }
+    entropy.model_guesses = models
+        .iter()
+        .filter_map(|model| {
+            model.guesses(&password).map(|guesses| ModelGuesses {
+                name: model.name().to_owned(),
+                guesses,
+            })
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[derive(Debug)]
+    struct Fixed(Option<u64>);
+
+    impl GuessModel for Fixed {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        fn guesses(&self, _password: &str) -> Option<u64> {
+            self.0
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_with_guess_model() {
+        let plain = Zxcvbn::new().estimate("correcthorse", &[]);
+        assert!(plain.model_guesses().is_empty());
+        assert_eq!(plain.conservative_guesses(), plain.guesses());
+
+        let estimator = Zxcvbn::new()
+            .with_guess_model(Arc::new(Fixed(Some(42))))
+            .with_guess_model(Arc::new(Fixed(None)))
+            .with_guess_model(MarkovModel::bundled());
+        let entropy = estimator.estimate("correcthorse", &[]);
+        let names = entropy
+            .model_guesses()
+            .iter()
+            .map(|estimate| estimate.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["fixed", "markov"]);
+        assert_eq!(entropy.model_guesses()[0].guesses, 42);
+        assert_eq!(
+            entropy.model_guesses()[1].guesses,
+            MarkovModel::bundled().guesses("correcthorse")
+        );
+        // reported, not scored
+        assert_eq!(entropy.guesses(), plain.guesses());
+        assert_eq!(entropy.score(), plain.score());
+        assert_eq!(entropy.conservative_guesses(), 42);
+
+        assert!(estimator.estimate("", &[]).model_guesses().is_empty());
+    }
+}
@@ -0,0 +1,277 @@
+//! Checking a new password against the user's previous passwords, for policies such as "must
+//! differ significantly from your last 5 passwords".
+//!
+//! A password is too close to a previous one if it is only a few edits away from it, ignoring
+//! case, as "Summer2025!" is from "Summer2024!", or if knowing the previous one makes it easy
+//! to guess: estimated with the previous passwords as user inputs, as the matchers find them
+//! inside "Summer2024!!x" or reversed in "!4202remmuS".
+//!
+//! ```
+//! use zxcvbn::history::{differs_from_previous, HistoryPolicy, HistoryRejection};
+//!
+//! let previous = ["Summer2024!", "correcthorse"];
+//! let policy = HistoryPolicy::default();
+//! assert!(differs_from_previous("Xk7#qzLm2&Wp", &previous, &policy).is_ok());
+//! assert_eq!(
+//!     differs_from_previous("summer2025!", &previous, &policy),
+//!     Err(HistoryRejection::TooFewEdits {
+//!         index: 0,
+//!         distance: 1
+//!     })
+//! );
+//! ```
+//!
+//! The previous passwords are needed in plain text, e.g. those entered to confirm a password
+//! change, or kept encrypted for the purpose. Checking against stored hashes is only possible
+//! for exact reuse.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::Zxcvbn;
+
+/// How different a new password has to be from the previous ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[cfg_attr(feature = "builder", builder(default))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+pub struct HistoryPolicy {
+    /// Minimum number of characters to insert, delete or substitute to turn a previous
+    /// password into the new one, ignoring case. Defaults to 3.
+    pub min_edits: usize,
+    /// Minimum number of guesses the new password takes when the previous ones are known.
+    /// Only checked when knowing them makes the password easier to guess, so that passwords
+    /// that are weak anyway are left to the [`Requirements`](crate::Requirements). Defaults to
+    /// 10^6.
+    pub min_guesses: u64,
+}
+
+impl Default for HistoryPolicy {
+    fn default() -> Self {
+        HistoryPolicy {
+            min_edits: 3,
+            min_guesses: 1_000_000,
+        }
+    }
+}
+
+/// Why a password is too close to a previous password.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryRejection {
+    /// The password is fewer edits away from a previous password than required.
+    TooFewEdits {
+        /// Index of the previous password.
+        index: usize,
+        /// Number of edits between them, ignoring case.
+        distance: usize,
+    },
+    /// The password takes fewer guesses than required once a previous password is known.
+    GuessableFromPrevious {
+        /// Index of the previous password.
+        index: usize,
+        /// Guesses of the password with the previous password as a user input.
+        guesses: u64,
+    },
+}
+
+impl fmt::Display for HistoryRejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HistoryRejection::TooFewEdits { .. } => {
+                write!(
+                    f,
+                    "password differs from a previous password in too few characters"
+                )
+            }
+            HistoryRejection::GuessableFromPrevious { .. } => {
+                write!(f, "password is easy to guess from a previous password")
+            }
+        }
+    }
+}
+
+impl Error for HistoryRejection {}
+
+/// Checks that a password differs enough from each of the `previous` passwords, most
+/// recent first by convention, so that the index of a rejection tells which one it is
+/// close to.
+pub fn differs_from_previous(
+    password: &str,
+    previous: &[&str],
+    policy: &HistoryPolicy,
+) -> Result<(), HistoryRejection> {
+    check(password, previous, policy, |user_inputs| {
+        crate::zxcvbn(password, user_inputs).guesses()
+    })
+}
+
+impl Zxcvbn {
+    /// Checks a password against the previous passwords like [`differs_from_previous`],
+    /// estimating it with the banned words and passwords of this estimator.
+    pub fn differs_from_previous(
+        &self,
+        password: &str,
+        previous: &[&str],
+        policy: &HistoryPolicy,
+    ) -> Result<(), HistoryRejection> {
+        check(password, previous, policy, |user_inputs| {
+            self.estimate(password, user_inputs).guesses()
+        })
+    }
+}
+
+fn check<F>(
+    password: &str,
+    previous: &[&str],
+    policy: &HistoryPolicy,
+    guesses: F,
+) -> Result<(), HistoryRejection>
+where
+    F: Fn(&[&str]) -> u64,
+{
+    let lowercase = compared_chars(password);
+    for (index, previous) in previous.iter().enumerate() {
+        let distance = edit_distance(&lowercase, &compared_chars(previous));
+        if distance < policy.min_edits {
+            return Err(HistoryRejection::TooFewEdits { index, distance });
+        }
+    }
+    if previous.is_empty() {
+        return Ok(());
+    }
+    let without_previous = guesses(&[]);
+    for (index, previous) in previous.iter().enumerate() {
+        let with_previous = guesses(&[previous]);
+        if with_previous < without_previous && with_previous < policy.min_guesses {
+            return Err(HistoryRejection::GuessableFromPrevious {
+                index,
+                guesses: with_previous,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// The lowercased characters of a password that are compared with the previous ones: the
+/// first 100, like estimates, which also bounds the time the edit distance takes, quadratic in
+/// the lengths. Lowercasing may expand characters, so the result is truncated again.
+fn compared_chars(password: &str) -> Vec<char> {
+    let password = password.chars().take(100).collect::<String>();
+    password.to_lowercase().chars().take(100).collect()
+}
+
+/// The Levenshtein distance between `a` and `b`: the fewest characters to insert, delete or
+/// substitute to turn one into the other.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, &x) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &y) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(x != y);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    fn distance(a: &str, b: &str) -> usize {
+        edit_distance(
+            &a.chars().collect::<Vec<_>>(),
+            &b.chars().collect::<Vec<_>>(),
+        )
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_edit_distance() {
+        assert_eq!(distance("kitten", "sitting"), 3);
+        assert_eq!(distance("summer2024!", "summer2025!"), 1);
+        assert_eq!(distance("", "abc"), 3);
+        assert_eq!(distance("abc", ""), 3);
+        assert_eq!(distance("pässwörd", "passwörd"), 1);
+        assert_eq!(distance("same", "same"), 0);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_differs_from_previous() {
+        let previous = ["Tr0ub4dor&3", "Summer2024!"];
+        let policy = HistoryPolicy::default();
+        assert_eq!(
+            differs_from_previous("tr0ub4dor&3", &previous, &policy),
+            Err(HistoryRejection::TooFewEdits {
+                index: 0,
+                distance: 0
+            })
+        );
+        assert_eq!(
+            differs_from_previous("Summer2024!", &previous, &policy),
+            Err(HistoryRejection::TooFewEdits {
+                index: 1,
+                distance: 0
+            })
+        );
+        // the previous password with a few characters added
+        assert!(matches!(
+            differs_from_previous("Summer2024!x9k", &previous, &policy),
+            Err(HistoryRejection::GuessableFromPrevious { index: 1, .. })
+        ));
+        // and reversed
+        assert!(matches!(
+            differs_from_previous("!4202remmuS", &previous, &policy),
+            Err(HistoryRejection::GuessableFromPrevious { index: 1, .. })
+        ));
+        assert!(differs_from_previous("correcthorsebatterystaple", &previous, &policy).is_ok());
+        // a weak password is not blamed on the previous ones
+        assert!(differs_from_previous("password1", &previous, &policy).is_ok());
+        assert!(differs_from_previous("Summer2024!", &[], &policy).is_ok());
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_differs_from_previous_compares_the_first_100_characters() {
+        let previous = "Xk7#qzLm2&Wp".repeat(9);
+        // estimates ignore what comes after the first 100 characters, and so does the check
+        let password = format!("{}{}", &previous[..100], "anything at all");
+        assert_eq!(
+            differs_from_previous(&password, &[&previous], &HistoryPolicy::default()),
+            Err(HistoryRejection::TooFewEdits {
+                index: 0,
+                distance: 0
+            })
+        );
+        // "İ" lowercases to two characters
+        assert_eq!(compared_chars(&"İ".repeat(100)).len(), 100);
+        assert_eq!(compared_chars(&"a".repeat(1000)).len(), 100);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_estimator_differs_from_previous() {
+        let estimator = Zxcvbn::new();
+        let policy = HistoryPolicy {
+            min_edits: 1,
+            ..HistoryPolicy::default()
+        };
+        assert!(estimator
+            .differs_from_previous("Summer2025!", &["Summer2024!"], &policy)
+            .is_ok());
+        assert_eq!(
+            HistoryRejection::TooFewEdits {
+                index: 0,
+                distance: 1
+            }
+            .to_string(),
+            "password differs from a previous password in too few characters"
+        );
+    }
+}
@@ -7,16 +7,22 @@
 #[cfg(feature = "builder")]
 extern crate derive_builder;
 
-#[macro_use]
-extern crate lazy_static;
-
 #[cfg(feature = "ser")]
 extern crate serde;
 #[cfg(feature = "ser")]
 #[macro_use]
 extern crate serde_derive;
+use std::collections::BTreeSet;
+use std::sync::Arc;
 use std::time::Duration;
 
+use arc_swap::ArcSwap;
+
+use crate::banlist::{Banlist, RuleSet};
+use crate::fingerprint::Fingerprint;
+use crate::normalization::Normalizers;
+use crate::profile::Profile;
+
 #[cfg(test)]
 #[macro_use]
 extern crate quickcheck;
@@ -25,15 +31,51 @@ use time_estimates::CrackTimes;
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::wasm_bindgen;
 
+pub use crate::banlist::{BanPosition, BanRule, BanlistError};
+#[cfg(feature = "dictionary-files")]
+pub use crate::frequency_lists::DictionaryFile;
 pub use crate::matching::Match;
+pub use crate::requirements::{meets_requirements, Rejection, Requirements};
+pub use crate::scoring::{ScoringParams, ScoringScratch};
+pub use crate::time_estimates::Score;
+pub use crate::user_inputs::user_input_tokens;
 
 mod adjacency_graphs;
+pub mod analysis;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod ban_list_sync;
+mod banlist;
+#[cfg(feature = "cache")]
+mod cache;
+pub mod context;
+pub mod display;
 pub mod feedback;
+mod fingerprint;
 mod frequency_lists;
+pub mod guess_model;
+pub mod history;
+pub mod mangling;
+pub mod markov;
 /// Defines structures for matches found in a password
 pub mod matching;
-mod scoring;
+pub mod normalization;
+pub mod policy;
+pub mod profile;
+pub mod reporting;
+pub mod requirements;
+pub mod rescore;
+pub mod scoring;
+pub mod search_graph;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod stream;
+pub mod testing;
 pub mod time_estimates;
+pub mod user_inputs;
+pub mod util;
+#[cfg(feature = "zxcvbn-validator")]
+pub mod validation;
+#[cfg(any(feature = "axum", feature = "actix"))]
+pub mod web;
 
 #[cfg(not(target_arch = "wasm32"))]
 fn time_scoped<F, R>(f: F) -> (R, Duration)
@@ -73,17 +115,34 @@ pub struct Entropy {
     guesses: u64,
     /// Order of magnitude of `guesses`
     guesses_log10: f64,
+    /// Guesses of the optimal sequence of patterns.
+    pattern_guesses: u64,
+    /// Guesses of the Markov model of the estimator, if it has one.
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    markov_guesses: Option<u64>,
+    /// Guesses of the other models of the estimator, in the order they were added.
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Vec::is_empty"))]
+    model_guesses: Vec<guess_model::ModelGuesses>,
     /// List of back-of-the-envelope crack time estimations based on a few scenarios.
     crack_times: time_estimates::CrackTimes,
     /// Overall strength score from 0-4.
     /// Any score less than 3 should be considered too weak.
-    score: u8,
+    score: Score,
     /// Verbal feedback to help choose better passwords. Set when `score` <= 2.
     feedback: Option<feedback::Feedback>,
     /// The list of patterns the guess calculation was based on
     sequence: Vec<Match>,
+    /// Every match found in the password, including those not in `sequence`.
+    all_matches: Vec<Match>,
+    /// The common recipe the password was put together from, if any.
+    recipe: Option<feedback::Recipe>,
+    /// Identifies the matchers, dictionaries and constants the estimate was made with.
+    model_version: String,
     /// How long it took to calculate the answer.
     calc_time: Duration,
+    /// The search for `sequence`, if the estimator records it.
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    search_graph: Option<search_graph::SearchGraph>,
 }
 
 impl Entropy {
@@ -97,6 +156,37 @@ impl Entropy {
         self.guesses_log10
     }
 
+    /// The guesses of the optimal [`sequence`](Entropy::sequence) of patterns, the same as
+    /// [`guesses`](Entropy::guesses) unless the estimator took fewer guesses from its
+    /// [Markov model](Zxcvbn::with_markov_model).
+    pub fn pattern_guesses(&self) -> u64 {
+        self.pattern_guesses
+    }
+
+    /// The guesses of the password by the [Markov model](Zxcvbn::with_markov_model) of the
+    /// estimator, if it has one. Empty passwords have none.
+    pub fn markov_guesses(&self) -> Option<u64> {
+        self.markov_guesses
+    }
+
+    /// The estimates of the [other models](Zxcvbn::with_guess_model) of the estimator, in
+    /// the order they were added, leaving out those that could not estimate the password.
+    pub fn model_guesses(&self) -> &[guess_model::ModelGuesses] {
+        &self.model_guesses
+    }
+
+    /// The fewest guesses of the [`guesses`](Entropy::guesses), the
+    /// [Markov model](Entropy::markov_guesses) and the [other models](Entropy::model_guesses):
+    /// a conservative estimate assuming the attacker uses whichever of them suits the password
+    /// best.
+    pub fn conservative_guesses(&self) -> u64 {
+        self.model_guesses
+            .iter()
+            .map(|estimate| estimate.guesses)
+            .chain(self.markov_guesses)
+            .fold(self.guesses, u64::min)
+    }
+
     /// List of back-of-the-envelope crack time estimations based on a few scenarios.
     pub fn crack_times(&self) -> time_estimates::CrackTimes {
         self.crack_times
@@ -104,7 +194,7 @@ impl Entropy {
 
     /// Overall strength score from 0-4.
     /// Any score less than 3 should be considered too weak.
-    pub fn score(&self) -> u8 {
+    pub fn score(&self) -> Score {
         self.score
     }
 
@@ -118,53 +208,817 @@ impl Entropy {
         &self.sequence
     }
 
+    /// Every match found in the password with its guesses, ordered by position, including
+    /// the matches that did not make it into [`sequence`](Entropy::sequence). Useful to
+    /// highlight every weak part of a password, even where a cheaper cover was chosen.
+    /// Only the matches with the fewest guesses are kept past the
+    /// [match limit](Zxcvbn::with_max_matches).
+    ///
+    /// ```
+    /// use zxcvbn::zxcvbn;
+    ///
+    /// let entropy = zxcvbn("password", &[]);
+    /// assert_eq!(entropy.sequence().len(), 1);
+    /// assert!(entropy.all_matches().iter().any(|m| m.token == "word"));
+    /// ```
+    pub fn all_matches(&self) -> &[Match] {
+        &self.all_matches
+    }
+
+    /// The common recipe the password was put together from, e.g. a word followed by digits.
+    /// Unlike the feedback, this is set whatever the score.
+    ///
+    /// ```
+    /// use zxcvbn::feedback::Recipe;
+    /// use zxcvbn::zxcvbn;
+    ///
+    /// assert_eq!(zxcvbn("Summer2024!", &[]).recipe(), Some(Recipe::CapitalizedWordYearSymbol));
+    /// ```
+    pub fn recipe(&self) -> Option<feedback::Recipe> {
+        self.recipe
+    }
+
+    /// Identifies the matchers, dictionaries and constants the estimate was made with, so
+    /// that stored scores can be told apart from those of a newer model.
+    ///
+    /// This is [`MODEL_VERSION`], followed by a `+` and a tag for every setting that
+    /// changes guesses: the current year, which dates are guessed around, builds without
+    /// the `full-dictionaries` or `all-matchers` features or with lists shortened by
+    /// `ZXCVBN_MAX_RANK`, the [`LegacyJsCompatible`](profile::Profile::LegacyJsCompatible)
+    /// profile, non-default normalizers, the word list packs and keyboards compiled in and
+    /// a [Markov model](Zxcvbn::with_markov_model) taking the lower of the estimates.
+    /// Estimators whose [scoring parameters](Zxcvbn::with_scoring_params),
+    /// [year window](Zxcvbn::with_year_window), [match limit](Zxcvbn::with_max_matches),
+    /// [dictionary files](Zxcvbn::add_dictionary) or Markov model differ from those of
+    /// [`zxcvbn`] add a `+settings=` tag with a hash of them. Banned words, user inputs and
+    /// [context](Zxcvbn::with_context) are not part of the model.
+    ///
+    /// ```
+    /// use zxcvbn::profile::Profile;
+    /// use zxcvbn::{zxcvbn, ScoringParams, Zxcvbn, MODEL_VERSION};
+    ///
+    /// let version = zxcvbn("password", &[]).model_version().to_string();
+    /// assert!(version.starts_with(MODEL_VERSION));
+    /// let estimator = Zxcvbn::from_profile(Profile::LegacyJsCompatible);
+    /// assert!(estimator.estimate("password", &[]).model_version().ends_with("+js"));
+    /// let estimator = Zxcvbn::new().with_scoring_params(ScoringParams::new().match_orderings(false));
+    /// assert!(estimator.estimate("password", &[]).model_version().contains("+settings="));
+    /// ```
+    pub fn model_version(&self) -> &str {
+        &self.model_version
+    }
+
     /// How long it took to calculate the answer.
     pub fn calculation_time(&self) -> Duration {
         self.calc_time
     }
+
+    /// The candidate matches and best partial sequences the search for
+    /// [`sequence`](Entropy::sequence) went through, if the estimator was made with
+    /// [`with_search_graph`](Zxcvbn::with_search_graph). Empty and banned passwords are not
+    /// searched and have none.
+    pub fn search_graph(&self) -> Option<&search_graph::SearchGraph> {
+        self.search_graph.as_ref()
+    }
+}
+
+/// Only this many characters of a password are estimated, so that extremely long inputs
+/// can't be used to slow estimates down.
+pub(crate) const MAX_PASSWORD_LENGTH: usize = 100;
+
+/// Version of the scoring model, bumped whenever a change to the matchers, dictionaries or
+/// constants can change the guesses of a password. See [`Entropy::model_version`].
+pub const MODEL_VERSION: &str = "26";
+
+/// The model version of estimates made with these settings, up to those only an estimator
+/// has, which [`Zxcvbn::model_version`] adds.
+fn model_version(
+    normalizers: &Normalizers,
+    profile: Profile,
+    years: &matching::YearWindow,
+) -> String {
+    let mut version = MODEL_VERSION.to_string();
+    // years within the window around the current year are guessed more easily
+    version.push_str("+year=");
+    version.push_str(&years.current_year.to_string());
+    if !cfg!(feature = "full-dictionaries") {
+        version.push_str("+lite-dictionaries");
+    }
+    if !cfg!(feature = "all-matchers") {
+        version.push_str("+lite-matchers");
+    }
+    if let Some(max_rank) = option_env!("ZXCVBN_MAX_RANK") {
+        version.push_str("+max-rank=");
+        version.push_str(max_rank);
+    }
+    if profile.js_compatible() {
+        // neither normalizers, word list packs nor extra keyboards are used
+        version.push_str("+js");
+        return version;
+    }
+    if cfg!(feature = "fuzzy-matching") {
+        version.push_str("+fuzzy-matching");
+    }
+    if cfg!(feature = "keyboard-typos") {
+        version.push_str("+keyboard-typos");
+    }
+    if *normalizers != Normalizers::default() {
+        version.push_str("+normalizers=");
+        version.push_str(&normalizers.tag());
+    }
+    for pack in frequency_lists::word_list_packs() {
+        version.push('+');
+        version.push_str(pack);
+    }
+    for region in matching::national_id_regions() {
+        version.push('+');
+        version.push_str(region);
+    }
+    for keyboard in adjacency_graphs::extra_keyboards() {
+        version.push('+');
+        version.push_str(keyboard);
+    }
+    version
+}
+
+/// The model version of the estimates of [`zxcvbn`].
+pub(crate) fn default_model_version() -> String {
+    model_version(
+        &Normalizers::default(),
+        Profile::default(),
+        &matching::YearWindow::default(),
+    )
 }
 
 /// Takes a password string and optionally a list of user-supplied inputs
 /// (e.g. username, email, first name) and calculates the strength of the password
 /// based on entropy, using a number of different factors.
+///
+/// The estimate is deterministic: the same password and user inputs always produce the
+/// same guesses, match sequence and feedback, regardless of hasher seeds or platform.
+/// Only `calculation_time` varies between calls, and date matches are scored against
+/// the current year.
+///
+/// The password is [normalized](normalization::Normalizers) before matching, so
+/// look-alike characters such as Cyrillic "о" in "pаsswоrd" don't hide dictionary words.
+/// Matches still index the password as given.
+///
+/// This is the same as calling [`Zxcvbn::estimate`] on an estimator with no banned words.
 pub fn zxcvbn(password: &str, user_inputs: &[&str]) -> Entropy {
+    estimate(
+        password,
+        user_inputs,
+        &Normalizers::default(),
+        Profile::default(),
+        None,
+        None,
+        #[cfg(feature = "dictionary-files")]
+        &[],
+        matching::DEFAULT_MAX_MATCHES,
+        matching::YearWindow::default(),
+        &ScoringParams::default(),
+        false,
+        &mut ScoringScratch::default(),
+    )
+}
+
+/// Builds the dictionaries, keyboard graphs and regular expressions estimates are made with.
+///
+/// They are otherwise built by the first estimate that needs them, which then takes
+/// noticeably longer than the next ones. Services can call this at startup so that no
+/// request pays for it. Calling it again does nothing.
+///
+/// ```
+/// zxcvbn::initialize();
+/// assert_eq!(zxcvbn::zxcvbn("password", &[]).guesses(), 3);
+/// ```
+pub fn initialize() {
+    matching::initialize();
+    scoring::initialize();
+}
+
+/// How long [`preload`] took to build each part of the static data.
+///
+/// Parts that were already built, by an earlier call or estimate, take next to no time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+pub struct PreloadTimes {
+    /// Building the built-in dictionaries, and the typo automaton with `fuzzy-matching`.
+    pub dictionaries: Duration,
+    /// Building the keyboard graphs and the statistics spatial estimates use.
+    pub keyboards: Duration,
+    /// Compiling the regular expressions of the matchers.
+    pub regexes: Duration,
+    /// Training the [bundled Markov model](markov::MarkovModel::bundled), which only
+    /// estimators given it use, but which takes the longest to build.
+    pub markov: Duration,
+    /// The whole of the call, including the smaller tables not counted above.
+    pub total: Duration,
+}
+
+/// Like [`initialize`], but also trains the [bundled Markov model](markov::MarkovModel::bundled)
+/// and reports how long building each part took, e.g. for logging the cold start of a
+/// serverless function.
+///
+/// ```
+/// let times = zxcvbn::preload();
+/// assert!(times.dictionaries + times.keyboards + times.regexes + times.markov <= times.total);
+/// ```
+pub fn preload() -> PreloadTimes {
+    let ((dictionaries, keyboards, regexes, markov), total) = time_scoped(|| {
+        let ((), dictionaries) = time_scoped(matching::initialize_dictionaries);
+        let ((), keyboards) = time_scoped(|| {
+            matching::initialize_keyboards();
+            scoring::initialize();
+        });
+        let ((), regexes) = time_scoped(matching::initialize_regexes);
+        matching::initialize();
+        let (_, markov) = time_scoped(markov::MarkovModel::bundled);
+        (dictionaries, keyboards, regexes, markov)
+    });
+    PreloadTimes {
+        dictionaries,
+        keyboards,
+        regexes,
+        markov,
+        total,
+    }
+}
+
+/// A password strength estimator that can learn new banned words and passwords while in use.
+///
+/// Keep one in shared state, e.g. behind an `Arc` or in a `static`, and call
+/// [`add_banned_words`](Zxcvbn::add_banned_words), [`add_ban_rules`](Zxcvbn::add_ban_rules)
+/// or [`add_banned_passwords`](Zxcvbn::add_banned_passwords) whenever the lists grow.
+/// Estimates running at the same time are not blocked, and see either all or none of the
+/// words added by one call.
+///
+/// ```
+/// use zxcvbn::{Score, Zxcvbn};
+///
+/// let estimator = Zxcvbn::new();
+/// assert_eq!(estimator.estimate("SummerSale2026", &[]).score(), Score::SafelyUnguessable);
+///
+/// estimator.add_banned_words(["summersale"]).unwrap();
+/// assert_eq!(estimator.estimate("SummerSale2026", &[]).score(), Score::VeryGuessable);
+/// ```
+#[derive(Debug, Default)]
+pub struct Zxcvbn {
+    banlist: ArcSwap<Banlist>,
+    banned_passwords: ArcSwap<BTreeSet<String>>,
+    #[cfg(feature = "dictionary-files")]
+    dictionaries: ArcSwap<Vec<Arc<DictionaryFile>>>,
+    normalizers: Normalizers,
+    profile: Profile,
+    reporter: Option<reporting::Reporter>,
+    max_matches: Option<usize>,
+    years: matching::YearWindow,
+    scoring: ScoringParams,
+    search_graph: bool,
+    context: context::Context,
+    markov: Option<(std::sync::Arc<markov::MarkovModel>, markov::MarkovMode)>,
+    guess_models: Vec<std::sync::Arc<dyn guess_model::GuessModel>>,
+    #[cfg(feature = "cache")]
+    cache: Option<cache::EntropyCache>,
+}
+
+impl Zxcvbn {
+    /// Creates an estimator with no banned words or passwords.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an estimator running the given normalizers on passwords before matching,
+    /// instead of all of them.
+    ///
+    /// ```
+    /// use zxcvbn::normalization::Normalizers;
+    /// use zxcvbn::{Score, Zxcvbn};
+    ///
+    /// let estimator = Zxcvbn::from_normalizers(Normalizers {
+    ///     confusables: false,
+    ///     ..Normalizers::default()
+    /// });
+    /// assert!(estimator.estimate("pаsswоrd", &[]).score() > Score::TooGuessable);
+    /// assert_eq!(Zxcvbn::new().estimate("pаsswоrd", &[]).score(), Score::TooGuessable);
+    /// ```
+    pub fn from_normalizers(normalizers: Normalizers) -> Self {
+        Zxcvbn {
+            normalizers,
+            ..Self::default()
+        }
+    }
+
+    /// Creates an estimator with the normalizers, matchers and dictionaries of a profile.
+    ///
+    /// ```
+    /// use zxcvbn::profile::Profile;
+    /// use zxcvbn::Zxcvbn;
+    ///
+    /// let estimator = Zxcvbn::from_profile(Profile::LegacyJsCompatible);
+    /// let entropy = estimator.estimate("qwertyuiop", &[]);
+    /// assert!(entropy.guesses() > Zxcvbn::new().estimate("qwertyuiop", &[]).guesses());
+    /// ```
+    pub fn from_profile(profile: Profile) -> Self {
+        Zxcvbn {
+            normalizers: profile.normalizers(),
+            profile,
+            scoring: ScoringParams::default().js_match_order(profile.js_compatible()),
+            ..Self::default()
+        }
+    }
+
+    /// Sets how many candidate matches an estimate may find, 2000 by default.
+    ///
+    /// Crafted passwords can match in ways that multiply, e.g. repeated tokens whose base
+    /// token is matched again. Matching a base token again counts as one match per character
+    /// and is counted before it is done, so base tokens past the limit are guessed as
+    /// bruteforce without being matched, and the time taken by an estimate is bounded along
+    /// with the matches it finds. Only the matches with the fewest guesses are kept past the
+    /// limit. Passwords only get more guesses as matches are dropped, never fewer, and
+    /// ordinary passwords stay far below the default.
+    ///
+    /// ```
+    /// use zxcvbn::Zxcvbn;
+    ///
+    /// let estimator = Zxcvbn::new().with_max_matches(1);
+    /// let entropy = estimator.estimate("password1", &[]);
+    /// assert_eq!(entropy.all_matches().len(), 1);
+    /// assert!(entropy.guesses() >= Zxcvbn::new().estimate("password1", &[]).guesses());
+    /// ```
+    pub fn with_max_matches(mut self, max_matches: usize) -> Self {
+        self.max_matches = Some(max_matches);
+        self
+    }
+
+    /// Sets the years dates and years in passwords are guessed around, the current year
+    /// and the 20 years around it by default.
+    ///
+    /// ```
+    /// use zxcvbn::matching::YearWindow;
+    /// use zxcvbn::Zxcvbn;
+    ///
+    /// let estimator = Zxcvbn::new().with_year_window(YearWindow {
+    ///     reference_year: 2030,
+    ///     min_year_space: 5,
+    ///     ..YearWindow::default()
+    /// });
+    /// assert!(estimator.estimate("2030", &[]).guesses() < Zxcvbn::new().estimate("2030", &[]).guesses());
+    /// ```
+    pub fn with_year_window(mut self, years: matching::YearWindow) -> Self {
+        self.years = years;
+        self
+    }
+
+    /// Sets how matches are scored, see [`ScoringParams`].
+    pub fn with_scoring_params(mut self, params: ScoringParams) -> Self {
+        self.scoring = params;
+        self
+    }
+
+    /// Sets whether estimates record the search for their sequence, see
+    /// [`Entropy::search_graph`]. Off by default, as recording it copies every candidate
+    /// match; meant for debugging scoring rather than production use.
+    pub fn with_search_graph(mut self, enabled: bool) -> Self {
+        self.search_graph = enabled;
+        self
+    }
+
+    /// The model version of this estimator's estimates, see [`Entropy::model_version`].
+    pub fn model_version(&self) -> String {
+        let mut version = model_version(&self.normalizers, self.profile, &self.years);
+        self.tag_settings(&mut version);
+        version
+    }
+
+    /// Adds the tags of the settings [`model_version`](crate::model_version) doesn't know
+    /// about: a Markov model taking the lower of the estimates, and a hash of the scoring
+    /// parameters, year window, match limit, dictionary files and Markov model that differ
+    /// from those of [`zxcvbn`].
+    fn tag_settings(&self, version: &mut String) {
+        let mut settings = Fingerprint::new();
+        let mut changed = false;
+        let mut scoring = Fingerprint::new();
+        self.scoring.fingerprint(&mut scoring);
+        let mut default_scoring = Fingerprint::new();
+        ScoringParams::default()
+            .js_match_order(self.profile.js_compatible())
+            .fingerprint(&mut default_scoring);
+        if scoring.finish() != default_scoring.finish() {
+            settings.write_str("scoring");
+            settings.write_u64(scoring.finish());
+            changed = true;
+        }
+        if self.years.reference_year != self.years.current_year
+            || self.years.min_year_space != scoring::MIN_YEAR_SPACE
+        {
+            settings.write_str("years");
+            settings.write_u64(self.years.reference_year as u64);
+            settings.write_u64(self.years.min_year_space as u64);
+            changed = true;
+        }
+        if let Some(max_matches) = self
+            .max_matches
+            .filter(|&max_matches| max_matches != matching::DEFAULT_MAX_MATCHES)
+        {
+            settings.write_str("max-matches");
+            settings.write_u64(max_matches as u64);
+            changed = true;
+        }
+        #[cfg(feature = "dictionary-files")]
+        for dictionary in self.dictionaries.load().iter() {
+            settings.write_str("dictionary");
+            dictionary.fingerprint(&mut settings);
+            changed = true;
+        }
+        if let Some((model, markov::MarkovMode::Minimum)) = &self.markov {
+            version.push_str("+markov");
+            settings.write_str("markov");
+            settings.write_u64(model.fingerprint());
+            changed = true;
+        }
+        if changed {
+            version.push_str("+settings=");
+            version.push_str(&settings.to_string());
+        }
+    }
+
+    /// Adds words that are treated as the most guessable words there are wherever they
+    /// appear in a password, including reversed or with l33t substitutions.
+    /// Words are compared case-insensitively.
+    ///
+    /// Returns an error, leaving the banned words as they were, if they would grow too many
+    /// to compile.
+    pub fn add_banned_words<I>(&self, words: I) -> Result<(), BanlistError>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.add_ban_rules(words.into_iter().map(|word| BanRule::new(word.as_ref())))
+    }
+
+    /// Adds words that are banned wherever their rules allow, like
+    /// [`add_banned_words`](Zxcvbn::add_banned_words) does for whole words anywhere.
+    ///
+    /// Returns an error, leaving the banned words as they were, if they would grow too many
+    /// to compile.
+    pub fn add_ban_rules<I>(&self, rules: I) -> Result<(), BanlistError>
+    where
+        I: IntoIterator<Item = BanRule>,
+    {
+        let rules = rules
+            .into_iter()
+            .filter(|rule| !rule.word().is_empty())
+            .collect::<Vec<_>>();
+        if rules.is_empty() {
+            return Ok(());
+        }
+        self.update_banlist(|banlist| banlist.with_rules(&rules))
+    }
+
+    /// Replaces the rules of a named list of banned words, such as one kept in sync with an
+    /// organization's list by [`ban_list_sync`]. Rules added with
+    /// [`add_ban_rules`](Zxcvbn::add_ban_rules) and the other lists are kept, and a list
+    /// without rules is removed. Only this list is compiled again, so words that change
+    /// often are best kept in a list rather than added with
+    /// [`add_ban_rules`](Zxcvbn::add_ban_rules), which compiles every rule added so far.
+    ///
+    /// Returns an error, leaving the list as it was, if it has too many words to compile.
+    ///
+    /// ```
+    /// use zxcvbn::{BanRule, Zxcvbn};
+    ///
+    /// let estimator = Zxcvbn::new();
+    /// estimator.set_ban_list("offices", [BanRule::new("Springfield")])?;
+    /// let entropy = estimator.estimate("Springfield2026", &[]);
+    /// estimator.set_ban_list("offices", [BanRule::new("Shelbyville")])?;
+    /// assert!(estimator.estimate("Springfield2026", &[]).guesses() > entropy.guesses());
+    /// # Ok::<(), zxcvbn::BanlistError>(())
+    /// ```
+    pub fn set_ban_list<I>(&self, name: &str, rules: I) -> Result<(), BanlistError>
+    where
+        I: IntoIterator<Item = BanRule>,
+    {
+        let rules = rules
+            .into_iter()
+            .filter(|rule| !rule.word().is_empty())
+            .collect::<Vec<_>>();
+        // compiled once, however often the swap is retried, and without the other lists
+        let list = if rules.is_empty() {
+            None
+        } else {
+            Some(Arc::new(RuleSet::new(rules)?))
+        };
+        self.banlist
+            .rcu(|banlist| banlist.with_list(name, list.clone()));
+        self.clear_cache();
+        Ok(())
+    }
+
+    /// Swaps in the banlist `update` compiles from the current one, unless it fails.
+    fn update_banlist<F>(&self, mut update: F) -> Result<(), BanlistError>
+    where
+        F: FnMut(&Banlist) -> Result<Banlist, BanlistError>,
+    {
+        let mut result = Ok(());
+        self.banlist.rcu(|banlist| match update(banlist) {
+            Ok(updated) => {
+                result = Ok(());
+                Arc::new(updated)
+            }
+            Err(err) => {
+                result = Err(err);
+                Arc::clone(banlist)
+            }
+        });
+        if result.is_ok() {
+            self.clear_cache();
+        }
+        result
+    }
+
+    /// Adds passwords that are rejected outright: a password that is one of them, ignoring
+    /// case and l33t substitutions, gets a score of 0 without running any matchers.
+    ///
+    /// Unlike [`add_banned_words`](Zxcvbn::add_banned_words), longer passwords that merely
+    /// contain a banned password are scored as usual.
+    pub fn add_banned_passwords<I>(&self, passwords: I)
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let passwords = passwords
+            .into_iter()
+            .map(|password| password.as_ref().to_lowercase())
+            .filter(|password| !password.is_empty())
+            .collect::<Vec<_>>();
+        if passwords.is_empty() {
+            return;
+        }
+        self.banned_passwords.rcu(|banned_passwords| {
+            let mut banned_passwords = BTreeSet::clone(banned_passwords);
+            banned_passwords.extend(passwords.iter().cloned());
+            banned_passwords
+        });
+        self.clear_cache();
+    }
+
+    /// Adds a word list loaded from a dictionary file, matched alongside the built-in
+    /// dictionaries. Each word is guessed by its rank in the file.
+    #[cfg(feature = "dictionary-files")]
+    pub fn add_dictionary(&self, dictionary: DictionaryFile) {
+        let dictionary = Arc::new(dictionary);
+        self.dictionaries.rcu(|dictionaries| {
+            let mut dictionaries = Vec::clone(dictionaries);
+            dictionaries.push(Arc::clone(&dictionary));
+            dictionaries
+        });
+        self.clear_cache();
+    }
+
+    fn clear_cache(&self) {
+        #[cfg(feature = "cache")]
+        if let Some(cache) = &self.cache {
+            cache.clear();
+        }
+    }
+
+    /// Calculates the strength of a password like [`zxcvbn`], also checking the banned
+    /// passwords and matching the banned words and added dictionaries.
+    pub fn estimate(&self, password: &str, user_inputs: &[&str]) -> Entropy {
+        self.estimate_with_scratch(password, user_inputs, &mut ScoringScratch::default())
+    }
+
+    /// Calculates the strength of a password like [`estimate`](Zxcvbn::estimate), scoring it
+    /// with the buffers of `scratch` instead of allocating new ones.
+    ///
+    /// Keeping one [`ScoringScratch`] per thread and passing it to every estimate saves
+    /// allocating the scoring tables on each call, for latency-sensitive callers. Matching
+    /// and feedback allocate as they do in [`estimate`](Zxcvbn::estimate).
+    ///
+    /// ```
+    /// use zxcvbn::{ScoringScratch, Zxcvbn};
+    ///
+    /// let estimator = Zxcvbn::new();
+    /// let mut scratch = ScoringScratch::new();
+    /// for password in ["correcthorsebatterystaple", "password1"] {
+    ///     let entropy = estimator.estimate_with_scratch(password, &[], &mut scratch);
+    ///     assert_eq!(entropy.guesses(), estimator.estimate(password, &[]).guesses());
+    /// }
+    /// ```
+    pub fn estimate_with_scratch(
+        &self,
+        password: &str,
+        user_inputs: &[&str],
+        scratch: &mut ScoringScratch,
+    ) -> Entropy {
+        #[cfg(feature = "cache")]
+        let entropy = match &self.cache {
+            Some(cache) => {
+                let digest = cache.digest(password, user_inputs);
+                cache.get(&digest).unwrap_or_else(|| {
+                    let generation = cache.generation();
+                    let entropy = self.estimate_uncached(password, user_inputs, scratch);
+                    cache.insert(digest, &entropy, generation);
+                    entropy
+                })
+            }
+            None => self.estimate_uncached(password, user_inputs, scratch),
+        };
+        #[cfg(not(feature = "cache"))]
+        let entropy = self.estimate_uncached(password, user_inputs, scratch);
+        if let Some(reporter) = &self.reporter {
+            reporter.report(&reporting::WeaknessReport::new(
+                &entropy,
+                password.chars().count(),
+            ));
+        }
+        entropy
+    }
+
+    fn estimate_uncached(
+        &self,
+        password: &str,
+        user_inputs: &[&str],
+        scratch: &mut ScoringScratch,
+    ) -> Entropy {
+        let banlist = self.banlist.load();
+        let banned_passwords = self.banned_passwords.load();
+        #[cfg(feature = "dictionary-files")]
+        let dictionaries = self.dictionaries.load();
+        let mut inputs = Vec::new();
+        let user_inputs = if self.context.is_empty() {
+            user_inputs
+        } else {
+            inputs.extend_from_slice(user_inputs);
+            inputs.extend(self.context.words().iter().map(String::as_str));
+            &inputs
+        };
+        let mut entropy = estimate(
+            password,
+            user_inputs,
+            &self.normalizers,
+            self.profile,
+            Some(&banlist),
+            Some(&banned_passwords),
+            #[cfg(feature = "dictionary-files")]
+            &dictionaries,
+            self.max_matches.unwrap_or(matching::DEFAULT_MAX_MATCHES),
+            self.years,
+            &self.scoring,
+            self.search_graph,
+            scratch,
+        );
+        if let Some((model, mode)) = &self.markov {
+            markov::apply(
+                &mut entropy,
+                password,
+                model,
+                *mode,
+                &self.years,
+                &self.scoring,
+            );
+        }
+        guess_model::apply(&mut entropy, password, &self.guess_models);
+        self.tag_settings(&mut entropy.model_version);
+        entropy
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn estimate(
+    password: &str,
+    user_inputs: &[&str],
+    normalizers: &Normalizers,
+    profile: Profile,
+    banlist: Option<&Banlist>,
+    banned_passwords: Option<&BTreeSet<String>>,
+    #[cfg(feature = "dictionary-files")] dictionaries: &[Arc<DictionaryFile>],
+    max_matches: usize,
+    years: matching::YearWindow,
+    scoring: &ScoringParams,
+    record_search: bool,
+    scratch: &mut ScoringScratch,
+) -> Entropy {
+    // Only evaluate the first 100 characters of the input.
+    // This prevents potential DoS attacks from sending extremely long input strings.
+    // Normalizing may expand characters, so the result is truncated again.
+    let password = password
+        .chars()
+        .take(MAX_PASSWORD_LENGTH)
+        .collect::<String>();
+    let (password, mut origins) = normalizers.normalize_with_origins(&password);
+    let password = password
+        .chars()
+        .take(MAX_PASSWORD_LENGTH)
+        .collect::<String>();
+    origins.truncate(MAX_PASSWORD_LENGTH);
+    let model_version = model_version(normalizers, profile, &years);
+
     if password.is_empty() {
+        let (feedback, calc_time) = time_scoped(|| {
+            feedback::get_feedback(&password, Score::TooGuessable, &[], None, &years, scoring)
+        });
         return Entropy {
             guesses: 0,
             guesses_log10: f64::NEG_INFINITY,
+            pattern_guesses: 0,
+            markov_guesses: None,
+            model_guesses: Vec::new(),
             crack_times: CrackTimes::new(0),
-            score: 0,
-            feedback: feedback::get_feedback(0, &[]),
+            score: Score::TooGuessable,
+            feedback,
             sequence: Vec::default(),
-            calc_time: Duration::from_secs(0),
+            all_matches: Vec::default(),
+            recipe: None,
+            model_version,
+            calc_time,
+            search_graph: None,
         };
     }
 
-    let (result, calc_time) = time_scoped(|| {
-        // Only evaluate the first 100 characters of the input.
-        // This prevents potential DoS attacks from sending extremely long input strings.
-        let password = password.chars().take(100).collect::<String>();
+    let (is_banned, calc_time) = time_scoped(|| {
+        banned_passwords.is_some_and(|banned| matching::is_banned_password(&password, banned))
+    });
+    if is_banned {
+        return Entropy {
+            guesses: 1,
+            guesses_log10: 0.0,
+            pattern_guesses: 1,
+            markov_guesses: None,
+            model_guesses: Vec::new(),
+            crack_times: CrackTimes::new(1),
+            score: Score::TooGuessable,
+            feedback: Some(feedback::get_banned_password_feedback()),
+            sequence: Vec::default(),
+            all_matches: Vec::default(),
+            recipe: None,
+            model_version,
+            calc_time,
+            search_graph: None,
+        };
+    }
 
-        let sanitized_inputs = user_inputs
-            .iter()
-            .enumerate()
-            .map(|(i, x)| (x.to_lowercase(), i + 1))
-            .collect();
+    let ((result, all_matches, recipe, search_graph, feedback), calc_time) = time_scoped(|| {
+        let custom_words = matching::CustomWords {
+            user_inputs: user_inputs
+                .iter()
+                .enumerate()
+                .map(|(i, x)| (x.to_lowercase(), i + 1))
+                .collect(),
+            banlist: banlist.filter(|banlist| !banlist.is_empty()),
+            #[cfg(feature = "dictionary-files")]
+            dictionaries,
+            js_compatible: profile.js_compatible(),
+            budget: matching::MatchBudget::new(max_matches),
+            years,
+            scoring: scoring.clone(),
+            base_analyses: Default::default(),
+        };
 
-        let matches = matching::omnimatch(&password, &sanitized_inputs);
-        scoring::most_guessable_match_sequence(&password, &matches, false)
+        let matches = matching::omnimatch(&password, &custom_words);
+        let matches = scoring::cap_matches(&password, matches, max_matches);
+        let recipe = feedback::get_recipe(&password, &matches);
+        let (result, search_graph) = if record_search {
+            let (result, graph) =
+                scoring::traced_match_sequence(&password, &matches, scoring, scratch);
+            (result, Some(graph))
+        } else {
+            let result = scoring::most_guessable_match_sequence_with_scratch(
+                &password, &matches, scoring, scratch,
+            );
+            (result, None)
+        };
+        // feedback is timed too, as finding the minimum addition scores the password again
+        let score = time_estimates::estimate_attack_times(result.guesses).1;
+        let feedback = feedback::get_sensitive_data_feedback(&matches).or_else(|| {
+            feedback::get_feedback(&password, score, &result.sequence, recipe, &years, scoring)
+        });
+        (result, matches, recipe, search_graph, feedback)
     });
     let (crack_times, score) = time_estimates::estimate_attack_times(result.guesses);
-    let feedback = feedback::get_feedback(score, &result.sequence);
+    let (mut sequence, mut all_matches) = (result.sequence, all_matches);
+    if !origins.is_identity() {
+        for m in sequence.iter_mut().chain(&mut all_matches) {
+            origins.restore(m);
+        }
+    }
 
     Entropy {
         guesses: result.guesses,
         guesses_log10: result.guesses_log10,
+        pattern_guesses: result.guesses,
+        markov_guesses: None,
+        model_guesses: Vec::new(),
         crack_times,
         score,
         feedback,
-        sequence: result.sequence,
+        sequence,
+        all_matches,
+        recipe,
+        model_version,
         calc_time,
+        search_graph,
     }
 }
 
@@ -184,6 +1038,45 @@ mod tests {
             TestResult::from_bool(true)
         }
 
+        fn test_appending_a_character_never_lowers_guesses(
+            password: String,
+            c: char
+        ) -> TestResult {
+            use matching::patterns::MatchPattern;
+
+            // other patterns may end with the character and cost fewer guesses than the
+            // password without it, e.g. "c4" and "c4n", but a bruteforced character always
+            // adds guesses
+            let entropy = zxcvbn(&format!("{}{}", password, c), &[]);
+            match entropy.sequence().last() {
+                Some(last) if matches!(last.pattern, MatchPattern::BruteForce) => {
+                    TestResult::from_bool(entropy.guesses() >= zxcvbn(&password, &[]).guesses())
+                }
+                _ => TestResult::discard(),
+            }
+        }
+
+        fn test_user_input_from_password_never_raises_guesses(
+            password: String,
+            user_inputs: Vec<String>,
+            start: usize,
+            len: usize
+        ) -> TestResult {
+            let chars = password.chars().collect::<Vec<char>>();
+            if chars.is_empty() {
+                return TestResult::discard();
+            }
+            let start = start % chars.len();
+            let end = start + 1 + len % (chars.len() - start);
+            let substring = chars[start..end].iter().collect::<String>();
+            let inputs = user_inputs.iter().map(|s| s.as_ref()).collect::<Vec<&str>>();
+            let mut with_substring = inputs.clone();
+            with_substring.push(&substring);
+            TestResult::from_bool(
+                zxcvbn(&password, &with_substring).guesses() <= zxcvbn(&password, &inputs).guesses(),
+            )
+        }
+
         #[cfg(feature = "ser")]
         fn test_zxcvbn_serialisation_doesnt_panic(password: String, user_inputs: Vec<String>) -> TestResult {
             let inputs = user_inputs.iter().map(|s| s.as_ref()).collect::<Vec<&str>>();
@@ -198,18 +1091,620 @@ mod tests {
         let password = "r0sebudmaelstrom11/20/91aaaa";
         let entropy = zxcvbn(password, &[]);
         assert_eq!(entropy.guesses_log10 as u16, 14);
-        assert_eq!(entropy.score, 4);
+        assert_eq!(entropy.score, Score::VeryUnguessable);
         assert!(!entropy.sequence.is_empty());
         assert!(entropy.feedback.is_none());
         assert!(entropy.calc_time.as_nanos() > 0);
     }
 
+    /// Scores a corpus exercising every matcher in two child processes, which seed their
+    /// hashers differently, and checks that both print the same results.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_zxcvbn_is_deterministic() {
+        const CHILD_ENV: &str = "ZXCVBN_DETERMINISM_CHILD";
+        const CORPUS: &[&str] = &[
+            "password",
+            "p@ssw0rd",
+            "P4$$w0rd!",
+            "correcthorsebatterystaple",
+            "Tr0ub4dour&3",
+            "qwertyuiop",
+            "zxcvbnm,./",
+            "1qaz2wsx3edc",
+            "aoeuidhtns",
+            "789456123",
+            "aaaaaaaa",
+            "abcabcabc",
+            "abcdefg",
+            "9876543",
+            "1990",
+            "11/20/91",
+            "2001-09-11",
+            "johnsmith1987",
+            "alice1990",
+            "r0sebudmaelstrom11/20/91aaaa",
+            "mary|eva1|anne",
+            "<({[1337]})>",
+            "Imaginative-Say-Shoulder-Dish-0",
+        ];
+
+        if std::env::var_os(CHILD_ENV).is_some() {
+            for password in CORPUS {
+                let entropy = zxcvbn(password, &["alice", "smith"]);
+                println!(
+                    "entropy: {} {} {:?} {:?}",
+                    entropy.guesses, entropy.score, entropy.feedback, entropy.sequence
+                );
+            }
+            return;
+        }
+
+        let run = || {
+            let output = std::process::Command::new(std::env::current_exe().unwrap())
+                .args([
+                    "--exact",
+                    "tests::test_zxcvbn_is_deterministic",
+                    "--nocapture",
+                ])
+                .env(CHILD_ENV, "1")
+                .output()
+                .unwrap();
+            assert!(output.status.success());
+            String::from_utf8(output.stdout)
+                .unwrap()
+                .lines()
+                // The harness prints the test name without a newline before the output.
+                .filter_map(|line| line.find("entropy: ").map(|k| line[k..].to_string()))
+                .collect::<Vec<_>>()
+        };
+        let first = run();
+        assert_eq!(first.len(), CORPUS.len());
+        assert_eq!(first, run());
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_add_banned_words() {
+        let estimator = Zxcvbn::new();
+        estimator.add_banned_words(["SummerSale", ""]).unwrap();
+        estimator
+            .add_banned_words(vec![String::from("hunter")])
+            .unwrap();
+        for password in ["summersale", "5ummer5ale", "elasremmus", "hunter!"] {
+            let entropy = estimator.estimate(password, &[]);
+            assert!(entropy.score <= Score::VeryGuessable, "{}", password);
+            assert_eq!(
+                entropy.feedback.unwrap().warning(),
+                Some(feedback::Warning::ThisContainsABannedWord)
+            );
+        }
+        assert!(zxcvbn("5ummer5ale", &[]).score > Score::TooGuessable);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_add_banned_passwords() {
+        let estimator = Zxcvbn::new();
+        estimator.add_banned_passwords(["Correct Horse Battery Staple"]);
+        for password in [
+            "correct horse battery staple",
+            "C0rrect H0r$e B4ttery 5taple",
+        ] {
+            let entropy = estimator.estimate(password, &[]);
+            assert_eq!(entropy.score, Score::TooGuessable, "{}", password);
+            assert_eq!(entropy.guesses, 1);
+            assert!(entropy.sequence.is_empty());
+            let feedback = entropy.feedback.unwrap();
+            assert_eq!(
+                feedback.warning(),
+                Some(feedback::Warning::ThisIsABannedPassword)
+            );
+            assert_eq!(feedback.minimum_addition(), None);
+        }
+        assert!(
+            estimator
+                .estimate("correct horse battery staple!", &[])
+                .score
+                > Score::TooGuessable
+        );
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_add_ban_rules() {
+        use crate::matching::patterns::{DictionaryPattern, MatchPattern};
+
+        let estimator = Zxcvbn::new();
+        estimator
+            .add_ban_rules([BanRule::new("RoadRunner")
+                .min_length(5)
+                .position(BanPosition::Start)])
+            .unwrap();
+        let is_banned = |password: &str| {
+            estimator.estimate(password, &[]).sequence.iter().any(|m| {
+                matches!(
+                    m.pattern,
+                    MatchPattern::Dictionary(DictionaryPattern {
+                        dictionary_name: frequency_lists::DictionaryType::Banned,
+                        ..
+                    })
+                )
+            })
+        };
+        assert!(is_banned("roadrunner"));
+        assert!(is_banned("Runner!9x"));
+        assert!(is_banned("rennur!9x"));
+        assert!(!is_banned("9x!runner"));
+        assert!(!is_banned("road1234"));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_add_banned_words_while_estimating() {
+        let estimator = Zxcvbn::new();
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for _ in 0..20 {
+                        estimator.estimate("bluemoon", &[]);
+                    }
+                });
+            }
+            for word in ["blue", "moon", "bluemoon"] {
+                estimator.add_banned_words([word]).unwrap();
+            }
+        });
+        assert_eq!(estimator.banlist.load().len(), 3);
+        assert_eq!(
+            estimator.estimate("bluemoon", &[]).score,
+            Score::TooGuessable
+        );
+    }
+
+    #[cfg(feature = "dictionary-files")]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_add_dictionary() {
+        let mut bytes = Vec::new();
+        DictionaryFile::compile(["xkqzvbnm", "wqjfplrt"], &mut bytes).unwrap();
+        let estimator = Zxcvbn::new();
+        let before = estimator.estimate("Wqjfplrt", &[]);
+        estimator.add_dictionary(DictionaryFile::new(bytes).unwrap());
+        let entropy = estimator.estimate("Wqjfplrt", &[]);
+        assert!(entropy.guesses < before.guesses);
+        assert_eq!(entropy.score, Score::TooGuessable);
+        match &entropy.sequence[0].pattern {
+            matching::patterns::MatchPattern::Dictionary(p) => {
+                assert_eq!(p.dictionary_name, frequency_lists::DictionaryType::Custom);
+                assert_eq!(p.rank, 2);
+            }
+            _ => panic!("expected a dictionary match"),
+        }
+        assert!(zxcvbn("Wqjfplrt", &[]).score > Score::TooGuessable);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_zxcvbn_normalizes_homoglyphs() {
+        for password in ["pаsswоrd", "ｐａｓｓｗｏｒｄ", "pass\u{200D}word"] {
+            let entropy = zxcvbn(password, &[]);
+            assert_eq!(entropy.score, Score::TooGuessable, "{}", password);
+            // matches index the password as given
+            let m = &entropy.sequence[0];
+            assert_eq!((m.i, m.j), (0, password.chars().count() - 1));
+            assert_eq!(m.token, password);
+            assert!(
+                matches!(&m.pattern, matching::patterns::MatchPattern::Dictionary(p) if p.matched_word == "password")
+            );
+        }
+        let entropy = zxcvbn("\u{200B}\u{200B}", &[]);
+        assert_eq!(entropy.guesses, 0);
+        for password in [
+            "ﬁ\u{200B}re",
+            "\u{200B}\u{200B}abc",
+            "e\u{301}clair",
+            "½ｐａｓｓ①",
+        ] {
+            let chars = password.chars().collect::<Vec<_>>();
+            let entropy = zxcvbn(password, &[]);
+            for m in entropy.sequence.iter().chain(&entropy.all_matches) {
+                assert_eq!(m.token, chars[m.i..=m.j].iter().collect::<String>());
+            }
+            assert_eq!(entropy.sequence[0].i, 0);
+            assert_eq!(entropy.sequence.last().unwrap().j, chars.len() - 1);
+        }
+
+        let estimator = Zxcvbn::from_normalizers(normalization::Normalizers::none());
+        assert!(estimator.estimate("pаsswоrd", &[]).score > Score::TooGuessable);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_zxcvbn_emoji() {
+        // Each of these emoji takes several chars, which bruteforce would count separately.
+        let entropy = zxcvbn("🇳🇱👍🏽🦑🧗🏿", &[]);
+        assert!(entropy
+            .sequence
+            .iter()
+            .all(|m| m.pattern.variant() == "emoji"));
+        assert!(entropy.guesses_log10 < 15.0, "{}", entropy.guesses_log10);
+
+        let entropy = zxcvbn("🔥🔥🔥🔥🔥🔥", &[]);
+        assert_eq!(entropy.score, Score::TooGuessable);
+        assert_eq!(
+            entropy.feedback.unwrap().warning(),
+            Some(feedback::Warning::RepeatsLikeAaaAreEasyToGuess)
+        );
+
+        let entropy = zxcvbn("😀😃😄😁😆😅", &[]);
+        assert_eq!(entropy.score, Score::TooGuessable);
+        assert_eq!(
+            entropy.feedback.unwrap().warning(),
+            Some(feedback::Warning::EmojiNextToEachOtherOnTheKeyboardAreEasyToGuess)
+        );
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_zxcvbn_decades_and_year_ranges() {
+        for password in ["1990s", "80's", "1990-1995", "2001to05"] {
+            let entropy = zxcvbn(password, &[]);
+            assert_eq!(entropy.sequence.len(), 1, "{}", password);
+            assert!(entropy.score <= Score::VeryGuessable);
+            assert_eq!(
+                entropy.feedback.unwrap().warning(),
+                Some(feedback::Warning::DatesAreOftenEasyToGuess)
+            );
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_zxcvbn_appended_current_year() {
+        let years = matching::YearWindow {
+            current_year: 2025,
+            ..matching::YearWindow::new(1950)
+        };
+        let estimator = Zxcvbn::new().with_year_window(years);
+        let entropy = estimator.estimate("monkey2025", &[]);
+        let two_years_on = matching::YearWindow {
+            current_year: 2027,
+            ..years
+        };
+        let later = Zxcvbn::new().with_year_window(two_years_on);
+        assert!(entropy.guesses() < later.estimate("monkey2025", &[]).guesses());
+        assert_eq!(
+            entropy.guesses(),
+            later.estimate("monkey2027", &[]).guesses()
+        );
+        assert!(entropy
+            .feedback
+            .unwrap()
+            .suggestions()
+            .contains(&feedback::Suggestion::AvoidRecentYears));
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_zxcvbn_birth_date_from_user_inputs() {
+        let warning = |password: &str, user_inputs: &[&str]| {
+            zxcvbn(password, user_inputs).feedback.unwrap().warning()
+        };
+        // either way the day and month are read
+        for birth_date in ["1990-05-12", "12.05.1990", "05/12/1990", "19900512"] {
+            assert_eq!(
+                warning("12051990", &["jsmith", birth_date]),
+                Some(feedback::Warning::ThisIsADateFromYourPersonalData),
+                "{}",
+                birth_date
+            );
+        }
+        assert_eq!(
+            warning("12/05/90", &["1990-05-12"]),
+            Some(feedback::Warning::ThisIsADateFromYourPersonalData)
+        );
+        assert_eq!(
+            warning("12051990", &["1990-05-13"]),
+            Some(feedback::Warning::DatesAreOftenEasyToGuess)
+        );
+        assert_eq!(
+            warning("12051990", &["1990"]),
+            Some(feedback::Warning::DatesAreOftenEasyToGuess)
+        );
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_zxcvbn_phone_number_from_user_inputs() {
+        let user_inputs = ["jsmith", "+1 (415) 392-7146"];
+        for password in ["Tigers3927146", "3927146!", "4153927146x"] {
+            let entropy = zxcvbn(password, &user_inputs);
+            assert!(
+                entropy.guesses < zxcvbn(password, &[]).guesses,
+                "{}",
+                password
+            );
+            assert!(entropy
+                .sequence
+                .iter()
+                .any(|m| m.pattern.variant() == "phone"));
+            assert_eq!(
+                entropy.feedback.unwrap().warning(),
+                Some(feedback::Warning::ThisIsYourPhoneNumber),
+                "{}",
+                password
+            );
+        }
+    }
+
+    #[cfg(feature = "national-ids-us")]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_zxcvbn_national_id() {
+        // warned about even in a password that is otherwise strong enough
+        let entropy = zxcvbn("correct horse 078-05-1120", &[]);
+        assert!(entropy.score >= Score::SafelyUnguessable);
+        let feedback = entropy.feedback.unwrap();
+        assert_eq!(
+            feedback.warning(),
+            Some(feedback::Warning::ThisLooksLikeANationalIdNumber)
+        );
+        assert_eq!(
+            feedback.suggestions()[0],
+            feedback::Suggestion::NeverUseIdNumbers
+        );
+        assert!(feedback.minimum_addition().is_none());
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_zxcvbn_payment_details() {
+        for (password, warning) in [
+            (
+                "4111 1111 1111 1111",
+                feedback::Warning::ThisLooksLikeACardNumber,
+            ),
+            (
+                "DE89370400440532013000",
+                feedback::Warning::ThisLooksLikeABankAccountNumber,
+            ),
+        ] {
+            let entropy = zxcvbn(password, &[]);
+            assert!(entropy.score <= Score::VeryGuessable, "{}", password);
+            let feedback = entropy.feedback.unwrap();
+            assert_eq!(feedback.warning(), Some(warning));
+            assert_eq!(
+                feedback.suggestions()[0],
+                feedback::Suggestion::NeverUsePaymentDetails
+            );
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_zxcvbn_mirrored_text() {
+        let password = "Xk7#qzzq#7kX";
+        let entropy = zxcvbn(password, &[]);
+        assert_eq!(entropy.sequence.len(), 1);
+        assert_eq!(entropy.sequence[0].pattern.variant(), "mirror");
+        assert!(entropy.guesses < zxcvbn("Xk7#qz", &[]).guesses * 4);
+        assert_eq!(
+            entropy.feedback.unwrap().warning(),
+            Some(feedback::Warning::MirroredTextIsHardlyStrongerThanHalfOfIt)
+        );
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_all_matches() {
+        let entropy = zxcvbn("iloveyouXk7#q", &[]);
+        let all_matches = entropy.all_matches();
+        assert!(all_matches.len() > entropy.sequence().len());
+        assert!(all_matches.iter().all(|m| m.guesses.is_some()));
+        assert!(all_matches
+            .windows(2)
+            .all(|w| (w[0].i, w[0].j) <= (w[1].i, w[1].j)));
+        // the sequence is made of some of the matches, besides bruteforce
+        for m in entropy.sequence() {
+            if m.pattern.variant() != "bruteforce" {
+                assert!(all_matches.contains(m), "{:?}", m);
+            }
+        }
+        assert!(all_matches.iter().any(|m| m.token == "love"));
+        assert!(zxcvbn("", &[]).all_matches().is_empty());
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_max_matches() {
+        // mirrored and shifted repeats of every substring are matched again and again
+        let password = "1!".repeat(50);
+        let entropy = zxcvbn(&password, &[]);
+        assert!(entropy.all_matches().len() <= matching::DEFAULT_MAX_MATCHES);
+        assert!(entropy.score() <= Score::VeryGuessable);
+
+        let estimator = Zxcvbn::new().with_max_matches(10);
+        let capped = estimator.estimate(&password, &[]);
+        assert_eq!(capped.all_matches().len(), 10);
+        assert!(capped.guesses() >= entropy.guesses());
+
+        let password = "iloveyouXk7#q";
+        assert_eq!(
+            Zxcvbn::new().estimate(password, &[]).all_matches(),
+            zxcvbn(password, &[]).all_matches()
+        );
+        let entropy = Zxcvbn::new().with_max_matches(0).estimate(password, &[]);
+        assert!(entropy.all_matches().is_empty());
+        assert_eq!(entropy.sequence()[0].pattern.variant(), "bruteforce");
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_model_version() {
+        let version = zxcvbn("password", &[]).model_version().to_string();
+        assert!(version.starts_with(MODEL_VERSION));
+        assert_eq!(
+            version.contains("+sports-teams"),
+            cfg!(feature = "sports-teams")
+        );
+        assert_eq!(
+            version.contains("+national-ids-us"),
+            cfg!(feature = "national-ids-us")
+        );
+        // the same for every password, even those returned early
+        assert_eq!(zxcvbn("", &[]).model_version(), version);
+        let estimator = Zxcvbn::new();
+        estimator.add_banned_passwords(["hunter2"]);
+        assert_eq!(estimator.estimate("hunter2", &[]).model_version(), version);
+
+        let estimator = Zxcvbn::from_normalizers(Normalizers {
+            confusables: false,
+            ..Normalizers::default()
+        });
+        assert!(estimator
+            .estimate("password", &[])
+            .model_version()
+            .contains("+normalizers=zero-width,nfkc"));
+        let estimator = Zxcvbn::from_normalizers(Normalizers::none());
+        assert!(estimator
+            .estimate("password", &[])
+            .model_version()
+            .contains("+normalizers=none"));
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_model_version_settings() {
+        let version = zxcvbn("password", &[]).model_version().to_string();
+        assert!(version.contains(&format!("+year={}", *scoring::REFERENCE_YEAR)));
+        assert_eq!(Zxcvbn::new().model_version(), version);
+
+        let years = matching::YearWindow {
+            reference_year: 2020,
+            min_year_space: 20,
+            current_year: 2020,
+        };
+        let estimators = [
+            Zxcvbn::new().with_scoring_params(
+                ScoringParams::new().min_guesses(matching::patterns::PatternKind::Date, 1),
+            ),
+            Zxcvbn::new().with_scoring_params(ScoringParams::new().sequence_length_penalty(100)),
+            Zxcvbn::new().with_scoring_params(
+                ScoringParams::new()
+                    .sequence_penalty(scoring::SequencePenalty::function(|len| len as u64)),
+            ),
+            Zxcvbn::new().with_year_window(matching::YearWindow {
+                reference_year: 2020,
+                ..matching::YearWindow::default()
+            }),
+            Zxcvbn::new().with_year_window(matching::YearWindow {
+                min_year_space: 5,
+                ..matching::YearWindow::default()
+            }),
+            Zxcvbn::new().with_max_matches(10),
+        ];
+        let mut versions = BTreeSet::new();
+        for estimator in &estimators {
+            let estimated = estimator
+                .estimate("password", &[])
+                .model_version()
+                .to_string();
+            assert_eq!(estimated, estimator.model_version());
+            assert_ne!(estimated, version);
+            assert!(estimated.contains("+settings="), "{}", estimated);
+            versions.insert(estimated);
+        }
+        assert_eq!(versions.len(), estimators.len());
+        // a pinned current year is tagged as such
+        let pinned = Zxcvbn::new().with_year_window(years).model_version();
+        assert!(pinned.contains("+year=2020"));
+        assert!(!pinned.contains("+settings="));
+
+        // the same settings give the same version
+        let estimator = Zxcvbn::new().with_max_matches(10);
+        assert_eq!(estimator.model_version(), estimators[5].model_version());
+    }
+
+    #[cfg(feature = "dictionary-files")]
+    #[test]
+    fn test_model_version_dictionary_files() {
+        let dictionary = |words: &[&str]| {
+            let mut bytes = Vec::new();
+            DictionaryFile::compile(words, &mut bytes).unwrap();
+            DictionaryFile::new(bytes).unwrap()
+        };
+        let estimator = Zxcvbn::new();
+        let version = estimator.model_version();
+        estimator.add_dictionary(dictionary(&["acmecorp"]));
+        let with_acme = estimator.model_version();
+        assert_ne!(with_acme, version);
+        assert_eq!(
+            estimator.estimate("acmecorp", &[]).model_version(),
+            with_acme
+        );
+
+        let estimator = Zxcvbn::new();
+        estimator.add_dictionary(dictionary(&["roadrunner"]));
+        assert_ne!(estimator.model_version(), with_acme);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_zxcvbn_shift_repeats() {
+        for password in ["Xk7#q*xK&3Q8", "123456^%$#@!"] {
+            let entropy = zxcvbn(password, &[]);
+            assert_eq!(entropy.sequence.len(), 1, "{}", password);
+            assert_eq!(entropy.sequence[0].pattern.variant(), "shift_repeat");
+            assert_eq!(
+                entropy.feedback.unwrap().warning(),
+                Some(feedback::Warning::RetypingWithShiftIsEasyToGuess)
+            );
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_zxcvbn_echoes() {
+        let entropy = zxcvbn("ppaasswwoorrdd", &[]);
+        assert_eq!(entropy.sequence.len(), 1);
+        assert_eq!(entropy.sequence[0].pattern.variant(), "echo");
+        assert!(entropy.guesses < zxcvbn("password", &[]).guesses * 5);
+        assert_eq!(
+            entropy.feedback.unwrap().warning(),
+            Some(feedback::Warning::TypingEachCharacterTwiceIsEasyToGuess)
+        );
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_zxcvbn_shifted_sequences() {
+        let entropy = zxcvbn("!@#$%6", &[]);
+        assert_eq!(entropy.sequence.len(), 1);
+        assert_eq!(entropy.sequence[0].pattern.variant(), "sequence");
+        assert_eq!(entropy.score, Score::TooGuessable);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_zxcvbn_character_codes() {
+        for password in ["alt+0169", "\\u00e9", "U+1F600", "&#169;"] {
+            let entropy = zxcvbn(password, &[]);
+            assert_eq!(entropy.sequence.len(), 1, "{}", password);
+            assert_eq!(entropy.sequence[0].pattern.variant(), "regex");
+            assert_eq!(
+                entropy.feedback.unwrap().warning(),
+                Some(feedback::Warning::CharacterCodesAreEasyToGuess)
+            );
+        }
+    }
+
     #[cfg_attr(not(target_arch = "wasm32"), test)]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn test_zxcvbn_empty() {
         let password = "";
         let entropy = zxcvbn(password, &[]);
-        assert_eq!(entropy.score, 0);
+        assert_eq!(entropy.score, Score::TooGuessable);
         assert_eq!(entropy.guesses, 0);
         assert_eq!(entropy.guesses_log10, f64::NEG_INFINITY);
         assert_eq!(entropy.crack_times, CrackTimes::new(0));
@@ -221,7 +1716,7 @@ mod tests {
     fn test_zxcvbn_unicode() {
         let password = "𐰊𐰂𐰄𐰀𐰁";
         let entropy = zxcvbn(password, &[]);
-        assert_eq!(entropy.score, 1);
+        assert_eq!(entropy.score, Score::VeryGuessable);
     }
 
     #[cfg_attr(not(target_arch = "wasm32"), test)]
@@ -229,7 +1724,7 @@ mod tests {
     fn test_zxcvbn_unicode_2() {
         let password = "r0sebudmaelstrom丂/20/91aaaa";
         let entropy = zxcvbn(password, &[]);
-        assert_eq!(entropy.score, 4);
+        assert_eq!(entropy.score, Score::VeryUnguessable);
     }
 
     #[cfg_attr(not(target_arch = "wasm32"), test)]
@@ -237,9 +1732,11 @@ mod tests {
     fn test_issue_13() {
         let password = "Imaginative-Say-Shoulder-Dish-0";
         let entropy = zxcvbn(password, &[]);
-        assert_eq!(entropy.score, 4);
+        assert_eq!(entropy.score, Score::VeryUnguessable);
     }
 
+    // "me" and "now" are words phone keyboards suggest, and are guessed sooner with them
+    #[cfg(not(feature = "autosuggest"))]
     #[cfg_attr(not(target_arch = "wasm32"), test)]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn test_issue_15_example_1() {
@@ -247,7 +1744,7 @@ mod tests {
         let entropy = zxcvbn(password, &[]);
         assert_eq!(entropy.guesses, 372_010_000);
         assert!((entropy.guesses_log10 - 8.57055461430783).abs() < f64::EPSILON);
-        assert_eq!(entropy.score, 3);
+        assert_eq!(entropy.score, Score::SafelyUnguessable);
     }
 
     #[cfg_attr(not(target_arch = "wasm32"), test)]
@@ -257,7 +1754,7 @@ mod tests {
         let entropy = zxcvbn(password, &[]);
         assert_eq!(entropy.guesses, 1_010_000);
         assert!((entropy.guesses_log10 - 6.004321373782642).abs() < f64::EPSILON);
-        assert_eq!(entropy.score, 2);
+        assert_eq!(entropy.score, Score::SomewhatGuessable);
     }
 
     #[cfg_attr(not(target_arch = "wasm32"), test)]
@@ -265,8 +1762,8 @@ mod tests {
     fn test_overflow_safety() {
         let password = "!QASW@#EDFR$%TGHY^&UJKI*(OL";
         let entropy = zxcvbn(password, &[]);
-        assert_eq!(entropy.guesses, u64::max_value());
-        assert_eq!(entropy.score, 4);
+        assert_eq!(entropy.guesses, u64::MAX);
+        assert_eq!(entropy.score, Score::VeryUnguessable);
     }
 
     #[cfg_attr(not(target_arch = "wasm32"), test)]
@@ -275,6 +1772,6 @@ mod tests {
         let password = "08märz2010";
         let entropy = zxcvbn(password, &[]);
         assert_eq!(entropy.guesses, 100010000);
-        assert_eq!(entropy.score, 3);
+        assert_eq!(entropy.score, Score::SafelyUnguessable);
     }
 }
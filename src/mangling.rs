@@ -0,0 +1,345 @@
+//! Experimental guess estimates for dictionary matches under a concrete cracking attack.
+//!
+//! zxcvbn guesses a dictionary match from the rank of its word in the bundled lists, with
+//! heuristic multipliers for capitalization, l33t substitutions and reversal. Security
+//! teams know the attack they would actually run: a wordlist put through a set of mangling
+//! rules, e.g. hashcat's best64.rule. [`RuleAttack`] estimates when such an attack would
+//! reach each dictionary match of an estimate, to compare against the heuristics.
+//! [`hashcat_attack`] goes the other way, describing a hashcat attack that would crack the
+//! password of an estimate, to check the estimate against real cracking runs.
+//!
+//! ```
+//! use zxcvbn::mangling::RuleAttack;
+//! use zxcvbn::zxcvbn;
+//!
+//! let attack = RuleAttack::new(["dragon", "monkey", "password"], 64);
+//! let estimates = attack.estimate(&zxcvbn("Monkey1987", &[]));
+//! assert_eq!(estimates[0].word, "monkey");
+//! assert_eq!(estimates[0].attack_guesses, Some(2 * 64));
+//! ```
+//!
+//! This module is experimental and may change in minor releases.
+
+use std::collections::HashMap;
+
+use zxcvbn_core::guesses::Guesses;
+
+use crate::matching::patterns::{DictionaryPattern, MatchPattern};
+use crate::matching::Match;
+use crate::Entropy;
+
+/// A dictionary attack running every word of a wordlist through a number of mangling rules,
+/// one word after the other.
+#[derive(Debug, Clone, Default)]
+pub struct RuleAttack {
+    /// Rank of each lowercased word in the wordlist, starting at 1.
+    ranks: HashMap<String, u64>,
+    /// Number of rules applied to each word, including the rule leaving it unchanged.
+    rules: u64,
+}
+
+/// How a dictionary match of an estimate compares with a [`RuleAttack`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+pub struct ManglingEstimate {
+    /// Beginning of the match.
+    pub i: usize,
+    /// End of the match.
+    pub j: usize,
+    /// Token that has been matched.
+    pub token: String,
+    /// The dictionary word the token was matched with.
+    pub word: String,
+    /// The guesses zxcvbn estimated for the match.
+    pub heuristic_guesses: u64,
+    /// The guesses the attack needs to reach the token, or `None` if the word is not in
+    /// its wordlist.
+    pub attack_guesses: Option<u64>,
+}
+
+impl RuleAttack {
+    /// Creates an attack from a wordlist, most likely word first, and the number of rules
+    /// applied to each word. Words are compared case-insensitively, and a rule count of 0 is
+    /// taken as 1, trying every word as is.
+    pub fn new<I>(words: I, rules: u64) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut ranks = HashMap::new();
+        for word in words {
+            let rank = ranks.len() as u64 + 1;
+            // a word listed twice is tried when the attack first reaches it.
+            ranks.entry(word.as_ref().to_lowercase()).or_insert(rank);
+        }
+        RuleAttack {
+            ranks,
+            rules: rules.max(1),
+        }
+    }
+
+    /// Estimates the guesses of the attack for each dictionary match in the sequence of
+    /// an estimate.
+    ///
+    /// A token typed exactly like its word is reached by the first rule, which by
+    /// convention leaves words unchanged. Otherwise the rule that produces it is unknown,
+    /// and the attack is assumed to need every rule on the word.
+    pub fn estimate(&self, entropy: &Entropy) -> Vec<ManglingEstimate> {
+        entropy
+            .sequence()
+            .iter()
+            .filter_map(|m| {
+                let pattern = if let MatchPattern::Dictionary(ref pattern) = m.pattern {
+                    pattern
+                } else {
+                    return None;
+                };
+                let unchanged =
+                    !pattern.l33t && !pattern.reversed && m.token == pattern.matched_word;
+                let attack_guesses = self.ranks.get(&pattern.matched_word).map(|&rank| {
+                    let earlier_words = Guesses::new(rank - 1) * self.rules;
+                    let guesses = if unchanged {
+                        earlier_words + 1
+                    } else {
+                        earlier_words + self.rules
+                    };
+                    guesses.get()
+                });
+                Some(ManglingEstimate {
+                    i: m.i,
+                    j: m.j,
+                    token: m.token.clone(),
+                    word: pattern.matched_word.clone(),
+                    heuristic_guesses: m.guesses.unwrap_or_default(),
+                    attack_guesses,
+                })
+            })
+            .collect()
+    }
+}
+
+/// A hashcat attack that cracks a password.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+pub enum HashcatAttack {
+    /// A mask attack (`-a 3`) with a mask such as `?u?l?l?l?l?d?d?d?d`.
+    Mask(String),
+    /// A dictionary attack (`-a 0`) reaching the password through a word of the wordlist and
+    /// a rule, e.g. the word "password" with the rule `c $1 $9 $8 $7` for "Password1987".
+    /// John the Ripper understands the same rules.
+    Rule {
+        /// The word, as it appears in the wordlist.
+        word: String,
+        /// The rule turning the word into the password.
+        rule: String,
+    },
+}
+
+/// Describes a hashcat attack that would crack the password of an estimate, or `None` for
+/// an empty password.
+///
+/// A password with a single dictionary match is cracked from its word with a rule for the
+/// case changes, substitutions and reversal of the word and for the characters around it.
+/// Any other password is described by a mask of its character classes.
+///
+/// ```
+/// use zxcvbn::mangling::{hashcat_attack, HashcatAttack};
+/// use zxcvbn::zxcvbn;
+///
+/// assert_eq!(
+///     hashcat_attack(&zxcvbn("Password1987", &[])),
+///     Some(HashcatAttack::Rule {
+///         word: "password".to_string(),
+///         rule: "c $1 $9 $8 $7".to_string(),
+///     })
+/// );
+/// assert_eq!(
+///     hashcat_attack(&zxcvbn("Xk7#", &[])),
+///     Some(HashcatAttack::Mask("?u?l?d?s".to_string()))
+/// );
+/// ```
+pub fn hashcat_attack(entropy: &Entropy) -> Option<HashcatAttack> {
+    let sequence = entropy.sequence();
+    if sequence.is_empty() {
+        return None;
+    }
+    let mut words = sequence
+        .iter()
+        .enumerate()
+        .filter_map(|(k, m)| match m.pattern {
+            MatchPattern::Dictionary(ref pattern) => Some((k, m, pattern)),
+            _ => None,
+        });
+    if let (Some((k, m, pattern)), None) = (words.next(), words.next()) {
+        let prefix = sequence[..k]
+            .iter()
+            .map(|m| m.token.as_str())
+            .collect::<String>();
+        let suffix = sequence[k + 1..]
+            .iter()
+            .map(|m| m.token.as_str())
+            .collect::<String>();
+        if let Some(rule) = hashcat_rule(m, pattern, &prefix, &suffix) {
+            return Some(HashcatAttack::Rule {
+                word: pattern.matched_word.clone(),
+                rule,
+            });
+        }
+    }
+    let password = sequence
+        .iter()
+        .map(|m| m.token.as_str())
+        .collect::<String>();
+    Some(HashcatAttack::Mask(hashcat_mask(&password)))
+}
+
+/// The hashcat mask matching exactly the password, with a `?b` for each byte of
+/// characters outside of printable ASCII.
+fn hashcat_mask(password: &str) -> String {
+    password
+        .chars()
+        .map(|c| match c {
+            'a'..='z' => "?l".to_string(),
+            'A'..='Z' => "?u".to_string(),
+            '0'..='9' => "?d".to_string(),
+            ' '..='~' => "?s".to_string(),
+            c => "?b".repeat(c.len_utf8()),
+        })
+        .collect()
+}
+
+/// The rule turning the word of a dictionary match into the password, or `None` if hashcat
+/// rules can't address every changed position of the word.
+fn hashcat_rule(
+    m: &Match,
+    pattern: &DictionaryPattern,
+    prefix: &str,
+    suffix: &str,
+) -> Option<String> {
+    /// Positions in rules are written as a single character, 0-9 then A-Z.
+    const MAX_POSITION: usize = 35;
+
+    let token = m.token.chars().collect::<Vec<_>>();
+    let mut word = pattern.matched_word.chars().collect::<Vec<_>>();
+    if word.len() != token.len() || word.len() > MAX_POSITION + 1 {
+        return None;
+    }
+    let mut functions = Vec::new();
+    if pattern.reversed {
+        functions.push("r".to_string());
+        word.reverse();
+    }
+    let uppercase = token.iter().filter(|c| c.is_uppercase()).count();
+    if uppercase > 1 && token.iter().all(|c| !c.is_lowercase()) {
+        functions.push("u".to_string());
+        word = word.iter().flat_map(|c| c.to_uppercase()).collect();
+    } else if uppercase == 1 && token[0].is_uppercase() {
+        functions.push("c".to_string());
+        word = word
+            .iter()
+            .enumerate()
+            .flat_map(|(k, c)| {
+                if k == 0 {
+                    c.to_uppercase().collect()
+                } else {
+                    vec![*c]
+                }
+            })
+            .collect();
+    }
+    for (k, (&from, &to)) in word.iter().zip(&token).enumerate() {
+        if from == to {
+            continue;
+        }
+        let position = std::char::from_digit(k as u32, 36)?.to_ascii_uppercase();
+        if from.to_uppercase().eq(Some(to)) || from.to_lowercase().eq(Some(to)) {
+            functions.push(format!("T{position}"));
+        } else {
+            functions.push(format!("o{position}{to}"));
+        }
+    }
+    functions.extend(prefix.chars().rev().map(|c| format!("^{c}")));
+    functions.extend(suffix.chars().map(|c| format!("${c}")));
+    if functions.is_empty() {
+        // the rule leaving the word unchanged
+        functions.push(":".to_string());
+    }
+    Some(functions.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zxcvbn;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_rule_attack_estimate() {
+        let attack = RuleAttack::new(["Dragon", "monkey", "password", "dragon"], 64);
+        let guesses = |password: &str| {
+            attack
+                .estimate(&zxcvbn(password, &[]))
+                .into_iter()
+                .map(|estimate| (estimate.word, estimate.attack_guesses))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(guesses("password"), [("password".to_string(), Some(129))]);
+        assert_eq!(guesses("dr4gon"), [("dragon".to_string(), Some(64))]);
+        assert_eq!(guesses("Monkey1987"), [("monkey".to_string(), Some(128))]);
+        assert_eq!(guesses("superman"), [("superman".to_string(), None)]);
+        assert!(guesses("7q!zK#").is_empty());
+
+        let estimates = attack.estimate(&zxcvbn("password", &[]));
+        assert_eq!((estimates[0].i, estimates[0].j), (0, 7));
+        assert_eq!(estimates[0].token, "password");
+        assert_eq!(
+            Some(estimates[0].heuristic_guesses),
+            zxcvbn("password", &[]).sequence()[0].guesses
+        );
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_hashcat_attack() {
+        let rule = |password: &str| match hashcat_attack(&zxcvbn(password, &[])) {
+            Some(HashcatAttack::Rule { word, rule }) => (word, rule),
+            attack => panic!("{:?} for {}", attack, password),
+        };
+        assert_eq!(rule("password"), ("password".to_string(), ":".to_string()));
+        assert_eq!(
+            rule("Password1987"),
+            ("password".to_string(), "c $1 $9 $8 $7".to_string())
+        );
+        assert_eq!(rule("DRAGON"), ("dragon".to_string(), "u".to_string()));
+        assert_eq!(
+            rule("Dr4g0n"),
+            ("dragon".to_string(), "c o24 o40".to_string())
+        );
+        assert_eq!(
+            rule("!!drowssap"),
+            ("password".to_string(), "r ^! ^!".to_string())
+        );
+
+        assert_eq!(
+            hashcat_attack(&zxcvbn("Xk7#", &[])),
+            Some(HashcatAttack::Mask("?u?l?d?s".to_string()))
+        );
+        assert_eq!(
+            hashcat_attack(&zxcvbn("passworddragon", &[])),
+            Some(HashcatAttack::Mask("?l".repeat(14)))
+        );
+        assert_eq!(hashcat_mask("é a"), "?b?b?s?l");
+        assert_eq!(hashcat_attack(&zxcvbn("", &[])), None);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_rule_attack_without_rules() {
+        let attack = RuleAttack::new(["dragon", "password"], 0);
+        let estimates = attack.estimate(&zxcvbn("password", &[]));
+        assert_eq!(estimates[0].attack_guesses, Some(2));
+    }
+}
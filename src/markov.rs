@@ -0,0 +1,447 @@
+//! A character-level Markov model of passwords, estimating guesses from how likely each
+//! character is after the ones before it rather than from the patterns a password is made of.
+//!
+//! The patterns zxcvbn matches miss weak strings that follow no pattern of theirs but look
+//! like common passwords, such as pronounceable made-up words. A [`MarkovModel`] trained on
+//! common passwords gives them few guesses, and [`Zxcvbn::with_markov_model`] reports its
+//! estimate alongside the pattern estimate, or takes the lower of the two:
+//!
+//! ```
+//! use zxcvbn::markov::{MarkovMode, MarkovModel};
+//! use zxcvbn::Zxcvbn;
+//!
+//! let estimator = Zxcvbn::new().with_markov_model(MarkovModel::bundled(), MarkovMode::Minimum);
+//! let entropy = estimator.estimate("lovemonkey", &[]);
+//! assert!(entropy.markov_guesses().unwrap() >= entropy.guesses());
+//! assert_eq!(entropy.pattern_guesses(), Zxcvbn::new().estimate("lovemonkey", &[]).guesses());
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+
+use crate::fingerprint::Fingerprint;
+use crate::{feedback, time_estimates, Entropy, Zxcvbn};
+
+/// The order of the bundled model: each character is predicted from the 3 before it.
+const BUNDLED_ORDER: usize = 3;
+/// Pads the context of the first characters of a password.
+const START: char = '\u{2}';
+/// Follows the last character of a password, so that the model accounts for its length.
+const END: char = '\u{3}';
+/// The characters a password is made of as far as unseen characters are concerned: the
+/// printable ASCII characters and the end of the password.
+const ALPHABET_SIZE: f64 = 96.0;
+/// How many passwords are sampled from a model to estimate the guesses of others.
+const SAMPLES: usize = 10_000;
+/// Sampled passwords are cut off at the length passwords are truncated to.
+const MAX_SAMPLE_LENGTH: usize = 100;
+
+static BUNDLED: Lazy<Arc<MarkovModel>> = Lazy::new(|| {
+    let passwords = crate::frequency_lists::common_passwords(u32::MAX, 1);
+    Arc::new(MarkovModel::train(
+        BUNDLED_ORDER,
+        passwords.map(|(word, _)| word),
+    ))
+});
+
+/// How an estimator uses the guesses of its Markov model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MarkovMode {
+    /// Reports them with [`Entropy::markov_guesses`], leaving the estimate as it is.
+    Report,
+    /// Also takes them as the guesses of the password when they are fewer than those of its
+    /// patterns, scoring the password with them.
+    Minimum,
+}
+
+/// Counts of the characters following a context.
+#[derive(Debug, Clone, Default)]
+struct Followers {
+    total: u32,
+    counts: HashMap<char, u32>,
+}
+
+/// A character-level Markov model, smoothed with Witten-Bell interpolation so that
+/// characters never seen after a context still get some probability.
+///
+/// The guesses of a password are the number of passwords the model finds more likely,
+/// estimated from a sample of passwords drawn from the model as described by Dell'Amico and
+/// Filippone in "Monte Carlo Strength Evaluation", so that models with different orders or
+/// training data give comparable guesses.
+#[derive(Clone)]
+pub struct MarkovModel {
+    order: usize,
+    /// The followers of every context of up to `order` characters seen in training, keyed
+    /// by the context.
+    contexts: HashMap<String, Followers>,
+    /// The characters seen in training and the end of a password, which samples are made of.
+    alphabet: Vec<char>,
+    /// The log10 probabilities of the sampled passwords, most likely first, each with the
+    /// estimated number of passwords at least as likely.
+    ranks: Vec<(f64, f64)>,
+    /// A hash of the order and the counts of every context, which the guesses follow from,
+    /// for model versions. Unlike the ranks, counts are the same on every platform.
+    fingerprint: u64,
+}
+
+impl MarkovModel {
+    /// Trains a model predicting each character from the `order` characters before it, on a
+    /// list of passwords or words, such as a list of leaked passwords loaded at runtime.
+    /// Words listed several times count as many times.
+    ///
+    /// Training also samples 10,000 passwords from the model, with a fixed seed so that the
+    /// same words always give the same model.
+    pub fn train<'a, I>(order: usize, words: I) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        // Counts the followers of the contexts of `order` characters, then adds them up into
+        // those of the shorter contexts they end with.
+        let mut contexts = HashMap::<String, Followers>::new();
+        for word in words {
+            let chars = padded(order, word);
+            for i in order..chars.len() {
+                let context = chars[i - order..i].iter().collect::<String>();
+                let followers = contexts.entry(context).or_default();
+                followers.total += 1;
+                *followers.counts.entry(chars[i]).or_default() += 1;
+            }
+        }
+        let mut shorter = HashMap::<String, Followers>::new();
+        for (context, followers) in &contexts {
+            let chars = context.chars().collect::<Vec<_>>();
+            for len in 0..order {
+                let suffix = chars[order - len..].iter().collect::<String>();
+                let counted = shorter.entry(suffix).or_default();
+                counted.total += followers.total;
+                for (&c, &count) in &followers.counts {
+                    *counted.counts.entry(c).or_default() += count;
+                }
+            }
+        }
+        contexts.extend(shorter);
+        let mut alphabet = contexts
+            .get("")
+            .map(|followers| followers.counts.keys().copied().collect::<Vec<_>>())
+            .unwrap_or_default();
+        alphabet.sort_unstable();
+        let mut model = MarkovModel {
+            order,
+            contexts,
+            alphabet,
+            ranks: Vec::new(),
+            fingerprint: 0,
+        };
+        model.ranks = model.sample_ranks();
+        model.fingerprint = model.count_fingerprint();
+        model
+    }
+
+    /// A model of order 3 trained on the common passwords bundled with the crate, built on
+    /// first use or by [`preload`](crate::preload).
+    pub fn bundled() -> Arc<MarkovModel> {
+        Arc::clone(&BUNDLED)
+    }
+
+    /// How many characters each character is predicted from.
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// Hashes the order and the counts of every context, in a fixed order.
+    fn count_fingerprint(&self) -> u64 {
+        let mut fingerprint = Fingerprint::new();
+        fingerprint.write_u64(self.order as u64);
+        let mut contexts = self.contexts.iter().collect::<Vec<_>>();
+        contexts.sort_unstable_by_key(|&(context, _)| context);
+        for (context, followers) in contexts {
+            fingerprint.write_str(context);
+            fingerprint.write_u64(followers.counts.len() as u64);
+            let mut counts = followers.counts.iter().collect::<Vec<_>>();
+            counts.sort_unstable();
+            for (&c, &count) in counts {
+                fingerprint.write_u64(u64::from(u32::from(c)) << 32 | u64::from(count));
+            }
+        }
+        fingerprint.finish()
+    }
+
+    /// The hash of the model written into model versions.
+    pub(crate) fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// The guesses of a password: the estimated number of passwords the model finds at
+    /// least as likely. Passwords less likely than any of the sampled ones are taken to need
+    /// one over their probability.
+    pub fn guesses(&self, password: &str) -> u64 {
+        let log10_probability = self.log10_probability(password);
+        let more_likely = self
+            .ranks
+            .partition_point(|&(sampled, _)| sampled > log10_probability);
+        let guesses = if more_likely == self.ranks.len() {
+            let ranked = self.ranks.last().map_or(0.0, |&(_, rank)| rank);
+            ranked.max(10f64.powf(-log10_probability))
+        } else if more_likely == 0 {
+            1.0
+        } else {
+            self.ranks[more_likely - 1].1
+        };
+        // the conversion saturates at u64::MAX
+        (guesses.round() as u64).max(1)
+    }
+
+    /// The log10 probability of a password, including the end of it.
+    fn log10_probability(&self, password: &str) -> f64 {
+        let chars = padded(self.order, password);
+        (self.order..chars.len())
+            .map(|i| {
+                let levels = self.levels(&chars[i - self.order..i]);
+                probability(&levels, chars[i]).log10()
+            })
+            .sum()
+    }
+
+    /// The followers of the ends of `context` seen in training, from the empty context to
+    /// the longest one.
+    fn levels(&self, context: &[char]) -> Vec<&Followers> {
+        (0..=context.len())
+            .map_while(|len| {
+                let key = context[context.len() - len..].iter().collect::<String>();
+                self.contexts.get(&key)
+            })
+            .collect()
+    }
+
+    /// Samples passwords from the model, returning their log10 probabilities, most likely
+    /// first, each with the number of passwords estimated to be at least as likely.
+    fn sample_ranks(&self) -> Vec<(f64, f64)> {
+        if self.alphabet.is_empty() {
+            return Vec::new();
+        }
+        let mut random = SplitMix64(0x5eed);
+        // samples go through the same few contexts over and over
+        let mut distributions = HashMap::new();
+        let mut samples = (0..SAMPLES)
+            .map(|_| self.sample(&mut random, &mut distributions))
+            .collect::<Vec<_>>();
+        samples.sort_unstable_by(|a, b| b.total_cmp(a));
+        let mut rank = 0.0;
+        samples
+            .into_iter()
+            .map(|log10_probability| {
+                rank += 10f64.powf(-log10_probability) / SAMPLES as f64;
+                (log10_probability, rank)
+            })
+            .collect()
+    }
+
+    /// Draws a password from the model, returning its log10 probability. The probabilities
+    /// of the alphabet after each context are kept in `distributions`.
+    fn sample(
+        &self,
+        random: &mut SplitMix64,
+        distributions: &mut HashMap<Vec<char>, Vec<f64>>,
+    ) -> f64 {
+        let mut chars = vec![START; self.order];
+        let mut log10_probability = 0.0;
+        loop {
+            let context = &chars[chars.len() - self.order..];
+            let probabilities = distributions.entry(context.to_vec()).or_insert_with(|| {
+                let levels = self.levels(context);
+                self.alphabet
+                    .iter()
+                    .map(|&c| probability(&levels, c))
+                    .collect()
+            });
+            let mut target = random.next_f64() * probabilities.iter().sum::<f64>();
+            let index = probabilities
+                .iter()
+                .position(|&p| {
+                    target -= p;
+                    target < 0.0
+                })
+                .unwrap_or(probabilities.len() - 1);
+            let c = self.alphabet[index];
+            log10_probability += probabilities[index].log10();
+            if c == END || chars.len() - self.order == MAX_SAMPLE_LENGTH {
+                return log10_probability;
+            }
+            chars.push(c);
+        }
+    }
+}
+
+/// The characters of `word` after `order` start characters, followed by the end character.
+fn padded(order: usize, word: &str) -> Vec<char> {
+    std::iter::repeat(START)
+        .take(order)
+        .chain(word.chars())
+        .chain(std::iter::once(END))
+        .collect()
+}
+
+/// The probability of `c` after a context with the followers `levels`, interpolating from
+/// the empty context up to the whole of it.
+fn probability(levels: &[&Followers], c: char) -> f64 {
+    let mut probability = 1.0 / ALPHABET_SIZE;
+    for followers in levels {
+        let count = f64::from(followers.counts.get(&c).copied().unwrap_or_default());
+        let distinct = followers.counts.len() as f64;
+        let total = f64::from(followers.total);
+        probability = (count + distinct * probability) / (total + distinct);
+    }
+    probability
+}
+
+/// A small deterministic random number generator, enough to sample passwords.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    /// A number in [0, 1).
+    fn next_f64(&mut self) -> f64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+impl fmt::Debug for MarkovModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MarkovModel")
+            .field("order", &self.order)
+            .field("contexts", &self.contexts.len())
+            .finish()
+    }
+}
+
+impl Zxcvbn {
+    /// Estimates passwords with a Markov model too, reported by
+    /// [`Entropy::markov_guesses`] and, with [`MarkovMode::Minimum`], taken as the guesses
+    /// of passwords it finds easier to guess than their patterns do.
+    pub fn with_markov_model(mut self, model: Arc<MarkovModel>, mode: MarkovMode) -> Self {
+        self.markov = Some((model, mode));
+        self
+    }
+}
+
+/// Adds the guesses of `model` to an estimate of a non-empty password, and with
+/// [`MarkovMode::Minimum`], scores the password with them if they are fewer.
+pub(crate) fn apply(
+    entropy: &mut Entropy,
+    password: &str,
+    model: &MarkovModel,
+    mode: MarkovMode,
+    years: &crate::matching::YearWindow,
+    scoring: &crate::ScoringParams,
+) {
+    let password = password.chars().take(100).collect::<String>();
+    if password.is_empty() {
+        return;
+    }
+    let guesses = model.guesses(&password);
+    entropy.markov_guesses = Some(guesses);
+    if mode == MarkovMode::Report {
+        return;
+    }
+    if guesses >= entropy.guesses {
+        return;
+    }
+    let (crack_times, score) = time_estimates::estimate_attack_times(guesses);
+    entropy.guesses = guesses;
+    entropy.guesses_log10 = (guesses as f64).log10();
+    entropy.crack_times = crack_times;
+    entropy.score = score;
+    if entropy.feedback.is_none() {
+        entropy.feedback = feedback::get_feedback(
+            &password,
+            score,
+            &entropy.sequence,
+            entropy.recipe,
+            years,
+            scoring,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Score;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_train() {
+        let model = MarkovModel::train(1, ["abab", "abba", "abab"]);
+        assert_eq!(model.order(), 1);
+        // 'b' always follows 'a'
+        assert!(model.guesses("abab") < model.guesses("aabb"));
+        assert!(model.guesses("abab") < model.guesses("abba"));
+        // unseen characters still get some probability
+        assert!(model.guesses("xyz") < u64::MAX);
+        assert!(model.guesses("abab") >= 1);
+        assert!(model.guesses("ab") < model.guesses("abababababab"));
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_bundled() {
+        let model = MarkovModel::bundled();
+        assert_eq!(model.order(), BUNDLED_ORDER);
+        assert!(model.guesses("password") < model.guesses("pqzzxwvb"));
+        assert!(model.guesses("Xk7#qzLm2&Wp") > 10u64.pow(12));
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_with_markov_model() {
+        // a made-up word no dictionary has, common in the passwords the model is trained on
+        let password = "skibbidoo";
+        let model = Arc::new(MarkovModel::train(
+            3,
+            std::iter::repeat(password)
+                .take(100)
+                .chain(["password", "dragon", "monkey"]),
+        ));
+        let plain = Zxcvbn::new().estimate(password, &[]);
+        assert_eq!(plain.markov_guesses(), None);
+        assert_eq!(plain.pattern_guesses(), plain.guesses());
+
+        let reported = Zxcvbn::new()
+            .with_markov_model(Arc::clone(&model), MarkovMode::Report)
+            .estimate(password, &[]);
+        let markov_guesses = reported.markov_guesses().unwrap();
+        assert!(markov_guesses < 10);
+        assert_eq!(reported.guesses(), plain.guesses());
+        assert_eq!(reported.model_version(), plain.model_version());
+
+        let minimum = Zxcvbn::new()
+            .with_markov_model(model, MarkovMode::Minimum)
+            .estimate(password, &[]);
+        assert_eq!(minimum.guesses(), markov_guesses);
+        assert_eq!(minimum.pattern_guesses(), plain.guesses());
+        assert_eq!(minimum.score(), Score::TooGuessable);
+        assert!(minimum.feedback().is_some());
+        assert!(minimum.model_version().contains("+markov+settings="));
+
+        // models trained on other words are other models
+        let other = Zxcvbn::new().with_markov_model(
+            Arc::new(MarkovModel::train(3, ["password", "dragon", "monkey"])),
+            MarkovMode::Minimum,
+        );
+        assert_ne!(other.model_version(), minimum.model_version());
+        assert_eq!(
+            other.estimate("", &[]).model_version(),
+            other.model_version()
+        );
+    }
+}
@@ -0,0 +1,82 @@
+//! Matching the first letters of the words of a famous quote or song lyric, such as
+//! "tbontbtitq" for "to be or not to be that is the question", a way of making up passwords
+//! that is often recommended and just as often taken from the same few phrases.
+
+use crate::frequency_lists;
+
+use super::patterns::{AcronymPattern, MatchPattern};
+use super::{CustomWords, Match, Matcher};
+
+/// Matches the acronyms of the bundled phrases.
+pub(super) struct AcronymMatch {}
+
+impl Matcher for AcronymMatch {
+    fn get_matches(&self, password: &str, _custom_words: &CustomWords) -> Vec<Match> {
+        let chars = password.chars().collect::<Vec<_>>();
+        let mut matches = Vec::new();
+        for i in 0..chars.len() {
+            let mut acronym = String::new();
+            for (j, c) in chars.iter().enumerate().skip(i) {
+                acronym.extend(c.to_lowercase());
+                let (phrase, extendable) = frequency_lists::search_acronyms(&acronym);
+                if let Some((phrase, rank)) = phrase {
+                    matches.push(Match {
+                        pattern: MatchPattern::Acronym(AcronymPattern {
+                            phrase,
+                            rank: rank as usize,
+                            ..AcronymPattern::default()
+                        }),
+                        i,
+                        j,
+                        token: chars[i..=j].iter().collect(),
+                        ..Match::default()
+                    });
+                }
+                if !extendable {
+                    break;
+                }
+            }
+        }
+        matches
+    }
+
+    fn in_js_implementation(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matched(password: &str) -> Vec<(usize, usize, &'static str)> {
+        (AcronymMatch {})
+            .get_matches(password, &CustomWords::default())
+            .into_iter()
+            .map(|m| match m.pattern {
+                MatchPattern::Acronym(p) => (m.i, m.j, p.phrase),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_acronym_matches() {
+        assert_eq!(
+            matched("tbontbtitq"),
+            [(0, 9, "to be or not to be that is the question")]
+        );
+        assert_eq!(matched("1MTFBWY!"), [(1, 6, "may the force be with you")]);
+        assert!(matched("tbontbtit").is_empty());
+        assert!(matched("mtfbw").is_empty());
+    }
+
+    #[cfg(feature = "all-matchers")]
+    #[test]
+    fn test_acronym_is_guessed_before_bruteforce() {
+        let entropy = crate::zxcvbn("tbontbtitq", &[]);
+        assert_eq!(entropy.sequence().len(), 1);
+        assert_eq!(entropy.sequence()[0].pattern.variant(), "acronym");
+        assert!(entropy.guesses() < 10_000);
+    }
+}
@@ -0,0 +1,695 @@
+//! Matching years, decades, date ranges, alt codes and dates, the patterns found with regular
+//! expressions.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::char_indexing::{CharIndexable, CharIndexableStr};
+use super::patterns::*;
+use super::{CustomWords, Match, Matcher, YearWindow};
+
+pub(super) struct RegexMatch {}
+
+impl Matcher for RegexMatch {
+    fn get_matches(&self, password: &str, custom_words: &CustomWords) -> Vec<Match> {
+        let mut matches = Vec::new();
+        let recent_year = recent_year_regex(&custom_words.years);
+        for (&name, regex) in REGEXES.iter() {
+            // the JavaScript implementation only matches years
+            if custom_words.js_compatible && name != "recent_year" {
+                continue;
+            }
+            let regex = match name {
+                "recent_year" => &*recent_year,
+                _ => regex,
+            };
+            for capture in regex.captures_iter(password) {
+                let m = capture.get(0).unwrap();
+                let pattern = MatchPattern::Regex(RegexPattern {
+                    regex_name: name,
+                    regex_match: capture
+                        .iter()
+                        .map(|x| x.map_or_else(String::new, |x| x.as_str().to_string()))
+                        .collect(),
+                });
+                let (i, j) = (
+                    password[..m.start()].chars().count(),
+                    password[..m.end()].chars().count() - 1,
+                );
+                matches.push(Match {
+                    pattern,
+                    token: m.as_str().to_string(),
+                    i,
+                    j,
+                    ..Match::default()
+                });
+            }
+        }
+        matches
+    }
+}
+
+/// Matches the years of the century of the reference year, of the century before and of the
+/// century of the current year.
+fn recent_year_regex(years: &YearWindow) -> Cow<'static, Regex> {
+    let century = years.reference_year.div_euclid(100);
+    let current_century = years.current_year.div_euclid(100);
+    let mut centuries = vec![century - 1, century];
+    if !centuries.contains(&current_century) {
+        centuries.push(current_century);
+    }
+    if centuries == [19, 20] {
+        return Cow::Borrowed(&REGEXES["recent_year"]);
+    }
+    let pattern = centuries
+        .iter()
+        .map(|century| format!(r"{}[0-9]{{2}}", century))
+        .join("|");
+    Cow::Owned(Regex::new(&pattern).unwrap())
+}
+
+static REGEXES: Lazy<BTreeMap<&'static str, Regex>> = Lazy::new(|| {
+    let mut table = BTreeMap::new();
+    table.insert("recent_year", Regex::new(r"19[0-9]{2}|20[0-9]{2}").unwrap());
+    // decades, e.g. "1990s", "80s" or "80's"
+    table.insert("decade", Regex::new(r"(?i)((?:19|20)?[0-9]0)'?s").unwrap());
+    // year ranges, e.g. "1990-1995", "1990-95" or "2001to2005"
+    table.insert(
+        "year_range",
+        Regex::new(r"(?i)((?:19|20)[0-9]{2}) ?(?:-|/|_|\.|~|to) ?((?:19|20)[0-9]{2}|[0-9]{2})")
+            .unwrap(),
+    );
+    // alt codes typed out literally, e.g. "alt+0169" for the copyright sign
+    table.insert(
+        "alt_code",
+        Regex::new(r"(?i)alt[ +-]?([0-9]{1,4})").unwrap(),
+    );
+    // code points written as escapes, e.g. "\u00e9", "\u{1F600}", "U+00A9" or "&#169;".
+    // the last capture holds a decimal code point, the others hex ones.
+    table.insert(
+        "unicode_escape",
+        Regex::new(
+            r"(?i)(?:\\u\{([0-9a-f]{1,6})\}|\\u([0-9a-f]{4})|\\x([0-9a-f]{2})|u\+([0-9a-f]{4,6})|&#x([0-9a-f]{1,6});|&#([0-9]{1,7});)",
+        )
+        .unwrap(),
+    );
+    table
+});
+
+/// a "date" is recognized as:
+///   any 3-tuple that starts or ends with a 2- or 4-digit year,
+///   with 2 or 0 separator chars (1.1.91 or 1191),
+///   maybe zero-padded (01-01-91 vs 1-1-91),
+///   a month between 1 and 12,
+///   a day between 1 and 31.
+///
+/// note: this isn't true date parsing in that "feb 31st" is allowed,
+/// this doesn't check for leap years, etc.
+///
+/// recipe:
+/// start with regex to find maybe-dates, then attempt to map the integers
+/// onto month-day-year to filter the maybe-dates into dates.
+/// finally, remove matches that are substrings of other matches to reduce noise.
+///
+/// note: instead of using a lazy or greedy regex to find many dates over the full string,
+/// this uses a ^...$ regex against every substring of the password -- less performant but leads
+/// to every possible date match.
+pub(super) struct DateMatch {}
+
+impl Matcher for DateMatch {
+    fn get_matches(&self, password: &str, custom_words: &CustomWords) -> Vec<Match> {
+        let years = &custom_words.years;
+        let user_dates = custom_words
+            .user_inputs
+            .keys()
+            .filter_map(|user_input| user_input_date(user_input, years))
+            .collect::<Vec<_>>();
+        let is_user_date = |date: (i32, i8, i8)| {
+            user_dates
+                .iter()
+                .any(|&user_date| is_same_date(date, user_date))
+        };
+        let mut matches = Vec::new();
+        let char_indexable = CharIndexableStr::from(password);
+
+        let password_len = password.chars().count();
+        // dates without separators are between length 4 '1191' and 8 '11111991'
+        if password_len < 4 {
+            return matches;
+        }
+        for i in 0..(password_len - 3) {
+            for j in (i + 3)..(i + 8) {
+                if j >= password_len {
+                    break;
+                }
+                let token_str = char_indexable.char_index(i..j + 1);
+                let best_candidate = match date_without_separator(token_str, years) {
+                    Some(date) => date,
+                    None => continue,
+                };
+                let pattern = MatchPattern::Date(DatePattern {
+                    separator: String::new(),
+                    year: best_candidate.0,
+                    month: best_candidate.1,
+                    day: best_candidate.2,
+                    user_input: is_user_date(best_candidate),
+                });
+                matches.push(Match {
+                    pattern,
+                    token: token_str.to_string(),
+                    i,
+                    j,
+                    ..Match::default()
+                });
+            }
+        }
+
+        // dates with separators are between length 6 '1/1/91' and 10 '11/11/1991'
+        if password_len >= 6 {
+            for i in 0..(password_len - 5) {
+                for j in (i + 5)..(i + 10) {
+                    if j >= password_len {
+                        break;
+                    }
+                    let token = char_indexable.char_index(i..j + 1);
+                    let (ymd, separator) = match date_with_separator(token, years) {
+                        Some(date) => date,
+                        None => continue,
+                    };
+                    let pattern = MatchPattern::Date(DatePattern {
+                        separator,
+                        year: ymd.0,
+                        month: ymd.1,
+                        day: ymd.2,
+                        user_input: is_user_date(ymd),
+                    });
+                    matches.push(Match {
+                        pattern,
+                        token: token.to_string(),
+                        i,
+                        j,
+                        ..Match::default()
+                    });
+                }
+            }
+        }
+
+        matches
+            .iter()
+            .filter(|&x| !matches.iter().any(|y| *x != *y && y.i <= x.i && y.j >= x.j))
+            .cloned()
+            .collect()
+    }
+}
+
+/// The (y, m, d) date a string of digits like "11151991" stands for, if any.
+fn date_without_separator(token: &str, years: &YearWindow) -> Option<(i32, i8, i8)> {
+    if !MAYBE_DATE_NO_SEPARATOR_REGEX.is_match(token) {
+        return None;
+    }
+    let token = CharIndexableStr::from(token);
+    let n = token.char_count();
+    let candidates = date_splits(n).iter().filter_map(|&(k, l)| {
+        map_ints_to_ymd(
+            years,
+            token.char_index(0..k).parse().unwrap(),
+            token.char_index(k..l).parse().unwrap(),
+            token.char_index(l..n).parse().unwrap(),
+        )
+    });
+    // at this point: different possible ymd mappings for the same token.
+    // match the candidate date that likely takes the fewest guesses: a year closest
+    // to the reference year.
+    //
+    // ie, considering '111504', prefer 11-15-04 to 1-1-1504
+    // (interpreting '04' as 2004)
+    candidates.min_by_key(|candidate| (candidate.0 - years.reference_year).abs())
+}
+
+/// The (y, m, d) date and separator of a date like "11/15/1991", if the token is one.
+fn date_with_separator(token: &str, years: &YearWindow) -> Option<((i32, i8, i8), String)> {
+    let captures = MAYBE_DATE_WITH_SEPARATOR_REGEX.captures(token)?;
+    if captures[2] != captures[4] {
+        // Original code uses regex backreferences, Rust doesn't support these.
+        // Need to manually test that group 2 and 4 are the same
+        return None;
+    }
+    let ymd = map_ints_to_ymd(
+        years,
+        captures[1].parse().unwrap(),
+        captures[3].parse().unwrap(),
+        captures[5].parse().unwrap(),
+    )?;
+    Some((ymd, captures[2].to_string()))
+}
+
+/// The date a user input stands for, such as a birth date, if it is a whole date.
+/// Shorter inputs like "1990" are more likely years or numbers.
+fn user_input_date(user_input: &str, years: &YearWindow) -> Option<(i32, i8, i8)> {
+    if user_input.chars().count() < MIN_USER_INPUT_DATE_LENGTH {
+        return None;
+    }
+    date_with_separator(user_input, years)
+        .map(|(ymd, _)| ymd)
+        .or_else(|| date_without_separator(user_input, years))
+}
+
+/// Whether two dates are the same, either way the day and month may have been read, as
+/// "05/12/1990" is the 5th of December in some places and the 12th of May in others.
+fn is_same_date(a: (i32, i8, i8), b: (i32, i8, i8)) -> bool {
+    a.0 == b.0 && ((a.1, a.2) == (b.1, b.2) || (a.1, a.2) == (b.2, b.1))
+}
+
+/// Takes three ints and returns them in a (y, m, d) tuple
+fn map_ints_to_ymd(
+    years: &YearWindow,
+    first: u16,
+    second: u16,
+    third: u16,
+) -> Option<(i32, i8, i8)> {
+    // given a 3-tuple, discard if:
+    //   middle int is over 31 (for all ymd formats, years are never allowed in the middle)
+    //   middle int is zero
+    //   any int is over the max allowable year
+    //   any int is over two digits but under the min allowable year
+    //   2 ints are over 31, the max allowable day
+    //   2 ints are zero
+    //   all ints are over 12, the max allowable month
+    if second > 31 || second == 0 {
+        return None;
+    }
+    let mut over_12 = 0;
+    let mut over_31 = 0;
+    let mut zero = 0;
+    let max_year = years.max_year();
+    for &i in &[first, second, third] {
+        let i = i32::from(i);
+        if 99 < i && i < DATE_MIN_YEAR || i > max_year {
+            return None;
+        }
+        if i > 31 {
+            over_31 += 1;
+        }
+        if i > 12 {
+            over_12 += 1;
+        }
+        if i == 0 {
+            zero += 1;
+        }
+    }
+    if over_31 >= 2 || over_12 == 3 || zero >= 2 {
+        return None;
+    }
+
+    // first look for a four digit year: yyyy + daymonth or daymonth + yyyy
+    let possible_year_splits = &[(third, first, second), (first, second, third)];
+    for &(year, second, third) in possible_year_splits {
+        if (DATE_MIN_YEAR..=max_year).contains(&i32::from(year)) {
+            let dm = map_ints_to_md(second, third);
+            if let Some(dm) = dm {
+                return Some((i32::from(year), dm.0, dm.1));
+            } else {
+                // for a candidate that includes a four-digit year,
+                // when the remaining ints don't match to a day and month,
+                // it is not a date.
+                return None;
+            }
+        }
+    }
+
+    // given no four-digit year, two digit years are the most flexible int to match, so
+    // try to parse a day-month out of (first, second) or (second, first)
+    for &(year, second, third) in possible_year_splits {
+        let dm = map_ints_to_md(second, third);
+        if let Some(dm) = dm {
+            let year = if year > 99 {
+                i32::from(year)
+            } else {
+                years.four_digit_year(year)
+            };
+            return Some((year, dm.0, dm.1));
+        }
+    }
+
+    None
+}
+
+/// Takes two ints and returns them in a (m, d) tuple
+fn map_ints_to_md(first: u16, second: u16) -> Option<(i8, i8)> {
+    for &(d, m) in &[(first, second), (second, first)] {
+        if (1..=31).contains(&d) && (1..=12).contains(&m) {
+            return Some((m as i8, d as i8));
+        }
+    }
+    None
+}
+
+const DATE_MIN_YEAR: i32 = 1000;
+/// Length of the shortest user input that is matched as a date, like "1/1/90" or "010190".
+const MIN_USER_INPUT_DATE_LENGTH: usize = 6;
+/// The ways to split a date of `n` digits without separators, as the indices the second
+/// and third parts start at.
+fn date_splits(n: usize) -> &'static [(usize, usize)] {
+    match n {
+        // for length-4 strings, eg 1191 or 9111, two ways to split:
+        // 1 1 91 (2nd split starts at index 1, 3rd at index 2)
+        // 91 1 1
+        4 => &[(1, 2), (2, 3)],
+        // 1 11 91
+        // 11 1 91
+        5 => &[(1, 3), (2, 3)],
+        // 1 1 1991
+        // 11 11 91
+        // 1991 1 1
+        6 => &[(1, 2), (2, 4), (4, 5)],
+        // 1 11 1991
+        // 11 1 1991
+        // 1991 1 11
+        // 1991 11 1
+        7 => &[(1, 3), (2, 3), (4, 5), (4, 6)],
+        // 11 11 1991
+        // 1991 11 11
+        8 => &[(2, 4), (4, 6)],
+        _ => &[],
+    }
+}
+static MAYBE_DATE_NO_SEPARATOR_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[0-9]{4,8}$").unwrap());
+static MAYBE_DATE_WITH_SEPARATOR_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^([0-9]{1,4})([\s/\\_.-])([0-9]{1,2})([\s/\\_.-])([0-9]{1,4})$").unwrap()
+});
+
+/// Compiles the regular expressions of the regex and date matchers.
+pub(super) fn initialize() {
+    Lazy::force(&REGEXES);
+    Lazy::force(&MAYBE_DATE_NO_SEPARATOR_REGEX);
+    Lazy::force(&MAYBE_DATE_WITH_SEPARATOR_REGEX);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{matching, scoring};
+
+    #[test]
+    fn test_regex_matching() {
+        let test_data = [
+            ("1922", "recent_year"),
+            ("2017", "recent_year"),
+            ("1990s", "decade"),
+            ("80's", "decade"),
+            ("1990-1995", "year_range"),
+            ("2001to05", "year_range"),
+            ("alt+0169", "alt_code"),
+            ("ALT233", "alt_code"),
+            ("\\u00e9", "unicode_escape"),
+            ("\\u{1F600}", "unicode_escape"),
+            ("U+00A9", "unicode_escape"),
+            ("&#169;", "unicode_escape"),
+            ("&#xa9;", "unicode_escape"),
+        ];
+        for &(pattern, name) in &test_data {
+            let matches = (RegexMatch {}).get_matches(pattern, &CustomWords::default());
+            let m = matches.iter().find(|m| m.token == *pattern).unwrap();
+            assert_eq!(m.i, 0);
+            assert_eq!(m.j, pattern.len() - 1);
+            let p = if let MatchPattern::Regex(ref p) = m.pattern {
+                p
+            } else {
+                panic!("Wrong match pattern")
+            };
+            assert_eq!(p.regex_name, name);
+        }
+    }
+
+    #[test]
+    fn test_date_matching_with_various_separators() {
+        let separators = ["", " ", "-", "/", "\\", "_", "."];
+        for sep in &separators {
+            let password = format!("13{}2{}1921", sep, sep);
+            let matches = (DateMatch {}).get_matches(&password, &CustomWords::default());
+            let m = matches.iter().find(|m| m.token == password).unwrap();
+            assert_eq!(m.i, 0);
+            assert_eq!(m.j, password.len() - 1);
+            let p = if let MatchPattern::Date(ref p) = m.pattern {
+                p
+            } else {
+                panic!("Wrong match pattern")
+            };
+            assert_eq!(p.year, 1921);
+            assert_eq!(p.month, 2);
+            assert_eq!(p.day, 13);
+            assert_eq!(p.separator, sep.to_string());
+        }
+    }
+
+    #[test]
+    fn test_date_matches_year_closest_to_reference_year() {
+        let password = format!("1115{}", *scoring::REFERENCE_YEAR % 100);
+        let matches = (DateMatch {}).get_matches(&password, &CustomWords::default());
+        let m = matches.iter().find(|m| m.token == password).unwrap();
+        assert_eq!(m.i, 0);
+        assert_eq!(m.j, password.len() - 1);
+        let p = if let MatchPattern::Date(ref p) = m.pattern {
+            p
+        } else {
+            panic!("Wrong match pattern")
+        };
+        assert_eq!(p.year, *scoring::REFERENCE_YEAR);
+        assert_eq!(p.month, 11);
+        assert_eq!(p.day, 15);
+        assert_eq!(p.separator, "".to_string());
+    }
+
+    #[test]
+    fn test_date_matches_follow_year_window() {
+        let custom_words = CustomWords {
+            years: YearWindow::new(2100),
+            ..CustomWords::default()
+        };
+        let date = |password: &str, custom_words: &CustomWords| {
+            let matches = (DateMatch {}).get_matches(password, custom_words);
+            matches
+                .into_iter()
+                .find(|m| m.token == password)
+                .and_then(|m| match m.pattern {
+                    MatchPattern::Date(p) => Some((p.year, p.month, p.day)),
+                    _ => None,
+                })
+        };
+        // two digit years are the latest ones dates are matched with
+        assert_eq!(date("11/15/87", &custom_words), Some((2087, 11, 15)));
+        assert_eq!(date("11/15/30", &custom_words), Some((2030, 11, 15)));
+        assert_eq!(
+            date("11/15/87", &CustomWords::default()),
+            Some((1987, 11, 15))
+        );
+        // and dates are matched up to 25 years after the reference year
+        assert_eq!(date("1/1/2125", &custom_words), Some((2125, 1, 1)));
+        assert_eq!(date("1/1/2126", &custom_words), None);
+    }
+
+    #[test]
+    fn test_recent_years_follow_year_window() {
+        let custom_words = CustomWords {
+            years: YearWindow::new(2150),
+            ..CustomWords::default()
+        };
+        let years = |password: &str, custom_words: &CustomWords| {
+            (RegexMatch {})
+                .get_matches(password, custom_words)
+                .into_iter()
+                .filter(|m| match m.pattern {
+                    MatchPattern::Regex(ref p) => p.regex_name == "recent_year",
+                    _ => false,
+                })
+                .map(|m| m.token)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(years("1999 2099 2149", &custom_words), ["2099", "2149"]);
+        assert_eq!(
+            years("1999 2099 2149", &CustomWords::default()),
+            ["1999", "2099"]
+        );
+
+        // years closer to the reference year take fewer guesses
+        let guesses = |custom_words: &CustomWords| {
+            matching::omnimatch("2099", custom_words)
+                .into_iter()
+                .find(|m| match m.pattern {
+                    MatchPattern::Regex(ref p) => p.regex_name == "recent_year",
+                    _ => false,
+                })
+                .and_then(|m| m.guesses)
+        };
+        let closer = CustomWords {
+            years: YearWindow::new(2100),
+            ..CustomWords::default()
+        };
+        assert_eq!(guesses(&custom_words), Some(51));
+        assert_eq!(guesses(&closer), Some(20));
+
+        // the years of the current century are matched whatever the reference year
+        let past = CustomWords {
+            years: YearWindow {
+                current_year: 2025,
+                ..YearWindow::new(1850)
+            },
+            ..CustomWords::default()
+        };
+        assert_eq!(years("1799 1999 2025", &past), ["1799", "2025"]);
+    }
+
+    #[test]
+    fn test_date_matches_user_input_dates() {
+        let custom_words = CustomWords {
+            user_inputs: [
+                ("jsmith", 1),
+                ("1990-05-12", 2),
+                ("19900512", 3),
+                ("1988", 4),
+            ]
+            .into_iter()
+            .map(|(input, rank)| (input.to_string(), rank))
+            .collect(),
+            ..CustomWords::default()
+        };
+        let user_input = |password: &str| {
+            let matches = (DateMatch {}).get_matches(password, &custom_words);
+            match matches
+                .iter()
+                .find(|m| m.token == password)
+                .unwrap()
+                .pattern
+            {
+                MatchPattern::Date(ref p) => p.user_input,
+                _ => panic!("Wrong match pattern"),
+            }
+        };
+        assert!(user_input("12051990"));
+        assert!(user_input("5/12/90"));
+        assert!(!user_input("13051990"));
+        assert!(!user_input("01011988"));
+    }
+
+    #[test]
+    fn test_date_matches() {
+        let test_data = [(1, 1, 1999), (11, 8, 2000), (9, 12, 2005), (22, 11, 1551)];
+        for &(day, month, year) in &test_data {
+            let password = format!("{}{}{}", year, month, day);
+            let matches = (DateMatch {}).get_matches(&password, &CustomWords::default());
+            let m = matches.iter().find(|m| m.token == password).unwrap();
+            assert_eq!(m.i, 0);
+            assert_eq!(m.j, password.len() - 1);
+            let p = if let MatchPattern::Date(ref p) = m.pattern {
+                p
+            } else {
+                panic!("Wrong match pattern")
+            };
+            assert_eq!(p.year, year);
+            assert_eq!(p.separator, "".to_string());
+        }
+        for &(day, month, year) in &test_data {
+            let password = format!("{}.{}.{}", year, month, day);
+            let matches = (DateMatch {}).get_matches(&password, &CustomWords::default());
+            let m = matches.iter().find(|m| m.token == password).unwrap();
+            assert_eq!(m.i, 0);
+            assert_eq!(m.j, password.len() - 1);
+            let p = if let MatchPattern::Date(ref p) = m.pattern {
+                p
+            } else {
+                panic!("Wrong match pattern")
+            };
+            assert_eq!(p.year, year);
+            assert_eq!(p.separator, ".".to_string());
+        }
+    }
+
+    #[test]
+    fn test_matching_zero_padded_dates() {
+        let password = "02/02/02";
+        let matches = (DateMatch {}).get_matches(password, &CustomWords::default());
+        let m = matches.iter().find(|m| m.token == password).unwrap();
+        assert_eq!(m.i, 0);
+        assert_eq!(m.j, password.len() - 1);
+        let p = if let MatchPattern::Date(ref p) = m.pattern {
+            p
+        } else {
+            panic!("Wrong match pattern")
+        };
+        assert_eq!(p.year, 2002);
+        assert_eq!(p.month, 2);
+        assert_eq!(p.day, 2);
+        assert_eq!(p.separator, "/".to_string());
+    }
+
+    #[test]
+    fn test_matching_embedded_dates() {
+        let password = "a1/1/91!";
+        let matches = (DateMatch {}).get_matches(password, &CustomWords::default());
+        let m = matches.iter().find(|m| &m.token == "1/1/91").unwrap();
+        assert_eq!(m.i, 1);
+        assert_eq!(m.j, password.len() - 2);
+        let p = if let MatchPattern::Date(ref p) = m.pattern {
+            p
+        } else {
+            panic!("Wrong match pattern")
+        };
+        assert_eq!(p.year, 1991);
+        assert_eq!(p.month, 1);
+        assert_eq!(p.day, 1);
+        assert_eq!(p.separator, "/".to_string());
+    }
+
+    #[test]
+    fn test_matching_overlapping_dates() {
+        let password = "12/20/1991.12.20";
+        let matches = (DateMatch {}).get_matches(password, &CustomWords::default());
+        let m = matches.iter().find(|m| &m.token == "12/20/1991").unwrap();
+        assert_eq!(m.i, 0);
+        assert_eq!(m.j, 9);
+        let p = if let MatchPattern::Date(ref p) = m.pattern {
+            p
+        } else {
+            panic!("Wrong match pattern")
+        };
+        assert_eq!(p.year, 1991);
+        assert_eq!(p.month, 12);
+        assert_eq!(p.day, 20);
+        assert_eq!(p.separator, "/".to_string());
+        let m = matches.iter().find(|m| &m.token == "1991.12.20").unwrap();
+        assert_eq!(m.i, 6);
+        assert_eq!(m.j, password.len() - 1);
+        let p = if let MatchPattern::Date(ref p) = m.pattern {
+            p
+        } else {
+            panic!("Wrong match pattern")
+        };
+        assert_eq!(p.year, 1991);
+        assert_eq!(p.month, 12);
+        assert_eq!(p.day, 20);
+        assert_eq!(p.separator, ".".to_string());
+    }
+
+    #[test]
+    fn test_matches_dates_padded_by_non_ambiguous_digits() {
+        let password = "912/20/919";
+        let matches = (DateMatch {}).get_matches(password, &CustomWords::default());
+        let m = matches.iter().find(|m| &m.token == "12/20/91").unwrap();
+        assert_eq!(m.i, 1);
+        assert_eq!(m.j, password.len() - 2);
+        let p = if let MatchPattern::Date(ref p) = m.pattern {
+            p
+        } else {
+            panic!("Wrong match pattern")
+        };
+        assert_eq!(p.year, 1991);
+        assert_eq!(p.month, 12);
+        assert_eq!(p.day, 20);
+        assert_eq!(p.separator, "/".to_string());
+    }
+}
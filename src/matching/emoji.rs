@@ -0,0 +1,123 @@
+//! Emoji recognition for the emoji matcher.
+//!
+//! An emoji may take several chars: a base character followed by a variation selector or
+//! skin tone, two regional indicators forming a flag, or emoji joined with zero width
+//! joiners. These are grouped into clusters, which is what a person picks on an emoji
+//! keyboard.
+
+/// Approximate number of emoji a keyboard offers.
+pub(crate) const EMOJI_SPACE: u64 = 3600;
+
+/// The most used emoji, most common first.
+pub(crate) const COMMON_EMOJI: [char; 60] = [
+    '😂', '❤', '🤣', '👍', '😭', '🙏', '😘', '🥰', '😍', '😊', '🎉', '😁', '💕', '🥺', '😅', '🔥',
+    '☺', '🤦', '♥', '🤷', '🙄', '😆', '🤗', '😉', '🎂', '🤔', '👏', '🙂', '😳', '🥳', '😎', '👌',
+    '💜', '😔', '💪', '✨', '💖', '👀', '😋', '😏', '😢', '👉', '💗', '😩', '💯', '🌹', '💞', '🎈',
+    '💙', '😃', '😡', '💐', '😜', '🙈', '🤘', '😄', '🤩', '😀', '💛', '💚',
+];
+
+/// Emoji in the order emoji keyboards show them, starting with the smileys page.
+pub(crate) const KEYBOARD_ORDER: [char; 60] = [
+    '😀', '😃', '😄', '😁', '😆', '😅', '🤣', '😂', '🙂', '🙃', '😉', '😊', '😇', '🥰', '😍', '🤩',
+    '😘', '😗', '☺', '😚', '😙', '😋', '😛', '😜', '🤪', '😝', '🤑', '🤗', '🤭', '🤫', '🤔', '🤐',
+    '🤨', '😐', '😑', '😶', '😏', '😒', '🙄', '😬', '🤥', '😌', '😔', '😪', '🤤', '😴', '😷', '🤒',
+    '🤕', '🤢', '🤮', '🤧', '🥵', '🥶', '🥴', '😵', '🤯', '🤠', '🥳', '😎',
+];
+
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+
+/// Whether the char starts an emoji on its own.
+fn is_emoji_base(c: char) -> bool {
+    matches!(
+        c,
+        '\u{1F300}'..='\u{1F3FA}'
+            | '\u{1F400}'..='\u{1FAFF}'
+            | '\u{2600}'..='\u{27BF}'
+            | '\u{2B05}'..='\u{2B55}'
+            | '\u{1F000}'..='\u{1F0FF}'
+            | '\u{1F1E6}'..='\u{1F1FF}'
+    )
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+}
+
+/// Whether the char modifies the emoji before it, e.g. a skin tone or variation selector.
+fn is_emoji_modifier(c: char) -> bool {
+    matches!(
+        c,
+        '\u{FE0F}' | '\u{20E3}' | '\u{1F3FB}'..='\u{1F3FF}' | '\u{E0020}'..='\u{E007F}'
+    )
+}
+
+/// Emoji clusters in the password, as inclusive ranges of char indexes.
+pub(crate) fn clusters(chars: &[char]) -> Vec<(usize, usize)> {
+    let mut clusters = Vec::new();
+    let mut k = 0;
+    while k < chars.len() {
+        if !is_emoji_base(chars[k]) {
+            k += 1;
+            continue;
+        }
+        let i = k;
+        if is_regional_indicator(chars[k])
+            && chars.get(k + 1).is_some_and(|&c| is_regional_indicator(c))
+        {
+            k += 1;
+        }
+        loop {
+            while chars.get(k + 1).is_some_and(|&c| is_emoji_modifier(c)) {
+                k += 1;
+            }
+            let joins_next = chars.get(k + 1) == Some(&ZERO_WIDTH_JOINER)
+                && chars.get(k + 2).is_some_and(|&c| is_emoji_base(c));
+            if !joins_next {
+                break;
+            }
+            k += 2;
+        }
+        clusters.push((i, k));
+        k += 1;
+    }
+    clusters
+}
+
+/// The single emoji a cluster shows, ignoring variation selectors and skin tones, or
+/// `None` for flags and joined emoji.
+pub(crate) fn base_emoji(cluster: &[char]) -> Option<char> {
+    let mut bases = cluster.iter().filter(|&&c| !is_emoji_modifier(c));
+    match (bases.next(), bases.next()) {
+        (Some(&c), None) => Some(c),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clusters_of(s: &str) -> Vec<String> {
+        let chars = s.chars().collect::<Vec<_>>();
+        clusters(&chars)
+            .into_iter()
+            .map(|(i, j)| chars[i..=j].iter().collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_emoji_clusters() {
+        assert_eq!(clusters_of("a😀b🔥🔥"), ["😀", "🔥", "🔥"]);
+        assert_eq!(clusters_of("❤\u{FE0F}👍🏽"), ["❤\u{FE0F}", "👍🏽"]);
+        assert_eq!(clusters_of("🇳🇱🇧🇪"), ["🇳🇱", "🇧🇪"]);
+        assert_eq!(clusters_of("👩\u{200D}💻x"), ["👩\u{200D}💻"]);
+        assert_eq!(clusters_of("1\u{FE0F}\u{20E3}abc"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_base_emoji() {
+        assert_eq!(base_emoji(&['❤', '\u{FE0F}']), Some('❤'));
+        assert_eq!(base_emoji(&['👍', '\u{1F3FD}']), Some('👍'));
+        assert_eq!(base_emoji(&['🇳', '🇱']), None);
+    }
+}
@@ -0,0 +1,157 @@
+//! Matching common passwords with a keyboard typo, with the `keyboard-typos` feature.
+//!
+//! The typos are generated on first use: the most common passwords with one key replaced by
+//! a key next to it on a QWERTY keyboard, like "passwprd" or "psssword", with the neighbors
+//! of the QWERTY graph scoring counts them by. Matching them is a lookup, unlike the searches
+//! of the `fuzzy-matching` feature.
+
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+use once_cell::sync::Lazy;
+
+use super::patterns::{DictionaryPattern, MatchPattern};
+use super::{CustomWords, Match, Matcher};
+use crate::adjacency_graphs::qwerty_neighbors;
+use crate::frequency_lists::{self, DictionaryType};
+
+/// Passwords ranked this high or better are expanded with keyboard typos.
+const MAX_RANK: u32 = 1000;
+/// Shorter passwords are a typo away from too many strings to expand them.
+const MIN_LEN: usize = 6;
+
+/// The common passwords with one key replaced by a neighboring one, each with the password
+/// and its rank. A typo of several passwords goes with the most common.
+static KEYBOARD_TYPOS: Lazy<BTreeMap<String, (&'static str, u32)>> = Lazy::new(|| {
+    let mut typos: BTreeMap<String, (&'static str, u32)> = BTreeMap::new();
+    for (password, rank) in frequency_lists::common_passwords(MAX_RANK, MIN_LEN) {
+        let chars = password.chars().collect::<Vec<_>>();
+        for (k, &c) in chars.iter().enumerate() {
+            for neighbor in qwerty_neighbors(c) {
+                let mut typo = chars.clone();
+                typo[k] = neighbor;
+                let typo = typo.into_iter().collect::<String>();
+                let best = typos.entry(typo).or_insert((password, rank));
+                if rank < best.1 {
+                    *best = (password, rank);
+                }
+            }
+        }
+    }
+    typos
+});
+
+pub(super) fn initialize() {
+    Lazy::force(&KEYBOARD_TYPOS);
+}
+
+/// Finds a common password with a keyboard typo, returning the password and its rank,
+/// along with whether any longer typo may start with `word`.
+fn search(word: &str) -> (Option<(&'static str, u32)>, bool) {
+    let mut after = KEYBOARD_TYPOS.range::<str, _>((Bound::Included(word), Bound::Unbounded));
+    match after.next() {
+        Some((typo, &found)) if typo == word => (
+            Some(found),
+            after.next().is_some_and(|(next, _)| next.starts_with(word)),
+        ),
+        Some((typo, _)) => (None, typo.starts_with(word)),
+        None => (None, false),
+    }
+}
+
+/// Matches common passwords with a key replaced by a neighboring one.
+pub(super) struct KeyboardTypoMatch {}
+
+impl Matcher for KeyboardTypoMatch {
+    fn get_matches(&self, password: &str, _custom_words: &CustomWords) -> Vec<Match> {
+        let chars = super::lowercase_chars(password);
+        let mut matches = Vec::new();
+        for i in 0..chars.len() {
+            let mut word = String::new();
+            for (j, &c) in chars.iter().enumerate().skip(i) {
+                word.push(c);
+                let (found, extendable) = search(&word);
+                if let Some((matched_word, rank)) = found {
+                    matches.push(Match {
+                        pattern: MatchPattern::Dictionary(DictionaryPattern {
+                            matched_word: matched_word.to_string(),
+                            rank: rank as usize,
+                            dictionary_name: DictionaryType::Passwords,
+                            typos: 1,
+                            ..DictionaryPattern::default()
+                        }),
+                        i,
+                        j,
+                        token: password.chars().take(j + 1).skip(i).collect(),
+                        ..Match::default()
+                    });
+                }
+                if !extendable {
+                    break;
+                }
+            }
+        }
+        matches
+    }
+
+    fn in_js_implementation(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyboard_typo_matches() {
+        let matches = (KeyboardTypoMatch {}).get_matches("Passwprd!", &CustomWords::default());
+        assert_eq!(matches.len(), 1);
+        assert_eq!((matches[0].i, matches[0].j), (0, 7));
+        assert_eq!(matches[0].token, "Passwprd");
+        match &matches[0].pattern {
+            MatchPattern::Dictionary(p) => {
+                assert_eq!(p.matched_word, "password");
+                assert_eq!(p.rank, 2);
+                assert_eq!(p.typos, 1);
+            }
+            _ => panic!("expected a dictionary match"),
+        }
+        // only keys next to each other
+        assert!((KeyboardTypoMatch {})
+            .get_matches("passwzrd", &CustomWords::default())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_keyboard_typo_matches_index_the_password() {
+        // "İ" lowercases to two characters
+        let password = "İİİpasswprd";
+        let matches = (KeyboardTypoMatch {}).get_matches(password, &CustomWords::default());
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].token, "passwprd");
+        crate::zxcvbn("İİİpassword", &[]);
+    }
+
+    #[test]
+    fn test_keyboard_typos_are_scored_as_keyboard_typos() {
+        // every typo is one scoring counts among the keyboard typos of its password
+        for (typo, (password, _)) in KEYBOARD_TYPOS.iter().take(1000) {
+            let pattern = DictionaryPattern {
+                matched_word: password.to_string(),
+                typos: 1,
+                ..DictionaryPattern::default()
+            };
+            let token = typo.as_str();
+            assert!(
+                crate::scoring::is_keyboard_typo(
+                    &pattern.matched_word.chars().collect::<Vec<_>>(),
+                    token
+                ),
+                "{} {}",
+                typo,
+                password
+            );
+        }
+    }
+}
@@ -0,0 +1,322 @@
+//! Matching dictionary words typed with the wrong keyboard layout active, such as a Russian
+//! word typed on QWERTY, "gfhjkm" for "пароль", an English word typed on the Russian ЙЦУКЕН
+//! layout, "зфыыцщкв" for "password", or a word typed on QWERTY by fingers used to Dvorak,
+//! "ra;;,soh" for "password", and the other way around.
+//!
+//! The password is mapped key by key to the other layout and searched in the dictionaries
+//! again. Russian words are only found with the `russian` feature, or in user inputs and
+//! banned words written in Cyrillic.
+
+use super::patterns::MatchPattern;
+use super::{dictionary_matches, CustomWords, Match, Matcher};
+
+/// Shorter tokens map to some dictionary word on another layout too often to be worth
+/// matching, e.g. "ltkj" to "дело" or "зфыы" to "pass".
+const MIN_LEN: usize = 5;
+
+/// The unshifted keys of a QWERTY keyboard and the ЙЦУКЕН letters on the same keys.
+const KEYS: [(char, char); 33] = [
+    ('`', 'ё'),
+    ('q', 'й'),
+    ('w', 'ц'),
+    ('e', 'у'),
+    ('r', 'к'),
+    ('t', 'е'),
+    ('y', 'н'),
+    ('u', 'г'),
+    ('i', 'ш'),
+    ('o', 'щ'),
+    ('p', 'з'),
+    ('[', 'х'),
+    (']', 'ъ'),
+    ('a', 'ф'),
+    ('s', 'ы'),
+    ('d', 'в'),
+    ('f', 'а'),
+    ('g', 'п'),
+    ('h', 'р'),
+    ('j', 'о'),
+    ('k', 'л'),
+    ('l', 'д'),
+    (';', 'ж'),
+    ('\'', 'э'),
+    ('z', 'я'),
+    ('x', 'ч'),
+    ('c', 'с'),
+    ('v', 'м'),
+    ('b', 'и'),
+    ('n', 'т'),
+    ('m', 'ь'),
+    (',', 'б'),
+    ('.', 'ю'),
+];
+
+/// The unshifted keys of a QWERTY keyboard that Dvorak has other characters on, and those
+/// characters.
+const DVORAK: [(char, char); 33] = [
+    ('-', '['),
+    ('=', ']'),
+    ('q', '\''),
+    ('w', ','),
+    ('e', '.'),
+    ('r', 'p'),
+    ('t', 'y'),
+    ('y', 'f'),
+    ('u', 'g'),
+    ('i', 'c'),
+    ('o', 'r'),
+    ('p', 'l'),
+    ('[', '/'),
+    (']', '='),
+    ('s', 'o'),
+    ('d', 'e'),
+    ('f', 'u'),
+    ('g', 'i'),
+    ('h', 'd'),
+    ('j', 'h'),
+    ('k', 't'),
+    ('l', 'n'),
+    (';', 's'),
+    ('\'', '-'),
+    ('z', ';'),
+    ('x', 'q'),
+    ('c', 'j'),
+    ('v', 'k'),
+    ('b', 'x'),
+    ('n', 'b'),
+    (',', 'w'),
+    ('.', 'v'),
+    ('/', 'z'),
+];
+
+/// The Latin letters the confusables normalizer turns Cyrillic letters into, and the
+/// lowercase Cyrillic letters they stand for in a password typed in Cyrillic.
+const CONFUSABLES: [(char, char); 12] = [
+    ('a', 'а'),
+    ('b', 'в'),
+    ('c', 'с'),
+    ('e', 'е'),
+    ('h', 'н'),
+    ('k', 'к'),
+    ('m', 'м'),
+    ('o', 'о'),
+    ('p', 'р'),
+    ('t', 'т'),
+    ('x', 'х'),
+    ('y', 'у'),
+];
+
+/// Matches dictionary words typed with a QWERTY layout in place of ЙЦУКЕН or Dvorak, or the
+/// other way around.
+pub(super) struct WrongLayoutMatch {}
+
+impl Matcher for WrongLayoutMatch {
+    fn get_matches(&self, password: &str, custom_words: &CustomWords) -> Vec<Match> {
+        let chars = password.to_lowercase().chars().collect::<Vec<_>>();
+        // lowercasing keeps the length of Latin and Cyrillic letters
+        if chars.len() != password.chars().count() {
+            return Vec::new();
+        }
+        let mut matches = Vec::new();
+        let as_jcuken = chars
+            .iter()
+            .map(|&c| to_layout(&KEYS, c))
+            .collect::<Vec<_>>();
+        if as_jcuken.iter().any(Option::is_some) {
+            matches.extend(layout_matches(
+                password,
+                &chars,
+                &as_jcuken,
+                "jcuken",
+                custom_words,
+            ));
+        }
+        if chars.iter().any(|&c| from_layout(&KEYS, c).is_some()) {
+            // Latin letters the confusables normalizer made out of Cyrillic ones are keys too
+            let as_qwerty = chars
+                .iter()
+                .map(|&c| {
+                    let cyrillic = CONFUSABLES
+                        .iter()
+                        .find(|&&(latin, _)| latin == c)
+                        .map_or(c, |&(_, cyrillic)| cyrillic);
+                    from_layout(&KEYS, cyrillic)
+                })
+                .collect::<Vec<_>>();
+            matches.extend(
+                layout_matches(password, &chars, &as_qwerty, "qwerty", custom_words)
+                    .into_iter()
+                    .filter(|m| {
+                        chars[m.i..=m.j]
+                            .iter()
+                            .any(|&c| from_layout(&KEYS, c).is_some())
+                    }),
+            );
+        }
+        // Dvorak leaves a few keys as they are, so tokens left unchanged are skipped
+        for (mapped, layout) in [
+            (translate(&chars, |c| to_layout(&DVORAK, c)), "dvorak"),
+            (translate(&chars, |c| from_layout(&DVORAK, c)), "qwerty"),
+        ] {
+            matches.extend(
+                layout_matches(password, &chars, &mapped, layout, custom_words)
+                    .into_iter()
+                    .filter(|m| match &m.pattern {
+                        MatchPattern::Dictionary(p) => {
+                            chars[m.i..=m.j].iter().collect::<String>() != p.matched_word
+                        }
+                        _ => true,
+                    }),
+            );
+        }
+        matches
+    }
+
+    fn in_js_implementation(&self) -> bool {
+        false
+    }
+}
+
+/// The dictionary matches of the password with its characters replaced by `mapped`, where
+/// set, with the words written in `layout`. Only tokens of at least [`MIN_LEN`] characters
+/// that were mapped as a whole are kept.
+fn layout_matches(
+    password: &str,
+    chars: &[char],
+    mapped: &[Option<char>],
+    layout: &'static str,
+    custom_words: &CustomWords,
+) -> Vec<Match> {
+    let translated = chars
+        .iter()
+        .zip(mapped)
+        .map(|(&c, mapped)| mapped.unwrap_or(c))
+        .collect::<String>();
+    dictionary_matches(&translated, custom_words, false)
+        .into_iter()
+        .filter(|m| m.j - m.i + 1 >= MIN_LEN && mapped[m.i..=m.j].iter().all(Option::is_some))
+        .map(|mut m| {
+            m.token = password.chars().take(m.j + 1).skip(m.i).collect();
+            if let MatchPattern::Dictionary(ref mut pattern) = m.pattern {
+                pattern.layout = Some(layout);
+            }
+            m
+        })
+        .collect()
+}
+
+/// Maps every character of a password with `key`, keeping those it has no key for as they
+/// are.
+fn translate<F>(chars: &[char], key: F) -> Vec<Option<char>>
+where
+    F: Fn(char) -> Option<char>,
+{
+    chars.iter().map(|&c| Some(key(c).unwrap_or(c))).collect()
+}
+
+/// The character of another layout on the QWERTY key `c`, if the layout has another one.
+fn to_layout(keys: &[(char, char)], c: char) -> Option<char> {
+    keys.iter()
+        .find(|&&(qwerty, _)| qwerty == c)
+        .map(|&(_, other)| other)
+}
+
+/// The QWERTY key of the character `c` of another layout, if it is on another key.
+fn from_layout(keys: &[(char, char)], c: char) -> Option<char> {
+    keys.iter()
+        .find(|&&(_, other)| other == c)
+        .map(|&(qwerty, _)| qwerty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matching::patterns::{DictionaryPattern, DictionaryType};
+
+    /// The words matched in a password, with their tokens.
+    fn matched(password: &str, custom_words: &CustomWords) -> Vec<(String, DictionaryPattern)> {
+        (WrongLayoutMatch {})
+            .get_matches(password, custom_words)
+            .into_iter()
+            .map(|m| match m.pattern {
+                MatchPattern::Dictionary(p) => (m.token, p),
+                _ => panic!("Wrong match pattern"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_word_typed_on_jcuken() {
+        let matches = matched("зфыыцщкв1", &CustomWords::default());
+        let (_, p) = matches
+            .iter()
+            .find(|(token, _)| token == "зфыыцщкв")
+            .unwrap();
+        assert_eq!(p.matched_word, "password");
+        assert_eq!(p.dictionary_name, DictionaryType::Passwords);
+        assert_eq!(p.layout, Some("qwerty"));
+
+        // "monkey" typed on ЙЦУКЕН is "ьщтлун", which the confusables normalizer turns
+        // into "ьщтлyн"
+        let matches = matched("ьщтлyн", &CustomWords::default());
+        assert!(matches.iter().any(|(_, p)| p.matched_word == "monkey"));
+    }
+
+    #[test]
+    fn test_word_typed_on_qwerty() {
+        let custom_words = CustomWords {
+            user_inputs: [("телефон".to_string(), 1)].into_iter().collect(),
+            ..CustomWords::default()
+        };
+        let matches = matched("Ntktajy99", &custom_words);
+        let (token, p) = matches
+            .iter()
+            .find(|(_, p)| p.matched_word == "телефон")
+            .unwrap();
+        assert_eq!(token, "Ntktajy");
+        assert_eq!(p.dictionary_name, DictionaryType::UserInputs);
+        assert_eq!(p.layout, Some("jcuken"));
+    }
+
+    #[test]
+    fn test_word_typed_on_dvorak() {
+        // "password" typed on QWERTY as if it were Dvorak, and the other way around
+        for (password, layout) in [("Ra;;,soh", "dvorak"), ("laoo,rpe", "qwerty")] {
+            let matches = matched(password, &CustomWords::default());
+            let (_, p) = matches.iter().find(|(token, _)| token == password).unwrap();
+            assert_eq!(p.matched_word, "password");
+            assert_eq!(p.layout, Some(layout));
+        }
+
+        // words that come out the same are left to the dictionary matcher
+        assert!(matched("mama", &CustomWords::default())
+            .iter()
+            .all(|(token, _)| token != "mama"));
+    }
+
+    #[test]
+    fn test_short_words_are_not_matched() {
+        // "pass" typed on ЙЦУКЕН, "love" on Dvorak
+        for password in ["зфыы", "nrk.", "зфыы1234"] {
+            let matches = matched(password, &CustomWords::default());
+            assert!(
+                matches
+                    .iter()
+                    .all(|(token, _)| token.chars().count() >= MIN_LEN),
+                "{}: {:?}",
+                password,
+                matches
+            );
+        }
+        assert!(!matched("зфыыцщкв", &CustomWords::default()).is_empty());
+    }
+
+    #[cfg(feature = "russian")]
+    #[test]
+    fn test_russian_words() {
+        let matches = matched("gfhjkm", &CustomWords::default());
+        assert!(matches.iter().any(
+            |(_, p)| p.matched_word == "пароль" && p.dictionary_name == DictionaryType::Russian
+        ));
+    }
+}
@@ -1,14 +1,51 @@
+// Without the `all-matchers` feature, the matchers in modules of their own are left out,
+// along with the dependencies they need, and those defined here are compiled but unused.
+#![cfg_attr(not(feature = "all-matchers"), allow(dead_code))]
+
+#[cfg(feature = "all-matchers")]
+mod acronyms;
 mod char_indexing;
+#[cfg(feature = "all-matchers")]
+mod dates;
+mod emoji;
+#[cfg(feature = "keyboard-typos")]
+mod keyboard_typos;
+mod layouts;
+#[cfg(any(
+    feature = "national-ids-us",
+    feature = "national-ids-uk",
+    feature = "national-ids-ca"
+))]
+mod national_ids;
 /// Defines potential patterns used to match against a password
 pub mod patterns;
+#[cfg(any(
+    feature = "all-matchers",
+    feature = "national-ids-us",
+    feature = "national-ids-uk",
+    feature = "national-ids-ca"
+))]
+mod payment;
+pub(crate) mod regions;
+pub(crate) mod shapes;
+#[cfg(feature = "fuzzy-matching")]
+mod typos;
+pub(crate) mod unlock;
+mod word_pairs;
 
 use self::patterns::*;
-use crate::frequency_lists::DictionaryType;
+use crate::banlist::Banlist;
+use crate::frequency_lists::{self, DictionaryType, Ranks};
+use crate::scoring::GuessCalculation;
 use char_indexing::{CharIndexable, CharIndexableStr};
-use fancy_regex::Regex as FancyRegex;
 use itertools::Itertools;
-use regex::Regex;
-use std::collections::HashMap;
+use once_cell::sync::Lazy;
+use std::cell::{Cell, RefCell};
+use std::cmp;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::ops::Bound;
 
 /// A match of a predictable pattern in the password.
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -29,6 +66,140 @@ pub struct Match {
     pub guesses: Option<u64>,
 }
 
+/// Words supplied at runtime, matched alongside the built-in dictionaries.
+#[derive(Debug, Default)]
+pub(crate) struct CustomWords<'a> {
+    /// Lowercased user inputs, ranked by their position in the input list.
+    pub(crate) user_inputs: HashMap<String, usize>,
+    /// Words banned by the estimator's rules.
+    pub(crate) banlist: Option<&'a Banlist>,
+    /// Word lists loaded from dictionary files.
+    #[cfg(feature = "dictionary-files")]
+    pub(crate) dictionaries: &'a [std::sync::Arc<frequency_lists::DictionaryFile>],
+    /// Whether to only match what the JavaScript implementation does.
+    pub(crate) js_compatible: bool,
+    /// How many more matches may be found, counting those of every base token.
+    pub(crate) budget: MatchBudget,
+    /// The years dates and years are guessed around.
+    pub(crate) years: YearWindow,
+    /// How matches are scored.
+    pub(crate) scoring: crate::scoring::ScoringParams,
+    /// The base tokens of repeat, mirror and shift repeat matches matched and scored so far,
+    /// as the same base comes up in overlapping matches and at every level of recursion.
+    pub(crate) base_analyses: RefCell<HashMap<String, GuessCalculation>>,
+}
+
+/// The years that dates and years in passwords are guessed around.
+///
+/// Years are guessed starting from the reference year and moving away from it, so that
+/// years closer to it take fewer guesses. The years within the minimum year space of it
+/// are taken to be equally likely, as people pick both recent and upcoming years. The
+/// current year and the year before are also guessed first at the end of a password, where
+/// people append them, however far the reference year is.
+///
+/// ```
+/// use zxcvbn::matching::YearWindow;
+/// use zxcvbn::Zxcvbn;
+///
+/// let estimator = Zxcvbn::new().with_year_window(YearWindow::new(2000));
+/// let entropy = estimator.estimate("1980", &[]);
+/// assert!(entropy.guesses() < Zxcvbn::new().estimate("1980", &[]).guesses());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[cfg_attr(feature = "builder", builder(default))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+pub struct YearWindow {
+    /// The year guessed first, the current year by default.
+    pub reference_year: i32,
+    /// How many years away from the reference year are guessed as likely as it, 20 by
+    /// default.
+    pub min_year_space: i32,
+    /// The year it is, read from the clock by default. Set it to make estimates of passwords
+    /// ending in a year independent of when they are run.
+    pub current_year: i32,
+}
+
+impl YearWindow {
+    /// A window around `reference_year`, with the default minimum year space.
+    pub fn new(reference_year: i32) -> Self {
+        YearWindow {
+            reference_year,
+            ..Self::default()
+        }
+    }
+
+    /// Whether `year` is one people append to their passwords as it is: the current year or
+    /// the year before.
+    pub(crate) fn is_current(&self, year: i32) -> bool {
+        (self.current_year - 1..=self.current_year).contains(&year)
+    }
+
+    /// The latest year dates are matched with.
+    fn max_year(&self) -> i32 {
+        self.reference_year + DATE_YEARS_AHEAD
+    }
+
+    /// The year a two digit year stands for: the latest year ending with these digits that
+    /// dates are matched with, e.g. 2015 for "15" and 1987 for "87".
+    fn four_digit_year(&self, year: u16) -> i32 {
+        let max_year = self.max_year();
+        max_year - (max_year - i32::from(year)).rem_euclid(100)
+    }
+}
+
+impl Default for YearWindow {
+    fn default() -> Self {
+        YearWindow {
+            reference_year: *crate::scoring::REFERENCE_YEAR,
+            min_year_space: crate::scoring::MIN_YEAR_SPACE,
+            current_year: *crate::scoring::REFERENCE_YEAR,
+        }
+    }
+}
+
+/// Number of candidate matches an estimate may find unless configured otherwise with
+/// [`Zxcvbn::with_max_matches`](crate::Zxcvbn::with_max_matches).
+pub(crate) const DEFAULT_MAX_MATCHES: usize = 2000;
+
+/// The number of matches left to find for a password, shared by the matching of the
+/// password and of the base tokens of its repeat, mirror, shift repeat and interleaved
+/// matches, which would otherwise multiply on crafted inputs. Matching a base token again
+/// is paid for before it is done, a match per character, so that the budget bounds the work
+/// done as well as the matches found.
+#[derive(Debug)]
+pub(crate) struct MatchBudget(Cell<usize>);
+
+impl MatchBudget {
+    pub(crate) fn new(max_matches: usize) -> Self {
+        MatchBudget(Cell::new(max_matches))
+    }
+
+    fn is_spent(&self) -> bool {
+        self.0.get() == 0
+    }
+
+    fn spend(&self, matches: usize) {
+        self.0.set(self.0.get().saturating_sub(matches));
+    }
+
+    /// Spends `matches` if that many are left.
+    fn try_spend(&self, matches: usize) -> bool {
+        let left = self.0.get();
+        if left < matches {
+            return false;
+        }
+        self.0.set(left - matches);
+        true
+    }
+}
+
+impl Default for MatchBudget {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_MATCHES)
+    }
+}
+
 impl Match {
     /// Get the range of the index of the chars that are included in the match.
     pub fn range_inclusive(&self) -> std::ops::RangeInclusive<usize> {
@@ -36,13 +207,89 @@ impl Match {
     }
 }
 
-#[allow(clippy::implicit_hasher)]
-pub(crate) fn omnimatch(password: &str, user_inputs: &HashMap<String, usize>) -> Vec<Match> {
+/// A reason a match can't be scored, with the index of the match in the matches checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InvalidMatch {
+    /// The match ends before it begins.
+    EmptySpan {
+        /// Index of the match.
+        index: usize,
+    },
+    /// The match ends past the end of the password.
+    OutOfBounds {
+        /// Index of the match.
+        index: usize,
+    },
+    /// The token of the match isn't the characters of the password it covers.
+    TokenMismatch {
+        /// Index of the match.
+        index: usize,
+    },
+    /// The match is identical to an earlier one. Only
+    /// [`check_matches`](crate::testing::check_matches) looks for them, as scoring finds the
+    /// same sequence with or without them.
+    Duplicate {
+        /// Index of the match.
+        index: usize,
+        /// Index of the earlier match.
+        earlier: usize,
+    },
+}
+
+impl fmt::Display for InvalidMatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidMatch::EmptySpan { index } => write!(f, "match {index} ends before it begins"),
+            InvalidMatch::OutOfBounds { index } => {
+                write!(f, "match {index} ends past the end of the password")
+            }
+            InvalidMatch::TokenMismatch { index } => write!(
+                f,
+                "the token of match {index} isn't the characters of the password it covers"
+            ),
+            InvalidMatch::Duplicate { index, earlier } => {
+                write!(f, "match {index} is identical to match {earlier}")
+            }
+        }
+    }
+}
+
+impl Error for InvalidMatch {}
+
+impl InvalidMatch {
+    /// The index of the match that can't be scored.
+    pub fn index(&self) -> usize {
+        match *self {
+            InvalidMatch::EmptySpan { index }
+            | InvalidMatch::OutOfBounds { index }
+            | InvalidMatch::TokenMismatch { index }
+            | InvalidMatch::Duplicate { index, .. } => index,
+        }
+    }
+}
+
+/// Finds the matches of every matcher in the password. Once the budget is spent, base
+/// tokens are no longer matched, so they are guessed as bruteforce.
+pub(crate) fn omnimatch(password: &str, custom_words: &CustomWords) -> Vec<Match> {
+    if custom_words.budget.is_spent() {
+        return Vec::new();
+    }
     let mut matches: Vec<Match> = MATCHERS
         .iter()
-        .flat_map(|x| x.get_matches(password, user_inputs))
+        .filter(|x| !custom_words.js_compatible || x.in_js_implementation())
+        .flat_map(|x| x.get_matches(password, custom_words))
         .collect();
-    matches.sort_unstable_by(|a, b| {
+    custom_words.budget.spend(matches.len());
+    crate::scoring::estimate_configured_guesses(
+        password,
+        &mut matches,
+        &custom_words.years,
+        &custom_words.scoring,
+        custom_words.js_compatible,
+    );
+    // A stable sort keeps matches covering the same range in matcher order.
+    matches.sort_by(|a, b| {
         let range1 = a.range_inclusive();
         let range2 = b.range_inclusive();
         range1
@@ -53,107 +300,256 @@ pub(crate) fn omnimatch(password: &str, user_inputs: &HashMap<String, usize>) ->
     matches
 }
 
-lazy_static! {
-    static ref L33T_TABLE: HashMap<char, Vec<char>> = {
-        let mut table = HashMap::with_capacity(12);
-        table.insert('a', vec!['4', '@']);
-        table.insert('b', vec!['8']);
-        table.insert('c', vec!['(', '{', '[', '<']);
-        table.insert('e', vec!['3']);
-        table.insert('g', vec!['6', '9']);
-        table.insert('i', vec!['1', '!', '|']);
-        table.insert('l', vec!['1', '|', '7']);
-        table.insert('o', vec!['0']);
-        table.insert('s', vec!['$', '5']);
-        table.insert('t', vec!['+', '7']);
-        table.insert('x', vec!['%']);
-        table.insert('z', vec!['2']);
-        table
-    };
-    static ref GRAPHS: HashMap<&'static str, &'static HashMap<char, Vec<Option<&'static str>>>> = {
-        let mut table = HashMap::with_capacity(4);
-        table.insert("qwerty", &*super::adjacency_graphs::QWERTY);
-        table.insert("dvorak", &*super::adjacency_graphs::DVORAK);
-        table.insert("keypad", &*super::adjacency_graphs::KEYPAD);
-        table.insert("mac_keypad", &*super::adjacency_graphs::MAC_KEYPAD);
-        table
+/// The optimal match sequence of the base token of a repeat, mirror or shift repeat match,
+/// matched and scored once per estimate. Base tokens the budget can't pay for are guessed as
+/// bruteforce without being matched.
+fn analyze_base(base_token: &str, custom_words: &CustomWords) -> GuessCalculation {
+    if let Some(analysis) = custom_words.base_analyses.borrow().get(base_token) {
+        return analysis.clone();
+    }
+    let matches = if custom_words.budget.try_spend(base_token.chars().count()) {
+        omnimatch(base_token, custom_words)
+    } else {
+        Vec::new()
     };
+    let analysis =
+        super::scoring::most_guessable_match_sequence(base_token, &matches, &custom_words.scoring);
+    custom_words
+        .base_analyses
+        .borrow_mut()
+        .insert(base_token.to_string(), analysis.clone());
+    analysis
+}
+
+static L33T_TABLE: Lazy<BTreeMap<char, Vec<char>>> = Lazy::new(|| {
+    let mut table = BTreeMap::new();
+    table.insert('a', vec!['4', '@']);
+    table.insert('b', vec!['8']);
+    table.insert('c', vec!['(', '{', '[', '<']);
+    table.insert('e', vec!['3']);
+    table.insert('g', vec!['6', '9']);
+    table.insert('i', vec!['1', '!', '|']);
+    table.insert('l', vec!['1', '|', '7']);
+    table.insert('o', vec!['0']);
+    table.insert('s', vec!['$', '5']);
+    table.insert('t', vec!['+', '7']);
+    table.insert('x', vec!['%']);
+    table.insert('z', vec!['2']);
+    table
+});
+/// The letters each l33t character may stand for.
+static L33T_READINGS: Lazy<BTreeMap<char, Vec<char>>> = Lazy::new(|| {
+    let mut readings: BTreeMap<char, Vec<char>> = BTreeMap::new();
+    for (letter, subs) in L33T_TABLE.iter() {
+        for sub in subs {
+            readings.entry(*sub).or_default().push(*letter);
+        }
+    }
+    readings
+});
+
+/// Builds the tables, keyboard graphs and regular expressions of the matchers and the
+/// dictionaries they search, see [`crate::initialize`].
+pub(crate) fn initialize() {
+    initialize_dictionaries();
+    initialize_keyboards();
+    initialize_regexes();
+    Lazy::force(&L33T_READINGS);
+    Lazy::force(&MATCHERS);
+}
+
+/// Builds the built-in dictionaries and the tables typos of them are searched in.
+pub(crate) fn initialize_dictionaries() {
+    #[cfg(feature = "fuzzy-matching")]
+    typos::initialize();
+    #[cfg(feature = "keyboard-typos")]
+    keyboard_typos::initialize();
+    frequency_lists::initialize();
+}
+
+/// Builds the keyboard graphs and the tables derived from them.
+pub(crate) fn initialize_keyboards() {
+    Lazy::force(&crate::adjacency_graphs::GRAPHS);
+    Lazy::force(&SHIFT_TOGGLES);
+    regions::initialize();
+    shapes::initialize();
+}
+
+/// Compiles the regular expressions of the regex and date matchers.
+pub(crate) fn initialize_regexes() {
+    #[cfg(feature = "all-matchers")]
+    dates::initialize();
 }
 
 trait Matcher: Send + Sync {
-    fn get_matches(&self, password: &str, user_inputs: &HashMap<String, usize>) -> Vec<Match>;
+    fn get_matches(&self, password: &str, custom_words: &CustomWords) -> Vec<Match>;
+
+    /// Whether the JavaScript implementation has this matcher.
+    fn in_js_implementation(&self) -> bool {
+        true
+    }
 }
 
-lazy_static! {
-    static ref MATCHERS: [Box<dyn Matcher>; 8] = [
+/// Names of the national ID regions compiled in, as their features are named.
+pub(crate) fn national_id_regions() -> impl Iterator<Item = &'static str> {
+    [
+        ("national-ids-us", cfg!(feature = "national-ids-us")),
+        ("national-ids-uk", cfg!(feature = "national-ids-uk")),
+        ("national-ids-ca", cfg!(feature = "national-ids-ca")),
+    ]
+    .into_iter()
+    .filter(|&(_, enabled)| enabled)
+    .map(|(name, _)| name)
+}
+
+static MATCHERS: Lazy<Vec<Box<dyn Matcher>>> = Lazy::new(|| {
+    vec![
         Box::new(DictionaryMatch {}),
         Box::new(ReverseDictionaryMatch {}),
         Box::new(L33tMatch {}),
+        Box::new(layouts::WrongLayoutMatch {}),
+        #[cfg(feature = "fuzzy-matching")]
+        Box::new(typos::TypoMatch {}),
+        #[cfg(feature = "keyboard-typos")]
+        Box::new(keyboard_typos::KeyboardTypoMatch {}),
         Box::new(SpatialMatch {}),
         Box::new(RepeatMatch {}),
+        #[cfg(feature = "all-matchers")]
+        Box::new(MirrorMatch {}),
+        #[cfg(feature = "all-matchers")]
+        Box::new(ShiftRepeatMatch {}),
+        #[cfg(feature = "all-matchers")]
+        Box::new(EchoMatch {}),
+        #[cfg(feature = "all-matchers")]
+        Box::new(PhoneMatch {}),
+        #[cfg(any(
+            feature = "national-ids-us",
+            feature = "national-ids-uk",
+            feature = "national-ids-ca"
+        ))]
+        Box::new(national_ids::NationalIdMatch {}),
+        #[cfg(feature = "all-matchers")]
+        Box::new(payment::PaymentMatch {}),
+        #[cfg(feature = "all-matchers")]
+        Box::new(unlock::UnlockMatch {}),
+        Box::new(word_pairs::WordPairMatch {}),
+        #[cfg(feature = "all-matchers")]
+        Box::new(acronyms::AcronymMatch {}),
         Box::new(SequenceMatch {}),
-        Box::new(RegexMatch {}),
-        Box::new(DateMatch {}),
-    ];
-}
+        #[cfg(feature = "all-matchers")]
+        Box::new(ShiftedSequenceMatch {}),
+        #[cfg(feature = "all-matchers")]
+        Box::new(InterleavedMatch {}),
+        #[cfg(feature = "all-matchers")]
+        Box::new(dates::RegexMatch {}),
+        #[cfg(feature = "all-matchers")]
+        Box::new(dates::DateMatch {}),
+        #[cfg(feature = "all-matchers")]
+        Box::new(EmojiMatch {}),
+    ]
+});
 
 struct DictionaryMatch {}
 
 impl Matcher for DictionaryMatch {
-    fn get_matches(&self, password: &str, user_inputs: &HashMap<String, usize>) -> Vec<Match> {
-        let password_lower_string = password.to_lowercase();
-        let password_lower = CharIndexableStr::from(password_lower_string.as_str());
-
-        let do_trials = move |matches: &mut Vec<Match>,
-                              password: &str,
-                              dictionary_name: DictionaryType,
-                              ranked_dict: &HashMap<&str, usize>| {
-            let len = password.chars().count();
-            for i in 0..len {
-                for j in i..len {
-                    let word = password_lower.char_index(i..j + 1);
-                    if let Some(rank) = ranked_dict.get(word).cloned() {
-                        let pattern = MatchPattern::Dictionary(DictionaryPattern {
-                            matched_word: word.to_string(),
-                            rank,
-                            dictionary_name,
-                            ..DictionaryPattern::default()
-                        });
-                        matches.push(Match {
-                            pattern,
-                            i,
-                            j,
-                            token: password.chars().take(j + 1).skip(i).collect(),
-                            ..Match::default()
-                        });
-                    }
+    fn get_matches(&self, password: &str, custom_words: &CustomWords) -> Vec<Match> {
+        dictionary_matches(password, custom_words, false)
+    }
+}
+
+/// Matches the password against every dictionary. `reversed` tells whether the password
+/// has been reversed, for banned words that have to be at its start or end.
+fn dictionary_matches(password: &str, custom_words: &CustomWords, reversed: bool) -> Vec<Match> {
+    let password_lower_string = password.to_lowercase();
+    let password_lower = CharIndexableStr::from(password_lower_string.as_str());
+
+    /// Finds the rank and dictionary of a word, and whether longer words may start with it.
+    type Search<'a> = dyn Fn(&str) -> (Option<(usize, DictionaryType)>, bool) + 'a;
+
+    let do_trials = move |matches: &mut Vec<Match>, password: &str, search: &Search| {
+        let len = password.chars().count();
+        for i in 0..len {
+            for j in i..len {
+                let word = password_lower.char_index(i..j + 1);
+                let (found, extendable) = search(word);
+                if let Some((rank, dictionary_name)) = found {
+                    let pattern = MatchPattern::Dictionary(DictionaryPattern {
+                        matched_word: word.to_string(),
+                        rank,
+                        dictionary_name,
+                        ..DictionaryPattern::default()
+                    });
+                    matches.push(Match {
+                        pattern,
+                        i,
+                        j,
+                        token: password.chars().take(j + 1).skip(i).collect(),
+                        ..Match::default()
+                    });
+                }
+                if !extendable {
+                    break;
                 }
             }
-        };
+        }
+    };
 
-        let mut matches = Vec::new();
+    let mut matches = Vec::new();
 
-        for (dictionary_name, ranked_dict) in super::frequency_lists::RANKED_DICTIONARIES.iter() {
-            do_trials(&mut matches, password, *dictionary_name, ranked_dict);
+    // Banned words go first so they win ties with the same word in other dictionaries.
+    if let Some(banlist) = custom_words.banlist {
+        for banned in banlist.find(&password_lower_string, reversed) {
+            matches.push(Match {
+                pattern: MatchPattern::Dictionary(DictionaryPattern {
+                    matched_word: banned.word.to_string(),
+                    rank: 1,
+                    dictionary_name: DictionaryType::Banned,
+                    ..DictionaryPattern::default()
+                }),
+                i: banned.i,
+                j: banned.j,
+                token: password.chars().take(banned.j + 1).skip(banned.i).collect(),
+                ..Match::default()
+            });
         }
-        do_trials(
-            &mut matches,
-            password,
-            DictionaryType::UserInputs,
-            &user_inputs.iter().map(|(x, &i)| (x.as_str(), i)).collect(),
-        );
-
-        matches
     }
+    // Each word is matched once, in the dictionary where it ranks best.
+    do_trials(&mut matches, password, &|word| {
+        let (provenance, extendable) = frequency_lists::search(word);
+        let best = provenance.and_then(|provenance| {
+            provenance
+                .iter()
+                .find(|(dictionary, _)| {
+                    !custom_words.js_compatible || !dictionary.is_word_list_pack()
+                })
+                .map(|&(dictionary, rank)| (rank as usize, dictionary))
+        });
+        (best, extendable)
+    });
+    do_trials(&mut matches, password, &|word| {
+        let (rank, extendable) = custom_words.user_inputs.search(word);
+        (
+            rank.map(|rank| (rank, DictionaryType::UserInputs)),
+            extendable,
+        )
+    });
+    #[cfg(feature = "dictionary-files")]
+    for dictionary in custom_words.dictionaries {
+        do_trials(&mut matches, password, &|word| {
+            let (rank, extendable) = dictionary.search(word);
+            (rank.map(|rank| (rank, DictionaryType::Custom)), extendable)
+        });
+    }
+
+    matches
 }
 
 struct ReverseDictionaryMatch {}
 
 impl Matcher for ReverseDictionaryMatch {
-    fn get_matches(&self, password: &str, user_inputs: &HashMap<String, usize>) -> Vec<Match> {
+    fn get_matches(&self, password: &str, custom_words: &CustomWords) -> Vec<Match> {
         let reversed_password = password.chars().rev().collect::<String>();
-        (DictionaryMatch {})
-            .get_matches(&reversed_password, user_inputs)
+        dictionary_matches(&reversed_password, custom_words, true)
             .into_iter()
             .map(|mut m| {
                 // Reverse token back
@@ -173,14 +569,16 @@ impl Matcher for ReverseDictionaryMatch {
 struct L33tMatch {}
 
 impl Matcher for L33tMatch {
-    fn get_matches(&self, password: &str, user_inputs: &HashMap<String, usize>) -> Vec<Match> {
+    fn get_matches(&self, password: &str, custom_words: &CustomWords) -> Vec<Match> {
         let mut matches = Vec::new();
-        for sub in enumerate_l33t_replacements(&relevant_l33t_subtable(password)) {
+        // words with no l33t character read more than one way are found in every reading
+        let mut found = BTreeSet::new();
+        for sub in l33t_readings(&relevant_l33t_subtable(password)) {
             if sub.is_empty() {
                 break;
             }
             let subbed_password = translate(password, &sub);
-            for mut m4tch in (DictionaryMatch {}).get_matches(&subbed_password, user_inputs) {
+            for mut m4tch in (DictionaryMatch {}).get_matches(&subbed_password, custom_words) {
                 let token = password
                     .chars()
                     .take(m4tch.j + 1)
@@ -196,11 +594,24 @@ impl Matcher for L33tMatch {
                         // Only return the matches that contain an actual substitution
                         continue;
                     }
-                    let match_sub: HashMap<char, char> = sub
+                    let match_sub: BTreeMap<char, char> = sub
                         .clone()
                         .into_iter()
                         .filter(|&(subbed_chr, _)| token.contains(subbed_chr))
                         .collect();
+                    // a word l33ts each of its letters one way, though the next word may
+                    // l33t the same letter another way
+                    let letters = match_sub.values().collect::<BTreeSet<_>>();
+                    if letters.len() < match_sub.len()
+                        || !found.insert((
+                            m4tch.i,
+                            m4tch.j,
+                            pattern.dictionary_name,
+                            match_sub.clone(),
+                        ))
+                    {
+                        continue;
+                    }
                     m4tch.token = token;
                     pattern.l33t = true;
                     pattern.sub_display = Some(
@@ -214,23 +625,40 @@ impl Matcher for L33tMatch {
                 matches.push(m4tch);
             }
         }
+        // the JavaScript implementation drops one-character matches, such as "!" read as "i"
+        let min_len = if custom_words.js_compatible { 2 } else { 1 };
         matches
             .into_iter()
-            .filter(|x| !x.token.is_empty())
+            .filter(|x| x.token.chars().count() >= min_len)
             .collect()
     }
 }
 
-fn translate(string: &str, chr_map: &HashMap<char, char>) -> String {
+/// The lowercase of each character of the password, one for one, so that indexes into them
+/// are indexes into the password. Characters lowercased to more than one, like "İ", are kept.
+pub(crate) fn lowercase_chars(password: &str) -> Vec<char> {
+    password
+        .chars()
+        .map(|c| {
+            let mut lower = c.to_lowercase();
+            match (lower.next(), lower.next()) {
+                (Some(lower), None) => lower,
+                _ => c,
+            }
+        })
+        .collect()
+}
+
+fn translate(string: &str, chr_map: &BTreeMap<char, char>) -> String {
     string
         .chars()
         .map(|c| *chr_map.get(&c).unwrap_or(&c))
         .collect()
 }
 
-fn relevant_l33t_subtable(password: &str) -> HashMap<char, Vec<char>> {
+fn relevant_l33t_subtable(password: &str) -> BTreeMap<char, Vec<char>> {
     let password_chars: Vec<char> = password.chars().collect();
-    let mut subtable: HashMap<char, Vec<char>> = HashMap::new();
+    let mut subtable: BTreeMap<char, Vec<char>> = BTreeMap::new();
     for (letter, subs) in L33T_TABLE.iter() {
         let relevant_subs: Vec<char> = subs
             .iter()
@@ -244,71 +672,158 @@ fn relevant_l33t_subtable(password: &str) -> HashMap<char, Vec<char>> {
     subtable
 }
 
-fn enumerate_l33t_replacements(table: &HashMap<char, Vec<char>>) -> Vec<HashMap<char, char>> {
-    /// Recursive function that does the work
-    fn helper(
-        table: &HashMap<char, Vec<char>>,
-        subs: Vec<Vec<(char, char)>>,
-        remaining_keys: &[char],
-    ) -> Vec<Vec<(char, char)>> {
-        if remaining_keys.is_empty() {
-            return subs;
-        }
-        let (first_key, rest_keys) = remaining_keys.split_first().unwrap();
-        let mut next_subs: Vec<Vec<(char, char)>> = Vec::new();
-        for l33t_chr in &table[first_key] {
-            for sub in &subs {
-                let mut dup_l33t_index = None;
-                for (i, item) in sub.iter().enumerate() {
-                    if item.0 == *l33t_chr {
-                        dup_l33t_index = Some(i);
-                        break;
-                    }
-                }
-                if let Some(idx) = dup_l33t_index {
-                    let mut sub_alternative = sub.clone();
-                    sub_alternative.remove(idx);
-                    sub_alternative.push((*l33t_chr, *first_key));
-                    next_subs.push(sub.clone());
-                    next_subs.push(sub_alternative);
-                } else {
-                    let mut sub_extension = sub.clone();
-                    sub_extension.push((*l33t_chr, *first_key));
-                    next_subs.push(sub_extension);
-                }
+/// Every way of reading the l33t characters of the whole password as letters, each character
+/// as one of the letters it stands for in `table`. The words of the password are matched in
+/// each reading, so that words next to each other are found however each of them is l33ted:
+/// "p@55w0rd4ng3l" is read as "password" followed by "angel".
+fn l33t_readings(table: &BTreeMap<char, Vec<char>>) -> Vec<BTreeMap<char, char>> {
+    let mut letters: BTreeMap<char, Vec<char>> = BTreeMap::new();
+    for (&letter, subs) in table {
+        for &sub in subs {
+            letters.entry(sub).or_default().push(letter);
+        }
+    }
+    letters
+        .iter()
+        .fold(vec![BTreeMap::new()], |readings, (&sub, letters)| {
+            readings
+                .iter()
+                .flat_map(|reading| {
+                    letters.iter().map(move |&letter| {
+                        let mut reading = reading.clone();
+                        reading.insert(sub, letter);
+                        reading
+                    })
+                })
+                .collect()
+        })
+}
+
+/// Whether the whole password is one of the banned passwords, ignoring case and trying
+/// every l33t reading of it.
+pub(crate) fn is_banned_password(password: &str, banned_passwords: &BTreeSet<String>) -> bool {
+    /// Extends `prefix` one character at a time, giving up as soon as no banned password
+    /// starts with it. Each l33t character is read on its own, so "pa$5" is "pass".
+    fn helper(prefix: &mut String, rest: &[char], banned_passwords: &BTreeSet<String>) -> bool {
+        let (chr, rest) = match rest.split_first() {
+            Some(split) => split,
+            None => return banned_passwords.contains(prefix.as_str()),
+        };
+        let readings = L33T_READINGS.get(chr).map_or(&[][..], Vec::as_slice);
+        for &reading in std::iter::once(chr).chain(readings) {
+            prefix.push(reading);
+            let is_prefix = banned_passwords
+                .range::<str, _>((Bound::Included(prefix.as_str()), Bound::Unbounded))
+                .next()
+                .is_some_and(|banned| banned.starts_with(prefix.as_str()));
+            if is_prefix && helper(prefix, rest, banned_passwords) {
+                return true;
             }
+            prefix.pop();
         }
-        helper(
-            table,
-            next_subs
-                .into_iter()
-                .map(|x| x.iter().unique().cloned().collect())
-                .collect(),
-            rest_keys,
-        )
+        false
     }
 
+    let password = password.to_lowercase().chars().collect::<Vec<_>>();
     helper(
-        table,
-        vec![vec![]],
-        table.keys().cloned().collect::<Vec<char>>().as_slice(),
+        &mut String::with_capacity(password.len()),
+        &password,
+        banned_passwords,
     )
-    .into_iter()
-    .map(|sub| sub.into_iter().collect::<HashMap<char, char>>())
-    .collect()
 }
 
 struct SpatialMatch {}
 
 impl Matcher for SpatialMatch {
-    fn get_matches(&self, password: &str, _user_inputs: &HashMap<String, usize>) -> Vec<Match> {
-        GRAPHS
-            .iter()
-            .flat_map(|(graph_name, graph)| spatial_match_helper(password, graph, graph_name))
-            .collect()
+    fn get_matches(&self, password: &str, custom_words: &CustomWords) -> Vec<Match> {
+        let graphs = &crate::adjacency_graphs::GRAPHS;
+        let mut matches = if custom_words.scoring.has_js_match_order() {
+            JS_GRAPH_ORDER
+                .iter()
+                .flat_map(|&graph_name| {
+                    spatial_match_helper(password, graphs[graph_name].keys, graph_name)
+                })
+                .collect::<Vec<_>>()
+        } else {
+            graphs
+                .iter()
+                .flat_map(|(graph_name, graph)| {
+                    spatial_match_helper(password, graph.keys, graph_name)
+                })
+                .collect::<Vec<_>>()
+        };
+        if !custom_words.js_compatible {
+            matches.extend(region_matches(password));
+            matches.extend(shape_matches(password));
+        }
+        matches
     }
 }
 
+/// The graphs of keypads, which have no shift key.
+const PAD_GRAPHS: [&str; 4] = ["keypad", "mac_keypad", "phone_pad", "pin_pad"];
+
+/// The keyboards in the order the JavaScript implementation matches them in.
+const JS_GRAPH_ORDER: [&str; 4] = ["qwerty", "dvorak", "keypad", "mac_keypad"];
+
+/// Matches of whole rows, whole columns and rectangular blocks of keys.
+fn region_matches(password: &str) -> Vec<Match> {
+    let chars = password.chars().collect::<Vec<_>>();
+    let mut matches = Vec::new();
+    for (graph_name, max_len) in regions::graphs() {
+        for i in 0..chars.len() {
+            for j in i + 2..chars.len().min(i + max_len) {
+                let token = chars[i..=j].iter().collect::<String>();
+                if let Some(region) = regions::find(graph_name, &token) {
+                    matches.push(Match {
+                        pattern: MatchPattern::Spatial(SpatialPattern {
+                            graph: graph_name.to_string(),
+                            turns: region.turns,
+                            shifted_count: 0,
+                            region: Some(region.kind),
+                            shape: None,
+                        }),
+                        i,
+                        j,
+                        token,
+                        ..Match::default()
+                    });
+                }
+            }
+        }
+    }
+    matches
+}
+
+/// Matches of boxes, triangles and crosses drawn on the keyboard.
+fn shape_matches(password: &str) -> Vec<Match> {
+    let chars = password.chars().collect::<Vec<_>>();
+    let mut matches = Vec::new();
+    for (graph_name, max_len) in shapes::graphs() {
+        for i in 0..chars.len() {
+            for j in i + 2..chars.len().min(i + max_len) {
+                let token = chars[i..=j].iter().collect::<String>();
+                if let Some(shape) = shapes::find(graph_name, &token) {
+                    matches.push(Match {
+                        pattern: MatchPattern::Spatial(SpatialPattern {
+                            graph: graph_name.to_string(),
+                            turns: shape.turns,
+                            shifted_count: 0,
+                            region: None,
+                            shape: Some(shape.kind),
+                        }),
+                        i,
+                        j,
+                        token,
+                        ..Match::default()
+                    });
+                }
+            }
+        }
+    }
+    matches
+}
+
 const SHIFTED_CHARS: [char; 49] = [
     '[', '~', '!', '@', '#', '$', '%', '^', '&', '*', '(', ')', '_', '+', 'Q', 'W', 'E', 'R', 'T',
     'Y', 'U', 'I', 'O', 'P', '{', '}', '|', 'A', 'S', 'D', 'F', 'G', 'H', 'J', 'K', 'L', ':', '"',
@@ -330,7 +845,7 @@ fn spatial_match_helper(
         let mut j = i + 1;
         let mut last_direction = None;
         let mut turns = 0;
-        let mut shifted_count = if ["qwerty", "dvorak"].contains(&graph_name)
+        let mut shifted_count = if !PAD_GRAPHS.contains(&graph_name)
             && SHIFTED_CHARS.contains(&password.chars().nth(i).unwrap())
         {
             1
@@ -381,6 +896,8 @@ fn spatial_match_helper(
                         graph: graph_name.to_string(),
                         turns,
                         shifted_count,
+                        region: None,
+                        shape: None,
                     });
                     matches.push(Match {
                         pattern,
@@ -398,73 +915,85 @@ fn spatial_match_helper(
     matches
 }
 
+/// The first run of a repeated string in `chars`, as the index it starts at, the length of
+/// the repeated string and how many times it is repeated, like the regex `(.+)\1+` if
+/// `greedy` and `(.+?)\1+` otherwise: the run that starts first, with the longest or shortest
+/// repeated string there, repeated as many times as it is.
+fn find_repeat(chars: &[char], greedy: bool) -> Option<(usize, usize, usize)> {
+    let repeat_count = |start: usize, len: usize| {
+        let base = &chars[start..start + len];
+        chars[start..]
+            .chunks_exact(len)
+            .take_while(|&chunk| chunk == base)
+            .count()
+    };
+    (0..chars.len()).find_map(|start| {
+        // like `.`, the repeated string can't hold a newline
+        let max_len = chars[start..]
+            .iter()
+            .take_while(|&&c| c != '\n')
+            .count()
+            .min((chars.len() - start) / 2);
+        let mut lens = 1..=max_len;
+        let mut repeat = |len| {
+            let count = repeat_count(start, len);
+            (count > 1).then_some((start, len, count))
+        };
+        if greedy {
+            lens.rev().find_map(&mut repeat)
+        } else {
+            lens.find_map(&mut repeat)
+        }
+    })
+}
+
 struct RepeatMatch {}
 
 impl Matcher for RepeatMatch {
-    fn get_matches(&self, password: &str, user_inputs: &HashMap<String, usize>) -> Vec<Match> {
-        lazy_static! {
-            static ref GREEDY_REGEX: FancyRegex = FancyRegex::new(r"(.+)\1+").unwrap();
-            static ref LAZY_REGEX: FancyRegex = FancyRegex::new(r"(.+?)\1+").unwrap();
-            static ref LAZY_ANCHORED_REGEX: FancyRegex = FancyRegex::new(r"^(.+?)\1+$").unwrap();
-        }
-
+    fn get_matches(&self, password: &str, custom_words: &CustomWords) -> Vec<Match> {
         let mut matches = Vec::new();
         let mut last_index = 0;
-        let char_indexable_password = CharIndexableStr::from(password);
-        let char_count = password.chars().count();
-        while last_index < char_count {
-            let token = char_indexable_password.char_index(last_index..char_count);
-            let greedy_matches = GREEDY_REGEX.captures(token).unwrap();
-            if greedy_matches.is_none() {
-                break;
-            }
-            let lazy_matches = LAZY_REGEX.captures(token).unwrap();
-            let greedy_matches = greedy_matches.unwrap();
-            let lazy_matches = lazy_matches.unwrap();
-            let m4tch;
-            let base_token = if greedy_matches.get(0).unwrap().as_str().chars().count()
-                > lazy_matches.get(0).unwrap().as_str().chars().count()
-            {
+        let chars = password.chars().collect::<Vec<_>>();
+        while last_index < chars.len() {
+            let token = &chars[last_index..];
+            let greedy = match find_repeat(token, true) {
+                Some(greedy) => greedy,
+                None => break,
+            };
+            let lazy = find_repeat(token, false).unwrap();
+            let (start, len, count) = if greedy.1 * greedy.2 > lazy.1 * lazy.2 {
                 // greedy beats lazy for 'aabaab'
                 //   greedy: [aabaab, aab]
                 //   lazy:   [aa,     a]
-                m4tch = greedy_matches;
                 // greedy's repeated string might itself be repeated, eg.
                 // aabaab in aabaabaabaab.
-                // run an anchored lazy match on greedy's repeated string
-                // to find the shortest repeated string
-                LAZY_ANCHORED_REGEX
-                    .captures(m4tch.get(0).unwrap().as_str())
-                    .unwrap()
-                    .unwrap()
-                    .get(1)
-                    .unwrap()
-                    .as_str()
-                    .to_string()
+                // find the shortest string the whole of greedy's run repeats
+                let (start, len, count) = greedy;
+                let run = &token[start..start + len * count];
+                let base_len = (1..=len)
+                    .find(|&base_len| {
+                        run.len() % base_len == 0
+                            && run
+                                .chunks_exact(base_len)
+                                .all(|chunk| chunk == &run[..base_len])
+                    })
+                    .unwrap();
+                (start, base_len, run.len() / base_len)
             } else {
                 // lazy beats greedy for 'aaaaa'
                 //   greedy: [aaaa,  aa]
                 //   lazy:   [aaaaa, a]
-                m4tch = lazy_matches;
-                m4tch.get(1).unwrap().as_str().to_string()
+                lazy
             };
 
-            let m = m4tch.get(0).unwrap();
-            let (i, j) = (
-                last_index + token[..m.start()].chars().count(),
-                last_index + token[..m.end()].chars().count() - 1,
-            );
+            let (i, j) = (last_index + start, last_index + start + len * count - 1);
+            let base_token = token[start..start + len].iter().collect::<String>();
             // recursively match and score the base string
-            let base_analysis = super::scoring::most_guessable_match_sequence(
-                &base_token,
-                &omnimatch(&base_token, user_inputs),
-                false,
-            );
+            let base_analysis = analyze_base(&base_token, custom_words);
             let base_matches = base_analysis.sequence;
             let base_guesses = base_analysis.guesses;
             let pattern = MatchPattern::Repeat(RepeatPattern {
-                repeat_count: m4tch.get(0).unwrap().as_str().chars().count()
-                    / base_token.chars().count(),
+                repeat_count: count,
                 base_token,
                 base_guesses,
                 base_matches,
@@ -473,7 +1002,7 @@ impl Matcher for RepeatMatch {
                 pattern,
                 i,
                 j,
-                token: m4tch.get(0).unwrap().as_str().to_string(),
+                token: chars[i..=j].iter().collect(),
                 ..Match::default()
             });
             last_index = j + 1;
@@ -482,6 +1011,390 @@ impl Matcher for RepeatMatch {
     }
 }
 
+/// Text followed by its mirror image, e.g. "abccba", or with a middle character,
+/// e.g. "racecar". Only maximal palindromes are reported: the longest around each middle,
+/// unless it lies within a longer one around another middle.
+struct MirrorMatch {}
+
+impl Matcher for MirrorMatch {
+    fn get_matches(&self, password: &str, custom_words: &CustomWords) -> Vec<Match> {
+        /// Minimum length of a match, e.g. "abba" or "aba1aba" but not "aba".
+        const MIN_LENGTH: usize = 4;
+
+        let chars = password.chars().collect::<Vec<_>>();
+        let mut palindromes = longest_palindromes(&chars)
+            .into_iter()
+            .filter(|&(i, j)| j - i >= MIN_LENGTH)
+            // runs of one character are left to the repeat matcher.
+            .filter(|&(i, j)| chars[i..j].iter().any(|&c| c != chars[i]))
+            .collect::<Vec<_>>();
+        // longest first among those starting together, so that each palindrome within
+        // another comes after it
+        palindromes.sort_by_key(|&(i, j)| (i, cmp::Reverse(j)));
+        let mut matches = Vec::new();
+        let mut end = 0;
+        for (i, j) in palindromes {
+            if j <= end {
+                continue;
+            }
+            end = j;
+            let base_token = chars[i..i + (j - i).div_ceil(2)].iter().collect::<String>();
+            // recursively match and score the mirrored half
+            let base_analysis = analyze_base(&base_token, custom_words);
+            matches.push(Match {
+                pattern: MatchPattern::Mirror(MirrorPattern {
+                    base_token,
+                    base_matches: base_analysis.sequence,
+                    base_guesses: base_analysis.guesses,
+                }),
+                i,
+                j: j - 1,
+                token: chars[i..j].iter().collect(),
+                ..Match::default()
+            });
+        }
+        matches
+    }
+
+    fn in_js_implementation(&self) -> bool {
+        false
+    }
+}
+
+/// The start and end, exclusive, of the longest palindrome around each char and around the
+/// gap before each char, in linear time with Manacher's algorithm.
+fn longest_palindromes(chars: &[char]) -> Vec<(usize, usize)> {
+    let n = chars.len();
+    let mut palindromes = Vec::with_capacity(2 * n);
+    // `odd` palindromes are chars[m - r..m + r + 1] around the char m, others are
+    // chars[m - r..m + r] around the gap before it.
+    for odd in [true, false] {
+        let odd = usize::from(odd);
+        let mut radii = vec![0; n];
+        // the palindrome reaching furthest right so far, as chars[left..right]
+        let (mut left, mut right) = (0, 0);
+        for m in 0..n {
+            let mut r = if m < right {
+                // at least as long as around the mirror image of m in that palindrome
+                let mirror = left + right - m - odd;
+                radii[mirror].min(right - m - odd)
+            } else {
+                0
+            };
+            while m > r && m + r + odd < n && chars[m - r - 1] == chars[m + r + odd] {
+                r += 1;
+            }
+            radii[m] = r;
+            if m + r + odd > right {
+                left = m - r;
+                right = m + r + odd;
+            }
+            palindromes.push((m - r, m + r + odd));
+        }
+    }
+    palindromes
+}
+
+/// Each key's character mapped to the character typed with shift held, and back,
+/// e.g. '1' to '!' and 'A' to 'a'.
+static SHIFT_TOGGLES: Lazy<HashMap<char, char>> = Lazy::new(|| {
+    crate::adjacency_graphs::QWERTY
+        .values()
+        .flatten()
+        .flatten()
+        .flat_map(|key| {
+            let mut chars = key.chars();
+            match (chars.next(), chars.next()) {
+                (Some(unshifted), Some(shifted)) => {
+                    vec![(unshifted, shifted), (shifted, unshifted)]
+                }
+                _ => vec![],
+            }
+        })
+        .collect()
+});
+
+/// The digit a symbol is typed as with shift held, e.g. '1' for '!'.
+pub(crate) fn unshifted_digit(c: char) -> Option<char> {
+    SHIFT_TOGGLES
+        .get(&c)
+        .copied()
+        .filter(|unshifted| !c.is_ascii_digit() && unshifted.is_ascii_digit())
+}
+
+/// Text followed by the same keys typed with shift toggled, e.g. "123!@#", or in reverse
+/// order, e.g. "123456^%$#@!". Only the longest match starting at each index is reported,
+/// and none starting within an earlier match, so that the retyped text of overlapping
+/// matches isn't estimated over and over.
+struct ShiftRepeatMatch {}
+
+impl Matcher for ShiftRepeatMatch {
+    fn get_matches(&self, password: &str, custom_words: &CustomWords) -> Vec<Match> {
+        /// Minimum length of the text that is retyped, e.g. "123" in "123!@#".
+        const MIN_BASE_LENGTH: usize = 3;
+
+        let chars = password.chars().collect::<Vec<_>>();
+        let toggled = chars
+            .iter()
+            .map(|c| SHIFT_TOGGLES.get(c).copied())
+            .collect::<Vec<_>>();
+        let mut matches = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            let longest = (MIN_BASE_LENGTH..=(chars.len() - i) / 2)
+                .rev()
+                // the retyped text starts with the first or last key of the text toggled
+                .filter(|&len| {
+                    let first = Some(chars[i + len]);
+                    toggled[i] == first || toggled[i + len - 1] == first
+                })
+                .find_map(|len| {
+                    let base = i..i + len;
+                    let retyped = &chars[i + len..i + 2 * len];
+                    if base
+                        .clone()
+                        .zip(retyped)
+                        .all(|(k, &c)| toggled[k] == Some(c))
+                    {
+                        Some((len, false))
+                    } else if base.rev().zip(retyped).all(|(k, &c)| toggled[k] == Some(c)) {
+                        Some((len, true))
+                    } else {
+                        None
+                    }
+                });
+            if let Some((len, reversed)) = longest {
+                let base_token = chars[i..i + len].iter().collect::<String>();
+                // recursively match and score the retyped text
+                let base_analysis = analyze_base(&base_token, custom_words);
+                matches.push(Match {
+                    pattern: MatchPattern::ShiftRepeat(ShiftRepeatPattern {
+                        base_token,
+                        base_matches: base_analysis.sequence,
+                        base_guesses: base_analysis.guesses,
+                        reversed,
+                    }),
+                    i,
+                    j: i + 2 * len - 1,
+                    token: chars[i..i + 2 * len].iter().collect(),
+                    ..Match::default()
+                });
+                i += 2 * len;
+            } else {
+                i += 1;
+            }
+        }
+        matches
+    }
+
+    fn in_js_implementation(&self) -> bool {
+        false
+    }
+}
+
+/// Text with every character typed the same number of times in a row, e.g. "ppaasswwoorrdd"
+/// or "aaabbbccc", where the double letters of dictionary words may be typed only as often as
+/// the others, as in "ppaasswwoorrdd" for "password". Only the longest match of each echo count
+/// is reported at a time.
+struct EchoMatch {}
+
+impl Matcher for EchoMatch {
+    fn get_matches(&self, password: &str, custom_words: &CustomWords) -> Vec<Match> {
+        /// Minimum length of the text that is echoed, e.g. "abc" in "aabbcc".
+        const MIN_BASE_LENGTH: usize = 3;
+        /// Maximum number of times each character is typed.
+        const MAX_ECHO_COUNT: usize = 4;
+
+        let chars = password.chars().collect::<Vec<_>>();
+        let mut matches = Vec::new();
+        for echo_count in 2..=MAX_ECHO_COUNT {
+            let mut i = 0;
+            while i < chars.len() {
+                // the number of characters from i typed echo_count times each
+                let base_len = chars[i..]
+                    .chunks_exact(echo_count)
+                    .take_while(|chunk| chunk.iter().all(|&c| c == chunk[0]))
+                    .count();
+                let base = chars[i..]
+                    .iter()
+                    .step_by(echo_count)
+                    .take(base_len)
+                    .copied()
+                    .collect::<Vec<_>>();
+                // runs of one character are left to the repeat matcher.
+                if base_len < MIN_BASE_LENGTH || base.iter().all(|&c| c == chars[i]) {
+                    i += 1;
+                    continue;
+                }
+                let j = i + base_len * echo_count;
+                let token = chars[i..j].iter().collect::<String>();
+                // the echoed text, and the text with its double letters typed as often as the
+                // others, if it has any
+                let bases = std::iter::once((base.iter().collect::<String>(), false))
+                    .chain(with_double_letters(&base).map(|base| (base, true)));
+                // recursively match and score each, keeping the fewest guesses
+                let pattern = bases
+                    .map(|(base_token, collapsed)| {
+                        let base_analysis = analyze_base(&base_token, custom_words);
+                        EchoPattern {
+                            base_token,
+                            base_matches: base_analysis.sequence,
+                            base_guesses: base_analysis.guesses,
+                            echo_count,
+                            collapsed,
+                        }
+                    })
+                    .min_by_key(|pattern| super::scoring::estimate_echo(pattern, &token))
+                    .unwrap();
+                matches.push(Match {
+                    pattern: MatchPattern::Echo(pattern),
+                    i,
+                    j: j - 1,
+                    token,
+                    ..Match::default()
+                });
+                i = j;
+            }
+        }
+        matches
+    }
+
+    fn in_js_implementation(&self) -> bool {
+        false
+    }
+}
+
+/// The echoed text with each letter doubled that a word of the built-in dictionaries has
+/// doubled there, e.g. "password" for "pasword" echoed as "ppaasswwoorrdd", or `None` if no
+/// letter is. Longer words win over the shorter words they overlap.
+fn with_double_letters(base: &[char]) -> Option<String> {
+    /// Words this long or longer are looked for around each letter.
+    const MIN_WORD_LENGTH: usize = 4;
+    /// Words this long or shorter are looked for around each letter.
+    const MAX_WORD_LENGTH: usize = 12;
+
+    let lower = lowercase_chars(&base.iter().collect::<String>());
+    // the words with a letter doubled, by the range of the echoed text they cover and the
+    // letter
+    let mut words = Vec::new();
+    for k in 0..lower.len() {
+        // a letter already typed twice in a row is a double letter of its own
+        if !lower[k].is_alphabetic() || lower.get(k + 1) == Some(&lower[k]) {
+            continue;
+        }
+        for start in (k + 2).saturating_sub(MAX_WORD_LENGTH)..=k {
+            let last = (start + MAX_WORD_LENGTH - 1).min(lower.len());
+            for end in (k + 1).max(start + MIN_WORD_LENGTH - 1)..=last {
+                let word = lower[start..=k]
+                    .iter()
+                    .chain(&lower[k..end])
+                    .collect::<String>();
+                if frequency_lists::search(&word).0.is_some() {
+                    words.push((start..end, k));
+                }
+            }
+        }
+    }
+    words.sort_by_key(|(range, k)| (cmp::Reverse(range.len()), range.start, *k));
+    let mut covered = vec![false; lower.len()];
+    let mut doubled = vec![false; lower.len()];
+    for (range, k) in words {
+        if covered[range.clone()].iter().all(|&covered| !covered) {
+            covered[range]
+                .iter_mut()
+                .for_each(|covered| *covered = true);
+            doubled[k] = true;
+        }
+    }
+    if !doubled.contains(&true) {
+        return None;
+    }
+    let mut with_doubles = String::new();
+    for (&c, doubled) in base.iter().zip(doubled) {
+        with_doubles.push(c);
+        if doubled {
+            with_doubles.push(c);
+        }
+    }
+    Some(with_doubles)
+}
+
+/// Digits, or parts of them, of phone numbers among the user inputs, e.g. "4567" of
+/// "+1 (555) 123-4567". Each occurrence of each part is reported.
+struct PhoneMatch {}
+
+impl Matcher for PhoneMatch {
+    fn get_matches(&self, password: &str, custom_words: &CustomWords) -> Vec<Match> {
+        let mut matches = Vec::new();
+        // in rank order, so that the matches don't depend on hash map iteration order
+        let user_inputs = custom_words
+            .user_inputs
+            .iter()
+            .sorted_by_key(|&(_, &rank)| rank);
+        for (user_input, &rank) in user_inputs {
+            for (part, digits) in phone_parts(user_input) {
+                for (start, token) in password.match_indices(digits.as_str()) {
+                    let i = password[..start].chars().count();
+                    matches.push(Match {
+                        pattern: MatchPattern::Phone(PhonePattern { part, rank }),
+                        i,
+                        j: i + token.chars().count() - 1,
+                        token: token.to_string(),
+                        ..Match::default()
+                    });
+                }
+            }
+        }
+        matches
+    }
+
+    fn in_js_implementation(&self) -> bool {
+        false
+    }
+}
+
+/// The parts of a user input that is a phone number, like "+1 (555) 123-4567", each with the
+/// name of the part, without duplicates and most significant first. Empty if the user input
+/// isn't a phone number.
+fn phone_parts(user_input: &str) -> Vec<(&'static str, String)> {
+    let is_phone = user_input
+        .trim_start_matches('+')
+        .chars()
+        .all(|c| c.is_ascii_digit() || PHONE_SEPARATORS.contains(&c));
+    let digits = user_input
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect::<String>();
+    if !is_phone || !PHONE_DIGITS.contains(&digits.len()) {
+        return Vec::new();
+    }
+    // the national number of a North American number with its country code
+    let national = match digits.strip_prefix('1') {
+        Some(national) if national.len() == 10 => national,
+        _ => digits.as_str(),
+    };
+    let mut parts = vec![
+        ("number", digits.clone()),
+        ("number", national.to_string()),
+        ("formatted", user_input.trim().to_string()),
+        ("local", digits[digits.len() - 7..].to_string()),
+    ];
+    if national.len() == 10 {
+        parts.push(("area_code", national[..3].to_string()));
+    }
+    parts.push(("last_four", digits[digits.len() - 4..].to_string()));
+    parts
+        .into_iter()
+        .unique_by(|(_, digits)| digits.clone())
+        .collect()
+}
+
+/// Characters phone numbers are written with besides digits and a leading '+'.
+const PHONE_SEPARATORS: &[char] = &[' ', '-', '.', '(', ')'];
+/// How many digits phone numbers have, from national numbers to the longest international
+/// ones.
+const PHONE_DIGITS: std::ops::RangeInclusive<usize> = 9..=15;
+
 const MAX_DELTA: i32 = 5;
 
 /// Identifies sequences by looking for repeated differences in unicode codepoint.
@@ -499,28 +1412,12 @@ const MAX_DELTA: i32 = 5;
 struct SequenceMatch {}
 
 impl Matcher for SequenceMatch {
-    fn get_matches(&self, password: &str, _user_inputs: &HashMap<String, usize>) -> Vec<Match> {
+    fn get_matches(&self, password: &str, _custom_words: &CustomWords) -> Vec<Match> {
         fn update(i: usize, j: usize, delta: i32, password: &str, matches: &mut Vec<Match>) {
             let delta_abs = delta.abs();
             if (j - i > 1 || delta_abs == 1) && (0 < delta_abs && delta_abs <= MAX_DELTA) {
                 let token = password.chars().take(j + 1).skip(i).collect::<String>();
-                let first_chr = token.chars().next().unwrap();
-                let (sequence_name, sequence_space) = if first_chr.is_lowercase() {
-                    ("lower", 26)
-                } else if first_chr.is_uppercase() {
-                    ("upper", 26)
-                } else if first_chr.is_ascii_digit() {
-                    ("digits", 10)
-                } else {
-                    // conservatively stick with roman alphabet size.
-                    // (this could be improved)
-                    ("unicode", 26)
-                };
-                let pattern = MatchPattern::Sequence(SequencePattern {
-                    sequence_name,
-                    sequence_space,
-                    ascending: delta > 0,
-                });
+                let pattern = MatchPattern::Sequence(sequence_pattern(&token, delta));
                 matches.push(Match {
                     pattern,
                     i,
@@ -540,329 +1437,294 @@ impl Matcher for SequenceMatch {
 
         let mut i = 0;
         let mut j;
-        let mut last_delta = 0;
+        // none until the first delta, as 0 is the delta of a repeated character
+        let mut last_delta = None;
 
         for k in 1..password_len {
             let delta = password.chars().nth(k).unwrap() as i32
                 - password.chars().nth(k - 1).unwrap() as i32;
-            if last_delta == 0 {
-                last_delta = delta;
-            }
-            if last_delta == delta {
+            let previous_delta = *last_delta.get_or_insert(delta);
+            if previous_delta == delta {
                 continue;
             }
             j = k - 1;
-            update(i, j, last_delta, password, &mut matches);
+            update(i, j, previous_delta, password, &mut matches);
             i = j;
-            last_delta = delta;
+            last_delta = Some(delta);
+        }
+        if let Some(last_delta) = last_delta {
+            update(i, password_len - 1, last_delta, password, &mut matches);
         }
-        update(i, password_len - 1, last_delta, password, &mut matches);
         matches
     }
 }
 
-struct RegexMatch {}
+/// Digits in sequence with some or all of them typed with shift held, e.g. "!@#$%^" or
+/// "!@#$%6", which are as easy to guess as the digits with a few more guesses for the shift.
+/// Sequences of digits alone are left to the sequence matcher.
+struct ShiftedSequenceMatch {}
 
-impl Matcher for RegexMatch {
-    fn get_matches(&self, password: &str, _user_inputs: &HashMap<String, usize>) -> Vec<Match> {
+impl Matcher for ShiftedSequenceMatch {
+    fn get_matches(&self, password: &str, _custom_words: &CustomWords) -> Vec<Match> {
+        /// Minimum length of a match.
+        const MIN_LENGTH: usize = 3;
+
+        let chars = password.chars().collect::<Vec<_>>();
+        let digits = chars
+            .iter()
+            .map(|&c| {
+                if c.is_ascii_digit() {
+                    Some(c)
+                } else {
+                    unshifted_digit(c)
+                }
+            })
+            .collect::<Vec<_>>();
         let mut matches = Vec::new();
-        for (&name, regex) in REGEXES.iter() {
-            for capture in regex.captures_iter(password) {
-                let m = capture.get(0).unwrap();
-                let pattern = MatchPattern::Regex(RegexPattern {
-                    regex_name: name,
-                    regex_match: capture
-                        .iter()
-                        .map(|x| x.unwrap().as_str().to_string())
-                        .collect(),
-                });
-                let (i, j) = (
-                    password[..m.start()].chars().count(),
-                    password[..m.end()].chars().count() - 1,
-                );
+        let mut i = 0;
+        while i < chars.len() {
+            // the longest run of digits stepping by one in the same direction from i. like
+            // the sequence matcher, runs changing direction share the char they turn at.
+            let mut j = i;
+            let mut delta = 0;
+            while let (Some(Some(a)), Some(Some(b))) = (digits.get(j), digits.get(j + 1)) {
+                let step = *b as i32 - *a as i32;
+                if step.abs() != 1 || (delta != 0 && step != delta) {
+                    break;
+                }
+                delta = step;
+                j += 1;
+            }
+            let shifted_count = chars[i..=j].iter().filter(|c| !c.is_ascii_digit()).count();
+            if j + 1 - i >= MIN_LENGTH && shifted_count > 0 {
                 matches.push(Match {
-                    pattern,
-                    token: m.as_str().to_string(),
+                    pattern: MatchPattern::Sequence(SequencePattern {
+                        sequence_name: "digits",
+                        sequence_space: 10,
+                        ascending: delta > 0,
+                        shifted_count,
+                    }),
                     i,
                     j,
+                    token: chars[i..=j].iter().collect(),
                     ..Match::default()
                 });
             }
+            i = j.max(i + 1);
         }
         matches
     }
+
+    fn in_js_implementation(&self) -> bool {
+        false
+    }
 }
 
-lazy_static! {
-    static ref REGEXES: HashMap<&'static str, Regex> = {
-        let mut table = HashMap::with_capacity(1);
-        table.insert("recent_year", Regex::new(r"19[0-9]{2}|20[0-9]{2}").unwrap());
-        table
+/// The sequence a token with the given difference between its chars forms.
+fn sequence_pattern(token: &str, delta: i32) -> SequencePattern {
+    let first_chr = token.chars().next().unwrap();
+    let (sequence_name, sequence_space) = if first_chr.is_lowercase() {
+        ("lower", 26)
+    } else if first_chr.is_uppercase() {
+        ("upper", 26)
+    } else if first_chr.is_ascii_digit() {
+        ("digits", 10)
+    } else {
+        // conservatively stick with roman alphabet size.
+        // (this could be improved)
+        ("unicode", 26)
     };
+    SequencePattern {
+        sequence_name,
+        sequence_space,
+        ascending: delta > 0,
+        shifted_count: 0,
+    }
 }
 
-/// a "date" is recognized as:
-///   any 3-tuple that starts or ends with a 2- or 4-digit year,
-///   with 2 or 0 separator chars (1.1.91 or 1191),
-///   maybe zero-padded (01-01-91 vs 1-1-91),
-///   a month between 1 and 12,
-///   a day between 1 and 31.
-///
-/// note: this isn't true date parsing in that "feb 31st" is allowed,
-/// this doesn't check for leap years, etc.
-///
-/// recipe:
-/// start with regex to find maybe-dates, then attempt to map the integers
-/// onto month-day-year to filter the maybe-dates into dates.
-/// finally, remove matches that are substrings of other matches to reduce noise.
-///
-/// note: instead of using a lazy or greedy regex to find many dates over the full string,
-/// this uses a ^...$ regex against every substring of the password -- less performant but leads
-/// to every possible date match.
-struct DateMatch {}
+/// Two sequences of different kinds of characters with their characters alternating,
+/// e.g. "a1b2c3" or "9z8y7x". The whole token can't be a sequence by itself, so this
+/// doesn't overlap with the sequence matcher.
+struct InterleavedMatch {}
 
-impl Matcher for DateMatch {
-    fn get_matches(&self, password: &str, _user_inputs: &HashMap<String, usize>) -> Vec<Match> {
-        let mut matches = Vec::new();
-        let char_indexable = CharIndexableStr::from(password);
+impl Matcher for InterleavedMatch {
+    fn get_matches(&self, password: &str, _custom_words: &CustomWords) -> Vec<Match> {
+        /// Minimum length of a match, two chars of each sequence.
+        const MIN_LENGTH: usize = 4;
+        /// Minimum length of a match with sequences stepping by more than one.
+        const MIN_LENGTH_WIDE_STEPS: usize = 6;
 
-        let password_len = password.chars().count();
-        // dates without separators are between length 4 '1191' and 8 '11111991'
-        if password_len < 4 {
-            return matches;
-        }
-        for i in 0..(password_len - 3) {
-            for j in (i + 3)..(i + 8) {
-                if j >= password_len {
-                    break;
-                }
-                let token_str = char_indexable.char_index(i..j + 1);
-                if !MAYBE_DATE_NO_SEPARATOR_REGEX.is_match(token_str) {
-                    continue;
-                }
-                let token = CharIndexableStr::from(token_str);
-                let mut candidates = Vec::new();
-                for &(k, l) in &DATE_SPLITS[&token.char_count()] {
-                    let ymd = map_ints_to_ymd(
-                        token.char_index(0..k).parse().unwrap(),
-                        token.char_index(k..l).parse().unwrap(),
-                        token.char_index(l..j + 1).parse().unwrap(),
-                    );
-                    if let Some(ymd) = ymd {
-                        candidates.push(ymd);
-                    }
-                }
-                if candidates.is_empty() {
-                    continue;
-                }
-                // at this point: different possible ymd mappings for the same i,j substring.
-                // match the candidate date that likely takes the fewest guesses: a year closest to 2000.
-                // (scoring.REFERENCE_YEAR).
-                //
-                // ie, considering '111504', prefer 11-15-04 to 1-1-1504
-                // (interpreting '04' as 2004)
-                let metric = |candidate: &(i32, i8, i8)| {
-                    (candidate.0 - *super::scoring::REFERENCE_YEAR).abs()
-                };
-                let best_candidate = candidates.iter().min_by_key(|&c| metric(c)).unwrap();
-                let pattern = MatchPattern::Date(DatePattern {
-                    separator: String::new(),
-                    year: best_candidate.0,
-                    month: best_candidate.1,
-                    day: best_candidate.2,
-                });
-                matches.push(Match {
-                    pattern,
-                    token: token_str.to_string(),
-                    i,
-                    j,
-                    ..Match::default()
-                });
+        let chars = password.chars().collect::<Vec<_>>();
+        let delta = |k: usize| chars[k + 2] as i32 - chars[k] as i32;
+        let mut matches = Vec::new();
+        let mut i = 0;
+        while i + MIN_LENGTH <= chars.len() {
+            let deltas = [delta(i), delta(i + 1)];
+            let mut j = i + 3;
+            while j + 1 < chars.len() && delta(j - 1) == deltas[(j - 1 - i) % 2] {
+                j += 1;
             }
-        }
-
-        // dates with separators are between length 6 '1/1/91' and 10 '11/11/1991'
-        if password_len >= 6 {
-            for i in 0..(password_len - 5) {
-                for j in (i + 5)..(i + 10) {
-                    if j >= password_len {
-                        break;
-                    }
-                    let token = char_indexable.char_index(i..j + 1);
-                    let (ymd, separator) = {
-                        let captures = MAYBE_DATE_WITH_SEPARATOR_REGEX.captures(token);
-                        if captures.is_none() {
-                            continue;
-                        }
-                        let captures = captures.unwrap();
-                        if captures[2] != captures[4] {
-                            // Original code uses regex backreferences, Rust doesn't support these.
-                            // Need to manually test that group 2 and 4 are the same
-                            continue;
-                        }
-                        (
-                            map_ints_to_ymd(
-                                captures[1].parse().unwrap(),
-                                captures[3].parse().unwrap(),
-                                captures[5].parse().unwrap(),
-                            ),
-                            captures[2].to_string(),
-                        )
-                    };
-                    if let Some(ymd) = ymd {
-                        let pattern = MatchPattern::Date(DatePattern {
-                            separator,
-                            year: ymd.0,
-                            month: ymd.1,
-                            day: ymd.2,
-                        });
-                        matches.push(Match {
-                            pattern,
-                            token: token.to_string(),
-                            i,
-                            j,
-                            ..Match::default()
-                        });
-                    }
-                }
+            let len = j - i + 1;
+            let token = chars[i..=j].iter().collect::<String>();
+            let strand = |offset: usize| token.chars().skip(offset).step_by(2).collect::<String>();
+            let (first_token, second_token) = (strand(0), strand(1));
+            let first_sequence = sequence_pattern(&first_token, deltas[0]);
+            let second_sequence = sequence_pattern(&second_token, deltas[1]);
+            let is_match = deltas.iter().all(|&d| 0 < d.abs() && d.abs() <= MAX_DELTA)
+                && (len >= MIN_LENGTH_WIDE_STEPS || deltas.iter().all(|&d| d.abs() == 1))
+                && first_sequence.sequence_name != second_sequence.sequence_name;
+            if !is_match {
+                i += 1;
+                continue;
             }
+            matches.push(Match {
+                pattern: MatchPattern::Interleaved(InterleavedPattern {
+                    first_token,
+                    first_sequence,
+                    second_token,
+                    second_sequence,
+                }),
+                i,
+                j,
+                token,
+                ..Match::default()
+            });
+            i = j + 1;
         }
-
         matches
-            .iter()
-            .filter(|&x| !matches.iter().any(|y| *x != *y && y.i <= x.i && y.j >= x.j))
-            .cloned()
-            .collect()
     }
-}
 
-/// Takes three ints and returns them in a (y, m, d) tuple
-fn map_ints_to_ymd(first: u16, second: u16, third: u16) -> Option<(i32, i8, i8)> {
-    // given a 3-tuple, discard if:
-    //   middle int is over 31 (for all ymd formats, years are never allowed in the middle)
-    //   middle int is zero
-    //   any int is over the max allowable year
-    //   any int is over two digits but under the min allowable year
-    //   2 ints are over 31, the max allowable day
-    //   2 ints are zero
-    //   all ints are over 12, the max allowable month
-    if second > 31 || second == 0 {
-        return None;
-    }
-    let mut over_12 = 0;
-    let mut over_31 = 0;
-    let mut zero = 0;
-    for &i in &[first, second, third] {
-        if 99 < i && i < DATE_MIN_YEAR || i > DATE_MAX_YEAR {
-            return None;
-        }
-        if i > 31 {
-            over_31 += 1;
-        }
-        if i > 12 {
-            over_12 += 1;
-        }
-        if i == 0 {
-            zero += 1;
-        }
-    }
-    if over_31 >= 2 || over_12 == 3 || zero >= 2 {
-        return None;
+    fn in_js_implementation(&self) -> bool {
+        false
     }
+}
 
-    // first look for a four digit year: yyyy + daymonth or daymonth + yyyy
-    let possible_year_splits = &[(third, first, second), (first, second, third)];
-    for &(year, second, third) in possible_year_splits {
-        if (DATE_MIN_YEAR..=DATE_MAX_YEAR).contains(&year) {
-            let dm = map_ints_to_md(second, third);
-            if let Some(dm) = dm {
-                return Some((i32::from(year), dm.0, dm.1));
-            } else {
-                // for a candidate that includes a four-digit year,
-                // when the remaining ints don't match to a day and month,
-                // it is not a date.
-                return None;
-            }
-        }
-    }
+struct EmojiMatch {}
+
+impl Matcher for EmojiMatch {
+    fn get_matches(&self, password: &str, _custom_words: &CustomWords) -> Vec<Match> {
+        /// Minimum number of emoji in a keyboard match.
+        const MIN_KEYBOARD_LENGTH: usize = 3;
+
+        let chars = password.chars().collect::<Vec<_>>();
+        let clusters = emoji::clusters(&chars);
+        let bases = clusters
+            .iter()
+            .map(|&(i, j)| emoji::base_emoji(&chars[i..=j]))
+            .collect::<Vec<_>>();
+        let single = |k: usize| match bases[k]
+            .and_then(|c| emoji::COMMON_EMOJI.iter().position(|&x| x == c))
+        {
+            Some(rank) => ("common", rank as u64 + 1),
+            None => ("other", emoji::EMOJI_SPACE),
+        };
+        let keyboard_position =
+            |k: usize| bases[k].and_then(|c| emoji::KEYBOARD_ORDER.iter().position(|&x| x == c));
+        let adjacent = |k: usize| clusters[k].1 + 1 == clusters[k + 1].0;
+
+        let mut matches = Vec::new();
+        let mut push = |first: usize, last: usize, pattern: EmojiPattern| {
+            let (i, j) = (clusters[first].0, clusters[last].1);
+            matches.push(Match {
+                pattern: MatchPattern::Emoji(pattern),
+                i,
+                j,
+                token: chars[i..=j].iter().collect(),
+                ..Match::default()
+            });
+        };
 
-    // given no four-digit year, two digit years are the most flexible int to match, so
-    // try to parse a day-month out of (first, second) or (second, first)
-    for &(year, second, third) in possible_year_splits {
-        let dm = map_ints_to_md(second, third);
-        if let Some(dm) = dm {
-            let year = two_to_four_digit_year(year);
-            return Some((i32::from(year), dm.0, dm.1));
+        for k in 0..clusters.len() {
+            let (emoji_kind, base_guesses) = single(k);
+            push(
+                k,
+                k,
+                EmojiPattern {
+                    emoji_kind,
+                    emoji_count: 1,
+                    base_guesses,
+                    ..EmojiPattern::default()
+                },
+            );
         }
-    }
 
-    None
-}
+        let mut first = 0;
+        while first < clusters.len() {
+            let (i, j) = clusters[first];
+            let mut last = first;
+            while last + 1 < clusters.len() && adjacent(last) && {
+                let (next_i, next_j) = clusters[last + 1];
+                chars[i..=j] == chars[next_i..=next_j]
+            } {
+                last += 1;
+            }
+            if last > first {
+                push(
+                    first,
+                    last,
+                    EmojiPattern {
+                        emoji_kind: "repeat",
+                        emoji_count: last - first + 1,
+                        base_guesses: single(first).1,
+                        ..EmojiPattern::default()
+                    },
+                );
+            }
+            first = last + 1;
+        }
 
-/// Takes two ints and returns them in a (m, d) tuple
-fn map_ints_to_md(first: u16, second: u16) -> Option<(i8, i8)> {
-    for &(d, m) in &[(first, second), (second, first)] {
-        if (1..=31).contains(&d) && (1..=12).contains(&m) {
-            return Some((m as i8, d as i8));
+        let mut first = 0;
+        while first < clusters.len() {
+            let mut last = first;
+            let mut delta = 0;
+            while last + 1 < clusters.len() && adjacent(last) {
+                let step = match (keyboard_position(last), keyboard_position(last + 1)) {
+                    (Some(a), Some(b)) => b as i64 - a as i64,
+                    _ => break,
+                };
+                if step.abs() != 1 || (delta != 0 && step != delta) {
+                    break;
+                }
+                delta = step;
+                last += 1;
+            }
+            if last + 1 - first >= MIN_KEYBOARD_LENGTH {
+                push(
+                    first,
+                    last,
+                    EmojiPattern {
+                        emoji_kind: "keyboard",
+                        emoji_count: last - first + 1,
+                        ascending: delta > 0,
+                        base_guesses: emoji::KEYBOARD_ORDER.len() as u64,
+                    },
+                );
+            }
+            first = last.max(first + 1);
         }
+        matches
     }
-    None
-}
 
-fn two_to_four_digit_year(year: u16) -> u16 {
-    if year > 99 {
-        year
-    } else if year > 50 {
-        // 87 -> 1987
-        year + 1900
-    } else {
-        // 15 -> 2015
-        year + 2000
+    fn in_js_implementation(&self) -> bool {
+        false
     }
 }
 
-const DATE_MIN_YEAR: u16 = 1000;
-const DATE_MAX_YEAR: u16 = 2050;
-lazy_static! {
-    static ref DATE_SPLITS: HashMap<usize, Vec<(usize, usize)>> = {
-        let mut table = HashMap::with_capacity(5);
-        // for length-4 strings, eg 1191 or 9111, two ways to split:
-        // 1 1 91 (2nd split starts at index 1, 3rd at index 2)
-        // 91 1 1
-        table.insert(4, vec![(1, 2), (2, 3)]);
-        // 1 11 91
-        // 11 1 91
-        table.insert(5, vec![(1, 3), (2, 3)]);
-        // 1 1 1991
-        // 11 11 91
-        // 1991 1 1
-        table.insert(6, vec![(1, 2), (2, 4), (4, 5)]);
-        // 1 11 1991
-        // 11 1 1991
-        // 1991 1 11
-        // 1991 11 1
-        table.insert(7, vec![(1, 3), (2, 3), (4, 5), (4, 6)]);
-        // 11 11 1991
-        // 1991 11 11
-        table.insert(8, vec![(2, 4), (4, 6)]);
-        table
-    };
-    static ref MAYBE_DATE_NO_SEPARATOR_REGEX: Regex = Regex::new(r"^[0-9]{4,8}$").unwrap();
-    static ref MAYBE_DATE_WITH_SEPARATOR_REGEX: Regex = Regex::new(r"^([0-9]{1,4})([\s/\\_.-])([0-9]{1,2})([\s/\\_.-])([0-9]{1,4})$").unwrap();
-}
+/// How many years after the reference year dates are matched up to.
+const DATE_YEARS_AHEAD: i32 = 25;
 
 #[cfg(test)]
 mod tests {
     use crate::matching;
     use crate::matching::patterns::*;
-    use crate::matching::Matcher;
-    use std::collections::HashMap;
+    use crate::matching::{CustomWords, Matcher};
+    use std::collections::{BTreeMap, BTreeSet};
 
     #[test]
     fn test_translate() {
         let chr_map = vec![('a', 'A'), ('b', 'B')]
             .into_iter()
-            .collect::<HashMap<char, char>>();
+            .collect::<BTreeMap<char, char>>();
         let test_data = [
             ("a", chr_map.clone(), "A"),
             ("c", chr_map.clone(), "c"),
@@ -871,17 +1733,42 @@ mod tests {
             ("aa", chr_map.clone(), "AA"),
             ("abab", chr_map.clone(), "ABAB"),
             ("", chr_map, ""),
-            ("", HashMap::new(), ""),
-            ("abc", HashMap::new(), "abc"),
+            ("", BTreeMap::new(), ""),
+            ("abc", BTreeMap::new(), "abc"),
         ];
         for &(string, ref map, result) in &test_data {
             assert_eq!(matching::translate(string, map), result);
         }
     }
 
+    #[test]
+    fn test_is_banned_password() {
+        let banned = ["password", "acme", "acme123!"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<BTreeSet<_>>();
+        for password in [
+            "password", "PassWord", "p@$5w0rd", "4CM3", "@cme123!", "4cm3123!",
+        ] {
+            assert!(
+                matching::is_banned_password(password, &banned),
+                "{}",
+                password
+            );
+        }
+        for password in ["password1", "acmecorp", "drowssap", ""] {
+            assert!(
+                !matching::is_banned_password(password, &banned),
+                "{}",
+                password
+            );
+        }
+    }
+
     #[test]
     fn test_dictionary_matches_words_that_contain_other_words() {
-        let matches = (matching::DictionaryMatch {}).get_matches("motherboard", &HashMap::new());
+        let matches =
+            (matching::DictionaryMatch {}).get_matches("motherboard", &CustomWords::default());
         let patterns = ["mother", "motherboard", "board"];
         let ijs = [(0, 5), (0, 10), (6, 10)];
         for (k, &pattern) in patterns.iter().enumerate() {
@@ -899,7 +1786,8 @@ mod tests {
 
     #[test]
     fn test_dictionary_matches_multiple_words_when_they_overlap() {
-        let matches = (matching::DictionaryMatch {}).get_matches("1abcdef12", &HashMap::new());
+        let matches =
+            (matching::DictionaryMatch {}).get_matches("1abcdef12", &CustomWords::default());
         let patterns = ["1abcdef", "abcdef12"];
         let ijs = [(0, 6), (1, 8)];
         for (k, &pattern) in patterns.iter().enumerate() {
@@ -917,7 +1805,7 @@ mod tests {
 
     #[test]
     fn test_dictionary_ignores_uppercasing() {
-        let matches = (matching::DictionaryMatch {}).get_matches("BoaRdZ", &HashMap::new());
+        let matches = (matching::DictionaryMatch {}).get_matches("BoaRdZ", &CustomWords::default());
         let patterns = ["BoaRd"];
         let ijs = [(0, 4)];
         for (k, &pattern) in patterns.iter().enumerate() {
@@ -935,7 +1823,8 @@ mod tests {
 
     #[test]
     fn test_dictionary_identifies_words_surrounded_by_non_words() {
-        let matches = (matching::DictionaryMatch {}).get_matches("asdf1234&*", &HashMap::new());
+        let matches =
+            (matching::DictionaryMatch {}).get_matches("asdf1234&*", &CustomWords::default());
         let patterns = ["asdf", "asdf1234"];
         let ijs = [(0, 3), (0, 7)];
         for (k, &pattern) in patterns.iter().enumerate() {
@@ -951,14 +1840,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_dictionary_matches_use_best_rank() {
+        use crate::frequency_lists::DictionaryType;
+        // "yankees" is a common password, and the most common sports team
+        let matches = (matching::DictionaryMatch {})
+            .get_matches("yankees", &CustomWords::default())
+            .into_iter()
+            .filter(|m| (m.i, m.j) == (0, 6))
+            .collect::<Vec<_>>();
+        assert_eq!(matches.len(), 1);
+        let p = if let MatchPattern::Dictionary(ref p) = matches[0].pattern {
+            p
+        } else {
+            panic!("Wrong match pattern")
+        };
+        if cfg!(feature = "sports-teams") {
+            assert_eq!(
+                (p.dictionary_name, p.rank),
+                (DictionaryType::SportsTeams, 1)
+            );
+        } else {
+            assert_eq!(p.dictionary_name, DictionaryType::Passwords);
+        }
+
+        // the JavaScript implementation has no word list packs
+        let custom_words = CustomWords {
+            js_compatible: true,
+            ..CustomWords::default()
+        };
+        let matches = (matching::DictionaryMatch {}).get_matches("yankees", &custom_words);
+        assert!(matches.iter().all(|m| match m.pattern {
+            MatchPattern::Dictionary(ref p) => !p.dictionary_name.is_word_list_pack(),
+            _ => true,
+        }));
+    }
+
     #[test]
     fn test_dictionary_matches_user_inputs() {
         use crate::frequency_lists::DictionaryType;
-        let user_inputs = [("bejeebus".to_string(), 1)]
-            .iter()
-            .cloned()
-            .collect::<HashMap<String, usize>>();
-        let matches = (matching::DictionaryMatch {}).get_matches("bejeebus", &user_inputs);
+        let custom_words = CustomWords {
+            user_inputs: [("bejeebus".to_string(), 1)].into_iter().collect(),
+            ..CustomWords::default()
+        };
+        let matches = (matching::DictionaryMatch {}).get_matches("bejeebus", &custom_words);
         let patterns = ["bejeebus"];
         let ijs = [(0, 7)];
         for (k, &pattern) in patterns.iter().enumerate() {
@@ -977,7 +1902,8 @@ mod tests {
 
     #[test]
     fn test_dictionary_matches_against_reversed_words() {
-        let matches = (matching::ReverseDictionaryMatch {}).get_matches("rehtom", &HashMap::new());
+        let matches =
+            (matching::ReverseDictionaryMatch {}).get_matches("rehtom", &CustomWords::default());
         let patterns = ["rehtom"];
         let ijs = [(0, 5)];
         for (k, &pattern) in patterns.iter().enumerate() {
@@ -990,15 +1916,15 @@ mod tests {
             } else {
                 panic!("Wrong match pattern")
             };
-            assert_eq!(p.reversed, true);
+            assert!(p.reversed);
         }
     }
 
     #[test]
     fn test_reduces_l33t_table_to_only_relevant_substitutions() {
         let test_data = vec![
-            ("", HashMap::new()),
-            ("a", HashMap::new()),
+            ("", BTreeMap::new()),
+            ("a", BTreeMap::new()),
             ("4", vec![('a', vec!['4'])].into_iter().collect()),
             ("4@", vec![('a', vec!['4', '@'])].into_iter().collect()),
             (
@@ -1019,38 +1945,45 @@ mod tests {
     }
 
     #[test]
-    fn test_enumerates_sets_of_l33t_subs_a_password_might_be_using() {
+    fn test_enumerates_readings_of_l33t_characters() {
         let test_data = vec![
-            (HashMap::new(), vec![HashMap::new()]),
+            (BTreeMap::new(), vec![BTreeMap::new()]),
             (
                 vec![('a', vec!['@'])].into_iter().collect(),
                 vec![vec![('@', 'a')].into_iter().collect()],
             ),
             (
                 vec![('a', vec!['@', '4'])].into_iter().collect(),
-                vec![
-                    vec![('@', 'a')].into_iter().collect(),
-                    vec![('4', 'a')].into_iter().collect(),
-                ],
+                vec![vec![('@', 'a'), ('4', 'a')].into_iter().collect()],
             ),
             (
                 vec![('a', vec!['@', '4']), ('c', vec!['('])]
                     .into_iter()
                     .collect(),
+                vec![vec![('@', 'a'), ('4', 'a'), ('(', 'c')]
+                    .into_iter()
+                    .collect()],
+            ),
+            (
+                vec![('i', vec!['1']), ('l', vec!['1', '7']), ('t', vec!['7'])]
+                    .into_iter()
+                    .collect(),
                 vec![
-                    vec![('@', 'a'), ('(', 'c')].into_iter().collect(),
-                    vec![('4', 'a'), ('(', 'c')].into_iter().collect(),
+                    vec![('1', 'i'), ('7', 'l')].into_iter().collect(),
+                    vec![('1', 'i'), ('7', 't')].into_iter().collect(),
+                    vec![('1', 'l'), ('7', 'l')].into_iter().collect(),
+                    vec![('1', 'l'), ('7', 't')].into_iter().collect(),
                 ],
             ),
         ];
-        for (table, subs) in test_data {
-            assert_eq!(matching::enumerate_l33t_replacements(&table), subs);
+        for (table, readings) in test_data {
+            assert_eq!(matching::l33t_readings(&table), readings);
         }
     }
 
     #[test]
     fn test_dictionary_matches_against_l33t_words() {
-        let matches = (matching::L33tMatch {}).get_matches("m0th3r", &HashMap::new());
+        let matches = (matching::L33tMatch {}).get_matches("m0th3r", &CustomWords::default());
         let patterns = ["m0th3r"];
         let ijs = [(0, 5)];
         for (k, &pattern) in patterns.iter().enumerate() {
@@ -1063,13 +1996,13 @@ mod tests {
             } else {
                 panic!("Wrong match pattern")
             };
-            assert_eq!(p.l33t, true);
+            assert!(p.l33t);
         }
     }
 
     #[test]
     fn test_dictionary_matches_overlapping_l33ted_words() {
-        let matches = (matching::L33tMatch {}).get_matches("p@ssw0rd", &HashMap::new());
+        let matches = (matching::L33tMatch {}).get_matches("p@ssw0rd", &CustomWords::default());
         let patterns = ["p@ss", "@ssw0rd"];
         let ijs = [(0, 3), (1, 7)];
         for (k, &pattern) in patterns.iter().enumerate() {
@@ -1082,26 +2015,69 @@ mod tests {
             } else {
                 panic!("Wrong match pattern")
             };
-            assert_eq!(p.l33t, true);
+            assert!(p.l33t);
+        }
+    }
+
+    #[test]
+    fn test_dictionary_matches_adjacent_l33ted_words() {
+        for (password, words) in [
+            (
+                "P@55w0rdL3tM31n",
+                [("P@55w0rd", "password"), ("L3tM31n", "letmein")],
+            ),
+            // a l33ted two ways, and 1 read as i and as l
+            (
+                "p@55w0rd4ng3l",
+                [("p@55w0rd", "password"), ("4ng3l", "angel")],
+            ),
+            (
+                "5unsh1n3w0r1d",
+                [("5unsh1n3", "sunshine"), ("w0r1d", "world")],
+            ),
+        ] {
+            let matches = (matching::L33tMatch {}).get_matches(password, &CustomWords::default());
+            for (token, word) in words {
+                let patterns = matches
+                    .iter()
+                    .filter(|m| m.token == token)
+                    .filter_map(|m| match m.pattern {
+                        MatchPattern::Dictionary(ref p) if p.matched_word == word => Some(p),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                assert!(!patterns.is_empty(), "{} in {}", word, password);
+                // once per dictionary however many readings it's found in, with only its own
+                // substitutions
+                let dictionaries = patterns
+                    .iter()
+                    .map(|p| p.dictionary_name)
+                    .collect::<BTreeSet<_>>();
+                assert_eq!(dictionaries.len(), patterns.len());
+                for p in patterns {
+                    let sub = p.sub.as_ref().unwrap();
+                    assert!(sub.keys().all(|&c| token.contains(c)), "{:?}", sub);
+                }
+            }
         }
     }
 
     #[test]
     fn test_doesnt_match_when_multiple_l33t_subs_needed_for_same_letter() {
-        let matches = (matching::L33tMatch {}).get_matches("p4@ssword", &HashMap::new());
+        let matches = (matching::L33tMatch {}).get_matches("p4@ssword", &CustomWords::default());
         assert!(!matches.iter().any(|m| &m.token == "p4@ssword"));
     }
 
     #[test]
     fn test_doesnt_match_single_character_l33ted_words() {
-        let matches = (matching::L33tMatch {}).get_matches("4 ( @", &HashMap::new());
+        let matches = (matching::L33tMatch {}).get_matches("4 ( @", &CustomWords::default());
         assert!(matches.is_empty());
     }
 
     #[test]
     fn test_doesnt_match_1_and_2_char_spatial_patterns() {
         for password in &["", "/", "qw", "*/"] {
-            let result = (matching::SpatialMatch {}).get_matches(password, &HashMap::new());
+            let result = (matching::SpatialMatch {}).get_matches(password, &CustomWords::default());
             assert!(!result.into_iter().any(|m| m.token == *password));
         }
     }
@@ -1110,7 +2086,7 @@ mod tests {
     fn test_matches_spatial_patterns_surrounded_by_non_spatial_patterns() {
         let password = "6tfGHJ";
         let m = (matching::SpatialMatch {})
-            .get_matches(password, &HashMap::new())
+            .get_matches(password, &CustomWords::default())
             .into_iter()
             .find(|m| m.token == *password)
             .unwrap();
@@ -1124,6 +2100,62 @@ mod tests {
         assert_eq!(p.shifted_count, 3);
     }
 
+    #[test]
+    fn test_spatial_matches_follow_js_graph_order() {
+        let graphs = |custom_words: &CustomWords| {
+            (matching::SpatialMatch {})
+                .get_matches("12345", custom_words)
+                .into_iter()
+                .filter(|m| m.token == "12345")
+                .map(|m| match m.pattern {
+                    MatchPattern::Spatial(p) => p.graph,
+                    _ => panic!("Wrong match pattern"),
+                })
+                .collect::<Vec<_>>()
+        };
+        let default_order = graphs(&CustomWords::default());
+        // leaving out the extra keyboards, which the JavaScript implementation doesn't have
+        let default_order = default_order
+            .iter()
+            .map(String::as_str)
+            .filter(|graph| matching::JS_GRAPH_ORDER.contains(graph))
+            .collect::<Vec<_>>();
+        assert_eq!(default_order, ["dvorak", "qwerty"]);
+        let custom_words = CustomWords {
+            scoring: crate::scoring::ScoringParams::new().js_match_order(true),
+            ..CustomWords::default()
+        };
+        assert_eq!(graphs(&custom_words), ["qwerty", "dvorak"]);
+    }
+
+    #[cfg(feature = "extra-keyboards")]
+    #[test]
+    fn test_spatial_matches_on_extra_keyboards() {
+        // a diagonal of keys on grids, but no pattern on staggered rows
+        let matches = (matching::SpatialMatch {}).get_matches("qSc", &CustomWords::default());
+        let graphs = matches
+            .iter()
+            .map(|m| match &m.pattern {
+                MatchPattern::Spatial(p) => {
+                    assert_eq!((p.turns, p.shifted_count), (1, 1));
+                    p.graph.as_str()
+                }
+                _ => panic!("Wrong match pattern"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(graphs, ["ergodox", "planck"]);
+        assert!(crate::zxcvbn("qSc", &[])
+            .model_version()
+            .ends_with("+ergodox+planck"));
+
+        // the halves of a split keyboard are apart
+        let matches = (matching::SpatialMatch {}).get_matches("tgby", &CustomWords::default());
+        assert!(matches.iter().all(|m| match &m.pattern {
+            MatchPattern::Spatial(p) => p.graph != "ergodox" || m.token != "tgby",
+            _ => true,
+        }));
+    }
+
     #[test]
     fn test_matches_pattern_as_a_keyboard_pattern() {
         let test_data = vec![
@@ -1141,9 +2173,14 @@ mod tests {
             ("*-632.0214", "mac_keypad", 9, 0),
             ("aoEP%yIxkjq:", "dvorak", 4, 5),
             (";qoaOQ:Aoq;a", "dvorak", 11, 4),
+            ("2580", "pin_pad", 1, 0),
+            ("1478963", "pin_pad", 3, 0),
+            ("*0#", "phone_pad", 1, 0),
+            ("#96", "phone_pad", 1, 0),
         ];
         for (password, keyboard, turns, shifts) in test_data {
-            let matches = (matching::SpatialMatch {}).get_matches(password, &HashMap::new());
+            let matches =
+                (matching::SpatialMatch {}).get_matches(password, &CustomWords::default());
             let m = matches
                 .into_iter()
                 .find(|m| {
@@ -1165,20 +2202,100 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_matches_keyboard_regions() {
+        let test_data = [
+            ("qwertyuiop", "qwerty", "row"),
+            ("1qaz", "qwerty", "column"),
+            ("1qaz2wsx", "qwerty", "block"),
+            ("zxcasdqwe", "qwerty", "block"),
+            ("741852963", "keypad", "block"),
+            ("2580", "phone_pad", "column"),
+        ];
+        for (password, keyboard, region) in test_data {
+            let matches =
+                (matching::SpatialMatch {}).get_matches(password, &CustomWords::default());
+            assert!(
+                matches.iter().any(|m| match m.pattern {
+                    MatchPattern::Spatial(ref p) =>
+                        m.token == password && p.graph == keyboard && p.region == Some(region),
+                    _ => false,
+                }),
+                "{}",
+                password
+            );
+        }
+        let matches =
+            (matching::SpatialMatch {}).get_matches("xqwertyuiopx", &CustomWords::default());
+        let m = matches
+            .iter()
+            .find(|m| matches!(m.pattern, MatchPattern::Spatial(ref p) if p.region.is_some()))
+            .unwrap();
+        assert_eq!((m.i, m.j), (1, 10));
+    }
+
+    #[test]
+    fn test_matches_interleaved_sequences() {
+        let test_data = [
+            ("a1b2c3d4", "abcd", "1234"),
+            ("1a2b3c", "123", "abc"),
+            ("z9y8x7", "zyx", "987"),
+            ("aAbBcC", "abc", "ABC"),
+        ];
+        for (password, first, second) in test_data {
+            let matches =
+                (matching::InterleavedMatch {}).get_matches(password, &CustomWords::default());
+            assert_eq!(matches.len(), 1, "{}", password);
+            let p = if let MatchPattern::Interleaved(ref p) = matches[0].pattern {
+                p
+            } else {
+                panic!("Wrong match pattern")
+            };
+            assert_eq!((matches[0].i, matches[0].j), (0, password.len() - 1));
+            assert_eq!(
+                (p.first_token.as_str(), p.second_token.as_str()),
+                (first, second)
+            );
+        }
+
+        let matches =
+            (matching::InterleavedMatch {}).get_matches("xa1b2c3!", &CustomWords::default());
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].token, "a1b2c3");
+
+        // a sequence by itself, repeats and unrelated characters aren't interleaved
+        for password in ["abcdef", "acegik", "a1a1a1", "a1x7q0"] {
+            let matches =
+                (matching::InterleavedMatch {}).get_matches(password, &CustomWords::default());
+            assert!(matches.is_empty(), "{}", password);
+        }
+    }
+
     #[test]
     fn test_doesnt_match_len_1_sequences() {
         for &password in &["", "a", "1"] {
             assert_eq!(
-                (matching::SequenceMatch {}).get_matches(password, &HashMap::new()),
+                (matching::SequenceMatch {}).get_matches(password, &CustomWords::default()),
                 Vec::new()
             );
         }
     }
 
+    #[test]
+    fn test_doesnt_match_repeated_chars_as_sequences() {
+        let matches = (matching::SequenceMatch {}).get_matches("aac", &CustomWords::default());
+        assert_eq!(matches, Vec::new());
+        let matches = (matching::SequenceMatch {}).get_matches("aaab", &CustomWords::default());
+        assert_eq!(
+            matches.iter().map(|m| (m.i, m.j)).collect::<Vec<_>>(),
+            [(2, 3)]
+        );
+    }
+
     #[test]
     fn test_matches_overlapping_sequences() {
         let password = "abcbabc";
-        let matches = (matching::SequenceMatch {}).get_matches(password, &HashMap::new());
+        let matches = (matching::SequenceMatch {}).get_matches(password, &CustomWords::default());
         for &(pattern, i, j, ascending) in &[
             ("abc", 0, 2, true),
             ("cba", 2, 4, false),
@@ -1200,7 +2317,7 @@ mod tests {
     #[test]
     fn test_matches_embedded_sequence_patterns() {
         let password = "!jihg22";
-        let matches = (matching::SequenceMatch {}).get_matches(password, &HashMap::new());
+        let matches = (matching::SequenceMatch {}).get_matches(password, &CustomWords::default());
         let m = matches.iter().find(|m| &m.token == "jihg").unwrap();
         let p = if let MatchPattern::Sequence(ref p) = m.pattern {
             p
@@ -1208,7 +2325,45 @@ mod tests {
             panic!("Wrong match pattern")
         };
         assert_eq!(p.sequence_name, "lower");
-        assert_eq!(p.ascending, false);
+        assert!(!p.ascending);
+    }
+
+    #[test]
+    fn test_matches_emoji_patterns() {
+        let emoji_matches = |password: &str| {
+            (matching::EmojiMatch {})
+                .get_matches(password, &CustomWords::default())
+                .into_iter()
+                .map(|m| match m.pattern {
+                    MatchPattern::Emoji(p) => (m.token, p.emoji_kind, p.emoji_count, p.ascending),
+                    _ => panic!("Wrong match pattern"),
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            emoji_matches("a🔥🔥b😂"),
+            [
+                ("🔥".to_string(), "common", 1, false),
+                ("🔥".to_string(), "common", 1, false),
+                ("😂".to_string(), "common", 1, false),
+                ("🔥🔥".to_string(), "repeat", 2, false),
+            ]
+        );
+        assert_eq!(
+            emoji_matches("🦑👍🏽👍🏽")[3..],
+            [("👍🏽👍🏽".to_string(), "repeat", 2, false)]
+        );
+        assert_eq!(emoji_matches("🦑")[0].1, "other");
+        assert_eq!(
+            emoji_matches("😀😃😄😁😄😃")[6..],
+            [
+                ("😀😃😄😁".to_string(), "keyboard", 4, true),
+                ("😁😄😃".to_string(), "keyboard", 3, false),
+            ]
+        );
+        // Keyboard neighbours have to be typed next to each other.
+        assert_eq!(emoji_matches("😀😃x😄").len(), 3);
+        assert!(emoji_matches("password").is_empty());
     }
 
     #[test]
@@ -1229,7 +2384,8 @@ mod tests {
             ("97531", "digits", false),
         ];
         for &(pattern, name, is_ascending) in &test_data {
-            let matches = (matching::SequenceMatch {}).get_matches(pattern, &HashMap::new());
+            let matches =
+                (matching::SequenceMatch {}).get_matches(pattern, &CustomWords::default());
             let m = matches.iter().find(|m| m.token == *pattern).unwrap();
             assert_eq!(m.i, 0);
             assert_eq!(m.j, pattern.len() - 1);
@@ -1247,7 +2403,7 @@ mod tests {
     fn test_doesnt_match_len_1_repeat_patterns() {
         for &password in &["", "#"] {
             assert_eq!(
-                (matching::RepeatMatch {}).get_matches(password, &HashMap::new()),
+                (matching::RepeatMatch {}).get_matches(password, &CustomWords::default()),
                 Vec::new()
             );
         }
@@ -1257,7 +2413,7 @@ mod tests {
     fn test_matches_embedded_repeat_patterns() {
         let password = "y4@&&&&&u%7";
         let (i, j) = (3, 7);
-        let matches = (matching::RepeatMatch {}).get_matches(password, &HashMap::new());
+        let matches = (matching::RepeatMatch {}).get_matches(password, &CustomWords::default());
         let m = matches.iter().find(|m| &m.token == "&&&&&").unwrap();
         assert_eq!(m.i, i);
         assert_eq!(m.j, j);
@@ -1274,7 +2430,8 @@ mod tests {
         for len in 3..13 {
             for &chr in &['a', 'Z', '4', '&'] {
                 let password = (0..len).map(|_| chr).collect::<String>();
-                let matches = (matching::RepeatMatch {}).get_matches(&password, &HashMap::new());
+                let matches =
+                    (matching::RepeatMatch {}).get_matches(&password, &CustomWords::default());
                 let m = matches
                     .iter()
                     .find(|m| {
@@ -1295,7 +2452,7 @@ mod tests {
     #[test]
     fn test_multiple_adjacent_repeats() {
         let password = "BBB1111aaaaa@@@@@@";
-        let matches = (matching::RepeatMatch {}).get_matches(password, &HashMap::new());
+        let matches = (matching::RepeatMatch {}).get_matches(password, &CustomWords::default());
         let test_data = [
             ("BBB", 0, 2),
             ("1111", 3, 6),
@@ -1318,7 +2475,7 @@ mod tests {
     #[test]
     fn test_multiple_non_adjacent_repeats() {
         let password = "2818BBBbzsdf1111@*&@!aaaaaEUDA@@@@@@1729";
-        let matches = (matching::RepeatMatch {}).get_matches(password, &HashMap::new());
+        let matches = (matching::RepeatMatch {}).get_matches(password, &CustomWords::default());
         let test_data = [
             ("BBB", 4, 6),
             ("1111", 12, 15),
@@ -1342,7 +2499,7 @@ mod tests {
     fn test_multiple_character_repeats() {
         let password = "abab";
         let (i, j) = (0, 3);
-        let matches = (matching::RepeatMatch {}).get_matches(password, &HashMap::new());
+        let matches = (matching::RepeatMatch {}).get_matches(password, &CustomWords::default());
         let m = matches.iter().find(|m| m.token == *password).unwrap();
         assert_eq!(m.i, i);
         assert_eq!(m.j, j);
@@ -1358,7 +2515,7 @@ mod tests {
     fn test_matches_longest_repeat() {
         let password = "aabaab";
         let (i, j) = (0, 5);
-        let matches = (matching::RepeatMatch {}).get_matches(password, &HashMap::new());
+        let matches = (matching::RepeatMatch {}).get_matches(password, &CustomWords::default());
         let m = matches.iter().find(|m| m.token == *password).unwrap();
         assert_eq!(m.i, i);
         assert_eq!(m.j, j);
@@ -1374,7 +2531,7 @@ mod tests {
     fn test_identifies_simplest_repeat() {
         let password = "abababab";
         let (i, j) = (0, 7);
-        let matches = (matching::RepeatMatch {}).get_matches(password, &HashMap::new());
+        let matches = (matching::RepeatMatch {}).get_matches(password, &CustomWords::default());
         let m = matches.iter().find(|m| m.token == *password).unwrap();
         assert_eq!(m.i, i);
         assert_eq!(m.j, j);
@@ -1387,198 +2544,323 @@ mod tests {
     }
 
     #[test]
-    fn test_identifies_repeat_with_multibyte_utf8() {
-        let password = "x\u{1F431}\u{1F436}\u{1F431}\u{1F436}";
-        let (i, j) = (1, 4);
-        let matches = (matching::RepeatMatch {}).get_matches(password, &HashMap::new());
-        let m = matches.iter().find(|m| m.token == password[1..]).unwrap();
-        assert_eq!(m.i, i);
-        assert_eq!(m.j, j);
+    fn test_scores_repeat_base_token_recursively() {
+        let password = "hgfdhgfdhgfd";
+        let matches = (matching::RepeatMatch {}).get_matches(password, &CustomWords::default());
+        let m = matches.iter().find(|m| m.token == *password).unwrap();
         let p = if let MatchPattern::Repeat(ref p) = m.pattern {
             p
         } else {
             panic!("Wrong match pattern")
         };
-        assert_eq!(p.base_token, "\u{1F431}\u{1F436}".to_string());
+        assert_eq!(p.base_token, "hgfd");
+        assert_eq!(p.repeat_count, 3);
+        let base_variants = p
+            .base_matches
+            .iter()
+            .map(|m| m.pattern.variant())
+            .collect::<Vec<_>>();
+        assert_eq!(base_variants, ["spatial"]);
+        let base_analysis = crate::scoring::most_guessable_match_sequence(
+            "hgfd",
+            &matching::omnimatch("hgfd", &CustomWords::default()),
+            &crate::scoring::ScoringParams::default(),
+        );
+        assert_eq!(p.base_guesses, base_analysis.guesses);
+        // far fewer guesses than bruteforcing the four characters
+        assert!(p.base_guesses < 10_000);
     }
 
     #[test]
-    fn test_regex_matching() {
-        let test_data = [("1922", "recent_year"), ("2017", "recent_year")];
-        for &(pattern, name) in &test_data {
-            let matches = (matching::RegexMatch {}).get_matches(pattern, &HashMap::new());
-            let m = matches.iter().find(|m| m.token == *pattern).unwrap();
-            assert_eq!(m.i, 0);
-            assert_eq!(m.j, pattern.len() - 1);
-            let p = if let MatchPattern::Regex(ref p) = m.pattern {
-                p
-            } else {
-                panic!("Wrong match pattern")
-            };
-            assert_eq!(p.regex_name, name);
+    fn test_matches_mirrored_text() {
+        let password = "racecar123321";
+        let matches = (matching::MirrorMatch {}).get_matches(password, &CustomWords::default());
+        let found = matches
+            .iter()
+            .map(|m| {
+                let p = if let MatchPattern::Mirror(ref p) = m.pattern {
+                    p
+                } else {
+                    panic!("Wrong match pattern")
+                };
+                (m.token.as_str(), m.i, m.j, p.base_token.as_str())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(found, [("racecar", 0, 6, "race"), ("123321", 7, 12, "123")]);
+
+        let matches = (matching::MirrorMatch {}).get_matches("abc|cba", &CustomWords::default());
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].token, "abc|cba");
+
+        for password in ["aba", "aaaa", "abcd"] {
+            let matches = (matching::MirrorMatch {}).get_matches(password, &CustomWords::default());
+            assert!(matches.is_empty(), "{}", password);
         }
     }
 
     #[test]
-    fn test_date_matching_with_various_separators() {
-        let separators = ["", " ", "-", "/", "\\", "_", "."];
-        for sep in &separators {
-            let password = format!("13{}2{}1921", sep, sep);
-            let matches = (matching::DateMatch {}).get_matches(&password, &HashMap::new());
-            let m = matches.iter().find(|m| m.token == password).unwrap();
-            assert_eq!(m.i, 0);
-            assert_eq!(m.j, password.len() - 1);
-            let p = if let MatchPattern::Date(ref p) = m.pattern {
-                p
-            } else {
-                panic!("Wrong match pattern")
-            };
-            assert_eq!(p.year, 1921);
-            assert_eq!(p.month, 2);
-            assert_eq!(p.day, 13);
-            assert_eq!(p.separator, sep.to_string());
+    fn test_longest_palindromes() {
+        // every middle, against growing each palindrome one char at a time
+        for password in [
+            "racecar123321",
+            "abababab",
+            "abbaabba",
+            "xyzzyxxyzzyx",
+            "a",
+            "",
+        ] {
+            let chars = password.chars().collect::<Vec<_>>();
+            let mut expected = Vec::new();
+            for odd in [1, 0] {
+                for m in 0..chars.len() {
+                    let mut r = 0;
+                    while m > r
+                        && m + r + odd < chars.len()
+                        && chars[m - r - 1] == chars[m + r + odd]
+                    {
+                        r += 1;
+                    }
+                    expected.push((m - r, m + r + odd));
+                }
+            }
+            assert_eq!(
+                matching::longest_palindromes(&chars),
+                expected,
+                "{}",
+                password
+            );
         }
     }
 
     #[test]
-    fn test_date_matches_year_closest_to_reference_year() {
-        let now = time::OffsetDateTime::now_utc();
-        let password = format!("1115{}", now.year() % 100);
-        let matches = (matching::DateMatch {}).get_matches(&password, &HashMap::new());
-        let m = matches.iter().find(|m| m.token == password).unwrap();
-        assert_eq!(m.i, 0);
-        assert_eq!(m.j, password.len() - 1);
-        let p = if let MatchPattern::Date(ref p) = m.pattern {
-            p
-        } else {
-            panic!("Wrong match pattern")
-        };
-        assert_eq!(p.year, now.year());
-        assert_eq!(p.month, 11);
-        assert_eq!(p.day, 15);
-        assert_eq!(p.separator, "".to_string());
+    fn test_mirror_matches_only_maximal_palindromes() {
+        let password = "ab".repeat(50);
+        let matches = (matching::MirrorMatch {}).get_matches(&password, &CustomWords::default());
+        let spans = matches.iter().map(|m| (m.i, m.j)).collect::<Vec<_>>();
+        assert_eq!(spans, [(0, 98), (1, 99)]);
     }
 
     #[test]
-    fn test_date_matches() {
-        let test_data = [(1, 1, 1999), (11, 8, 2000), (9, 12, 2005), (22, 11, 1551)];
-        for &(day, month, year) in &test_data {
-            let password = format!("{}{}{}", year, month, day);
-            let matches = (matching::DateMatch {}).get_matches(&password, &HashMap::new());
-            let m = matches.iter().find(|m| m.token == password).unwrap();
-            assert_eq!(m.i, 0);
-            assert_eq!(m.j, password.len() - 1);
-            let p = if let MatchPattern::Date(ref p) = m.pattern {
+    fn test_matches_shift_repeats() {
+        for (password, token, base_token, reversed) in [
+            ("123!@#", "123!@#", "123", false),
+            ("x1qaz!QAZ", "1qaz!QAZ", "1qaz", false),
+            ("123456^%$#@!", "123456^%$#@!", "123456", true),
+            ("!@#$1234", "!@#$1234", "!@#$", false),
+            ("passwordPASSWORD", "passwordPASSWORD", "password", false),
+        ] {
+            let matches =
+                (matching::ShiftRepeatMatch {}).get_matches(password, &CustomWords::default());
+            let m = &matches[0];
+            assert_eq!(m.token, token);
+            let p = if let MatchPattern::ShiftRepeat(ref p) = m.pattern {
                 p
             } else {
                 panic!("Wrong match pattern")
             };
-            assert_eq!(p.year, year);
-            assert_eq!(p.separator, "".to_string());
+            assert_eq!(p.base_token, base_token);
+            assert_eq!(p.reversed, reversed);
         }
-        for &(day, month, year) in &test_data {
-            let password = format!("{}.{}.{}", year, month, day);
-            let matches = (matching::DateMatch {}).get_matches(&password, &HashMap::new());
-            let m = matches.iter().find(|m| m.token == password).unwrap();
-            assert_eq!(m.i, 0);
-            assert_eq!(m.j, password.len() - 1);
-            let p = if let MatchPattern::Date(ref p) = m.pattern {
+
+        for password in ["12!@", "123!@$", "123123", "abc!@#"] {
+            let matches =
+                (matching::ShiftRepeatMatch {}).get_matches(password, &CustomWords::default());
+            assert!(matches.is_empty(), "{}", password);
+        }
+    }
+
+    #[test]
+    fn test_matches_echoes() {
+        for (password, token, base_token, echo_count) in [
+            ("ppaasssswwoorrdd", "ppaasssswwoorrdd", "password", 2),
+            ("ppaasswwoorrdd", "ppaasswwoorrdd", "password", 2),
+            ("1ddrraaggoonn!", "ddrraaggoonn", "dragon", 2),
+            ("aaabbbccc", "aaabbbccc", "abc", 3),
+            ("hheelllloo", "hheelllloo", "hello", 2),
+        ] {
+            let matches = (matching::EchoMatch {}).get_matches(password, &CustomWords::default());
+            let m = matches.iter().find(|m| m.token == token).unwrap();
+            let p = if let MatchPattern::Echo(ref p) = m.pattern {
                 p
             } else {
                 panic!("Wrong match pattern")
             };
-            assert_eq!(p.year, year);
-            assert_eq!(p.separator, ".".to_string());
+            assert_eq!(p.base_token, base_token);
+            assert_eq!(p.echo_count, echo_count);
+        }
+
+        for password in ["aabb", "aaaaaa", "abcabc", "password"] {
+            let matches = (matching::EchoMatch {}).get_matches(password, &CustomWords::default());
+            assert!(matches.is_empty(), "{}", password);
         }
     }
 
     #[test]
-    fn test_matching_zero_padded_dates() {
-        let password = "02/02/02";
-        let matches = (matching::DateMatch {}).get_matches(password, &HashMap::new());
-        let m = matches.iter().find(|m| m.token == password).unwrap();
-        assert_eq!(m.i, 0);
-        assert_eq!(m.j, password.len() - 1);
-        let p = if let MatchPattern::Date(ref p) = m.pattern {
-            p
-        } else {
-            panic!("Wrong match pattern")
-        };
-        assert_eq!(p.year, 2002);
-        assert_eq!(p.month, 2);
-        assert_eq!(p.day, 2);
-        assert_eq!(p.separator, "/".to_string());
+    fn test_with_double_letters() {
+        let doubled = |base: &str| matching::with_double_letters(&base.chars().collect::<Vec<_>>());
+        assert_eq!(doubled("pasword").as_deref(), Some("password"));
+        assert_eq!(doubled("Helo1").as_deref(), Some("Hello1"));
+        assert_eq!(
+            doubled("paswordpasword").as_deref(),
+            Some("passwordpassword")
+        );
+        assert_eq!(doubled("xkqz"), None);
     }
 
     #[test]
-    fn test_matching_embedded_dates() {
-        let password = "a1/1/91!";
-        let matches = (matching::DateMatch {}).get_matches(password, &HashMap::new());
-        let m = matches.iter().find(|m| &m.token == "1/1/91").unwrap();
-        assert_eq!(m.i, 1);
-        assert_eq!(m.j, password.len() - 2);
-        let p = if let MatchPattern::Date(ref p) = m.pattern {
-            p
-        } else {
-            panic!("Wrong match pattern")
+    fn test_shift_repeats_do_not_overlap() {
+        let password = "aA".repeat(50);
+        let matches =
+            (matching::ShiftRepeatMatch {}).get_matches(&password, &CustomWords::default());
+        let spans = matches.iter().map(|m| (m.i, m.j)).collect::<Vec<_>>();
+        assert_eq!(spans, [(0, 99)]);
+    }
+
+    #[test]
+    fn test_phone_parts() {
+        let parts = |user_input: &str| {
+            matching::phone_parts(user_input)
+                .into_iter()
+                .map(|(part, digits)| format!("{}:{}", part, digits))
+                .collect::<Vec<_>>()
         };
-        assert_eq!(p.year, 1991);
-        assert_eq!(p.month, 1);
-        assert_eq!(p.day, 1);
-        assert_eq!(p.separator, "/".to_string());
+        assert_eq!(
+            parts("+1 (555) 123-4567"),
+            [
+                "number:15551234567",
+                "number:5551234567",
+                "formatted:+1 (555) 123-4567",
+                "local:1234567",
+                "area_code:555",
+                "last_four:4567",
+            ]
+        );
+        assert_eq!(
+            parts("5551234567"),
+            [
+                "number:5551234567",
+                "local:1234567",
+                "area_code:555",
+                "last_four:4567",
+            ]
+        );
+        assert_eq!(
+            parts("+44 20 7946 0958"),
+            [
+                "number:442079460958",
+                "formatted:+44 20 7946 0958",
+                "local:9460958",
+                "last_four:0958",
+            ]
+        );
+        for user_input in [
+            "jsmith",
+            "1990-05-12",
+            "12345678",
+            "555-1234 ext 2",
+            "1+5551234567",
+        ] {
+            assert!(parts(user_input).is_empty(), "{}", user_input);
+        }
     }
 
     #[test]
-    fn test_matching_overlapping_dates() {
-        let password = "12/20/1991.12.20";
-        let matches = (matching::DateMatch {}).get_matches(password, &HashMap::new());
-        let m = matches.iter().find(|m| &m.token == "12/20/1991").unwrap();
-        assert_eq!(m.i, 0);
-        assert_eq!(m.j, 9);
-        let p = if let MatchPattern::Date(ref p) = m.pattern {
-            p
-        } else {
-            panic!("Wrong match pattern")
+    fn test_matches_phone_numbers() {
+        let custom_words = CustomWords {
+            user_inputs: [("jsmith", 1), ("(555) 123-4567", 2)]
+                .into_iter()
+                .map(|(input, rank)| (input.to_string(), rank))
+                .collect(),
+            ..CustomWords::default()
         };
-        assert_eq!(p.year, 1991);
-        assert_eq!(p.month, 12);
-        assert_eq!(p.day, 20);
-        assert_eq!(p.separator, "/".to_string());
-        let m = matches.iter().find(|m| &m.token == "1991.12.20").unwrap();
-        assert_eq!(m.i, 6);
-        assert_eq!(m.j, password.len() - 1);
-        let p = if let MatchPattern::Date(ref p) = m.pattern {
+        let matches = (matching::PhoneMatch {}).get_matches("x4567jsmith4567", &custom_words);
+        assert_eq!(matches.len(), 2);
+        for (m, i) in matches.iter().zip([1, 11]) {
+            assert_eq!((m.i, m.j, m.token.as_str()), (i, i + 3, "4567"));
+            assert_eq!(
+                m.pattern,
+                MatchPattern::Phone(PhonePattern {
+                    part: "last_four",
+                    rank: 2,
+                })
+            );
+        }
+
+        let matches = (matching::PhoneMatch {}).get_matches("é555-123-4567", &custom_words);
+        let tokens = matches.iter().map(|m| m.token.as_str()).collect::<Vec<_>>();
+        assert_eq!(tokens, ["555", "4567"]);
+        assert_eq!((matches[1].i, matches[1].j), (9, 12));
+
+        let matches = (matching::PhoneMatch {}).get_matches("4567", &CustomWords::default());
+        assert!(matches.is_empty());
+    }
+
+    #[test]
+    fn test_matches_shifted_sequences() {
+        for (password, token, ascending, shifted_count) in [
+            ("!@#$%6", "!@#$%6", true, 5),
+            ("x!@#$%^", "!@#$%^", true, 6),
+            ("(*&", "(*&", false, 3),
+            ("1@3", "1@3", true, 1),
+        ] {
+            let matches =
+                (matching::ShiftedSequenceMatch {}).get_matches(password, &CustomWords::default());
+            assert_eq!(matches.len(), 1, "{}", password);
+            let m = &matches[0];
+            assert_eq!(m.token, token);
+            let p = if let MatchPattern::Sequence(ref p) = m.pattern {
+                p
+            } else {
+                panic!("Wrong match pattern")
+            };
+            assert_eq!(p.sequence_name, "digits");
+            assert_eq!(p.ascending, ascending);
+            assert_eq!(p.shifted_count, shifted_count);
+        }
+
+        for password in ["123456", "!@", "!#%", "!@a#$"] {
+            let matches =
+                (matching::ShiftedSequenceMatch {}).get_matches(password, &CustomWords::default());
+            assert!(matches.is_empty(), "{}", password);
+        }
+    }
+
+    #[test]
+    fn test_identifies_repeat_with_multibyte_utf8() {
+        let password = "x\u{1F431}\u{1F436}\u{1F431}\u{1F436}";
+        let (i, j) = (1, 4);
+        let matches = (matching::RepeatMatch {}).get_matches(password, &CustomWords::default());
+        let m = matches.iter().find(|m| m.token == password[1..]).unwrap();
+        assert_eq!(m.i, i);
+        assert_eq!(m.j, j);
+        let p = if let MatchPattern::Repeat(ref p) = m.pattern {
             p
         } else {
             panic!("Wrong match pattern")
         };
-        assert_eq!(p.year, 1991);
-        assert_eq!(p.month, 12);
-        assert_eq!(p.day, 20);
-        assert_eq!(p.separator, ".".to_string());
+        assert_eq!(p.base_token, "\u{1F431}\u{1F436}".to_string());
     }
 
     #[test]
-    fn test_matches_dates_padded_by_non_ambiguous_digits() {
-        let password = "912/20/919";
-        let matches = (matching::DateMatch {}).get_matches(password, &HashMap::new());
-        let m = matches.iter().find(|m| &m.token == "12/20/91").unwrap();
-        assert_eq!(m.i, 1);
-        assert_eq!(m.j, password.len() - 2);
-        let p = if let MatchPattern::Date(ref p) = m.pattern {
-            p
-        } else {
-            panic!("Wrong match pattern")
+    fn test_base_tokens_are_paid_for_before_matching() {
+        let custom_words = CustomWords {
+            budget: matching::MatchBudget::new(7),
+            ..CustomWords::default()
         };
-        assert_eq!(p.year, 1991);
-        assert_eq!(p.month, 12);
-        assert_eq!(p.day, 20);
-        assert_eq!(p.separator, "/".to_string());
+        // "password" costs 8, more than is left
+        let analysis = matching::analyze_base("password", &custom_words);
+        assert_eq!(analysis.sequence.len(), 1);
+        assert_eq!(analysis.sequence[0].pattern.variant(), "bruteforce");
+        assert!(!custom_words.budget.is_spent());
+
+        let analysis = matching::analyze_base("pass", &custom_words);
+        assert_eq!(analysis.sequence[0].pattern.variant(), "dictionary");
+        assert!(custom_words.budget.is_spent());
     }
 
     #[test]
     fn test_omnimatch() {
-        assert_eq!(matching::omnimatch("", &HashMap::new()), Vec::new());
+        assert_eq!(matching::omnimatch("", &CustomWords::default()), Vec::new());
         let password = "r0sebudmaelstrom11/20/91aaaa";
         let expected = [
             ("dictionary", 0, 6),
@@ -1586,7 +2868,7 @@ mod tests {
             ("date", 16, 23),
             ("repeat", 24, 27),
         ];
-        let matches = matching::omnimatch(password, &HashMap::new());
+        let matches = matching::omnimatch(password, &CustomWords::default());
         for &(pattern_name, i, j) in &expected {
             assert!(matches
                 .iter()
@@ -0,0 +1,196 @@
+//! Matching the shapes of national ID numbers, with a `national-ids-<region>` feature for each
+//! region.
+//!
+//! A password holding an ID number gives the number away with the password, so matches get
+//! their own warning whatever the score of the password. The regions are chosen at build time,
+//! as the formats of one region can look like ordinary numbers in another.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::patterns::{MatchPattern, NationalIdPattern};
+#[cfg(feature = "national-ids-ca")]
+use super::payment::passes_luhn;
+use super::{CustomWords, Match, Matcher};
+
+/// A format of ID number.
+struct NationalIdFormat {
+    id_name: &'static str,
+    region: &'static str,
+    regex: Regex,
+    /// Whether the captures of a match are those of an ID number that can be issued.
+    is_valid: fn(&regex::Captures) -> bool,
+    /// Characters that can't come right before or after an ID number, as it would be part of a
+    /// longer one.
+    is_continuation: fn(char) -> bool,
+}
+
+static FORMATS: Lazy<Vec<NationalIdFormat>> = Lazy::new(|| {
+    vec![
+        #[cfg(feature = "national-ids-us")]
+        NationalIdFormat {
+            id_name: "ssn",
+            region: "us",
+            regex: Regex::new(r"([0-9]{3})([- ])([0-9]{2})([- ])([0-9]{4})").unwrap(),
+            is_valid: is_valid_ssn,
+            is_continuation: |c| c.is_ascii_digit(),
+        },
+        #[cfg(feature = "national-ids-uk")]
+        NationalIdFormat {
+            id_name: "nino",
+            region: "uk",
+            regex: Regex::new(r"([a-zA-Z]{2}) ?([0-9]{2}) ?([0-9]{2}) ?([0-9]{2}) ?([a-dA-D])")
+                .unwrap(),
+            is_valid: is_valid_nino,
+            is_continuation: |c| c.is_ascii_alphanumeric(),
+        },
+        #[cfg(feature = "national-ids-ca")]
+        NationalIdFormat {
+            id_name: "sin",
+            region: "ca",
+            regex: Regex::new(r"([0-9]{3})([- ])([0-9]{3})([- ])([0-9]{3})").unwrap(),
+            is_valid: is_valid_sin,
+            is_continuation: |c| c.is_ascii_digit(),
+        },
+    ]
+});
+
+/// Matches ID numbers of the regions compiled in.
+pub(super) struct NationalIdMatch {}
+
+impl Matcher for NationalIdMatch {
+    fn get_matches(&self, password: &str, _custom_words: &CustomWords) -> Vec<Match> {
+        let mut matches = Vec::new();
+        for format in FORMATS.iter() {
+            for captures in format.regex.captures_iter(password) {
+                let whole = captures.get(0).unwrap();
+                let before = password[..whole.start()].chars().next_back();
+                let after = password[whole.end()..].chars().next();
+                let is_part_of_longer = before.into_iter().chain(after).any(format.is_continuation);
+                if is_part_of_longer || !(format.is_valid)(&captures) {
+                    continue;
+                }
+                let i = password[..whole.start()].chars().count();
+                matches.push(Match {
+                    pattern: MatchPattern::NationalId(NationalIdPattern {
+                        id_name: format.id_name,
+                        region: format.region,
+                    }),
+                    i,
+                    j: i + whole.as_str().chars().count() - 1,
+                    token: whole.as_str().to_string(),
+                    ..Match::default()
+                });
+            }
+        }
+        matches
+    }
+
+    fn in_js_implementation(&self) -> bool {
+        false
+    }
+}
+
+/// An area other than 000, 666 or 900-999, a group other than 00 and a serial other than 0000,
+/// with the same separator between them.
+#[cfg(feature = "national-ids-us")]
+fn is_valid_ssn(captures: &regex::Captures) -> bool {
+    let area = &captures[1];
+    captures[2] == captures[4]
+        && area != "000"
+        && area != "666"
+        && !area.starts_with('9')
+        && &captures[3] != "00"
+        && &captures[5] != "0000"
+}
+
+/// A prefix of two letters that can be issued, and the same spacing throughout.
+#[cfg(feature = "national-ids-uk")]
+fn is_valid_nino(captures: &regex::Captures) -> bool {
+    let whole = &captures[0];
+    let spaces = whole.matches(' ').count();
+    let prefix = captures[1].to_ascii_uppercase();
+    let (first, second) = (prefix.as_bytes()[0], prefix.as_bytes()[1]);
+    (spaces == 0 || spaces == 4)
+        && !b"DFIQUV".contains(&first)
+        && !b"DFIOQUV".contains(&second)
+        && !["BG", "GB", "KN", "NK", "NT", "TN", "ZZ"].contains(&prefix.as_str())
+}
+
+/// A first digit other than 0 or 8, a valid Luhn check digit and the same separator
+/// throughout.
+#[cfg(feature = "national-ids-ca")]
+fn is_valid_sin(captures: &regex::Captures) -> bool {
+    let digits = [&captures[1], &captures[3], &captures[5]].concat();
+    captures[2] == captures[4] && !digits.starts_with(['0', '8']) && passes_luhn(&digits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matched_ids(password: &str) -> Vec<(&'static str, &str)> {
+        (NationalIdMatch {})
+            .get_matches(password, &CustomWords::default())
+            .into_iter()
+            .map(|m| match m.pattern {
+                MatchPattern::NationalId(p) => (p.id_name, &password[m.i..=m.j]),
+                _ => panic!("Wrong match pattern"),
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "national-ids-us")]
+    #[test]
+    fn test_ssn_matches() {
+        assert_eq!(matched_ids("ssn078-05-1120!"), [("ssn", "078-05-1120")]);
+        assert_eq!(matched_ids("078 05 1120"), [("ssn", "078 05 1120")]);
+        for password in [
+            "078-05 1120",
+            "666-05-1120",
+            "900-05-1120",
+            "078-00-1120",
+            "078-05-0000",
+            "1078-05-1120",
+            "078-05-11201",
+            "\u{ff10}78-05-1120",
+        ] {
+            assert!(matched_ids(password).is_empty(), "{}", password);
+        }
+    }
+
+    #[cfg(feature = "national-ids-uk")]
+    #[test]
+    fn test_nino_matches() {
+        assert_eq!(matched_ids("AB123456C!"), [("nino", "AB123456C")]);
+        assert_eq!(matched_ids("ab 12 34 56 c"), [("nino", "ab 12 34 56 c")]);
+        for password in [
+            "AB 123456C",
+            "QQ123456C",
+            "GB123456C",
+            "AO123456C",
+            "AB123456E",
+            "XAB123456C",
+            // the Kelvin sign, which matches "k" ignoring case
+            "A\u{212a}123456C",
+        ] {
+            assert!(matched_ids(password).is_empty(), "{}", password);
+        }
+    }
+
+    #[cfg(feature = "national-ids-ca")]
+    #[test]
+    fn test_sin_matches() {
+        assert_eq!(matched_ids("x130-692-544"), [("sin", "130-692-544")]);
+        assert_eq!(matched_ids("130 692 544"), [("sin", "130 692 544")]);
+        for password in [
+            "130-692-545",
+            "130 692-544",
+            "046-454-286",
+            "1130-692-544",
+            "١٣٠-٦٩٢-٥٤٤",
+        ] {
+            assert!(matched_ids(password).is_empty(), "{}", password);
+        }
+    }
+}
@@ -1,12 +1,13 @@
-use crate::frequency_lists::DictionaryType;
+pub use crate::frequency_lists::DictionaryType;
 use crate::matching::Match;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 /// Pattern type used to detect a match
 #[derive(Debug, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "ser", derive(Serialize))]
 #[cfg_attr(feature = "ser", serde(tag = "pattern"))]
 #[cfg_attr(feature = "ser", serde(rename_all = "lowercase"))]
+#[non_exhaustive]
 pub enum MatchPattern {
     /// A match based on a word in a dictionary
     Dictionary(DictionaryPattern),
@@ -20,22 +21,150 @@ pub enum MatchPattern {
     Regex(RegexPattern),
     /// A match based on date patterns
     Date(DatePattern),
+    /// A match based on emoji, scored per emoji rather than per char
+    Emoji(EmojiPattern),
+    /// A match based on two sequences with alternating characters, e.g. "a1b2c3"
+    Interleaved(InterleavedPattern),
+    /// A match based on text followed by its mirror image, e.g. "abccba" or "racecar"
+    Mirror(MirrorPattern),
+    /// A match based on text followed by the same keys typed with shift toggled, e.g. "123!@#"
+    ShiftRepeat(ShiftRepeatPattern),
+    /// A match based on text with every character typed several times, e.g. "ppaasswwoorrdd"
+    Echo(EchoPattern),
+    /// A match based on a phone number from the user inputs, or part of one
+    Phone(PhonePattern),
+    /// A match based on the shape of a national ID number, e.g. "078-05-1120", with the
+    /// `national-ids` features
+    NationalId(NationalIdPattern),
+    /// A match based on a payment card number or IBAN with a valid check digit
+    Payment(PaymentPattern),
+    /// A match based on digits tracing the unlock pattern of a phone lock screen, e.g. "14789"
+    Unlock(UnlockPattern),
+    /// A match based on two words typed one after the other, e.g. "SarahJane"
+    WordPair(WordPairPattern),
+    /// A match based on the first letters of the words of a famous phrase, e.g. "tbontbtitq"
+    Acronym(AcronymPattern),
     /// A match based on bruteforce attempting to guess a password
     #[default]
     BruteForce,
 }
 
 impl MatchPattern {
-    #[cfg(test)]
-    pub(crate) fn variant(&self) -> &str {
-        match *self {
-            MatchPattern::Dictionary(_) => "dictionary",
-            MatchPattern::Spatial(_) => "spatial",
-            MatchPattern::Repeat(_) => "repeat",
-            MatchPattern::Sequence(_) => "sequence",
-            MatchPattern::Regex(_) => "regex",
-            MatchPattern::Date(_) => "date",
-            MatchPattern::BruteForce => "bruteforce",
+    /// Every name [`variant`](MatchPattern::variant) returns.
+    pub(crate) const VARIANTS: [&'static str; 18] = [
+        "dictionary",
+        "spatial",
+        "repeat",
+        "sequence",
+        "regex",
+        "date",
+        "emoji",
+        "interleaved",
+        "mirror",
+        "shift_repeat",
+        "echo",
+        "phone",
+        "national_id",
+        "payment",
+        "unlock",
+        "word_pair",
+        "acronym",
+        "bruteforce",
+    ];
+
+    /// Lowercase name of the pattern, as used when serializing, e.g. `"dictionary"`.
+    pub fn variant(&self) -> &'static str {
+        self.kind().map_or("bruteforce", PatternKind::name)
+    }
+
+    /// The kind of pattern this is, or `None` for bruteforce.
+    pub fn kind(&self) -> Option<PatternKind> {
+        Some(match *self {
+            MatchPattern::Dictionary(_) => PatternKind::Dictionary,
+            MatchPattern::Spatial(_) => PatternKind::Spatial,
+            MatchPattern::Repeat(_) => PatternKind::Repeat,
+            MatchPattern::Sequence(_) => PatternKind::Sequence,
+            MatchPattern::Regex(_) => PatternKind::Regex,
+            MatchPattern::Date(_) => PatternKind::Date,
+            MatchPattern::Emoji(_) => PatternKind::Emoji,
+            MatchPattern::Interleaved(_) => PatternKind::Interleaved,
+            MatchPattern::Mirror(_) => PatternKind::Mirror,
+            MatchPattern::ShiftRepeat(_) => PatternKind::ShiftRepeat,
+            MatchPattern::Echo(_) => PatternKind::Echo,
+            MatchPattern::Phone(_) => PatternKind::Phone,
+            MatchPattern::NationalId(_) => PatternKind::NationalId,
+            MatchPattern::Payment(_) => PatternKind::Payment,
+            MatchPattern::Unlock(_) => PatternKind::Unlock,
+            MatchPattern::WordPair(_) => PatternKind::WordPair,
+            MatchPattern::Acronym(_) => PatternKind::Acronym,
+            MatchPattern::BruteForce => return None,
+        })
+    }
+}
+
+/// The patterns matches are found with, without bruteforce, whose guesses follow from those
+/// of the others. Used to configure every match of a pattern, e.g. with
+/// [`ScoringParams::min_guesses`](crate::ScoringParams::min_guesses).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum PatternKind {
+    /// [`MatchPattern::Dictionary`]
+    Dictionary,
+    /// [`MatchPattern::Spatial`]
+    Spatial,
+    /// [`MatchPattern::Repeat`]
+    Repeat,
+    /// [`MatchPattern::Sequence`]
+    Sequence,
+    /// [`MatchPattern::Regex`]
+    Regex,
+    /// [`MatchPattern::Date`]
+    Date,
+    /// [`MatchPattern::Emoji`]
+    Emoji,
+    /// [`MatchPattern::Interleaved`]
+    Interleaved,
+    /// [`MatchPattern::Mirror`]
+    Mirror,
+    /// [`MatchPattern::ShiftRepeat`]
+    ShiftRepeat,
+    /// [`MatchPattern::Echo`]
+    Echo,
+    /// [`MatchPattern::Phone`]
+    Phone,
+    /// [`MatchPattern::NationalId`]
+    NationalId,
+    /// [`MatchPattern::Payment`]
+    Payment,
+    /// [`MatchPattern::Unlock`]
+    Unlock,
+    /// [`MatchPattern::WordPair`]
+    WordPair,
+    /// [`MatchPattern::Acronym`]
+    Acronym,
+}
+
+impl PatternKind {
+    /// Lowercase name of the pattern, the same as [`MatchPattern::variant`].
+    pub fn name(self) -> &'static str {
+        match self {
+            PatternKind::Dictionary => "dictionary",
+            PatternKind::Spatial => "spatial",
+            PatternKind::Repeat => "repeat",
+            PatternKind::Sequence => "sequence",
+            PatternKind::Regex => "regex",
+            PatternKind::Date => "date",
+            PatternKind::Emoji => "emoji",
+            PatternKind::Interleaved => "interleaved",
+            PatternKind::Mirror => "mirror",
+            PatternKind::ShiftRepeat => "shift_repeat",
+            PatternKind::Echo => "echo",
+            PatternKind::Phone => "phone",
+            PatternKind::NationalId => "national_id",
+            PatternKind::Payment => "payment",
+            PatternKind::Unlock => "unlock",
+            PatternKind::WordPair => "word_pair",
+            PatternKind::Acronym => "acronym",
         }
     }
 }
@@ -45,6 +174,7 @@ impl MatchPattern {
 #[cfg_attr(feature = "builder", derive(Builder))]
 #[cfg_attr(feature = "builder", builder(default))]
 #[cfg_attr(feature = "ser", derive(Serialize))]
+#[non_exhaustive]
 pub struct DictionaryPattern {
     /// Word that has been found in a dictionary.
     pub matched_word: String,
@@ -56,8 +186,17 @@ pub struct DictionaryPattern {
     pub reversed: bool,
     /// Whether a l33t-substituted word has been found in a dictionary.
     pub l33t: bool,
+    /// Number of characters inserted, deleted or replaced in the matched word, with the
+    /// `fuzzy-matching` feature.
+    pub typos: usize,
+    /// Keyboard layout the matched word is written with, if it was typed with another
+    /// layout active: "jcuken" for a Russian word typed on QWERTY, e.g. "gfhjkm" for
+    /// "пароль", "dvorak" for a word typed on QWERTY as if it were Dvorak, e.g. "ra;;,soh"
+    /// for "password", or "qwerty" for a word typed on the Russian layout or Dvorak, e.g.
+    /// "зфыыцщкв" or "laoo,rpe" for "password".
+    pub layout: Option<&'static str>,
     /// Substitutions used for the match.
-    pub sub: Option<HashMap<char, char>>,
+    pub sub: Option<BTreeMap<char, char>>,
     /// String for displaying the substitutions used for the match.
     pub sub_display: Option<String>,
     /// Number of variations of the matched dictionary word.
@@ -73,6 +212,7 @@ pub struct DictionaryPattern {
 #[cfg_attr(feature = "builder", derive(Builder))]
 #[cfg_attr(feature = "builder", builder(default))]
 #[cfg_attr(feature = "ser", derive(Serialize))]
+#[non_exhaustive]
 pub struct SpatialPattern {
     /// Name of the graph for which a spatial match has been found.
     pub graph: String,
@@ -80,6 +220,12 @@ pub struct SpatialPattern {
     pub turns: usize,
     /// Number of shifts in the matched spatial pattern.
     pub shifted_count: usize,
+    /// "row", "column" or "block" if the match covers a whole row, a whole column or a
+    /// rectangular block of keys.
+    pub region: Option<&'static str>,
+    /// "box", "triangle" or "cross" if the match draws the outline of a box or a triangle, or
+    /// two lines of keys crossing in their middle.
+    pub shape: Option<&'static str>,
 }
 
 /// A match based on repeating patterns
@@ -87,6 +233,7 @@ pub struct SpatialPattern {
 #[cfg_attr(feature = "builder", derive(Builder))]
 #[cfg_attr(feature = "builder", builder(default))]
 #[cfg_attr(feature = "ser", derive(Serialize))]
+#[non_exhaustive]
 pub struct RepeatPattern {
     /// Base token that repeats in the matched pattern.
     pub base_token: String,
@@ -98,11 +245,163 @@ pub struct RepeatPattern {
     pub repeat_count: usize,
 }
 
+/// A match based on text followed by its mirror image, e.g. "abccba" or "racecar"
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[cfg_attr(feature = "builder", builder(default))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[non_exhaustive]
+pub struct MirrorPattern {
+    /// The half that is mirrored, including the middle character of an odd-length match,
+    /// e.g. "abc" for "abccba" and "race" for "racecar".
+    pub base_token: String,
+    /// Matches for the mirrored half.
+    pub base_matches: Vec<Match>,
+    /// Estimated number of tries for guessing the mirrored half.
+    pub base_guesses: u64,
+}
+
+/// A match based on text followed by the same keys typed with shift toggled, e.g. "123!@#"
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[cfg_attr(feature = "builder", builder(default))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[non_exhaustive]
+pub struct ShiftRepeatPattern {
+    /// The text that is retyped with shift toggled, e.g. "123" for "123!@#".
+    pub base_token: String,
+    /// Matches for the retyped text.
+    pub base_matches: Vec<Match>,
+    /// Estimated number of tries for guessing the retyped text.
+    pub base_guesses: u64,
+    /// Whether the keys are retyped in reverse order, e.g. "123#@!".
+    pub reversed: bool,
+}
+
+/// A match based on text with every character typed several times, e.g. "ppaasswwoorrdd"
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[cfg_attr(feature = "builder", builder(default))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[non_exhaustive]
+pub struct EchoPattern {
+    /// The text whose characters are echoed, e.g. "password" for "ppaasswwoorrdd".
+    pub base_token: String,
+    /// Matches for the echoed text.
+    pub base_matches: Vec<Match>,
+    /// Estimated number of tries for guessing the echoed text.
+    pub base_guesses: u64,
+    /// Number of times each character is typed, e.g. 2 for "ppaasswwoorrdd".
+    pub echo_count: usize,
+    /// Whether a doubled letter of the echoed text is typed only as often as the other
+    /// letters, e.g. the "ss" of "password" in "ppaasswwoorrdd".
+    pub collapsed: bool,
+}
+
+/// A match based on a phone number from the user inputs, or part of one
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[cfg_attr(feature = "builder", builder(default))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[non_exhaustive]
+pub struct PhonePattern {
+    /// The part of the phone number that was matched: "formatted" for the number as given,
+    /// "number" for its digits, "local" for its last 7 digits, "area_code" for the first 3
+    /// digits of a 10-digit national number or "last_four" for its last 4 digits.
+    pub part: &'static str,
+    /// Rank of the user input the phone number is, 1 for the first one.
+    pub rank: usize,
+}
+
+/// A match based on the shape of a national ID number, e.g. "078-05-1120"
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[cfg_attr(feature = "builder", builder(default))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[non_exhaustive]
+pub struct NationalIdPattern {
+    /// The kind of ID number: "ssn" for a US Social Security number, "nino" for a UK National
+    /// Insurance number or "sin" for a Canadian Social Insurance number.
+    pub id_name: &'static str,
+    /// The region the ID number is issued in, as its feature is named, e.g. "us" for
+    /// `national-ids-us`.
+    pub region: &'static str,
+}
+
+/// A match based on a payment card number or IBAN with a valid check digit, e.g.
+/// "4111 1111 1111 1111"
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[cfg_attr(feature = "builder", builder(default))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[non_exhaustive]
+pub struct PaymentPattern {
+    /// "card" for a payment card number passing the Luhn check, "iban" for an International
+    /// Bank Account Number passing its mod-97 check.
+    pub payment_kind: &'static str,
+}
+
+/// A match based on digits tracing the unlock pattern of a phone lock screen, with the 3×3
+/// grid of dots numbered like a phone pad, e.g. "14789" for an L
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[cfg_attr(feature = "builder", builder(default))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[non_exhaustive]
+pub struct UnlockPattern {
+    /// Number of knight moves, two dots one way and one the other, which the unlock screen
+    /// allows but few patterns make.
+    pub knight_moves: usize,
+}
+
+/// A match based on two words typed one after the other, each capitalized or not, e.g.
+/// "SarahJane" or "OkayThanks"
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[cfg_attr(feature = "builder", builder(default))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[non_exhaustive]
+pub struct WordPairPattern {
+    /// The first word, lowercased.
+    pub first_word: String,
+    /// The second word, lowercased.
+    pub second_word: String,
+    /// Rank of the first word in the dictionary, or 0 if only the pair is listed, as for single
+    /// letters and the words of [`DictionaryType::Bigrams`].
+    pub first_rank: usize,
+    /// Rank of the second word in the dictionary, or 0 as for the first word.
+    pub second_rank: usize,
+    /// Rank of the two words among the pairs of the dictionary that often go together, if
+    /// they are one of them.
+    pub pair_rank: Option<usize>,
+    /// Name of the dictionary the words have been found in.
+    pub dictionary_name: DictionaryType,
+    /// Number of ways the two words could be capitalized.
+    pub uppercase_variations: u64,
+}
+
+/// A match based on the first letters of the words of a famous quote or song lyric, e.g.
+/// "tbontbtitq" for "to be or not to be that is the question"
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[cfg_attr(feature = "builder", builder(default))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[non_exhaustive]
+pub struct AcronymPattern {
+    /// The phrase whose words start with the letters of the token.
+    pub phrase: &'static str,
+    /// Rank of the phrase among the bundled phrases.
+    pub rank: usize,
+    /// Number of ways the letters could be capitalized.
+    pub uppercase_variations: u64,
+}
+
 /// A match based on sequences of characters, e.g. "abcd"
 #[derive(Debug, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "builder", derive(Builder))]
 #[cfg_attr(feature = "builder", builder(default))]
 #[cfg_attr(feature = "ser", derive(Serialize))]
+#[non_exhaustive]
 pub struct SequencePattern {
     /// Name of the sequence that was matched.
     pub sequence_name: &'static str,
@@ -110,6 +409,26 @@ pub struct SequencePattern {
     pub sequence_space: u8,
     /// Whether the matched sequence is ascending.
     pub ascending: bool,
+    /// Number of digits typed as the symbols above them on the keyboard, e.g. 5 for
+    /// "!@#$%6".
+    pub shifted_count: usize,
+}
+
+/// A match based on two sequences with alternating characters, e.g. "a1b2c3"
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[cfg_attr(feature = "builder", builder(default))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[non_exhaustive]
+pub struct InterleavedPattern {
+    /// The characters of the first sequence, e.g. "abc" for "a1b2c3".
+    pub first_token: String,
+    /// The first sequence, starting the match.
+    pub first_sequence: SequencePattern,
+    /// The characters of the second sequence, e.g. "123" for "a1b2c3".
+    pub second_token: String,
+    /// The second sequence.
+    pub second_sequence: SequencePattern,
 }
 
 /// A match based on one of the regex patterns used in zxcvbn.
@@ -117,6 +436,7 @@ pub struct SequencePattern {
 #[cfg_attr(feature = "builder", derive(Builder))]
 #[cfg_attr(feature = "builder", builder(default))]
 #[cfg_attr(feature = "ser", derive(Serialize))]
+#[non_exhaustive]
 pub struct RegexPattern {
     /// Name of the regular expression that was matched.
     pub regex_name: &'static str,
@@ -129,6 +449,7 @@ pub struct RegexPattern {
 #[cfg_attr(feature = "builder", derive(Builder))]
 #[cfg_attr(feature = "builder", builder(default))]
 #[cfg_attr(feature = "ser", derive(Serialize))]
+#[non_exhaustive]
 pub struct DatePattern {
     /// Separator of a date that was matched.
     pub separator: String,
@@ -138,4 +459,27 @@ pub struct DatePattern {
     pub month: i8,
     /// Day that was matched.
     pub day: i8,
+    /// Whether the date is one of the user inputs, such as the user's birth date.
+    pub user_input: bool,
+}
+
+/// A match based on emoji, scored per emoji rather than per char
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[cfg_attr(feature = "builder", builder(default))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[non_exhaustive]
+pub struct EmojiPattern {
+    /// How the emoji were matched: "repeat" for one emoji repeated, "keyboard" for emoji
+    /// next to each other on an emoji keyboard, "common" for one of the most used emoji
+    /// and "other" for any other emoji.
+    pub emoji_kind: &'static str,
+    /// Number of emoji that were matched, counting e.g. a flag or an emoji with a skin
+    /// tone once.
+    pub emoji_count: usize,
+    /// Whether keyboard neighbours were matched in keyboard order.
+    pub ascending: bool,
+    /// Estimated number of tries for guessing a single emoji, or the first of a keyboard
+    /// match.
+    pub base_guesses: u64,
 }
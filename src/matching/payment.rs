@@ -0,0 +1,275 @@
+//! Matching payment card numbers and IBANs, which organizations handling payment data have to
+//! keep out of passwords.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::patterns::{MatchPattern, PaymentPattern};
+use super::{CustomWords, Match, Matcher};
+
+/// Runs of digits, each optionally preceded by a space or dash, as card numbers are written in
+/// groups.
+static DIGIT_RUN_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"[0-9](?:[ -]?[0-9])*").unwrap());
+/// The fewest and most digits of a card number.
+const CARD_LENGTHS: (usize, usize) = (13, 19);
+/// The digits of a year or a PIN, written before or after a card number.
+const DIGITS_AROUND_CARD: usize = 4;
+/// The prefixes of the major card networks, as the first and last of a range of prefixes of
+/// the same number of digits, with the lengths of the card numbers they issue: Visa,
+/// Mastercard, American Express, Diners Club, Discover, JCB and UnionPay.
+const CARD_NETWORKS: &[(u32, u32, &[usize])] = &[
+    (4, 4, &[13, 16, 19]),
+    (51, 55, &[16]),
+    (2221, 2720, &[16]),
+    (34, 34, &[15]),
+    (37, 37, &[15]),
+    (300, 305, &[14]),
+    (36, 36, &[14]),
+    (38, 39, &[14, 16]),
+    (6011, 6011, &[16, 17, 18, 19]),
+    (644, 649, &[16, 19]),
+    (65, 65, &[16, 19]),
+    (3528, 3589, &[16, 17, 18, 19]),
+    (62, 62, &[16, 17, 18, 19]),
+];
+/// A country code, two check digits and 11 to 30 letters and digits, optionally in groups
+/// separated by spaces.
+static IBAN_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[a-zA-Z]{2}[0-9]{2}(?: ?[a-zA-Z0-9]){11,30}").unwrap());
+
+/// Matches card numbers passing the Luhn check and IBANs passing the mod-97 check.
+pub(super) struct PaymentMatch {}
+
+impl Matcher for PaymentMatch {
+    fn get_matches(&self, password: &str, _custom_words: &CustomWords) -> Vec<Match> {
+        let mut matches = Vec::new();
+        for run in DIGIT_RUN_REGEX.find_iter(password) {
+            for (start, end) in card_windows(run.as_str()) {
+                let token = &run.as_str()[start..end];
+                if is_card_number(token) {
+                    matches.push(payment_match(password, run.start() + start, token, "card"));
+                }
+            }
+        }
+        for found in IBAN_REGEX.find_iter(password) {
+            // the regex takes the longest run it can, so only the letters of a longer word
+            // before an IBAN, or a digit after it, are left around it
+            let before = password[..found.start()].chars().next_back();
+            let after = password[found.end()..].chars().next();
+            let is_part_of_longer = after.is_some_and(|c| c.is_ascii_digit())
+                || before.is_some_and(|c| c.is_ascii_alphanumeric());
+            if !is_part_of_longer && is_iban(found.as_str()) {
+                matches.push(payment_match(
+                    password,
+                    found.start(),
+                    found.as_str(),
+                    "iban",
+                ));
+            }
+        }
+        matches
+    }
+
+    fn in_js_implementation(&self) -> bool {
+        false
+    }
+}
+
+fn payment_match(password: &str, start: usize, token: &str, payment_kind: &'static str) -> Match {
+    let i = password[..start].chars().count();
+    Match {
+        pattern: MatchPattern::Payment(PaymentPattern { payment_kind }),
+        i,
+        j: i + token.chars().count() - 1,
+        token: token.to_string(),
+        ..Match::default()
+    }
+}
+
+/// The byte ranges of a run of digits that may be card numbers: the whole run, and the run
+/// without the four digits at either end, so that a card number with a year or a PIN written
+/// next to it is found, but not every card number a long run of random digits happens to hold.
+fn card_windows(run: &str) -> Vec<(usize, usize)> {
+    let digits = run
+        .char_indices()
+        .filter(|(_, c)| c.is_ascii_digit())
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    let is_card_len = |len| (CARD_LENGTHS.0..=CARD_LENGTHS.1).contains(&len);
+    let mut windows = Vec::new();
+    if is_card_len(digits.len()) {
+        windows.push((0, run.len()));
+    }
+    let len = digits.len().saturating_sub(DIGITS_AROUND_CARD);
+    if is_card_len(len) {
+        windows.push((0, digits[len - 1] + 1));
+        windows.push((digits[DIGITS_AROUND_CARD], run.len()));
+    }
+    windows
+}
+
+/// Whether the digits of `token` are a card number: the prefix and length of a card network,
+/// at most one kind of separator and a valid Luhn check digit.
+fn is_card_number(token: &str) -> bool {
+    let digits = token
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect::<String>();
+    let has_both_separators = token.contains(' ') && token.contains('-');
+    !has_both_separators && is_card_network(&digits) && passes_luhn(&digits)
+}
+
+/// Whether a card number of ASCII digits has the prefix and length of a card network.
+fn is_card_network(digits: &str) -> bool {
+    CARD_NETWORKS.iter().any(|&(first, last, lengths)| {
+        let prefix_len = first.to_string().len();
+        lengths.contains(&digits.len())
+            && digits[..prefix_len]
+                .parse::<u32>()
+                .is_ok_and(|prefix| (first..=last).contains(&prefix))
+    })
+}
+
+/// Whether a string of ASCII digits passes the Luhn check: with every second digit from the
+/// right doubled, the sum of the digits is a multiple of 10.
+pub(super) fn passes_luhn(digits: &str) -> bool {
+    let sum = digits
+        .bytes()
+        .rev()
+        .map(|b| u32::from(b - b'0'))
+        .enumerate()
+        .map(|(k, d)| {
+            if k % 2 == 1 {
+                d * 2 % 10 + d * 2 / 10
+            } else {
+                d
+            }
+        })
+        .sum::<u32>();
+    sum % 10 == 0
+}
+
+/// Whether `token`, without spaces, is an IBAN: with the first four characters moved to the
+/// end and letters read as numbers from 10 to 35, it is 1 modulo 97.
+fn is_iban(token: &str) -> bool {
+    let iban = token.replace(' ', "").to_ascii_uppercase();
+    let (head, tail) = iban.split_at(4);
+    let mut remainder = 0;
+    for c in tail.chars().chain(head.chars()) {
+        let value = c.to_digit(36).unwrap();
+        remainder = if value < 10 {
+            (remainder * 10 + value) % 97
+        } else {
+            (remainder * 100 + value) % 97
+        };
+    }
+    remainder == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matched(password: &str) -> Vec<(&'static str, String)> {
+        (PaymentMatch {})
+            .get_matches(password, &CustomWords::default())
+            .into_iter()
+            .map(|m| match m.pattern {
+                MatchPattern::Payment(p) => (p.payment_kind, m.token),
+                _ => panic!("Wrong match pattern"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_passes_luhn() {
+        assert!(passes_luhn("4111111111111111"));
+        assert!(passes_luhn("378282246310005"));
+        assert!(passes_luhn("130692544"));
+        assert!(!passes_luhn("4111111111111112"));
+    }
+
+    #[test]
+    fn test_card_matches() {
+        for card in [
+            "4111111111111111",
+            "4111 1111 1111 1111",
+            "4111-1111-1111-1111",
+            "3782 822463 10005",
+        ] {
+            assert_eq!(
+                matched(&format!("visa{}!", card)),
+                [("card", card.to_string())]
+            );
+        }
+        for password in [
+            "4111111111111112",
+            "4111 1111-1111 1111",
+            "1111111111111117",
+            "411111111111",
+            // a Visa prefix and a valid check digit, but 15 digits
+            "411111111111116",
+        ] {
+            assert!(matched(password).is_empty(), "{}", password);
+        }
+    }
+
+    #[test]
+    fn test_card_matches_next_to_other_digits() {
+        assert_eq!(
+            matched("41111111111111112024"),
+            [("card", "4111111111111111".to_string())]
+        );
+        assert_eq!(
+            matched("1234 4111 1111 1111 1111"),
+            [("card", "4111 1111 1111 1111".to_string())]
+        );
+        // not in the middle of a longer run
+        assert!(matched("12341111111111111111152024").is_empty());
+        assert!(matched("411111111111111112").is_empty());
+    }
+
+    #[test]
+    fn test_few_random_digit_runs_are_card_numbers() {
+        // a fixed xorshift generator, so that the test is deterministic
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        let runs = 10_000;
+        let mut cards = 0;
+        for _ in 0..runs {
+            let len = 13 + (next() % 7) as usize;
+            let digits = (0..len)
+                .map(|_| char::from(b'0' + (next() % 10) as u8))
+                .collect::<String>();
+            if !matched(&digits).is_empty() {
+                cards += 1;
+            }
+        }
+        // one in ten runs with a network prefix passes the Luhn check, so some are left, but
+        // far fewer than the one in twenty matched by any first digit of a network
+        assert!(cards * 50 < runs, "{} of {} runs", cards, runs);
+    }
+
+    #[test]
+    fn test_iban_matches() {
+        for iban in ["DE89370400440532013000", "gb82 west 1234 5698 7654 32"] {
+            assert_eq!(matched(iban), [("iban", iban.to_string())]);
+        }
+        for password in ["DE89370400440532013001", "xDE89370400440532013000"] {
+            assert!(matched(password).is_empty(), "{}", password);
+        }
+    }
+
+    #[test]
+    fn test_non_ascii_digits_and_letters() {
+        // Arabic-Indic digits and the Kelvin sign, which matches "k" ignoring case
+        for password in ["٤١١١١١١١١١١١١١١١", "\u{212a}x89370400440532013000"] {
+            assert!(matched(password).is_empty(), "{}", password);
+        }
+    }
+}
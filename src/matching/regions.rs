@@ -0,0 +1,171 @@
+//! Whole regions of a keyboard: full rows, full columns and rectangular blocks of keys.
+//!
+//! These are among the first keyboard patterns an attacker tries, so rather than
+//! estimating them from their turns like other spatial patterns, each is ranked among
+//! the regions of the same kind, smaller regions first.
+
+use std::collections::{BTreeMap, HashMap};
+
+use once_cell::sync::Lazy;
+
+/// A region of keys typed in one of the orders an attacker would try.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Region {
+    /// "row", "column" or "block".
+    pub(crate) kind: &'static str,
+    /// Number of straight lines of keys the region is typed as.
+    pub(crate) turns: usize,
+    /// Number of regions of the same kind with at most as many keys, in every typing order.
+    pub(crate) guesses: u64,
+}
+
+/// The keys of each keyboard, one string per row, with the keys of a column at the same
+/// index in every row.
+pub(super) const GRIDS: [(&str, [&str; 4]); 4] = [
+    (
+        "qwerty",
+        ["1234567890", "qwertyuiop", "asdfghjkl;", "zxcvbnm,./"],
+    ),
+    (
+        "dvorak",
+        ["1234567890", "',.pyfgcrl", "aoeuidhtns", ";qjkxbmwvz"],
+    ),
+    ("keypad", ["789", "456", "123", ""]),
+    ("phone_pad", ["123", "456", "789", "*0#"]),
+];
+
+static REGIONS: Lazy<BTreeMap<&'static str, HashMap<String, Region>>> = Lazy::new(|| {
+    GRIDS
+        .iter()
+        .map(|(graph, rows)| {
+            let rows = rows
+                .iter()
+                .filter(|row| !row.is_empty())
+                .map(|row| row.chars().collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            (*graph, regions(&rows))
+        })
+        .collect()
+});
+
+pub(crate) fn initialize() {
+    Lazy::force(&REGIONS);
+}
+
+/// Every region of a grid, keyed by the keys in typing order.
+fn regions(rows: &[Vec<char>]) -> HashMap<String, Region> {
+    let height = rows.len();
+    let width = rows[0].len();
+    // (kind, number of keys, keys, turns) of every region in every typing order.
+    let mut found: Vec<(&'static str, usize, String, usize)> = Vec::new();
+    for h in 1..=height {
+        for w in 1..=width {
+            let kind = match (h, w) {
+                (1, w) if w == width => "row",
+                (h, 1) if h == height => "column",
+                (1, _) | (_, 1) => continue,
+                _ => "block",
+            };
+            for top in 0..=height - h {
+                for left in 0..=width - w {
+                    let lines_by_row = (top..top + h)
+                        .map(|r| rows[r][left..left + w].to_vec())
+                        .collect::<Vec<_>>();
+                    let lines_by_column = (left..left + w)
+                        .map(|c| (top..top + h).map(|r| rows[r][c]).collect::<Vec<_>>())
+                        .collect::<Vec<_>>();
+                    for lines in [lines_by_row, lines_by_column] {
+                        for (reverse_lines, reverse_keys) in
+                            [(false, false), (false, true), (true, false), (true, true)]
+                        {
+                            let mut lines = lines.clone();
+                            if reverse_lines {
+                                lines.reverse();
+                            }
+                            let keys = lines
+                                .iter()
+                                .flat_map(|line| {
+                                    let mut line = line.clone();
+                                    if reverse_keys {
+                                        line.reverse();
+                                    }
+                                    line
+                                })
+                                .collect::<String>();
+                            found.push((kind, h * w, keys, lines.len()));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    // the same keys can be typed in several ways, e.g. a row read as one line or as a
+    // column-wise walk over columns of one key. count each string once, with its fewest turns.
+    found.sort_by(|a, b| (a.0, a.1, &a.2, a.3).cmp(&(b.0, b.1, &b.2, b.3)));
+    found.dedup_by(|a, b| a.2 == b.2);
+    let mut counts: BTreeMap<(&str, usize), u64> = BTreeMap::new();
+    for &(kind, size, _, _) in &found {
+        *counts.entry((kind, size)).or_default() += 1;
+    }
+    let mut regions = HashMap::with_capacity(found.len());
+    for (kind, size, keys, turns) in found {
+        let guesses = counts
+            .range((kind, 0)..=(kind, size))
+            .map(|(_, count)| count)
+            .sum();
+        regions.insert(
+            keys,
+            Region {
+                kind,
+                turns,
+                guesses,
+            },
+        );
+    }
+    regions
+}
+
+/// The region the token covers on the keyboard of a graph, if any.
+pub(crate) fn find(graph: &str, token: &str) -> Option<&'static Region> {
+    REGIONS.get(graph).and_then(|regions| regions.get(token))
+}
+
+/// Names of the graphs with regions, and the number of keys in their largest region.
+pub(crate) fn graphs() -> impl Iterator<Item = (&'static str, usize)> {
+    GRIDS.iter().map(|(graph, rows)| {
+        let keys = rows.iter().map(|row| row.chars().count()).sum();
+        (*graph, keys)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_regions() {
+        let row = find("qwerty", "qwertyuiop").unwrap();
+        assert_eq!((row.kind, row.turns), ("row", 1));
+        assert_eq!(find("qwerty", "poiuytrewq").unwrap().kind, "row");
+        assert_eq!(find("qwerty", "1qaz").unwrap().kind, "column");
+        assert_eq!(find("qwerty", "zaq1").unwrap().kind, "column");
+        let block = find("qwerty", "1qaz2wsx").unwrap();
+        assert_eq!((block.kind, block.turns), ("block", 2));
+        assert_eq!(find("qwerty", "qweasdzxc").unwrap().kind, "block");
+        assert_eq!(find("keypad", "789456123").unwrap().kind, "block");
+        assert_eq!(find("dvorak", "aoeuidhtns").unwrap().kind, "row");
+        assert_eq!(find("qwerty", "qwert"), None);
+        assert_eq!(find("qwerty", "qwsa"), None);
+    }
+
+    #[test]
+    fn test_region_guesses() {
+        // 4 rows, each typed forwards or backwards
+        assert_eq!(find("qwerty", "zxcvbnm,./").unwrap().guesses, 8);
+        // 10 columns
+        assert_eq!(find("qwerty", "1qaz").unwrap().guesses, 20);
+        let small = find("qwerty", "qwas").unwrap().guesses;
+        let large = find("qwerty", "1qaz2wsx").unwrap().guesses;
+        assert!(small < large, "{} {}", small, large);
+    }
+}
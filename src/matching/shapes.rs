@@ -0,0 +1,228 @@
+//! Shapes drawn on a keyboard: the outlines of boxes and triangles, and crosses of two lines of
+//! keys, such as "qazxcvfr", three sides of a box from q down to z, across to v and back up.
+//!
+//! Like whole regions of keys, these are tried early by attackers, so rather than estimating
+//! them from their turns like other spatial patterns, each is ranked among the shapes of the
+//! same kind, smaller shapes first. A box or triangle is drawn back to its first key, up to the
+//! key next to it, or leaving out its last side, which the eye fills in.
+
+use std::collections::{BTreeMap, HashMap};
+
+use once_cell::sync::Lazy;
+
+use super::regions::GRIDS;
+
+/// Shapes of fewer keys are drawn by chance by most walks with a few turns.
+const MIN_KEYS: usize = 6;
+
+/// A shape of keys drawn in one of the orders an attacker would try.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Shape {
+    /// "box", "triangle" or "cross".
+    pub(crate) kind: &'static str,
+    /// Number of straight lines of keys the shape is drawn as.
+    pub(crate) turns: usize,
+    /// Number of shapes of the same kind with at most as many keys, in every drawing order.
+    pub(crate) guesses: u64,
+}
+
+/// A step from a key to the next one of a straight line, in rows and columns of the grid.
+type Step = (isize, isize);
+
+static SHAPES: Lazy<BTreeMap<&'static str, HashMap<String, Shape>>> = Lazy::new(|| {
+    GRIDS
+        .iter()
+        .map(|(graph, rows)| {
+            let rows = rows
+                .iter()
+                .filter(|row| !row.is_empty())
+                .map(|row| row.chars().collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            (*graph, shapes(&rows, &directions(graph)))
+        })
+        .collect()
+});
+
+pub(crate) fn initialize() {
+    Lazy::force(&SHAPES);
+}
+
+/// The directions of straight lines of keys on the keyboard of a graph. The rows of a keyboard
+/// are offset from one another, so a line down a column leans right and only the other
+/// diagonal is a line, while the keys of keypads also line up along both diagonals.
+fn directions(graph: &str) -> Vec<Step> {
+    let mut lines = vec![(0, 1), (1, 0), (1, -1)];
+    if graph == "keypad" || graph == "phone_pad" {
+        lines.push((1, 1));
+    }
+    lines
+        .into_iter()
+        .flat_map(|(row, column)| [(row, column), (-row, -column)])
+        .collect()
+}
+
+/// Whether two directions draw the same line, one way or the other.
+fn parallel(a: Step, b: Step) -> bool {
+    a == b || a == (-b.0, -b.1)
+}
+
+/// Every shape of a grid, keyed by the keys in drawing order.
+fn shapes(rows: &[Vec<char>], directions: &[Step]) -> HashMap<String, Shape> {
+    let height = rows.len() as isize;
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0) as isize;
+    let longest = height.max(width) as usize;
+    let key = |(row, column): Step| {
+        usize::try_from(row)
+            .ok()
+            .and_then(|row| rows.get(row))
+            .and_then(|keys| usize::try_from(column).ok().and_then(|c| keys.get(c)))
+            .copied()
+    };
+    // the keys from `start` along the sides, each a direction and a number of steps,
+    // including `start`
+    let walk = |start: Step, sides: &[(Step, usize)]| -> Option<Vec<char>> {
+        let mut at = start;
+        let mut keys = vec![key(at)?];
+        for &((row, column), steps) in sides {
+            for _ in 0..steps {
+                at = (at.0 + row, at.1 + column);
+                keys.push(key(at)?);
+            }
+        }
+        Some(keys)
+    };
+
+    // (kind, keys, turns) of every shape in every drawing order.
+    let mut found: Vec<(&'static str, String, usize)> = Vec::new();
+    for row in 0..height {
+        for column in 0..width {
+            let start = (row, column);
+            for &u in directions {
+                for &v in directions.iter().filter(|&&v| !parallel(u, v)) {
+                    // boxes, with sides of m and n steps
+                    for m in 1..longest {
+                        for n in 1..longest {
+                            let sides = [(u, m), (v, n), ((-u.0, -u.1), m), ((-v.0, -v.1), n)];
+                            if let Some(keys) = walk(start, &sides) {
+                                found.extend(drawings("box", &keys, &sides));
+                            }
+                        }
+                    }
+                    // triangles, whose third side takes them back to the first key
+                    let w = (-u.0 - v.0, -u.1 - v.1);
+                    if directions.contains(&w) {
+                        for k in 1..longest {
+                            let sides = [(u, k), (v, k), (w, k)];
+                            if let Some(keys) = walk(start, &sides) {
+                                found.extend(drawings("triangle", &keys, &sides));
+                            }
+                        }
+                    }
+                    // crosses of two lines of the same length through `start`
+                    for h in 1..longest as isize {
+                        let from_u = (row - h * u.0, column - h * u.1);
+                        let from_v = (row - h * v.0, column - h * v.1);
+                        let steps = 2 * h as usize;
+                        if let (Some(first), Some(second)) =
+                            (walk(from_u, &[(u, steps)]), walk(from_v, &[(v, steps)]))
+                        {
+                            let keys = first.into_iter().chain(second).collect();
+                            found.push(("cross", keys, 2));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    found.retain(|(_, keys, _)| keys.chars().count() >= MIN_KEYS);
+    // the same keys can be drawn as several shapes. count each string once, with its fewest
+    // turns.
+    found.sort_by(|a, b| (a.1.chars().count(), &a.1, a.2).cmp(&(b.1.chars().count(), &b.1, b.2)));
+    found.dedup_by(|a, b| a.1 == b.1);
+    let mut counts: BTreeMap<(&str, usize), u64> = BTreeMap::new();
+    for (kind, keys, _) in &found {
+        *counts.entry((kind, keys.chars().count())).or_default() += 1;
+    }
+    let mut shapes = HashMap::with_capacity(found.len());
+    for (kind, keys, turns) in found {
+        let guesses = counts
+            .range((kind, 0)..=(kind, keys.chars().count()))
+            .map(|(_, count)| count)
+            .sum();
+        shapes.insert(
+            keys,
+            Shape {
+                kind,
+                turns,
+                guesses,
+            },
+        );
+    }
+    shapes
+}
+
+/// The ways to draw a closed shape whose keys go around its sides back to the first key: back
+/// to the first key, up to the key next to it, and leaving out the last side.
+fn drawings(
+    kind: &'static str,
+    keys: &[char],
+    sides: &[(Step, usize)],
+) -> [(&'static str, String, usize); 3] {
+    let last_side = sides[sides.len() - 1].1;
+    [
+        (kind, keys.iter().collect(), sides.len()),
+        (kind, keys[..keys.len() - 1].iter().collect(), sides.len()),
+        (
+            kind,
+            keys[..keys.len() - last_side].iter().collect(),
+            sides.len() - 1,
+        ),
+    ]
+}
+
+/// The shape the token draws on the keyboard of a graph, if any.
+pub(crate) fn find(graph: &str, token: &str) -> Option<&'static Shape> {
+    SHAPES.get(graph).and_then(|shapes| shapes.get(token))
+}
+
+/// Names of the graphs with shapes, and the number of keys in their longest shape.
+pub(crate) fn graphs() -> impl Iterator<Item = (&'static str, usize)> {
+    SHAPES.iter().map(|(graph, shapes)| {
+        let keys = shapes
+            .keys()
+            .map(|keys| keys.chars().count())
+            .max()
+            .unwrap_or(0);
+        (*graph, keys)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_shapes() {
+        let open_box = find("qwerty", "qazxcvfr").unwrap();
+        assert_eq!((open_box.kind, open_box.turns), ("box", 3));
+        let closed_box = find("qwerty", "qazxcvfrewq").unwrap();
+        assert_eq!((closed_box.kind, closed_box.turns), ("box", 4));
+        assert_eq!(find("qwerty", "qazxcvfrew").unwrap().kind, "box");
+        // down the left of t, across the bottom and back up
+        let triangle = find("qwerty", "tfcvbgt").unwrap();
+        assert_eq!((triangle.kind, triangle.turns), ("triangle", 3));
+        let cross = find("qwerty", "wsxasd").unwrap();
+        assert_eq!((cross.kind, cross.turns), ("cross", 2));
+        assert_eq!(find("keypad", "741236987").unwrap().kind, "box");
+        assert_eq!(find("qwerty", "qazxcv"), None);
+        assert_eq!(find("qwerty", "qwerty"), None);
+    }
+
+    #[test]
+    fn test_shape_guesses() {
+        let small = find("qwerty", "qazxcvfr").unwrap().guesses;
+        let large = find("qwerty", "1qazxcvbgt5432").unwrap().guesses;
+        assert!(small < large, "{} {}", small, large);
+        assert!(small < 10_000, "{}", small);
+    }
+}
@@ -0,0 +1,211 @@
+//! Matching common passwords with a typo, with the `fuzzy-matching` feature.
+//!
+//! The most common passwords are kept in an FST, searched with a Levenshtein automaton for
+//! every substring of the password that could be one of them with a character inserted,
+//! deleted or replaced. Building an automaton costs far more than searching with it, so
+//! substrings are first checked against the first and last three characters of the
+//! passwords: one typo leaves at least one of them intact.
+
+use std::collections::HashSet;
+
+use fst::automaton::Levenshtein;
+use fst::{IntoStreamer, Streamer};
+use once_cell::sync::Lazy;
+
+use super::patterns::{DictionaryPattern, MatchPattern};
+use super::{CustomWords, Match, Matcher};
+use crate::frequency_lists::{self, DictionaryType};
+
+/// Passwords ranked this high or better are matched with a typo.
+const MAX_RANK: u32 = 10_000;
+/// Shorter passwords are a typo away from too many strings to match them with one.
+const MIN_LEN: usize = 6;
+/// Longer passwords are rare enough not to be worth searching every substring that long.
+const MAX_LEN: usize = 16;
+
+/// The common passwords matched with a typo.
+struct TypoPasswords {
+    map: fst::Map<Vec<u8>>,
+    /// The first three characters and the length of every password.
+    prefixes: HashSet<([char; 3], usize)>,
+    /// The last three characters and the length of every password.
+    suffixes: HashSet<([char; 3], usize)>,
+}
+
+impl TypoPasswords {
+    fn new() -> Self {
+        let passwords = frequency_lists::common_passwords(MAX_RANK, MIN_LEN)
+            .filter(|(word, _)| word.chars().count() <= MAX_LEN)
+            .collect::<Vec<_>>();
+        let trigram = |chars: &mut dyn Iterator<Item = char>| {
+            [
+                chars.next().unwrap(),
+                chars.next().unwrap(),
+                chars.next().unwrap(),
+            ]
+        };
+        let mut prefixes = HashSet::new();
+        let mut suffixes = HashSet::new();
+        for (word, _) in &passwords {
+            let len = word.chars().count();
+            prefixes.insert((trigram(&mut word.chars()), len));
+            let mut last = trigram(&mut word.chars().rev());
+            last.reverse();
+            suffixes.insert((last, len));
+        }
+        let map = fst::Map::from_iter(
+            passwords
+                .into_iter()
+                .map(|(word, rank)| (word, u64::from(rank))),
+        )
+        .expect("dictionary is sorted by word");
+        TypoPasswords {
+            map,
+            prefixes,
+            suffixes,
+        }
+    }
+
+    /// Whether a token may be one of the passwords with a typo, which is at most one
+    /// character longer or shorter. For a typo in the first three characters the last three
+    /// are intact, as both have at least five.
+    fn may_match(&self, token: &[char]) -> bool {
+        let n = token.len();
+        let first = [token[0], token[1], token[2]];
+        let last = [token[n - 3], token[n - 2], token[n - 1]];
+        (n - 1..=n + 1).any(|len| {
+            self.prefixes.contains(&(first, len)) || self.suffixes.contains(&(last, len))
+        })
+    }
+
+    /// The best ranked password the token is one typo away from, and its rank.
+    fn search(&self, token: &[char]) -> Option<(String, u64)> {
+        if !self.may_match(token) {
+            return None;
+        }
+        let token = token.iter().collect::<String>();
+        let automaton = Levenshtein::new(&token, 1).ok()?;
+        let mut found = self.map.search(automaton).into_stream();
+        let mut best: Option<(String, u64)> = None;
+        while let Some((word, rank)) = found.next() {
+            // exact matches are left to the dictionary matcher
+            if word != token.as_bytes() && best.as_ref().map_or(true, |(_, best)| rank < *best) {
+                best = Some((String::from_utf8_lossy(word).into_owned(), rank));
+            }
+        }
+        best
+    }
+}
+
+static TYPO_PASSWORDS: Lazy<TypoPasswords> = Lazy::new(TypoPasswords::new);
+
+pub(super) fn initialize() {
+    Lazy::force(&TYPO_PASSWORDS);
+}
+
+/// Matches common passwords with one character inserted, deleted or replaced, like
+/// "pasword123" or "passwprd".
+pub(super) struct TypoMatch {}
+
+impl Matcher for TypoMatch {
+    fn get_matches(&self, password: &str, _custom_words: &CustomWords) -> Vec<Match> {
+        let chars = super::lowercase_chars(password);
+        let mut matches = Vec::new();
+        for i in 0..chars.len() {
+            // a typo changes the length by at most one
+            let min_j = i + MIN_LEN - 2;
+            let max_j = (i + MAX_LEN).min(chars.len() - 1);
+            for j in min_j..=max_j {
+                if let Some((matched_word, rank)) = TYPO_PASSWORDS.search(&chars[i..=j]) {
+                    matches.push(Match {
+                        pattern: MatchPattern::Dictionary(DictionaryPattern {
+                            matched_word,
+                            rank: rank as usize,
+                            dictionary_name: DictionaryType::Passwords,
+                            typos: 1,
+                            ..DictionaryPattern::default()
+                        }),
+                        i,
+                        j,
+                        token: password.chars().take(j + 1).skip(i).collect(),
+                        ..Match::default()
+                    });
+                }
+            }
+        }
+        matches
+    }
+
+    fn in_js_implementation(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typo_matches(password: &str) -> Vec<(String, String)> {
+        (TypoMatch {})
+            .get_matches(password, &CustomWords::default())
+            .into_iter()
+            .map(|m| match m.pattern {
+                MatchPattern::Dictionary(p) => (m.token, p.matched_word),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_typo_matches() {
+        for (password, word) in [
+            ("passwprd", "password"),
+            ("psssword", "password"),
+            ("pasword123", "password123"),
+            ("iloveyoi", "iloveyou"),
+        ] {
+            assert!(
+                typo_matches(password)
+                    .iter()
+                    .any(|(token, matched)| token == password && matched == word),
+                "{}",
+                password
+            );
+        }
+        // exact matches are left to the dictionary matcher
+        assert!(typo_matches("password")
+            .iter()
+            .all(|(token, matched)| token != matched));
+        assert!(typo_matches("Xk7#q*zP2!vR").is_empty());
+    }
+
+    #[test]
+    fn test_typo_matches_index_the_password() {
+        // "İ" lowercases to two characters
+        let password = "İİİpasswprd";
+        for m in (TypoMatch {}).get_matches(password, &CustomWords::default()) {
+            assert_eq!(
+                m.token,
+                password.chars().take(m.j + 1).skip(m.i).collect::<String>()
+            );
+        }
+        crate::zxcvbn("İİİpassword", &[]);
+    }
+
+    #[test]
+    fn test_may_match() {
+        // every password matches itself with a typo in the first or last three characters
+        for (word, _) in frequency_lists::common_passwords(MAX_RANK, MIN_LEN).take(500) {
+            let chars = word.chars().collect::<Vec<_>>();
+            if chars.len() > MAX_LEN {
+                continue;
+            }
+            let mut start = chars.clone();
+            start[0] = '~';
+            let mut end = chars.clone();
+            end.pop();
+            assert!(TYPO_PASSWORDS.may_match(&start), "{}", word);
+            assert!(TYPO_PASSWORDS.may_match(&end), "{}", word);
+        }
+    }
+}
@@ -0,0 +1,158 @@
+//! Matching the unlock patterns of phone lock screens written down as digits, such as "14789"
+//! for an L, with the 3×3 grid of dots numbered like a phone pad.
+//!
+//! A pattern joins at least 4 dots, each at most once, and moves from a dot to one of the dots
+//! around it or a knight's move away, two dots one way and one the other, which the unlock
+//! screen allows as they pass between dots rather than over one.
+
+use once_cell::sync::Lazy;
+
+use super::patterns::{MatchPattern, UnlockPattern};
+use super::{CustomWords, Match, Matcher};
+
+/// The fewest dots of an unlock pattern.
+const MIN_DOTS: usize = 4;
+
+/// Matches runs of the digits 1 to 9 tracing an unlock pattern.
+pub(super) struct UnlockMatch {}
+
+impl Matcher for UnlockMatch {
+    fn get_matches(&self, password: &str, _custom_words: &CustomWords) -> Vec<Match> {
+        let chars = password.chars().collect::<Vec<_>>();
+        let mut matches = Vec::new();
+        for i in 0..chars.len() {
+            let mut dots = Vec::new();
+            let mut knight_moves = 0;
+            for (j, &c) in chars.iter().enumerate().skip(i) {
+                let dot = match c.to_digit(10) {
+                    Some(dot) if dot != 0 && !dots.contains(&dot) => dot,
+                    _ => break,
+                };
+                if let Some(&last) = dots.last() {
+                    match step(last, dot) {
+                        Some(Step::Knight) => knight_moves += 1,
+                        Some(Step::Neighbor) => {}
+                        None => break,
+                    }
+                }
+                dots.push(dot);
+                if dots.len() >= MIN_DOTS {
+                    matches.push(Match {
+                        pattern: MatchPattern::Unlock(UnlockPattern { knight_moves }),
+                        i,
+                        j,
+                        token: chars[i..=j].iter().collect(),
+                        ..Match::default()
+                    });
+                }
+            }
+        }
+        matches
+    }
+
+    fn in_js_implementation(&self) -> bool {
+        false
+    }
+}
+
+/// A move of an unlock pattern from one dot to another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    /// To one of the 8 dots around.
+    Neighbor,
+    /// Two dots one way and one the other.
+    Knight,
+}
+
+/// The move between two dots, numbered 1 to 9 from the top left, if a pattern can make it
+/// without passing over another dot.
+fn step(from: u32, to: u32) -> Option<Step> {
+    let (from_row, from_column) = ((from - 1) / 3, (from - 1) % 3);
+    let (to_row, to_column) = ((to - 1) / 3, (to - 1) % 3);
+    match (from_row.abs_diff(to_row), from_column.abs_diff(to_column)) {
+        (0, 0) => None,
+        (0..=1, 0..=1) => Some(Step::Neighbor),
+        (1, 2) | (2, 1) => Some(Step::Knight),
+        _ => None,
+    }
+}
+
+/// The number of unlock patterns starting at each dot, by number of dots.
+static PATTERN_COUNTS: Lazy<[[u64; 10]; 9]> = Lazy::new(|| {
+    fn count(dots: &mut Vec<u32>, counts: &mut [u64; 10]) {
+        counts[dots.len()] += 1;
+        for next in 1..=9 {
+            if !dots.contains(&next) && step(*dots.last().unwrap(), next).is_some() {
+                dots.push(next);
+                count(dots, counts);
+                dots.pop();
+            }
+        }
+    }
+
+    let mut counts = [[0; 10]; 9];
+    for (start, counts) in (1..=9).zip(counts.iter_mut()) {
+        count(&mut vec![start], counts);
+    }
+    counts
+});
+
+/// The number of unlock patterns of `dots` dots starting at the dot `start`, or `None` if
+/// there is no such dot or no pattern has that many dots.
+pub(crate) fn pattern_count(start: u32, dots: usize) -> Option<u64> {
+    let counts = PATTERN_COUNTS.get((start as usize).checked_sub(1)?)?;
+    counts.get(dots).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps() {
+        assert_eq!(step(1, 5), Some(Step::Neighbor));
+        assert_eq!(step(4, 2), Some(Step::Neighbor));
+        assert_eq!(step(1, 6), Some(Step::Knight));
+        assert_eq!(step(8, 1), Some(Step::Knight));
+        // over the dot between them
+        assert_eq!(step(1, 3), None);
+        assert_eq!(step(1, 9), None);
+        assert_eq!(step(5, 5), None);
+    }
+
+    #[test]
+    fn test_pattern_counts() {
+        // the center is next to every other dot, a corner to 3 and a knight move from 2
+        assert_eq!(pattern_count(5, 1), Some(1));
+        assert_eq!(pattern_count(5, 2), Some(8));
+        assert_eq!(pattern_count(1, 2), Some(5));
+        assert_eq!(pattern_count(2, 2), Some(7));
+        assert_eq!(pattern_count(0, 2), None);
+        assert_eq!(pattern_count(1, 10), None);
+    }
+
+    #[test]
+    fn test_unlock_matches() {
+        let matches = (UnlockMatch {}).get_matches("x14789", &CustomWords::default());
+        let tokens = matches.iter().map(|m| m.token.as_str()).collect::<Vec<_>>();
+        assert_eq!(tokens, ["1478", "14789", "4789"]);
+        assert_eq!(
+            matches[1].pattern,
+            MatchPattern::Unlock(UnlockPattern { knight_moves: 0 })
+        );
+
+        // a zigzag of knight moves
+        let matches = (UnlockMatch {}).get_matches("1834", &CustomWords::default());
+        assert_eq!(
+            matches[0].pattern,
+            MatchPattern::Unlock(UnlockPattern { knight_moves: 3 })
+        );
+
+        // passing over a dot, a repeated dot and 0, which has no dot
+        for password in ["1397", "14741", "2580"] {
+            assert!((UnlockMatch {})
+                .get_matches(password, &CustomWords::default())
+                .is_empty());
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! Matching two words typed one after the other: the common pairs of the bundled bigrams,
+//! such as "whitetiger" or "SarahJane", and with the `autosuggest` feature, any two of the
+//! words phone keyboards suggest as you type, such as "OkayThanks".
+//!
+//! A listed pair is guessed by its own rank, without the factorial and the additive term two
+//! separate matches would take in the optimal sequence. Other pairs are guessed as the product
+//! of the ranks of their words, each drawn from the dictionary on its own.
+
+use crate::frequency_lists::{self, DictionaryType};
+
+use super::patterns::{MatchPattern, WordPairPattern};
+use super::{CustomWords, Match, Matcher};
+
+/// The dictionaries any two words of which are matched as a pair, listed or not.
+const PAIR_DICTIONARIES: &[DictionaryType] = &[
+    #[cfg(feature = "autosuggest")]
+    DictionaryType::Autosuggest,
+];
+/// The fewest characters of each word of a pair that isn't listed as going together.
+const MIN_WORD_LEN: usize = 2;
+
+/// Matches two words next to each other.
+pub(super) struct WordPairMatch {}
+
+impl Matcher for WordPairMatch {
+    fn get_matches(&self, password: &str, _custom_words: &CustomWords) -> Vec<Match> {
+        let chars = password.chars().collect::<Vec<_>>();
+        let mut matches = listed_pairs(&chars);
+        for &dictionary in PAIR_DICTIONARIES {
+            // the words of the dictionary starting at each character
+            let words = (0..chars.len())
+                .map(|i| words_from(&chars, i, dictionary))
+                .collect::<Vec<_>>();
+            for (i, firsts) in words.iter().enumerate() {
+                for (k, first_word, first_rank) in firsts {
+                    for (j, second_word, second_rank) in words.get(k + 1).into_iter().flatten() {
+                        // listed pairs are already matched with their own rank
+                        let listed = frequency_lists::search_word_pairs(&format!(
+                            "{}{}",
+                            first_word, second_word
+                        ))
+                        .0
+                        .is_some_and(|(first_len, _, _)| first_len == first_word.len());
+                        if listed
+                            || first_word.chars().count() < MIN_WORD_LEN
+                            || second_word.chars().count() < MIN_WORD_LEN
+                        {
+                            continue;
+                        }
+                        matches.push(Match {
+                            pattern: MatchPattern::WordPair(WordPairPattern {
+                                first_word: first_word.clone(),
+                                second_word: second_word.clone(),
+                                first_rank: *first_rank,
+                                second_rank: *second_rank,
+                                pair_rank: None,
+                                dictionary_name: dictionary,
+                                ..WordPairPattern::default()
+                            }),
+                            i,
+                            j: *j,
+                            token: chars[i..=*j].iter().collect(),
+                            ..Match::default()
+                        });
+                    }
+                }
+            }
+        }
+        matches
+    }
+
+    fn in_js_implementation(&self) -> bool {
+        false
+    }
+}
+
+/// Matches the pairs of words listed as going together, with the rank of each word in the
+/// dictionary of the pair.
+fn listed_pairs(chars: &[char]) -> Vec<Match> {
+    let mut matches = Vec::new();
+    for i in 0..chars.len() {
+        let mut words = String::new();
+        for (j, c) in chars.iter().enumerate().skip(i) {
+            words.extend(c.to_lowercase());
+            let (pair, extendable) = frequency_lists::search_word_pairs(&words);
+            if let Some((first_len, dictionary, pair_rank)) = pair {
+                let (first_word, second_word) = words.split_at(first_len);
+                matches.push(Match {
+                    pattern: MatchPattern::WordPair(WordPairPattern {
+                        first_word: first_word.to_string(),
+                        second_word: second_word.to_string(),
+                        first_rank: rank_in(first_word, dictionary).unwrap_or_default(),
+                        second_rank: rank_in(second_word, dictionary).unwrap_or_default(),
+                        pair_rank: Some(pair_rank as usize),
+                        dictionary_name: dictionary,
+                        ..WordPairPattern::default()
+                    }),
+                    i,
+                    j,
+                    token: chars[i..=j].iter().collect(),
+                    ..Match::default()
+                });
+            }
+            if !extendable {
+                break;
+            }
+        }
+    }
+    matches
+}
+
+/// The rank of `word` in `dictionary`, if it is in it.
+fn rank_in(word: &str, dictionary: DictionaryType) -> Option<usize> {
+    frequency_lists::search(word)
+        .0
+        .into_iter()
+        .flatten()
+        .find(|&&(other, _)| other == dictionary)
+        .map(|&(_, rank)| rank as usize)
+}
+
+/// The words of `dictionary` starting at the `i`th character, each with its last character,
+/// lowercased, and with its rank.
+fn words_from(chars: &[char], i: usize, dictionary: DictionaryType) -> Vec<(usize, String, usize)> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for (j, c) in chars.iter().enumerate().skip(i) {
+        word.extend(c.to_lowercase());
+        if let Some(rank) = rank_in(&word, dictionary) {
+            words.push((j, word.clone(), rank));
+        }
+        if !frequency_lists::search(&word).1 {
+            break;
+        }
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matched(password: &str) -> Vec<(String, String, Option<usize>)> {
+        (WordPairMatch {})
+            .get_matches(password, &CustomWords::default())
+            .into_iter()
+            .filter(|m| m.token.chars().count() == password.chars().count())
+            .map(|m| match m.pattern {
+                MatchPattern::WordPair(p) => (p.first_word, p.second_word, p.pair_rank),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_bigram_matches() {
+        assert_eq!(
+            matched("WhiteTiger"),
+            [("white".to_string(), "tiger".to_string(), Some(3))]
+        );
+        assert_eq!(
+            matched("sarahjane"),
+            [("sarah".to_string(), "jane".to_string(), Some(16))]
+        );
+        assert!(matched("whitetige").is_empty());
+        assert!(matched("tigerwhite").is_empty());
+    }
+
+    #[test]
+    fn test_bigram_is_guessed_before_two_words() {
+        let entropy = crate::zxcvbn("sarahjane", &[]);
+        assert_eq!(entropy.sequence().len(), 1);
+        assert_eq!(entropy.sequence()[0].pattern.variant(), "word_pair");
+    }
+
+    #[cfg(feature = "autosuggest")]
+    #[test]
+    fn test_autosuggest_pair_matches() {
+        assert_eq!(
+            matched("OkayThanks"),
+            [("okay".to_string(), "thanks".to_string(), None)]
+        );
+        assert_eq!(
+            matched("thankyou"),
+            [("thank".to_string(), "you".to_string(), None)]
+        );
+        assert!(matched("thanks").is_empty());
+        assert!(matched("thankszebra").is_empty());
+    }
+
+    #[cfg(feature = "autosuggest")]
+    #[test]
+    fn test_autosuggest_pair_is_guessed_before_two_words() {
+        let entropy = crate::zxcvbn("sorryhoney", &[]);
+        assert_eq!(entropy.sequence().len(), 1);
+        assert_eq!(entropy.sequence()[0].pattern.variant(), "word_pair");
+    }
+}
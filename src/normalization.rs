@@ -0,0 +1,332 @@
+//! Normalization applied to passwords before matching, so that dictionary words dressed up
+//! with look-alike characters are still recognized.
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::matching::Match;
+
+/// Which normalizers run on a password before it is matched. All of them are enabled by
+/// default.
+///
+/// Matches are found in the normalized password, then moved back onto the password as given:
+/// their indexes count its characters and their tokens are the characters they cover there,
+/// while their patterns describe the normalized text, e.g. "pаsswоrd" with Cyrillic "а" and
+/// "о" is the dictionary word "password". Removed characters belong to the match before
+/// them, and a character normalized into several, like "ﬁ", to the match of each.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[cfg_attr(feature = "builder", builder(default))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+pub struct Normalizers {
+    /// Removes invisible characters such as zero-width spaces and joiners and soft hyphens.
+    pub strip_zero_width: bool,
+    /// Applies Unicode NFKC normalization, e.g. full-width "ｐａｓｓ" becomes "pass".
+    pub nfkc: bool,
+    /// Replaces characters that look like Latin letters, e.g. Cyrillic "о" or
+    /// Greek "Α", with the characters they look like.
+    pub confusables: bool,
+}
+
+impl Default for Normalizers {
+    fn default() -> Self {
+        Normalizers {
+            strip_zero_width: true,
+            nfkc: true,
+            confusables: true,
+        }
+    }
+}
+
+impl Normalizers {
+    /// No normalization at all, passwords are matched as given.
+    pub fn none() -> Self {
+        Normalizers {
+            strip_zero_width: false,
+            nfkc: false,
+            confusables: false,
+        }
+    }
+
+    /// The enabled normalizers, comma-separated, or "none".
+    pub(crate) fn tag(&self) -> String {
+        let enabled = [
+            ("zero-width", self.strip_zero_width),
+            ("nfkc", self.nfkc),
+            ("confusables", self.confusables),
+        ]
+        .into_iter()
+        .filter(|&(_, enabled)| enabled)
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+        if enabled.is_empty() {
+            "none".to_string()
+        } else {
+            enabled.join(",")
+        }
+    }
+
+    /// Runs the enabled normalizers on a password.
+    pub fn normalize(&self, password: &str) -> String {
+        // Most passwords are ASCII, which none of the normalizers change.
+        if password.is_ascii() {
+            return password.to_string();
+        }
+        let mut password = password.to_string();
+        if self.strip_zero_width {
+            password.retain(|c| !is_zero_width(c));
+        }
+        if self.nfkc {
+            password = password.nfkc().collect();
+        }
+        if self.confusables {
+            password = password.chars().map(unconfuse).collect();
+        }
+        password
+    }
+
+    /// Runs the enabled normalizers on a password like [`normalize`](Normalizers::normalize),
+    /// along with where each character of the result comes from in `password`.
+    pub(crate) fn normalize_with_origins(&self, password: &str) -> (String, Origins) {
+        let normalized = self.normalize(password);
+        let chars = password.chars().collect::<Vec<_>>();
+        let normalized_chars = normalized.chars().collect::<Vec<_>>();
+        let mut origins = Vec::with_capacity(normalized_chars.len());
+        if normalized_chars == chars {
+            origins.extend(0..chars.len());
+        } else {
+            // each character comes from the character of the password after which the
+            // normalized prefixes of the password first end with it
+            for k in 0..chars.len() {
+                let prefix = self.normalize(&chars[..=k].iter().collect::<String>());
+                let settled = prefix
+                    .chars()
+                    .zip(&normalized_chars)
+                    .take_while(|&(a, &b)| a == b)
+                    .count();
+                while origins.len() < settled {
+                    origins.push(k);
+                }
+            }
+        }
+        let changed = normalized_chars != chars;
+        (
+            normalized,
+            Origins {
+                password: chars,
+                origins,
+                changed,
+            },
+        )
+    }
+}
+
+/// Where each character of a normalized password comes from in the password as given.
+#[derive(Debug)]
+pub(crate) struct Origins {
+    password: Vec<char>,
+    origins: Vec<usize>,
+    /// Whether the normalized password differs from the password.
+    changed: bool,
+}
+
+impl Origins {
+    /// Moves a match of the normalized password onto the characters of the password it
+    /// was normalized from.
+    pub(crate) fn restore(&self, m: &mut Match) {
+        // characters removed from the start belong to the first match
+        let i = if m.i == 0 { 0 } else { self.origins[m.i] };
+        let j = match self.origins.get(m.j + 1) {
+            Some(&next) => next.saturating_sub(1).max(i),
+            None => self.password.len() - 1,
+        };
+        m.i = i;
+        m.j = j;
+        m.token = self.password[i..=j].iter().collect();
+    }
+
+    /// Keeps the first `len` characters of the normalized password, and the characters of
+    /// the password they come from.
+    pub(crate) fn truncate(&mut self, len: usize) {
+        if len < self.origins.len() {
+            let end =
+                self.origins[len].max(len.checked_sub(1).map_or(0, |last| self.origins[last] + 1));
+            self.password.truncate(end);
+            self.origins.truncate(len);
+        }
+    }
+
+    /// Whether normalizing left the password as it was.
+    pub(crate) fn is_identity(&self) -> bool {
+        !self.changed
+    }
+}
+
+fn is_zero_width(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00AD}' | '\u{180E}' | '\u{200B}'..='\u{200F}' | '\u{2060}'..='\u{2064}' | '\u{FEFF}'
+    )
+}
+
+/// Maps a character to the ASCII character it is most commonly mistaken for, if any.
+fn unconfuse(c: char) -> char {
+    match c {
+        // Cyrillic
+        'а' => 'a',
+        'А' => 'A',
+        'В' => 'B',
+        'с' => 'c',
+        'С' => 'C',
+        'е' | 'ё' => 'e',
+        'Е' | 'Ё' => 'E',
+        'Н' => 'H',
+        'і' => 'i',
+        'І' => 'I',
+        'ј' => 'j',
+        'Ј' => 'J',
+        'К' => 'K',
+        'М' => 'M',
+        'о' => 'o',
+        'О' => 'O',
+        'р' => 'p',
+        'Р' => 'P',
+        'ѕ' => 's',
+        'Ѕ' => 'S',
+        'Т' => 'T',
+        'х' => 'x',
+        'Х' => 'X',
+        'у' => 'y',
+        'У' => 'Y',
+        'ԁ' => 'd',
+        'һ' => 'h',
+        'ԛ' => 'q',
+        'ԝ' => 'w',
+        // Greek
+        'Α' => 'A',
+        'Β' => 'B',
+        'Ε' => 'E',
+        'Ζ' => 'Z',
+        'Η' => 'H',
+        'Ι' => 'I',
+        'Κ' => 'K',
+        'Μ' => 'M',
+        'Ν' => 'N',
+        'Ο' => 'O',
+        'Ρ' => 'P',
+        'Τ' => 'T',
+        'Υ' => 'Y',
+        'Χ' => 'X',
+        'ο' => 'o',
+        'ν' => 'v',
+        'ι' => 'i',
+        'κ' => 'k',
+        'ρ' => 'p',
+        'υ' => 'u',
+        // Others
+        'ı' => 'i',
+        'ℓ' => 'l',
+        c => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_normalize() {
+        let normalizers = Normalizers::default();
+        assert_eq!(normalizers.normalize("password"), "password");
+        assert_eq!(
+            normalizers.normalize("pass\u{200B}wo\u{00AD}rd"),
+            "password"
+        );
+        assert_eq!(normalizers.normalize("ｐａｓｓｗｏｒｄ"), "password");
+        assert_eq!(normalizers.normalize("pаsswоrd"), "password");
+        assert_eq!(normalizers.normalize("ΡΑSSWΟRD"), "PASSWORD");
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_normalizers_can_be_disabled() {
+        let password = "pаss\u{200B}ｗord";
+        assert_eq!(Normalizers::none().normalize(password), password);
+        let normalizers = Normalizers {
+            nfkc: false,
+            ..Normalizers::default()
+        };
+        assert_eq!(normalizers.normalize(password), "passｗord");
+        let normalizers = Normalizers {
+            confusables: false,
+            ..Normalizers::default()
+        };
+        assert_eq!(normalizers.normalize(password), "pаssword");
+        let normalizers = Normalizers {
+            strip_zero_width: false,
+            ..Normalizers::default()
+        };
+        assert_eq!(normalizers.normalize(password), "pass\u{200B}word");
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_matches_are_restored_to_the_password() {
+        let restored = |password: &str, i: usize, j: usize| {
+            let (normalized, origins) = Normalizers::default().normalize_with_origins(password);
+            let mut m = Match {
+                i,
+                j,
+                token: normalized.chars().take(j + 1).skip(i).collect(),
+                ..Match::default()
+            };
+            origins.restore(&mut m);
+            (m.i, m.j, m.token)
+        };
+        // "pass" and "word" of "\u{200B}pass\u{200B}wоrd"
+        let password = "\u{200B}pass\u{200B}wоrd";
+        assert_eq!(
+            restored(password, 0, 3),
+            (0, 5, "\u{200B}pass\u{200B}".to_string())
+        );
+        assert_eq!(restored(password, 4, 7), (6, 9, "wоrd".to_string()));
+        // "ﬁ" is normalized into "fi", whose characters both come from it
+        assert_eq!(restored("ﬁre", 0, 0), (0, 0, "ﬁ".to_string()));
+        assert_eq!(restored("ﬁre", 1, 3), (0, 2, "ﬁre".to_string()));
+
+        let (_, mut origins) = Normalizers::default().normalize_with_origins("ａｂｃ");
+        origins.truncate(2);
+        let mut m = Match {
+            i: 1,
+            j: 1,
+            token: "b".to_string(),
+            ..Match::default()
+        };
+        origins.restore(&mut m);
+        assert_eq!(m.token, "ｂ");
+        assert!(Normalizers::default()
+            .normalize_with_origins("abc")
+            .1
+            .is_identity());
+    }
+
+    #[cfg(feature = "builder")]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_normalizers_builder() {
+        let normalizers = NormalizersBuilder::default()
+            .confusables(false)
+            .build()
+            .unwrap();
+        assert_eq!(
+            normalizers,
+            Normalizers {
+                confusables: false,
+                ..Normalizers::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,338 @@
+//! The classic Active Directory password complexity rules, so that organizations moving to
+//! zxcvbn scores can check passwords against both and compare what each rejects.
+//!
+//! A password meets the rules if it is long enough, has characters of at least three of the
+//! character classes and contains neither the account name nor any token of the display
+//! name, ignoring case.
+//!
+//! ```
+//! use zxcvbn::policy::ad_compat::{check, AdAccount, AdComplexity, AdRejection};
+//!
+//! let account = AdAccount {
+//!     sam_account_name: "jsmith",
+//!     display_name: "Smith, John",
+//! };
+//! let policy = AdComplexity::default();
+//! assert!(check("Summer2026!", &account, &policy).is_ok());
+//! assert_eq!(
+//!     check("John2026!", &account, &policy),
+//!     Err(AdRejection::ContainsDisplayNameToken {
+//!         token: "John".to_string()
+//!     })
+//! );
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use crate::requirements::{Rejection, Requirements};
+use crate::{Entropy, Zxcvbn};
+
+/// Account and display name tokens shorter than this are not checked, as AD does.
+const MIN_NAME_LENGTH: usize = 3;
+/// The characters AD counts as non-alphanumeric.
+const SPECIAL_CHARACTERS: &str = "~!@#$%^&*_-+=`|\\(){}[]:;\"'<>,.?/";
+/// The characters display names are split into tokens at.
+const DISPLAY_NAME_DELIMITERS: &[char] = &[',', '.', '-', '_', ' ', '#', '\t'];
+
+/// The user a password is for, as Active Directory knows them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AdAccount<'a> {
+    /// The account name, e.g. "jsmith".
+    pub sam_account_name: &'a str,
+    /// The full name, e.g. "Smith, John".
+    pub display_name: &'a str,
+}
+
+impl<'a> AdAccount<'a> {
+    /// The tokens of the display name that passwords may not contain: the parts between
+    /// commas, periods, dashes, underscores, spaces, pound signs and tabs that are at least
+    /// three characters long.
+    pub fn display_name_tokens(&self) -> Vec<&'a str> {
+        self.display_name
+            .split(DISPLAY_NAME_DELIMITERS)
+            .filter(|token| token.chars().count() >= MIN_NAME_LENGTH)
+            .collect()
+    }
+
+    /// The account name and display name tokens, to estimate passwords with as user inputs.
+    pub fn user_inputs(&self) -> Vec<&'a str> {
+        std::iter::once(self.sam_account_name)
+            .chain(self.display_name_tokens())
+            .filter(|input| !input.is_empty())
+            .collect()
+    }
+}
+
+/// The settings of the complexity rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[cfg_attr(feature = "builder", builder(default))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+pub struct AdComplexity {
+    /// Minimum number of characters. Defaults to 7, the minimum password length of the
+    /// default domain policy.
+    pub min_len: usize,
+    /// Minimum number of character classes. Defaults to 3.
+    pub min_char_classes: usize,
+}
+
+impl Default for AdComplexity {
+    fn default() -> Self {
+        AdComplexity {
+            min_len: 7,
+            min_char_classes: 3,
+        }
+    }
+}
+
+/// Why a password does not meet the [`AdComplexity`] rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdRejection {
+    /// The password has fewer characters than required.
+    TooShort {
+        /// Number of characters in the password.
+        len: usize,
+        /// Minimum number of characters.
+        min_len: usize,
+    },
+    /// The password contains the account name.
+    ContainsAccountName,
+    /// The password contains a token of the display name.
+    ContainsDisplayNameToken {
+        /// The token, as it appears in the display name.
+        token: String,
+    },
+    /// The password has characters of too few character classes.
+    TooFewCharClasses {
+        /// Number of character classes in the password.
+        char_classes: usize,
+        /// Minimum number of character classes.
+        min_char_classes: usize,
+    },
+}
+
+impl fmt::Display for AdRejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AdRejection::TooShort { len, min_len } => write!(
+                f,
+                "password is {len} characters long, at least {min_len} are required"
+            ),
+            AdRejection::ContainsAccountName => write!(f, "password contains the account name"),
+            AdRejection::ContainsDisplayNameToken { token } => {
+                write!(f, "password contains \"{token}\" from the display name")
+            }
+            AdRejection::TooFewCharClasses {
+                char_classes,
+                min_char_classes,
+            } => write!(
+                f,
+                "password has {char_classes} kinds of characters, at least {min_char_classes} \
+                 are required"
+            ),
+        }
+    }
+}
+
+impl Error for AdRejection {}
+
+/// The number of character classes in a password, out of uppercase letters, lowercase
+/// letters, digits, the special characters AD counts and letters without case, such as
+/// those of Asian languages.
+pub fn char_classes(password: &str) -> usize {
+    let classes: [fn(char) -> bool; 5] = [
+        char::is_uppercase,
+        char::is_lowercase,
+        |c| c.is_ascii_digit(),
+        |c| SPECIAL_CHARACTERS.contains(c),
+        |c| c.is_alphabetic() && !c.is_uppercase() && !c.is_lowercase(),
+    ];
+    classes
+        .into_iter()
+        .filter(|class| password.chars().any(class))
+        .count()
+}
+
+/// Checks a password against the complexity rules, in the order AD reports them.
+pub fn check(
+    password: &str,
+    account: &AdAccount,
+    policy: &AdComplexity,
+) -> Result<(), AdRejection> {
+    let len = password.chars().count();
+    if len < policy.min_len {
+        return Err(AdRejection::TooShort {
+            len,
+            min_len: policy.min_len,
+        });
+    }
+    let lowercase = password.to_lowercase();
+    if account.sam_account_name.chars().count() >= MIN_NAME_LENGTH
+        && lowercase.contains(&account.sam_account_name.to_lowercase())
+    {
+        return Err(AdRejection::ContainsAccountName);
+    }
+    let token = account
+        .display_name_tokens()
+        .into_iter()
+        .find(|token| lowercase.contains(&token.to_lowercase()));
+    if let Some(token) = token {
+        return Err(AdRejection::ContainsDisplayNameToken {
+            token: token.to_string(),
+        });
+    }
+    let char_classes = char_classes(password);
+    if char_classes < policy.min_char_classes {
+        return Err(AdRejection::TooFewCharClasses {
+            char_classes,
+            min_char_classes: policy.min_char_classes,
+        });
+    }
+    Ok(())
+}
+
+/// The verdicts of the complexity rules and of the zxcvbn requirements on one password.
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    /// The verdict of the complexity rules.
+    pub ad: Result<(), AdRejection>,
+    /// The verdict of the requirements, with the estimate of the password if it was made.
+    pub zxcvbn: Result<Entropy, Rejection>,
+}
+
+impl Comparison {
+    /// Whether both accept the password or both reject it.
+    pub fn agrees(&self) -> bool {
+        self.ad.is_ok() == self.zxcvbn.is_ok()
+    }
+}
+
+/// Checks a password against both the complexity rules and the requirements, estimating it
+/// with the account name and display name tokens as user inputs.
+///
+/// ```
+/// use zxcvbn::policy::ad_compat::{compare, AdAccount, AdComplexity};
+/// use zxcvbn::{Requirements, Zxcvbn};
+///
+/// let account = AdAccount {
+///     sam_account_name: "jsmith",
+///     display_name: "John Smith",
+/// };
+/// let estimator = Zxcvbn::new();
+/// let requirements = Requirements::default();
+/// // complex enough for AD, but one of the most common passwords
+/// let comparison = compare(&estimator, "P@ssw0rd", &account, &AdComplexity::default(), &requirements);
+/// assert!(comparison.ad.is_ok());
+/// assert!(comparison.zxcvbn.is_err());
+/// assert!(!comparison.agrees());
+/// ```
+pub fn compare(
+    estimator: &Zxcvbn,
+    password: &str,
+    account: &AdAccount,
+    policy: &AdComplexity,
+    requirements: &Requirements,
+) -> Comparison {
+    Comparison {
+        ad: check(password, account, policy),
+        zxcvbn: estimator.meets_requirements(password, &account.user_inputs(), requirements),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    fn account() -> AdAccount<'static> {
+        AdAccount {
+            sam_account_name: "jsmith",
+            display_name: "Smith, John Q. - IT",
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_display_name_tokens() {
+        assert_eq!(account().display_name_tokens(), ["Smith", "John"]);
+        assert_eq!(account().user_inputs(), ["jsmith", "Smith", "John"]);
+        assert!(AdAccount::default().user_inputs().is_empty());
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_char_classes() {
+        assert_eq!(char_classes("password"), 1);
+        assert_eq!(char_classes("Password"), 2);
+        assert_eq!(char_classes("Passw0rd"), 3);
+        assert_eq!(char_classes("P@ssw0rd"), 4);
+        assert_eq!(char_classes("P@ssw0rd密码"), 5);
+        // only the special characters AD knows count
+        assert_eq!(char_classes("password€"), 1);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_check() {
+        let policy = AdComplexity::default();
+        assert_eq!(
+            check("Ab1!", &account(), &policy),
+            Err(AdRejection::TooShort { len: 4, min_len: 7 })
+        );
+        assert_eq!(
+            check("JSmith2026!", &account(), &policy),
+            Err(AdRejection::ContainsAccountName)
+        );
+        assert_eq!(
+            check("Q7#johnny", &account(), &policy),
+            Err(AdRejection::ContainsDisplayNameToken {
+                token: "John".to_string()
+            })
+        );
+        assert_eq!(
+            check("correcthorsebatterystaple", &account(), &policy),
+            Err(AdRejection::TooFewCharClasses {
+                char_classes: 1,
+                min_char_classes: 3
+            })
+        );
+        // short tokens like "Q" and "IT" are allowed
+        assert!(check("Quit2026!", &account(), &policy).is_ok());
+        assert_eq!(
+            AdRejection::ContainsAccountName.to_string(),
+            "password contains the account name"
+        );
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_compare() {
+        let estimator = Zxcvbn::new();
+        let policy = AdComplexity::default();
+        let requirements = Requirements::default();
+        let compare = |password| compare(&estimator, password, &account(), &policy, &requirements);
+
+        let comparison = compare("correcthorsebatterystaple");
+        assert_eq!(
+            comparison.ad,
+            Err(AdRejection::TooFewCharClasses {
+                char_classes: 1,
+                min_char_classes: 3
+            })
+        );
+        assert!(comparison.zxcvbn.is_ok());
+        assert!(!comparison.agrees());
+
+        // the display name tokens are user inputs to zxcvbn too
+        let comparison = compare("Smith1990!");
+        assert!(comparison.ad.is_err());
+        assert!(matches!(
+            comparison.zxcvbn,
+            Err(Rejection::ContainsUserInput { .. })
+        ));
+        assert!(comparison.agrees());
+    }
+}
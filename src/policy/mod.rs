@@ -0,0 +1,4 @@
+//! Password policies other than zxcvbn's own, to run alongside the
+//! [`requirements`](crate::requirements) while migrating off them.
+
+pub mod ad_compat;
@@ -0,0 +1,177 @@
+//! Named presets of normalizers, matchers, dictionaries and requirements.
+//!
+//! ```
+//! use zxcvbn::profile::Profile;
+//! use zxcvbn::{Rejection, Zxcvbn};
+//!
+//! let profile = Profile::Strict;
+//! let estimator = Zxcvbn::from_profile(profile);
+//! assert!(matches!(
+//!     estimator.meets_requirements("correcthorsebatterystaple", &[], &profile.requirements()),
+//!     Err(Rejection::MissingUserInputs)
+//! ));
+//! ```
+
+use crate::normalization::Normalizers;
+use crate::requirements::Requirements;
+use crate::Score;
+
+/// A preset for [`Zxcvbn::from_profile`](crate::Zxcvbn::from_profile).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+pub enum Profile {
+    /// For accounts worth attacking: every matcher, every dictionary compiled in, and
+    /// requirements asking for 12 characters, the highest score and the user's personal
+    /// data to check the password against. Enable the `wordlist-packs` feature to match
+    /// the most words.
+    Strict,
+    /// What [`zxcvbn`](crate::zxcvbn) and [`Zxcvbn::new`](crate::Zxcvbn::new) do: every
+    /// matcher, every dictionary compiled in and the default [`Requirements`].
+    #[default]
+    Balanced,
+    /// Only what the JavaScript implementation does, to compare estimates with it: no
+    /// normalization, none of the matchers or patterns it lacks, such as mirrored text,
+    /// emoji or keyboard regions, and only its dictionaries. Matches are found and ties broken
+    /// in its order, see
+    /// [`ScoringParams::js_match_order`](crate::scoring::ScoringParams::js_match_order). The
+    /// default [`Requirements`] apply.
+    LegacyJsCompatible,
+}
+
+impl Profile {
+    /// The normalizers run on passwords before matching.
+    pub fn normalizers(self) -> Normalizers {
+        match self {
+            Profile::Strict | Profile::Balanced => Normalizers::default(),
+            Profile::LegacyJsCompatible => Normalizers::none(),
+        }
+    }
+
+    /// The requirements passwords are checked against with
+    /// [`Zxcvbn::meets_requirements`](crate::Zxcvbn::meets_requirements).
+    pub fn requirements(self) -> Requirements {
+        match self {
+            Profile::Strict => Requirements {
+                min_len: 12,
+                min_score: Score::VeryUnguessable,
+                forbid_user_inputs: true,
+                require_user_inputs: true,
+            },
+            Profile::Balanced | Profile::LegacyJsCompatible => Requirements::default(),
+        }
+    }
+
+    /// Whether matching is limited to the matchers, patterns and dictionaries of the
+    /// JavaScript implementation.
+    pub(crate) fn js_compatible(self) -> bool {
+        self == Profile::LegacyJsCompatible
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Rejection, Zxcvbn};
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_balanced_is_the_default() {
+        let password = "qwertyuiop\u{200B}Xk7#";
+        assert_eq!(
+            Zxcvbn::from_profile(Profile::Balanced)
+                .estimate(password, &[])
+                .guesses(),
+            crate::zxcvbn(password, &[]).guesses()
+        );
+        assert_eq!(Profile::default().requirements(), Requirements::default());
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_legacy_js_compatible() {
+        let estimator = Zxcvbn::from_profile(Profile::LegacyJsCompatible);
+        // keyboard rows are only matched as ordinary spatial patterns
+        let entropy = estimator.estimate("qwertyuiop", &[]);
+        assert!(entropy.guesses() > crate::zxcvbn("qwertyuiop", &[]).guesses());
+        // no mirror matches
+        let entropy = estimator.estimate("Xk7#qzzq#7kX", &[]);
+        assert!(entropy
+            .all_matches()
+            .iter()
+            .all(|m| m.pattern.variant() != "mirror"));
+        // only years are matched by regexes
+        let entropy = estimator.estimate("alt+0169", &[]);
+        assert!(entropy
+            .all_matches()
+            .iter()
+            .all(|m| m.pattern.variant() != "regex"));
+        // passwords are not normalized
+        assert!(estimator.estimate("pаsswоrd", &[]).score() > Score::TooGuessable);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_legacy_js_compatible_matches_zxcvbn_js() {
+        // guesses, scores and match sequences of zxcvbn.js 4.4.2
+        let vectors: &[(&str, u64, Score, &[&str])] = &[
+            ("password", 3, Score::TooGuessable, &["password"]),
+            ("drowssap", 5, Score::TooGuessable, &["drowssap"]),
+            ("p@ssw0rd", 9, Score::TooGuessable, &["p@ssw0rd"]),
+            ("P@ssw0rd", 17, Score::TooGuessable, &["P@ssw0rd"]),
+            ("Password1", 379, Score::TooGuessable, &["Password1"]),
+            ("abcdefg", 29, Score::TooGuessable, &["abcdefg"]),
+            ("aaaaaaaa", 97, Score::TooGuessable, &["aaaaaaaa"]),
+            ("horse7", 25_422, Score::VeryGuessable, &["horse", "7"]),
+            (
+                "password!",
+                11_100,
+                Score::VeryGuessable,
+                &["password", "!"],
+            ),
+            (
+                "correcthorsebatterystaple",
+                273_500_327_700_640,
+                Score::VeryUnguessable,
+                &["correct", "horse", "battery", "staple"],
+            ),
+        ];
+        let estimator = Zxcvbn::from_profile(Profile::LegacyJsCompatible);
+        for &(password, guesses, score, tokens) in vectors {
+            let entropy = estimator.estimate(password, &[]);
+            assert_eq!(entropy.guesses(), guesses, "{}", password);
+            assert_eq!(entropy.score(), score, "{}", password);
+            assert_eq!(
+                entropy
+                    .sequence()
+                    .iter()
+                    .map(|m| m.token.as_str())
+                    .collect::<Vec<_>>(),
+                tokens,
+                "{}",
+                password
+            );
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_strict_requirements() {
+        let profile = Profile::Strict;
+        let estimator = Zxcvbn::from_profile(profile);
+        let requirements = profile.requirements();
+        assert!(matches!(
+            estimator.meets_requirements("Xk7#q*zP2!vR", &[], &requirements),
+            Err(Rejection::MissingUserInputs)
+        ));
+        assert!(matches!(
+            estimator.meets_requirements("Xk7#q*zP2!v", &["jsmith"], &requirements),
+            Err(Rejection::TooShort { .. })
+        ));
+        assert!(estimator
+            .meets_requirements("Xk7#q*zP2!vR", &["jsmith"], &requirements)
+            .is_ok());
+    }
+}
@@ -0,0 +1,372 @@
+//! Opt-in reports of how weak estimated passwords are, safe to log or aggregate.
+//!
+//! A report never holds any part of the password: only its score, a range its length falls
+//! in, the kinds of patterns it was made of and the warning it got. Products can use them
+//! to follow password quality over time without logging password material.
+//!
+//! ```
+//! use std::sync::{Arc, Mutex};
+//! use zxcvbn::reporting::LengthBucket;
+//! use zxcvbn::Zxcvbn;
+//!
+//! let reports = Arc::new(Mutex::new(Vec::new()));
+//! let sink = Arc::clone(&reports);
+//! let estimator = Zxcvbn::new().with_reporter(move |report| {
+//!     sink.lock().unwrap().push(report.clone());
+//! });
+//! estimator.estimate("password123", &[]);
+//!
+//! let reports = reports.lock().unwrap();
+//! assert_eq!(reports[0].length_bucket, LengthBucket::From8To11);
+//! assert_eq!(reports[0].pattern_counts["dictionary"], 1);
+//! ```
+//!
+//! Reports can be tallied in [`WeaknessCounts`], which can add differentially private
+//! noise to its counters before they are published.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::feedback::Warning;
+use crate::{Entropy, Score, Zxcvbn};
+
+/// The range the length of a password falls in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+pub enum LengthBucket {
+    /// Fewer than 8 characters.
+    UpTo7,
+    /// 8 to 11 characters.
+    From8To11,
+    /// 12 to 15 characters.
+    From12To15,
+    /// 16 to 19 characters.
+    From16To19,
+    /// 20 characters or more.
+    From20,
+}
+
+impl LengthBucket {
+    /// The bucket of a password of `len` characters.
+    pub fn from_len(len: usize) -> Self {
+        match len {
+            0..=7 => LengthBucket::UpTo7,
+            8..=11 => LengthBucket::From8To11,
+            12..=15 => LengthBucket::From12To15,
+            16..=19 => LengthBucket::From16To19,
+            _ => LengthBucket::From20,
+        }
+    }
+}
+
+impl fmt::Display for LengthBucket {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let range = match self {
+            LengthBucket::UpTo7 => "0-7",
+            LengthBucket::From8To11 => "8-11",
+            LengthBucket::From12To15 => "12-15",
+            LengthBucket::From16To19 => "16-19",
+            LengthBucket::From20 => "20+",
+        };
+        write!(f, "{range}")
+    }
+}
+
+/// What an estimate revealed about a password, without any of the password itself.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+pub struct WeaknessReport {
+    /// Score of the password.
+    pub score: Score,
+    /// The range the length of the password falls in.
+    pub length_bucket: LengthBucket,
+    /// Number of matches of each pattern kind, such as "dictionary" or "bruteforce", in the
+    /// sequence the guesses were based on.
+    pub pattern_counts: BTreeMap<&'static str, usize>,
+    /// The warning of the feedback, if any.
+    pub warning: Option<Warning>,
+}
+
+impl WeaknessReport {
+    /// The report of an estimate of a password of `len` characters.
+    pub(crate) fn new(entropy: &Entropy, len: usize) -> Self {
+        let mut pattern_counts = BTreeMap::new();
+        for m in entropy.sequence() {
+            *pattern_counts.entry(m.pattern.variant()).or_insert(0) += 1;
+        }
+        WeaknessReport {
+            score: entropy.score(),
+            length_bucket: LengthBucket::from_len(len),
+            pattern_counts,
+            warning: entropy
+                .feedback()
+                .as_ref()
+                .and_then(|feedback| feedback.warning()),
+        }
+    }
+}
+
+/// Kinds of patterns, as named by [`MatchPattern::variant`](crate::matching::patterns::MatchPattern::variant).
+const PATTERN_KINDS: [&str; 18] = [
+    "dictionary",
+    "spatial",
+    "repeat",
+    "sequence",
+    "regex",
+    "date",
+    "emoji",
+    "interleaved",
+    "mirror",
+    "shift_repeat",
+    "echo",
+    "phone",
+    "national_id",
+    "payment",
+    "unlock",
+    "word_pair",
+    "acronym",
+    "bruteforce",
+];
+
+/// Counters of weakness reports, e.g. of a day of sign-ups, for password quality
+/// dashboards.
+///
+/// ```
+/// use zxcvbn::reporting::{WeaknessCounts, WeaknessReport};
+/// use zxcvbn::Score;
+///
+/// let mut counts = WeaknessCounts::default();
+/// # let reports: Vec<WeaknessReport> = Vec::new();
+/// for report in &reports {
+///     counts.add(report);
+/// }
+/// // use a cryptographically secure generator instead
+/// let mut seed = 1u64;
+/// let uniform = || {
+///     seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+///     (seed >> 11) as f64 / (1u64 << 53) as f64
+/// };
+/// let published = counts.with_noise(1.0, uniform);
+/// assert!(published.scores.contains_key(&Score::VeryUnguessable));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+pub struct WeaknessCounts {
+    /// Number of reports with each score.
+    pub scores: BTreeMap<Score, u64>,
+    /// Number of reports in each length range.
+    pub length_buckets: BTreeMap<LengthBucket, u64>,
+    /// Number of reports with at least one match of each pattern kind.
+    pub pattern_kinds: BTreeMap<&'static str, u64>,
+}
+
+impl WeaknessCounts {
+    /// Counts a report.
+    pub fn add(&mut self, report: &WeaknessReport) {
+        *self.scores.entry(report.score).or_insert(0) += 1;
+        *self.length_buckets.entry(report.length_bucket).or_insert(0) += 1;
+        for &kind in report.pattern_counts.keys() {
+            *self.pattern_kinds.entry(kind).or_insert(0) += 1;
+        }
+    }
+
+    /// The counters with Laplace noise added, making them `epsilon`-differentially private
+    /// with respect to adding or removing a single report. Smaller values of `epsilon` give
+    /// stronger guarantees and noisier counters.
+    ///
+    /// The privacy budget is split evenly between scores, length ranges and pattern kinds.
+    /// Every score, length range and pattern kind gets a counter, even those no report
+    /// counted, so that their absence reveals nothing. Noisy counters are rounded and
+    /// clamped at zero, which does not weaken the guarantee.
+    ///
+    /// `uniform` must return uniformly distributed numbers in `[0, 1)` from a
+    /// cryptographically secure generator, or the noise can be predicted and removed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `epsilon` is not a positive finite number.
+    pub fn with_noise<F>(&self, epsilon: f64, mut uniform: F) -> WeaknessCounts
+    where
+        F: FnMut() -> f64,
+    {
+        assert!(
+            epsilon > 0.0 && epsilon.is_finite(),
+            "epsilon must be positive and finite"
+        );
+        let epsilon = epsilon / 3.0;
+        let mut noisy = |count: Option<&u64>, sensitivity: usize| {
+            let scale = sensitivity as f64 / epsilon;
+            let count = count.copied().unwrap_or(0) as f64 + laplace(scale, uniform());
+            count.round().max(0.0) as u64
+        };
+        let scores = (0..=4)
+            .map(|score| Score::try_from(score).unwrap())
+            .map(|score| (score, noisy(self.scores.get(&score), 1)))
+            .collect();
+        let length_buckets = [
+            LengthBucket::UpTo7,
+            LengthBucket::From8To11,
+            LengthBucket::From12To15,
+            LengthBucket::From16To19,
+            LengthBucket::From20,
+        ]
+        .into_iter()
+        .map(|bucket| (bucket, noisy(self.length_buckets.get(&bucket), 1)))
+        .collect();
+        // a report counts once for each kind of pattern it has
+        let pattern_kinds = PATTERN_KINDS
+            .into_iter()
+            .map(|kind| {
+                let count = noisy(self.pattern_kinds.get(kind), PATTERN_KINDS.len());
+                (kind, count)
+            })
+            .collect();
+        WeaknessCounts {
+            scores,
+            length_buckets,
+            pattern_kinds,
+        }
+    }
+}
+
+/// A sample of the Laplace distribution centered on zero, from a uniform sample in `[0, 1)`.
+fn laplace(scale: f64, uniform: f64) -> f64 {
+    let u = uniform - 0.5;
+    // a sample of exactly 0 would give an infinite magnitude
+    let magnitude = -scale * (1.0 - 2.0 * u.abs()).max(f64::MIN_POSITIVE).ln();
+    if u < 0.0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+/// The callback reports are passed to.
+#[derive(Clone)]
+pub(crate) struct Reporter(Arc<dyn Fn(&WeaknessReport) + Send + Sync>);
+
+impl Reporter {
+    pub(crate) fn report(&self, report: &WeaknessReport) {
+        (self.0)(report)
+    }
+}
+
+impl fmt::Debug for Reporter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Reporter")
+    }
+}
+
+impl Zxcvbn {
+    /// Passes a [`WeaknessReport`] of every password this estimator estimates to
+    /// `reporter`, on the thread estimating it.
+    pub fn with_reporter<F>(mut self, reporter: F) -> Self
+    where
+        F: Fn(&WeaknessReport) + Send + Sync + 'static,
+    {
+        self.reporter = Some(Reporter(Arc::new(reporter)));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    /// A predictable generator, good enough for tests.
+    fn lcg(mut seed: u64) -> impl FnMut() -> f64 {
+        move || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 11) as f64 / (1u64 << 53) as f64
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_length_buckets() {
+        let test_data = [
+            (0, "0-7"),
+            (7, "0-7"),
+            (8, "8-11"),
+            (15, "12-15"),
+            (16, "16-19"),
+            (100, "20+"),
+        ];
+        for (len, expected) in test_data {
+            assert_eq!(LengthBucket::from_len(len).to_string(), expected);
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_reports() {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&reports);
+        let estimator = Zxcvbn::new().with_reporter(move |report: &WeaknessReport| {
+            sink.lock().unwrap().push(report.clone());
+        });
+        estimator.add_banned_passwords(["hunter2"]);
+        estimator.estimate("qwerty2024", &[]);
+        estimator.estimate("hunter2", &[]);
+        estimator.estimate("correcthorsebatterystaple", &[]);
+
+        let reports = reports.lock().unwrap();
+        assert_eq!(reports.len(), 3);
+        assert_eq!(reports[0].length_bucket, LengthBucket::From8To11);
+        assert_eq!(reports[0].pattern_counts["dictionary"], 1);
+        assert_eq!(reports[0].pattern_counts["regex"], 1);
+        assert_eq!(reports[0].score, Score::VeryGuessable);
+        assert!(reports[0].warning.is_some());
+        assert!(reports[1].pattern_counts.is_empty());
+        assert_eq!(reports[2].length_bucket, LengthBucket::From20);
+        assert_eq!(reports[2].warning, None);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_weakness_counts() {
+        let mut counts = WeaknessCounts::default();
+        for password in ["password", "qwerty2024", "correcthorsebatterystaple"] {
+            let entropy = crate::zxcvbn(password, &[]);
+            counts.add(&WeaknessReport::new(&entropy, password.chars().count()));
+        }
+        assert_eq!(counts.scores[&Score::TooGuessable], 1);
+        assert_eq!(counts.length_buckets[&LengthBucket::From8To11], 2);
+        assert_eq!(counts.pattern_kinds["dictionary"], 3);
+        assert_eq!(counts.pattern_kinds.get("spatial"), None);
+
+        // every counter is published, and barely changes with a large epsilon
+        let noisy = counts.with_noise(1e9, lcg(7));
+        assert_eq!(noisy.scores.len(), 5);
+        assert_eq!(noisy.length_buckets.len(), 5);
+        assert_eq!(noisy.pattern_kinds.len(), PATTERN_KINDS.len());
+        assert_eq!(noisy.scores[&Score::TooGuessable], 1);
+        assert_eq!(noisy.pattern_kinds["dictionary"], 3);
+        assert_eq!(noisy.pattern_kinds["spatial"], 0);
+
+        // a small epsilon hides the counters
+        let noisy = counts.with_noise(0.01, lcg(7));
+        assert_ne!(noisy, counts.with_noise(1e9, lcg(7)));
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_laplace() {
+        assert_eq!(laplace(1.0, 0.5), 0.0);
+        assert!(laplace(1.0, 0.25) < 0.0);
+        assert!(laplace(1.0, 0.75) > 0.0);
+        assert!(laplace(1.0, 0.0).is_finite());
+        // the mean absolute deviation is the scale
+        let mut uniform = lcg(1);
+        let n = 100_000;
+        let mean = (0..n).map(|_| laplace(2.0, uniform()).abs()).sum::<f64>() / n as f64;
+        assert!((mean - 2.0).abs() < 0.05, "{}", mean);
+    }
+}
@@ -0,0 +1,249 @@
+//! A ready-made password policy for consumers that only need a yes or no with a reason.
+//!
+//! ```
+//! use zxcvbn::requirements::{meets_requirements, Rejection, Requirements};
+//!
+//! let requirements = Requirements::default();
+//! assert!(meets_requirements("correcthorsebatterystaple", &[], &requirements).is_ok());
+//! assert!(matches!(
+//!     meets_requirements("jsmith2024!", &["jsmith"], &requirements),
+//!     Err(Rejection::ContainsUserInput { .. })
+//! ));
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use crate::feedback::Feedback;
+use crate::{Entropy, Score, Zxcvbn};
+
+/// User inputs shorter than this are too common to forbid inside passwords.
+const MIN_FORBIDDEN_USER_INPUT_LENGTH: usize = 3;
+
+/// What a password needs to be accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[cfg_attr(feature = "builder", builder(default))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+pub struct Requirements {
+    /// Minimum number of characters. Shorter passwords are rejected without being estimated.
+    /// Defaults to 8.
+    pub min_len: usize,
+    /// Minimum score. Defaults to [`Score::SafelyUnguessable`], the lowest score that gets no
+    /// feedback.
+    pub min_score: Score,
+    /// Whether to reject passwords containing one of the user inputs, ignoring case.
+    /// Defaults to true.
+    pub forbid_user_inputs: bool,
+    /// Whether to reject passwords checked without any user inputs, so that they are always
+    /// checked against the user's personal data. Defaults to false.
+    pub require_user_inputs: bool,
+}
+
+impl Default for Requirements {
+    fn default() -> Self {
+        Requirements {
+            min_len: 8,
+            min_score: Score::SafelyUnguessable,
+            forbid_user_inputs: true,
+            require_user_inputs: false,
+        }
+    }
+}
+
+/// Why a password does not meet the [`Requirements`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rejection {
+    /// The password has fewer characters than required.
+    TooShort {
+        /// Number of characters in the password.
+        len: usize,
+        /// Minimum number of characters.
+        min_len: usize,
+    },
+    /// No user inputs were given although they are required.
+    MissingUserInputs,
+    /// The password contains one of the user inputs.
+    ContainsUserInput {
+        /// The user input, as given.
+        user_input: String,
+    },
+    /// The password scores lower than required.
+    TooGuessable {
+        /// Score of the password.
+        score: Score,
+        /// Minimum score.
+        min_score: Score,
+        /// Feedback to help choose a better password.
+        feedback: Option<Feedback>,
+    },
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rejection::TooShort { len, min_len } => write!(
+                f,
+                "password is {len} characters long, at least {min_len} are required"
+            ),
+            Rejection::MissingUserInputs => {
+                write!(f, "user inputs are required to check the password")
+            }
+            Rejection::ContainsUserInput { user_input } => {
+                write!(f, "password contains \"{user_input}\"")
+            }
+            Rejection::TooGuessable {
+                score, min_score, ..
+            } => write!(
+                f,
+                "password scores {score}, a score of at least {min_score} is required"
+            ),
+        }
+    }
+}
+
+impl Error for Rejection {}
+
+/// Checks a password against the requirements, returning its estimate if it meets them.
+///
+/// The length and user inputs are checked before estimating the password, so short
+/// passwords are rejected quickly.
+pub fn meets_requirements(
+    password: &str,
+    user_inputs: &[&str],
+    requirements: &Requirements,
+) -> Result<Entropy, Rejection> {
+    check(password, user_inputs, requirements, || {
+        crate::zxcvbn(password, user_inputs)
+    })
+}
+
+impl Zxcvbn {
+    /// Checks a password against the requirements like [`meets_requirements`], estimating
+    /// it with the banned words and passwords of this estimator.
+    pub fn meets_requirements(
+        &self,
+        password: &str,
+        user_inputs: &[&str],
+        requirements: &Requirements,
+    ) -> Result<Entropy, Rejection> {
+        check(password, user_inputs, requirements, || {
+            self.estimate(password, user_inputs)
+        })
+    }
+}
+
+fn check<F>(
+    password: &str,
+    user_inputs: &[&str],
+    requirements: &Requirements,
+    estimate: F,
+) -> Result<Entropy, Rejection>
+where
+    F: FnOnce() -> Entropy,
+{
+    let len = password.chars().count();
+    if len < requirements.min_len {
+        return Err(Rejection::TooShort {
+            len,
+            min_len: requirements.min_len,
+        });
+    }
+    if requirements.require_user_inputs && user_inputs.is_empty() {
+        return Err(Rejection::MissingUserInputs);
+    }
+    if requirements.forbid_user_inputs {
+        let lowercase = password.to_lowercase();
+        let forbidden = user_inputs.iter().find(|user_input| {
+            user_input.chars().count() >= MIN_FORBIDDEN_USER_INPUT_LENGTH
+                && lowercase.contains(&user_input.to_lowercase())
+        });
+        if let Some(user_input) = forbidden {
+            return Err(Rejection::ContainsUserInput {
+                user_input: user_input.to_string(),
+            });
+        }
+    }
+    let entropy = estimate();
+    if entropy.score() < requirements.min_score {
+        return Err(Rejection::TooGuessable {
+            score: entropy.score(),
+            min_score: requirements.min_score,
+            feedback: entropy.feedback().clone(),
+        });
+    }
+    Ok(entropy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_meets_requirements() {
+        let requirements = Requirements::default();
+        let entropy = meets_requirements("correcthorsebatterystaple", &[], &requirements).unwrap();
+        assert!(entropy.score() >= Score::SafelyUnguessable);
+
+        assert_eq!(
+            meets_requirements("Xk7#q", &[], &requirements).unwrap_err(),
+            Rejection::TooShort { len: 5, min_len: 8 }
+        );
+        assert_eq!(
+            meets_requirements("xJSmith!q8#z", &["jsmith", "js"], &requirements).unwrap_err(),
+            Rejection::ContainsUserInput {
+                user_input: "jsmith".to_string()
+            }
+        );
+        // inputs this short are ignored
+        assert!(meets_requirements("correcthorsebatterystaple", &["or"], &requirements).is_ok());
+
+        match meets_requirements("password123", &[], &requirements).unwrap_err() {
+            Rejection::TooGuessable {
+                score,
+                min_score,
+                feedback,
+            } => {
+                assert!(score < Score::SafelyUnguessable);
+                assert_eq!(min_score, Score::SafelyUnguessable);
+                assert!(feedback.is_some());
+            }
+            rejection => panic!("{:?}", rejection),
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_requirements_can_be_relaxed() {
+        let requirements = Requirements {
+            min_len: 0,
+            min_score: Score::TooGuessable,
+            forbid_user_inputs: false,
+            require_user_inputs: false,
+        };
+        assert!(meets_requirements("jsmith", &["jsmith"], &requirements).is_ok());
+        assert_eq!(
+            Rejection::TooShort { len: 5, min_len: 8 }.to_string(),
+            "password is 5 characters long, at least 8 are required"
+        );
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_estimator_meets_requirements() {
+        let estimator = Zxcvbn::new();
+        let requirements = Requirements::default();
+        assert!(estimator
+            .meets_requirements("SummerSale2026", &[], &requirements)
+            .is_ok());
+        estimator.add_banned_words(["summersale"]).unwrap();
+        assert!(matches!(
+            estimator.meets_requirements("SummerSale2026", &[], &requirements),
+            Err(Rejection::TooGuessable { .. })
+        ));
+    }
+}
@@ -0,0 +1,227 @@
+//! Finding scores stored with an older scoring model, and migrating them.
+//!
+//! Scores saved alongside accounts go stale when the crate is upgraded with new matchers,
+//! dictionaries or constants. Without the passwords they can only be fully recomputed when
+//! the user next types theirs, e.g. at login, but a score higher than any password of the
+//! same length can get under the current model can be lowered right away.
+//!
+//! ```
+//! use zxcvbn::rescore::{needs_rescore, Migration, Rescore, StoredScore};
+//! use zxcvbn::{zxcvbn, Score};
+//!
+//! let entropy = zxcvbn("correcthorsebatterystaple", &[]);
+//! assert!(!needs_rescore(entropy.model_version()));
+//!
+//! let stored = [
+//!     StoredScore {
+//!         length: 25,
+//!         score: entropy.score(),
+//!         model_version: entropy.model_version().to_string(),
+//!     },
+//!     StoredScore {
+//!         length: 6,
+//!         score: Score::VeryUnguessable,
+//!         model_version: "2".to_string(),
+//!     },
+//! ];
+//! let rescored = Migration::new().rescore_batch(&stored);
+//! assert_eq!(rescored[0], Rescore::UpToDate);
+//! assert_eq!(rescored[1], Rescore::Lowered(Score::VeryGuessable));
+//! ```
+
+use crate::{scoring, Score, Zxcvbn};
+
+/// Whether a score stored with the given model version should be recomputed, because
+/// [`zxcvbn`](crate::zxcvbn) now estimates with a different model. Scores of estimators
+/// with other [settings](crate::Entropy::model_version) than `zxcvbn`'s always need it.
+pub fn needs_rescore(stored_model_version: &str) -> bool {
+    stored_model_version != crate::default_model_version()
+}
+
+impl Zxcvbn {
+    /// Whether a score stored with the given model version should be recomputed, because
+    /// this estimator estimates with a different model.
+    pub fn needs_rescore(&self, stored_model_version: &str) -> bool {
+        stored_model_version != self.model_version()
+    }
+
+    /// A migration to the model of this estimator.
+    pub fn migration(&self) -> Migration {
+        Migration {
+            model_version: self.model_version(),
+        }
+    }
+}
+
+/// What is stored about a password alongside an account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+pub struct StoredScore {
+    /// Number of characters in the password. Characters that normalization expands, such
+    /// as ligatures, should be counted as expanded.
+    pub length: usize,
+    /// The score the password was given.
+    pub score: Score,
+    /// The [model version](crate::Entropy::model_version) the score was computed with.
+    pub model_version: String,
+}
+
+/// What to do about a stored score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+pub enum Rescore {
+    /// The score was computed with the current model.
+    UpToDate,
+    /// The score is higher than any password of its length can get under the current
+    /// model. Store this score instead, keeping the old model version so that the password
+    /// is still re-scored when next available.
+    Lowered(Score),
+    /// The score may be stale, re-score the password when next available, e.g. at login.
+    OnNextLogin,
+}
+
+/// Re-scores stored scores against the model of an estimator.
+#[derive(Debug, Clone)]
+pub struct Migration {
+    model_version: String,
+}
+
+impl Migration {
+    /// A migration to the model of [`zxcvbn`](crate::zxcvbn).
+    pub fn new() -> Self {
+        Migration {
+            model_version: crate::default_model_version(),
+        }
+    }
+
+    /// The model version scores are migrated to.
+    pub fn model_version(&self) -> &str {
+        &self.model_version
+    }
+
+    /// Re-scores a stored score as far as possible without the password.
+    pub fn rescore(&self, stored: &StoredScore) -> Rescore {
+        if stored.model_version == self.model_version {
+            return Rescore::UpToDate;
+        }
+        let max_score = Score::from_guesses(scoring::max_guesses(stored.length));
+        if stored.score > max_score {
+            Rescore::Lowered(max_score)
+        } else {
+            Rescore::OnNextLogin
+        }
+    }
+
+    /// Re-scores stored scores in order, e.g. a page of rows read from a database.
+    pub fn rescore_batch(&self, stored: &[StoredScore]) -> Vec<Rescore> {
+        stored.iter().map(|stored| self.rescore(stored)).collect()
+    }
+}
+
+impl Default for Migration {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matching::patterns::PatternKind;
+    use crate::normalization::Normalizers;
+    use crate::profile::Profile;
+    use crate::ScoringParams;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_needs_rescore() {
+        let entropy = crate::zxcvbn("password", &[]);
+        assert!(!needs_rescore(entropy.model_version()));
+        assert!(needs_rescore("0"));
+        assert!(needs_rescore(""));
+
+        let estimator = Zxcvbn::from_profile(Profile::LegacyJsCompatible);
+        let entropy = estimator.estimate("password", &[]);
+        assert!(!estimator.needs_rescore(entropy.model_version()));
+        assert!(needs_rescore(entropy.model_version()));
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_needs_rescore_with_scoring_params() {
+        let estimator = Zxcvbn::new().with_scoring_params(
+            ScoringParams::new().min_guesses(PatternKind::Dictionary, 1_000_000),
+        );
+        let entropy = estimator.estimate("password", &[]);
+        assert!(!estimator.needs_rescore(entropy.model_version()));
+        assert!(needs_rescore(entropy.model_version()));
+        assert!(Zxcvbn::new().needs_rescore(entropy.model_version()));
+        assert!(estimator.needs_rescore(crate::zxcvbn("password", &[]).model_version()));
+        assert_eq!(
+            Migration::new().rescore(&StoredScore {
+                length: 8,
+                score: entropy.score(),
+                model_version: entropy.model_version().to_string(),
+            }),
+            Rescore::OnNextLogin
+        );
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_rescore() {
+        let migration = Migration::new();
+        let stale = |length, score| StoredScore {
+            length,
+            score,
+            model_version: "0".to_string(),
+        };
+        // no password of 3 characters needs more than 1001 guesses
+        assert_eq!(
+            migration.rescore(&stale(3, Score::VeryGuessable)),
+            Rescore::Lowered(Score::TooGuessable)
+        );
+        assert_eq!(
+            migration.rescore(&stale(3, Score::TooGuessable)),
+            Rescore::OnNextLogin
+        );
+        assert_eq!(
+            migration.rescore(&stale(12, Score::VeryUnguessable)),
+            Rescore::OnNextLogin
+        );
+        assert_eq!(
+            migration.rescore(&stale(0, Score::SafelyUnguessable)),
+            Rescore::Lowered(Score::TooGuessable)
+        );
+
+        // a lowered score never exceeds what a password of that length gets
+        for length in 1..12 {
+            let password = "Xk7#q*zP2!vR".chars().take(length).collect::<String>();
+            let score = crate::zxcvbn(&password, &[]).score();
+            match migration.rescore(&stale(length, Score::VeryUnguessable)) {
+                Rescore::Lowered(max_score) => assert!(score <= max_score, "{}", password),
+                Rescore::OnNextLogin => (),
+                Rescore::UpToDate => unreachable!(),
+            }
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_estimator_migration() {
+        let estimator = Zxcvbn::from_normalizers(Normalizers::none());
+        let migration = estimator.migration();
+        assert_eq!(migration.model_version(), estimator.model_version());
+        let entropy = estimator.estimate("password", &[]);
+        let stored = StoredScore {
+            length: 8,
+            score: entropy.score(),
+            model_version: entropy.model_version().to_string(),
+        };
+        assert_eq!(migration.rescore(&stored), Rescore::UpToDate);
+        assert_eq!(Migration::new().rescore(&stored), Rescore::OnNextLogin);
+    }
+}
@@ -1,9 +1,37 @@
+//! The guesses of matches and of the sequences of matches covering a password.
+//!
+//! Each pattern's estimate can be made on its own, so that crates matching passwords in
+//! their own ways can reuse the math of the built-in patterns. A word typed on a different
+//! keyboard layout than the one selected, for example, takes as many guesses as the word
+//! times the number of layouts:
+//!
+//! ```
+//! use zxcvbn::matching::patterns::DictionaryPattern;
+//! use zxcvbn::scoring;
+//!
+//! let mut word = DictionaryPattern::default();
+//! word.rank = 3;
+//! let layouts = 2;
+//! assert_eq!(scoring::estimate_dictionary(&word, "password") * layouts, 6);
+//! ```
+
+use crate::adjacency_graphs::qwerty_neighbors;
+use crate::fingerprint::Fingerprint;
 use crate::matching::patterns::*;
-use crate::matching::Match;
+use crate::matching::{unlock, InvalidMatch, Match, YearWindow};
+use crate::search_graph::{BestSequence, SearchGraph};
+use once_cell::sync::Lazy;
 use std::cmp;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use zxcvbn_core::estimates::{self, shift_variations, uppercase_variations};
+use zxcvbn_core::guesses::Guesses;
+use zxcvbn_core::search::{self, Search, SequenceParams, Span, Step};
 
+pub use zxcvbn_core::search::SequencePenalty;
+
+/// The sequence of matches covering a password with the fewest guesses.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct GuessCalculation {
     /// Estimated guesses needed to crack the password
     pub guesses: u64,
@@ -13,227 +41,901 @@ pub struct GuessCalculation {
     pub sequence: Vec<Match>,
 }
 
-#[derive(Debug, Clone)]
-struct Optimal {
-    /// optimal.m[k][l] holds final match in the best length-l match sequence covering the
-    /// password prefix up to k, inclusive.
-    /// if there is no length-l sequence that scores better (fewer guesses) than
-    /// a shorter match sequence spanning the same prefix, optimal.m[k][l] is undefined.
-    m: Vec<HashMap<usize, Match>>,
-    /// same structure as optimal.m -- holds the product term Prod(m.guesses for m in sequence).
-    /// optimal.pi allows for fast (non-looping) updates to the minimization function.
-    pi: Vec<HashMap<usize, u64>>,
-    /// same structure as optimal.m -- holds the overall metric.
-    g: Vec<HashMap<usize, u64>>,
-}
-
 #[cfg(not(target_arch = "wasm32"))]
-lazy_static! {
-    pub(crate) static ref REFERENCE_YEAR: i32 = time::OffsetDateTime::now_utc().year();
+pub(crate) static REFERENCE_YEAR: Lazy<i32> = Lazy::new(|| {
+    let seconds = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    year_of_unix_day((seconds / 86_400) as i64)
+});
+
+/// The year of a number of days since 1970-01-01 in the Gregorian calendar.
+#[cfg(not(target_arch = "wasm32"))]
+fn year_of_unix_day(days: i64) -> i32 {
+    // count from 0000-03-01, so that leap days end the 400 year eras
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    // months counted from March, so January and February belong to the next year
+    let month = (5 * day_of_year + 2) / 153;
+    (era * 400 + year_of_era + i64::from(month >= 10)) as i32
 }
 
 #[cfg(target_arch = "wasm32")]
-lazy_static! {
-    pub(crate) static ref REFERENCE_YEAR: i32 = web_sys::js_sys::Date::new_0()
+pub(crate) static REFERENCE_YEAR: Lazy<i32> = Lazy::new(|| {
+    web_sys::js_sys::Date::new_0()
         .get_full_year()
         .try_into()
-        .unwrap();
+        .unwrap()
+});
+
+pub(crate) const MIN_YEAR_SPACE: i32 = 20;
+const MIRROR_VARIATIONS: u64 = 2;
+const SHIFT_REPEAT_VARIATIONS: u64 = 2;
+const COLLAPSED_ECHO_VARIATIONS: u64 = 2;
+/// the translations a word typed with the wrong layout is found with: QWERTY keys read as
+/// ЙЦУКЕН and the other way around, and QWERTY keys read as Dvorak and the other way around.
+const LAYOUT_VARIATIONS: u64 = 4;
+/// the parts of a phone number tried for each one: as given, its digits, its local number,
+/// its area code and its last four digits.
+const PHONE_PARTS: u64 = 5;
+const MIN_DECADE_SPACE: i32 = 2;
+/// "80s", "80's", "1980s" and "1980's".
+const DECADE_FORMS: u64 = 4;
+const MIN_YEAR_RANGE_SPAN: i32 = 10;
+/// the latin-1 range every alt code and escape is at least guessed from.
+const MIN_CODE_POINT_SPACE: u64 = 256;
+/// an alt code with or without a leading zero, with "alt", "alt+", "alt-" or "alt ".
+const ALT_CODE_VARIANTS: u64 = 8;
+/// escapes like \u00e9, \u{e9}, \xe9, U+00E9, &#xe9; and &#233;.
+const ESCAPE_SYNTAXES: u64 = 6;
+/// the ID numbers that can be issued: US Social Security numbers by area, group and serial.
+const SSN_SPACE: u64 = 898 * 99 * 9999;
+/// UK National Insurance numbers by prefix, digits and suffix.
+const NINO_SPACE: u64 = 373 * 1_000_000 * 4;
+/// Canadian Social Insurance numbers by first digit and the digits before the check digit.
+const SIN_SPACE: u64 = 8 * 10_000_000;
+/// an ID number with either of two separators, or with or without spaces.
+const NATIONAL_ID_FORMS: u64 = 2;
+/// payment details are to be kept out of passwords rather than estimated: guessing them like
+/// the first few thousand common passwords scores passwords holding them as weak.
+const PAYMENT_GUESSES: u64 = 10_000;
+/// published studies of the unlock patterns people choose find about two in five starting at
+/// the top left dot, leaving the rest to the other eight dots.
+const UNLOCK_TOP_LEFT_START_PER_MILLE: u64 = 400;
+const UNLOCK_OTHER_START_PER_MILLE: u64 = (1000 - UNLOCK_TOP_LEFT_START_PER_MILLE) / 8;
+/// the same studies find knight moves rare, so each one is guessed later.
+const UNLOCK_KNIGHT_MOVE_FACTOR: u64 = 4;
+/// an attack on acronyms starts from a list of famous phrases much longer than the bundled
+/// one, spelling each a few ways, with or without its short words or numbers as digits: the
+/// most famous phrase is reached after a thousand guesses, and each later one ten guesses on.
+const ACRONYM_BASE_GUESSES: u64 = 1000;
+const ACRONYM_SPELLINGS: u64 = 10;
+
+/// Finds the sequence of `matches` and bruteforce matches covering `password` with the fewest
+/// guesses.
+///
+/// # Panics
+///
+/// With debug assertions enabled, panics if one of `matches` doesn't cover characters of
+/// `password` or doesn't hold them as its token. See [`try_most_guessable_match_sequence`]
+/// to check them in release builds.
+pub fn most_guessable_match_sequence(
+    password: &str,
+    matches: &[crate::matching::Match],
+    params: &ScoringParams,
+) -> GuessCalculation {
+    search(password, matches, params, &mut ScoringScratch::default())
 }
 
-const MIN_YEAR_SPACE: i32 = 20;
-const BRUTEFORCE_CARDINALITY: u64 = 10;
-const MIN_GUESSES_BEFORE_GROWING_SEQUENCE: u64 = 10_000;
-const MIN_SUBMATCH_GUESSES_SINGLE_CHAR: u64 = 10;
-const MIN_SUBMATCH_GUESSES_MULTI_CHAR: u64 = 50;
+/// Finds the optimal sequence like [`most_guessable_match_sequence`], returning the first of
+/// `matches` that doesn't cover characters of `password` or doesn't hold them as its token
+/// instead.
+///
+/// [`most_guessable_match_sequence`] only checks them with debug assertions enabled, and
+/// panics on the first that can't be scored. Without them, such matches give wrong guesses.
+///
+/// ```
+/// use zxcvbn::matching::{InvalidMatch, Match};
+/// use zxcvbn::scoring::try_most_guessable_match_sequence;
+/// use zxcvbn::ScoringParams;
+///
+/// let m = Match {
+///     i: 2,
+///     j: 4,
+///     token: "abc".to_string(),
+///     ..Match::default()
+/// };
+/// let result = try_most_guessable_match_sequence("abc", &[m], &ScoringParams::default());
+/// assert_eq!(result.unwrap_err(), InvalidMatch::OutOfBounds { index: 0 });
+/// ```
+pub fn try_most_guessable_match_sequence(
+    password: &str,
+    matches: &[Match],
+    params: &ScoringParams,
+) -> Result<GuessCalculation, InvalidMatch> {
+    check_spans(password, matches)?;
+    Ok(search(
+        password,
+        matches,
+        params,
+        &mut ScoringScratch::default(),
+    ))
+}
 
-pub fn most_guessable_match_sequence(
+/// Checks that each of `matches` covers characters of `password` and holds them as its token,
+/// returning the first that doesn't.
+pub(crate) fn check_spans(password: &str, matches: &[Match]) -> Result<(), InvalidMatch> {
+    let n = password.chars().count();
+    for (index, m) in matches.iter().enumerate() {
+        if m.j < m.i {
+            return Err(InvalidMatch::EmptySpan { index });
+        }
+        if m.j >= n {
+            return Err(InvalidMatch::OutOfBounds { index });
+        }
+        if !m.token.chars().eq(password.chars().take(m.j + 1).skip(m.i)) {
+            return Err(InvalidMatch::TokenMismatch { index });
+        }
+    }
+    Ok(())
+}
+
+/// Finds the optimal sequence like [`most_guessable_match_sequence`], with the buffers of
+/// `scratch`, for matchers written outside the crate scoring many passwords. Once `scratch`
+/// has scored a password as long, this only allocates the sequence it returns, as long as
+/// the guesses of `matches` are estimated.
+///
+/// ```
+/// use zxcvbn::scoring::{self, ScoringScratch};
+/// use zxcvbn::ScoringParams;
+///
+/// let mut scratch = ScoringScratch::new();
+/// for password in ["abc", "abcdef"] {
+///     let result = scoring::most_guessable_match_sequence_with_scratch(
+///         password,
+///         &[],
+///         &ScoringParams::default(),
+///         &mut scratch,
+///     );
+///     assert_eq!(result.sequence.len(), 1);
+/// }
+/// ```
+pub fn most_guessable_match_sequence_with_scratch(
     password: &str,
-    matches: &[crate::matching::Match],
-    exclude_additive: bool,
+    matches: &[Match],
+    params: &ScoringParams,
+    scratch: &mut ScoringScratch,
 ) -> GuessCalculation {
+    search(password, matches, params, scratch)
+}
+
+/// Finds the optimal sequence like [`most_guessable_match_sequence`], also recording the
+/// candidate matches and best partial sequences of the search.
+pub(crate) fn traced_match_sequence(
+    password: &str,
+    matches: &[Match],
+    params: &ScoringParams,
+    scratch: &mut ScoringScratch,
+) -> (GuessCalculation, SearchGraph) {
+    let result = search(password, matches, params, scratch);
+    let best = scratch
+        .found
+        .best()
+        .map(|(j, len, best)| BestSequence {
+            j,
+            len,
+            guesses: best.guesses,
+            last: scratch.step_match(best.last, matches, password),
+        })
+        .collect();
+    let graph = SearchGraph {
+        password: password.to_string(),
+        candidates: scratch.candidate_matches(matches),
+        best,
+        sequence: result.sequence.clone(),
+        guesses: result.guesses,
+    };
+    (result, graph)
+}
+
+/// Buffers scoring reuses from one estimate to the next, see
+/// [`Zxcvbn::estimate_with_scratch`](crate::Zxcvbn::estimate_with_scratch).
+///
+/// The tables of the search for the optimal sequence, the guesses and spans of the candidate
+/// matches, the bounds of bruteforce matches and the steps of the optimal sequence grow to
+/// fit the longest password scored with them, and are not allocated again for shorter ones.
+/// Only scoring reuses them: an estimate still allocates its matches, feedback and the rest
+/// of its [`Entropy`](crate::Entropy).
+#[derive(Debug, Clone, Default)]
+pub struct ScoringScratch {
+    found: Search,
+    spans: Vec<Span>,
+    bruteforce_starts: Vec<usize>,
+    /// The guesses of each match scored.
+    guesses: Vec<u64>,
+    /// The index of the match each of `spans` stands for.
+    candidates: Vec<usize>,
+    steps: Vec<Step>,
+}
+
+impl ScoringScratch {
+    /// Creates empty buffers, which allocate nothing until used.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The `index`th match the last search could pick, with its guesses estimated.
+    fn candidate(&self, index: usize, matches: &[Match]) -> Match {
+        let index = self.candidates[index];
+        Match {
+            guesses: Some(self.guesses[index]),
+            ..matches[index].clone()
+        }
+    }
+
+    /// The matches the last search could pick, with their guesses estimated.
+    fn candidate_matches(&self, matches: &[Match]) -> Vec<Match> {
+        (0..self.candidates.len())
+            .map(|index| self.candidate(index, matches))
+            .collect()
+    }
+
+    /// The match a step of the last search stands for, with its guesses estimated.
+    fn step_match(&self, step: Step, matches: &[Match], password: &str) -> Match {
+        match step {
+            Step::Candidate(index) => self.candidate(index, matches),
+            Step::Bruteforce { i, j } => Match {
+                guesses: Some(bruteforce_part_guesses(i, j, self.bruteforce_starts.len())),
+                ..make_bruteforce_match(i, j, password)
+            },
+        }
+    }
+}
+
+/// The optimal search, leaving its final state in `scratch`.
+fn search(
+    password: &str,
+    matches: &[Match],
+    params: &ScoringParams,
+    scratch: &mut ScoringScratch,
+) -> GuessCalculation {
+    if cfg!(debug_assertions) {
+        if let Err(error) = check_spans(password, matches) {
+            panic!("{error} in {password:?}: {:?}", matches[error.index()]);
+        }
+    }
     let n = password.chars().count();
+    fill_candidates(password, matches, params, scratch);
+    let bruteforce = |i, j| bruteforce_part_guesses(i, j, n);
+    search::search_into(
+        &mut scratch.found,
+        n,
+        &scratch.spans,
+        &scratch.bruteforce_starts,
+        bruteforce,
+        &params.sequence,
+    );
+
+    scratch.found.sequence_into(&mut scratch.steps);
+    let mut sequence = Vec::with_capacity(scratch.steps.len());
+    sequence.extend(
+        scratch
+            .steps
+            .iter()
+            .map(|&step| scratch.step_match(step, matches, password)),
+    );
+    GuessCalculation {
+        guesses: scratch.found.guesses(),
+        guesses_log10: (scratch.found.guesses() as f64).log10(),
+        sequence,
+    }
+}
 
-    // partition matches into sublists according to ending index j
-    let mut matches_by_j: Vec<Vec<Match>> = vec![Vec::new(); n];
+/// Fills `scratch` with the bruteforce starts of `password`, the guesses of `matches`, and the
+/// matches the search can pick with their spans, sorted by j and then i for deterministic
+/// output.
+fn fill_candidates(
+    password: &str,
+    matches: &[Match],
+    params: &ScoringParams,
+    scratch: &mut ScoringScratch,
+) {
+    let n = password.chars().count();
+    fill_bruteforce_starts(&mut scratch.bruteforce_starts, n, matches);
+    scratch.guesses.clear();
+    scratch.guesses.extend(
+        matches
+            .iter()
+            .map(|m| m.guesses.unwrap_or_else(|| estimate_match(m, password))),
+    );
+    if params.js_match_order {
+        js_order_matches(matches, &mut scratch.candidates);
+    } else {
+        prune_matches(
+            matches,
+            &scratch.guesses,
+            &scratch.bruteforce_starts,
+            &mut scratch.candidates,
+        );
+    }
+    scratch.spans.clear();
+    scratch
+        .spans
+        .extend(scratch.candidates.iter().map(|&index| Span {
+            i: matches[index].i,
+            j: matches[index].j,
+            guesses: scratch.guesses[index],
+        }));
+}
+
+/// The first index a bruteforce match ending at each index may start at.
+///
+/// Emoji are covered by the emoji matcher, which knows how many of them there are.
+/// Bruteforce would count each of their chars separately, so it must not span them.
+fn bruteforce_starts(n: usize, matches: &[Match]) -> Vec<usize> {
+    let mut bruteforce_starts = Vec::with_capacity(n);
+    fill_bruteforce_starts(&mut bruteforce_starts, n, matches);
+    bruteforce_starts
+}
+
+/// Replaces the contents of `bruteforce_starts` with the [`bruteforce_starts`] of a password
+/// of `n` chars.
+fn fill_bruteforce_starts(bruteforce_starts: &mut Vec<usize>, n: usize, matches: &[Match]) {
+    bruteforce_starts.clear();
+    bruteforce_starts.resize(n, 0);
+    // emoji chars are marked first, as a bruteforce match ending at one must start after it
     for m in matches {
-        matches_by_j[m.j].push(m.clone());
+        if let MatchPattern::Emoji(_) = m.pattern {
+            bruteforce_starts[m.i..=m.j]
+                .iter_mut()
+                .for_each(|start| *start = usize::MAX);
+        }
     }
-    // small detail: for deterministic output, sort each sublist by i.
-    for lst in &mut matches_by_j {
-        lst.sort_by_key(|m| m.i);
+    let mut last_start = 0;
+    for (k, start) in bruteforce_starts.iter_mut().enumerate() {
+        if *start == usize::MAX {
+            last_start = k + 1;
+        }
+        *start = last_start;
     }
+}
 
-    let mut optimal = Optimal {
-        m: vec![HashMap::new(); n],
-        pi: vec![HashMap::new(); n],
-        g: vec![HashMap::new(); n],
-    };
+/// The guesses of bruteforcing the characters from i to j, inclusive, of a password of `n`
+/// characters, without building the match.
+fn bruteforce_part_guesses(i: usize, j: usize, n: usize) -> u64 {
+    let len = j - i + 1;
+    cmp::max(estimates::bruteforce(len), estimates::min_guesses(len, n))
+}
 
-    /// helper: considers whether a length-l sequence ending at match m is better (fewer guesses)
-    /// than previously encountered sequences, updating state if so.
-    fn update(
-        mut m: Match,
-        len: usize,
-        password: &str,
-        optimal: &mut Optimal,
-        exclude_additive: bool,
-    ) {
-        let k = m.j;
-        let mut pi = estimate_guesses(&mut m, password);
-        if len > 1 {
-            // we're considering a length-l sequence ending with match m:
-            // obtain the product term in the minimization function by multiplying m's guesses
-            // by the product of the length-(l-1) sequence ending just before m, at m.i - 1.
-            pi = pi.saturating_mul(optimal.pi[m.i - 1][&(len - 1)]);
+/// Makes a bruteforce match spanning i to j, inclusive.
+fn make_bruteforce_match(i: usize, j: usize, password: &str) -> Match {
+    // sliced rather than collected, so that the token is allocated once
+    let mut bounds = password
+        .char_indices()
+        .map(|(k, _)| k)
+        .chain([password.len()]);
+    let start = bounds.nth(i).unwrap();
+    let end = bounds.nth(j - i).unwrap();
+    Match {
+        pattern: MatchPattern::BruteForce,
+        token: password[start..end].to_string(),
+        i,
+        j,
+        ..Match::default()
+    }
+}
+
+/// One of the best sequences of a length ending at some index, kept by
+/// [`k_best_match_sequences`].
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    /// Index of the last match of the sequence in the matches of the search.
+    m: usize,
+    /// The product of the guesses of the matches of the sequence.
+    pi: u64,
+    /// Rank of the sequence this one extends among those one match shorter, ending right
+    /// before the last match.
+    prev: usize,
+}
+
+/// Finds up to `k` sequences of matches covering the password with the fewest guesses,
+/// fewest first. The first is an optimal sequence like the one of
+/// [`most_guessable_match_sequence`], the others are the next best interpretations of the
+/// password, such as a date where the optimal sequence has a dictionary word.
+///
+/// Every match of `matches` is considered, not only the cheapest of each span. As in the
+/// optimal search, sequences with two adjacent bruteforce matches are skipped, a single one
+/// over both spans taking fewer guesses.
+pub(crate) fn k_best_match_sequences(
+    password: &str,
+    matches: &[Match],
+    params: &ScoringParams,
+    k: usize,
+) -> Vec<GuessCalculation> {
+    let n = password.chars().count();
+    if n == 0 || k == 0 {
+        return Vec::new();
+    }
+    let bruteforce_starts = bruteforce_starts(n, matches);
+
+    // for a given length, the guesses of a sequence only grow with the product of the
+    // guesses of its matches, so keeping the k smallest products of each length ending at
+    // each index is enough to find the k best sequences.
+    let mut all_matches: Vec<Match> = Vec::new();
+    let mut matches_by_j: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for m in matches {
+        let mut m = m.clone();
+        estimate_guesses(&mut m, password);
+        if !all_matches.contains(&m) {
+            matches_by_j[m.j].push(all_matches.len());
+            all_matches.push(m);
         }
-        // calculate the minimization func
-        let mut guesses = (factorial(len) as u64).saturating_mul(pi);
-        if !exclude_additive {
-            let additive = if len == 1 {
-                1
-            } else {
-                (2..len).fold(MIN_GUESSES_BEFORE_GROWING_SEQUENCE, |acc, _| {
-                    acc.saturating_mul(MIN_GUESSES_BEFORE_GROWING_SEQUENCE)
-                })
-            };
-            guesses = guesses.saturating_add(additive);
+    }
+    let mut best: Vec<BTreeMap<usize, Vec<Candidate>>> = vec![BTreeMap::new(); n];
+
+    fn insert(candidates: &mut Vec<Candidate>, candidate: Candidate, k: usize) {
+        let position = candidates.partition_point(|c| c.pi <= candidate.pi);
+        if position < k {
+            candidates.insert(position, candidate);
+            candidates.truncate(k);
         }
-        // update state if new best.
-        // first see if any competing sequences covering this prefix, with l or fewer matches,
-        // fare better than this sequence. if so, skip it and return.
-        for (&competing_l, &competing_guesses) in &optimal.g[k] {
-            if competing_l > len {
-                continue;
-            }
-            if competing_guesses <= guesses {
-                return;
-            }
+    }
+
+    /// Extends the best sequences ending right before match m with it.
+    fn extend(
+        m: usize,
+        all_matches: &[Match],
+        best: &mut [BTreeMap<usize, Vec<Candidate>>],
+        k: usize,
+    ) {
+        let (i, j, guesses) = (all_matches[m].i, all_matches[m].j, all_matches[m].guesses);
+        let guesses = guesses.unwrap();
+        if i == 0 {
+            let candidate = Candidate {
+                m,
+                pi: guesses,
+                prev: 0,
+            };
+            insert(best[j].entry(1).or_default(), candidate, k);
+            return;
         }
-        // this sequence might be part of the final optimal sequence.
-        optimal.g[k].insert(len, guesses);
-        optimal.m[k].insert(len, m);
-        optimal.pi[k].insert(len, pi);
-    }
-
-    /// helper: evaluate bruteforce matches ending at k.
-    fn bruteforce_update(k: usize, password: &str, optimal: &mut Optimal, exclude_additive: bool) {
-        // see if a single bruteforce match spanning the k-prefix is optimal.
-        let m = make_bruteforce_match(0, k, password);
-        update(m, 1, password, optimal, exclude_additive);
-        for i in 1..=k {
-            // generate k bruteforce matches, spanning from (i=1, j=k) up to (i=k, j=k).
-            // see if adding these new matches to any of the sequences in optimal[i-1]
-            // leads to new bests.
-            let m = make_bruteforce_match(i, k, password);
-            for (l, last_m) in optimal.m[i - 1].clone() {
-                // corner: an optimal sequence will never have two adjacent bruteforce matches.
-                // it is strictly better to have a single bruteforce match spanning the same region:
-                // same contribution to the guess product with a lower length.
-                // --> safe to skip those cases.
-                if last_m.pattern == MatchPattern::BruteForce {
+        let is_bruteforce = all_matches[m].pattern == MatchPattern::BruteForce;
+        let mut extended = Vec::new();
+        for (&len, candidates) in &best[i - 1] {
+            for (prev, c) in candidates.iter().enumerate() {
+                if is_bruteforce && all_matches[c.m].pattern == MatchPattern::BruteForce {
                     continue;
                 }
-                // try adding m to this length-l sequence.
-                update(m.clone(), l + 1, password, optimal, exclude_additive);
+                let candidate = Candidate {
+                    m,
+                    pi: (Guesses::new(guesses) * c.pi).get(),
+                    prev,
+                };
+                extended.push((len + 1, candidate));
             }
         }
-    }
-
-    /// helper: make bruteforce match objects spanning i to j, inclusive.
-    fn make_bruteforce_match(i: usize, j: usize, password: &str) -> Match {
-        Match {
-            pattern: MatchPattern::BruteForce,
-            token: password.chars().take(j + 1).skip(i).collect(),
-            i,
-            j,
-            ..Match::default()
+        for (len, candidate) in extended {
+            insert(best[j].entry(len).or_default(), candidate, k);
         }
     }
 
-    /// helper: step backwards through optimal.m starting at the end,
-    /// constructing the final optimal match sequence.
-    #[allow(clippy::many_single_char_names)]
-    fn unwind(n: usize, optimal: &mut Optimal) -> Vec<Match> {
-        let mut optimal_match_sequence = Vec::new();
-        let mut k = n - 1;
-        // find the final best sequence length and score
-        let mut l = None;
-        let mut g = None;
-        for (candidate_l, candidate_g) in &optimal.g[k] {
-            if g.is_none() || *candidate_g < *g.as_ref().unwrap() {
-                l = Some(*candidate_l);
-                g = Some(*candidate_g);
-            }
+    for j in 0..n {
+        for &m in &matches_by_j[j] {
+            extend(m, &all_matches, &mut best, k);
         }
-
-        loop {
-            let m = &optimal.m[k][&l.unwrap()];
-            optimal_match_sequence.insert(0, m.clone());
-            if m.i == 0 {
-                return optimal_match_sequence;
-            }
-            k = m.i - 1;
-            l = l.map(|x| x - 1);
+        for i in bruteforce_starts[j]..=j {
+            let mut m = make_bruteforce_match(i, j, password);
+            estimate_guesses(&mut m, password);
+            all_matches.push(m);
+            extend(all_matches.len() - 1, &all_matches, &mut best, k);
         }
     }
 
-    for (k, match_by_j) in matches_by_j.iter().enumerate() {
-        for m in match_by_j {
-            if m.i > 0 {
-                let keys: Vec<usize> = optimal.m[m.i - 1].keys().cloned().collect();
-                for l in keys {
-                    update(m.clone(), l + 1, password, &mut optimal, exclude_additive);
+    let mut ends = best[n - 1]
+        .iter()
+        .flat_map(|(&len, candidates)| {
+            (0..candidates.len())
+                .map(move |rank| (params.sequence.guesses(len, candidates[rank].pi), len, rank))
+        })
+        .collect::<Vec<_>>();
+    ends.sort();
+    ends.into_iter()
+        .take(k)
+        .map(|(guesses, len, rank)| {
+            let mut sequence = Vec::with_capacity(len);
+            let (mut j, mut len, mut rank) = (n - 1, len, rank);
+            loop {
+                let candidate = best[j][&len][rank];
+                let m = &all_matches[candidate.m];
+                sequence.push(m.clone());
+                if m.i == 0 {
+                    break;
                 }
-            } else {
-                update(m.clone(), 1, password, &mut optimal, exclude_additive);
+                j = m.i - 1;
+                len -= 1;
+                rank = candidate.prev;
             }
-        }
-        bruteforce_update(k, password, &mut optimal, exclude_additive);
-    }
-    let optimal_match_sequence = unwind(n, &mut optimal);
-    let optimal_l = optimal_match_sequence.len();
+            sequence.reverse();
+            GuessCalculation {
+                guesses,
+                guesses_log10: (guesses as f64).log10(),
+                sequence,
+            }
+        })
+        .collect()
+}
 
-    // corner: empty password
-    let guesses = if password.is_empty() {
-        1
-    } else {
-        optimal.g[n - 1][&optimal_l]
-    };
+/// Replaces the contents of `candidates` with the indexes of the matches an optimal sequence
+/// can be made of, sorted by j and then i, given the `guesses` of each match. Of the matches
+/// covering the same span only the one with the fewest guesses is kept, the first on ties as
+/// the search would pick it, and none of them if bruteforcing the span takes fewer guesses.
+/// Dictionary-dense passwords have many matches over the same spans, each of which the search
+/// would otherwise try after every sequence ending right before it.
+fn prune_matches(
+    matches: &[Match],
+    guesses: &[u64],
+    bruteforce_starts: &[usize],
+    candidates: &mut Vec<usize>,
+) {
+    let n = bruteforce_starts.len();
+    candidates.clear();
+    candidates.extend((0..matches.len()).filter(|&index| {
+        let m = &matches[index];
+        // bruteforce may not span emoji
+        bruteforce_starts[m.j] > m.i || guesses[index] <= bruteforce_part_guesses(m.i, m.j, n)
+    }));
+    // unstable sorts don't allocate, and the index keeps the first of equal matches first
+    candidates
+        .sort_unstable_by_key(|&index| (matches[index].j, matches[index].i, guesses[index], index));
+    candidates.dedup_by_key(|&mut index| (matches[index].j, matches[index].i));
+}
 
-    GuessCalculation {
-        guesses,
-        guesses_log10: (guesses as f64).log10(),
-        sequence: optimal_match_sequence,
-    }
+/// Replaces the contents of `candidates` with the indexes of every match sorted by j and then
+/// i, keeping the order they were found in otherwise, as the JavaScript implementation
+/// searches them.
+fn js_order_matches(matches: &[Match], candidates: &mut Vec<usize>) {
+    candidates.clear();
+    candidates.extend(0..matches.len());
+    candidates.sort_unstable_by_key(|&index| (matches[index].j, matches[index].i, index));
 }
 
-fn factorial(n: usize) -> usize {
-    (1..=n).product()
+/// Keeps at most `max_matches` matches, those with the fewest guesses and the first of them
+/// on ties, in their original order and with their guesses estimated. The guesses of a
+/// password can only go up as matches are dropped, never down.
+pub(crate) fn cap_matches(
+    password: &str,
+    mut matches: Vec<Match>,
+    max_matches: usize,
+) -> Vec<Match> {
+    for m in &mut matches {
+        estimate_guesses(m, password);
+    }
+    if matches.len() <= max_matches {
+        return matches;
+    }
+    let mut matches = matches.into_iter().enumerate().collect::<Vec<_>>();
+    matches.sort_by_key(|(_, m)| m.guesses);
+    matches.truncate(max_matches);
+    matches.sort_by_key(|&(index, _)| index);
+    matches.into_iter().map(|(_, m)| m).collect()
 }
 
-fn estimate_guesses(m: &mut Match, password: &str) -> u64 {
+pub(crate) fn estimate_guesses(m: &mut Match, password: &str) -> u64 {
     if let Some(guesses) = m.guesses {
         // a match's guess estimate doesn't change. cache it.
         return guesses;
     }
-    let min_guesses = if m.token.chars().count() < password.chars().count() {
-        if m.token.chars().count() == 1 {
-            MIN_SUBMATCH_GUESSES_SINGLE_CHAR
-        } else {
-            MIN_SUBMATCH_GUESSES_MULTI_CHAR
-        }
-    } else {
-        1
-    };
     let guesses = m.pattern.estimate(&m.token);
-    m.guesses = Some(cmp::max(guesses, min_guesses));
+    m.guesses = Some(cmp::max(guesses, min_guesses(&m.token, password)));
     m.guesses.unwrap()
 }
 
+/// Estimates the guesses of the matches that depend on how the estimator is configured: date
+/// and year matches, guessed around the years of `years`, which the estimates of the patterns
+/// alone take to be the default ones, spatial matches of the JavaScript-compatible profile,
+/// guessed like the JavaScript implementation does, and matches of patterns with a minimum
+/// number of guesses in `params`. The others are estimated with the defaults when they are
+/// scored.
+pub(crate) fn estimate_configured_guesses(
+    password: &str,
+    matches: &mut [Match],
+    years: &YearWindow,
+    params: &ScoringParams,
+    js_compatible: bool,
+) {
+    for m in matches.iter_mut().filter(|m| m.guesses.is_none()) {
+        let min_guesses = m
+            .pattern
+            .kind()
+            .and_then(|kind| params.min_guesses.get(&kind).copied());
+        let configured_guesses = match m.pattern {
+            MatchPattern::Date(ref p) => Some(p.estimate_with_years(years)),
+            MatchPattern::Regex(_) if is_appended_year(m, password, years) => {
+                Some(years.min_year_space as u64)
+            }
+            MatchPattern::Regex(ref p) => p.estimate_with_years(years),
+            MatchPattern::Spatial(ref p) if js_compatible => Some(p.estimate_as_js(&m.token)),
+            _ => None,
+        };
+        if configured_guesses.is_none() && min_guesses.is_none() {
+            continue;
+        }
+        let guesses = configured_guesses.unwrap_or_else(|| m.pattern.estimate(&m.token));
+        let min_guesses = min_guesses.unwrap_or_else(|| self::min_guesses(&m.token, password));
+        m.guesses = Some(cmp::max(guesses, min_guesses));
+    }
+}
+
+/// Whether `m` is the current year or the year before at the end of `password`, which people
+/// append to their passwords whatever year they are guessed around.
+pub(crate) fn is_appended_year(m: &Match, password: &str, years: &YearWindow) -> bool {
+    match m.pattern {
+        MatchPattern::Regex(ref p) if p.regex_name == "recent_year" => {
+            m.j + 1 == password.chars().count()
+                && years.is_current(p.regex_match[0].parse().unwrap())
+        }
+        _ => false,
+    }
+}
+
+/// How matches are scored, for deployments that need to deviate from the defaults.
+///
+/// ```
+/// use zxcvbn::matching::patterns::PatternKind;
+/// use zxcvbn::{ScoringParams, Zxcvbn};
+///
+/// // common words count for what they are even within longer passwords, while short
+/// // keyboard patterns count for more
+/// let params = ScoringParams::new()
+///     .min_guesses(PatternKind::Dictionary, 1)
+///     .min_guesses(PatternKind::Spatial, 1000);
+/// let estimator = Zxcvbn::new().with_scoring_params(params);
+/// let entropy = estimator.estimate("dragonXk7#q", &[]);
+/// assert!(entropy.guesses() < Zxcvbn::new().estimate("dragonXk7#q", &[]).guesses());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScoringParams {
+    /// The fewest guesses of matches of each pattern.
+    min_guesses: BTreeMap<PatternKind, u64>,
+    /// How the guesses of sequences follow from those of their matches.
+    sequence: SequenceParams,
+    /// Whether matches are found and compared in the order of the JavaScript implementation.
+    js_match_order: bool,
+}
+
+impl ScoringParams {
+    /// The default parameters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the fewest guesses a match of `pattern` may take.
+    ///
+    /// By default, matches covering part of the password take at least 10 guesses if they are
+    /// one character long and 50 otherwise, so that many short matches don't add up to fewer
+    /// guesses than a few long ones. Matches covering the whole password take at least 1.
+    /// The minimum set here replaces both.
+    pub fn min_guesses(mut self, pattern: PatternKind, guesses: u64) -> Self {
+        self.min_guesses.insert(pattern, guesses);
+        self
+    }
+
+    /// Whether to leave out the additive term that makes sequences of many matches take more
+    /// guesses. Defaults to `false`.
+    ///
+    /// A sequence of `l` matches takes `l! * product of guesses + penalty^(l - 1)` guesses,
+    /// where the additive `penalty^(l - 1)` stands for the attacker having to try sequences of
+    /// every shorter length first. Leaving it out scores passwords by their matches alone, as
+    /// the scoring tests of the original zxcvbn do.
+    pub fn exclude_additive(mut self, exclude_additive: bool) -> Self {
+        self.sequence.exclude_additive = exclude_additive;
+        self
+    }
+
+    /// Sets the base of the additive term, the guesses an attacker is assumed to make before
+    /// moving on to sequences of one more match. Defaults to 10,000.
+    ///
+    /// Lower values favor splitting passwords into many matches, higher ones favor a few long
+    /// matches or bruteforce.
+    pub fn sequence_length_penalty(mut self, penalty: u64) -> Self {
+        self.sequence.penalty = SequencePenalty::Exponential(penalty);
+        self
+    }
+
+    /// Sets the additive term of sequences by their number of matches, replacing the
+    /// `penalty^(l - 1)` curve of [`sequence_length_penalty`](ScoringParams::sequence_length_penalty)
+    /// with a table or a function, to compare other ways of preferring fewer matches.
+    ///
+    /// ```
+    /// use zxcvbn::scoring::SequencePenalty;
+    /// use zxcvbn::{ScoringParams, Zxcvbn};
+    ///
+    /// // each additional match costs as much as the one before
+    /// let params = ScoringParams::new()
+    ///     .sequence_penalty(SequencePenalty::function(|len| 10_000 * (len as u64 - 1)));
+    /// let estimator = Zxcvbn::new().with_scoring_params(params);
+    /// let entropy = estimator.estimate("dragon2019!blue", &[]);
+    /// assert!(entropy.guesses() < Zxcvbn::new().estimate("dragon2019!blue", &[]).guesses());
+    /// ```
+    pub fn sequence_penalty(mut self, penalty: SequencePenalty) -> Self {
+        self.sequence.penalty = penalty;
+        self
+    }
+
+    /// Whether the guesses of a sequence of `l` matches are multiplied by `l!`. Defaults to
+    /// `true`.
+    ///
+    /// The factorial stands for the attacker not knowing which order the patterns of the
+    /// sequence come in. Turning it off models an attacker who tries the patterns in the order
+    /// they appear in.
+    pub fn match_orderings(mut self, match_orderings: bool) -> Self {
+        self.sequence.match_orderings = match_orderings;
+        self
+    }
+
+    /// Whether matches are found and compared in the order of the JavaScript implementation,
+    /// so that ties between equally good sequences are broken the same way. Defaults to
+    /// `false`, and to `true` for [`Profile::LegacyJsCompatible`](crate::profile::Profile).
+    ///
+    /// The JavaScript implementation matches keyboard patterns on QWERTY, Dvorak, the keypad
+    /// and the Mac keypad in that order, and its search goes through every match, the first
+    /// one of equally good ones winning. By default, keyboards are tried in alphabetical
+    /// order and matches that cannot be part of the optimal sequence are dropped before the
+    /// search, so a row of digits typed the same on QWERTY and Dvorak is reported as Dvorak.
+    pub fn js_match_order(mut self, js_match_order: bool) -> Self {
+        self.js_match_order = js_match_order;
+        self
+    }
+
+    /// Whether matches are found and compared in the order of the JavaScript implementation,
+    /// see [`js_match_order`](ScoringParams::js_match_order).
+    pub(crate) fn has_js_match_order(&self) -> bool {
+        self.js_match_order
+    }
+
+    /// The guesses of a sequence of `len` matches whose guesses multiply to `pi`.
+    pub(crate) fn sequence_guesses(&self, len: usize, pi: u64) -> u64 {
+        self.sequence.guesses(len, pi)
+    }
+
+    /// Hashes the parameters that change guesses into a model version. Penalties are hashed
+    /// by their terms for every number of matches an estimate can have, which tells
+    /// functions apart too.
+    pub(crate) fn fingerprint(&self, fingerprint: &mut Fingerprint) {
+        for (pattern, guesses) in &self.min_guesses {
+            fingerprint.write_str(pattern.name());
+            fingerprint.write_u64(*guesses);
+        }
+        fingerprint.write(&[
+            self.sequence.match_orderings as u8,
+            self.sequence.exclude_additive as u8,
+            self.js_match_order as u8,
+        ]);
+        for len in 1..=100 {
+            fingerprint.write_u64(self.sequence.penalty.penalty(len).get());
+        }
+    }
+}
+
+fn min_guesses(token: &str, password: &str) -> u64 {
+    estimates::min_guesses(token.chars().count(), password.chars().count())
+}
+
 trait Estimator {
     fn estimate(&mut self, token: &str) -> u64;
 }
 
+/// The guesses of a match, at least the minimum of matches of its length within `password`,
+/// as the optimal sequence counts them. A match whose guesses are already set keeps them.
+pub fn estimate_match(m: &Match, password: &str) -> u64 {
+    estimate_guesses(&mut m.clone(), password)
+}
+
+/// The guesses of a dictionary word: its rank, times its uppercase, l33t and typo
+/// variations, doubled if it is reversed.
+pub fn estimate_dictionary(pattern: &DictionaryPattern, token: &str) -> u64 {
+    pattern.clone().estimate(token)
+}
+
+/// The guesses of a keyboard pattern, by the starting keys, turns and shifted keys of its
+/// graph, or of a whole region of keys.
+pub fn estimate_spatial(pattern: &SpatialPattern, token: &str) -> u64 {
+    pattern.clone().estimate(token)
+}
+
+/// The guesses of a repeated token: those of the base token times the repetitions.
+pub fn estimate_repeat(pattern: &RepeatPattern, token: &str) -> u64 {
+    pattern.clone().estimate(token)
+}
+
+/// The guesses of text followed by its mirror image, from those of its first half.
+pub fn estimate_mirror(pattern: &MirrorPattern, token: &str) -> u64 {
+    pattern.clone().estimate(token)
+}
+
+/// The guesses of text retyped with shift toggled, from those of the text.
+pub fn estimate_shift_repeat(pattern: &ShiftRepeatPattern, token: &str) -> u64 {
+    pattern.clone().estimate(token)
+}
+
+/// The guesses of text with every character typed several times, from those of the text.
+pub fn estimate_echo(pattern: &EchoPattern, _token: &str) -> u64 {
+    echo_guesses(pattern)
+}
+
+/// The guesses of a phone number from the user inputs, or part of one, by the rank of the
+/// user input.
+pub fn estimate_phone(pattern: &PhonePattern, token: &str) -> u64 {
+    pattern.clone().estimate(token)
+}
+
+/// The guesses of a national ID number: every number of its kind that can be issued. An ID
+/// of a kind no matcher of the crate finds is bruteforced.
+pub fn estimate_national_id(pattern: &NationalIdPattern, token: &str) -> u64 {
+    pattern.clone().estimate(token)
+}
+
+/// The guesses of a payment card number or IBAN, a low constant.
+pub fn estimate_payment(pattern: &PaymentPattern, token: &str) -> u64 {
+    pattern.clone().estimate(token)
+}
+
+/// The guesses of an unlock pattern written as digits, by its first dot, length and knight
+/// moves. A token that can't be drawn on the grid is bruteforced.
+pub fn estimate_unlock(pattern: &UnlockPattern, token: &str) -> u64 {
+    pattern.clone().estimate(token)
+}
+
+/// The guesses of two words typed one after the other: the rank of the pair if the words
+/// often go together, or else the product of their ranks, times their capitalizations.
+pub fn estimate_word_pair(pattern: &WordPairPattern, token: &str) -> u64 {
+    pattern.clone().estimate(token)
+}
+
+/// The guesses of the first letters of the words of a famous phrase, by the rank of the phrase
+/// and the capitalization of the letters.
+pub fn estimate_acronym(pattern: &AcronymPattern, token: &str) -> u64 {
+    pattern.clone().estimate(token)
+}
+
+/// The guesses of a sequence of characters, by its first character, direction and length. An
+/// empty token is bruteforced.
+pub fn estimate_sequence(pattern: &SequencePattern, token: &str) -> u64 {
+    pattern.clone().estimate(token)
+}
+
+/// The guesses of two interleaved sequences: the product of their guesses.
+pub fn estimate_interleaved(pattern: &InterleavedPattern, token: &str) -> u64 {
+    pattern.clone().estimate(token)
+}
+
+/// The guesses of emoji, by their kind and count.
+pub fn estimate_emoji(pattern: &EmojiPattern, token: &str) -> u64 {
+    pattern.clone().estimate(token)
+}
+
+/// The guesses of a regex match, such as a run of digits, a year or an escaped code point,
+/// with years guessed around the current one. A regex no matcher of the crate finds, or
+/// without the captures its matcher makes, is bruteforced.
+pub fn estimate_regex(pattern: &RegexPattern, token: &str) -> u64 {
+    pattern.clone().estimate(token)
+}
+
+/// The guesses of a date, by its distance from the current year and whether it has a
+/// separator.
+pub fn estimate_date(pattern: &DatePattern, token: &str) -> u64 {
+    pattern.clone().estimate(token)
+}
+
+/// The guesses of bruteforcing `token`, 10 per character.
+pub fn estimate_bruteforce(token: &str) -> u64 {
+    estimates::bruteforce(token.chars().count())
+}
+
 impl Estimator for MatchPattern {
     fn estimate(&mut self, token: &str) -> u64 {
         match *self {
@@ -243,27 +945,31 @@ impl Estimator for MatchPattern {
             MatchPattern::Sequence(ref mut p) => p.estimate(token),
             MatchPattern::Regex(ref mut p) => p.estimate(token),
             MatchPattern::Date(ref mut p) => p.estimate(token),
-            MatchPattern::BruteForce => {
-                let mut guesses = BRUTEFORCE_CARDINALITY;
-                let token_len = token.chars().count();
-                if token_len >= 2 {
-                    for _ in 2..=token_len {
-                        guesses = guesses.saturating_mul(BRUTEFORCE_CARDINALITY);
-                    }
-                }
-                // small detail: make bruteforce matches at minimum one guess bigger than smallest allowed
-                // submatch guesses, such that non-bruteforce submatches over the same [i..j] take precedence.
-                let min_guesses = if token_len == 1 {
-                    MIN_SUBMATCH_GUESSES_SINGLE_CHAR + 1
-                } else {
-                    MIN_SUBMATCH_GUESSES_MULTI_CHAR + 1
-                };
-                cmp::max(guesses, min_guesses)
-            }
+            MatchPattern::Emoji(ref mut p) => p.estimate(token),
+            MatchPattern::Interleaved(ref mut p) => p.estimate(token),
+            MatchPattern::Mirror(ref mut p) => p.estimate(token),
+            MatchPattern::ShiftRepeat(ref mut p) => p.estimate(token),
+            MatchPattern::Echo(ref mut p) => p.estimate(token),
+            MatchPattern::Phone(ref mut p) => p.estimate(token),
+            MatchPattern::NationalId(ref mut p) => p.estimate(token),
+            MatchPattern::Payment(ref mut p) => p.estimate(token),
+            MatchPattern::Unlock(ref mut p) => p.estimate(token),
+            MatchPattern::WordPair(ref mut p) => p.estimate(token),
+            MatchPattern::Acronym(ref mut p) => p.estimate(token),
+            MatchPattern::BruteForce => estimates::bruteforce(token.chars().count()),
         }
     }
 }
 
+/// The most guesses a password of `len` characters can need: those of bruteforcing it
+/// whole, which every other match sequence has to beat.
+pub(crate) fn max_guesses(len: usize) -> u64 {
+    if len == 0 {
+        return 1;
+    }
+    (Guesses::new(estimates::bruteforce(len)) + 1).get()
+}
+
 impl Estimator for DictionaryPattern {
     fn estimate(&mut self, token: &str) -> u64 {
         let uppercase_variations = uppercase_variations(token);
@@ -271,144 +977,252 @@ impl Estimator for DictionaryPattern {
         self.base_guesses = self.rank as u64;
         self.uppercase_variations = uppercase_variations;
         self.l33t_variations = l33t_variations;
-        self.base_guesses
+        let guesses = Guesses::new(self.base_guesses)
             * self.uppercase_variations
             * self.l33t_variations
+            * typo_variations(self, token)
             * if self.reversed { 2 } else { 1 }
+            * if self.layout.is_some() {
+                LAYOUT_VARIATIONS
+            } else {
+                1
+            };
+        guesses.get()
     }
 }
 
-fn uppercase_variations(token: &str) -> u64 {
-    if token.chars().all(char::is_lowercase) || token.to_lowercase().as_str() == token {
+/// Characters a typo is assumed to insert or replace a character with: lowercase letters
+/// and digits.
+const TYPO_ALPHABET: u64 = 36;
+
+/// Number of variations of the matched word with as many typos as the match has. A key
+/// replaced by one next to it is the likeliest typo, so an attacker tries every key replaced
+/// by each of its neighbors first. Other typos take trying every deleted character, every
+/// character replaced by another and every character inserted.
+fn typo_variations(pattern: &DictionaryPattern, token: &str) -> u64 {
+    if pattern.typos == 0 {
         return 1;
     }
-    // a capitalized token is the most common capitalization scheme,
-    // so it only doubles the search space (uncapitalized + capitalized).
-    // allcaps and end-capitalized are common enough too, underestimate as 2x factor to be safe.
-    if ((token.chars().next().unwrap().is_uppercase()
-        || token.chars().last().unwrap().is_uppercase())
-        && token.chars().filter(|&c| c.is_uppercase()).count() == 1)
-        || token.chars().all(char::is_uppercase)
-    {
-        return 2;
-    }
-    // otherwise calculate the number of ways to capitalize U+L uppercase+lowercase letters
-    // with U uppercase letters or less. or, if there's more uppercase than lower (for eg. PASSwORD),
-    // the number of ways to lowercase U+L letters with L lowercase letters or less.
-    let upper = token.chars().filter(|c| c.is_uppercase()).count();
-    let lower = token.chars().filter(|c| c.is_lowercase()).count();
-    (1..=cmp::min(upper, lower))
-        .map(|i| n_ck(upper + lower, i))
-        .sum()
+    let word = pattern.matched_word.chars().collect::<Vec<_>>();
+    if pattern.typos == 1 && is_keyboard_typo(&word, token) {
+        return word
+            .iter()
+            .map(|&c| qwerty_neighbors(c).count() as u64)
+            .sum();
+    }
+    let len = word.len() as u64;
+    let one_typo = len + (TYPO_ALPHABET - 1) * len + TYPO_ALPHABET * (len + 1);
+    Guesses::new(one_typo).pow(pattern.typos as u32).get()
 }
 
 fn l33t_variations(pattern: &DictionaryPattern, token: &str) -> u64 {
     if !pattern.l33t {
         return 1;
     }
-    let mut variations = 1;
-    for (subbed, unsubbed) in pattern.sub.as_ref().unwrap() {
+    let mut variations = Guesses::new(1);
+    for (subbed, unsubbed) in pattern.sub.iter().flatten() {
         // lower-case match.token before calculating: capitalization shouldn't affect l33t calc.
         let token = token.to_lowercase();
         let subbed = token.chars().filter(|c| c == subbed).count();
         let unsubbed = token.chars().filter(|c| c == unsubbed).count();
-        if subbed == 0 || unsubbed == 0 {
-            // for this sub, password is either fully subbed (444) or fully unsubbed (aaa)
-            // treat that as doubling the space (attacker needs to try fully subbed chars in addition to
-            // unsubbed.)
-            variations *= 2;
-        } else {
-            // this case is similar to capitalization:
-            // with aa44a, U = 3, S = 2, attacker needs to try unsubbed + one sub + two subs
-            let p = cmp::min(unsubbed, subbed);
-            let possibilities: u64 = (1..=p).map(|i| n_ck(unsubbed + subbed, i)).sum();
-            variations *= possibilities;
-        }
+        variations *= estimates::substitution_variations(subbed, unsubbed);
     }
-    variations
+    variations.get()
 }
 
-fn n_ck(n: usize, k: usize) -> u64 {
-    // http://blog.plover.com/math/choose.html
-    (if k > n {
-        0
-    } else if k == 0 {
-        1
-    } else {
-        let mut r: usize = 1;
-        let mut n = n;
-        for d in 1..=k {
-            r = r.saturating_mul(n);
-            r /= d;
-            n -= 1;
-        }
-        r
-    }) as u64
+/// Whether the token is the word with one key replaced by a neighboring one.
+pub(crate) fn is_keyboard_typo(word: &[char], token: &str) -> bool {
+    let token = token.to_lowercase().chars().collect::<Vec<_>>();
+    if token.len() != word.len() {
+        return false;
+    }
+    let mut differences = word.iter().zip(&token).filter(|(w, t)| w != t);
+    match (differences.next(), differences.next()) {
+        (Some((&w, &t)), None) => qwerty_neighbors(w).any(|neighbor| neighbor == t),
+        _ => false,
+    }
 }
 
 impl Estimator for SpatialPattern {
     fn estimate(&mut self, token: &str) -> u64 {
-        let (starts, degree) = if ["qwerty", "dvorak"].contains(&self.graph.as_str()) {
-            (*KEYBOARD_STARTING_POSITIONS, *KEYBOARD_AVERAGE_DEGREE)
+        if self.region.is_some() {
+            if let Some(region) = crate::matching::regions::find(&self.graph, token) {
+                return region.guesses;
+            }
+        }
+        if self.shape.is_some() {
+            if let Some(shape) = crate::matching::shapes::find(&self.graph, token) {
+                return shape.guesses;
+            }
+        }
+        let graphs = &crate::adjacency_graphs::GRAPHS;
+        // patterns on a graph zxcvbn doesn't know are guessed like those on a keyboard
+        let graph = graphs.get(self.graph.as_str()).unwrap_or(&graphs["qwerty"]);
+        self.guesses_on(graph, token)
+    }
+}
+
+impl SpatialPattern {
+    /// The guesses of the pattern as the JavaScript implementation makes them, with the
+    /// statistics of QWERTY for both keyboards and those of the keypad for both keypads.
+    fn estimate_as_js(&self, token: &str) -> u64 {
+        let graphs = &crate::adjacency_graphs::GRAPHS;
+        let graph = if ["qwerty", "dvorak"].contains(&self.graph.as_str()) {
+            &graphs["qwerty"]
         } else {
-            (*KEYPAD_STARTING_POSITIONS, *KEYPAD_AVERAGE_DEGREE)
+            &graphs["keypad"]
         };
-        let mut guesses = 0u64;
+        self.guesses_on(graph, token)
+    }
+
+    /// The guesses of the pattern as a walk on a graph with the statistics of `graph`.
+    fn guesses_on(&self, graph: &crate::adjacency_graphs::Graph, token: &str) -> u64 {
         let len = token.chars().count();
-        // estimate the number of possible patterns w/ length L or less with t turns or less.
-        for i in 2..=len {
-            let possible_turns = cmp::min(self.turns, i - 1);
-            for j in 1..=possible_turns {
-                guesses = guesses.saturating_add(
-                    n_ck(i - 1, j - 1)
-                        .saturating_mul(starts)
-                        .saturating_mul(degree.pow(j as u32)),
-                );
-            }
-        }
+        let mut guesses = Guesses::new(estimates::spatial(
+            len,
+            self.turns,
+            graph.starting_positions,
+            graph.average_degree,
+        ));
         // add extra guesses for shifted keys. (% instead of 5, A instead of a.)
         // math is similar to extra guesses of l33t substitutions in dictionary matches.
-        let shifted_count = self.shifted_count;
-        if shifted_count > 0 {
-            let unshifted_count = len - shifted_count;
-            if unshifted_count == 0 {
-                guesses = guesses.saturating_mul(2);
-            } else {
-                let shifted_variations: u64 = (1..=cmp::min(shifted_count, unshifted_count))
-                    .map(|i| n_ck(shifted_count + unshifted_count, i))
-                    .sum();
-                guesses = guesses.saturating_mul(shifted_variations);
-            }
+        if self.shifted_count > 0 {
+            guesses *= shift_variations(self.shifted_count, len);
         }
-        guesses
+        guesses.get()
     }
 }
 
-lazy_static! {
-    static ref KEYBOARD_AVERAGE_DEGREE: u64 = calc_average_degree(&crate::adjacency_graphs::QWERTY);
-    // slightly different for keypad/mac keypad, but close enough
-    static ref KEYPAD_AVERAGE_DEGREE: u64 = calc_average_degree(&crate::adjacency_graphs::KEYPAD);
-    static ref KEYBOARD_STARTING_POSITIONS: u64 = crate::adjacency_graphs::QWERTY.len() as u64;
-    static ref KEYPAD_STARTING_POSITIONS: u64 = crate::adjacency_graphs::KEYPAD.len() as u64;
+/// Finds the reference year, see [`crate::initialize`].
+pub(crate) fn initialize() {
+    Lazy::force(&REFERENCE_YEAR);
 }
 
-fn calc_average_degree(graph: &HashMap<char, Vec<Option<&'static str>>>) -> u64 {
-    let sum: u64 = graph
-        .values()
-        .map(|neighbors| neighbors.iter().filter(|n| n.is_some()).count() as u64)
-        .sum();
-    sum / graph.len() as u64
+impl Estimator for RepeatPattern {
+    fn estimate(&mut self, _: &str) -> u64 {
+        (Guesses::new(self.base_guesses) * self.repeat_count as u64).get()
+    }
 }
 
-impl Estimator for RepeatPattern {
+impl Estimator for MirrorPattern {
+    fn estimate(&mut self, _: &str) -> u64 {
+        // the second half is free once the first is guessed. mirroring with or without a
+        // middle character doubles the guesses.
+        (Guesses::new(self.base_guesses) * MIRROR_VARIATIONS).get()
+    }
+}
+
+impl Estimator for ShiftRepeatPattern {
     fn estimate(&mut self, _: &str) -> u64 {
-        self.base_guesses.saturating_mul(self.repeat_count as u64)
+        // the retyped keys are free once the base is guessed, retyping them in the same or
+        // reverse order doubles the guesses.
+        (Guesses::new(self.base_guesses) * SHIFT_REPEAT_VARIATIONS).get()
+    }
+}
+
+impl Estimator for EchoPattern {
+    fn estimate(&mut self, _: &str) -> u64 {
+        echo_guesses(self)
+    }
+}
+
+/// The guesses of an echo match, which the echo matcher compares for each way of reading the
+/// echoed text without copying the matches of the text.
+fn echo_guesses(pattern: &EchoPattern) -> u64 {
+    // the echoes are free once the text is guessed. an attacker tries typing each character
+    // once, twice and so on, up to the echo count, with doubled letters typed as often as the
+    // others or not.
+    let variations = if pattern.collapsed {
+        COLLAPSED_ECHO_VARIATIONS
+    } else {
+        1
+    };
+    (Guesses::new(pattern.base_guesses) * pattern.echo_count as u64 * variations).get()
+}
+
+impl Estimator for PhonePattern {
+    fn estimate(&mut self, _: &str) -> u64 {
+        // an attacker knowing the user's phone number tries each of its parts
+        (Guesses::new(self.rank as u64) * PHONE_PARTS).get()
+    }
+}
+
+impl Estimator for NationalIdPattern {
+    fn estimate(&mut self, token: &str) -> u64 {
+        let space = match self.id_name {
+            "ssn" => SSN_SPACE,
+            "nino" => NINO_SPACE,
+            "sin" => SIN_SPACE,
+            // an ID no matcher of the crate finds is bruteforced
+            _ => return estimate_bruteforce(token),
+        };
+        space * NATIONAL_ID_FORMS
+    }
+}
+
+impl Estimator for PaymentPattern {
+    fn estimate(&mut self, _: &str) -> u64 {
+        PAYMENT_GUESSES
+    }
+}
+
+impl Estimator for UnlockPattern {
+    fn estimate(&mut self, token: &str) -> u64 {
+        let start = token.chars().next().and_then(|c| c.to_digit(10));
+        let patterns = start.and_then(|start| {
+            unlock::pattern_count(start, token.chars().count()).map(|count| (start, count))
+        });
+        // a token that can't be drawn on the grid is bruteforced
+        let (start, patterns) = match patterns {
+            Some(patterns) => patterns,
+            None => return estimate_bruteforce(token),
+        };
+        let share = if start == 1 {
+            UNLOCK_TOP_LEFT_START_PER_MILLE
+        } else {
+            UNLOCK_OTHER_START_PER_MILLE
+        };
+        // the patterns of the same length from the same dot, spread over the share of
+        // patterns starting there
+        let knight_moves = Guesses::new(UNLOCK_KNIGHT_MOVE_FACTOR).pow(self.knight_moves as u32);
+        (Guesses::new(patterns * 1000 / share) * knight_moves).get()
+    }
+}
+
+impl Estimator for WordPairPattern {
+    fn estimate(&mut self, token: &str) -> u64 {
+        // each word is capitalized on its own, as in "OkayThanks"
+        let first_len = self.first_word.chars().count();
+        let first = token.chars().take(first_len).collect::<String>();
+        let second = token.chars().skip(first_len).collect::<String>();
+        self.uppercase_variations =
+            (Guesses::new(uppercase_variations(&first)) * uppercase_variations(&second)).get();
+        let draws = match self.pair_rank {
+            Some(rank) => Guesses::new(rank as u64),
+            None => Guesses::new(self.first_rank as u64) * self.second_rank as u64,
+        };
+        (draws * self.uppercase_variations).get()
+    }
+}
+
+impl Estimator for AcronymPattern {
+    fn estimate(&mut self, token: &str) -> u64 {
+        self.uppercase_variations = uppercase_variations(token);
+        let spellings = Guesses::new(self.rank as u64) * ACRONYM_SPELLINGS;
+        ((spellings + ACRONYM_BASE_GUESSES) * self.uppercase_variations).get()
     }
 }
 
 impl Estimator for SequencePattern {
     fn estimate(&mut self, token: &str) -> u64 {
-        let first_chr = token.chars().next().unwrap();
+        let mut first_chr = match token.chars().next() {
+            Some(first_chr) => first_chr,
+            None => return estimate_bruteforce(token),
+        };
+        if self.shifted_count > 0 {
+            // the digit the sequence starts at, e.g. '1' for "!@#"
+            first_chr = crate::matching::unshifted_digit(first_chr).unwrap_or(first_chr);
+        }
         // lower guesses for obvious starting points
         let mut base_guesses = if ['a', 'A', 'z', 'Z', '0', '1', '9'].contains(&first_chr) {
             4
@@ -424,44 +1238,153 @@ impl Estimator for SequencePattern {
             // 2x guesses
             base_guesses *= 2;
         }
-        base_guesses * token.chars().count() as u64
+        let len = token.chars().count();
+        let guesses = Guesses::new(base_guesses) * len as u64;
+        if self.shifted_count > 0 {
+            (guesses * shift_variations(self.shifted_count, len)).get()
+        } else {
+            guesses.get()
+        }
+    }
+}
+
+impl Estimator for InterleavedPattern {
+    fn estimate(&mut self, _: &str) -> u64 {
+        // guessing either sequence is no easier for the other one being interleaved with it.
+        let first = self.first_sequence.estimate(&self.first_token);
+        let second = self.second_sequence.estimate(&self.second_token);
+        (Guesses::new(first) * second).get()
+    }
+}
+
+impl Estimator for EmojiPattern {
+    fn estimate(&mut self, _: &str) -> u64 {
+        let count = self.emoji_count as u64;
+        match self.emoji_kind {
+            "repeat" => (Guesses::new(self.base_guesses) * count).get(),
+            "keyboard" => {
+                let directions = if self.ascending { 1 } else { 2 };
+                (Guesses::new(self.base_guesses) * directions * count).get()
+            }
+            _ => self.base_guesses,
+        }
     }
 }
 
 impl Estimator for RegexPattern {
     fn estimate(&mut self, token: &str) -> u64 {
-        if CHAR_CLASS_BASES.keys().any(|x| *x == self.regex_name) {
-            CHAR_CLASS_BASES[self.regex_name].pow(token.chars().count() as u32)
+        let char_class_base = CHAR_CLASS_BASES
+            .iter()
+            .find(|&&(name, _)| name == self.regex_name);
+        if let Some(&(_, base)) = char_class_base {
+            Guesses::new(base).pow(token.chars().count() as u32).get()
+        } else if let Some(guesses) = self.estimate_with_years(&YearWindow::default()) {
+            guesses
         } else {
-            match self.regex_name {
-                "recent_year" => {
-                    let year_space =
-                        (self.regex_match[0].parse::<i32>().unwrap() - *REFERENCE_YEAR).abs();
-                    cmp::max(year_space, MIN_YEAR_SPACE) as u64
+            let guesses = match self.regex_name {
+                "alt_code" => self.regex_match.get(1).and_then(|code| {
+                    // lower codes are more memorable, like years closer to the reference year.
+                    let code = code.parse::<u64>().ok()?;
+                    let space = cmp::max(Guesses::new(code) + 1, MIN_CODE_POINT_SPACE.into());
+                    Some((space * ALT_CODE_VARIANTS).get())
+                }),
+                "unicode_escape" => escaped_code_point(&self.regex_match).map(|code_point| {
+                    let space = cmp::max(Guesses::new(code_point) + 1, MIN_CODE_POINT_SPACE.into());
+                    (space * ESCAPE_SYNTAXES).get()
+                }),
+                _ => None,
+            };
+            // a regex no matcher of the crate finds, or a match it wouldn't make, is bruteforced
+            guesses.unwrap_or_else(|| estimate_bruteforce(token))
+        }
+    }
+}
+
+impl RegexPattern {
+    /// The guesses of a year, decade or year range, or `None` for other regexes and for
+    /// captures that aren't years.
+    fn estimate_with_years(&self, years: &YearWindow) -> Option<u64> {
+        let reference_year = years.reference_year;
+        let guesses = match self.regex_name {
+            "recent_year" => {
+                let year = self.regex_match.first()?.parse::<i32>().ok()?;
+                let year_space = (year - reference_year).abs();
+                cmp::max(year_space, years.min_year_space) as u64
+            }
+            "decade" => {
+                let mut decade = self.regex_match.get(1)?.parse::<i32>().ok()?;
+                if decade < 100 {
+                    // "20s" is the 2020s, but "50s" the 1950s until the 2050s come.
+                    let century = reference_year.div_euclid(100) * 100;
+                    decade += if decade + century > reference_year {
+                        century - 100
+                    } else {
+                        century
+                    };
                 }
-                _ => unreachable!(),
+                let decade_space = (decade - reference_year).abs() / 10;
+                cmp::max(decade_space, MIN_DECADE_SPACE) as u64 * DECADE_FORMS
+            }
+            "year_range" => {
+                let (first, last) = range_years(&self.regex_match)?;
+                let year_space = cmp::max((first - reference_year).abs(), years.min_year_space);
+                let span = cmp::max((last - first).abs(), MIN_YEAR_RANGE_SPAN);
+                // add factor of 4 for separator selection, as for dates
+                (year_space * span) as u64 * 4
             }
+            _ => return None,
+        };
+        Some(guesses)
+    }
+}
+
+/// The first and last year of a `year_range` match. A two digit last year is taken to be
+/// in the century of the first year, or the next one for ranges like "1995-05".
+fn range_years(regex_match: &[String]) -> Option<(i32, i32)> {
+    let first = regex_match.get(1)?.parse::<i32>().ok()?;
+    let mut last = regex_match.get(2)?.parse::<i32>().ok()?;
+    if regex_match[2].len() == 2 {
+        last += first / 100 * 100;
+        if last < first {
+            last += 100;
         }
     }
+    Some((first, last))
 }
 
-lazy_static! {
-    static ref CHAR_CLASS_BASES: HashMap<&'static str, u64> = {
-        let mut table = HashMap::with_capacity(6);
-        table.insert("alpha_lower", 26);
-        table.insert("alpha_upper", 26);
-        table.insert("alpha", 52);
-        table.insert("alphanumeric", 62);
-        table.insert("digits", 10);
-        table.insert("symbols", 33);
-        table
-    };
+/// The code point of a `unicode_escape` match. The last capture is decimal, the others hex.
+fn escaped_code_point(regex_match: &[String]) -> Option<u64> {
+    let (k, digits) = regex_match
+        .iter()
+        .enumerate()
+        .skip(1)
+        .find(|(_, x)| !x.is_empty())?;
+    let radix = if k == regex_match.len() - 1 { 10 } else { 16 };
+    u64::from_str_radix(digits, radix).ok()
 }
 
+const CHAR_CLASS_BASES: &[(&str, u64)] = &[
+    ("alpha_lower", 26),
+    ("alpha_upper", 26),
+    ("alpha", 52),
+    ("alphanumeric", 62),
+    ("digits", 10),
+    ("symbols", 33),
+];
+
 impl Estimator for DatePattern {
     fn estimate(&mut self, _: &str) -> u64 {
-        // base guesses: (year distance from REFERENCE_YEAR) * num_days * num_years
-        let year_space = cmp::max((self.year - *REFERENCE_YEAR).abs(), MIN_YEAR_SPACE);
+        self.estimate_with_years(&YearWindow::default())
+    }
+}
+
+impl DatePattern {
+    fn estimate_with_years(&self, years: &YearWindow) -> u64 {
+        // base guesses: (year distance from the reference year) * num_days * num_years
+        let year_space = cmp::max(
+            (self.year - years.reference_year).abs(),
+            years.min_year_space,
+        );
         let mut guesses = year_space as u64 * 365;
         // add factor of 4 for separator selection (one of ~4 choices)
         if !self.separator.is_empty() {
@@ -474,11 +1397,19 @@ impl Estimator for DatePattern {
 #[cfg(test)]
 mod tests {
     use crate::matching::patterns::*;
-    use crate::matching::Match;
+    use crate::matching::{InvalidMatch, Match, YearWindow};
     use crate::scoring;
     use crate::scoring::Estimator;
     use quickcheck::TestResult;
-    use std::collections::HashMap;
+    use std::cmp;
+    use std::collections::BTreeMap;
+    use zxcvbn_core::combinatorics::{factorial, n_ck};
+    use zxcvbn_core::guesses::Guesses;
+    use zxcvbn_core::search::SequenceParams;
+
+    fn without_additive() -> scoring::ScoringParams {
+        scoring::ScoringParams::new().exclude_additive(true)
+    }
 
     #[test]
     fn test_n_ck() {
@@ -493,14 +1424,14 @@ mod tests {
             (33, 7, 4_272_048),
         ];
         for &(n, k, result) in &test_data {
-            assert_eq!(scoring::n_ck(n, k), result);
+            assert_eq!(n_ck(n, k), result);
         }
     }
 
     quickcheck! {
         fn test_n_ck_mul_overflow(n: usize, k: usize) -> TestResult {
-            if n >= 63 && n <= 100 {
-                scoring::n_ck(n, k); // Must not panic
+            if (63..=100).contains(&n) {
+                n_ck(n, k); // Must not panic
                 TestResult::from_bool(true)
             } else {
                 TestResult::discard()
@@ -508,24 +1439,86 @@ mod tests {
         }
 
         fn test_n_ck_mirror_identity(n: usize, k: usize) -> TestResult {
-            if k > n || n >= 63 {
+            if k > n {
                 return TestResult::discard();
             }
-            TestResult::from_bool(scoring::n_ck(n, k) == scoring::n_ck(n, n-k))
+            TestResult::from_bool(n_ck(n, k) == n_ck(n, n-k))
+        }
+
+        fn test_n_ck_grows_with_n(n: usize, k: usize) -> TestResult {
+            if k > n || n == usize::MAX {
+                return TestResult::discard();
+            }
+            TestResult::from_bool(n_ck(n + 1, k) >= n_ck(n, k))
         }
 
         fn test_n_ck_pascals_triangle(n: usize, k: usize) -> TestResult {
-            if n == 0 || k == 0 || n >= 63 {
+            if n == 0 || k == 0 {
                 return TestResult::discard();
             }
-            TestResult::from_bool(scoring::n_ck(n, k) == scoring::n_ck(n-1, k-1) + scoring::n_ck(n-1, k))
+            TestResult::from_bool(n_ck(n, k) == n_ck(n-1, k-1).saturating_add(n_ck(n-1, k)))
         }
+
+        fn test_guesses_saturate(a: u64, b: u64) -> bool {
+            let (wide_a, wide_b) = (u128::from(a), u128::from(b));
+            let max = u128::from(u64::MAX);
+            u128::from((Guesses::new(a) * b).get()) == cmp::min(wide_a * wide_b, max)
+                && u128::from((Guesses::new(a) + b).get()) == cmp::min(wide_a + wide_b, max)
+        }
+
+        fn test_factorial_saturates(n: usize) -> TestResult {
+            if n > 1000 {
+                return TestResult::discard();
+            }
+            TestResult::from_bool(factorial(n + 1) >= factorial(n))
+        }
+
+        fn test_sequence_guesses_dont_overflow(len: usize, pi: u64) -> TestResult {
+            if len == 0 || len > 200 {
+                return TestResult::discard();
+            }
+            let guesses = SequenceParams::default().guesses(len, pi);
+            TestResult::from_bool(guesses >= pi)
+        }
+
+        fn test_dictionary_guesses_dont_overflow(token: String, rank: usize, l33t: bool) -> TestResult {
+            if token.is_empty() {
+                return TestResult::discard();
+            }
+            let mut p = DictionaryPattern {
+                rank,
+                l33t,
+                sub: Some(token.chars().zip(token.chars().rev()).collect()),
+                reversed: true,
+                ..DictionaryPattern::default()
+            };
+            TestResult::from_bool(p.estimate(&token) >= rank as u64)
+        }
+    }
+
+    #[test]
+    fn test_long_token_guesses_saturate() {
+        // the ways to capitalize half of 100 letters are more than a u64 can count
+        let token = "aA".repeat(50);
+        let mut p = DictionaryPattern {
+            rank: 1000,
+            ..DictionaryPattern::default()
+        };
+        assert_eq!(p.estimate(&token), u64::MAX);
+        let mut p = SpatialPattern {
+            graph: "qwerty".to_string(),
+            turns: 100,
+            shifted_count: 50,
+            ..SpatialPattern::default()
+        };
+        assert_eq!(p.estimate(&"qQ".repeat(50)), u64::MAX);
+        assert_eq!(SequenceParams::default().guesses(100, 2), u64::MAX);
     }
 
     #[test]
     fn test_search_returns_one_bruteforce_match_given_empty_match_sequence() {
         let password = "0123456789";
-        let result = scoring::most_guessable_match_sequence(password, &[], true);
+        let result = scoring::most_guessable_match_sequence(password, &[], &without_additive());
         assert_eq!(result.sequence.len(), 1);
         let m0 = &result.sequence[0];
         assert_eq!(m0.pattern.variant(), "bruteforce");
@@ -540,12 +1533,16 @@ mod tests {
         let m = Match {
             i: 0,
             j: 5,
+            token: password[0..=5].to_string(),
             guesses: Some(1),
             pattern: MatchPattern::Dictionary(DictionaryPattern::default()),
-            ..Match::default()
         };
 
-        let result = scoring::most_guessable_match_sequence(password, &[m.clone()], true);
+        let result = scoring::most_guessable_match_sequence(
+            password,
+            std::slice::from_ref(&m),
+            &without_additive(),
+        );
         assert_eq!(result.sequence.len(), 2);
         assert_eq!(result.sequence[0], m);
         let m1 = &result.sequence[1];
@@ -560,12 +1557,16 @@ mod tests {
         let m = Match {
             i: 3,
             j: 9,
+            token: password[3..=9].to_string(),
             guesses: Some(1),
             pattern: MatchPattern::Dictionary(DictionaryPattern::default()),
-            ..Match::default()
         };
 
-        let result = scoring::most_guessable_match_sequence(password, &[m.clone()], true);
+        let result = scoring::most_guessable_match_sequence(
+            password,
+            std::slice::from_ref(&m),
+            &without_additive(),
+        );
         assert_eq!(result.sequence.len(), 2);
         let m0 = &result.sequence[0];
         assert_eq!(m0.pattern.variant(), "bruteforce");
@@ -580,12 +1581,16 @@ mod tests {
         let m = Match {
             i: 1,
             j: 8,
+            token: password[1..=8].to_string(),
             guesses: Some(1),
             pattern: MatchPattern::Dictionary(DictionaryPattern::default()),
-            ..Match::default()
         };
 
-        let result = scoring::most_guessable_match_sequence(password, &[m.clone()], true);
+        let result = scoring::most_guessable_match_sequence(
+            password,
+            std::slice::from_ref(&m),
+            &without_additive(),
+        );
         assert_eq!(result.sequence.len(), 3);
         assert_eq!(result.sequence[1], m);
         let m0 = &result.sequence[0];
@@ -604,25 +1609,32 @@ mod tests {
         let mut m0 = Match {
             i: 0,
             j: 9,
+            token: password[0..=9].to_string(),
             guesses: Some(1),
             pattern: MatchPattern::Dictionary(DictionaryPattern::default()),
-            ..Match::default()
         };
         let m1 = Match {
             i: 0,
             j: 9,
+            token: password[0..=9].to_string(),
             guesses: Some(2),
             pattern: MatchPattern::Dictionary(DictionaryPattern::default()),
-            ..Match::default()
         };
 
-        let result =
-            scoring::most_guessable_match_sequence(password, &[m0.clone(), m1.clone()], true);
+        let result = scoring::most_guessable_match_sequence(
+            password,
+            &[m0.clone(), m1.clone()],
+            &without_additive(),
+        );
         assert_eq!(result.sequence.len(), 1);
         assert_eq!(result.sequence[0], m0);
         // make sure ordering doesn't matter
         m0.guesses = Some(3);
-        let result = scoring::most_guessable_match_sequence(password, &[m0, m1.clone()], true);
+        let result = scoring::most_guessable_match_sequence(
+            password,
+            &[m0, m1.clone()],
+            &without_additive(),
+        );
         assert_eq!(result.sequence.len(), 1);
         assert_eq!(result.sequence[0], m1);
     }
@@ -633,59 +1645,352 @@ mod tests {
         let m0 = Match {
             i: 0,
             j: 9,
+            token: password[0..=9].to_string(),
+            guesses: Some(3),
+            pattern: MatchPattern::Dictionary(DictionaryPattern::default()),
+        };
+        let m1 = Match {
+            i: 0,
+            j: 3,
+            token: password[0..=3].to_string(),
+            guesses: Some(2),
+            pattern: MatchPattern::Dictionary(DictionaryPattern::default()),
+        };
+        let m2 = Match {
+            i: 4,
+            j: 9,
+            token: password[4..=9].to_string(),
+            guesses: Some(1),
+            pattern: MatchPattern::Dictionary(DictionaryPattern::default()),
+        };
+
+        let result = scoring::most_guessable_match_sequence(
+            password,
+            &[m0.clone(), m1, m2],
+            &without_additive(),
+        );
+        assert_eq!(result.guesses, 3);
+        assert_eq!(result.sequence, vec![m0]);
+    }
+
+    #[test]
+    fn test_search_when_m0_covers_m1_and_m2_choose_m1_m2_when_m0_gt_m1_t_m2_t_fact_2() {
+        let password = "0123456789";
+        let m0 = Match {
+            i: 0,
+            j: 9,
+            token: password[0..=9].to_string(),
+            guesses: Some(5),
+            pattern: MatchPattern::Dictionary(DictionaryPattern::default()),
+        };
+        let m1 = Match {
+            i: 0,
+            j: 3,
+            token: password[0..=3].to_string(),
+            guesses: Some(2),
+            pattern: MatchPattern::Dictionary(DictionaryPattern::default()),
+        };
+        let m2 = Match {
+            i: 4,
+            j: 9,
+            token: password[4..=9].to_string(),
+            guesses: Some(1),
+            pattern: MatchPattern::Dictionary(DictionaryPattern::default()),
+        };
+
+        let result = scoring::most_guessable_match_sequence(
+            password,
+            &[m0, m1.clone(), m2.clone()],
+            &without_additive(),
+        );
+        assert_eq!(result.guesses, 4);
+        assert_eq!(result.sequence, vec![m1, m2]);
+    }
+
+    #[test]
+    fn test_search_with_sequence_length_params() {
+        let password = "0123456789";
+        let m0 = Match {
+            i: 0,
+            j: 9,
+            token: password[0..=9].to_string(),
             guesses: Some(3),
             pattern: MatchPattern::Dictionary(DictionaryPattern::default()),
-            ..Match::default()
         };
         let m1 = Match {
             i: 0,
             j: 3,
-            guesses: Some(2),
+            token: password[0..=3].to_string(),
+            guesses: Some(2),
+            pattern: MatchPattern::Dictionary(DictionaryPattern::default()),
+        };
+        let m2 = Match {
+            i: 4,
+            j: 9,
+            token: password[4..=9].to_string(),
+            guesses: Some(1),
+            pattern: MatchPattern::Dictionary(DictionaryPattern::default()),
+        };
+        let matches = [m0.clone(), m1.clone(), m2.clone()];
+
+        let result =
+            scoring::most_guessable_match_sequence(password, &matches, &Default::default());
+        assert_eq!(result.guesses, 4);
+        assert_eq!(result.sequence, vec![m0.clone()]);
+
+        // without the factorial, the two matches take fewer guesses than the one
+        let params = without_additive().match_orderings(false);
+        let result = scoring::most_guessable_match_sequence(password, &matches, &params);
+        assert_eq!(result.guesses, 2);
+        assert_eq!(result.sequence, vec![m1.clone(), m2.clone()]);
+
+        // 2! * 2 + 1 > 3 + 1
+        let params = scoring::ScoringParams::new().sequence_length_penalty(1);
+        let result = scoring::most_guessable_match_sequence(password, &matches, &params);
+        assert_eq!(result.guesses, 4);
+        assert_eq!(result.sequence, vec![m0]);
+
+        let params = params.match_orderings(false);
+        let result = scoring::most_guessable_match_sequence(password, &matches, &params);
+        assert_eq!(result.guesses, 3);
+        assert_eq!(result.sequence, vec![m1, m2]);
+    }
+
+    #[test]
+    fn test_try_search_checks_matches() {
+        let password = "0123456789";
+        let m = Match {
+            i: 4,
+            j: 3,
+            guesses: Some(1),
             pattern: MatchPattern::Dictionary(DictionaryPattern::default()),
             ..Match::default()
         };
-        let m2 = Match {
-            i: 4,
+        let result = scoring::try_most_guessable_match_sequence(
+            password,
+            std::slice::from_ref(&m),
+            &without_additive(),
+        );
+        assert_eq!(result.unwrap_err(), InvalidMatch::EmptySpan { index: 0 });
+
+        let m = Match {
             j: 9,
+            token: "456789".to_string(),
+            ..m
+        };
+        let result = scoring::try_most_guessable_match_sequence(
+            password,
+            std::slice::from_ref(&m),
+            &without_additive(),
+        );
+        assert_eq!(result.unwrap().sequence.len(), 2);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "the token of match 0 isn't the characters of the password")]
+    fn test_search_panics_on_mismatched_token() {
+        let m = Match {
+            i: 0,
+            j: 2,
+            token: "abd".to_string(),
             guesses: Some(1),
             pattern: MatchPattern::Dictionary(DictionaryPattern::default()),
-            ..Match::default()
         };
+        scoring::most_guessable_match_sequence("abcdef", &[m], &without_additive());
+    }
 
-        let result = scoring::most_guessable_match_sequence(password, &[m0.clone(), m1, m2], true);
-        assert_eq!(result.guesses, 3);
-        assert_eq!(result.sequence, vec![m0]);
+    /// The matches the search can pick among `matches`.
+    fn candidates(
+        password: &str,
+        matches: &[Match],
+        params: &scoring::ScoringParams,
+    ) -> Vec<Match> {
+        let mut scratch = scoring::ScoringScratch::new();
+        scoring::fill_candidates(password, matches, params, &mut scratch);
+        scratch.candidate_matches(matches)
     }
 
     #[test]
-    fn test_search_when_m0_covers_m1_and_m2_choose_m1_m2_when_m0_gt_m1_t_m2_t_fact_2() {
+    fn test_prune_matches() {
         let password = "0123456789";
-        let m0 = Match {
-            i: 0,
-            j: 9,
-            guesses: Some(5),
+        let dictionary = |i, j, guesses| Match {
+            i,
+            j,
+            token: password[i..=j].to_string(),
+            guesses: Some(guesses),
             pattern: MatchPattern::Dictionary(DictionaryPattern::default()),
-            ..Match::default()
         };
-        let m1 = Match {
-            i: 0,
-            j: 3,
-            guesses: Some(2),
+        let matches = [
+            dictionary(4, 9, 7),
+            dictionary(0, 3, 5),
+            dictionary(0, 3, 2),
+            dictionary(4, 9, 3),
+            dictionary(4, 9, 3),
+            // more than bruteforcing "89"
+            dictionary(8, 9, 1000),
+        ];
+        let pruned = candidates(password, &matches, &scoring::ScoringParams::default());
+        assert_eq!(pruned, [matches[2].clone(), matches[3].clone()]);
+
+        // many repeats of "a" cover the same spans
+        let password = "aaaaaaaaaaaaaaaa";
+        let matches = crate::matching::omnimatch(password, &Default::default());
+        let pruned = candidates(password, &matches, &scoring::ScoringParams::default());
+        assert!(pruned.len() * 3 < matches.len() * 2);
+        let result = scoring::most_guessable_match_sequence(
+            password,
+            &matches,
+            &scoring::ScoringParams::default(),
+        );
+        assert!(result
+            .sequence
+            .iter()
+            .all(|m| m.pattern == MatchPattern::BruteForce || pruned.contains(m)));
+    }
+
+    #[test]
+    fn test_js_ordered_matches() {
+        let password = "0123456789";
+        let dictionary = |i, j, guesses| Match {
+            i,
+            j,
+            token: password[i..=j].to_string(),
+            guesses: Some(guesses),
             pattern: MatchPattern::Dictionary(DictionaryPattern::default()),
-            ..Match::default()
         };
-        let m2 = Match {
-            i: 4,
-            j: 9,
-            guesses: Some(1),
+        let matches = [
+            dictionary(4, 9, 7),
+            dictionary(0, 3, 5),
+            dictionary(4, 9, 3),
+            dictionary(8, 9, 1000),
+        ];
+        // nothing is dropped, and matches of the same span stay in the order they were found
+        let params = scoring::ScoringParams::default().js_match_order(true);
+        let ordered = candidates(password, &matches, &params);
+        assert_eq!(
+            ordered,
+            [
+                matches[1].clone(),
+                matches[0].clone(),
+                matches[2].clone(),
+                matches[3].clone()
+            ]
+        );
+
+        let params = scoring::ScoringParams::new().js_match_order(true);
+        let result = scoring::most_guessable_match_sequence(password, &matches, &params);
+        let default =
+            scoring::most_guessable_match_sequence(password, &matches, &Default::default());
+        assert_eq!(result.guesses, default.guesses);
+        assert_eq!(result.sequence, [matches[1].clone(), matches[2].clone()]);
+    }
+
+    #[test]
+    fn test_cap_matches() {
+        let password = "0123456789";
+        let dictionary = |i, j, guesses| Match {
+            i,
+            j,
+            token: password[i..=j].to_string(),
+            guesses: Some(guesses),
             pattern: MatchPattern::Dictionary(DictionaryPattern::default()),
-            ..Match::default()
         };
+        let matches = vec![
+            dictionary(0, 3, 50),
+            dictionary(0, 9, 7),
+            dictionary(2, 5, 7),
+            dictionary(4, 9, 3),
+        ];
+        let capped = scoring::cap_matches(password, matches.clone(), 2);
+        assert_eq!(capped, [matches[1].clone(), matches[3].clone()]);
+        assert_eq!(scoring::cap_matches(password, matches.clone(), 4), matches);
+        assert!(scoring::cap_matches(password, matches, 0).is_empty());
+    }
+
+    #[test]
+    fn test_k_best_match_sequences() {
+        let params = scoring::ScoringParams::default();
+        for password in [
+            "march1987",
+            "Tr0ub4dour&3",
+            "iloveyou1234",
+            "🎉party🎉",
+            "a",
+        ] {
+            let matches = crate::matching::omnimatch(password, &Default::default());
+            let optimal = scoring::most_guessable_match_sequence(password, &matches, &params);
+            let best = scoring::k_best_match_sequences(password, &matches, &params, 4);
+            assert_eq!(best[0].guesses, optimal.guesses, "{}", password);
+            for (k, calculation) in best.iter().enumerate() {
+                let covered = calculation
+                    .sequence
+                    .iter()
+                    .map(|m| m.token.as_str())
+                    .collect::<String>();
+                assert_eq!(covered, password);
+                if k > 0 {
+                    assert!(calculation.guesses >= best[k - 1].guesses);
+                    assert_ne!(calculation.sequence, best[k - 1].sequence);
+                }
+            }
+        }
+        let matches = crate::matching::omnimatch("a", &Default::default());
+        assert_eq!(
+            scoring::k_best_match_sequences("a", &matches, &params, 4).len(),
+            1
+        );
+        assert!(scoring::k_best_match_sequences("a", &matches, &params, 0).is_empty());
+        assert!(scoring::k_best_match_sequences("", &[], &params, 4).is_empty());
+    }
 
+    #[test]
+    fn test_search_does_not_bruteforce_emoji() {
+        let password = "a\u{1F991}\u{1F980}b";
+        let emoji = |i| Match {
+            i,
+            j: i,
+            token: password.chars().nth(i).unwrap().to_string(),
+            guesses: Some(3600),
+            pattern: MatchPattern::Emoji(EmojiPattern::default()),
+        };
+        let matches = [emoji(1), emoji(2)];
         let result =
-            scoring::most_guessable_match_sequence(password, &[m0, m1.clone(), m2.clone()], true);
-        assert_eq!(result.guesses, 4);
-        assert_eq!(result.sequence, vec![m1, m2]);
+            scoring::most_guessable_match_sequence(password, &matches, &without_additive());
+        let variants = result
+            .sequence
+            .iter()
+            .map(|m| m.pattern.variant())
+            .collect::<Vec<_>>();
+        assert_eq!(variants, ["bruteforce", "emoji", "emoji", "bruteforce"]);
+    }
+
+    #[test]
+    fn test_search_reuses_scratch() {
+        let mut scratch = scoring::ScoringScratch::new();
+        let emoji = Match {
+            i: 1,
+            j: 1,
+            token: "\u{1F991}".to_string(),
+            guesses: Some(3600),
+            pattern: MatchPattern::Emoji(EmojiPattern::default()),
+        };
+        for (password, matches) in [
+            ("a\u{1F991}bcdefgh", vec![emoji]),
+            ("abc", vec![]),
+            ("abcdefghij", vec![]),
+        ] {
+            let reused = scoring::most_guessable_match_sequence_with_scratch(
+                password,
+                &matches,
+                &without_additive(),
+                &mut scratch,
+            );
+            let fresh =
+                scoring::most_guessable_match_sequence(password, &matches, &without_additive());
+            assert_eq!(reused.guesses, fresh.guesses);
+            assert_eq!(reused.sequence, fresh.sequence);
+        }
     }
 
     #[test]
@@ -730,8 +2035,8 @@ mod tests {
         for &(token, base_token, repeat_count) in &test_data {
             let base_guesses = scoring::most_guessable_match_sequence(
                 base_token,
-                &crate::matching::omnimatch(base_token, &HashMap::new()),
-                false,
+                &crate::matching::omnimatch(base_token, &Default::default()),
+                &scoring::ScoringParams::default(),
             )
             .guesses;
             let mut p = RepeatPattern {
@@ -745,6 +2050,174 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_mirror_guesses() {
+        let base_token = "race";
+        let base_guesses = scoring::most_guessable_match_sequence(
+            base_token,
+            &crate::matching::omnimatch(base_token, &Default::default()),
+            &scoring::ScoringParams::default(),
+        )
+        .guesses;
+        let mut p = MirrorPattern {
+            base_token: base_token.to_string(),
+            base_guesses,
+            ..MirrorPattern::default()
+        };
+        assert_eq!(p.estimate("racecar"), base_guesses * 2);
+    }
+
+    #[test]
+    fn test_shift_repeat_guesses() {
+        let mut p = ShiftRepeatPattern {
+            base_token: "123456".to_string(),
+            base_guesses: 11,
+            reversed: true,
+            ..ShiftRepeatPattern::default()
+        };
+        assert_eq!(p.estimate("123456^%$#@!"), 22);
+    }
+
+    #[test]
+    fn test_echo_guesses() {
+        let mut p = EchoPattern {
+            base_token: "password".to_string(),
+            base_guesses: 3,
+            echo_count: 2,
+            ..EchoPattern::default()
+        };
+        assert_eq!(p.estimate("ppaasssswwoorrdd"), 6);
+        p.collapsed = true;
+        assert_eq!(p.estimate("ppaasswwoorrdd"), 12);
+    }
+
+    #[test]
+    fn test_phone_guesses() {
+        let mut p = PhonePattern {
+            part: "last_four",
+            rank: 2,
+        };
+        assert_eq!(p.estimate("4567"), 10);
+    }
+
+    #[test]
+    fn test_national_id_guesses() {
+        let mut p = NationalIdPattern {
+            id_name: "ssn",
+            region: "us",
+        };
+        assert_eq!(p.estimate("078-05-1120"), 898 * 99 * 9999 * 2);
+    }
+
+    #[test]
+    fn test_payment_guesses() {
+        let mut p = PaymentPattern {
+            payment_kind: "card",
+        };
+        assert_eq!(p.estimate("4111111111111111"), 10_000);
+    }
+
+    #[test]
+    fn test_unlock_guesses() {
+        // 572 patterns of 5 dots start at the top left dot, which 40% of patterns start at
+        let mut p = UnlockPattern { knight_moves: 0 };
+        assert_eq!(p.estimate("14789"), 572 * 1000 / 400);
+        assert!(p.estimate("14789") < p.estimate("36987"));
+        let mut p = UnlockPattern { knight_moves: 1 };
+        assert_eq!(p.estimate("16789"), 572 * 1000 / 400 * 4);
+    }
+
+    #[test]
+    fn test_acronym_guesses() {
+        let mut p = AcronymPattern {
+            phrase: "to be or not to be that is the question",
+            rank: 1,
+            ..AcronymPattern::default()
+        };
+        assert_eq!(p.estimate("tbontbtitq"), 1010);
+        assert_eq!(p.estimate("Tbontbtitq"), 1010 * 2);
+        p.rank = 50;
+        assert_eq!(p.estimate("tbontbtitq"), 1500);
+    }
+
+    #[test]
+    fn test_estimates_of_default_patterns() {
+        // patterns built outside the crate may hold anything, and must not panic
+        for token in ["", "x", "a2\u{1F991}"] {
+            scoring::estimate_match(&Match::default(), token);
+            scoring::estimate_dictionary(&DictionaryPattern::default(), token);
+            scoring::estimate_spatial(&SpatialPattern::default(), token);
+            scoring::estimate_repeat(&RepeatPattern::default(), token);
+            scoring::estimate_mirror(&MirrorPattern::default(), token);
+            scoring::estimate_shift_repeat(&ShiftRepeatPattern::default(), token);
+            scoring::estimate_echo(&EchoPattern::default(), token);
+            scoring::estimate_phone(&PhonePattern::default(), token);
+            scoring::estimate_payment(&PaymentPattern::default(), token);
+            scoring::estimate_word_pair(&WordPairPattern::default(), token);
+            scoring::estimate_acronym(&AcronymPattern::default(), token);
+            scoring::estimate_interleaved(&InterleavedPattern::default(), token);
+            scoring::estimate_emoji(&EmojiPattern::default(), token);
+            scoring::estimate_date(&DatePattern::default(), token);
+            // those the crate can't make sense of are bruteforced
+            let bruteforce = scoring::estimate_bruteforce(token);
+            assert_eq!(
+                scoring::estimate_national_id(&NationalIdPattern::default(), token),
+                bruteforce
+            );
+            assert_eq!(
+                scoring::estimate_unlock(&UnlockPattern::default(), token),
+                bruteforce
+            );
+            assert_eq!(
+                scoring::estimate_regex(&RegexPattern::default(), token),
+                bruteforce
+            );
+        }
+        assert_eq!(
+            scoring::estimate_sequence(&SequencePattern::default(), ""),
+            scoring::estimate_bruteforce("")
+        );
+        // a known regex without the captures its matcher makes
+        for regex_name in [
+            "recent_year",
+            "decade",
+            "year_range",
+            "alt_code",
+            "unicode_escape",
+        ] {
+            let p = RegexPattern {
+                regex_name,
+                regex_match: vec!["x".to_string()],
+                ..RegexPattern::default()
+            };
+            assert_eq!(
+                scoring::estimate_regex(&p, "x"),
+                scoring::estimate_bruteforce("x")
+            );
+        }
+        // an unlock pattern longer than the grid
+        assert_eq!(
+            scoring::estimate_unlock(&UnlockPattern::default(), "1234567890"),
+            scoring::estimate_bruteforce("1234567890")
+        );
+    }
+
+    #[test]
+    fn test_word_pair_guesses() {
+        let mut p = WordPairPattern {
+            first_word: "okay".to_string(),
+            second_word: "thanks".to_string(),
+            first_rank: 100,
+            second_rank: 50,
+            ..WordPairPattern::default()
+        };
+        assert_eq!(p.estimate("okaythanks"), 100 * 50);
+        assert_eq!(p.estimate("OkayThanks"), 100 * 50 * 2 * 2);
+        assert_eq!(p.uppercase_variations, 4);
+        p.pair_rank = Some(7);
+        assert_eq!(p.estimate("Okaythanks"), 7 * 2);
+    }
+
     #[test]
     fn test_sequence_guesses() {
         let test_data = [
@@ -763,6 +2236,62 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_shifted_sequence_guesses() {
+        let mut p = SequencePattern {
+            sequence_name: "digits",
+            sequence_space: 10,
+            ascending: true,
+            shifted_count: 6,
+        };
+        // obvious start * len-6 * all shifted
+        assert_eq!(p.estimate("!@#$%^"), 4 * 6 * 2);
+        p.shifted_count = 5;
+        let shift_variations: u64 = n_ck(6, 1);
+        assert_eq!(p.estimate("!@#$%6"), 4 * 6 * shift_variations);
+        p.shifted_count = 2;
+        let shift_variations = n_ck(4, 1) + n_ck(4, 2);
+        assert_eq!(p.estimate("@#45"), 10 * 4 * shift_variations);
+    }
+
+    #[test]
+    fn test_interleaved_guesses() {
+        let sequence = |sequence_name, sequence_space, ascending| SequencePattern {
+            sequence_name,
+            sequence_space,
+            ascending,
+            shifted_count: 0,
+        };
+        let mut p = InterleavedPattern {
+            first_token: "abcd".to_string(),
+            first_sequence: sequence("lower", 26, true),
+            second_token: "9876".to_string(),
+            second_sequence: sequence("digits", 10, false),
+        };
+        // obvious start * len-4, times obvious start * len-4 * descending
+        assert_eq!(p.estimate("a9b8c7d6"), (4 * 4) * (4 * 4 * 2));
+    }
+
+    #[test]
+    fn test_emoji_guesses() {
+        let test_data = [
+            ("common", 1, true, 3, 3),              // rank
+            ("other", 1, true, 3600, 3600),         // emoji space
+            ("repeat", 4, true, 16, 16 * 4),        // base * count
+            ("keyboard", 3, true, 60, 60 * 3),      // keyboard starts * count
+            ("keyboard", 3, false, 60, 60 * 3 * 2), // descending
+        ];
+        for &(emoji_kind, emoji_count, ascending, base_guesses, guesses) in &test_data {
+            let mut p = EmojiPattern {
+                emoji_kind,
+                emoji_count,
+                ascending,
+                base_guesses,
+            };
+            assert_eq!(p.estimate(""), guesses);
+        }
+    }
+
     #[test]
     fn test_regex_guesses_lowercase() {
         let token = "aizocdk";
@@ -792,13 +2321,14 @@ mod tests {
         };
         assert_eq!(
             p.estimate(token),
-            (*scoring::REFERENCE_YEAR - 1972).abs() as u64
+            (*scoring::REFERENCE_YEAR - 1972).unsigned_abs() as u64
         );
     }
 
     #[test]
     fn test_regex_guesses_recent_year() {
-        let token = "2005";
+        let token = (*scoring::REFERENCE_YEAR - 5).to_string();
+        let token = token.as_str();
         let mut p = RegexPattern {
             regex_name: "recent_year",
             regex_match: vec![token.to_string()],
@@ -806,9 +2336,25 @@ mod tests {
         assert_eq!(p.estimate(token), scoring::MIN_YEAR_SPACE as u64);
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_year_of_unix_day() {
+        for (days, year) in [
+            (-1, 1969),
+            (0, 1970),
+            (10_956, 1999),
+            (10_957, 2000),
+            (11_016, 2000),
+            (20_088, 2024),
+            (20_089, 2025),
+        ] {
+            assert_eq!(scoring::year_of_unix_day(days), year, "{}", days);
+        }
+    }
+
     #[test]
     fn test_regex_guesses_current_year() {
-        let token = time::OffsetDateTime::now_utc().year().to_string();
+        let token = scoring::REFERENCE_YEAR.to_string();
         let mut p = RegexPattern {
             regex_name: "recent_year",
             regex_match: vec![token.to_string()],
@@ -816,6 +2362,191 @@ mod tests {
         assert_eq!(p.estimate(&token), scoring::MIN_YEAR_SPACE as u64);
     }
 
+    #[test]
+    fn test_regex_guesses_decade() {
+        let mut p = RegexPattern {
+            regex_name: "decade",
+            regex_match: vec!["1950s".to_string(), "1950".to_string()],
+        };
+        let decade_space = (*scoring::REFERENCE_YEAR - 1950).unsigned_abs() as u64 / 10;
+        assert_eq!(p.estimate("1950s"), decade_space * 4);
+        p.regex_match = vec!["50's".to_string(), "50".to_string()];
+        assert_eq!(p.estimate("50's"), decade_space * 4);
+    }
+
+    #[test]
+    fn test_year_guesses_follow_year_window() {
+        let p = RegexPattern {
+            regex_name: "decade",
+            regex_match: vec!["50s".to_string(), "50".to_string()],
+        };
+        // the 2050s once they have come
+        let years = YearWindow::new(2060);
+        assert_eq!(p.estimate_with_years(&years), Some(2 * 4));
+        let years = YearWindow {
+            reference_year: 1950,
+            min_year_space: 1,
+            ..YearWindow::default()
+        };
+        assert_eq!(p.estimate_with_years(&years), Some(2 * 4));
+
+        let date = DatePattern {
+            year: 1960,
+            ..DatePattern::default()
+        };
+        assert_eq!(date.estimate_with_years(&years), 10 * 365);
+        let mut matches = vec![Match {
+            i: 0,
+            j: 3,
+            token: "1960".to_string(),
+            pattern: MatchPattern::Date(date),
+            guesses: None,
+        }];
+        scoring::estimate_configured_guesses(
+            "1960",
+            &mut matches,
+            &years,
+            &scoring::ScoringParams::default(),
+            false,
+        );
+        assert_eq!(matches[0].guesses, Some(10 * 365));
+    }
+
+    #[test]
+    fn test_appended_year_guesses() {
+        let years = YearWindow {
+            current_year: 2025,
+            ..YearWindow::new(1950)
+        };
+        let year = |i, token: &str| Match {
+            i,
+            j: i + 3,
+            token: token.to_string(),
+            pattern: MatchPattern::Regex(RegexPattern {
+                regex_name: "recent_year",
+                regex_match: vec![token.to_string()],
+            }),
+            guesses: None,
+        };
+        let guesses = |password: &str, m: Match| {
+            let mut matches = vec![m];
+            scoring::estimate_configured_guesses(
+                password,
+                &mut matches,
+                &years,
+                &scoring::ScoringParams::default(),
+                false,
+            );
+            matches[0].guesses
+        };
+        // the current year and the year before at the end of a password, as submatches
+        assert_eq!(guesses("monkey2025", year(6, "2025")), Some(50));
+        assert_eq!(guesses("monkey2024", year(6, "2024")), Some(50));
+        assert_eq!(guesses("2025", year(0, "2025")), Some(20));
+        // guessed around the reference year elsewhere, or for other years
+        assert_eq!(guesses("2025monkey", year(0, "2025")), Some(75));
+        assert_eq!(guesses("monkey2023", year(6, "2023")), Some(73));
+    }
+
+    #[test]
+    fn test_min_guesses_per_pattern() {
+        let params = scoring::ScoringParams::new()
+            .min_guesses(PatternKind::Dictionary, 1)
+            .min_guesses(PatternKind::Spatial, 1000);
+        let dictionary = Match {
+            i: 0,
+            j: 3,
+            token: "pass".to_string(),
+            pattern: MatchPattern::Dictionary(DictionaryPattern {
+                rank: 4,
+                ..DictionaryPattern::default()
+            }),
+            guesses: None,
+        };
+        let spatial = Match {
+            i: 4,
+            j: 6,
+            token: "zxc".to_string(),
+            pattern: MatchPattern::Spatial(SpatialPattern {
+                graph: "qwerty".to_string(),
+                turns: 1,
+                shifted_count: 0,
+                ..SpatialPattern::default()
+            }),
+            guesses: None,
+        };
+        let password = "passzxc1";
+        let mut matches = vec![dictionary.clone(), spatial.clone()];
+        scoring::estimate_configured_guesses(
+            password,
+            &mut matches,
+            &YearWindow::default(),
+            &params,
+            false,
+        );
+        assert_eq!(matches[0].guesses, Some(4));
+        assert!(matches[1].guesses.unwrap() >= 1000);
+
+        // without parameters, submatches take at least 50 guesses
+        let mut matches = vec![dictionary, spatial];
+        scoring::estimate_configured_guesses(
+            password,
+            &mut matches,
+            &YearWindow::default(),
+            &scoring::ScoringParams::default(),
+            false,
+        );
+        assert_eq!(matches[0].guesses, None);
+        assert_eq!(scoring::estimate_guesses(&mut matches[0], password), 50);
+    }
+
+    #[test]
+    fn test_regex_guesses_year_range() {
+        let mut p = RegexPattern {
+            regex_name: "year_range",
+            regex_match: vec![
+                "1960-1995".to_string(),
+                "1960".to_string(),
+                "1995".to_string(),
+            ],
+        };
+        let year_space = (*scoring::REFERENCE_YEAR - 1960).unsigned_abs() as u64;
+        assert_eq!(p.estimate("1960-1995"), year_space * 35 * 4);
+        p.regex_match = vec!["1960-63".to_string(), "1960".to_string(), "63".to_string()];
+        assert_eq!(p.estimate("1960-63"), year_space * 10 * 4);
+        assert_eq!(
+            scoring::range_years(&["1995-05".to_string(), "1995".to_string(), "05".to_string()]),
+            Some((1995, 2005))
+        );
+    }
+
+    #[test]
+    fn test_regex_guesses_alt_code() {
+        let mut p = RegexPattern {
+            regex_name: "alt_code",
+            regex_match: vec!["alt+0169".to_string(), "0169".to_string()],
+        };
+        assert_eq!(p.estimate("alt+0169"), 256 * 8);
+        p.regex_match[1] = "1234".to_string();
+        assert_eq!(p.estimate("alt+1234"), 1235 * 8);
+    }
+
+    #[test]
+    fn test_regex_guesses_unicode_escape() {
+        let test_data = [
+            (vec!["\\u00e9", "", "00e9", "", "", "", ""], 256 * 6),
+            (vec!["U+1F600", "", "", "", "1F600", "", ""], 0x1F601 * 6),
+            (vec!["&#1000;", "", "", "", "", "", "1000"], 1001 * 6),
+        ];
+        for (regex_match, guesses) in test_data {
+            let mut p = RegexPattern {
+                regex_name: "unicode_escape",
+                regex_match: regex_match.iter().map(|x| x.to_string()).collect(),
+            };
+            assert_eq!(p.estimate(regex_match[0]), guesses);
+        }
+    }
+
     #[test]
     fn test_date_guesses() {
         let mut p = DatePattern {
@@ -823,11 +2554,12 @@ mod tests {
             year: 1923,
             month: 1,
             day: 1,
+            user_input: false,
         };
         let token = "1123";
         assert_eq!(
             p.estimate(token),
-            365 * (*scoring::REFERENCE_YEAR - p.year).abs() as u64
+            365 * (*scoring::REFERENCE_YEAR - p.year).unsigned_abs() as u64
         );
     }
 
@@ -838,6 +2570,7 @@ mod tests {
             year: 2010,
             month: 1,
             day: 1,
+            user_input: false,
         };
         let token = "1/1/2010";
         assert_eq!(p.estimate(token), 365 * scoring::MIN_YEAR_SPACE as u64 * 4);
@@ -850,12 +2583,46 @@ mod tests {
             graph: "qwerty".to_string(),
             turns: 1,
             shifted_count: 0,
+            region: None,
+            shape: None,
         };
         let token = "zxcvbn";
-        let base_guesses = *scoring::KEYBOARD_STARTING_POSITIONS
-            * *scoring::KEYBOARD_AVERAGE_DEGREE
-            * (token.len() - 1) as u64;
-        assert_eq!(p.estimate(token), base_guesses as u64);
+        let qwerty = &crate::adjacency_graphs::GRAPHS["qwerty"];
+        let base_guesses =
+            qwerty.starting_positions * qwerty.average_degree * (token.len() - 1) as u64;
+        assert_eq!(p.estimate(token), base_guesses);
+    }
+
+    #[test]
+    fn test_spatial_guesses_for_regions() {
+        let mut p = SpatialPattern {
+            graph: "qwerty".to_string(),
+            turns: 1,
+            shifted_count: 0,
+            region: Some("row"),
+            shape: None,
+        };
+        let token = "qwertyuiop";
+        let region_guesses = p.estimate(token);
+        assert_eq!(region_guesses, 8);
+        p.region = None;
+        assert!(p.estimate(token) > 100 * region_guesses);
+    }
+
+    #[test]
+    fn test_spatial_guesses_for_shapes() {
+        let mut p = SpatialPattern {
+            graph: "qwerty".to_string(),
+            turns: 3,
+            shifted_count: 0,
+            region: None,
+            shape: Some("box"),
+        };
+        let token = "qazxcvfr";
+        let shape_guesses = p.estimate(token);
+        assert!(shape_guesses < 10_000, "{}", shape_guesses);
+        p.shape = None;
+        assert!(p.estimate(token) > 10 * shape_guesses);
     }
 
     #[test]
@@ -865,12 +2632,15 @@ mod tests {
             graph: "qwerty".to_string(),
             turns: 1,
             shifted_count: 2,
+            region: None,
+            shape: None,
         };
         let token = "ZxCvbn";
-        let base_guesses = *scoring::KEYBOARD_STARTING_POSITIONS
-            * *scoring::KEYBOARD_AVERAGE_DEGREE
+        let qwerty = &crate::adjacency_graphs::GRAPHS["qwerty"];
+        let base_guesses = qwerty.starting_positions
+            * qwerty.average_degree
             * (token.len() - 1) as u64
-            * (scoring::n_ck(6, 2) + scoring::n_ck(6, 1));
+            * (n_ck(6, 2) + n_ck(6, 1));
         assert_eq!(p.estimate(token), base_guesses);
     }
 
@@ -881,13 +2651,14 @@ mod tests {
             graph: "qwerty".to_string(),
             turns: 1,
             shifted_count: 6,
+            region: None,
+            shape: None,
         };
         let token = "ZXCVBN";
-        let base_guesses = *scoring::KEYBOARD_STARTING_POSITIONS
-            * *scoring::KEYBOARD_AVERAGE_DEGREE
-            * (token.len() - 1) as u64
-            * 2;
-        assert_eq!(p.estimate(token), base_guesses as u64);
+        let qwerty = &crate::adjacency_graphs::GRAPHS["qwerty"];
+        let base_guesses =
+            qwerty.starting_positions * qwerty.average_degree * (token.len() - 1) as u64 * 2;
+        assert_eq!(p.estimate(token), base_guesses);
     }
 
     #[test]
@@ -897,16 +2668,17 @@ mod tests {
             graph: "qwerty".to_string(),
             turns: 3,
             shifted_count: 0,
+            region: None,
+            shape: None,
         };
+        let qwerty = &crate::adjacency_graphs::GRAPHS["qwerty"];
         let token = "zxcft6yh";
         let guesses: u64 = (2..(token.len() + 1))
             .map(|i| {
                 (1..::std::cmp::min(p.turns + 1, i))
                     .map(|j| {
-                        scoring::n_ck(i - 1, j - 1)
-                            * (*scoring::KEYBOARD_STARTING_POSITIONS
-                                * scoring::KEYBOARD_AVERAGE_DEGREE.pow(j as u32))
-                                as u64
+                        n_ck(i - 1, j - 1)
+                            * (qwerty.starting_positions * qwerty.average_degree.pow(j as u32))
                     })
                     .sum::<u64>()
             })
@@ -914,6 +2686,58 @@ mod tests {
         assert_eq!(p.estimate(token), guesses);
     }
 
+    #[test]
+    fn test_spatial_guesses_use_the_stats_of_their_graph() {
+        let mut p = SpatialPattern {
+            graph: "keypad".to_string(),
+            turns: 1,
+            shifted_count: 0,
+            region: None,
+            shape: None,
+        };
+        let token = "789";
+        // 15 keys with 5 neighbors on average
+        assert_eq!(p.estimate(token), 15 * 5 * 2);
+        // the mac keypad has an extra "=" key
+        p.graph = "mac_keypad".to_string();
+        assert_eq!(p.estimate(token), 16 * 5 * 2);
+        p.graph = "dvorak".to_string();
+        assert_eq!(p.estimate(token), 94 * 4 * 2);
+    }
+
+    #[test]
+    fn test_spatial_guesses_use_the_js_stats_when_js_compatible() {
+        let p = SpatialPattern {
+            graph: "mac_keypad".to_string(),
+            turns: 1,
+            shifted_count: 0,
+            region: None,
+            shape: None,
+        };
+        let mut matches = vec![Match {
+            i: 0,
+            j: 2,
+            token: "789".to_string(),
+            pattern: MatchPattern::Spatial(p),
+            guesses: None,
+        }];
+        let estimate = |matches: &mut [Match], js_compatible| {
+            matches[0].guesses = None;
+            scoring::estimate_configured_guesses(
+                "789",
+                matches,
+                &YearWindow::default(),
+                &scoring::ScoringParams::default(),
+                js_compatible,
+            );
+            matches[0].guesses
+        };
+        // the JavaScript implementation guesses both keypads with the stats of the keypad
+        assert_eq!(estimate(&mut matches, true), Some(15 * 5 * 2));
+        // and leaves the others to be estimated when they are scored
+        assert_eq!(estimate(&mut matches, false), None);
+    }
+
     #[test]
     fn test_dictionary_base_guesses_equals_rank() {
         let mut p = DictionaryPattern {
@@ -945,9 +2769,19 @@ mod tests {
         assert_eq!(p.estimate(token), 32 * 2);
     }
 
+    #[test]
+    fn test_dictionary_guesses_multiplied_by_layouts_tried() {
+        let mut p = DictionaryPattern {
+            rank: 32,
+            layout: Some("dvorak"),
+            ..DictionaryPattern::default()
+        };
+        assert_eq!(p.estimate("laoo,rpe"), 32 * 4);
+    }
+
     #[test]
     fn test_dictionary_guesses_added_for_l33t() {
-        let mut subs = HashMap::with_capacity(1);
+        let mut subs = BTreeMap::new();
         subs.insert('@', 'a');
         let mut p = DictionaryPattern {
             rank: 32,
@@ -962,7 +2796,7 @@ mod tests {
 
     #[test]
     fn test_dictionary_guesses_added_for_caps_and_l33t() {
-        let mut subs = HashMap::with_capacity(1);
+        let mut subs = BTreeMap::new();
         subs.insert('@', 'a');
         let mut p = DictionaryPattern {
             rank: 32,
@@ -986,20 +2820,37 @@ mod tests {
             ("Abcdef", 2),
             ("abcdeF", 2),
             ("ABCDEF", 2),
-            ("aBcdef", scoring::n_ck(6, 1)),
-            ("aBcDef", scoring::n_ck(6, 1) + scoring::n_ck(6, 2)),
-            ("ABCDEf", scoring::n_ck(6, 1)),
-            ("aBCDEf", scoring::n_ck(6, 1) + scoring::n_ck(6, 2)),
-            (
-                "ABCdef",
-                scoring::n_ck(6, 1) + scoring::n_ck(6, 2) + scoring::n_ck(6, 3),
-            ),
+            // all caps with digits or symbols in it
+            ("P455W0RD", 2),
+            ("aBcdef", n_ck(6, 1)),
+            ("aBcDef", n_ck(6, 1) + n_ck(6, 2)),
+            ("ABCDEf", n_ck(6, 1)),
+            ("aBCDEf", n_ck(6, 1) + n_ck(6, 2)),
+            ("ABCdef", n_ck(6, 1) + n_ck(6, 2) + n_ck(6, 3)),
         ];
         for &(word, variants) in &test_data {
             assert_eq!(scoring::uppercase_variations(word), variants);
         }
     }
 
+    #[test]
+    fn test_typo_variations() {
+        let mut p = DictionaryPattern {
+            matched_word: "password".to_string(),
+            ..DictionaryPattern::default()
+        };
+        assert_eq!(scoring::typo_variations(&p, "password"), 1);
+        p.typos = 1;
+        // 8 deletions, 8 * 35 replacements and 9 * 36 insertions
+        assert_eq!(scoring::typo_variations(&p, "pasword"), 8 + 8 * 35 + 9 * 36);
+        assert_eq!(
+            scoring::typo_variations(&p, "passwzrd"),
+            8 + 8 * 35 + 9 * 36
+        );
+        // every key of "password" replaced by each of its 4 to 6 neighbors
+        assert_eq!(scoring::typo_variations(&p, "Passwprd"), 46);
+    }
+
     #[test]
     fn test_l33t_variations_for_non_l33t() {
         let p = DictionaryPattern {
@@ -1012,82 +2863,82 @@ mod tests {
     #[test]
     fn test_l33t_variations() {
         let test_data = [
-            ("", 1, vec![].into_iter().collect::<HashMap<char, char>>()),
-            ("a", 1, vec![].into_iter().collect::<HashMap<char, char>>()),
+            ("", 1, vec![].into_iter().collect::<BTreeMap<char, char>>()),
+            ("a", 1, vec![].into_iter().collect::<BTreeMap<char, char>>()),
             (
                 "4",
                 2,
                 vec![('4', 'a')]
                     .into_iter()
-                    .collect::<HashMap<char, char>>(),
+                    .collect::<BTreeMap<char, char>>(),
             ),
             (
                 "4pple",
                 2,
                 vec![('4', 'a')]
                     .into_iter()
-                    .collect::<HashMap<char, char>>(),
+                    .collect::<BTreeMap<char, char>>(),
             ),
             (
                 "abcet",
                 1,
-                vec![].into_iter().collect::<HashMap<char, char>>(),
+                vec![].into_iter().collect::<BTreeMap<char, char>>(),
             ),
             (
                 "4bcet",
                 2,
                 vec![('4', 'a')]
                     .into_iter()
-                    .collect::<HashMap<char, char>>(),
+                    .collect::<BTreeMap<char, char>>(),
             ),
             (
                 "a8cet",
                 2,
                 vec![('8', 'b')]
                     .into_iter()
-                    .collect::<HashMap<char, char>>(),
+                    .collect::<BTreeMap<char, char>>(),
             ),
             (
                 "abce+",
                 2,
                 vec![('+', 't')]
                     .into_iter()
-                    .collect::<HashMap<char, char>>(),
+                    .collect::<BTreeMap<char, char>>(),
             ),
             (
                 "48cet",
                 4,
                 vec![('4', 'a'), ('8', 'b')]
                     .into_iter()
-                    .collect::<HashMap<char, char>>(),
+                    .collect::<BTreeMap<char, char>>(),
             ),
             (
                 "a4a4aa",
-                scoring::n_ck(6, 2) + scoring::n_ck(6, 1),
+                n_ck(6, 2) + n_ck(6, 1),
                 vec![('4', 'a')]
                     .into_iter()
-                    .collect::<HashMap<char, char>>(),
+                    .collect::<BTreeMap<char, char>>(),
             ),
             (
                 "4a4a44",
-                scoring::n_ck(6, 2) + scoring::n_ck(6, 1),
+                n_ck(6, 2) + n_ck(6, 1),
                 vec![('4', 'a')]
                     .into_iter()
-                    .collect::<HashMap<char, char>>(),
+                    .collect::<BTreeMap<char, char>>(),
             ),
             (
                 "Aa44aA",
-                scoring::n_ck(6, 2) + scoring::n_ck(6, 1),
+                n_ck(6, 2) + n_ck(6, 1),
                 vec![('4', 'a')]
                     .into_iter()
-                    .collect::<HashMap<char, char>>(),
+                    .collect::<BTreeMap<char, char>>(),
             ),
             (
                 "a44att+",
-                (scoring::n_ck(4, 2) + scoring::n_ck(4, 1)) * scoring::n_ck(3, 1),
+                (n_ck(4, 2) + n_ck(4, 1)) * n_ck(3, 1),
                 vec![('4', 'a'), ('+', 't')]
                     .into_iter()
-                    .collect::<HashMap<char, char>>(),
+                    .collect::<BTreeMap<char, char>>(),
             ),
         ];
         for &(word, variants, ref sub) in &test_data {
@@ -0,0 +1,180 @@
+//! The candidate matches and best partial sequences the scoring search went through, for
+//! seeing why a sequence was picked when diagnosing scoring bugs or teaching how zxcvbn
+//! works.
+//!
+//! Recording the graph is opt-in with
+//! [`Zxcvbn::with_search_graph`](crate::Zxcvbn::with_search_graph). It can be rendered with
+//! GraphViz from [`SearchGraph::to_dot`], or written as JSON with the `ser` feature.
+//!
+//! ```
+//! use zxcvbn::Zxcvbn;
+//!
+//! let estimator = Zxcvbn::new().with_search_graph(true);
+//! let entropy = estimator.estimate("march1987", &[]);
+//! let graph = entropy.search_graph().unwrap();
+//! assert_eq!(graph.sequence, entropy.sequence());
+//! assert!(graph.to_dot().starts_with("digraph search {"));
+//! ```
+
+use std::fmt::Write;
+
+use crate::matching::patterns::MatchPattern;
+use crate::Match;
+
+/// The search for the sequence of matches covering a password with the fewest guesses.
+///
+/// The password is a graph whose nodes are the positions between its characters, from 0
+/// before the first character to its length after the last one, and whose edges are the
+/// matches, going from the position before their first character to the one after their
+/// last. The search keeps the best sequence of each length ending at each position, and
+/// the sequence picked is the best path from the first position to the last one.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+pub struct SearchGraph {
+    /// The password as it was matched, after normalization.
+    pub password: String,
+    /// The matches the search could pick, with their guesses. Of the matches covering the
+    /// same characters only the one with the fewest guesses is a candidate, and none of them
+    /// if bruteforcing the characters takes fewer guesses.
+    pub candidates: Vec<Match>,
+    /// The best sequences the search kept, by end and length.
+    pub best: Vec<BestSequence>,
+    /// The sequence picked.
+    pub sequence: Vec<Match>,
+    /// The guesses of the sequence picked.
+    pub guesses: u64,
+}
+
+/// The best sequence of a given length covering the password up to a character, as far as
+/// the search went.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+pub struct BestSequence {
+    /// Index of the last character covered.
+    pub j: usize,
+    /// Number of matches of the sequence.
+    pub len: usize,
+    /// Guesses of the sequence, including the penalty for its length.
+    pub guesses: u64,
+    /// The last match of the sequence. The matches before it are those of the best
+    /// sequence one match shorter ending right before it.
+    pub last: Match,
+}
+
+impl SearchGraph {
+    /// Renders the graph in the GraphViz DOT language, with the best sequences of each
+    /// length ending at a position listed in its node and the sequence picked in bold.
+    ///
+    /// Bruteforce matches are only drawn where the search kept them in a best sequence, as
+    /// every part of the password could be bruteforced.
+    pub fn to_dot(&self) -> String {
+        let n = self.password.chars().count();
+        let mut dot = String::from("digraph search {\n    rankdir=LR;\n    node [shape=box];\n");
+        for position in 0..=n {
+            let mut label = position.to_string();
+            for best in self.best.iter().filter(|best| best.j + 1 == position) {
+                write!(label, "\\n{} matches: {} guesses", best.len, best.guesses).unwrap();
+            }
+            writeln!(dot, "    {} [label=\"{}\"];", position, label).unwrap();
+        }
+
+        let mut edges: Vec<&Match> = Vec::new();
+        let kept = self.best.iter().map(|best| &best.last);
+        for m in self.candidates.iter().chain(kept).chain(&self.sequence) {
+            if !edges.contains(&m) {
+                edges.push(m);
+            }
+        }
+        for m in edges {
+            let style = if self.sequence.contains(m) {
+                "penwidth=3"
+            } else if m.pattern == MatchPattern::BruteForce {
+                "style=dashed, color=gray"
+            } else {
+                "color=gray"
+            };
+            writeln!(
+                dot,
+                "    {} -> {} [label=\"{} {}\\n{} guesses\", {}];",
+                m.i,
+                m.j + 1,
+                m.pattern.variant(),
+                escape(&m.token),
+                m.guesses.unwrap_or_default(),
+                style
+            )
+            .unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Escapes a token for a quoted DOT string.
+fn escape(token: &str) -> String {
+    token.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Zxcvbn;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_search_graph() {
+        assert!(Zxcvbn::new()
+            .estimate("march1987", &[])
+            .search_graph()
+            .is_none());
+
+        let estimator = Zxcvbn::new().with_search_graph(true);
+        let entropy = estimator.estimate("march1987", &[]);
+        let graph = entropy.search_graph().unwrap();
+        assert_eq!(graph.password, "march1987");
+        assert_eq!(graph.guesses, entropy.guesses());
+        assert_eq!(graph.sequence, entropy.sequence());
+        assert!(graph.candidates.iter().any(|m| m.token == "march"));
+        // the sequence picked is the best one ending at the last character
+        let best = graph
+            .best
+            .iter()
+            .filter(|best| best.j == 8)
+            .min_by_key(|best| best.guesses)
+            .unwrap();
+        assert_eq!(best.guesses, entropy.guesses());
+        assert_eq!(&best.last, entropy.sequence().last().unwrap());
+
+        let dot = graph.to_dot();
+        assert!(dot.contains("    0 -> 5 [label=\"dictionary march\\n"));
+        assert!(dot.contains("penwidth=3"));
+        assert!(dot.ends_with("}\n"));
+
+        assert!(estimator.estimate("", &[]).search_graph().is_none());
+    }
+
+    #[cfg(feature = "ser")]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_search_graph_json() {
+        let entropy = Zxcvbn::new().estimate("march1987", &[]);
+        let json = serde_json::to_value(&entropy).unwrap();
+        assert!(json.get("search_graph").is_none());
+
+        let entropy = Zxcvbn::new()
+            .with_search_graph(true)
+            .estimate("march1987", &[]);
+        let json = serde_json::to_value(&entropy).unwrap();
+        assert_eq!(json["search_graph"]["password"], "march1987");
+        assert!(json["search_graph"]["best"][0]["last"]["token"].is_string());
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_escape() {
+        assert_eq!(escape("a\"b\\c"), "a\\\"b\\\\c");
+    }
+}
@@ -0,0 +1,390 @@
+//! Scoring newline-delimited passwords read from inputs of any size, such as breach dumps of
+//! several gigabytes.
+//!
+//! Lines are read in chunks and scored on a few threads. The reader only gets ahead of the
+//! writer by a bounded number of chunks, so memory use stays flat however large the input
+//! is, and a slow writer slows the reader down in turn. Lines are written in input order,
+//! each as the tab separated score, password, guesses_log10 and slow-hash crack time.
+//!
+//! ```
+//! use zxcvbn::stream::{score_lines, StreamOptions};
+//!
+//! let input = "password\ncorrecthorsebatterystaple\n";
+//! let mut output = Vec::new();
+//! let progress = score_lines(input.as_bytes(), &mut output, &StreamOptions::new())?;
+//! assert_eq!(progress.lines, 2);
+//!
+//! let output = String::from_utf8(output).unwrap();
+//! assert!(output.starts_with("0\tpassword\t"));
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Write as _;
+use std::io::{self, BufRead, Write};
+use std::num::NonZeroUsize;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crate::{Entropy, Zxcvbn};
+
+/// Number of lines handed to a thread at once.
+const CHUNK_SIZE: usize = 256;
+/// Number of chunks read ahead per thread before the reader waits for the writer.
+const CHUNKS_IN_FLIGHT_PER_THREAD: usize = 2;
+/// Longer lines are cut to this many bytes, which is well over the 100 characters that are
+/// estimated, so that a file without newlines can't be read into memory whole.
+pub const MAX_LINE_BYTES: usize = 1024;
+
+/// How far scoring has got, passed to the [progress callback](StreamOptions::on_progress)
+/// after each chunk of lines is written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Progress {
+    /// Number of lines scored and written.
+    pub lines: u64,
+    /// Number of bytes read up to the last line written, e.g. to compare with the size of the
+    /// input file.
+    pub bytes: u64,
+}
+
+/// Options of [`score_lines`].
+pub struct StreamOptions {
+    threads: NonZeroUsize,
+    user_inputs: Vec<String>,
+    progress: Option<ProgressCallback>,
+}
+
+type ProgressCallback = Box<dyn Fn(&Progress) + Send + Sync>;
+
+impl StreamOptions {
+    /// Scores on as many threads as there are CPUs, with no user inputs.
+    pub fn new() -> Self {
+        StreamOptions {
+            threads: thread::available_parallelism().unwrap_or(NonZeroUsize::new(1).unwrap()),
+            user_inputs: Vec::new(),
+            progress: None,
+        }
+    }
+
+    /// Sets the number of threads scoring lines.
+    pub fn threads(mut self, threads: NonZeroUsize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Sets the user inputs every line is scored with.
+    pub fn user_inputs<I>(mut self, user_inputs: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.user_inputs = user_inputs.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Calls `progress` on the thread calling [`score_lines`] after each chunk of lines is
+    /// written.
+    pub fn on_progress<F>(mut self, progress: F) -> Self
+    where
+        F: Fn(&Progress) + Send + Sync + 'static,
+    {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for StreamOptions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("StreamOptions")
+            .field("threads", &self.threads)
+            .field("user_inputs", &self.user_inputs)
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+/// Scores every line of `reader` like [`zxcvbn`](crate::zxcvbn), writing the results to
+/// `writer` in input order, and returns the final progress.
+///
+/// Lines may end with `\n` or `\r\n`. Bytes that are not UTF-8 are replaced with U+FFFD, and
+/// lines longer than [`MAX_LINE_BYTES`] are cut short. Stops at the first error reading or
+/// writing.
+pub fn score_lines<R, W>(reader: R, writer: W, opts: &StreamOptions) -> io::Result<Progress>
+where
+    R: BufRead + Send,
+    W: Write,
+{
+    run(reader, writer, opts, &crate::zxcvbn)
+}
+
+impl Zxcvbn {
+    /// Scores every line of `reader` with this estimator, like [`score_lines`].
+    pub fn score_lines<R, W>(
+        &self,
+        reader: R,
+        writer: W,
+        opts: &StreamOptions,
+    ) -> io::Result<Progress>
+    where
+        R: BufRead + Send,
+        W: Write,
+    {
+        run(reader, writer, opts, &|password, user_inputs| {
+            self.estimate(password, user_inputs)
+        })
+    }
+}
+
+/// A chunk of lines and the number of bytes read up to its end.
+type Chunk = (Vec<String>, u64);
+
+fn run<R, W>(
+    mut reader: R,
+    mut writer: W,
+    opts: &StreamOptions,
+    estimate: &(dyn Fn(&str, &[&str]) -> Entropy + Sync),
+) -> io::Result<Progress>
+where
+    R: BufRead + Send,
+    W: Write,
+{
+    let threads = opts.threads.get();
+    let max_in_flight = threads * CHUNKS_IN_FLIGHT_PER_THREAD;
+    let (chunk_tx, chunk_rx) = mpsc::sync_channel::<(usize, Chunk)>(max_in_flight);
+    let (output_tx, output_rx) = mpsc::sync_channel::<(usize, usize, String, u64)>(max_in_flight);
+    let chunk_rx = Arc::new(Mutex::new(chunk_rx));
+    // The reader takes a permit per chunk and the writer hands it back once the chunk is
+    // written, which bounds the chunks held anywhere, including those scored early and
+    // waiting for their turn to be written.
+    let (permit_tx, permit_rx) = mpsc::sync_channel::<()>(max_in_flight);
+    for _ in 0..max_in_flight {
+        permit_tx.send(()).unwrap();
+    }
+
+    let mut progress = Progress::default();
+    let result = thread::scope(|scope| {
+        let reader = scope.spawn(move || -> io::Result<()> {
+            let mut bytes = 0;
+            let mut line = Vec::new();
+            for index in 0.. {
+                if permit_rx.recv().is_err() {
+                    break;
+                }
+                let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+                while chunk.len() < CHUNK_SIZE {
+                    match read_line(&mut reader, &mut line)? {
+                        0 => break,
+                        read => bytes += read as u64,
+                    }
+                    chunk.push(String::from_utf8_lossy(&line).into_owned());
+                }
+                if chunk.is_empty() || chunk_tx.send((index, (chunk, bytes))).is_err() {
+                    break;
+                }
+            }
+            Ok(())
+        });
+
+        for _ in 0..threads {
+            let chunk_rx = Arc::clone(&chunk_rx);
+            let output_tx = output_tx.clone();
+            scope.spawn(move || {
+                let user_inputs = opts
+                    .user_inputs
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>();
+                loop {
+                    let next = chunk_rx.lock().unwrap().recv();
+                    let (index, (chunk, bytes)) = match next {
+                        Ok(next) => next,
+                        Err(_) => break,
+                    };
+                    let mut output = String::new();
+                    for password in &chunk {
+                        let entropy = estimate(password, &user_inputs);
+                        let _ = writeln!(
+                            output,
+                            "{}\t{}\t{:.2}\t{}",
+                            u8::from(entropy.score()),
+                            password,
+                            entropy.guesses_log10(),
+                            entropy.crack_times().offline_slow_hashing_1e4_per_second()
+                        );
+                    }
+                    if output_tx.send((index, chunk.len(), output, bytes)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(output_tx);
+
+        let mut pending = BTreeMap::new();
+        let mut next_index = 0;
+        let mut result = Ok(());
+        'chunks: for (index, lines, output, bytes) in output_rx {
+            pending.insert(index, (lines, output, bytes));
+            while let Some((lines, output, bytes)) = pending.remove(&next_index) {
+                result = writer.write_all(output.as_bytes());
+                if result.is_err() {
+                    // Dropping the receivers stops the scoring threads and the reader in turn.
+                    break 'chunks;
+                }
+                next_index += 1;
+                progress.lines += lines as u64;
+                progress.bytes = bytes;
+                if let Some(callback) = &opts.progress {
+                    callback(&progress);
+                }
+                let _ = permit_tx.send(());
+            }
+        }
+        drop(permit_tx);
+        reader.join().unwrap()?;
+        result
+    });
+    result.and_then(|()| writer.flush())?;
+    Ok(progress)
+}
+
+/// Reads the next line into `line` without its line ending, keeping at most
+/// [`MAX_LINE_BYTES`] of it. Returns the number of bytes read, 0 at the end of the input.
+fn read_line(reader: &mut impl BufRead, line: &mut Vec<u8>) -> io::Result<usize> {
+    line.clear();
+    let mut read = 0;
+    loop {
+        let buf = match reader.fill_buf() {
+            Ok(buf) => buf,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        if buf.is_empty() {
+            break;
+        }
+        let (len, done) = match buf.iter().position(|&b| b == b'\n') {
+            Some(newline) => (newline + 1, true),
+            None => (buf.len(), false),
+        };
+        let kept = len.min(MAX_LINE_BYTES.saturating_sub(line.len()));
+        line.extend_from_slice(&buf[..kept]);
+        reader.consume(len);
+        read += len;
+        if done {
+            break;
+        }
+    }
+    if line.last() == Some(&b'\n') {
+        line.pop();
+    }
+    if line.last() == Some(&b'\r') {
+        line.pop();
+    }
+    Ok(read)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(input: &[u8], opts: &StreamOptions) -> (Vec<String>, Progress) {
+        let mut output = Vec::new();
+        let progress = score_lines(input, &mut output, opts).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        (output.lines().map(str::to_string).collect(), progress)
+    }
+
+    #[test]
+    fn test_score_lines() {
+        let (lines, progress) = score(b"password\r\n\nXk7#q*zP2!vR", &StreamOptions::new());
+        assert_eq!(
+            progress,
+            Progress {
+                lines: 3,
+                bytes: 23
+            }
+        );
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("0\tpassword\t"));
+        assert!(lines[1].starts_with("0\t\t"));
+        assert!(lines[2].starts_with("4\tXk7#q*zP2!vR\t"));
+
+        let opts = StreamOptions::new().user_inputs(["Xk7#q*zP2!vR"]);
+        let (lines, _) = score(b"Xk7#q*zP2!vR", &opts);
+        assert!(lines[0].starts_with("0\t"));
+    }
+
+    #[test]
+    fn test_order_and_progress() {
+        let passwords = (0..1000).map(|i| format!("p{}", i)).collect::<Vec<_>>();
+        let input = passwords.join("\n");
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&calls);
+        let opts = StreamOptions::new()
+            .threads(NonZeroUsize::new(3).unwrap())
+            .on_progress(move |progress| sink.lock().unwrap().push(*progress));
+        let (lines, progress) = score(input.as_bytes(), &opts);
+        for (line, password) in lines.iter().zip(&passwords) {
+            assert_eq!(line.split('\t').nth(1), Some(password.as_str()));
+        }
+        assert_eq!(lines.len(), 1000);
+        assert_eq!(progress.bytes, input.len() as u64);
+
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.len(), 4);
+        assert_eq!(calls[0].lines, CHUNK_SIZE as u64);
+        assert_eq!(calls.last(), Some(&progress));
+    }
+
+    #[test]
+    fn test_read_line() {
+        let long = "a".repeat(MAX_LINE_BYTES + 10);
+        let input = [long.as_bytes(), b"\nb\xff\r\n"].concat();
+        let mut reader = io::BufReader::with_capacity(16, &input[..]);
+        let mut line = Vec::new();
+        assert_eq!(read_line(&mut reader, &mut line).unwrap(), long.len() + 1);
+        assert_eq!(line.len(), MAX_LINE_BYTES);
+        assert_eq!(read_line(&mut reader, &mut line).unwrap(), 4);
+        assert_eq!(String::from_utf8_lossy(&line), "b\u{FFFD}");
+        assert_eq!(read_line(&mut reader, &mut line).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_write_error() {
+        struct Failing;
+
+        impl Write for Failing {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::Error::other("disk full"))
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let input = "password\n".repeat(10 * CHUNK_SIZE);
+        let result = score_lines(input.as_bytes(), Failing, &StreamOptions::new());
+        assert_eq!(result.unwrap_err().to_string(), "disk full");
+    }
+
+    #[test]
+    fn test_estimator() {
+        let estimator = Zxcvbn::new();
+        estimator.add_banned_passwords(["acmecorp"]);
+        let mut output = Vec::new();
+        let opts = StreamOptions::new();
+        estimator
+            .score_lines(&b"acmecorp"[..], &mut output, &opts)
+            .unwrap();
+        assert!(output.starts_with(b"0\tacmecorp\t0.00\t"));
+    }
+}
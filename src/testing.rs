@@ -0,0 +1,181 @@
+//! Helpers for testing matchers written outside the crate.
+//!
+//! Matches passed to [`most_guessable_match_sequence`] are expected to cover a valid range
+//! of the characters of the password, to hold the characters of that range as their token,
+//! and not to be repeated. These helpers build matches that meet those expectations, check
+//! the matches of a matcher against them, and compare guesses with known values:
+//!
+//! ```
+//! use zxcvbn::matching::patterns::{MatchPattern, SequencePattern};
+//! use zxcvbn::testing::{assert_guesses, assert_valid_matches, match_at};
+//!
+//! let password = "xyabcdef";
+//! // the pattern structs are non-exhaustive, so they are built from their defaults
+//! let mut sequence = SequencePattern::default();
+//! sequence.sequence_name = "lower";
+//! sequence.sequence_space = 26;
+//! sequence.ascending = true;
+//! let m = match_at(password, 2, 7, MatchPattern::Sequence(sequence));
+//! assert_eq!(m.token, "abcdef");
+//! assert_valid_matches(password, std::slice::from_ref(&m));
+//! assert_guesses(password, &m, 50);
+//! ```
+
+use crate::matching::patterns::MatchPattern;
+use crate::scoring::{self, most_guessable_match_sequence};
+use crate::{Match, ScoringParams};
+
+pub use crate::matching::InvalidMatch;
+
+/// A match of `password` from its `i`th to its `j`th character, inclusive, with those
+/// characters as its token and guesses left to be estimated.
+///
+/// # Panics
+///
+/// Panics if `j` is before `i` or past the end of `password`.
+pub fn match_at(password: &str, i: usize, j: usize, pattern: MatchPattern) -> Match {
+    assert!(
+        i <= j && j < password.chars().count(),
+        "characters {i} to {j} are not within {password:?}"
+    );
+    Match {
+        i,
+        j,
+        token: password.chars().skip(i).take(j - i + 1).collect(),
+        pattern,
+        guesses: None,
+    }
+}
+
+/// Checks that each of `matches` covers characters of `password`, holds them as its token,
+/// and is not identical to another match, returning the first that doesn't.
+pub fn check_matches(password: &str, matches: &[Match]) -> Result<(), InvalidMatch> {
+    scoring::check_spans(password, matches)?;
+    for (index, m) in matches.iter().enumerate() {
+        if let Some(earlier) = matches[..index].iter().position(|other| other == m) {
+            return Err(InvalidMatch::Duplicate { index, earlier });
+        }
+    }
+    Ok(())
+}
+
+/// Asserts that `matches` found in `password` can be scored, see [`check_matches`].
+///
+/// # Panics
+///
+/// Panics with the reason and the offending match if one of `matches` can't be scored.
+pub fn assert_valid_matches(password: &str, matches: &[Match]) {
+    if let Err(error) = check_matches(password, matches) {
+        panic!("{error} in {password:?}: {:?}", matches[error.index()]);
+    }
+}
+
+/// Asserts that the match `m` of `password` takes `expected` guesses, as the optimal
+/// sequence counts them.
+///
+/// # Panics
+///
+/// Panics with both numbers of guesses if they differ.
+pub fn assert_guesses(password: &str, m: &Match, expected: u64) {
+    let guesses = scoring::estimate_match(m, password);
+    assert_eq!(
+        guesses, expected,
+        "{:?} of {password:?} takes {guesses} guesses, expected {expected}",
+        m.token
+    );
+}
+
+/// Asserts that the optimal sequence of `matches` and bruteforce covering `password` takes
+/// `expected` guesses with the default scoring parameters.
+///
+/// # Panics
+///
+/// Panics with both numbers of guesses and the sequence if they differ.
+pub fn assert_sequence_guesses(password: &str, matches: &[Match], expected: u64) {
+    let result = most_guessable_match_sequence(password, matches, &ScoringParams::default());
+    assert_eq!(
+        result.guesses, expected,
+        "{password:?} takes {} guesses, expected {expected}, with the sequence {:?}",
+        result.guesses, result.sequence
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matching::patterns::RepeatPattern;
+
+    fn repeat(password: &str, i: usize, j: usize) -> Match {
+        match_at(
+            password,
+            i,
+            j,
+            MatchPattern::Repeat(RepeatPattern::default()),
+        )
+    }
+
+    #[test]
+    fn test_match_at_takes_characters() {
+        let m = repeat("xéééy", 1, 3);
+        assert_eq!((m.i, m.j, m.token.as_str()), (1, 3, "ééé"));
+    }
+
+    #[test]
+    #[should_panic(expected = "are not within")]
+    fn test_match_at_past_the_end() {
+        repeat("abc", 1, 3);
+    }
+
+    #[test]
+    fn test_check_matches() {
+        let password = "xéééy";
+        let valid = repeat(password, 1, 3);
+        assert_eq!(
+            check_matches(password, std::slice::from_ref(&valid)),
+            Ok(())
+        );
+
+        let mut empty = valid.clone();
+        empty.j = 0;
+        assert_eq!(
+            check_matches(password, &[valid.clone(), empty]),
+            Err(InvalidMatch::EmptySpan { index: 1 })
+        );
+
+        let mut out_of_bounds = valid.clone();
+        out_of_bounds.j = 5;
+        assert_eq!(
+            check_matches(password, &[out_of_bounds]),
+            Err(InvalidMatch::OutOfBounds { index: 0 })
+        );
+
+        let mut mismatched = valid.clone();
+        mismatched.token = "eee".to_string();
+        assert_eq!(
+            check_matches(password, &[mismatched]),
+            Err(InvalidMatch::TokenMismatch { index: 0 })
+        );
+
+        assert_eq!(
+            check_matches(password, &[valid.clone(), repeat(password, 0, 3), valid]),
+            Err(InvalidMatch::Duplicate {
+                index: 2,
+                earlier: 0
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "match 0 ends past the end of the password")]
+    fn test_assert_valid_matches() {
+        let mut m = repeat("abc", 0, 2);
+        m.j = 3;
+        assert_valid_matches("abc", &[m]);
+    }
+
+    #[test]
+    #[should_panic(expected = "takes 12 guesses, expected 10")]
+    fn test_assert_sequence_guesses() {
+        assert_sequence_guesses("a", &[], 10);
+    }
+}
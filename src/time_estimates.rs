@@ -19,8 +19,26 @@
 //! #     Ok(())
 //! # }
 //! ```
+//!
+//! If you know how passwords are stored, [`HashCost`] gives a more realistic
+//! offline estimate than the fixed scenarios above:
+//!
+//! ```rust
+//! use zxcvbn::zxcvbn;
+//! use zxcvbn::time_estimates::HashCost;
+//!
+//! let hash: HashCost = "$2b$12$R9h/cIPz0gi.URNNX3kh2OPST9/PgBkqquzi.Ss7KIUgO2t0jWMUW"
+//!     .parse()
+//!     .unwrap();
+//! let entropy = zxcvbn("correcthorse", &[]);
+//! assert_eq!(entropy.crack_times().offline_hash(hash).to_string(), "18 minutes");
+//! ```
 
+use std::error::Error;
 use std::fmt;
+use std::str::FromStr;
+
+use crate::display::{format_crack_time, Locale};
 
 /// Back-of-the-envelope crack time estimations, in seconds, based on a few scenarios.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
@@ -68,8 +86,198 @@ impl CrackTimes {
     pub fn offline_fast_hashing_1e10_per_second(self) -> CrackTimeSeconds {
         CrackTimeSeconds::Float(self.guesses as f64 / 10_000_000_000.00)
     }
+
+    /// Offline attack on a single commodity GPU against passwords stored
+    /// with the given hash function and cost parameters.
+    pub fn offline_hash(self, hash: HashCost) -> CrackTimeSeconds {
+        CrackTimeSeconds::Float(self.guesses as f64 / hash.guesses_per_second())
+    }
+}
+
+/// A password hashing function together with its cost parameters.
+///
+/// Can be built directly, or parsed from a PHC string (`$argon2id$v=19$m=65536,t=3,p=4$...`)
+/// or a modular crypt bcrypt string (`$2b$12$...`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[non_exhaustive]
+pub enum HashCost {
+    /// bcrypt with the given log2 work factor.
+    Bcrypt {
+        /// Log2 of the number of key expansion rounds.
+        cost: u32,
+    },
+    /// Argon2 (any variant).
+    Argon2 {
+        /// Memory size in KiB.
+        m_cost: u32,
+        /// Number of passes over memory.
+        t_cost: u32,
+        /// Degree of parallelism.
+        p_cost: u32,
+    },
+    /// scrypt.
+    Scrypt {
+        /// Log2 of the CPU/memory cost parameter `N`.
+        log_n: u8,
+        /// Block size.
+        r: u32,
+        /// Parallelization parameter.
+        p: u32,
+    },
+    /// PBKDF2 using HMAC-SHA1.
+    Pbkdf2Sha1 {
+        /// Number of iterations.
+        iterations: u32,
+    },
+    /// PBKDF2 using HMAC-SHA256.
+    Pbkdf2Sha256 {
+        /// Number of iterations.
+        iterations: u32,
+    },
+    /// PBKDF2 using HMAC-SHA512.
+    Pbkdf2Sha512 {
+        /// Number of iterations.
+        iterations: u32,
+    },
+}
+
+impl HashCost {
+    /// Estimated guesses per second a single commodity GPU achieves against this hash.
+    ///
+    /// Figures are derived from public hashcat benchmarks of a current high-end consumer GPU
+    /// and scaled linearly with the work factor, so treat them as ballpark numbers.
+    pub fn guesses_per_second(self) -> f64 {
+        // hashcat -m 3200 benchmark runs at cost 5.
+        const BCRYPT_COST_5: f64 = 184_000.0;
+        // Memory-hard functions are bandwidth bound on GPUs, so cost scales with
+        // total memory touched. Calibrated on m=65536, t=3 at ~1000 guesses per second.
+        const ARGON2_KIB_PASSES: f64 = 196_608_000.0;
+        // hashcat -m 8900 benchmark runs at N=16384, r=1, p=1.
+        const SCRYPT_N16384_R1_P1: f64 = 7_100.0;
+        // hashcat -m 12000, 10900 and 12100 benchmarks, expressed per single iteration.
+        const PBKDF2_SHA1_ITERATIONS: f64 = 24_000_000_000.0;
+        const PBKDF2_SHA256_ITERATIONS: f64 = 8_800_000_000.0;
+        const PBKDF2_SHA512_ITERATIONS: f64 = 3_000_000_000.0;
+
+        let rate = match self {
+            HashCost::Bcrypt { cost } => BCRYPT_COST_5 / 2f64.powi(cost as i32 - 5),
+            HashCost::Argon2 { m_cost, t_cost, .. } => {
+                ARGON2_KIB_PASSES / (f64::from(m_cost) * f64::from(t_cost))
+            }
+            HashCost::Scrypt { log_n, r, p } => {
+                SCRYPT_N16384_R1_P1 * 16_384.0
+                    / (2f64.powi(i32::from(log_n)) * f64::from(r) * f64::from(p))
+            }
+            HashCost::Pbkdf2Sha1 { iterations } => PBKDF2_SHA1_ITERATIONS / f64::from(iterations),
+            HashCost::Pbkdf2Sha256 { iterations } => {
+                PBKDF2_SHA256_ITERATIONS / f64::from(iterations)
+            }
+            HashCost::Pbkdf2Sha512 { iterations } => {
+                PBKDF2_SHA512_ITERATIONS / f64::from(iterations)
+            }
+        };
+        // Zero cost parameters would otherwise divide by zero.
+        if rate.is_finite() {
+            rate
+        } else {
+            f64::MAX
+        }
+    }
+}
+
+impl FromStr for HashCost {
+    type Err = ParseHashCostError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.split('$');
+        if fields.next() != Some("") {
+            return Err(ParseHashCostError::InvalidFormat);
+        }
+        let id = fields.next().ok_or(ParseHashCostError::InvalidFormat)?;
+        match id {
+            "2a" | "2b" | "2x" | "2y" => {
+                let cost = fields
+                    .next()
+                    .and_then(|c| c.parse().ok())
+                    .ok_or(ParseHashCostError::InvalidParameter)?;
+                Ok(HashCost::Bcrypt { cost })
+            }
+            "argon2i" | "argon2d" | "argon2id" => {
+                let mut params = fields.next().ok_or(ParseHashCostError::InvalidFormat)?;
+                if params.starts_with("v=") {
+                    params = fields.next().ok_or(ParseHashCostError::InvalidFormat)?;
+                }
+                Ok(HashCost::Argon2 {
+                    m_cost: phc_param(params, "m")?,
+                    t_cost: phc_param(params, "t")?,
+                    p_cost: phc_param(params, "p")?,
+                })
+            }
+            "scrypt" => {
+                let params = fields.next().ok_or(ParseHashCostError::InvalidFormat)?;
+                Ok(HashCost::Scrypt {
+                    log_n: phc_param(params, "ln")?,
+                    r: phc_param(params, "r")?,
+                    p: phc_param(params, "p")?,
+                })
+            }
+            "pbkdf2" | "pbkdf2-sha1" | "pbkdf2-sha256" | "pbkdf2-sha512" => {
+                let params = fields.next().ok_or(ParseHashCostError::InvalidFormat)?;
+                // passlib stores a bare round count, the PHC spec uses `i=`.
+                let iterations = match params.parse() {
+                    Ok(iterations) => iterations,
+                    Err(_) => phc_param(params, "i")?,
+                };
+                Ok(match id {
+                    "pbkdf2-sha256" => HashCost::Pbkdf2Sha256 { iterations },
+                    "pbkdf2-sha512" => HashCost::Pbkdf2Sha512 { iterations },
+                    _ => HashCost::Pbkdf2Sha1 { iterations },
+                })
+            }
+            _ => Err(ParseHashCostError::UnknownAlgorithm),
+        }
+    }
 }
 
+fn phc_param<T: FromStr>(params: &str, name: &str) -> Result<T, ParseHashCostError> {
+    params
+        .split(',')
+        .filter_map(|param| param.split_once('='))
+        .find(|&(key, _)| key == name)
+        .ok_or(ParseHashCostError::MissingParameter)?
+        .1
+        .parse()
+        .map_err(|_| ParseHashCostError::InvalidParameter)
+}
+
+/// An error which can be returned when parsing a [`HashCost`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseHashCostError {
+    /// The string is not a PHC or modular crypt formatted hash.
+    InvalidFormat,
+    /// The hash function identifier is not supported.
+    UnknownAlgorithm,
+    /// A required cost parameter is missing.
+    MissingParameter,
+    /// A cost parameter could not be parsed.
+    InvalidParameter,
+}
+
+impl fmt::Display for ParseHashCostError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseHashCostError::InvalidFormat => write!(f, "invalid hash string format"),
+            ParseHashCostError::UnknownAlgorithm => write!(f, "unsupported hash algorithm"),
+            ParseHashCostError::MissingParameter => write!(f, "missing hash cost parameter"),
+            ParseHashCostError::InvalidParameter => write!(f, "invalid hash cost parameter"),
+        }
+    }
+}
+
+impl Error for ParseHashCostError {}
+
 /// Represents the time to crack a password.
 #[derive(Copy, Clone, Debug)]
 #[cfg_attr(feature = "ser", derive(Serialize))]
@@ -83,39 +291,7 @@ pub enum CrackTimeSeconds {
 
 impl fmt::Display for CrackTimeSeconds {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let seconds = match self {
-            CrackTimeSeconds::Integer(i) => *i,
-            CrackTimeSeconds::Float(f) => *f as u64,
-        };
-        const MINUTE: u64 = 60;
-        const HOUR: u64 = MINUTE * 60;
-        const DAY: u64 = HOUR * 24;
-        const MONTH: u64 = DAY * 31;
-        const YEAR: u64 = MONTH * 12;
-        const CENTURY: u64 = YEAR * 100;
-        if seconds < 1 {
-            write!(f, "less than a second")
-        } else if seconds < MINUTE {
-            let base = seconds;
-            write!(f, "{} second{}", base, if base > 1 { "s" } else { "" })
-        } else if seconds < HOUR {
-            let base = seconds / MINUTE;
-            write!(f, "{} minute{}", base, if base > 1 { "s" } else { "" })
-        } else if seconds < DAY {
-            let base = seconds / HOUR;
-            write!(f, "{} hour{}", base, if base > 1 { "s" } else { "" })
-        } else if seconds < MONTH {
-            let base = seconds / DAY;
-            write!(f, "{} day{}", base, if base > 1 { "s" } else { "" })
-        } else if seconds < YEAR {
-            let base = seconds / MONTH;
-            write!(f, "{} month{}", base, if base > 1 { "s" } else { "" })
-        } else if seconds < CENTURY {
-            let base = seconds / YEAR;
-            write!(f, "{} year{}", base, if base > 1 { "s" } else { "" })
-        } else {
-            write!(f, "centuries")
-        }
+        write!(f, "{}", format_crack_time(*self, Locale::English))
     }
 }
 
@@ -129,21 +305,188 @@ impl From<CrackTimeSeconds> for std::time::Duration {
     }
 }
 
-pub(crate) fn estimate_attack_times(guesses: u64) -> (CrackTimes, u8) {
-    (CrackTimes::new(guesses), calculate_score(guesses))
+pub(crate) fn estimate_attack_times(guesses: u64) -> (CrackTimes, Score) {
+    (CrackTimes::new(guesses), Score::from_guesses(guesses))
+}
+
+/// Overall strength score from 0-4, ordered from weakest to strongest.
+/// Any score less than [`Score::SafelyUnguessable`] should be considered too weak.
+///
+/// Serialized as its number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "ser", serde(into = "u8"))]
+#[repr(u8)]
+pub enum Score {
+    /// Too guessable: risky password, fewer than 10^3 guesses.
+    TooGuessable = 0,
+    /// Very guessable: protection from throttled online attacks, fewer than 10^6 guesses.
+    VeryGuessable = 1,
+    /// Somewhat guessable: protection from unthrottled online attacks, fewer than 10^8
+    /// guesses.
+    SomewhatGuessable = 2,
+    /// Safely unguessable: moderate protection from offline slow-hash scenarios, fewer than
+    /// 10^10 guesses.
+    SafelyUnguessable = 3,
+    /// Very unguessable: strong protection from offline slow-hash scenarios, 10^10 guesses
+    /// or more.
+    VeryUnguessable = 4,
+}
+
+impl Score {
+    /// The score of a password needing the given number of guesses, for pipelines
+    /// estimating guesses on their own.
+    pub fn from_guesses(guesses: u64) -> Self {
+        const DELTA: u64 = 5;
+        if guesses < 1_000 + DELTA {
+            Score::TooGuessable
+        } else if guesses < 1_000_000 + DELTA {
+            Score::VeryGuessable
+        } else if guesses < 100_000_000 + DELTA {
+            Score::SomewhatGuessable
+        } else if guesses < 10_000_000_000 + DELTA {
+            Score::SafelyUnguessable
+        } else {
+            Score::VeryUnguessable
+        }
+    }
 }
 
-fn calculate_score(guesses: u64) -> u8 {
-    const DELTA: u64 = 5;
-    if guesses < 1_000 + DELTA {
-        0
-    } else if guesses < 1_000_000 + DELTA {
-        1
-    } else if guesses < 100_000_000 + DELTA {
-        2
-    } else if guesses < 10_000_000_000 + DELTA {
-        3
-    } else {
-        4
+impl From<Score> for u8 {
+    fn from(score: Score) -> u8 {
+        score as u8
+    }
+}
+
+impl TryFrom<u8> for Score {
+    type Error = ScoreOutOfRangeError;
+
+    fn try_from(score: u8) -> Result<Self, Self::Error> {
+        match score {
+            0 => Ok(Score::TooGuessable),
+            1 => Ok(Score::VeryGuessable),
+            2 => Ok(Score::SomewhatGuessable),
+            3 => Ok(Score::SafelyUnguessable),
+            4 => Ok(Score::VeryUnguessable),
+            _ => Err(ScoreOutOfRangeError),
+        }
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", u8::from(*self))
+    }
+}
+
+/// The error returned when converting a number above 4 into a [`Score`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoreOutOfRangeError;
+
+impl fmt::Display for ScoreOutOfRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "score out of range, must be from 0 to 4")
+    }
+}
+
+impl Error for ScoreOutOfRangeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_parse_hash_cost() {
+        let test_data = [
+            (
+                "$2y$10$abcdefghijklmnopqrstuu5Ss7KIUgO2t0jWMUW",
+                HashCost::Bcrypt { cost: 10 },
+            ),
+            (
+                "$argon2id$v=19$m=65536,t=3,p=4$c29tZXNhbHQ$RdescudvJCsgt3ub+b+dWRWJTmaaJObG",
+                HashCost::Argon2 {
+                    m_cost: 65536,
+                    t_cost: 3,
+                    p_cost: 4,
+                },
+            ),
+            (
+                "$argon2i$m=4096,t=3,p=1$c29tZXNhbHQ",
+                HashCost::Argon2 {
+                    m_cost: 4096,
+                    t_cost: 3,
+                    p_cost: 1,
+                },
+            ),
+            (
+                "$scrypt$ln=15,r=8,p=1$c29tZXNhbHQ$aGFzaA",
+                HashCost::Scrypt {
+                    log_n: 15,
+                    r: 8,
+                    p: 1,
+                },
+            ),
+            (
+                "$pbkdf2-sha256$29000$N2bMmZNyLgVqJnZRwbgVgg$aGFzaA",
+                HashCost::Pbkdf2Sha256 { iterations: 29000 },
+            ),
+            (
+                "$pbkdf2-sha512$i=210000,l=64$c29tZXNhbHQ$aGFzaA",
+                HashCost::Pbkdf2Sha512 { iterations: 210000 },
+            ),
+        ];
+        for (hash, expected) in &test_data {
+            assert_eq!(hash.parse::<HashCost>(), Ok(*expected));
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_parse_hash_cost_errors() {
+        assert_eq!(
+            "5f4dcc3b5aa765d61d8327deb882cf99".parse::<HashCost>(),
+            Err(ParseHashCostError::InvalidFormat)
+        );
+        assert_eq!(
+            "$1$saltsalt$hash".parse::<HashCost>(),
+            Err(ParseHashCostError::UnknownAlgorithm)
+        );
+        assert_eq!(
+            "$argon2id$v=19$m=65536,p=4$c29tZXNhbHQ".parse::<HashCost>(),
+            Err(ParseHashCostError::MissingParameter)
+        );
+        assert_eq!(
+            "$2b$x$hash".parse::<HashCost>(),
+            Err(ParseHashCostError::InvalidParameter)
+        );
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_score() {
+        assert_eq!(Score::from_guesses(1_000), Score::TooGuessable);
+        assert_eq!(Score::from_guesses(1_000_004), Score::VeryGuessable);
+        assert_eq!(Score::from_guesses(10_000_000_005), Score::VeryUnguessable);
+        assert!(Score::TooGuessable < Score::VeryUnguessable);
+        for score in 0..=4 {
+            assert_eq!(Score::try_from(score).map(u8::from), Ok(score));
+        }
+        assert_eq!(Score::try_from(5), Err(ScoreOutOfRangeError));
+        assert_eq!(Score::SafelyUnguessable.to_string(), "3");
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_hash_cost_scales_with_work_factor() {
+        let cheap = HashCost::Bcrypt { cost: 10 }.guesses_per_second();
+        let expensive = HashCost::Bcrypt { cost: 12 }.guesses_per_second();
+        assert!((cheap / expensive - 4.0).abs() < 1e-9);
+        assert!(HashCost::Pbkdf2Sha1 { iterations: 0 }
+            .guesses_per_second()
+            .is_finite());
     }
 }
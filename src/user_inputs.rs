@@ -0,0 +1,157 @@
+//! Turning what a site knows about a user into the user inputs their passwords are estimated
+//! with.
+//!
+//! Passwords are built from a user's name and addresses in more ways than as given: "jsmith",
+//! "johnsmith" and "smithj" all come from "John Smith". [`user_input_tokens`] lists them, so
+//! that estimates recognize them all:
+//!
+//! ```
+//! use zxcvbn::{user_input_tokens, zxcvbn};
+//!
+//! let tokens = user_input_tokens("john.smith+news@acme.com", "John Smith", "jsmith84");
+//! let user_inputs = tokens.iter().map(String::as_str).collect::<Vec<_>>();
+//! assert!(user_inputs.contains(&"johnsmith"));
+//! assert!(zxcvbn("smithjohn!", &user_inputs).guesses() < zxcvbn("smithjohn!", &[]).guesses());
+//! ```
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::context::{split_words, Context, MIN_WORD_LENGTH};
+use crate::normalization::Normalizers;
+
+/// Lists the words passwords are commonly built from out of a user's email address, full
+/// name and username, lowercased and normalized like passwords are, most likely first.
+///
+/// - the username, and its words split on punctuation, camel case and digits
+/// - the local part of the email address without its `+` tag, joined and split on dots,
+///   dashes and underscores, and the words of its domain
+/// - the parts of the name, their initials, and the first and last names joined in both
+///   orders and with either one abbreviated to its initial
+///
+/// Names with accents are also listed without them. Empty arguments are skipped, and words
+/// shorter than 3 characters are left out as they would match inside unrelated passwords.
+pub fn user_input_tokens(email: &str, name: &str, username: &str) -> Vec<String> {
+    let mut tokens = Tokens::default();
+
+    tokens.push(username);
+    tokens.words(username);
+
+    let (local, domain) = email.rsplit_once('@').unwrap_or((email, ""));
+    let local = local.split('+').next().unwrap_or_default();
+    let parts = local
+        .split(['.', '-', '_'])
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>();
+    tokens.push(&parts.concat());
+    for part in &parts {
+        tokens.words(part);
+    }
+    for word in Context::new().email_domain(domain).words() {
+        tokens.push(word);
+    }
+
+    let names = name
+        .split(|c: char| !c.is_alphanumeric() && c != '\'' && c != '’')
+        .map(|part| part.replace(['\'', '’'], ""))
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>();
+    if let (Some(first), Some(last)) = (names.first(), names.last().filter(|_| names.len() > 1)) {
+        let first_initial = initial(first);
+        let last_initial = initial(last);
+        tokens.push(&format!("{first}{last}"));
+        tokens.push(&format!("{last}{first}"));
+        tokens.push(&format!("{first_initial}{last}"));
+        tokens.push(&format!("{last}{first_initial}"));
+        tokens.push(&format!("{first}{last_initial}"));
+        tokens.push(&names.iter().map(|part| initial(part)).collect::<String>());
+    }
+    for part in &names {
+        tokens.push(part);
+    }
+
+    tokens.0
+}
+
+fn initial(word: &str) -> &str {
+    word.char_indices().nth(1).map_or(word, |(i, _)| &word[..i])
+}
+
+#[derive(Default)]
+struct Tokens(Vec<String>);
+
+impl Tokens {
+    /// Adds the words of `text` split like [`Context`] splits them.
+    fn words(&mut self, text: &str) {
+        for part in text.split(|c: char| !c.is_alphanumeric()) {
+            for word in split_words(part) {
+                self.push(word);
+            }
+        }
+    }
+
+    fn push(&mut self, token: &str) {
+        let token = Normalizers::default().normalize(token).to_lowercase();
+        let unaccented = token
+            .nfd()
+            .filter(|&c| !is_combining_mark(c))
+            .collect::<String>();
+        for token in [token, unaccented] {
+            if token.chars().count() >= MIN_WORD_LENGTH && !self.0.contains(&token) {
+                self.0.push(token);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_input_tokens() {
+        assert_eq!(
+            user_input_tokens(
+                "john.q.smith+news@acme-widgets.com",
+                "John Q. Smith",
+                "JSmith84"
+            ),
+            [
+                "jsmith84",
+                "jsmith",
+                "johnqsmith",
+                "john",
+                "smith",
+                "acme",
+                "widgets",
+                "johnsmith",
+                "smithjohn",
+                "smithj",
+                "johns",
+                "jqs",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_user_input_tokens_names() {
+        assert_eq!(
+            user_input_tokens("", "José O'Brien", ""),
+            [
+                "joséobrien",
+                "joseobrien",
+                "obrienjosé",
+                "obrienjose",
+                "jobrien",
+                "obrienj",
+                "joséo",
+                "joseo",
+                "josé",
+                "jose",
+                "obrien",
+            ]
+        );
+        assert_eq!(user_input_tokens("", "Cher", ""), ["cher"]);
+        assert!(user_input_tokens("", "", "").is_empty());
+    }
+}
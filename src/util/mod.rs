@@ -0,0 +1,3 @@
+//! Helpers for the code around an estimator that handles passwords too.
+
+pub mod secure;
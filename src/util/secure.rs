@@ -0,0 +1,189 @@
+//! Comparing and hashing passwords without giving them away.
+//!
+//! Checking a password against a list of breached passwords with `==` takes longer the more
+//! of its first bytes match one of them, and a cache of estimates keyed by passwords keeps
+//! them in memory in plain text. [`constant_time_eq`] and [`constant_time_contains`] compare
+//! in a time that depends only on the lengths involved, and with the `keyed-hashing` feature,
+//! a [`Pepper`] hashes passwords with a secret key, so that lists and caches can hold digests
+//! that can't be checked against guesses without the key:
+//!
+//! ```
+//! # #[cfg(feature = "keyed-hashing")]
+//! # {
+//! use zxcvbn::util::secure::{constant_time_contains, Pepper};
+//!
+//! let pepper = Pepper::random();
+//! let breached = ["password1", "letmein"].map(|password| pepper.digest(password));
+//! assert!(constant_time_contains(pepper.digest("letmein"), &breached));
+//! assert!(!constant_time_contains(pepper.digest("letmein!"), &breached));
+//! # }
+//! ```
+//!
+//! These are best efforts: the compiler gives no guarantee that the loops are not shortened,
+//! and only the comparisons of equally long values, such as digests, hide their lengths.
+
+#[cfg(feature = "keyed-hashing")]
+use std::fmt;
+#[cfg(feature = "keyed-hashing")]
+use std::hash::{Hash, Hasher};
+
+/// Whether `a` and `b` are equal, going through every byte whatever the first difference. The
+/// time taken depends on the lengths, which are compared first.
+pub fn constant_time_eq(a: impl AsRef<[u8]>, b: impl AsRef<[u8]>) -> bool {
+    let (a, b) = (a.as_ref(), b.as_ref());
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Whether `needle` is one of `haystack`, compared with [`constant_time_eq`] to every one of
+/// them, also those after a match.
+pub fn constant_time_contains<T, U>(needle: T, haystack: impl IntoIterator<Item = U>) -> bool
+where
+    T: AsRef<[u8]>,
+    U: AsRef<[u8]>,
+{
+    haystack
+        .into_iter()
+        .fold(false, |found, item| found | constant_time_eq(&needle, item))
+}
+
+/// A keyed BLAKE3 hash of a password, compared in constant time.
+#[cfg(feature = "keyed-hashing")]
+#[derive(Clone, Copy, Eq)]
+pub struct Digest([u8; blake3::OUT_LEN]);
+
+#[cfg(feature = "keyed-hashing")]
+impl Digest {
+    /// The bytes of the digest.
+    pub fn as_bytes(&self) -> &[u8; blake3::OUT_LEN] {
+        &self.0
+    }
+}
+
+#[cfg(feature = "keyed-hashing")]
+impl AsRef<[u8]> for Digest {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(feature = "keyed-hashing")]
+impl PartialEq for Digest {
+    fn eq(&self, other: &Self) -> bool {
+        constant_time_eq(self.0, other.0)
+    }
+}
+
+#[cfg(feature = "keyed-hashing")]
+impl Hash for Digest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+/// Digests are not printed, as they can be checked against guesses by whoever has the key.
+#[cfg(feature = "keyed-hashing")]
+impl fmt::Debug for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Digest(..)")
+    }
+}
+
+/// A secret key passwords are hashed with, so that their digests can be kept in banlists and
+/// caches without giving the passwords away to whoever reads them.
+#[cfg(feature = "keyed-hashing")]
+#[derive(Clone)]
+pub struct Pepper {
+    key: [u8; blake3::KEY_LEN],
+}
+
+#[cfg(feature = "keyed-hashing")]
+impl Pepper {
+    /// A pepper with a key of its own, kept by the application, e.g. to share digests
+    /// between servers.
+    pub fn new(key: [u8; blake3::KEY_LEN]) -> Self {
+        Pepper { key }
+    }
+
+    /// A pepper with a random key, for digests that don't outlive the process.
+    pub fn random() -> Self {
+        let mut key = [0; blake3::KEY_LEN];
+        getrandom::getrandom(&mut key).expect("failed to generate a pepper");
+        Pepper { key }
+    }
+
+    /// The digest of a password.
+    pub fn digest(&self, password: &str) -> Digest {
+        self.digest_with_inputs(password, &[])
+    }
+
+    /// The digest of a password together with the user inputs it is estimated with, such as
+    /// the key of a cached estimate.
+    pub fn digest_with_inputs(&self, password: &str, user_inputs: &[&str]) -> Digest {
+        let mut hasher = blake3::Hasher::new_keyed(&self.key);
+        // lengths keep ("ab", ["c"]) and ("a", ["bc"]) apart
+        for input in std::iter::once(&password).chain(user_inputs) {
+            hasher.update(&(input.len() as u64).to_le_bytes());
+            hasher.update(input.as_bytes());
+        }
+        Digest(*hasher.finalize().as_bytes())
+    }
+}
+
+/// The key is not printed.
+#[cfg(feature = "keyed-hashing")]
+impl fmt::Debug for Pepper {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Pepper(..)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq("hunter2", "hunter2"));
+        assert!(!constant_time_eq("hunter2", "hunter3"));
+        assert!(!constant_time_eq("hunter2", "hunter"));
+        assert!(constant_time_eq("", ""));
+        assert!(constant_time_eq([1, 2], vec![1, 2]));
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_constant_time_contains() {
+        let banned = ["password", "letmein", "password"];
+        assert!(constant_time_contains("letmein", banned));
+        assert!(constant_time_contains("password", banned));
+        assert!(!constant_time_contains("Password", banned));
+        assert!(!constant_time_contains("password", Vec::<&str>::new()));
+    }
+
+    #[cfg(feature = "keyed-hashing")]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_pepper() {
+        let pepper = Pepper::new([7; blake3::KEY_LEN]);
+        assert_eq!(pepper.digest("ab"), pepper.digest_with_inputs("ab", &[]));
+        assert_ne!(
+            pepper.digest_with_inputs("ab", &["c"]),
+            pepper.digest_with_inputs("a", &["bc"])
+        );
+        // the same password with another key
+        assert_ne!(pepper.digest("ab"), Pepper::random().digest("ab"));
+        assert_eq!(
+            pepper.digest("ab"),
+            Pepper::new([7; blake3::KEY_LEN]).digest("ab")
+        );
+        assert_eq!(format!("{:?}", pepper), "Pepper(..)");
+        assert_eq!(format!("{:?}", pepper.digest("ab")), "Digest(..)");
+    }
+}
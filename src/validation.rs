@@ -0,0 +1,122 @@
+//! Enforcing a minimum password strength with the [`validator`] crate, from the custom
+//! validators web backends already run their forms through.
+//!
+//! [`validate_password`] checks a password against a minimum [`Score`]. Custom validators
+//! only take the field being validated, so [`password_validator!`](crate::password_validator)
+//! defines a function with the minimum score built in, to name in the derive attribute:
+//!
+//! ```
+//! use validator::Validate;
+//! use zxcvbn::Score;
+//!
+//! zxcvbn::password_validator!(strong_password, Score::SafelyUnguessable);
+//!
+//! #[derive(Validate)]
+//! struct SignupForm {
+//!     #[validate(custom(function = "strong_password"))]
+//!     password: String,
+//! }
+//!
+//! let form = SignupForm { password: "password1".to_string() };
+//! let errors = form.validate().unwrap_err();
+//! let error = &errors.field_errors()["password"][0];
+//! assert_eq!(error.code, "password_strength");
+//! assert_eq!(error.params["score"], 0);
+//!
+//! let form = SignupForm { password: "correct horse battery staple".to_string() };
+//! assert!(form.validate().is_ok());
+//! ```
+
+use std::borrow::Cow;
+
+pub use validator::ValidationError;
+
+use crate::{zxcvbn, Entropy, Score, Zxcvbn};
+
+/// The [`ValidationError::code`] of passwords scoring below the minimum.
+pub const CODE: &str = "password_strength";
+
+/// Checks that `password` scores at least `min_score`.
+///
+/// The error has the [`CODE`] `"password_strength"`, the password's `score` and the
+/// `min_score` as parameters, and the [warning](crate::feedback::Warning) about the password,
+/// if there is one, as its message.
+pub fn validate_password(password: &str, min_score: Score) -> Result<(), ValidationError> {
+    check(&zxcvbn(password, &[]), min_score)
+}
+
+/// Checks that `password` scores at least `min_score` with `estimator`, for validators that
+/// should also reject the estimator's banned words and passwords.
+pub fn validate_password_with(
+    estimator: &Zxcvbn,
+    password: &str,
+    min_score: Score,
+) -> Result<(), ValidationError> {
+    check(&estimator.estimate(password, &[]), min_score)
+}
+
+fn check(entropy: &Entropy, min_score: Score) -> Result<(), ValidationError> {
+    if entropy.score() >= min_score {
+        return Ok(());
+    }
+    let mut error = ValidationError::new(CODE);
+    error.add_param(Cow::Borrowed("score"), &u8::from(entropy.score()));
+    error.add_param(Cow::Borrowed("min_score"), &u8::from(min_score));
+    if let Some(warning) = entropy.feedback().as_ref().and_then(|f| f.warning()) {
+        error = error.with_message(Cow::Owned(warning.to_string()));
+    }
+    Err(error)
+}
+
+/// Defines a function checking that a password scores at least a minimum [`Score`], to use
+/// as a custom validator of the `validator` crate.
+///
+/// ```
+/// use zxcvbn::Score;
+///
+/// zxcvbn::password_validator!(pub strong_password, Score::SafelyUnguessable);
+///
+/// assert!(strong_password("password1").is_err());
+/// ```
+#[macro_export]
+macro_rules! password_validator {
+    ($(#[$attr:meta])* $vis:vis $name:ident, $min_score:expr) => {
+        $(#[$attr])*
+        $vis fn $name(
+            password: &str,
+        ) -> ::core::result::Result<(), $crate::validation::ValidationError> {
+            $crate::validation::validate_password(password, $min_score)
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    password_validator!(somewhat_guessable, Score::SomewhatGuessable);
+
+    #[test]
+    fn test_validate_password() {
+        let error = validate_password("password", Score::VeryGuessable).unwrap_err();
+        assert_eq!(error.code, CODE);
+        assert_eq!(error.params["score"], 0);
+        assert_eq!(error.params["min_score"], 1);
+        assert_eq!(
+            error.message.as_deref(),
+            Some("This is a top-10 common password.")
+        );
+        assert!(validate_password("password", Score::TooGuessable).is_ok());
+        assert!(somewhat_guessable("Tr0ub4dour&3").is_ok());
+        assert!(somewhat_guessable("qwerty").is_err());
+    }
+
+    #[test]
+    fn test_validate_password_with_banned_passwords() {
+        let estimator = Zxcvbn::new();
+        let password = "springfieldkwikemart";
+        assert!(validate_password_with(&estimator, password, Score::SafelyUnguessable).is_ok());
+        estimator.add_banned_passwords([password]);
+        assert!(validate_password_with(&estimator, password, Score::SafelyUnguessable).is_err());
+    }
+}
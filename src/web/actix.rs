@@ -0,0 +1,155 @@
+//! The [`CheckedPassword`] extractor for actix-web.
+//!
+//! ```
+//! use actix_web::{web, App, HttpResponse};
+//! use zxcvbn::requirements::Requirements;
+//! use zxcvbn::web::{CheckedPassword, PasswordField, PasswordPolicy};
+//!
+//! #[derive(serde_derive::Deserialize)]
+//! struct Signup {
+//!     email: String,
+//!     password: String,
+//! }
+//!
+//! impl PasswordField for Signup {
+//!     fn password(&self) -> &str {
+//!         &self.password
+//!     }
+//!
+//!     fn user_inputs(&self) -> Vec<&str> {
+//!         vec![&self.email]
+//!     }
+//! }
+//!
+//! async fn signup(checked: CheckedPassword<Signup>) -> HttpResponse {
+//!     HttpResponse::Ok().body(format!("welcome, {}", checked.form.email))
+//! }
+//!
+//! let app = App::new()
+//!     .app_data(web::Data::new(PasswordPolicy::new(Requirements::default())))
+//!     .route("/signup", web::post().to(signup));
+//! ```
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+use actix_web::dev::Payload;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Json};
+use actix_web::{FromRequest, HttpRequest, HttpResponse, ResponseError};
+use serde::de::DeserializeOwned;
+
+use super::{CheckedPassword, PasswordField, PasswordPolicy, RejectionBody};
+use crate::requirements::Rejection;
+
+/// A password that doesn't meet the requirements, answered with 422 Unprocessable Entity and a
+/// [`RejectionBody`].
+#[derive(Debug)]
+pub struct PasswordRejection(pub Rejection);
+
+impl fmt::Display for PasswordRejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ResponseError for PasswordRejection {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::UNPROCESSABLE_ENTITY
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).json(RejectionBody::from(&self.0))
+    }
+}
+
+/// Checks with the [`PasswordPolicy`] registered as `web::Data`, or the default one. Bodies
+/// that are not JSON of the expected type are answered like actix-web's `Json` does.
+impl<T> FromRequest for CheckedPassword<T>
+where
+    T: DeserializeOwned + PasswordField + 'static,
+{
+    type Error = actix_web::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let policy = req
+            .app_data::<Data<PasswordPolicy>>()
+            .map(|policy| PasswordPolicy::clone(policy))
+            .unwrap_or_default();
+        let json = Json::<T>::from_request(req, payload);
+        Box::pin(async move {
+            let form = json.await?.into_inner();
+            let entropy = policy.check(&form).map_err(PasswordRejection)?;
+            Ok(CheckedPassword { form, entropy })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::header::CONTENT_TYPE;
+    use actix_web::rt::System;
+    use actix_web::test::TestRequest;
+
+    use crate::requirements::Requirements;
+
+    #[derive(Debug, Deserialize)]
+    struct Signup {
+        password: String,
+    }
+
+    impl PasswordField for Signup {
+        fn password(&self) -> &str {
+            &self.password
+        }
+    }
+
+    fn extract(req: TestRequest) -> Result<CheckedPassword<Signup>, actix_web::Error> {
+        let (req, mut payload) = req
+            .insert_header((CONTENT_TYPE, "application/json"))
+            .to_http_parts();
+        System::new().block_on(CheckedPassword::<Signup>::from_request(&req, &mut payload))
+    }
+
+    #[test]
+    fn test_checked_password() {
+        let checked = extract(
+            TestRequest::post().set_payload(r#"{"password": "correcthorsebatterystaple"}"#),
+        )
+        .unwrap();
+        assert_eq!(checked.form.password, "correcthorsebatterystaple");
+
+        let err =
+            extract(TestRequest::post().set_payload(r#"{"password": "password"}"#)).unwrap_err();
+        let rejection = err.as_error::<PasswordRejection>().unwrap();
+        assert!(matches!(rejection.0, Rejection::TooGuessable { .. }));
+        assert_eq!(
+            err.as_response_error().status_code(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+
+        let err = extract(TestRequest::post().set_payload(r#"{"password": 1}"#)).unwrap_err();
+        assert!(err.as_error::<PasswordRejection>().is_none());
+    }
+
+    #[test]
+    fn test_policy_data() {
+        let policy = PasswordPolicy::new(Requirements {
+            min_len: 12,
+            ..Default::default()
+        });
+        let err = extract(
+            TestRequest::post()
+                .app_data(Data::new(policy))
+                .set_payload(r#"{"password": "Xk7#q"}"#),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err.as_error::<PasswordRejection>().unwrap().0,
+            Rejection::TooShort { min_len: 12, .. }
+        ));
+    }
+}
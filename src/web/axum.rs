@@ -0,0 +1,156 @@
+//! The [`CheckedPassword`] extractor for axum.
+//!
+//! ```
+//! use axum::routing::post;
+//! use axum::{Extension, Router};
+//! use zxcvbn::requirements::Requirements;
+//! use zxcvbn::web::{CheckedPassword, PasswordField, PasswordPolicy};
+//!
+//! #[derive(serde_derive::Deserialize)]
+//! struct Signup {
+//!     email: String,
+//!     password: String,
+//! }
+//!
+//! impl PasswordField for Signup {
+//!     fn password(&self) -> &str {
+//!         &self.password
+//!     }
+//!
+//!     fn user_inputs(&self) -> Vec<&str> {
+//!         vec![&self.email]
+//!     }
+//! }
+//!
+//! async fn signup(CheckedPassword { form, .. }: CheckedPassword<Signup>) -> String {
+//!     format!("welcome, {}", form.email)
+//! }
+//!
+//! let app: Router = Router::new()
+//!     .route("/signup", post(signup))
+//!     .layer(Extension(PasswordPolicy::new(Requirements::default())));
+//! ```
+
+use axum::extract::rejection::JsonRejection;
+use axum::extract::{FromRequest, Request};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::de::DeserializeOwned;
+
+use super::{CheckedPassword, PasswordField, PasswordPolicy, RejectionBody};
+use crate::requirements::Rejection;
+
+/// Why a [`CheckedPassword`] could not be extracted.
+#[derive(Debug)]
+pub enum PasswordRejection {
+    /// The body is not JSON of the expected type, answered like axum's `Json` does.
+    Json(JsonRejection),
+    /// The password doesn't meet the requirements, answered with 422 Unprocessable Entity and
+    /// a [`RejectionBody`].
+    Password(Rejection),
+}
+
+impl IntoResponse for PasswordRejection {
+    fn into_response(self) -> Response {
+        match self {
+            PasswordRejection::Json(rejection) => rejection.into_response(),
+            PasswordRejection::Password(rejection) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(RejectionBody::from(&rejection)),
+            )
+                .into_response(),
+        }
+    }
+}
+
+/// Checks with the [`PasswordPolicy`] added with `Extension`, or the default one.
+impl<S, T> FromRequest<S> for CheckedPassword<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned + PasswordField,
+{
+    type Rejection = PasswordRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let policy = req
+            .extensions()
+            .get::<PasswordPolicy>()
+            .cloned()
+            .unwrap_or_default();
+        let Json(form) = Json::<T>::from_request(req, state)
+            .await
+            .map_err(PasswordRejection::Json)?;
+        let entropy = policy.check(&form).map_err(PasswordRejection::Password)?;
+        Ok(CheckedPassword { form, entropy })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::header::CONTENT_TYPE;
+
+    #[derive(Debug, Deserialize)]
+    struct Signup {
+        password: String,
+    }
+
+    impl PasswordField for Signup {
+        fn password(&self) -> &str {
+            &self.password
+        }
+    }
+
+    fn request(body: &'static str) -> Request {
+        Request::builder()
+            .method("POST")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_checked_password() {
+        let req = request(r#"{"password": "correcthorsebatterystaple"}"#);
+        let checked = CheckedPassword::<Signup>::from_request(req, &())
+            .await
+            .unwrap();
+        assert_eq!(checked.form.password, "correcthorsebatterystaple");
+
+        let req = request(r#"{"password": "password"}"#);
+        let rejection = CheckedPassword::<Signup>::from_request(req, &())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            rejection,
+            PasswordRejection::Password(Rejection::TooGuessable { .. })
+        ));
+        let response = rejection.into_response();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let req = request(r#"{"password": 1}"#);
+        let rejection = CheckedPassword::<Signup>::from_request(req, &())
+            .await
+            .unwrap_err();
+        assert!(matches!(rejection, PasswordRejection::Json(_)));
+    }
+
+    #[tokio::test]
+    async fn test_policy_extension() {
+        let mut req = request(r#"{"password": "Xk7#q"}"#);
+        req.extensions_mut()
+            .insert(PasswordPolicy::new(crate::requirements::Requirements {
+                min_len: 12,
+                ..Default::default()
+            }));
+        let rejection = CheckedPassword::<Signup>::from_request(req, &())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            rejection,
+            PasswordRejection::Password(Rejection::TooShort { min_len: 12, .. })
+        ));
+    }
+}
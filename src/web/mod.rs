@@ -0,0 +1,187 @@
+//! Extractors checking passwords in web handlers, behind the `axum` and `actix` features.
+//!
+//! A handler taking a [`CheckedPassword`] only runs for JSON request bodies whose password
+//! meets the [`Requirements`] of the [`PasswordPolicy`] the application registered, or the
+//! default requirements if it registered none. Other passwords get a 422 Unprocessable Entity
+//! response with a [`RejectionBody`], so every form that sets a password rejects weak ones the
+//! same way:
+//!
+//! ```json
+//! {
+//!   "reason": "too_guessable",
+//!   "message": "password scores 0, a score of at least 3 is required",
+//!   "score": 0,
+//!   "min_score": 3,
+//!   "warning": "This is a top-10 common password.",
+//!   "suggestions": ["Add another word or two. Uncommon words are better."]
+//! }
+//! ```
+
+use std::sync::Arc;
+
+use crate::requirements::{Rejection, Requirements};
+use crate::{Entropy, Zxcvbn};
+
+#[cfg(feature = "actix")]
+pub mod actix;
+#[cfg(feature = "axum")]
+pub mod axum;
+
+/// A request body with a password to check.
+pub trait PasswordField {
+    /// The password to check.
+    fn password(&self) -> &str;
+
+    /// Other fields of the form the password must not be built from, such as the user name
+    /// and email address. Defaults to none.
+    fn user_inputs(&self) -> Vec<&str> {
+        Vec::new()
+    }
+}
+
+/// The estimator and requirements passwords are checked with.
+///
+/// Registered with `Extension` in axum and `web::Data` in actix-web.
+#[derive(Debug, Clone, Default)]
+pub struct PasswordPolicy {
+    /// The estimator, with the banned words and passwords of the application.
+    pub estimator: Arc<Zxcvbn>,
+    /// What passwords need to be accepted.
+    pub requirements: Requirements,
+}
+
+impl PasswordPolicy {
+    /// Creates a policy checking passwords against `requirements` with an estimator with no
+    /// banned words or passwords.
+    pub fn new(requirements: Requirements) -> Self {
+        PasswordPolicy {
+            estimator: Arc::default(),
+            requirements,
+        }
+    }
+
+    /// Checks passwords with `estimator` instead.
+    pub fn with_estimator(mut self, estimator: Arc<Zxcvbn>) -> Self {
+        self.estimator = estimator;
+        self
+    }
+
+    /// Checks the password of `form` against the requirements.
+    pub fn check<T: PasswordField>(&self, form: &T) -> Result<Entropy, Rejection> {
+        let user_inputs = form.user_inputs();
+        self.estimator
+            .meets_requirements(form.password(), &user_inputs, &self.requirements)
+    }
+}
+
+/// A request body whose password meets the requirements, extracted from JSON.
+#[derive(Debug)]
+pub struct CheckedPassword<T> {
+    /// The request body.
+    pub form: T,
+    /// The estimate of its password.
+    pub entropy: Entropy,
+}
+
+/// The body of the 422 response to a password that doesn't meet the requirements.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RejectionBody {
+    /// Why the password was rejected: `"too_short"`, `"missing_user_inputs"`,
+    /// `"contains_user_input"` or `"too_guessable"`.
+    pub reason: &'static str,
+    /// The rejection, in English.
+    pub message: String,
+    /// Score of the password, if it was estimated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<u8>,
+    /// Minimum score, if the password was estimated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_score: Option<u8>,
+    /// What's wrong with the password, if anything in particular.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+    /// How to choose a better password.
+    pub suggestions: Vec<String>,
+}
+
+impl From<&Rejection> for RejectionBody {
+    fn from(rejection: &Rejection) -> Self {
+        let mut body = RejectionBody {
+            reason: "",
+            message: rejection.to_string(),
+            score: None,
+            min_score: None,
+            warning: None,
+            suggestions: Vec::new(),
+        };
+        match rejection {
+            Rejection::TooShort { .. } => body.reason = "too_short",
+            Rejection::MissingUserInputs => body.reason = "missing_user_inputs",
+            Rejection::ContainsUserInput { .. } => body.reason = "contains_user_input",
+            Rejection::TooGuessable {
+                score,
+                min_score,
+                feedback,
+            } => {
+                body.reason = "too_guessable";
+                body.score = Some((*score).into());
+                body.min_score = Some((*min_score).into());
+                if let Some(feedback) = feedback {
+                    body.warning = feedback.warning().map(|w| w.to_string());
+                    body.suggestions = feedback
+                        .suggestions()
+                        .iter()
+                        .map(|s| s.to_string())
+                        .collect();
+                }
+            }
+        }
+        body
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub(super) struct Signup {
+        pub(super) email: String,
+        pub(super) password: String,
+    }
+
+    impl PasswordField for Signup {
+        fn password(&self) -> &str {
+            &self.password
+        }
+
+        fn user_inputs(&self) -> Vec<&str> {
+            vec![&self.email]
+        }
+    }
+
+    #[test]
+    fn test_rejection_body() {
+        let policy = PasswordPolicy::default();
+        let signup = Signup {
+            email: "jsmith@example.com".to_string(),
+            password: "password".to_string(),
+        };
+        let body = RejectionBody::from(&policy.check(&signup).unwrap_err());
+        assert_eq!(body.reason, "too_guessable");
+        assert_eq!(body.score, Some(0));
+        assert_eq!(body.min_score, Some(3));
+        assert_eq!(
+            body.warning.as_deref(),
+            Some("This is a top-10 common password.")
+        );
+        assert!(!body.suggestions.is_empty());
+
+        let signup = Signup {
+            email: "jsmith".to_string(),
+            password: "xJSmith!q8#z".to_string(),
+        };
+        let body = RejectionBody::from(&policy.check(&signup).unwrap_err());
+        assert_eq!(body.reason, "contains_user_input");
+        assert_eq!(body.score, None);
+    }
+}
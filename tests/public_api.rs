@@ -0,0 +1,88 @@
+//! Uses the public API the way a downstream crate would, so changes that break it fail to
+//! compile here before they reach a release.
+
+use zxcvbn::feedback::{Feedback, Phrasing, Recipe, Suggestion, Warning};
+use zxcvbn::matching::patterns::{DictionaryType, MatchPattern, SequencePattern};
+use zxcvbn::matching::Match;
+use zxcvbn::scoring::{self, GuessCalculation};
+use zxcvbn::time_estimates::{CrackTimeSeconds, CrackTimes, HashCost, Score};
+use zxcvbn::{zxcvbn, Entropy, ScoringParams, Zxcvbn};
+
+fn pattern_name(pattern: &MatchPattern) -> &'static str {
+    match pattern {
+        MatchPattern::Dictionary(_) => "dictionary",
+        MatchPattern::BruteForce => "bruteforce",
+        _ => "other",
+    }
+}
+
+fn dictionary_name(dictionary: DictionaryType) -> &'static str {
+    match dictionary {
+        DictionaryType::Passwords => "passwords",
+        _ => "other",
+    }
+}
+
+fn warning_text(warning: Warning) -> &'static str {
+    match warning {
+        Warning::ThisIsATop10Password => "top 10",
+        other => other.phrase(Phrasing::Terse),
+    }
+}
+
+#[test]
+fn test_estimate_surface() {
+    let entropy: Entropy = zxcvbn("password", &[]);
+    assert_eq!(entropy.score(), Score::TooGuessable);
+    let sequence: &[Match] = entropy.sequence();
+    assert_eq!(pattern_name(&sequence[0].pattern), "dictionary");
+    if let MatchPattern::Dictionary(p) = &sequence[0].pattern {
+        assert_eq!(dictionary_name(p.dictionary_name), "passwords");
+    }
+
+    let feedback: &Feedback = entropy.feedback().as_ref().unwrap();
+    assert_eq!(warning_text(feedback.warning().unwrap()), "top 10");
+    let _: &[Suggestion] = feedback.suggestions();
+    let _: Option<Recipe> = entropy.recipe();
+
+    let estimator = Zxcvbn::new();
+    assert_eq!(
+        estimator.estimate("password", &[]).guesses(),
+        entropy.guesses()
+    );
+}
+
+#[test]
+fn test_scoring_surface() {
+    let password = "abcdef";
+    let mut sequence = SequencePattern::default();
+    sequence.sequence_name = "lower";
+    sequence.sequence_space = 26;
+    sequence.ascending = true;
+    let m = Match {
+        i: 0,
+        j: 5,
+        token: password.to_string(),
+        pattern: MatchPattern::Sequence(sequence),
+        guesses: None,
+    };
+    let result: GuessCalculation = scoring::most_guessable_match_sequence(
+        password,
+        std::slice::from_ref(&m),
+        &ScoringParams::default(),
+    );
+    assert_eq!(result.sequence.len(), 1);
+    assert_eq!(
+        result.sequence[0].guesses,
+        Some(scoring::estimate_match(&m, password))
+    );
+}
+
+#[test]
+fn test_time_estimates_surface() {
+    let times = CrackTimes::new(1_000);
+    assert_eq!(times.guesses(), 1_000);
+    let seconds: CrackTimeSeconds = times.offline_hash(HashCost::Bcrypt { cost: 10 });
+    assert!(matches!(seconds, CrackTimeSeconds::Float(s) if s > 0.0));
+    assert_eq!(Score::from_guesses(10), Score::TooGuessable);
+}
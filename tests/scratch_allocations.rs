@@ -0,0 +1,60 @@
+//! Checks that `most_guessable_match_sequence_with_scratch` allocates only the sequence it
+//! returns, with an allocator counting the allocations of each thread, which needs a crate of
+//! its own. Whole estimates allocate their matches and feedback, so aren't checked.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use zxcvbn::scoring::{self, ScoringScratch};
+use zxcvbn::{zxcvbn, ScoringParams};
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // the count is gone while the thread is torn down
+        let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+#[test]
+fn test_scoring_with_scratch_allocates_only_the_sequence() {
+    let password = "correcthorse\u{1F991}battery5taple2024";
+    let mut matches = zxcvbn(password, &[]).all_matches().to_vec();
+    for m in &mut matches {
+        m.guesses = Some(scoring::estimate_match(m, password));
+    }
+    let params = ScoringParams::default();
+    let mut scratch = ScoringScratch::new();
+    scoring::most_guessable_match_sequence_with_scratch(password, &matches, &params, &mut scratch);
+
+    let before = allocations();
+    let result = scoring::most_guessable_match_sequence_with_scratch(
+        password,
+        &matches,
+        &params,
+        &mut scratch,
+    );
+    let scoring = allocations() - before;
+    let before = allocations();
+    let sequence = result.sequence.clone();
+    let cloning = allocations() - before;
+    assert!(sequence.len() > 1);
+    assert_eq!(scoring, cloning);
+}